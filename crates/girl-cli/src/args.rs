@@ -0,0 +1,190 @@
+//! Command-line arguments.
+
+use core::time::Duration;
+use std::path::PathBuf;
+
+use crate::{error::CliError, output::Output};
+
+/// Help printed for `--help` and after usage errors.
+pub(crate) const USAGE: &str = "\
+Usage: girl-cli [--json] <command>
+
+Commands:
+    list                    List connected gamepads and their capabilities
+    watch [--timeout <s>]   Print events as they arrive
+    test [<index>]          Walk through testing every control of a gamepad
+    record <file> [--timeout <s>]
+                            Record events to a JSON Lines event log
+    replay <file>           Print the events of an event log at their pace
+    dump <index>            Print everything a gamepad exposes
+
+Options:
+    --json                  Print JSON Lines instead of text
+    -h, --help              Print this help";
+
+/// What to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Command {
+    /// Prints [`USAGE`].
+    Help,
+    /// Lists connected gamepads.
+    List,
+    /// Prints events until the `timeout`, if any.
+    Watch {
+        /// How long to watch, until quit if [`None`].
+        timeout: Option<Duration>,
+    },
+    /// Runs the diagnostics routine on the gamepad at the device `index`.
+    Test {
+        /// Device index of the gamepad.
+        index: u32,
+    },
+    /// Records events to the `file` until the `timeout`, if any.
+    Record {
+        /// Event log to write.
+        file: PathBuf,
+        /// How long to record, until quit if [`None`].
+        timeout: Option<Duration>,
+    },
+    /// Prints the events of the event log `file`.
+    Replay {
+        /// Event log to read.
+        file: PathBuf,
+    },
+    /// Prints everything the gamepad at the device `index` exposes.
+    Dump {
+        /// Device index of the gamepad.
+        index: u32,
+    },
+}
+
+/// Parsed command-line arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Args {
+    /// How to print results.
+    pub(crate) output: Output,
+    /// What to run.
+    pub(crate) command: Command,
+}
+
+impl Args {
+    /// Parses the arguments, without the program name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::Usage`] if the arguments are invalid, or
+    /// [`CliError::Unavailable`] for `--json` without the `json` feature.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    pub(crate) fn parse<I>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut output = Output::Text;
+        let mut timeout = None;
+        let mut positional = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    return Ok(Self { output, command: Command::Help });
+                }
+                #[cfg(feature = "json")]
+                "--json" => output = Output::Json,
+                #[cfg(not(feature = "json"))]
+                "--json" => {
+                    return Err(CliError::Unavailable {
+                        what: "JSON output",
+                        feature: "json",
+                    });
+                }
+                "--timeout" => {
+                    let seconds = args.next().ok_or_else(|| {
+                        CliError::Usage("--timeout needs a value".to_owned())
+                    })?;
+                    timeout = Some(parse_seconds(&seconds)?);
+                }
+                flag if flag.starts_with('-') => {
+                    return Err(CliError::Usage(format!(
+                        "unknown option `{flag}`"
+                    )));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let name = positional
+            .next()
+            .ok_or_else(|| CliError::Usage("missing command".to_owned()))?;
+        let command = match name.as_str() {
+            "list" => Command::List,
+            "watch" => Command::Watch { timeout },
+            "test" => Command::Test {
+                index: positional
+                    .next()
+                    .map_or(Ok(0), |index| parse_index(&index))?,
+            },
+            "record" => {
+                let file = required(&mut positional, "file")?.into();
+                Command::Record { file, timeout }
+            }
+            "replay" => {
+                let file = required(&mut positional, "file")?.into();
+                Command::Replay { file }
+            }
+            "dump" => Command::Dump {
+                index: parse_index(&required(&mut positional, "index")?)?,
+            },
+            other => {
+                return Err(CliError::Usage(format!(
+                    "unknown command `{other}`"
+                )));
+            }
+        };
+        if let Some(extra) = positional.next() {
+            return Err(CliError::Usage(format!(
+                "unexpected argument `{extra}`"
+            )));
+        }
+        if timeout.is_some()
+            && !matches!(
+                command,
+                Command::Watch { .. } | Command::Record { .. }
+            )
+        {
+            return Err(CliError::Usage(format!(
+                "`{name}` doesn't take --timeout"
+            )));
+        }
+        Ok(Self { output, command })
+    }
+}
+
+/// Takes the next positional argument, the `what` the command requires.
+fn required<I>(positional: &mut I, what: &str) -> Result<String, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    positional
+        .next()
+        .ok_or_else(|| CliError::Usage(format!("missing <{what}>")))
+}
+
+/// Parses a device index.
+fn parse_index(index: &str) -> Result<u32, CliError> {
+    index
+        .parse()
+        .map_err(|_err| CliError::Usage(format!("invalid index `{index}`")))
+}
+
+/// Parses a non-negative number of seconds, e.g. `0.5`.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn parse_seconds(seconds: &str) -> Result<Duration, CliError> {
+    seconds
+        .parse()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| {
+            CliError::Usage(format!("invalid number of seconds `{seconds}`"))
+        })
+}
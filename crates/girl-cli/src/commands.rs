@@ -0,0 +1,474 @@
+//! The commands, using the public API of [`girl`] only.
+
+use core::{fmt, time::Duration};
+#[cfg(feature = "eventlog")]
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    thread,
+};
+use std::{path::Path, time::Instant};
+
+#[cfg(feature = "sensors")]
+use girl::Sensor;
+use girl::{
+    Anomaly, Button, Control, ControllerType, DiagnosticPrompt,
+    DiagnosticReport, Diagnostics, DiagnosticsConfig, Gamepad, GamepadDump,
+    GamepadId, Girl, Guid, PowerLevel, StepOutcome, Stick, Trigger,
+    app::{self, ControlFlow},
+};
+#[cfg(feature = "eventlog")]
+use girl::{EnrichedEvent, Event, EventLogReader, EventLogWriter};
+
+use crate::{error::CliError, output::Output};
+
+/// Time between two polls of [`record`].
+#[cfg(feature = "eventlog")]
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Identity and capabilities of a connected [`Gamepad`], [`None`] where the
+/// feature reporting them was compiled out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+struct Device {
+    /// See [`Gamepad::id`].
+    id: GamepadId,
+    /// See [`Gamepad::name`].
+    name: String,
+    /// See [`Gamepad::guid`].
+    guid: Guid,
+    /// See [`Gamepad::controller_type`].
+    controller_type: ControllerType,
+    /// See [`Gamepad::serial`].
+    serial: Option<String>,
+    /// See [`Gamepad::power`].
+    power: Option<PowerLevel>,
+    /// See [`Gamepad::has_led`].
+    led: bool,
+    /// See `Gamepad::has_rumble`.
+    rumble: Option<bool>,
+    /// See `Gamepad::has_rumble_triggers`.
+    trigger_rumble: Option<bool>,
+    /// See `Gamepad::has_sensor`.
+    gyroscope: Option<bool>,
+    /// See `Gamepad::has_sensor`.
+    accelerometer: Option<bool>,
+    /// See `Gamepad::num_touchpads`.
+    touchpads: Option<usize>,
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.name.is_empty() { "Gamepad" } else { &self.name };
+        writeln!(f, "{name} {}", self.id)?;
+        writeln!(f, "  guid: {}", self.guid)?;
+        writeln!(f, "  type: {:?}", self.controller_type)?;
+        writeln!(f, "  serial: {}", self.serial.as_deref().unwrap_or("none"))?;
+        match self.power {
+            Some(power) => writeln!(f, "  power: {power}")?,
+            None => writeln!(f, "  power: unknown")?,
+        }
+        writeln!(f, "  led: {}", yes_no(self.led))?;
+        let rumble = self.rumble.map(yes_no);
+        write_capability(f, "rumble", rumble, "rumble")?;
+        let trigger_rumble = self.trigger_rumble.map(yes_no);
+        write_capability(f, "trigger rumble", trigger_rumble, "rumble")?;
+        let gyroscope = self.gyroscope.map(yes_no);
+        write_capability(f, "gyroscope", gyroscope, "sensors")?;
+        let accelerometer = self.accelerometer.map(yes_no);
+        write_capability(f, "accelerometer", accelerometer, "sensors")?;
+        write_capability(f, "touchpads", self.touchpads, "touchpad")
+    }
+}
+
+impl Device {
+    /// Describes the [`Gamepad`].
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn of(gamepad: &Gamepad) -> Self {
+        Self {
+            id: gamepad.id(),
+            name: gamepad.name(),
+            guid: gamepad.guid(),
+            controller_type: gamepad.controller_type(),
+            serial: gamepad.serial(),
+            power: gamepad.power(),
+            led: gamepad.has_led(),
+            #[cfg(feature = "rumble")]
+            rumble: Some(gamepad.has_rumble()),
+            #[cfg(not(feature = "rumble"))]
+            rumble: None,
+            #[cfg(feature = "rumble")]
+            trigger_rumble: Some(gamepad.has_rumble_triggers()),
+            #[cfg(not(feature = "rumble"))]
+            trigger_rumble: None,
+            #[cfg(feature = "sensors")]
+            gyroscope: Some(gamepad.has_sensor(Sensor::Gyroscope)),
+            #[cfg(not(feature = "sensors"))]
+            gyroscope: None,
+            #[cfg(feature = "sensors")]
+            accelerometer: Some(gamepad.has_sensor(Sensor::Accelerometer)),
+            #[cfg(not(feature = "sensors"))]
+            accelerometer: None,
+            #[cfg(feature = "touchpad")]
+            touchpads: Some(gamepad.num_touchpads()),
+            #[cfg(not(feature = "touchpad"))]
+            touchpads: None,
+        }
+    }
+}
+
+/// [`GamepadDump`] with the SDL2 mapping of the [`Gamepad`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
+struct Dump {
+    /// See [`Gamepad::dump`].
+    #[cfg_attr(feature = "json", serde(flatten))]
+    dump: GamepadDump,
+    /// See [`Girl::export_mapping`].
+    mapping: String,
+}
+
+impl fmt::Display for Dump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dump)?;
+        write!(f, "  mapping: {}", self.mapping)
+    }
+}
+
+/// [`DiagnosticReport`] of [`test`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize), serde(transparent))]
+struct Report(DiagnosticReport);
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.0.controls {
+            write!(f, "{}: ", control_name(report.control))?;
+            match report.outcome {
+                StepOutcome::Passed => f.write_str("passed")?,
+                StepOutcome::TimedOut => f.write_str("timed out")?,
+                StepOutcome::Skipped => f.write_str("skipped")?,
+                _ => write!(f, "{:?}", report.outcome)?,
+            }
+            write!(f, ", range {:.2}", report.range)?;
+            if let Some(latency) = report.latency {
+                write!(f, ", latency {}ms", latency.as_millis())?;
+            }
+            writeln!(f)?;
+        }
+        for anomaly in &self.0.anomalies {
+            match *anomaly {
+                Anomaly::Dead(control) => {
+                    writeln!(f, "anomaly: {} is dead", control_name(control))?;
+                }
+                Anomaly::LimitedRange { control, reached } => {
+                    let control = control_name(control);
+                    writeln!(
+                        f,
+                        "anomaly: {control} only reaches {reached:.2}"
+                    )?;
+                }
+                Anomaly::Drift { stick, offset } => {
+                    let stick = stick_name(stick);
+                    writeln!(
+                        f,
+                        "anomaly: {stick} stick drifts by {offset:.2}"
+                    )?;
+                }
+                _ => writeln!(f, "anomaly: {anomaly:?}")?,
+            }
+        }
+        f.write_str(if self.0.passed() { "passed" } else { "failed" })
+    }
+}
+
+/// Lists the connected [`Gamepad`]s and their capabilities.
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn list(output: Output) -> Result<(), CliError> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+    let mut listed = false;
+    for gamepad in girl.gamepads() {
+        output.print(&Device::of(&gamepad))?;
+        listed = true;
+    }
+    if !listed {
+        eprintln!("no gamepads connected");
+    }
+    Ok(())
+}
+
+/// Prints every [`Event`] until the `timeout` or until quit, e.g. with
+/// Ctrl-C.
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize.
+///
+/// [`Event`]: girl::Event
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn watch(
+    timeout: Option<Duration>,
+    output: Output,
+) -> Result<(), CliError> {
+    let started = Instant::now();
+    let mut result = Ok(());
+    app::run(app::Config::new(), |girl, frame| {
+        for event in frame.events {
+            if let Err(err) = output.print(&girl.enrich(event)) {
+                result = Err(err);
+                return ControlFlow::Exit;
+            }
+        }
+        until(started, timeout)
+    })?;
+    result
+}
+
+/// Walks the user through testing every control of the [`Gamepad`] at the
+/// device `index`, waiting for it to connect, and prints the
+/// [`DiagnosticReport`].
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize, or if quit before the
+/// [`Gamepad`] connects.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn test(index: u32, output: Output) -> Result<(), CliError> {
+    let started = Instant::now();
+    let mut diagnostics = None;
+    let mut prompt = None;
+    app::run(app::Config::new(), |girl, frame| {
+        if diagnostics.is_none() {
+            diagnostics = girl.gamepad(index).map(|gamepad| {
+                eprintln!("testing {gamepad}");
+                Diagnostics::for_gamepad(&gamepad, DiagnosticsConfig::new())
+            });
+        }
+        let Some(ref mut running) = diagnostics else {
+            if frame.index == 0 {
+                eprintln!("waiting for a gamepad at index {index}...");
+            }
+            return ControlFlow::Continue;
+        };
+
+        let at = started.elapsed();
+        for event in frame.events {
+            running.observe(event, at);
+        }
+        running.tick(at);
+        if running.prompt() != prompt {
+            prompt = running.prompt();
+            if let Some(current) = prompt {
+                eprintln!("{}", instruction(current));
+            }
+        }
+        if running.is_finished() {
+            ControlFlow::Exit
+        } else {
+            ControlFlow::Continue
+        }
+    })?;
+    let diagnostics = diagnostics.ok_or(CliError::NoGamepad(index))?;
+    output.print(&Report(diagnostics.finish()))
+}
+
+/// Records every [`Event`] to the event log `file` until the `timeout` or
+/// until quit, e.g. with Ctrl-C.
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize or the log can't be
+/// written.
+///
+/// [`Event`]: girl::Event
+#[cfg(feature = "eventlog")]
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn record(
+    file: &Path,
+    timeout: Option<Duration>,
+) -> Result<(), CliError> {
+    let mut log = EventLogWriter::new(BufWriter::new(File::create(file)?))?;
+    let mut girl = Girl::new()?;
+    let started = Instant::now();
+    let mut recorded = 0_u64;
+    loop {
+        _ = girl.update();
+        let mut quit = false;
+        for event in girl.tee_events(&mut log) {
+            recorded = recorded.saturating_add(1);
+            quit |= matches!(event, Event::Quit);
+        }
+        if let Some(err) = log.take_error() {
+            return Err(err.into());
+        }
+        if quit || until(started, timeout) == ControlFlow::Exit {
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    log.flush()?;
+    eprintln!("recorded {recorded} events to {}", file.display());
+    Ok(())
+}
+
+/// Reports that recording needs the `eventlog` feature.
+///
+/// # Errors
+///
+/// Always returns [`CliError::Unavailable`].
+#[cfg(not(feature = "eventlog"))]
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) const fn record(
+    _file: &Path,
+    _timeout: Option<Duration>,
+) -> Result<(), CliError> {
+    Err(CliError::Unavailable { what: "recording", feature: "eventlog" })
+}
+
+/// Prints the [`Event`]s of the event log `file`, at the pace they were
+/// recorded.
+///
+/// # Errors
+///
+/// Returns an error if the log can't be read or is malformed.
+///
+/// [`Event`]: girl::Event
+#[cfg(feature = "eventlog")]
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn replay(file: &Path, output: Output) -> Result<(), CliError> {
+    let reader = EventLogReader::new(BufReader::new(File::open(file)?))?;
+    let header = reader.header();
+    eprintln!(
+        "recorded with girl {} and SDL {}",
+        header.crate_version, header.sdl_version,
+    );
+    let mut previous = None;
+    for record in reader {
+        let record = record?;
+        if let Some(last) = previous {
+            let gap = record.timestamp_ms.saturating_sub(last);
+            thread::sleep(Duration::from_millis(gap));
+        }
+        previous = Some(record.timestamp_ms);
+        output.print(&EnrichedEvent::new(record.event, record.device))?;
+    }
+    Ok(())
+}
+
+/// Reports that replaying needs the `eventlog` feature.
+///
+/// # Errors
+///
+/// Always returns [`CliError::Unavailable`].
+#[cfg(not(feature = "eventlog"))]
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) const fn replay(
+    _file: &Path,
+    _output: Output,
+) -> Result<(), CliError> {
+    Err(CliError::Unavailable { what: "replaying", feature: "eventlog" })
+}
+
+/// Prints everything the [`Gamepad`] at the device `index` exposes, see
+/// [`Gamepad::dump`].
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize or no [`Gamepad`] is
+/// connected at the `index`.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+pub(crate) fn dump(index: u32, output: Output) -> Result<(), CliError> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+    let mut gamepad = girl.gamepad(index).ok_or(CliError::NoGamepad(index))?;
+    let mapping = girl.export_mapping(&gamepad);
+    output.print(&Dump { dump: gamepad.dump(), mapping })
+}
+
+/// Checks if the `timeout` since `started` is up.
+fn until(started: Instant, timeout: Option<Duration>) -> ControlFlow {
+    if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+        ControlFlow::Exit
+    } else {
+        ControlFlow::Continue
+    }
+}
+
+/// Tells the user what the [`DiagnosticPrompt`] asks for.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn instruction(prompt: DiagnosticPrompt) -> String {
+    match prompt {
+        DiagnosticPrompt::Rest => "leave the controls alone".to_owned(),
+        DiagnosticPrompt::PressButton(button) => {
+            format!("press {}", button_name(button))
+        }
+        DiagnosticPrompt::MoveStick(stick) => {
+            format!("move the {} stick to every edge", stick_name(stick))
+        }
+        DiagnosticPrompt::PullTrigger(trigger) => {
+            format!("pull the {} trigger fully", trigger_name(trigger))
+        }
+        DiagnosticPrompt::TouchTouchpad => "touch the touchpad".to_owned(),
+        _ => format!("{prompt:?}"),
+    }
+}
+
+/// Names the [`Control`].
+fn control_name(control: Control) -> String {
+    match control {
+        Control::Button(button) => button_name(button).to_owned(),
+        Control::Stick(stick) => format!("{} stick", stick_name(stick)),
+        Control::Trigger(trigger) => {
+            format!("{} trigger", trigger_name(trigger))
+        }
+        Control::Touchpad => "touchpad".to_owned(),
+        _ => format!("{control:?}"),
+    }
+}
+
+/// Names the single [`Button`].
+fn button_name(button: Button) -> &'static str {
+    button.iter_names().next().map_or("button", |(name, _)| name)
+}
+
+/// Names the side of the [`Stick`].
+const fn stick_name(stick: Stick) -> &'static str {
+    match stick {
+        Stick::Left => "left",
+        Stick::Right => "right",
+    }
+}
+
+/// Names the side of the [`Trigger`].
+const fn trigger_name(trigger: Trigger) -> &'static str {
+    match trigger {
+        Trigger::Left => "left",
+        Trigger::Right => "right",
+    }
+}
+
+/// Writes a capability reported only with the `feature`.
+fn write_capability<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    value: Option<T>,
+    feature: &str,
+) -> fmt::Result {
+    match value {
+        Some(value) => writeln!(f, "  {label}: {value}"),
+        None => writeln!(
+            f,
+            "  {label}: unavailable, built without the `{feature}` feature",
+        ),
+    }
+}
+
+/// Renders a [`bool`] as `yes` or `no`.
+const fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
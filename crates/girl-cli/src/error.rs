@@ -0,0 +1,61 @@
+//! Errors reported by the commands.
+
+use core::fmt;
+use std::io;
+
+/// Error ending a command.
+#[derive(Debug)]
+pub(crate) enum CliError {
+    /// The command-line arguments are invalid.
+    Usage(String),
+    /// A capability was compiled out of this build.
+    #[cfg_attr(
+        all(feature = "eventlog", feature = "json"),
+        expect(dead_code, reason = "only with features compiled out")
+    )]
+    Unavailable {
+        /// What is unavailable, e.g. `"event logs"`.
+        what: &'static str,
+        /// Feature enabling it.
+        feature: &'static str,
+    },
+    /// No gamepad is connected at the device index.
+    NoGamepad(u32),
+    /// [`girl`] failed.
+    Girl(girl::Error),
+    /// Reading or writing failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for CliError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Usage(ref message) => f.write_str(message),
+            Self::Unavailable { what, feature } => write!(
+                f,
+                "{what} unavailable: girl-cli was built without the \
+                 `{feature}` feature",
+            ),
+            Self::NoGamepad(index) => {
+                write!(f, "no gamepad connected at index {index}")
+            }
+            Self::Girl(ref err) => write!(f, "{err}"),
+            Self::Io(ref err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<girl::Error> for CliError {
+    #[inline]
+    fn from(err: girl::Error) -> Self {
+        Self::Girl(err)
+    }
+}
+
+impl From<io::Error> for CliError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
@@ -0,0 +1,67 @@
+//! Diagnostic command-line tool for `girl`: lists devices, prints events,
+//! tests controls, records and replays event logs and dumps everything a
+//! gamepad exposes, as text or JSON Lines. See `girl-cli --help`.
+#![expect(
+    clippy::print_stdout,
+    clippy::print_stderr,
+    clippy::use_debug,
+    reason = "command-line interface"
+)]
+
+mod args;
+mod commands;
+mod error;
+mod output;
+
+use std::{env, process::ExitCode};
+
+use crate::{
+    args::{Args, Command, USAGE},
+    error::CliError,
+};
+
+/// Exit code of invalid command-line arguments.
+const USAGE_EXIT_CODE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err @ CliError::Usage(_)) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(USAGE_EXIT_CODE);
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command.
+///
+/// # Errors
+///
+/// Returns an error if the command fails.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn run(args: Args) -> Result<(), CliError> {
+    let Args { output, command } = args;
+    match command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Command::List => commands::list(output),
+        Command::Watch { timeout } => commands::watch(timeout, output),
+        Command::Test { index } => commands::test(index, output),
+        Command::Record { file, timeout } => commands::record(&file, timeout),
+        Command::Replay { file } => commands::replay(&file, output),
+        Command::Dump { index } => commands::dump(index, output),
+    }
+}
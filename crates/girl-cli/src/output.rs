@@ -0,0 +1,57 @@
+//! Printing results as text or JSON Lines.
+
+use core::fmt;
+#[cfg(feature = "json")]
+use std::io;
+
+use crate::error::CliError;
+
+/// Result of a command, printable as text and, with the `json` feature, as
+/// JSON.
+#[cfg(feature = "json")]
+pub(crate) trait Printable: fmt::Display + serde::Serialize {}
+
+#[cfg(feature = "json")]
+impl<T: fmt::Display + serde::Serialize> Printable for T {}
+
+/// Result of a command, printable as text and, with the `json` feature, as
+/// JSON.
+#[cfg(not(feature = "json"))]
+pub(crate) trait Printable: fmt::Display {}
+
+#[cfg(not(feature = "json"))]
+impl<T: fmt::Display> Printable for T {}
+
+/// How results are printed to the standard output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Output {
+    /// Readable text.
+    Text,
+    /// One JSON object per line.
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl Output {
+    /// Prints a result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result can't be serialized.
+    #[cfg_attr(
+        not(feature = "json"),
+        expect(clippy::unnecessary_wraps, reason = "feature gated")
+    )]
+    pub(crate) fn print<T: Printable>(self, value: &T) -> Result<(), CliError> {
+        match self {
+            Self::Text => println!("{value}"),
+            #[cfg(feature = "json")]
+            Self::Json => {
+                let line =
+                    serde_json::to_string(value).map_err(io::Error::from)?;
+                println!("{line}");
+            }
+        }
+        Ok(())
+    }
+}
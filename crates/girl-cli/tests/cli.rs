@@ -0,0 +1,77 @@
+//! Runs the `girl-cli` binary against whatever devices are connected, none
+//! in CI.
+#![expect(unused_crate_dependencies, reason = "only the binary is run")]
+#![expect(clippy::use_debug, reason = "test")]
+// TODO: Try remove on next Rust version update.
+#![expect(clippy::allow_attributes, reason = "lints integration tests")]
+#![allow(clippy::tests_outside_test_module, reason = "integration tests")]
+
+use std::{
+    error::Error,
+    io,
+    process::{Command, Output},
+};
+
+/// Runs `girl-cli` with the arguments.
+fn girl_cli(args: &[&str]) -> io::Result<Output> {
+    Command::new(env!("CARGO_BIN_EXE_girl-cli")).args(args).output()
+}
+
+/// `list` succeeds, also without any device connected.
+#[test]
+fn list() -> io::Result<()> {
+    let output = girl_cli(&["list"])?;
+    assert!(output.status.success(), "list failed: {output:?}");
+    Ok(())
+}
+
+/// `watch` stops once its timeout is up.
+#[test]
+fn watch_times_out() -> io::Result<()> {
+    let output = girl_cli(&["watch", "--timeout", "0.1"])?;
+    assert!(output.status.success(), "watch failed: {output:?}");
+    Ok(())
+}
+
+/// `--json` prints a JSON object per line.
+#[cfg(feature = "json")]
+#[test]
+fn json_lines() -> Result<(), Box<dyn Error>> {
+    for args in [
+        ["--json", "list"].as_slice(),
+        ["--json", "watch", "--timeout", "0"].as_slice(),
+    ] {
+        let output = girl_cli(args)?;
+        assert!(output.status.success(), "{args:?} failed: {output:?}");
+        for line in String::from_utf8(output.stdout)?.lines() {
+            _ = serde_json::from_str::<serde_json::Value>(line)?;
+        }
+    }
+    Ok(())
+}
+
+/// `--json` reports the missing `json` feature.
+#[cfg(not(feature = "json"))]
+#[test]
+fn json_unavailable() -> Result<(), Box<dyn Error>> {
+    let output = girl_cli(&["--json", "list"])?;
+    assert!(!output.status.success(), "--json succeeded: {output:?}");
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("`json` feature"), "unexpected error: {stderr}");
+    Ok(())
+}
+
+/// Invalid arguments exit with code 2.
+#[test]
+fn usage_errors() -> io::Result<()> {
+    for args in [
+        [].as_slice(),
+        ["frobnicate"].as_slice(),
+        ["dump"].as_slice(),
+        ["list", "--timeout"].as_slice(),
+    ] {
+        let output = girl_cli(args)?;
+        assert_eq!(output.status.code(), Some(2), "{args:?}: {output:?}");
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Times `Gamepad::buttons_changed` and counts its allocations, as a
+//! rollback netcode layer sampling input every frame would.
+//!
+//! Run with `cargo bench --bench buttons`, with a controller connected.
+#![expect(
+    unused_crate_dependencies,
+    clippy::print_stdout,
+    reason = "benchmark"
+)]
+
+mod counting;
+
+use core::hint::black_box;
+use std::time::Instant;
+
+use girl::prelude::*;
+
+/// Number of polls timed.
+const POLLS: u32 = 100_000;
+
+fn main() -> Result<(), Error> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+    let Some(mut gamepad) = girl.gamepad(0) else {
+        println!("no gamepad connected");
+        return Ok(());
+    };
+
+    let start = Instant::now();
+    let allocations = counting::allocations(|| {
+        for _ in 0..POLLS {
+            _ = black_box(gamepad.buttons_changed());
+        }
+    });
+    let elapsed = start.elapsed().checked_div(POLLS).unwrap_or_default();
+    println!("buttons_changed: {} ns per poll", elapsed.as_nanos());
+    println!("buttons_changed: {allocations} allocations in {POLLS} polls");
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Global allocator of the benchmarks, counting allocations.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::alloc::System;
+
+/// Allocations made so far.
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator of the benchmark, counting allocations.
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// [`System`] allocator counting allocations in [`ALLOCATIONS`].
+struct Counting;
+
+// SAFETY: forwards to `System`, only counting the calls.
+#[expect(unsafe_code, reason = "counting allocator")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        _ = ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc`.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` was allocated by `System` with `layout`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// Counts the allocations made while running `run`.
+#[expect(clippy::single_call_fn, reason = "shared between the targets")]
+pub(crate) fn allocations(run: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    run();
+    ALLOCATIONS.load(Ordering::Relaxed).saturating_sub(before)
+}
@@ -0,0 +1,50 @@
+//! Counts the allocations of `Girl::drain_into` with a warm buffer, as an
+//! engine collecting events into a buffer it owns every frame would.
+//!
+//! Run with `cargo bench --bench drain`, moving the sticks of a connected
+//! controller while it runs, so that there are events to collect.
+#![expect(
+    unused_crate_dependencies,
+    clippy::print_stdout,
+    reason = "benchmark"
+)]
+
+mod counting;
+
+use core::{iter, time::Duration};
+use std::thread;
+
+use girl::prelude::*;
+
+/// Number of frames counted.
+const FRAMES: u32 = 300;
+
+/// Number of frames before counting, growing the internal buffers.
+const WARMUP: u32 = 60;
+
+/// Time between frames, about 60 frames per second.
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Capacity of the buffer, more than any frame collects.
+const CAPACITY: usize = 1024;
+
+fn main() -> Result<(), Error> {
+    let mut girl = Girl::new()?;
+    let mut events = Vec::with_capacity(CAPACITY);
+    let mut frame = || {
+        events.clear();
+        let collected = girl.drain_into(&mut events);
+        thread::sleep(FRAME);
+        collected
+    };
+    (0..WARMUP).for_each(|_| _ = frame());
+    let mut collected = 0usize;
+    let allocations = counting::allocations(|| {
+        collected = iter::repeat_with(&mut frame)
+            .take(FRAMES as usize)
+            .fold(0, usize::saturating_add);
+    });
+    println!("drain_into: {collected} events in {FRAMES} frames");
+    println!("drain_into: {allocations} allocations in {FRAMES} frames");
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Counts the allocations of operations failing in a loop, as a game
+//! retrying an effect the controller can't play every frame would.
+//!
+//! Run with `cargo bench --bench errors`, with a controller lacking an LED or
+//! trigger rumble connected, e.g. an Xbox 360 controller.
+#![expect(
+    unused_crate_dependencies,
+    clippy::print_stdout,
+    reason = "benchmark"
+)]
+
+mod counting;
+
+use core::{hint::black_box, time::Duration};
+
+use girl::prelude::*;
+
+/// Number of failing calls counted per operation.
+const CALLS: usize = 10_000;
+
+fn main() -> Result<(), Error> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+    let Some(mut gamepad) = girl.gamepad(0) else {
+        println!("no gamepad connected");
+        return Ok(());
+    };
+
+    count("set_led", || gamepad.set_led(255, 0, 0));
+    let effect = Duration::from_millis(100);
+    count("set_rumble_triggers", || {
+        gamepad.set_rumble_triggers(0xffff, 0xffff, effect)
+    });
+    Ok(())
+}
+
+/// Prints the allocations of [`CALLS`] failing runs of `call`, or skips it
+/// if it succeeds.
+fn count(op: &str, mut call: impl FnMut() -> Result<(), Error>) {
+    // the first failure of a streak is logged
+    if call().is_ok() {
+        println!("{op}: supported, skipped");
+        return;
+    }
+    let allocations = counting::allocations(|| {
+        for _ in 0..CALLS {
+            drop(black_box(call()));
+        }
+    });
+    println!("{op}: {allocations} allocations in {CALLS} failing calls");
+}
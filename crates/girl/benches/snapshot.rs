@@ -0,0 +1,46 @@
+//! Compares `Girl::snapshot_all` against a `state` call per tracked
+//! gamepad, as a netcode layer polling four players at a fixed tick would.
+//!
+//! Run with `cargo bench --bench snapshot`, with the controllers to poll
+//! connected.
+#![expect(
+    unused_crate_dependencies,
+    clippy::print_stdout,
+    reason = "benchmark"
+)]
+
+use core::{hint::black_box, time::Duration};
+use std::time::Instant;
+
+use girl::prelude::*;
+
+/// Number of polls timed per approach.
+const POLLS: u32 = 100_000;
+
+fn main() -> Result<(), Error> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+    println!("polling {} gamepads", girl.gamepads().count());
+
+    let mut players = [None; 4];
+    let bulk = time(|| {
+        _ = black_box(girl.snapshot_all(black_box(&mut players)));
+    });
+    let single = time(|| {
+        for gamepad in girl.gamepads().take(4) {
+            _ = black_box(gamepad.state());
+        }
+    });
+    println!("snapshot_all: {} ns per poll", bulk.as_nanos());
+    println!("4 x state:    {} ns per poll", single.as_nanos());
+    Ok(())
+}
+
+/// Times `poll` over [`POLLS`] runs, returning the mean duration of one.
+fn time(mut poll: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..POLLS {
+        poll();
+    }
+    start.elapsed().checked_div(POLLS).unwrap_or_default()
+}
@@ -2,7 +2,6 @@
 #![expect(
     unused_crate_dependencies,
     clippy::absolute_paths,
-    clippy::dbg_macro,
     clippy::print_stdout,
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss,
@@ -10,64 +9,60 @@
     reason = "demo"
 )]
 
-use std::{thread, time::Duration};
+use core::time::Duration;
 
-use girl::{Button, Girl, Sensor, Stick, Trigger};
+use girl::{
+    app::{self, Config, ControlFlow},
+    prelude::*,
+};
 
-fn main() -> Result<(), girl::Error> {
+fn main() -> Result<(), Error> {
     tracing_subscriber::fmt::init();
 
-    let mut girl = Girl::new()?;
-
-    let gamepads = girl.gamepads_connected().len();
-    dbg!(gamepads);
-
-    let Some(mut gamepad) = girl.gamepad(0) else {
-        println!("No gamepad connected!");
-        return Ok(());
-    };
-    println!("{} connected", gamepad.name());
-
-    if gamepad.has_sensor(Sensor::Gyroscope) {
-        gamepad.enable_sensor(Sensor::Gyroscope)?;
-    }
-    if gamepad.has_sensor(Sensor::Accelerometer) {
-        gamepad.enable_sensor(Sensor::Accelerometer)?;
-    }
-
-    loop {
-        girl.update();
-
-        if !gamepad.connected()
-            && let Some(gp) = girl.gamepad(0)
-        {
-            gamepad = gp;
+    app::run(Config::new(), |girl, frame| {
+        for event in frame.events {
+            if let Event::ControllerDeviceAdded { which } = *event {
+                let id = girl.resolve_id(which);
+                if let Some(gamepad) = girl.gamepad_mut(id) {
+                    println!("{} connected", gamepad.name());
+                    for sensor in [Sensor::Gyroscope, Sensor::Accelerometer] {
+                        if gamepad.has_sensor(sensor) {
+                            drop(gamepad.enable_sensor(sensor));
+                        }
+                    }
+                }
+                // red and green LED channels following the triggers
+                let color = |state: &GamepadState| {
+                    let red = (state.trigger(Trigger::Left) * 255.0f64) as u8;
+                    let green =
+                        (state.trigger(Trigger::Right) * 255.0f64) as u8;
+                    Some(Rgb::new(red, green, 0))
+                };
+                _ = girl.add_led_binding(id, Box::new(color));
+            }
         }
 
-        if gamepad.connected() && gamepad.has_led() {
-            let left = gamepad.trigger(Trigger::Left);
-            let right = gamepad.trigger(Trigger::Right);
-
-            let red = (left * 255.0) as u8;
-            let green = (right * 255.0) as u8;
-
-            gamepad.set_led(red, green, 0)?;
+        // the primary gamepad, also after reconnecting
+        let Some(mut gamepad) =
+            frame.primary.and_then(|id| girl.gamepad_mut(id))
+        else {
+            return ControlFlow::Continue;
+        };
+        if gamepad.buttons_pressed(Button::Start) {
+            return ControlFlow::Exit;
         }
 
         if gamepad.has_rumble() {
-            if gamepad.buttons_pressed(Button::A | Button::B) {
-                gamepad.set_rumble(
-                    u16::MAX,
-                    u16::MAX,
-                    Duration::from_millis(100),
-                )?;
-            } else if gamepad.buttons_pressed(Button::A) {
-                gamepad.set_rumble(u16::MAX, 0, Duration::from_millis(100))?;
-            } else if gamepad.buttons_pressed(Button::B) {
-                gamepad.set_rumble(0, u16::MAX, Duration::from_millis(100))?;
+            let pulse = Duration::from_millis(100);
+            let low = gamepad.buttons_pressed(Button::A);
+            let high = gamepad.buttons_pressed(Button::B);
+            drop(if low || high {
+                let low = if low { u16::MAX } else { 0 };
+                let high = if high { u16::MAX } else { 0 };
+                gamepad.set_rumble(low, high, pulse)
             } else {
-                gamepad.end_rumble()?;
-            }
+                gamepad.end_rumble()
+            });
         }
 
         println!(
@@ -77,10 +72,9 @@ fn main() -> Result<(), girl::Error> {
             gamepad.trigger(Trigger::Right),
             gamepad.sensor(Sensor::Gyroscope),
             gamepad.sensor(Sensor::Accelerometer),
-            gamepad.touchpad(),
+            gamepad.has_touchpads().then(|| gamepad.touchpad()),
             gamepad = gamepad,
         );
-
-        thread::sleep(Duration::from_millis(10));
-    }
+        ControlFlow::Continue
+    })
 }
@@ -0,0 +1,52 @@
+//! A minimal program using only the `girl` prelude.
+//!
+//! Only requires the `sdl2` feature, so it is built under almost every
+//! feature combination and catches a prelude missing a type it needs.
+#![expect(
+    unused_crate_dependencies,
+    clippy::print_stdout,
+    clippy::use_debug,
+    reason = "example"
+)]
+#![expect(
+    clippy::redundant_type_annotations,
+    reason = "names the prelude types, so a missing one fails to build"
+)]
+
+use girl::prelude::*;
+
+fn main() -> Result<(), Error> {
+    let mut girl = Girl::new()?;
+    _ = girl.update();
+
+    while let Some(event) = girl.event() {
+        if let Event::ControllerButtonDown { button: Button::Start, .. } = event
+        {
+            return Ok(());
+        }
+    }
+
+    let Some(gamepad): Option<GamepadRef<'_>> = girl.gamepads().next() else {
+        println!("No gamepad connected!");
+        return Ok(());
+    };
+    let id: GamepadId = gamepad.id();
+    let state: GamepadState = gamepad.state();
+    let [x, y] = gamepad.stick(Stick::Left);
+    let throttle = gamepad.trigger(Trigger::Right);
+    let power: Option<PowerLevel> = gamepad.power();
+    let kind: ControllerType = gamepad.controller_type();
+    let form: FormFactor = kind.form_factor();
+    println!("{id}: {kind:?} ({form:?}), {power:?}");
+    println!("{state:?}, left stick [{x}, {y}], throttle {throttle}");
+
+    #[cfg(feature = "sensors")]
+    {
+        let gyroscope: Option<Sensor> = gamepad.primary_gyroscope();
+        println!("gyroscope: {gyroscope:?}");
+    }
+
+    let profile: InputProfile = gamepad.current_profile();
+    println!("{profile:?}");
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! Tracking of the most recently active [`Gamepad`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Event, GamepadId};
+
+/// Tracks which [`Gamepad`] most recently produced deliberate input, e.g.
+/// to lock onto the controller that pressed "start" on the title screen.
+///
+/// Sensor updates never count as activity, and neither do stick or trigger
+/// motions below [`ANALOG_THRESHOLD`], so a resting controller doesn't steal
+/// the focus with drift or noise. The last active [`Gamepad`] is forgotten
+/// once it is idle for longer than the [`idle_timeout`] or disconnects.
+///
+/// Used by [`Girl::last_active_gamepad`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{ActivityTracker, prelude::*};
+/// let mut tracker = ActivityTracker::new(Duration::from_secs(5));
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+///
+/// let press = Event::ControllerButtonDown { which: 0, button: Button::A };
+/// assert!(tracker.observe(&press, at(0)));
+/// assert_eq!(tracker.last_active(at(10)), Some(GamepadId::from_raw(0)));
+///
+/// // a slightly drifting stick on the other pad doesn't take over
+/// let drift = Event::ControllerStickMotion {
+///     which: 1,
+///     stick: Stick::Left,
+///     offset: [0.05, 0.0],
+/// };
+/// assert!(!tracker.observe(&drift, at(20)));
+/// assert_eq!(tracker.last_active(at(30)), Some(GamepadId::from_raw(0)));
+///
+/// // a deliberate push does
+/// let push = Event::ControllerStickMotion {
+///     which: 1,
+///     stick: Stick::Left,
+///     offset: [0.0, -0.9],
+/// };
+/// assert!(tracker.observe(&push, at(40)));
+/// assert_eq!(tracker.last_active(at(50)), Some(GamepadId::from_raw(1)));
+///
+/// // idle for too long
+/// assert_eq!(tracker.last_active(at(5100)), None);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`ANALOG_THRESHOLD`]: Self::ANALOG_THRESHOLD
+/// [`idle_timeout`]: Self::idle_timeout
+/// [`Girl::last_active_gamepad`]: crate::Girl::last_active_gamepad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivityTracker {
+    /// How long the last active [`Gamepad`] is remembered without input.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    idle_timeout: Duration,
+    /// The last active [`Gamepad`] and when it was last active.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    last: Option<(GamepadId, Instant)>,
+}
+
+impl Default for ActivityTracker {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_IDLE_TIMEOUT)
+    }
+}
+
+impl ActivityTracker {
+    /// Smallest stick deflection or trigger value counted as activity.
+    pub const ANALOG_THRESHOLD: f64 = 0.5;
+    /// Default [`idle_timeout`].
+    ///
+    /// [`idle_timeout`]: Self::idle_timeout
+    pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a new [`ActivityTracker`] with the given [`idle_timeout`].
+    ///
+    /// [`idle_timeout`]: Self::idle_timeout
+    #[must_use]
+    #[inline]
+    pub const fn new(idle_timeout: Duration) -> Self {
+        Self { idle_timeout, last: None }
+    }
+
+    /// Gets how long the last active [`Gamepad`] is remembered without
+    /// input.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub const fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Sets how long the last active [`Gamepad`] is remembered without
+    /// input.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub const fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = idle_timeout;
+    }
+
+    /// Observes an [`Event`] at `now`, returning whether it counted as
+    /// activity.
+    #[inline]
+    pub fn observe(&mut self, event: &Event, now: Instant) -> bool {
+        if let Event::ControllerDeviceRemoved { which } = *event
+            && self.last.is_some_and(|(id, _)| id.raw() == which)
+        {
+            self.last = None;
+        }
+
+        let active = match *event {
+            Event::ControllerStickMotion { offset: [x, y], .. } => {
+                x.hypot(y) >= Self::ANALOG_THRESHOLD
+            }
+            Event::ControllerTriggerMotion { offset, .. } => {
+                offset >= Self::ANALOG_THRESHOLD
+            }
+            #[cfg(feature = "sensors")]
+            Event::ControllerSensorUpdated { .. }
+            | Event::SensorEnabled { .. }
+            | Event::SensorEnableFailed { .. } => false,
+            Event::ControllerButtonDown { .. }
+            | Event::ControllerButtonDownWithState { .. }
+            | Event::StickDirectionPressed { .. } => true,
+            Event::StickTierChanged { from, to, .. } => to > from,
+            #[cfg(feature = "touchpad")]
+            Event::ControllerTouchpad(_) => true,
+            #[cfg(feature = "rumble")]
+            Event::RumbleFinished { .. } => false,
+            Event::Quit
+            | Event::ControllerButtonUp { .. }
+            | Event::ControllerDeviceAdded { .. }
+            | Event::ControllerDeviceRemoved { .. }
+            | Event::ControllerDeviceRemapped { .. }
+            | Event::ControllerRemapDiff { .. }
+            | Event::ControllerSteamHandleUpdate { .. }
+            | Event::ControllerAudioDeviceChanged { .. }
+            | Event::PowerQueryFailed { .. }
+            | Event::PoweringOffIdle { .. }
+            | Event::ConnectionFlapping { .. }
+            | Event::StickDirectionRepeat { .. }
+            | Event::StickDirectionReleased { .. }
+            | Event::Overflow { .. }
+            | Event::InputIdle { .. }
+            | Event::InputResumed
+            | Event::SubsystemReset
+            | Event::AppBackgrounded
+            | Event::AppForegrounded => false,
+        };
+        let Some(which) = event.which().filter(|_| active) else {
+            return false;
+        };
+        self.last = Some((GamepadId::from_raw(which), now));
+        true
+    }
+
+    /// Gets the [`GamepadId`] of the last active [`Gamepad`] at `now`.
+    ///
+    /// Returns [`None`] if no [`Gamepad`] was active within the
+    /// [`idle_timeout`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`idle_timeout`]: Self::idle_timeout
+    #[must_use]
+    #[inline]
+    pub fn last_active(&self, now: Instant) -> Option<GamepadId> {
+        let (id, seen) = self.last?;
+        let idle = now.saturating_duration_since(seen);
+        (idle <= self.idle_timeout).then_some(id)
+    }
+
+    /// Forgets the last active [`Gamepad`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.last = None;
+    }
+}
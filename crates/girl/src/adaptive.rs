@@ -0,0 +1,460 @@
+//! Deadzones of analog [`Stick`]s learned from their noise at rest.
+//!
+//! [`Stick`]: crate::Stick
+
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::Error;
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, Stick};
+
+/// Buckets of the histogram of resting noise levels, over `[0.0, max)`.
+const BUCKETS: usize = 32;
+
+/// Fewest resting samples to learn a deadzone from.
+const MIN_SAMPLES: u32 = 120;
+
+/// Resting samples after which the histogram ages, halving every count so
+/// the deadzone follows a stick drifting over time.
+const MAX_SAMPLES: u32 = 4096;
+
+/// Share of the resting samples the learned deadzone covers.
+const PERCENTILE: f64 = 0.99;
+
+/// Adaptive deadzones of [`Gamepad`] sticks.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Makes the deadzone of the [`Stick`] adapt to its noise at rest, see
+    /// [`AdaptiveDeadzone`], starting from its current deadzone.
+    ///
+    /// Like output smoothing, the estimator is clocked by the calls to
+    /// [`stick`] (and [`state`]): read the [`Stick`] once per frame. The
+    /// learned deadzone is reported by [`deadzone`] and is saved in the
+    /// [`InputProfile`] of [`current_profile`] along with the
+    /// [`AdaptiveDeadzoneConfig`], so applying the profile again later
+    /// resumes from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the configuration is not valid,
+    /// see [`AdaptiveDeadzoneConfig`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{AdaptiveDeadzoneConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let config = AdaptiveDeadzoneConfig::new();
+    /// gamepad.enable_adaptive_deadzone(Stick::Left, config)?;
+    /// assert_eq!(gamepad.adaptive_deadzone(Stick::Left), Some(config));
+    ///
+    /// let [x, y] = gamepad.stick(Stick::Left);
+    /// assert!(gamepad.deadzone(Stick::Left) >= config.min);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    /// [`state`]: Self::state
+    /// [`deadzone`]: Self::deadzone
+    /// [`current_profile`]: Self::current_profile
+    /// [`InputProfile`]: crate::InputProfile
+    #[inline]
+    pub fn enable_adaptive_deadzone(
+        &mut self,
+        stick: Stick,
+        config: AdaptiveDeadzoneConfig,
+    ) -> Result<(), Error> {
+        config.check()?;
+        let profile = self.profile_mut().stick_mut(stick);
+        profile.adaptive = Some(config);
+        let estimator = AdaptiveDeadzone::new(config, profile.deadzone);
+        self.estimator(stick).set(Some(estimator));
+        Ok(())
+    }
+
+    /// Stops adapting the deadzone of the [`Stick`], keeping the one learned
+    /// so far, see [`enable_adaptive_deadzone`].
+    ///
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    #[inline]
+    pub fn disable_adaptive_deadzone(&mut self, stick: Stick) {
+        let deadzone = self.deadzone(stick);
+        let profile = self.profile_mut().stick_mut(stick);
+        profile.deadzone = deadzone;
+        profile.adaptive = None;
+        self.estimator(stick).set(None);
+    }
+
+    /// Gets the configuration of the adaptive deadzone of the [`Stick`], if
+    /// enabled, see [`enable_adaptive_deadzone`].
+    ///
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    #[must_use]
+    #[inline]
+    pub const fn adaptive_deadzone(
+        &self,
+        stick: Stick,
+    ) -> Option<AdaptiveDeadzoneConfig> {
+        self.profile().stick(stick).adaptive
+    }
+
+    /// Gets the deadzone [`stick`] currently applies to the [`Stick`]: the
+    /// learned one if adaptive (see [`enable_adaptive_deadzone`]), otherwise
+    /// the configured one (see [`set_stick_deadzone`]).
+    ///
+    /// [`stick`]: Self::stick
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    /// [`set_stick_deadzone`]: Self::set_stick_deadzone
+    #[must_use]
+    #[inline]
+    pub fn deadzone(&self, stick: Stick) -> f64 {
+        let profile = self.profile().stick(stick);
+        let Some(config) = profile.adaptive else {
+            return profile.deadzone;
+        };
+        self.estimator(stick)
+            .get()
+            .filter(|estimator| estimator.config == config)
+            .map_or_else(
+                || profile.deadzone.max(config.min).min(config.max),
+                |estimator| estimator.deadzone(),
+            )
+    }
+
+    /// Feeds the raw `position` of the [`Stick`] to its adaptive deadzone,
+    /// returning the deadzone to apply, or [`None`] if not adaptive.
+    ///
+    /// The estimator starts over from the deadzone of the profile whenever
+    /// the profile changes its [`AdaptiveDeadzoneConfig`], e.g. when one is
+    /// applied.
+    #[inline]
+    pub(crate) fn adapt(
+        &self,
+        stick: Stick,
+        position: [f64; 2],
+    ) -> Option<f64> {
+        let profile = self.profile().stick(stick);
+        let cell = self.estimator(stick);
+        let Some(config) = profile.adaptive else {
+            cell.set(None);
+            return None;
+        };
+        let mut estimator = cell
+            .get()
+            .filter(|estimator| estimator.config == config)
+            .unwrap_or_else(|| AdaptiveDeadzone::new(config, profile.deadzone));
+        let deadzone = estimator.update_at(position, Instant::now());
+        cell.set(Some(estimator));
+        Some(deadzone)
+    }
+}
+
+/// Configuration of an adaptive [`Stick`] deadzone, see
+/// [`Gamepad::enable_adaptive_deadzone`] and [`AdaptiveDeadzone`].
+///
+/// Valid if `0.0 <= min <= max < 1.0`, `margin >= 1.0` and `rest_movement`
+/// is positive, all finite.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdaptiveDeadzoneConfig {
+    /// Smallest deadzone, which the learned one never shrinks below.
+    pub min: f64,
+    /// Largest deadzone. Deflections reaching it are intentional input.
+    pub max: f64,
+    /// Factor of the noise level at rest the deadzone is set to, so it
+    /// sits just above the noise.
+    pub margin: f64,
+    /// Farthest a [`Stick`] at rest wanders from where it came to rest, in
+    /// stick units, covering its noise. Moving farther is intentional
+    /// input.
+    pub rest_movement: f64,
+    /// Time after the last intentional input before reads count as at rest
+    /// again, so a [`Stick`] springing back isn't mistaken for noise.
+    pub settle: Duration,
+}
+
+impl Default for AdaptiveDeadzoneConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveDeadzoneConfig {
+    /// Creates the default [`AdaptiveDeadzoneConfig`]: deadzones between
+    /// `0.03` and `0.3`, `25%` above the noise, and reads within `0.1` of
+    /// where the [`Stick`] came to rest counting as at rest, one second
+    /// after the last input.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            min: 0.03,
+            max: 0.3,
+            margin: 1.25,
+            rest_movement: 0.1,
+            settle: Duration::from_secs(1),
+        }
+    }
+
+    /// Checks that the configuration is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if a bound is not finite, the
+    /// bounds are not in `0.0 <= min <= max < 1.0`, the `margin` is below
+    /// `1.0` or the `rest_movement` is not positive.
+    pub(crate) const fn check(&self) -> Result<(), Error> {
+        let valid = self.min.is_finite()
+            && self.max.is_finite()
+            && self.margin.is_finite()
+            && self.rest_movement.is_finite()
+            && 0.0f64 <= self.min
+            && self.min <= self.max
+            && self.max < 1.0f64
+            && self.margin >= 1.0f64
+            && self.rest_movement > 0.0f64;
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidArgument { what: "adaptive_deadzone" })
+        }
+    }
+}
+
+/// Learns the deadzone of a [`Stick`] from its noise at rest, see
+/// [`AdaptiveDeadzoneConfig`].
+///
+/// A read of the [`Stick`] is at rest when it stays within the
+/// [`rest_movement`] of where the [`Stick`] came to rest and below the
+/// [`max`] deadzone on both axes, once the [`settle`] time has passed since
+/// the last read that didn't. The deadzone is set to the [`margin`] times the
+/// level, on the larger axis, that 99% of the reads at rest stay below,
+/// within the [`min`] and [`max`] bounds. Nothing is learned while the
+/// [`Stick`] is in use, and older reads fade out over time so a drifting
+/// [`Stick`] is followed.
+///
+/// Used by [`Gamepad::enable_adaptive_deadzone`], and usable on its own for
+/// positions from other sources, e.g. [`Event`]s.
+///
+/// # Examples
+///
+/// A noisy stick at rest, then a drifting one:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{AdaptiveDeadzone, AdaptiveDeadzoneConfig};
+/// let config = AdaptiveDeadzoneConfig::new();
+/// let mut deadzone = AdaptiveDeadzone::new(config, 0.1);
+/// let dt = Duration::from_millis(10);
+/// // noise within 0.03 around the center, reproducibly
+/// let noise = |i: u32| f64::from(i % 7) / 100.0 - 0.03;
+///
+/// for i in 0..100 {
+///     deadzone.update([noise(i), noise(i + 3)], dt);
+/// }
+/// // settling, then too few reads at rest to tell
+/// assert_eq!(deadzone.deadzone(), 0.1);
+///
+/// for i in 0..200 {
+///     deadzone.update([noise(i), noise(i + 3)], dt);
+/// }
+/// let learned = deadzone.deadzone();
+/// assert!((0.04..0.06).contains(&learned), "{learned}");
+///
+/// // drifting to the right, followed over time
+/// for i in 0..20_000 {
+///     deadzone.update([0.12 + noise(i) / 4.0, 0.0], dt);
+/// }
+/// let drifted = deadzone.deadzone();
+/// assert!((0.15..0.2).contains(&drifted), "{drifted}");
+/// ```
+///
+/// Frozen while in use, and never below the floor:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{AdaptiveDeadzone, AdaptiveDeadzoneConfig};
+/// let config = AdaptiveDeadzoneConfig::new();
+/// let mut deadzone = AdaptiveDeadzone::new(config, 0.1);
+/// let dt = Duration::from_millis(10);
+///
+/// // a perfectly quiet stick still gets the smallest deadzone
+/// for _ in 0..300 {
+///     deadzone.update([0.0, 0.0], dt);
+/// }
+/// assert_eq!(deadzone.deadzone(), config.min);
+///
+/// // aiming with small deflections doesn't grow the deadzone
+/// deadzone.update([1.0, 0.0], dt);
+/// for i in 0..2_000 {
+///     let x = f64::from(i % 100) * 0.0025;
+///     deadzone.update([x, 0.0], dt);
+///     assert!(deadzone.is_frozen());
+/// }
+/// assert_eq!(deadzone.deadzone(), config.min);
+/// ```
+///
+/// [`rest_movement`]: AdaptiveDeadzoneConfig::rest_movement
+/// [`max`]: AdaptiveDeadzoneConfig::max
+/// [`min`]: AdaptiveDeadzoneConfig::min
+/// [`margin`]: AdaptiveDeadzoneConfig::margin
+/// [`settle`]: AdaptiveDeadzoneConfig::settle
+/// [`Event`]: crate::Event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveDeadzone {
+    /// Configuration.
+    config: AdaptiveDeadzoneConfig,
+    /// Reads at rest per noise level bucket.
+    counts: [u32; BUCKETS],
+    /// Total of the `counts`.
+    samples: u32,
+    /// Current deadzone.
+    deadzone: f64,
+    /// Position the [`Stick`] came to rest at, [`None`] before the first
+    /// update.
+    anchor: Option<[f64; 2]>,
+    /// Time since the last read that wasn't at rest.
+    since_use: Duration,
+    /// Time of the last [`AdaptiveDeadzone::update_at`].
+    last_at: Option<Instant>,
+}
+
+impl AdaptiveDeadzone {
+    /// Creates an [`AdaptiveDeadzone`] starting from the `initial` deadzone,
+    /// kept within the bounds of the [`AdaptiveDeadzoneConfig`] until
+    /// enough reads at rest are collected.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: AdaptiveDeadzoneConfig, initial: f64) -> Self {
+        Self {
+            config,
+            counts: [0; BUCKETS],
+            samples: 0,
+            deadzone: initial.max(config.min).min(config.max),
+            anchor: None,
+            since_use: Duration::ZERO,
+            last_at: None,
+        }
+    }
+
+    /// Gets the [`AdaptiveDeadzoneConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> &AdaptiveDeadzoneConfig {
+        &self.config
+    }
+
+    /// Gets the current deadzone.
+    #[must_use]
+    #[inline]
+    pub const fn deadzone(&self) -> f64 {
+        self.deadzone
+    }
+
+    /// Gets how many reads at rest the deadzone is currently learned from.
+    #[must_use]
+    #[inline]
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Checks if learning is frozen, because the [`Stick`] is in use or
+    /// settling after it.
+    #[must_use]
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.since_use < self.config.settle
+    }
+
+    /// Observes the raw `position` read `dt` after the previous one,
+    /// returning the deadzone to apply to it.
+    #[inline]
+    pub fn update(&mut self, position: [f64; 2], dt: Duration) -> f64 {
+        let [x, y] = position;
+        let level = x.abs().max(y.abs());
+        let wandered = self.anchor.is_none_or(|[ax, ay]| {
+            (x - ax).hypot(y - ay) > self.config.rest_movement
+        });
+        if level >= self.config.max || wandered {
+            self.anchor = Some(position);
+            self.since_use = Duration::ZERO;
+            return self.deadzone;
+        }
+        self.since_use = self.since_use.saturating_add(dt);
+        if !self.is_frozen() {
+            self.record(level);
+        }
+        self.deadzone
+    }
+
+    /// Observes the raw `position` read at `now`, timed against the
+    /// previous call.
+    #[inline]
+    pub fn update_at(&mut self, position: [f64; 2], now: Instant) -> f64 {
+        let dt = self
+            .last_at
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        self.last_at = Some(now);
+        self.update(position, dt)
+    }
+
+    /// Records the noise `level` of a read at rest, learning the deadzone
+    /// once there are enough.
+    fn record(&mut self, level: f64) {
+        let bucket = self.bucket(level);
+        if let Some(count) = self.counts.get_mut(bucket) {
+            *count = count.saturating_add(1);
+            self.samples = self.samples.saturating_add(1);
+        }
+        if self.samples >= MAX_SAMPLES {
+            for count in &mut self.counts {
+                *count /= 2;
+            }
+            self.samples = self.counts.iter().sum();
+        }
+        if self.samples < MIN_SAMPLES {
+            return;
+        }
+        let covered = f64::from(self.samples) * PERCENTILE;
+        let mut seen = 0;
+        let mut upper = self.config.max;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen = count.saturating_add(seen);
+            if f64::from(seen) >= covered {
+                upper = self.bucket_end(index);
+                break;
+            }
+        }
+        let deadzone = upper * self.config.margin;
+        self.deadzone = deadzone.max(self.config.min).min(self.config.max);
+    }
+
+    /// Gets the bucket of a noise `level` below the maximal deadzone.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "floored, positive and below the number of buckets"
+    )]
+    fn bucket(&self, level: f64) -> usize {
+        #[expect(clippy::cast_precision_loss, reason = "small constant")]
+        let buckets = BUCKETS as f64;
+        (level / self.config.max * buckets).floor().clamp(0.0, buckets - 1.0)
+            as usize
+    }
+
+    /// Gets the upper end of the noise levels of the bucket at `index`.
+    #[expect(clippy::cast_precision_loss, reason = "small numbers")]
+    fn bucket_end(&self, index: usize) -> f64 {
+        index.saturating_add(1) as f64 / BUCKETS as f64 * self.config.max
+    }
+}
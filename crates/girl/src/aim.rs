@@ -0,0 +1,456 @@
+//! Camera control helpers: gyro aiming and flick stick.
+
+use core::{mem, time::Duration};
+use std::time::Instant;
+
+/// Number of samples averaged by the [`FlickStick`] turn smoothing.
+const SMOOTHING_WINDOW: usize = 8;
+
+/// Configuration of a [`GyroAim`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GyroAimConfig {
+    /// Real-world sensitivity: degrees the camera turns per degree the
+    /// controller turns.
+    pub sensitivity: f64,
+    /// Acceleration curve replacing [`sensitivity`], if any.
+    ///
+    /// [`sensitivity`]: Self::sensitivity
+    pub acceleration: Option<GyroAcceleration>,
+    /// Angular speed, in degrees per second, below which rotation is scaled
+    /// down proportionally to steady shaky hands. `0.0` disables tightening.
+    pub tightening: f64,
+}
+
+impl Default for GyroAimConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GyroAimConfig {
+    /// Creates the default [`GyroAimConfig`]: 1:1 sensitivity without
+    /// acceleration or tightening.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { sensitivity: 1.0, acceleration: None, tightening: 0.0 }
+    }
+}
+
+/// Gyro acceleration curve: sensitivity interpolated linearly between a slow
+/// and a fast angular speed.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::GyroAcceleration;
+/// let curve = GyroAcceleration::new(10.0, 1.0, 110.0, 3.0);
+///
+/// assert_eq!(curve.sensitivity(0.0), 1.0);
+/// assert_eq!(curve.sensitivity(60.0), 2.0);
+/// assert_eq!(curve.sensitivity(500.0), 3.0);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GyroAcceleration {
+    /// Angular speed, in degrees per second, at and below which
+    /// [`slow_sensitivity`] applies.
+    ///
+    /// [`slow_sensitivity`]: Self::slow_sensitivity
+    pub slow_speed: f64,
+    /// Sensitivity at [`slow_speed`].
+    ///
+    /// [`slow_speed`]: Self::slow_speed
+    pub slow_sensitivity: f64,
+    /// Angular speed, in degrees per second, at and above which
+    /// [`fast_sensitivity`] applies.
+    ///
+    /// [`fast_sensitivity`]: Self::fast_sensitivity
+    pub fast_speed: f64,
+    /// Sensitivity at [`fast_speed`].
+    ///
+    /// [`fast_speed`]: Self::fast_speed
+    pub fast_sensitivity: f64,
+}
+
+impl GyroAcceleration {
+    /// Creates a new [`GyroAcceleration`] curve.
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        slow_speed: f64,
+        slow_sensitivity: f64,
+        fast_speed: f64,
+        fast_sensitivity: f64,
+    ) -> Self {
+        Self { slow_speed, slow_sensitivity, fast_speed, fast_sensitivity }
+    }
+
+    /// Gets the sensitivity at the angular `speed`, in degrees per second.
+    #[must_use]
+    #[inline]
+    pub fn sensitivity(&self, speed: f64) -> f64 {
+        let span = self.fast_speed - self.slow_speed;
+        let progress = if span > 0.0f64 {
+            ((speed - self.slow_speed) / span).clamp(0.0, 1.0)
+        } else if speed >= self.fast_speed {
+            1.0f64
+        } else {
+            0.0f64
+        };
+        (self.fast_sensitivity - self.slow_sensitivity)
+            .mul_add(progress, self.slow_sensitivity)
+    }
+}
+
+/// Turns gyroscope readings into camera rotation.
+///
+/// Consumes timestamped [`Sensor::Gyroscope`] readings and accumulates
+/// `[yaw_delta, pitch_delta]` in degrees until [`take`]n, usually once per
+/// frame. Positive yaw turns the camera right, positive pitch turns it up.
+///
+/// Every reading is integrated over the time since the previous one, so
+/// readings must be fed in order, each with the time it was taken at.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{f64::consts::PI, time::{Duration, Instant}};
+/// # use girl::{GyroAim, GyroAimConfig};
+/// let close = |[yaw, pitch]: [f64; 2], [x, y]: [f64; 2]| {
+///     (yaw - x).abs() < 1e-9 && (pitch - y).abs() < 1e-9
+/// };
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+///
+/// let mut config = GyroAimConfig::new();
+/// config.sensitivity = 2.0;
+/// let mut aim = GyroAim::new(config);
+///
+/// // the first reading only starts the clock
+/// aim.push([0.0, 0.0, 0.0], at(0));
+/// // turning right at 90 °/s for 100 ms, camera turns twice as much
+/// aim.push([0.0, -PI / 2.0, 0.0], at(100));
+/// assert!(close(aim.take(), [18.0, 0.0]));
+/// // tilting up at 45 °/s for 200 ms
+/// aim.push([PI / 4.0, 0.0, 0.0], at(300));
+/// assert!(close(aim.take(), [0.0, 18.0]));
+/// assert_eq!(aim.take(), [0.0, 0.0]);
+///
+/// // tightening halves rotation at half the threshold speed
+/// config.sensitivity = 1.0;
+/// config.tightening = 10.0;
+/// aim.set_config(config);
+/// aim.push([5.0_f64.to_radians(), 0.0, 0.0], at(1300));
+/// assert!(close(aim.take(), [0.0, 2.5]));
+/// ```
+///
+/// [`Sensor::Gyroscope`]: crate::Sensor::Gyroscope
+/// [`take`]: Self::take
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GyroAim {
+    /// Configuration.
+    config: GyroAimConfig,
+    /// When the previous reading was taken.
+    last: Option<Instant>,
+    /// Rotation accumulated since the last [`take`].
+    ///
+    /// [`take`]: Self::take
+    delta: [f64; 2],
+}
+
+impl Default for GyroAim {
+    #[inline]
+    fn default() -> Self {
+        Self::new(GyroAimConfig::new())
+    }
+}
+
+impl GyroAim {
+    /// Creates a new [`GyroAim`] with the given configuration.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: GyroAimConfig) -> Self {
+        Self { config, last: None, delta: [0.0, 0.0] }
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> &GyroAimConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration, keeping the accumulated rotation.
+    #[inline]
+    pub const fn set_config(&mut self, config: GyroAimConfig) {
+        self.config = config;
+    }
+
+    /// Feeds a gyroscope reading `[pitch, yaw, roll]` in radians per second,
+    /// as reported by [`Gamepad::sensor`], taken at `timestamp`.
+    ///
+    /// [`Gamepad::sensor`]: crate::Gamepad::sensor
+    #[inline]
+    pub fn push(&mut self, gyro: [f64; 3], timestamp: Instant) {
+        let Some(last) = self.last.replace(timestamp) else {
+            return;
+        };
+        let dt = timestamp.saturating_duration_since(last).as_secs_f64();
+        let [pitch, yaw, _] = gyro.map(f64::to_degrees);
+
+        let speed = pitch.hypot(yaw);
+        let sensitivity = self
+            .config
+            .acceleration
+            .map_or(self.config.sensitivity, |curve| curve.sensitivity(speed));
+        let tightening = if speed < self.config.tightening {
+            speed / self.config.tightening
+        } else {
+            1.0f64
+        };
+
+        let scale = sensitivity * tightening * dt;
+        let [yaw_delta, pitch_delta] = self.delta;
+        self.delta = [
+            (-yaw).mul_add(scale, yaw_delta),
+            pitch.mul_add(scale, pitch_delta),
+        ];
+    }
+
+    /// Takes the rotation `[yaw_delta, pitch_delta]` in degrees accumulated
+    /// since the previous call.
+    #[must_use]
+    #[inline]
+    pub fn take(&mut self) -> [f64; 2] {
+        mem::take(&mut self.delta)
+    }
+
+    /// Drops the accumulated rotation and restarts the clock, e.g. after
+    /// gyro aiming was paused.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.last = None;
+        self.delta = [0.0f64, 0.0f64];
+    }
+}
+
+/// Configuration of a [`FlickStick`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlickStickConfig {
+    /// [`Stick`] deflection at which the [`FlickStick`] engages.
+    ///
+    /// [`Stick`]: crate::Stick
+    pub threshold: f64,
+    /// How long a flick takes to turn the camera.
+    pub flick_time: Duration,
+    /// Turning speed, in degrees per second, below which turning is
+    /// smoothed, fully so below half of it. Hides the jitter of small
+    /// rotations of the stick. `0.0` disables smoothing.
+    pub smoothing_threshold: f64,
+}
+
+impl Default for FlickStickConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlickStickConfig {
+    /// Creates the default [`FlickStickConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            threshold: 0.9,
+            flick_time: Duration::from_millis(100),
+            smoothing_threshold: 90.0,
+        }
+    }
+}
+
+/// Turns the camera with a [`Stick`] the "flick stick" way.
+///
+/// Pushing the [`Stick`] past the [`threshold`] flicks the camera towards
+/// the direction pushed (forward is no turn, right is 90° to the right)
+/// over the [`flick_time`]. Rotating the pushed [`Stick`] then turns the
+/// camera by the same angle, smoothing small rotations. Releasing the
+/// [`Stick`] lets the next push flick again.
+///
+/// Fed with [`Stick`] positions in the usual `[x, y]` convention, with `y`
+/// from top to bottom, returning the yaw delta in degrees, positive to the
+/// right.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{FlickStick, FlickStickConfig};
+/// let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+/// let towards = |degrees: f64| {
+///     let radians = degrees.to_radians();
+///     [radians.sin(), -radians.cos()]
+/// };
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+/// let mut flick = FlickStick::new(FlickStickConfig::new());
+///
+/// // flicking right eases the camera 90° to the right over 100 ms
+/// assert!(close(flick.update([1.0, 0.0], at(0)), 0.0));
+/// assert!(close(flick.update([1.0, 0.0], at(50)), 67.5));
+/// assert!(close(flick.update([1.0, 0.0], at(100)), 22.5));
+/// assert!(close(flick.update([1.0, 0.0], at(110)), 0.0));
+///
+/// // below the threshold nothing happens
+/// assert!(close(flick.update([0.5, 0.0], at(120)), 0.0));
+///
+/// // flicking backwards turns around
+/// assert!(close(flick.update([0.0, 1.0], at(200)), 0.0));
+/// assert!(close(flick.update([0.0, 1.0], at(300)), 180.0));
+///
+/// // rotating the stick turns the camera, wrapping around behind
+/// assert!(close(flick.update(towards(-170.0), at(310)), 10.0));
+/// assert!(close(flick.update(towards(90.0), at(320)), -100.0));
+///
+/// // a tiny slow rotation is smoothed over several updates
+/// let tiny = flick.update(towards(90.1), at(330));
+/// assert!(close(tiny, 0.1 / 8.0));
+/// ```
+///
+/// [`Stick`]: crate::Stick
+/// [`threshold`]: FlickStickConfig::threshold
+/// [`flick_time`]: FlickStickConfig::flick_time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlickStick {
+    /// Configuration.
+    config: FlickStickConfig,
+    /// When the previous update happened.
+    last: Option<Instant>,
+    /// [`Stick`] angle of the previous update, in degrees, while engaged.
+    ///
+    /// [`Stick`]: crate::Stick
+    angle: Option<f64>,
+    /// Flick in progress: when it started, its angle and the eased progress
+    /// already turned.
+    flick: Option<(Instant, f64, f64)>,
+    /// Recent smoothed turning deltas, oldest first.
+    smoothing: [f64; SMOOTHING_WINDOW],
+}
+
+impl Default for FlickStick {
+    #[inline]
+    fn default() -> Self {
+        Self::new(FlickStickConfig::new())
+    }
+}
+
+impl FlickStick {
+    /// Creates a new [`FlickStick`] with the given configuration.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: FlickStickConfig) -> Self {
+        Self {
+            config,
+            last: None,
+            angle: None,
+            flick: None,
+            smoothing: [0.0; SMOOTHING_WINDOW],
+        }
+    }
+
+    /// Gets the configuration.
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> &FlickStickConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration.
+    #[inline]
+    pub const fn set_config(&mut self, config: FlickStickConfig) {
+        self.config = config;
+    }
+
+    /// Feeds the [`Stick`] `position` at `now`, returning how far to turn
+    /// the camera, in degrees, positive to the right.
+    ///
+    /// [`Stick`]: crate::Stick
+    #[inline]
+    pub fn update(&mut self, position: [f64; 2], now: Instant) -> f64 {
+        let dt = self.last.replace(now).map_or(0.0f64, |last| {
+            now.saturating_duration_since(last).as_secs_f64()
+        });
+        let [x, y] = position;
+        let mut turn: f64 = 0.0;
+
+        if x.hypot(y) >= self.config.threshold {
+            let angle = x.atan2(-y).to_degrees();
+            if let Some(previous) = self.angle {
+                turn += self.smooth(wrap_degrees(angle - previous), dt);
+            } else {
+                self.flick = Some((now, angle, 0.0f64));
+            }
+            self.angle = Some(angle);
+        } else {
+            self.angle = None;
+            self.smoothing = [0.0f64; SMOOTHING_WINDOW];
+        }
+
+        if let Some((start, target, done)) = self.flick {
+            let elapsed = now.saturating_duration_since(start).as_secs_f64();
+            let flick_time = self.config.flick_time.as_secs_f64();
+            let linear = if flick_time > 0.0f64 {
+                (elapsed / flick_time).min(1.0)
+            } else {
+                1.0f64
+            };
+            // ease out, so the flick lands softly
+            let progress = linear * (2.0f64 - linear);
+            turn = target.mul_add(progress - done, turn);
+            self.flick = (linear < 1.0f64).then_some((start, target, progress));
+        }
+
+        turn
+    }
+
+    /// Stops any flick in progress and disengages, so the next push past the
+    /// threshold flicks again.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.last = None;
+        self.angle = None;
+        self.flick = None;
+        self.smoothing = [0.0f64; SMOOTHING_WINDOW];
+    }
+
+    /// Smooths a turning `delta` over `dt` seconds: fast turns pass through,
+    /// slow ones are averaged over the last [`SMOOTHING_WINDOW`] updates.
+    fn smooth(&mut self, delta: f64, dt: f64) -> f64 {
+        let upper = self.config.smoothing_threshold;
+        let lower = upper / 2.0f64;
+        let direct = if upper > 0.0f64 && dt > 0.0f64 {
+            ((delta.abs() / dt - lower) / (upper - lower)).clamp(0.0, 1.0)
+        } else {
+            1.0f64
+        };
+
+        self.smoothing.rotate_left(1);
+        if let Some(newest) = self.smoothing.last_mut() {
+            *newest = delta * (1.0f64 - direct);
+        }
+        #[expect(clippy::cast_precision_loss, reason = "small constant")]
+        let window = SMOOTHING_WINDOW as f64;
+        let smoothed = self.smoothing.iter().sum::<f64>() / window;
+        delta.mul_add(direct, smoothed)
+    }
+}
+
+/// Wraps an angle in degrees into the `[-180.0, 180.0)` range.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn wrap_degrees(angle: f64) -> f64 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
@@ -0,0 +1,185 @@
+//! A minimal game loop around [`Girl`], for applications that don't have
+//! one of their own, e.g. tools and prototypes.
+//!
+//! [`run`] owns the loop: every frame it updates [`Girl`], collects the
+//! [`Event`]s, keeps track of the primary [`Gamepad`] across reconnects and
+//! hands it all to a callback, until the callback asks to exit or the
+//! application is asked to quit, e.g. with Ctrl-C.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use girl::{app::{self, Config, ControlFlow}, prelude::*};
+//! app::run(Config::new(), |girl, frame| {
+//!     let Some(gamepad) = frame.primary.and_then(|id| girl.gamepad_ref(id))
+//!     else {
+//!         return ControlFlow::Continue;
+//!     };
+//!     if gamepad.buttons_pressed(Button::Start) {
+//!         return ControlFlow::Exit;
+//!     }
+//!     println!("{:?}", gamepad.stick(Stick::Left));
+//!     ControlFlow::Continue
+//! })?;
+//! # Ok::<(), Error>(())
+//! ```
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+use core::time::Duration;
+use std::{thread, time::Instant};
+
+use crate::{Error, Event, GamepadId, Girl, Hints};
+
+/// Configuration of the loop of [`run`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Time between the starts of two frames, sleeping for what the frame
+    /// didn't take. [`None`] runs the next frame right away, for a
+    /// callback pacing the loop itself, e.g. by waiting for vsync.
+    pub poll_interval: Option<Duration>,
+    /// SDL2 [`Hints`] set before initializing [`Girl`].
+    pub hints: Hints,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Creates the default [`Config`]: frames every 10 milliseconds and the
+    /// default [`Hints`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            poll_interval: Some(Duration::from_millis(10)),
+            hints: Hints::new(),
+        }
+    }
+}
+
+/// What the loop of [`run`] does after a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[expect(clippy::exhaustive_enums, reason = "a loop either goes on or ends")]
+pub enum ControlFlow {
+    /// Runs the next frame.
+    #[default]
+    Continue,
+    /// Ends the loop, tearing down [`Girl`] before [`run`] returns.
+    Exit,
+}
+
+/// A frame of the loop of [`run`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Frame<'events> {
+    /// Every [`Event`] since the previous frame, in order.
+    pub events: &'events [Event],
+    /// Time since the start of the previous frame, zero for the first one.
+    pub dt: Duration,
+    /// Number of the frame, starting from zero.
+    pub index: u64,
+    /// The primary [`Gamepad`], if any is connected: the one of the
+    /// previous frame while it stays connected, otherwise the last active
+    /// or else the first connected one, e.g. when it reconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pub primary: Option<GamepadId>,
+}
+
+/// Runs a game loop, calling `frame_fn` every frame until it returns
+/// [`ControlFlow::Exit`] or [`Event::Quit`] is reported, e.g. on Ctrl-C.
+///
+/// Every frame, in order:
+///
+/// 1. updates [`Girl`], also advancing its time-driven layer, see
+///    [`Girl::update`];
+/// 2. collects every [`Event`] since the previous frame;
+/// 3. calls `frame_fn`, also without any [`Gamepad`] connected;
+/// 4. sleeps for the rest of the [`Config::poll_interval`].
+///
+/// The frame reporting [`Event::Quit`] still reaches `frame_fn`, e.g. to
+/// save state, and is the last one. [`Girl`] is dropped before returning,
+/// so another one can be initialized right after.
+///
+/// Must be called on the main thread on platforms where SDL2 requires it,
+/// see [`Girl::new`].
+///
+/// # Errors
+///
+/// Returns an error if [`Girl`] fails to initialize, see
+/// [`Girl::with_hints`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{app::{self, Config, ControlFlow}, prelude::*};
+/// let mut config = Config::new();
+/// config.poll_interval = None;
+///
+/// let mut frames = 0;
+/// app::run(config, |_, frame| {
+///     // called without any gamepad connected too
+///     assert_eq!(frame.index, frames);
+///     frames += 1;
+///     if frames == 3 { ControlFlow::Exit } else { ControlFlow::Continue }
+/// })?;
+///
+/// assert_eq!(frames, 3);
+/// assert!(!girl::is_initialized());
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+#[inline]
+pub fn run<F>(config: Config, mut frame_fn: F) -> Result<(), Error>
+where
+    F: FnMut(&mut Girl, &mut Frame<'_>) -> ControlFlow,
+{
+    let mut girl = Girl::with_hints(config.hints)?;
+    let mut events = Vec::new();
+    let mut primary = None;
+    let mut previous = None;
+
+    for index in 0u64.. {
+        let started = Instant::now();
+        _ = girl.update();
+        events.clear();
+        _ = girl.drain_into(&mut events);
+        primary = primary_of(&girl, primary);
+
+        let dt = previous.map_or(Duration::ZERO, |previous| {
+            started.saturating_duration_since(previous)
+        });
+        previous = Some(started);
+        let quit = events.iter().any(|event| matches!(*event, Event::Quit));
+        let mut frame = Frame { events: &events, dt, index, primary };
+        if frame_fn(&mut girl, &mut frame) == ControlFlow::Exit || quit {
+            break;
+        }
+
+        if let Some(interval) = config.poll_interval {
+            thread::sleep(interval.saturating_sub(started.elapsed()));
+        }
+    }
+
+    drop(girl);
+    Ok(())
+}
+
+/// Gets the primary [`Gamepad`], see [`Frame::primary`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn primary_of(girl: &Girl, previous: Option<GamepadId>) -> Option<GamepadId> {
+    let connected = |id: &GamepadId| girl.gamepad_ref(*id).is_some();
+    previous
+        .filter(connected)
+        .or_else(|| girl.last_active_gamepad().filter(connected))
+        .or_else(|| girl.gamepads().next().map(|gamepad| gamepad.id()))
+}
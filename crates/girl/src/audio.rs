@@ -0,0 +1,362 @@
+//! Correlation of [`Gamepad`]s with the audio devices they expose, e.g. to
+//! play the audio-driven haptics of a `DualSense`.
+
+use alloc::collections::BTreeMap;
+use core::{cmp::Ordering, ffi::CStr};
+
+use sdl2::sys as sdl2_sys;
+
+use crate::{ControllerType, Gamepad, Guid};
+
+/// Audio devices of [`Gamepad`]s.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+impl Gamepad {
+    /// Gets the name of the audio output device exposed by the [`Gamepad`],
+    /// if determinable, e.g. to play `DualSense` haptics as audio waveforms.
+    ///
+    /// The device is matched by name with an [`AudioMatcher`] (see
+    /// [`Girl::set_audio_matcher`]) among the audio output devices reported
+    /// by SDL2, which requires the SDL2 audio subsystem to be initialized by
+    /// the app. Changes are reported with
+    /// [`Event::ControllerAudioDeviceChanged`][changed].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Some(device) = gamepad.audio_device_hint() {
+    ///     // open `device` with the audio stack and play haptic waveforms
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::set_audio_matcher`]: crate::Girl::set_audio_matcher
+    /// [changed]: crate::Event::ControllerAudioDeviceChanged
+    #[must_use]
+    #[inline]
+    pub fn audio_device_hint(&self) -> Option<String> {
+        self.shared().audio_devices().get(&self.id()).cloned()
+    }
+}
+
+/// Identity of a [`Gamepad`] as matched against audio device names by an
+/// [`AudioMatcher`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioIdentity {
+    /// [`Guid`] of the [`Gamepad`], see [`Gamepad::guid`].
+    pub guid: Guid,
+    /// Name of the [`Gamepad`], see [`Gamepad::name`].
+    pub name: String,
+    /// Serial number of the [`Gamepad`], see [`Gamepad::serial`].
+    pub serial: Option<String>,
+    /// [`ControllerType`] of the [`Gamepad`], see
+    /// [`Gamepad::controller_type`].
+    pub controller_type: ControllerType,
+}
+
+impl AudioIdentity {
+    /// Creates a new [`AudioIdentity`] without a serial number.
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        guid: Guid,
+        name: String,
+        controller_type: ControllerType,
+    ) -> Self {
+        Self { guid, name, serial: None, controller_type }
+    }
+
+    /// Gets the [`AudioIdentity`] of a connected [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub fn of(gamepad: &Gamepad) -> Self {
+        let mut identity = Self::new(
+            gamepad.guid(),
+            gamepad.name(),
+            gamepad.controller_type(),
+        );
+        identity.serial = gamepad.serial();
+        identity
+    }
+}
+
+/// Heuristics matching [`Gamepad`]s with the audio output devices they
+/// expose, see [`Girl::set_audio_matcher`] and
+/// [`Gamepad::audio_device_hint`].
+///
+/// Every audio device gets a score per [`Gamepad`], from the strongest to
+/// the weakest evidence:
+///
+/// 1. the device is pinned to the [`Guid`] of the [`Gamepad`] with
+///    [`set_override`];
+/// 2. the device name contains the serial number of the [`Gamepad`];
+/// 3. the device name contains the name of the [`Gamepad`];
+/// 4. the device name contains a keyword of its [`ControllerType`], see
+///    [`set_keywords`].
+///
+/// Names are compared case-insensitively, ignoring everything but letters
+/// and digits. A [`Gamepad`] and a device are only matched if each one
+/// scores the other higher than any alternative, so two identical
+/// controllers without serial numbers stay undetermined instead of being
+/// matched at random.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{AudioIdentity, AudioMatcher, prelude::*};
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// let pad = |name: &str, serial: Option<&str>| {
+///     let mut identity =
+///         AudioIdentity::new(guid, name.to_owned(), ControllerType::Ps5);
+///     identity.serial = serial.map(str::to_owned);
+///     identity
+/// };
+/// let devices = |names: &[&str]| -> Vec<String> {
+///     names.iter().map(|&name| name.to_owned()).collect()
+/// };
+/// let mut matcher = AudioMatcher::new();
+///
+/// // by keyword of the controller type
+/// let speakers = devices(&[
+///     "Built-in Audio Analog Stereo",
+///     "DualSense Wireless Controller Analog Stereo",
+///     "HDMI Output",
+/// ]);
+/// let pads = [pad("PS5 Controller", None)];
+/// assert_eq!(matcher.assign(&pads, &speakers), [Some(1)]);
+///
+/// // identical controllers are told apart by serial number only
+/// let speakers = devices(&[
+///     "Speakers (Wireless Controller)",
+///     "Speakers (2- Wireless Controller)",
+/// ]);
+/// let twins = [pad("PS5 Controller", None), pad("PS5 Controller", None)];
+/// assert_eq!(matcher.assign(&twins, &speakers), [None, None]);
+/// let speakers = devices(&[
+///     "Wireless Controller a0:ab:51:00:00:01",
+///     "Wireless Controller a0:ab:51:00:00:02",
+/// ]);
+/// let twins = [
+///     pad("PS5 Controller", Some("A0AB51000002")),
+///     pad("PS5 Controller", Some("A0AB51000001")),
+/// ];
+/// assert_eq!(matcher.assign(&twins, &speakers), [Some(1), Some(0)]);
+///
+/// // unrelated devices are never matched
+/// let speakers = devices(&["Built-in Audio", "USB Headset"]);
+/// assert_eq!(matcher.assign(&pads, &speakers), [None]);
+///
+/// // custom keywords and overrides
+/// let speakers = devices(&["Gamepad Speaker", "Headset"]);
+/// assert_eq!(matcher.assign(&pads, &speakers), [None]);
+/// matcher.set_keywords(ControllerType::Ps5, vec!["Gamepad".to_owned()]);
+/// assert_eq!(matcher.assign(&pads, &speakers), [Some(0)]);
+/// matcher.set_override(guid, Some("Headset".to_owned()));
+/// assert_eq!(matcher.assign(&pads, &speakers), [Some(1)]);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::set_audio_matcher`]: crate::Girl::set_audio_matcher
+/// [`set_override`]: Self::set_override
+/// [`set_keywords`]: Self::set_keywords
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AudioMatcher {
+    /// Device name fragments per [`ControllerType`].
+    keywords: BTreeMap<ControllerType, Vec<String>>,
+    /// Device names pinned per [`Guid`].
+    overrides: BTreeMap<Guid, String>,
+}
+
+impl Default for AudioMatcher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioMatcher {
+    /// Score of a device name containing a [`ControllerType`] keyword.
+    const KEYWORD: u32 = 1;
+    /// Score of a device name containing the [`Gamepad`] name.
+    const NAME: u32 = 2;
+    /// Score of a device pinned with [`AudioMatcher::set_override`].
+    const OVERRIDE: u32 = 4;
+    /// Score of a device name containing the serial number.
+    const SERIAL: u32 = 3;
+
+    /// Creates an [`AudioMatcher`] with the built-in keywords of the
+    /// `PlayStation` controllers exposing audio devices, and no overrides.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        let mut matcher = Self::empty();
+        matcher.set_keywords(ControllerType::Ps4, vec![
+            "Wireless Controller".to_owned(),
+            "DUALSHOCK".to_owned(),
+        ]);
+        matcher.set_keywords(ControllerType::Ps5, vec![
+            "DualSense".to_owned(),
+            "Wireless Controller".to_owned(),
+        ]);
+        matcher
+    }
+
+    /// Creates an [`AudioMatcher`] without any keywords or overrides, only
+    /// matching by serial number and name.
+    #[must_use]
+    #[inline]
+    pub const fn empty() -> Self {
+        Self { keywords: BTreeMap::new(), overrides: BTreeMap::new() }
+    }
+
+    /// Sets the device name fragments identifying the audio devices of the
+    /// [`ControllerType`], replacing the previous ones.
+    #[inline]
+    pub fn set_keywords(
+        &mut self,
+        controller_type: ControllerType,
+        keywords: Vec<String>,
+    ) {
+        drop(self.keywords.insert(controller_type, keywords));
+    }
+
+    /// Pins the audio device with the exact `device` name to the [`Gamepad`]s
+    /// with the [`Guid`], or unpins it if [`None`].
+    ///
+    /// Identical controllers share a [`Guid`], so a pinned device stays
+    /// undetermined while several of them are connected.
+    #[inline]
+    pub fn set_override(&mut self, guid: Guid, device: Option<String>) {
+        drop(match device {
+            Some(name) => self.overrides.insert(guid, name),
+            None => self.overrides.remove(&guid),
+        });
+    }
+
+    /// Scores how likely the audio `device` belongs to the [`Gamepad`], `0`
+    /// meaning not at all.
+    #[must_use]
+    #[inline]
+    pub fn score(&self, gamepad: &AudioIdentity, device: &str) -> u32 {
+        if self.overrides.get(&gamepad.guid).is_some_and(|name| name == device)
+        {
+            return Self::OVERRIDE;
+        }
+        let haystack = normalize(device);
+        let contains = |text: &str| {
+            let needle = normalize(text);
+            !needle.is_empty() && haystack.contains(&needle)
+        };
+        if gamepad.serial.as_deref().is_some_and(contains) {
+            Self::SERIAL
+        } else if contains(&gamepad.name) {
+            Self::NAME
+        } else if self
+            .keywords
+            .get(&gamepad.controller_type)
+            .is_some_and(|keywords| keywords.iter().any(|word| contains(word)))
+        {
+            Self::KEYWORD
+        } else {
+            0
+        }
+    }
+
+    /// Matches each [`Gamepad`] with the index of its audio device in
+    /// `devices`, if determinable.
+    ///
+    /// A [`Gamepad`] and a device are matched if they score each other
+    /// strictly higher than any other device and [`Gamepad`] respectively.
+    #[must_use]
+    #[inline]
+    pub fn assign(
+        &self,
+        gamepads: &[AudioIdentity],
+        devices: &[String],
+    ) -> Vec<Option<usize>> {
+        let scores: Vec<Vec<u32>> = gamepads
+            .iter()
+            .map(|gamepad| {
+                devices
+                    .iter()
+                    .map(|device| self.score(gamepad, device))
+                    .collect()
+            })
+            .collect();
+        let column = |device: usize| {
+            scores.iter().map(move |row| row.get(device).copied().unwrap_or(0))
+        };
+        scores
+            .iter()
+            .enumerate()
+            .map(|(gamepad, row)| {
+                let device = unique_max(row.iter().copied())?;
+                (unique_max(column(device)) == Some(gamepad)).then_some(device)
+            })
+            .collect()
+    }
+}
+
+/// Gets the names of the audio output devices, empty unless the SDL2 audio
+/// subsystem is initialized.
+#[expect(clippy::single_call_fn, reason = "keeps the ffi in this module")]
+#[inline]
+pub(crate) fn output_devices() -> Vec<String> {
+    // SAFETY: SDL is alive, reports -1 if the audio subsystem isn't.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let count = unsafe { sdl2_sys::SDL_GetNumAudioDevices(0) };
+
+    (0..count).filter_map(output_device).collect()
+}
+
+/// Gets the name of the audio output device at the `index`.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn output_device(index: i32) -> Option<String> {
+    // SAFETY: SDL is alive, invalid indices are reported as null.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let ptr = unsafe { sdl2_sys::SDL_GetAudioDeviceName(index, 0) };
+    if ptr.is_null() {
+        return None;
+    }
+
+    // SAFETY: checked for null above, and copied before any other SDL call.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let name = unsafe { CStr::from_ptr(ptr) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// Lowercases the letters and digits of the `text`, dropping the rest.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Gets the index of the strictly greatest non-zero score, if any.
+fn unique_max<I: Iterator<Item = u32>>(scores: I) -> Option<usize> {
+    let mut best = None;
+    let mut best_score = 0;
+    let mut tied = false;
+    for (index, score) in scores.enumerate() {
+        match score.cmp(&best_score) {
+            Ordering::Greater => {
+                best = Some(index);
+                best_score = score;
+                tied = false;
+            }
+            Ordering::Equal => tied = true,
+            Ordering::Less => {}
+        }
+    }
+    best.filter(|_| !tied)
+}
@@ -0,0 +1,155 @@
+//! Pausing output while the application is in the background.
+
+#[cfg(feature = "sensors")]
+use crate::{GamepadId, Guid, Sensor};
+
+/// What [`Girl`] pauses while the application is in the background, see
+/// [`Girl::set_background_policy`].
+///
+/// Paused on [`Event::AppBackgrounded`] and restored on
+/// [`Event::AppForegrounded`], e.g. to save power on a handheld.
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::set_background_policy`]: crate::Girl::set_background_policy
+/// [`Event::AppBackgrounded`]: crate::Event::AppBackgrounded
+/// [`Event::AppForegrounded`]: crate::Event::AppForegrounded
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BackgroundPolicy {
+    /// Whether to stop the rumble of every [`Gamepad`], including trigger
+    /// rumble.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pub stop_rumble: bool,
+    /// Whether to pause LED animations, such as [`Gamepad::identify`],
+    /// restoring the previous LED color.
+    ///
+    /// [`Gamepad::identify`]: crate::Gamepad::identify
+    pub pause_leds: bool,
+    /// Whether to disable the enabled sensors, enabling them again in the
+    /// foreground. Requires the `sensors` feature.
+    pub disable_sensors: bool,
+}
+
+impl Default for BackgroundPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackgroundPolicy {
+    /// Creates a [`BackgroundPolicy`] pausing everything.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { stop_rumble: true, pause_leds: true, disable_sensors: true }
+    }
+
+    /// Creates a [`BackgroundPolicy`] pausing nothing, the default of
+    /// [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    #[must_use]
+    #[inline]
+    pub const fn disabled() -> Self {
+        Self { stop_rumble: false, pause_leds: false, disable_sensors: false }
+    }
+}
+
+/// Remembers the [`Sensor`]s disabled in the background, to enable them
+/// again in the foreground, see [`BackgroundPolicy::disable_sensors`].
+///
+/// Controllers may disconnect and reconnect with a new [`GamepadId`] while
+/// the application is in the background, so the [`Sensor`]s of a
+/// [`GamepadId`] no longer connected go to a reconnected controller with
+/// the same [`Guid`] instead.
+#[cfg(feature = "sensors")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SensorRestore {
+    /// Disabled [`Sensor`]s with the [`GamepadId`] and [`Guid`] of their
+    /// controller.
+    disabled: Vec<(GamepadId, Guid, Vec<Sensor>)>,
+}
+
+#[cfg(feature = "sensors")]
+impl SensorRestore {
+    /// Remembers the `sensors` disabled on the controller `id` with `guid`.
+    #[inline]
+    pub(crate) fn remember(
+        &mut self,
+        id: GamepadId,
+        guid: Guid,
+        sensors: Vec<Sensor>,
+    ) {
+        if !sensors.is_empty() {
+            self.disabled.push((id, guid, sensors));
+        }
+    }
+
+    /// Assigns the remembered [`Sensor`]s to the `connected` controllers,
+    /// forgetting all of them.
+    ///
+    /// [`Sensor`]s go to the controller with the same [`GamepadId`], or else
+    /// to a controller with the same [`Guid`] that got none yet. The ones of
+    /// controllers not connected anymore are dropped.
+    #[must_use]
+    #[inline]
+    pub(crate) fn assign(
+        &mut self,
+        connected: &[(GamepadId, Guid)],
+    ) -> Vec<(GamepadId, Vec<Sensor>)> {
+        let mut assigned: Vec<(GamepadId, Vec<Sensor>)> = Vec::new();
+        let mut orphans = Vec::new();
+        for (id, guid, sensors) in self.disabled.drain(..) {
+            if connected.iter().any(|&(connected, _)| connected == id) {
+                assigned.push((id, sensors));
+            } else {
+                orphans.push((guid, sensors));
+            }
+        }
+        for (guid, sensors) in orphans {
+            let free = connected.iter().find(|&&(id, connected)| {
+                connected == guid && !assigned.iter().any(|taken| taken.0 == id)
+            });
+            if let Some(&(id, _)) = free {
+                assigned.push((id, sensors));
+            }
+        }
+        assigned
+    }
+}
+
+/// Unit tests of [`SensorRestore`].
+#[cfg(test)]
+#[cfg(feature = "sensors")]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use super::SensorRestore;
+    use crate::{GamepadId, Guid, Sensor};
+
+    /// [`Sensor`]s go to a controller reconnected with another
+    /// [`GamepadId`] but the same [`Guid`].
+    #[test]
+    fn assigns_reconnected() {
+        let guid = Guid::from_bytes([1; 16]);
+        let (first, second) = (GamepadId::from_raw(1), GamepadId::from_raw(2));
+        let mut restore = SensorRestore::default();
+        restore.remember(first, guid, vec![Sensor::Gyroscope]);
+
+        let restored = restore.assign(&[(second, guid)]);
+        assert_eq!(restored, [(second, vec![Sensor::Gyroscope])], "restored");
+        assert!(restore.assign(&[(second, guid)]).is_empty(), "not forgotten");
+    }
+
+    /// [`Sensor`]s of controllers not reconnected are dropped.
+    #[test]
+    fn drops_disconnected() {
+        let guid = Guid::from_bytes([1; 16]);
+        let mut restore = SensorRestore::default();
+        restore.remember(GamepadId::from_raw(1), guid, vec![Sensor::Gyroscope]);
+
+        assert!(restore.assign(&[]).is_empty(), "restored");
+    }
+}
@@ -0,0 +1,263 @@
+//! How a [`Gamepad`] is connected, USB or Bluetooth, see
+//! [`Gamepad::connection_kind`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+//! [`Gamepad::connection_kind`]: crate::Gamepad::connection_kind
+
+use alloc::{borrow::Cow, collections::BTreeMap};
+use core::fmt;
+
+use crate::{Error, Guid, PowerLevel};
+
+/// Bus type SDL2 encodes in the first two bytes of a [`Guid`] for USB.
+const BUS_USB: u16 = 0x03;
+
+/// Bus type SDL2 encodes in the first two bytes of a [`Guid`] for
+/// Bluetooth.
+const BUS_BLUETOOTH: u16 = 0x05;
+
+/// USB vendor ID of Microsoft.
+const MICROSOFT_VENDOR: u16 = 0x045e;
+
+/// Product IDs Xbox controllers report over Bluetooth only, under
+/// [`MICROSOFT_VENDOR`].
+const BLUETOOTH_PRODUCTS: [u16; 7] =
+    [0x02e0, 0x02fd, 0x0b05, 0x0b13, 0x0b20, 0x0b21, 0x0b22];
+
+/// Lowercase name fragments of controllers connected over Bluetooth.
+const BLUETOOTH_NAMES: [&str; 1] = ["bluetooth"];
+
+/// How a [`Gamepad`] is connected, see [`Gamepad::connection_kind`].
+///
+/// Some features are limited over Bluetooth, e.g. trigger effects and
+/// high-rate sensors of a `DualSense`.
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::connection_kind`]: crate::Gamepad::connection_kind
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionKind {
+    /// Plugged in with a cable.
+    Usb,
+    /// Paired over Bluetooth.
+    Bluetooth,
+    /// Neither SDL2 nor the heuristics could tell.
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for ConnectionKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            Self::Usb => "USB",
+            Self::Bluetooth => "Bluetooth",
+            Self::Unknown => "unknown connection",
+        })
+    }
+}
+
+impl ConnectionKind {
+    /// Reads the [`ConnectionKind`] from the bus type SDL2 encodes in the
+    /// [`Guid`], if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ConnectionKind, Guid};
+    /// let usb: Guid = "030000004c050000e60c000000010000".parse()?;
+    /// assert_eq!(ConnectionKind::from_guid(usb), Some(ConnectionKind::Usb));
+    /// let bluetooth: Guid = "050000004c050000e60c000000810000".parse()?;
+    /// let kind = ConnectionKind::from_guid(bluetooth);
+    /// assert_eq!(kind, Some(ConnectionKind::Bluetooth));
+    /// assert_eq!(ConnectionKind::from_guid(Guid::default()), None);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    #[expect(
+        clippy::little_endian_bytes,
+        reason = "SDL2 stores the bus type little-endian"
+    )]
+    pub const fn from_guid(guid: Guid) -> Option<Self> {
+        let [b0, b1, ..] = *guid.as_bytes();
+        match u16::from_le_bytes([b0, b1]) {
+            BUS_USB => Some(Self::Usb),
+            BUS_BLUETOOTH => Some(Self::Bluetooth),
+            _ => None,
+        }
+    }
+
+    /// Attaches a hint to an [`Error::SdlError`] of an operation using
+    /// `feature` that it may be limited, if on [`Bluetooth`]. Other
+    /// [`Error`]s and connections are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ConnectionKind, ErrorCode, prelude::*};
+    /// let err = || Error::SdlError {
+    ///     op: "set_rumble_triggers",
+    ///     code: ErrorCode::Sdl,
+    ///     message: "Unsupported".into(),
+    /// };
+    ///
+    /// let hinted = ConnectionKind::Bluetooth.hint(err(), "trigger rumble");
+    /// assert_eq!(
+    ///     hinted.to_string(),
+    ///     "SDL error in set_rumble_triggers: Unsupported (this controller \
+    ///      is on Bluetooth; trigger rumble may be limited)",
+    /// );
+    ///
+    /// let plain = ConnectionKind::Usb.hint(err(), "trigger rumble");
+    /// assert_eq!(plain.to_string(), err().to_string());
+    /// let missing = Error::GamepadNotFound(GamepadId::from_raw(3));
+    /// let hinted = ConnectionKind::Bluetooth.hint(missing, "rumble");
+    /// assert!(matches!(hinted, Error::GamepadNotFound(_)));
+    /// ```
+    ///
+    /// [`Bluetooth`]: Self::Bluetooth
+    #[must_use]
+    #[inline]
+    pub fn hint(self, err: Error, feature: &'static str) -> Error {
+        if self == Self::Bluetooth
+            && let Error::SdlError { op, code, message } = err
+        {
+            let message = format!(
+                "{message} (this controller is on Bluetooth; {feature} may \
+                 be limited)"
+            );
+            Error::SdlError { op, code, message: Cow::Owned(message) }
+        } else {
+            err
+        }
+    }
+}
+
+/// Heuristics telling how a [`Gamepad`] is connected where SDL2 doesn't,
+/// see [`Gamepad::connection_kind`].
+///
+/// The [`ConnectionKind`] of a [`Gamepad`] is, in order:
+///
+/// 1. the one forced for its [`Guid`] with [`force`];
+/// 2. the bus type SDL2 encodes in its [`Guid`], see
+///    [`ConnectionKind::from_guid`];
+/// 3. [`Bluetooth`] if its name contains "Bluetooth", ignoring case;
+/// 4. [`Bluetooth`] if its [`Guid`] has the vendor and product IDs of an Xbox
+///    controller over Bluetooth, see [`Guid::vendor_product`];
+/// 5. [`Usb`] if its [`PowerLevel`] is [`PowerLevel::Wired`];
+/// 6. [`Unknown`] otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{ConnectionHeuristics, ConnectionKind, Guid, prelude::*};
+/// # use ConnectionKind::{Bluetooth, Unknown, Usb};
+/// let mut heuristics = ConnectionHeuristics::new();
+/// let guid = |guid: &str| guid.parse::<Guid>();
+/// let ps5 = "PS5 Controller";
+/// let wired = Some(PowerLevel::Wired);
+/// let battery = Some(PowerLevel::Full);
+///
+/// // the bus type SDL2 reports wins over the other heuristics
+/// let usb = guid("030000004c050000e60c000000010000")?;
+/// assert_eq!(heuristics.infer(usb, ps5, battery), Usb);
+/// let bluetooth = guid("050000004c050000e60c000000810000")?;
+/// assert_eq!(heuristics.infer(bluetooth, ps5, wired), Bluetooth);
+///
+/// // without a bus type
+/// let no_bus = guid("000000004c050000e60c000000010000")?;
+/// let name = "PS5 Controller (Bluetooth)";
+/// assert_eq!(heuristics.infer(no_bus, name, None), Bluetooth);
+/// let xbox = guid("000000005e040000e002000000000000")?;
+/// let name = "Xbox Wireless Controller";
+/// assert_eq!(heuristics.infer(xbox, name, battery), Bluetooth);
+/// assert_eq!(heuristics.infer(no_bus, ps5, wired), Usb);
+/// assert_eq!(heuristics.infer(no_bus, ps5, battery), Unknown);
+/// assert_eq!(heuristics.infer(Guid::default(), "", None), Unknown);
+///
+/// // forced kinds take precedence
+/// heuristics.force(usb, Bluetooth);
+/// assert_eq!(heuristics.infer(usb, ps5, wired), Bluetooth);
+/// assert_eq!(heuristics.unforce(usb), Some(Bluetooth));
+/// assert_eq!(heuristics.infer(usb, ps5, wired), Usb);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`force`]: Self::force
+/// [`Bluetooth`]: ConnectionKind::Bluetooth
+/// [`Usb`]: ConnectionKind::Usb
+/// [`Unknown`]: ConnectionKind::Unknown
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::connection_kind`]: crate::Gamepad::connection_kind
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionHeuristics {
+    /// Kinds forced per [`Guid`].
+    forced: BTreeMap<Guid, ConnectionKind>,
+}
+
+impl ConnectionHeuristics {
+    /// Creates [`ConnectionHeuristics`] without forced kinds.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { forced: BTreeMap::new() }
+    }
+
+    /// Forces the [`ConnectionKind`] of [`Gamepad`]s with the [`Guid`],
+    /// e.g. for a controller the heuristics misjudge.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn force(&mut self, guid: Guid, kind: ConnectionKind) {
+        _ = self.forced.insert(guid, kind);
+    }
+
+    /// Removes the [`ConnectionKind`] forced for the [`Guid`], returning
+    /// it.
+    #[inline]
+    pub fn unforce(&mut self, guid: Guid) -> Option<ConnectionKind> {
+        self.forced.remove(&guid)
+    }
+
+    /// Gets the [`ConnectionKind`] forced for the [`Guid`], if any.
+    #[must_use]
+    #[inline]
+    pub fn forced(&self, guid: Guid) -> Option<ConnectionKind> {
+        self.forced.get(&guid).copied()
+    }
+
+    /// Infers the [`ConnectionKind`] of the controller with the [`Guid`],
+    /// name and [`PowerLevel`], if known.
+    #[must_use]
+    #[inline]
+    pub fn infer(
+        &self,
+        guid: Guid,
+        name: &str,
+        power: Option<PowerLevel>,
+    ) -> ConnectionKind {
+        if let Some(kind) =
+            self.forced(guid).or_else(|| ConnectionKind::from_guid(guid))
+        {
+            return kind;
+        }
+        let name = name.to_lowercase();
+        let bluetooth_name =
+            BLUETOOTH_NAMES.iter().any(|fragment| name.contains(fragment));
+        let bluetooth_id =
+            guid.vendor_product().is_some_and(|(vendor, product)| {
+                vendor == MICROSOFT_VENDOR
+                    && BLUETOOTH_PRODUCTS.contains(&product)
+            });
+        if bluetooth_name || bluetooth_id {
+            ConnectionKind::Bluetooth
+        } else if power == Some(PowerLevel::Wired) {
+            ConnectionKind::Usb
+        } else {
+            ConnectionKind::Unknown
+        }
+    }
+}
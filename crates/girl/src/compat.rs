@@ -0,0 +1,727 @@
+//! Translation of SDL2 events into [`Event`]s for applications running their
+//! own SDL2 event loop.
+//!
+//! [`Girl`] uses the same [`Translator`] internally, so feeding it the SDL2
+//! events of an existing loop yields the same [`Event`]s as [`Girl::event`].
+//!
+//! # Examples
+//!
+//! The [`Event`]s of [`Girl`] match the ones of a standalone [`Translator`]
+//! fed the same SDL2 events:
+//!
+//! ```
+//! # use sdl2::{controller::{Axis, Button}, event::Event as SdlEvent};
+//! # use girl::{Girl, compat::Translator};
+//! let mut girl = Girl::new()?;
+//! let sdl = sdl2::init().map_err(girl::Error::Sdl2Init)?;
+//! let events = sdl.event().map_err(girl::Error::Sdl2Init)?;
+//!
+//! let axis = |axis, value| SdlEvent::ControllerAxisMotion {
+//!     timestamp: 0,
+//!     which: 0,
+//!     axis,
+//!     value,
+//! };
+//! let script = [
+//!     axis(Axis::LeftX, 20_000),
+//!     axis(Axis::LeftY, -20_000),
+//!     axis(Axis::RightY, 12_000),
+//!     axis(Axis::TriggerLeft, 30_000),
+//!     SdlEvent::ControllerButtonDown {
+//!         timestamp: 0,
+//!         which: 0,
+//!         button: Button::A,
+//!     },
+//!     axis(Axis::LeftX, 0),
+//! ];
+//! for event in &script {
+//!     events.push_event(event.clone()).unwrap();
+//! }
+//!
+//! let mut translator = Translator::new();
+//! let expected: Vec<_> =
+//!     script.iter().flat_map(|event| translator.feed(event)).collect();
+//! let actual: Vec<_> = std::iter::from_fn(|| girl.event()).collect();
+//! assert_eq!(format!("{actual:?}"), format!("{expected:?}"));
+//! # Ok::<(), girl::Error>(())
+//! ```
+//!
+//! [`Girl`]: crate::Girl
+//! [`Girl::event`]: crate::Girl::event
+
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "sdl2-interop")]
+use core::{error, fmt};
+use std::time::Instant;
+
+use sdl2::{
+    controller::Axis as SdlAxis, event::Event as SdlEvent, sys as sdl2_sys,
+};
+use smallvec::SmallVec;
+
+use crate::{
+    Button, Event, EventLevel, Gamepad, Hotplug, Stick,
+    dedup::HotplugDedup,
+    gamepadmanager::device_instance_id,
+    normalize::{self, apply_deadzone},
+};
+
+/// Stateful translator of SDL2 events into [`Event`]s.
+///
+/// Unlike [`translate`], it:
+///
+/// - caches stick axes, so [`Event::ControllerStickMotion`] reports the full
+///   `[x, y]` position of the stick instead of only the moved axis;
+/// - translates joystick-level hotplug events of game controllers into the
+///   controller-level ones, merging the pair SDL2 reports for the same
+///   controller within a second, and drops hotplug events of plain joysticks;
+/// - tracks the [`Button`]s held on each controller, see [`held`] and
+///   [`set_button_state`];
+/// - optionally coalesces the motion of a stick reported at the same timestamp,
+///   see [`set_coalescing`].
+///
+/// Must only be fed SDL2 events while SDL2 is initialized, as hotplug
+/// events are resolved with SDL2 itself.
+///
+/// # Examples
+///
+/// ```
+/// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+/// # use girl::{Event, Stick, compat::Translator};
+/// let axis = |axis, value| SdlEvent::ControllerAxisMotion {
+///     timestamp: 0,
+///     which: 0,
+///     axis,
+///     value,
+/// };
+/// let mut translator = Translator::new();
+///
+/// _ = translator.feed(&axis(Axis::LeftX, i16::MAX));
+/// let Some(Event::ControllerStickMotion { stick, offset, .. }) =
+///     translator.feed(&axis(Axis::LeftY, i16::MAX)).pop()
+/// else {
+///     unreachable!();
+/// };
+/// assert_eq!(stick, Stick::Left);
+/// assert_eq!(offset, [1.0, 1.0]);
+/// ```
+///
+/// Modifiers of button presses, in the order of the batch:
+///
+/// ```
+/// # use sdl2::{controller::Button as SdlButton, event::Event as SdlEvent};
+/// # use girl::{Button, Event, compat::Translator};
+/// let down = |which, button| SdlEvent::ControllerButtonDown {
+///     timestamp: 0,
+///     which,
+///     button,
+/// };
+/// let up = |which, button| SdlEvent::ControllerButtonUp {
+///     timestamp: 0,
+///     which,
+///     button,
+/// };
+/// let mut translator = Translator::new();
+/// translator.set_button_state(true);
+///
+/// let batch = [
+///     down(0, SdlButton::LeftShoulder),
+///     down(1, SdlButton::X),
+///     down(0, SdlButton::Y),
+///     up(0, SdlButton::LeftShoulder),
+///     down(0, SdlButton::A),
+///     down(0, SdlButton::LeftShoulder),
+/// ];
+/// let held: Vec<_> = batch
+///     .iter()
+///     .flat_map(|event| translator.feed(event))
+///     .filter_map(|event| match event {
+///         Event::ControllerButtonDownWithState {
+///             which: 0,
+///             button,
+///             held,
+///         } => Some((button, held)),
+///         _ => None,
+///     })
+///     .collect();
+/// assert_eq!(held, [
+///     (Button::LeftShoulder, Button::empty()),
+///     (Button::Y, Button::LeftShoulder),
+///     (Button::A, Button::Y),
+///     (Button::LeftShoulder, Button::Y | Button::A),
+/// ]);
+/// let held_now = Button::Y | Button::A | Button::LeftShoulder;
+/// assert_eq!(translator.held(0), held_now);
+/// assert_eq!(translator.held(1), Button::X);
+///
+/// // plain events unless enabled, the tracking goes on
+/// translator.set_button_state(false);
+/// assert!(matches!(
+///     translator.feed(&down(1, SdlButton::B))[..],
+///     [Event::ControllerButtonDown { which: 1, button: Button::B }]
+/// ));
+/// assert_eq!(translator.held(1), Button::X | Button::B);
+/// ```
+///
+/// [`held`]: Self::held
+/// [`set_button_state`]: Self::set_button_state
+/// [`set_coalescing`]: Self::set_coalescing
+#[derive(Debug, Clone, Default)]
+pub struct Translator {
+    /// Last `[x, y]` offsets per instance ID and [`Stick`].
+    sticks: BTreeMap<(u32, Stick), [f64; 2]>,
+    /// Merges hotplug events reported at both [`EventLevel`]s.
+    hotplug: HotplugDedup,
+    /// Instance IDs of connected game controllers seen in hotplug events,
+    /// to tell them apart from plain joysticks once they're removed.
+    controllers: BTreeSet<u32>,
+    /// [`Button`]s held per instance ID.
+    buttons: BTreeMap<u32, Button>,
+    /// Whether presses are reported as
+    /// [`Event::ControllerButtonDownWithState`].
+    button_state: bool,
+    /// Whether stick motion reported at the same timestamp is coalesced.
+    coalescing: bool,
+    /// Stick motion held back for coalescing, with the timestamp of its
+    /// SDL2 event.
+    held_motion: Option<(u32, Event)>,
+}
+
+impl Translator {
+    /// Creates a new [`Translator`] with no cached state.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets all cached state, keeping the settings.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self {
+            button_state: self.button_state,
+            coalescing: self.coalescing,
+            ..Self::new()
+        };
+    }
+
+    /// Sets whether button presses are reported as
+    /// [`Event::ControllerButtonDownWithState`] instead of
+    /// [`Event::ControllerButtonDown`]. Disabled by default.
+    #[inline]
+    pub const fn set_button_state(&mut self, enabled: bool) {
+        self.button_state = enabled;
+    }
+
+    /// Checks if button presses are reported as
+    /// [`Event::ControllerButtonDownWithState`], see [`set_button_state`].
+    ///
+    /// [`set_button_state`]: Self::set_button_state
+    #[must_use]
+    #[inline]
+    pub const fn button_state(&self) -> bool {
+        self.button_state
+    }
+
+    /// Sets whether the motion of a stick reported at the same timestamp is
+    /// coalesced into a single [`Event::ControllerStickMotion`] with the
+    /// final `[x, y]` position, instead of one per moved axis. Disabled by
+    /// default.
+    ///
+    /// The motion is held back until an SDL2 event other than motion of the
+    /// same stick at the same timestamp is fed, so [`flush`] must be called
+    /// once the SDL2 event queue is drained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+    /// # use girl::{Event, compat::Translator};
+    /// let axis = |timestamp, axis, value| SdlEvent::ControllerAxisMotion {
+    ///     timestamp,
+    ///     which: 0,
+    ///     axis,
+    ///     value,
+    /// };
+    /// let mut translator = Translator::new();
+    /// translator.set_coalescing(true);
+    ///
+    /// assert!(translator.feed(&axis(1, Axis::LeftX, i16::MAX)).is_empty());
+    /// assert!(translator.feed(&axis(1, Axis::LeftY, i16::MAX)).is_empty());
+    /// // a later report emits the coalesced motion first
+    /// let events = translator.feed(&axis(2, Axis::TriggerLeft, i16::MAX));
+    /// let [
+    ///     Event::ControllerStickMotion { offset, .. },
+    ///     Event::ControllerTriggerMotion { .. },
+    /// ] = events[..]
+    /// else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(offset, [1.0, 1.0]);
+    ///
+    /// assert!(translator.feed(&axis(3, Axis::RightX, i16::MAX)).is_empty());
+    /// let flushed = translator.flush();
+    /// let Some(Event::ControllerStickMotion { offset, .. }) = flushed else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(offset, [1.0, 0.0]);
+    /// assert!(translator.flush().is_none());
+    /// ```
+    ///
+    /// [`flush`]: Self::flush
+    #[inline]
+    pub const fn set_coalescing(&mut self, enabled: bool) {
+        self.coalescing = enabled;
+    }
+
+    /// Checks if the motion of a stick reported at the same timestamp is
+    /// coalesced, see [`set_coalescing`].
+    ///
+    /// [`set_coalescing`]: Self::set_coalescing
+    #[must_use]
+    #[inline]
+    pub const fn coalescing(&self) -> bool {
+        self.coalescing
+    }
+
+    /// Takes the stick motion held back for coalescing, see
+    /// [`set_coalescing`].
+    ///
+    /// [`set_coalescing`]: Self::set_coalescing
+    #[inline]
+    pub fn flush(&mut self) -> Option<Event> {
+        self.flush_timed().map(|(_, event)| event)
+    }
+
+    /// Takes the stick motion held back for coalescing along with the
+    /// timestamp of its SDL2 event.
+    #[inline]
+    pub(crate) const fn flush_timed(&mut self) -> Option<(u32, Event)> {
+        self.held_motion.take()
+    }
+
+    /// Gets the [`Button`]s held on the controller with the instance ID, as
+    /// of the last SDL2 event fed.
+    #[must_use]
+    #[cfg_attr(
+        not(feature = "sdl2-interop"),
+        expect(dead_code, reason = "only public with `sdl2-interop`")
+    )]
+    #[inline]
+    pub fn held(&self, which: u32) -> Button {
+        self.buttons.get(&which).copied().unwrap_or_default()
+    }
+
+    /// Translates the next SDL2 event, updating the cached state.
+    ///
+    /// Returns no [`Event`]s if the SDL2 event has no [`Event`] counterpart,
+    /// is a duplicate hotplug event or stick motion held back for
+    /// coalescing. Returns two if it ends the coalescing of a stick motion,
+    /// the coalesced motion first, see [`set_coalescing`].
+    ///
+    /// [`set_coalescing`]: Self::set_coalescing
+    #[inline]
+    pub fn feed(&mut self, sdl_event: &SdlEvent) -> SmallVec<[Event; 2]> {
+        let mut events = SmallVec::new();
+        let event = self.translate_one(sdl_event);
+        if self.coalescing
+            && let Some(
+                motion @ Event::ControllerStickMotion { which, stick, .. },
+            ) = event
+        {
+            let timestamp = sdl_event.get_timestamp();
+            let merged = matches!(
+                self.held_motion,
+                Some((held_at, Event::ControllerStickMotion {
+                    which: held_which,
+                    stick: held_stick,
+                    ..
+                })) if held_at == timestamp
+                    && held_which == which
+                    && held_stick == stick
+            );
+            if !merged {
+                events.extend(self.flush());
+            }
+            self.held_motion = Some((timestamp, motion));
+            return events;
+        }
+        events.extend(self.flush());
+        events.extend(event);
+        events
+    }
+
+    /// Translates the next SDL2 event into at most one [`Event`], see
+    /// [`feed`].
+    ///
+    /// [`feed`]: Self::feed
+    fn translate_one(&mut self, sdl_event: &SdlEvent) -> Option<Event> {
+        if let SdlEvent::ControllerAxisMotion {
+            timestamp: _,
+            which,
+            axis,
+            value,
+        } = *sdl_event
+        {
+            return self
+                .stick_motion(which, axis, value)
+                .or_else(|| translate(sdl_event));
+        }
+        if let SdlEvent::ControllerButtonDown { which, button, .. } = *sdl_event
+        {
+            return Some(self.button(which, Button::from_sdl(button), true));
+        }
+        if let SdlEvent::ControllerButtonUp { which, button, .. } = *sdl_event {
+            return Some(self.button(which, Button::from_sdl(button), false));
+        }
+        let hotplug = if let SdlEvent::JoyDeviceAdded { which, .. } = *sdl_event
+        {
+            Some((Hotplug::Added, which, EventLevel::Joystick))
+        } else if let SdlEvent::JoyDeviceRemoved { which, .. } = *sdl_event {
+            Some((Hotplug::Removed, which, EventLevel::Joystick))
+        } else if let SdlEvent::ControllerDeviceAdded { which, .. } = *sdl_event
+        {
+            Some((Hotplug::Added, which, EventLevel::Controller))
+        } else if let SdlEvent::ControllerDeviceRemoved { which, .. } =
+            *sdl_event
+        {
+            Some((Hotplug::Removed, which, EventLevel::Controller))
+        } else {
+            None
+        };
+        match hotplug {
+            Some((kind, which, level)) => self.hotplug(kind, which, level),
+            None => translate(sdl_event),
+        }
+    }
+
+    /// Updates the cached state from an [`Event`] that didn't come from an
+    /// SDL2 event, e.g. one injected with [`Girl::inject`], as if its SDL2
+    /// counterpart was fed.
+    ///
+    /// Button presses are reported like the ones fed, see
+    /// [`set_button_state`], stick motion replaces the cached offsets and
+    /// removals forget the controller. Other [`Event`]s are returned
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+    /// # use girl::{Button, Event, Stick, compat::Translator};
+    /// let mut translator = Translator::new();
+    /// translator.set_button_state(true);
+    ///
+    /// let down = |button| Event::ControllerButtonDown { which: 0, button };
+    /// _ = translator.observe(down(Button::LeftShoulder));
+    /// assert!(matches!(
+    ///     translator.observe(down(Button::Y)),
+    ///     Event::ControllerButtonDownWithState {
+    ///         which: 0,
+    ///         button: Button::Y,
+    ///         held: Button::LeftShoulder,
+    ///     }
+    /// ));
+    /// assert_eq!(translator.held(0), Button::LeftShoulder | Button::Y);
+    ///
+    /// let stick = Stick::Left;
+    /// let offset = [0.5, -0.5];
+    /// let moved = Event::ControllerStickMotion { which: 0, stick, offset };
+    /// _ = translator.observe(moved);
+    /// let Some(Event::ControllerStickMotion { offset, .. }) = translator
+    ///     .feed(&SdlEvent::ControllerAxisMotion {
+    ///         timestamp: 0,
+    ///         which: 0,
+    ///         axis: Axis::LeftX,
+    ///         value: 0,
+    ///     })
+    ///     .pop()
+    /// else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(offset, [0.0, -0.5]);
+    ///
+    /// _ = translator.observe(Event::ControllerDeviceRemoved { which: 0 });
+    /// assert_eq!(translator.held(0), Button::empty());
+    /// ```
+    ///
+    /// [`Girl::inject`]: crate::Girl::inject
+    /// [`set_button_state`]: Self::set_button_state
+    #[inline]
+    pub fn observe(&mut self, event: Event) -> Event {
+        if let Event::ControllerButtonDown { which, button }
+        | Event::ControllerButtonDownWithState { which, button, .. } = event
+        {
+            return self.button(which, button, true);
+        }
+        if let Event::ControllerButtonUp { which, button } = event {
+            return self.button(which, button, false);
+        }
+        if let Event::ControllerStickMotion { which, stick, offset } = event {
+            _ = self.sticks.insert((which, stick), offset);
+        }
+        if let Event::ControllerDeviceRemoved { which } = event {
+            _ = self.forget(which);
+        }
+        event
+    }
+
+    /// Records a press or release of the `button`, translating it into an
+    /// [`Event`].
+    #[inline]
+    pub(crate) fn button(
+        &mut self,
+        which: u32,
+        button: Button,
+        pressed: bool,
+    ) -> Event {
+        let buttons = self.buttons.entry(which).or_default();
+        let held = buttons.difference(button);
+        buttons.set(button, pressed);
+        if !pressed {
+            Event::ControllerButtonUp { which, button }
+        } else if self.button_state {
+            Event::ControllerButtonDownWithState { which, button, held }
+        } else {
+            Event::ControllerButtonDown { which, button }
+        }
+    }
+
+    /// Forgets the cached state of the controller with the instance ID,
+    /// returning whether it was a known game controller.
+    fn forget(&mut self, which: u32) -> bool {
+        self.sticks.retain(|&(id, _), _| id != which);
+        _ = self.buttons.remove(&which);
+        self.controllers.remove(&which)
+    }
+
+    /// Updates the cached offset of the stick moved along the `axis`.
+    ///
+    /// Returns [`None`] if the `axis` is not a stick axis.
+    fn stick_motion(
+        &mut self,
+        which: u32,
+        axis: SdlAxis,
+        value: i16,
+    ) -> Option<Event> {
+        let (stick, component) = match axis {
+            SdlAxis::LeftX => (Stick::Left, 0),
+            SdlAxis::LeftY => (Stick::Left, 1),
+            SdlAxis::RightX => (Stick::Right, 0),
+            SdlAxis::RightY => (Stick::Right, 1),
+            SdlAxis::TriggerLeft | SdlAxis::TriggerRight => return None,
+        };
+        let offset = self.sticks.entry((which, stick)).or_default();
+        if let Some(slot) = offset.get_mut(component) {
+            *slot =
+                apply_deadzone(normalize::axis(value), Gamepad::STICK_DEADZONE);
+        }
+        Some(Event::ControllerStickMotion { which, stick, offset: *offset })
+    }
+
+    /// Translates a hotplug event reported at the `level`.
+    fn hotplug(
+        &mut self,
+        kind: Hotplug,
+        which: u32,
+        level: EventLevel,
+    ) -> Option<Event> {
+        let is_controller = match kind {
+            Hotplug::Added => {
+                let is_controller = level == EventLevel::Controller
+                    || is_game_controller(which);
+                if is_controller && let Some(id) = device_instance_id(which) {
+                    _ = self.controllers.insert(id.raw());
+                }
+                is_controller
+            }
+            Hotplug::Removed => {
+                self.forget(which) || level == EventLevel::Controller
+            }
+        };
+        if !is_controller
+            || !self.hotplug.observe(kind, which, level, Instant::now())
+        {
+            return None;
+        }
+
+        Some(match kind {
+            Hotplug::Added => Event::ControllerDeviceAdded { which },
+            Hotplug::Removed => Event::ControllerDeviceRemoved { which },
+        })
+    }
+}
+
+/// Error converting an SDL2 event that has no [`Event`] counterpart, i.e.
+/// is not relevant to game controllers, with `Event::try_from`.
+#[cfg(feature = "sdl2-interop")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[expect(clippy::exhaustive_structs, reason = "marker error")]
+pub struct NotGamepadEvent;
+
+#[cfg(feature = "sdl2-interop")]
+impl fmt::Display for NotGamepadEvent {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not a gamepad-relevant event")
+    }
+}
+
+#[cfg(feature = "sdl2-interop")]
+impl error::Error for NotGamepadEvent {}
+
+/// Translates a single SDL2 event like [`translate`], for generic code
+/// routing events with `?` and `try_into`.
+///
+/// # Examples
+///
+/// ```
+/// # use sdl2::{
+/// #     controller::{Axis, Button as SdlButton},
+/// #     event::Event as SdlEvent,
+/// # };
+/// # use girl::{Button, Event, Stick, Trigger, compat::NotGamepadEvent};
+/// let axis = |axis, value| SdlEvent::ControllerAxisMotion {
+///     timestamp: 0,
+///     which: 1,
+///     axis,
+///     value,
+/// };
+///
+/// let stick = Event::try_from(&axis(Axis::LeftX, i16::MAX))?;
+/// let Event::ControllerStickMotion { which: 1, stick, offset } = stick else {
+///     unreachable!();
+/// };
+/// assert_eq!((stick, offset), (Stick::Left, [1.0, 0.0]));
+///
+/// let trigger = Event::try_from(&axis(Axis::TriggerRight, i16::MAX))?;
+/// let Event::ControllerTriggerMotion { trigger, offset, .. } = trigger else {
+///     unreachable!();
+/// };
+/// assert_eq!((trigger, offset), (Trigger::Right, 1.0));
+///
+/// let button = SdlButton::A;
+/// let down =
+///     SdlEvent::ControllerButtonDown { timestamp: 0, which: 1, button };
+/// assert!(matches!(Event::try_from(&down)?, Event::ControllerButtonDown {
+///     which: 1,
+///     button: Button::A
+/// }));
+///
+/// let added = SdlEvent::ControllerDeviceAdded { timestamp: 0, which: 1 };
+/// let added = Event::try_from(&added)?;
+/// assert!(matches!(added, Event::ControllerDeviceAdded { which: 1 }));
+///
+/// # #[cfg(feature = "touchpad")]
+/// # {
+/// let touch = SdlEvent::ControllerTouchpadDown {
+///     timestamp: 0,
+///     which: 1,
+///     touchpad: 0,
+///     finger: 0,
+///     x: 0.5,
+///     y: 0.25,
+///     pressure: 1.0,
+/// };
+/// let touch = girl::TouchpadEvent::try_from(Event::try_from(&touch)?);
+/// assert_eq!(touch.ok().map(|touch| touch.position), Some([0.5, 0.25]));
+/// # }
+///
+/// # #[cfg(feature = "sensors")]
+/// # {
+/// let gyro = SdlEvent::ControllerSensorUpdated {
+///     timestamp: 0,
+///     which: 1,
+///     sensor: sdl2::sensor::SensorType::Gyroscope,
+///     data: [0.0, 1.0, 0.0],
+/// };
+/// let Event::ControllerSensorUpdated { sensor, .. } = Event::try_from(&gyro)?
+/// else {
+///     unreachable!();
+/// };
+/// assert_eq!(sensor, girl::Sensor::Gyroscope);
+/// # }
+///
+/// let clipboard = SdlEvent::ClipboardUpdate { timestamp: 0 };
+/// assert!(matches!(Event::try_from(&clipboard), Err(NotGamepadEvent)));
+///
+/// // the most negative raw values stay in range
+/// let sticks = [
+///     (Axis::LeftX, [-1.0, 0.0]),
+///     (Axis::LeftY, [0.0, -1.0]),
+///     (Axis::RightX, [-1.0, 0.0]),
+///     (Axis::RightY, [0.0, -1.0]),
+/// ];
+/// for (moved, expected) in sticks {
+///     let Event::ControllerStickMotion { offset, .. } =
+///         Event::try_from(&axis(moved, i16::MIN))?
+///     else {
+///         unreachable!();
+///     };
+///     assert_eq!(offset, expected);
+/// }
+/// for moved in [Axis::TriggerLeft, Axis::TriggerRight] {
+///     let Event::ControllerTriggerMotion { offset, .. } =
+///         Event::try_from(&axis(moved, i16::MIN))?
+///     else {
+///         unreachable!();
+///     };
+///     assert_eq!(offset, 0.0);
+/// }
+/// # Ok::<(), NotGamepadEvent>(())
+/// ```
+#[cfg(feature = "sdl2-interop")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2-interop")))]
+impl TryFrom<&SdlEvent> for Event {
+    type Error = NotGamepadEvent;
+
+    #[inline]
+    fn try_from(sdl_event: &SdlEvent) -> Result<Self, Self::Error> {
+        Self::from_sdl(sdl_event).ok_or(NotGamepadEvent)
+    }
+}
+
+/// Translates a single SDL2 event into an [`Event`] without any state.
+///
+/// Stick motion reports only the moved axis, the other one is `0.0`, and
+/// hotplug events are passed through as is, see [`Translator`] for full
+/// stick positions and hotplug deduplication.
+///
+/// # Examples
+///
+/// ```
+/// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+/// # use girl::{Event, compat::translate};
+/// let event = SdlEvent::ControllerAxisMotion {
+///     timestamp: 0,
+///     which: 0,
+///     axis: Axis::RightY,
+///     value: i16::MAX,
+/// };
+/// let Some(Event::ControllerStickMotion { offset, .. }) = translate(&event)
+/// else {
+///     unreachable!();
+/// };
+/// assert_eq!(offset, [0.0, 1.0]);
+///
+/// assert!(translate(&SdlEvent::ClipboardUpdate { timestamp: 0 }).is_none());
+/// ```
+#[must_use]
+#[inline]
+pub fn translate(sdl_event: &SdlEvent) -> Option<Event> {
+    Event::from_sdl(sdl_event)
+}
+
+/// Checks if the joystick at the device `index` is a game controller.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn is_game_controller(index: u32) -> bool {
+    let Ok(index) = i32::try_from(index) else {
+        return false;
+    };
+
+    // SAFETY: SDL is alive, invalid indices are reported as not a controller.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let is_controller = unsafe { sdl2_sys::SDL_IsGameController(index) };
+
+    is_controller == sdl2_sys::SDL_bool::SDL_TRUE
+}
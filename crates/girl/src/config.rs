@@ -0,0 +1,282 @@
+//! Loading saved configurations of older versions, e.g. [`InputProfile`]s
+//! saved before a [`Button`] was renamed.
+//!
+//! [`Button`]s are saved by name, as the names of their flags joined with
+//! `" | "`, e.g. `"A | B"`, never as raw bits, so adding [`Button`]s keeps
+//! older files loading. Renamed [`Button`]s are translated by the
+//! [`MIGRATIONS`] of the versions after the one a file was saved with.
+//!
+//! # Examples
+//!
+//! ```
+//! # use girl::{config, prelude::*};
+//! let v1 = include_str!(concat!(
+//!     env!("CARGO_MANIFEST_DIR"),
+//!     "/tests/golden/profile_v1.json",
+//! ));
+//!
+//! let profile = config::load_profile(v1)?;
+//! assert_eq!(profile.version, config::VERSION);
+//! assert!(profile.left_stick.invert_y);
+//! assert_eq!(profile.right_trigger.threshold, 0.2);
+//! // `Share` was renamed to `Misc1` in version 2
+//! assert_eq!(profile.remap, [
+//!     (Button::Misc1, Button::A),
+//!     (Button::Paddle1, Button::B),
+//! ]);
+//! assert_eq!(profile.button_axes, [(
+//!     Button::Touchpad,
+//!     ButtonAxis::Trigger(Trigger::Left),
+//! )]);
+//!
+//! // saved again with the current version and names
+//! let saved = serde_json::to_string(&profile).unwrap();
+//! assert_eq!(config::load_profile(&saved)?, profile);
+//! # Ok::<(), config::ConfigError>(())
+//! ```
+//!
+//! [`InputProfile`]: crate::InputProfile
+//! [`Button`]: crate::Button
+
+use core::fmt;
+
+use serde_json::Value;
+
+use crate::{Button, InputProfile};
+
+/// Current version of saved configurations, see [`InputProfile::version`].
+///
+/// [`InputProfile::version`]: crate::InputProfile::version
+pub const VERSION: u32 = crate::gamepad::profile::PROFILE_VERSION;
+
+/// [`Button`] renames of every version, applied in order to files saved
+/// with an older one.
+///
+/// [`Button`]: crate::Button
+pub const MIGRATIONS: &[Migration] =
+    &[Migration { to: 2, renames: &[("Share", "Misc1")] }];
+
+/// Changes of the [`Button`] names in a version, see [`MIGRATIONS`].
+///
+/// [`Button`]: crate::Button
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// Version introducing the changes.
+    pub to: u32,
+    /// Old and new names of the renamed [`Button`]s.
+    ///
+    /// [`Button`]: crate::Button
+    pub renames: &'static [(&'static str, &'static str)],
+}
+
+impl Migration {
+    /// Translates an old [`Button`] name, returning it as is if it wasn't
+    /// renamed.
+    ///
+    /// [`Button`]: crate::Button
+    #[must_use]
+    #[inline]
+    pub fn rename<'name>(&self, name: &'name str) -> &'name str {
+        self.renames
+            .iter()
+            .find(|&&(old, _)| old == name)
+            .map_or(name, |&(_, new)| new)
+    }
+}
+
+/// Error loading a saved configuration.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A [`Button`] name is unknown in the version the file was saved
+    /// with.
+    ///
+    /// [`Button`]: crate::Button
+    UnknownButton {
+        /// The unknown name, as written in the file.
+        name: String,
+        /// Version the file was saved with.
+        version: u32,
+    },
+    /// The file was saved with a version this one can't load, e.g. a newer
+    /// one.
+    UnsupportedVersion(u32),
+    /// The file isn't valid JSON, or doesn't match the format.
+    Json(serde_json::Error),
+}
+
+/// Renders a human-readable description of the [`ConfigError`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::config::{self, ConfigError};
+/// let saved = r#"{ "version": 1, "remap": [["Shar", "A"]] }"#;
+/// let err = config::load_profile(saved).unwrap_err();
+/// assert!(matches!(
+///     &err,
+///     ConfigError::UnknownButton { name, version: 1 } if name == "Shar",
+/// ));
+/// assert_eq!(err.to_string(), "unknown button `Shar` in version 1 config");
+/// ```
+impl fmt::Display for ConfigError {
+    #[inline]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binds the name and error by reference"
+    )]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownButton { name, version } => {
+                write!(f, "unknown button `{name}` in version {version} config")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported config version {version}")
+            }
+            Self::Json(err) => write!(f, "invalid config: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ConfigError {
+    #[inline]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binds the error by reference"
+    )]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Json(err) => Some(err),
+            Self::UnknownButton { .. } | Self::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Migrates a saved [`InputProfile`] from `from_version` to [`VERSION`],
+/// renaming its [`Button`]s and updating its `version`.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::UnknownButton`] if a [`Button`] name is unknown
+/// in `from_version`, and [`ConfigError::UnsupportedVersion`] if
+/// `from_version` is `0` or newer than [`VERSION`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::config::{self, ConfigError};
+/// let saved = serde_json::json!({ "remap": [["Share | Back", "A"]] });
+/// let migrated = config::migrate(saved, 1)?;
+/// assert_eq!(
+///     migrated,
+///     serde_json::json!({
+///         "version": config::VERSION,
+///         "remap": [["Misc1 | Back", "A"]],
+///     }),
+/// );
+///
+/// let newer = serde_json::json!({});
+/// assert!(matches!(
+///     config::migrate(newer, config::VERSION + 1),
+///     Err(ConfigError::UnsupportedVersion(_)),
+/// ));
+/// # Ok::<(), ConfigError>(())
+/// ```
+///
+/// [`InputProfile`]: crate::InputProfile
+/// [`Button`]: crate::Button
+#[inline]
+#[expect(
+    clippy::pattern_type_mismatch,
+    reason = "rewrites the button names in place"
+)]
+pub fn migrate(
+    mut value: Value,
+    from_version: u32,
+) -> Result<Value, ConfigError> {
+    if from_version == 0 || from_version > VERSION {
+        return Err(ConfigError::UnsupportedVersion(from_version));
+    }
+    let migrations: Vec<_> = MIGRATIONS
+        .iter()
+        .filter(|migration| migration.to > from_version)
+        .collect();
+    let Some(profile) = value.as_object_mut() else {
+        return Ok(value);
+    };
+
+    let fields: [(&str, &[usize]); 2] =
+        [("remap", &[0, 1]), ("button_axes", &[0])];
+    for (field, positions) in fields {
+        let Some(entries) =
+            profile.get_mut(field).and_then(Value::as_array_mut)
+        else {
+            continue;
+        };
+        for entry in entries.iter_mut().filter_map(Value::as_array_mut) {
+            for &at in positions {
+                if let Some(Value::String(names)) = entry.get_mut(at) {
+                    *names = migrate_names(names, &migrations, from_version)?;
+                }
+            }
+        }
+    }
+    _ = profile.insert("version".to_owned(), Value::from(VERSION));
+    Ok(value)
+}
+
+/// Loads an [`InputProfile`] saved as JSON with any version up to
+/// [`VERSION`], see [`migrate`]. A file without a version is assumed to be
+/// of version `1`.
+///
+/// # Errors
+///
+/// Returns a [`ConfigError`] if the file is invalid, or can't be migrated.
+///
+/// [`InputProfile`]: crate::InputProfile
+#[inline]
+pub fn load_profile(json: &str) -> Result<InputProfile, ConfigError> {
+    let value: Value = serde_json::from_str(json)?;
+    let version = match value.get("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .and_then(|raw| u32::try_from(raw).ok())
+            .ok_or(ConfigError::UnsupportedVersion(u32::MAX))?,
+    };
+    Ok(serde_json::from_value(migrate(value, version)?)?)
+}
+
+/// Renames the `" | "`-separated [`Button`] `names` through the
+/// `migrations`, checking that each is known.
+///
+/// [`Button`]: crate::Button
+fn migrate_names(
+    names: &str,
+    migrations: &[&Migration],
+    version: u32,
+) -> Result<String, ConfigError> {
+    let mut migrated = Vec::new();
+    for name in names.split('|').map(str::trim).filter(|name| !name.is_empty())
+    {
+        let renamed = migrations
+            .iter()
+            .fold(name, |current, migration| migration.rename(current));
+        if Button::from_name(renamed).is_none() {
+            return Err(ConfigError::UnknownButton {
+                name: name.to_owned(),
+                version,
+            });
+        }
+        migrated.push(renamed);
+    }
+    Ok(migrated.join(" | "))
+}
@@ -0,0 +1,357 @@
+//! Estimation of the wireless connection quality of [`Gamepad`]s.
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{Event, GamepadId};
+
+/// Weight of the newest interval in the running averages, as in the RTP
+/// jitter estimate.
+const SMOOTHING: f64 = 16.0;
+
+/// Most intervals collected while calibrating.
+const MAX_CALIBRATION_SAMPLES: usize = 512;
+
+/// Fewest intervals to calibrate the expected interval from.
+const MIN_CALIBRATION_SAMPLES: usize = 8;
+
+/// Rough rating of a connection, see [`QualityReport::rating`].
+///
+/// A connection is [`Poor`] when more than 5% of the reports go missing or
+/// the jitter exceeds half of the expected interval, and [`Fair`] from 1%
+/// or a quarter of it.
+///
+/// [`Poor`]: Self::Poor
+/// [`Fair`]: Self::Fair
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionRating {
+    /// Not enough input to tell: still calibrating, or idle.
+    Unknown,
+    /// Reports go missing or arrive irregularly, noticeable as lag.
+    Poor,
+    /// Occasional missing or late reports.
+    Fair,
+    /// Reports arrive at a steady rate.
+    Good,
+}
+
+impl ConnectionRating {
+    /// Rates a connection by the fraction of missing reports and the jitter
+    /// relative to the expected interval.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn rate(loss: f64, jitter: f64) -> Self {
+        if loss > 0.05 || jitter > 0.5 {
+            Self::Poor
+        } else if loss > 0.01 || jitter > 0.25 {
+            Self::Fair
+        } else {
+            Self::Good
+        }
+    }
+}
+
+/// Connection quality of a [`Gamepad`], see [`ConnectionQuality::report`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QualityReport {
+    /// Interval the device reports at, calibrated over its first
+    /// [`ConnectionQuality::CALIBRATION`] of input. [`None`] while
+    /// calibrating.
+    pub expected_interval: Option<Duration>,
+    /// Running average of the intervals between reports, zero while
+    /// calibrating.
+    pub mean_interval: Duration,
+    /// Running average of the deviation of the intervals from the
+    /// expected one, zero while calibrating.
+    pub jitter: Duration,
+    /// Reports estimated to have gone missing since calibrating.
+    pub dropout_count: u64,
+    /// Rough rating of the connection.
+    pub rating: ConnectionRating,
+}
+
+/// Source of the reports a [`Link`] is timed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    /// Stick and trigger motion, only reported while moving.
+    Axis,
+    /// Updates of a [`Sensor`], reported continuously while enabled.
+    #[cfg(feature = "sensors")]
+    Sensor(Sensor),
+}
+
+impl Source {
+    /// Checks if this is a [`Source::Sensor`].
+    #[cfg_attr(
+        not(feature = "sensors"),
+        expect(clippy::unused_self, reason = "feature gated")
+    )]
+    const fn is_sensor(self) -> bool {
+        #[cfg(feature = "sensors")]
+        if let Self::Sensor(_) = self {
+            return true;
+        }
+        false
+    }
+}
+
+/// Timing statistics of the reports of a single device.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Link {
+    /// Source of the reports timed.
+    source: Option<Source>,
+    /// Timestamp of the last report.
+    last: Option<Duration>,
+    /// Intervals collected while calibrating.
+    calibration: Vec<Duration>,
+    /// Total of the intervals collected while calibrating.
+    calibrated_for: Duration,
+    /// Calibrated interval between reports.
+    expected: Option<Duration>,
+    /// Running average of the intervals, in seconds.
+    mean: f64,
+    /// Running average of the deviations, in seconds.
+    jitter: f64,
+    /// Running average of the fraction of missing reports.
+    loss: f64,
+    /// Reports estimated missing.
+    dropouts: u64,
+}
+
+impl Link {
+    /// Records a report from the `source` at `at`.
+    fn sample(&mut self, source: Source, at: Duration) {
+        if self.source != Some(source) {
+            // sensors report continuously and win over axes, which don't
+            let replaces = self.source.is_none_or(|current| {
+                !current.is_sensor() && source.is_sensor()
+            });
+            if !replaces {
+                return;
+            }
+            *self = Self { source: Some(source), ..Self::default() };
+        }
+
+        let Some(last) = self.last.replace(at) else {
+            return;
+        };
+        // the same packet, or the clock wrapped around
+        let Some(interval) = at.checked_sub(last).filter(|gap| !gap.is_zero())
+        else {
+            return;
+        };
+        if interval > ConnectionQuality::IDLE_GAP {
+            return;
+        }
+        let Some(expected) = self.expected else {
+            self.calibrate(interval);
+            return;
+        };
+
+        let (seconds, expected) =
+            (interval.as_secs_f64(), expected.as_secs_f64());
+        let missing = ((seconds / expected).round() - 1.0).max(0.0);
+        let deviation = expected.mul_add(-(missing + 1.0), seconds).abs();
+        self.mean += (seconds - self.mean) / SMOOTHING;
+        self.jitter += (deviation - self.jitter) / SMOOTHING;
+        self.loss += (missing / (missing + 1.0f64) - self.loss) / SMOOTHING;
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "rounded, positive and at most the idle gap in intervals"
+        )]
+        let missing = missing as u64;
+        self.dropouts = self.dropouts.saturating_add(missing);
+    }
+
+    /// Collects an `interval` for calibration, settling on the median
+    /// interval once enough are collected.
+    fn calibrate(&mut self, interval: Duration) {
+        if self.calibration.len() < MAX_CALIBRATION_SAMPLES {
+            self.calibration.push(interval);
+        }
+        self.calibrated_for = self.calibrated_for.saturating_add(interval);
+        if self.calibrated_for < ConnectionQuality::CALIBRATION
+            || self.calibration.len() < MIN_CALIBRATION_SAMPLES
+        {
+            return;
+        }
+        #[expect(
+            clippy::integer_division,
+            clippy::integer_division_remainder_used,
+            reason = "the upper median of an even count is fine"
+        )]
+        let middle = self.calibration.len() / 2;
+        let (_, &mut median, _) = self.calibration.select_nth_unstable(middle);
+        self.expected = Some(median);
+        self.mean = median.as_secs_f64();
+        self.calibration = Vec::new();
+    }
+
+    /// Reports the statistics as of `now`.
+    fn report(&self, now: Duration) -> QualityReport {
+        let idle = self.last.is_none_or(|last| {
+            now.saturating_sub(last) > ConnectionQuality::IDLE_GAP
+        });
+        let rating = self.expected.filter(|_| !idle).map_or(
+            ConnectionRating::Unknown,
+            |expected| {
+                let jitter = self.jitter / expected.as_secs_f64();
+                ConnectionRating::rate(self.loss, jitter)
+            },
+        );
+        QualityReport {
+            expected_interval: self.expected,
+            mean_interval: Duration::from_secs_f64(self.mean),
+            jitter: Duration::from_secs_f64(self.jitter),
+            dropout_count: self.dropouts,
+            rating,
+        }
+    }
+}
+
+/// Estimates the connection quality of every device from the timing of its
+/// reports, e.g. to tell players that "lag" is Bluetooth packet loss.
+///
+/// Devices report at a fixed rate, so longer gaps between reports indicate
+/// dropped packets. The expected interval is calibrated per device over its
+/// first [`CALIBRATION`] of input. Reports are timed by the updates of the
+/// first [`Sensor`] enabled, which arrive continuously, and otherwise by
+/// stick and trigger motion, which only arrives while moving. Gaps longer
+/// than the [`IDLE_GAP`] are idle periods and ignored, and a device idle
+/// for longer than that is rated [`ConnectionRating::Unknown`].
+///
+/// Only a few running averages are kept per device, so observing is cheap
+/// enough for every [`Event`].
+///
+/// Used by [`Girl::connection_quality`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{ConnectionQuality, ConnectionRating, prelude::*};
+/// let mut quality = ConnectionQuality::new();
+/// let ms = Duration::from_millis;
+/// let id = GamepadId::from_raw(0);
+/// let motion = Event::ControllerStickMotion {
+///     which: 0,
+///     stick: Stick::Left,
+///     offset: [0.5, 0.0],
+/// };
+///
+/// quality.observe(&Event::ControllerDeviceAdded { which: 0 }, ms(0));
+/// let report = quality.report(id, ms(0)).unwrap();
+/// assert_eq!(report.rating, ConnectionRating::Unknown);
+///
+/// // a stick moving for 2 seconds calibrates the 4 ms interval
+/// for step in 0..=500 {
+///     quality.observe(&motion, ms(step * 4));
+/// }
+/// let report = quality.report(id, ms(2000)).unwrap();
+/// assert_eq!(report.expected_interval, Some(ms(4)));
+/// assert_eq!(report.rating, ConnectionRating::Good);
+///
+/// // 3 reports go missing
+/// quality.observe(&motion, ms(2016));
+/// quality.observe(&motion, ms(2020));
+/// let report = quality.report(id, ms(2020)).unwrap();
+/// assert_eq!(report.dropout_count, 3);
+/// assert_eq!(report.rating, ConnectionRating::Fair);
+///
+/// // a stick at rest reports nothing, so the rating is unknown
+/// let report = quality.report(id, ms(3000)).unwrap();
+/// assert_eq!(report.rating, ConnectionRating::Unknown);
+///
+/// // and the idle period isn't counted once it moves again
+/// for step in 0..100 {
+///     quality.observe(&motion, ms(5000 + step * 4));
+/// }
+/// let report = quality.report(id, ms(5400)).unwrap();
+/// assert_eq!(report.dropout_count, 3);
+/// assert_eq!(report.rating, ConnectionRating::Good);
+///
+/// quality.observe(&Event::ControllerDeviceRemoved { which: 0 }, ms(5400));
+/// assert!(quality.report(id, ms(5400)).is_none());
+/// ```
+///
+/// [`CALIBRATION`]: Self::CALIBRATION
+/// [`IDLE_GAP`]: Self::IDLE_GAP
+/// [`Sensor`]: crate::Sensor
+/// [`Girl::connection_quality`]: crate::Girl::connection_quality
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionQuality {
+    /// Statistics of every device by controller instance ID.
+    links: BTreeMap<u32, Link>,
+}
+
+impl ConnectionQuality {
+    /// Input time the expected interval of a device is calibrated over.
+    pub const CALIBRATION: Duration = Duration::from_secs(2);
+    /// Longest gap between reports counted as missing reports rather than
+    /// an idle period.
+    pub const IDLE_GAP: Duration = Duration::from_millis(100);
+
+    /// Creates a new [`ConnectionQuality`] without any devices.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { links: BTreeMap::new() }
+    }
+
+    /// Observes an [`Event`] reported by the device at `at`, a timestamp on
+    /// any monotonic clock, e.g. of the SDL2 event.
+    #[inline]
+    pub fn observe(&mut self, event: &Event, at: Duration) {
+        if let Event::ControllerDeviceAdded { which } = *event {
+            _ = self.links.entry(which).or_default();
+        }
+        if let Event::ControllerDeviceRemoved { which } = *event {
+            drop(self.links.remove(&which));
+        }
+        if let Some((which, source)) = source_of(event) {
+            self.links.entry(which).or_default().sample(source, at);
+        }
+    }
+
+    /// Reports the connection quality of the device as of `now`, on the
+    /// clock of [`observe`].
+    ///
+    /// Returns [`None`] if no [`Event`] of the device was observed, or it
+    /// disconnected since.
+    ///
+    /// [`observe`]: Self::observe
+    #[must_use]
+    #[inline]
+    pub fn report(
+        &self,
+        id: GamepadId,
+        now: Duration,
+    ) -> Option<QualityReport> {
+        self.links.get(&id.raw()).map(|link| link.report(now))
+    }
+}
+
+/// Gets the device and [`Source`] of a report [`Event`].
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+const fn source_of(event: &Event) -> Option<(u32, Source)> {
+    if let Event::ControllerStickMotion { which, .. }
+    | Event::ControllerTriggerMotion { which, .. } = *event
+    {
+        return Some((which, Source::Axis));
+    }
+    #[cfg(feature = "sensors")]
+    if let Event::ControllerSensorUpdated { which, sensor, .. } = *event {
+        return Some((which, Source::Sensor(sensor)));
+    }
+    None
+}
@@ -0,0 +1,392 @@
+//! Frame-scoped routing of input between prioritized contexts, e.g. gameplay,
+//! menus and text entry.
+
+use alloc::collections::BTreeMap;
+
+use crate::{Button, Gamepad, GamepadState, Stick, Trigger};
+
+/// Handle of a context declared with [`InputContexts::declare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ContextId(usize);
+
+/// Analog input a context can claim, see [`InputContexts::set_axis`].
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "every analog input is either a stick, a trigger or a button"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputAxis {
+    /// Both axes of a [`Stick`].
+    Stick(Stick),
+    /// A [`Trigger`].
+    Trigger(Trigger),
+    /// A single [`Button`] read as an analog value, see
+    /// [`Gamepad::button_value`].
+    Button(Button),
+}
+
+/// How a context claims an [`InputAxis`], see [`InputContexts::set_axis`].
+#[expect(clippy::exhaustive_enums, reason = "an axis is either shared or not")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisMode {
+    /// Lower-priority contexts still read the axis.
+    #[default]
+    PassThrough,
+    /// Lower-priority contexts read the axis at rest.
+    Exclusive,
+}
+
+/// A context declared with [`InputContexts::declare`].
+#[derive(Debug, Clone)]
+struct Context {
+    /// Name of the context, see [`InputContexts::find`].
+    name: String,
+    /// Contexts with a higher priority consume input first.
+    priority: i32,
+    /// [`Button`]s consumed while the context is active.
+    buttons: Button,
+    /// Claimed [`InputAxis`]es.
+    axes: BTreeMap<InputAxis, AxisMode>,
+}
+
+/// Routes input between prioritized contexts, so a press is only seen by
+/// the context it is meant for, e.g. a pause menu overlay and the gameplay
+/// below it.
+///
+/// The app declares its contexts once, with a priority and the [`Button`]s
+/// each one consumes. Every frame it [`activate`]s the contexts on screen,
+/// and each context queries input through [`pressed`], [`stick`] and
+/// [`trigger`]. A query only sees the input that no active context with a
+/// strictly higher priority consumes. Inactive contexts see no input at all.
+///
+/// Activations only last for a frame: [`begin_frame`] deactivates every
+/// context, and [`Girl::update`] calls it on [`Girl::contexts`].
+///
+/// # Examples
+///
+/// A pause menu overlay stealing the confirm button from gameplay:
+///
+/// ```
+/// # use girl::{InputContexts, prelude::*};
+/// let mut contexts = InputContexts::new();
+/// let gameplay = contexts.declare("gameplay", 0, Button::A | Button::B);
+/// let pause = contexts.declare("pause", 10, Button::A | Button::Start);
+///
+/// let mut state = GamepadState::default();
+/// state.buttons = Button::A | Button::B;
+///
+/// // paused: the menu confirms, gameplay does not jump
+/// contexts.activate(gameplay);
+/// contexts.activate(pause);
+/// assert!(contexts.pressed_in(pause, &state, Button::A));
+/// assert!(!contexts.pressed_in(gameplay, &state, Button::A));
+/// // buttons the menu does not consume still reach gameplay
+/// assert!(contexts.pressed_in(gameplay, &state, Button::B));
+/// assert!(!contexts.pressed_in(gameplay, &state, Button::A | Button::B));
+///
+/// // next frame, nothing is active until activated again
+/// contexts.begin_frame();
+/// assert!(!contexts.pressed_in(pause, &state, Button::A));
+/// assert!(!contexts.pressed_in(gameplay, &state, Button::A));
+///
+/// // unpaused: the press reaches gameplay
+/// contexts.activate(gameplay);
+/// assert!(contexts.pressed_in(gameplay, &state, Button::A));
+/// assert!(!contexts.pressed_in(pause, &state, Button::A));
+/// assert_eq!(contexts.find("pause"), Some(pause));
+/// ```
+///
+/// Exclusive and pass-through axes:
+///
+/// ```
+/// # use girl::{AxisMode, InputAxis, InputContexts, prelude::*};
+/// let mut contexts = InputContexts::new();
+/// let gameplay = contexts.declare("gameplay", 0, Button::empty());
+/// let menu = contexts.declare("menu", 10, Button::empty());
+/// let hud = contexts.declare("hud", 20, Button::empty());
+/// let left = InputAxis::Stick(Stick::Left);
+/// contexts.set_axis(menu, left, Some(AxisMode::Exclusive));
+/// contexts.set_axis(hud, left, Some(AxisMode::PassThrough));
+///
+/// contexts.activate(gameplay);
+/// contexts.activate(menu);
+/// contexts.activate(hud);
+/// assert!(contexts.axis_available(hud, left));
+/// assert!(contexts.axis_available(menu, left));
+/// assert!(!contexts.axis_available(gameplay, left));
+/// let right = InputAxis::Stick(Stick::Right);
+/// assert!(contexts.axis_available(gameplay, right));
+/// ```
+///
+/// [`activate`]: Self::activate
+/// [`pressed`]: Self::pressed
+/// [`stick`]: Self::stick
+/// [`trigger`]: Self::trigger
+/// [`begin_frame`]: Self::begin_frame
+/// [`Girl::update`]: crate::Girl::update
+/// [`Girl::contexts`]: crate::Girl::contexts
+#[derive(Debug, Clone, Default)]
+pub struct InputContexts {
+    /// Declared contexts, indexed by [`ContextId`].
+    contexts: Vec<Context>,
+    /// Contexts activated this frame.
+    active: Vec<ContextId>,
+}
+
+impl InputContexts {
+    /// Creates [`InputContexts`] without any context.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { contexts: Vec::new(), active: Vec::new() }
+    }
+
+    /// Declares a context consuming the `buttons` while active, returning
+    /// its handle.
+    ///
+    /// Contexts with a higher `priority` consume input first.
+    #[inline]
+    pub fn declare(
+        &mut self,
+        name: &str,
+        priority: i32,
+        buttons: Button,
+    ) -> ContextId {
+        let id = ContextId(self.contexts.len());
+        self.contexts.push(Context {
+            name: name.to_owned(),
+            priority,
+            buttons,
+            axes: BTreeMap::new(),
+        });
+        id
+    }
+
+    /// Finds the first context declared with the `name`.
+    #[must_use]
+    #[inline]
+    pub fn find(&self, name: &str) -> Option<ContextId> {
+        self.contexts
+            .iter()
+            .position(|context| context.name == name)
+            .map(ContextId)
+    }
+
+    /// Sets the [`Button`]s the context consumes while active.
+    #[inline]
+    pub fn set_buttons(&mut self, context: ContextId, buttons: Button) {
+        if let Some(declared) = self.contexts.get_mut(context.0) {
+            declared.buttons = buttons;
+        }
+    }
+
+    /// Claims the `axis` for the context, or releases it if `mode` is
+    /// [`None`].
+    ///
+    /// [`AxisMode::Exclusive`] axes read at rest in lower-priority contexts
+    /// while the context is active, [`AxisMode::PassThrough`] ones do not.
+    #[inline]
+    pub fn set_axis(
+        &mut self,
+        context: ContextId,
+        axis: InputAxis,
+        mode: Option<AxisMode>,
+    ) {
+        let Some(declared) = self.contexts.get_mut(context.0) else {
+            return;
+        };
+        _ = match mode {
+            Some(claim) => declared.axes.insert(axis, claim),
+            None => declared.axes.remove(&axis),
+        };
+    }
+
+    /// Activates the context for the current frame.
+    #[inline]
+    pub fn activate(&mut self, context: ContextId) {
+        if context.0 < self.contexts.len() && !self.active.contains(&context) {
+            self.active.push(context);
+        }
+    }
+
+    /// Deactivates the context for the rest of the current frame.
+    #[inline]
+    pub fn deactivate(&mut self, context: ContextId) {
+        self.active.retain(|&active| active != context);
+    }
+
+    /// Checks if the context is active in the current frame.
+    #[must_use]
+    #[inline]
+    pub fn is_active(&self, context: ContextId) -> bool {
+        self.active.contains(&context)
+    }
+
+    /// Starts a new frame, deactivating every context.
+    ///
+    /// Called by [`Girl::update`] on [`Girl::contexts`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::contexts`]: crate::Girl::contexts
+    #[inline]
+    pub fn begin_frame(&mut self) {
+        self.active.clear();
+    }
+
+    /// Gets the [`Button`]s the context sees this frame: none if it is
+    /// inactive, otherwise all except the ones consumed by active contexts
+    /// with a higher priority.
+    #[must_use]
+    #[inline]
+    pub fn available(&self, context: ContextId) -> Button {
+        if !self.is_active(context) {
+            return Button::empty();
+        }
+        self.above(context)
+            .fold(Button::all(), |seen, above| seen.difference(above.buttons))
+    }
+
+    /// Checks if the context sees the `axis` this frame: not if it is
+    /// inactive, or if an active context with a higher priority claims it
+    /// as [`AxisMode::Exclusive`].
+    #[must_use]
+    #[inline]
+    pub fn axis_available(&self, context: ContextId, axis: InputAxis) -> bool {
+        self.is_active(context)
+            && self.above(context).all(|above| {
+                above.axes.get(&axis) != Some(&AxisMode::Exclusive)
+            })
+    }
+
+    /// Checks if all the `buttons` are pressed on the [`Gamepad`] and seen by
+    /// the context this frame, see [`available`].
+    ///
+    /// [`available`]: Self::available
+    #[must_use]
+    #[inline]
+    pub fn pressed(
+        &self,
+        context: ContextId,
+        gamepad: &Gamepad,
+        buttons: Button,
+    ) -> bool {
+        self.available(context).contains(buttons)
+            && gamepad.buttons_pressed(buttons)
+    }
+
+    /// Checks if all the `buttons` are pressed in the [`GamepadState`] and
+    /// seen by the context this frame, see [`pressed`].
+    ///
+    /// [`pressed`]: Self::pressed
+    #[must_use]
+    #[inline]
+    pub fn pressed_in(
+        &self,
+        context: ContextId,
+        state: &GamepadState,
+        buttons: Button,
+    ) -> bool {
+        self.available(context).contains(buttons)
+            && state.buttons.contains(buttons)
+    }
+
+    /// Gets the position of the [`Stick`] on the [`Gamepad`] as seen by the
+    /// context this frame, at rest if not available, see
+    /// [`axis_available`].
+    ///
+    /// [`axis_available`]: Self::axis_available
+    #[must_use]
+    #[inline]
+    pub fn stick(
+        &self,
+        context: ContextId,
+        gamepad: &Gamepad,
+        stick: Stick,
+    ) -> [f64; 2] {
+        if self.axis_available(context, InputAxis::Stick(stick)) {
+            gamepad.stick(stick)
+        } else {
+            [0.0, 0.0]
+        }
+    }
+
+    /// Gets the value of the [`Trigger`] on the [`Gamepad`] as seen by the
+    /// context this frame, at rest if not available, see
+    /// [`axis_available`].
+    ///
+    /// [`axis_available`]: Self::axis_available
+    #[must_use]
+    #[inline]
+    pub fn trigger(
+        &self,
+        context: ContextId,
+        gamepad: &Gamepad,
+        trigger: Trigger,
+    ) -> f64 {
+        if self.axis_available(context, InputAxis::Trigger(trigger)) {
+            gamepad.trigger(trigger)
+        } else {
+            0.0
+        }
+    }
+
+    /// Gets how far the single [`Button`] on the [`Gamepad`] is pressed as
+    /// seen by the context this frame, at rest if not available.
+    ///
+    /// Bound like an axis: the [`Button`] must be seen by the context (see
+    /// [`available`]) and its [`InputAxis::Button`] must not be claimed
+    /// exclusively above it (see [`axis_available`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ButtonAxis, InputContexts, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// let mut contexts = InputContexts::new();
+    /// let gameplay = contexts.declare("gameplay", 0, Button::empty());
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // an analog A button accelerates
+    /// let pressure = ButtonAxis::Joystick(6);
+    /// gamepad.set_button_axis_source(Button::A, Some(pressure))?;
+    /// contexts.activate(gameplay);
+    /// let throttle = contexts.button_value(gameplay, &gamepad, Button::A);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`available`]: Self::available
+    /// [`axis_available`]: Self::axis_available
+    #[must_use]
+    #[inline]
+    pub fn button_value(
+        &self,
+        context: ContextId,
+        gamepad: &Gamepad,
+        button: Button,
+    ) -> f64 {
+        if self.available(context).contains(button)
+            && self.axis_available(context, InputAxis::Button(button))
+        {
+            gamepad.button_value(button)
+        } else {
+            0.0
+        }
+    }
+
+    /// Iterates over the active contexts with a higher priority than the
+    /// `context`.
+    fn above(&self, context: ContextId) -> impl Iterator<Item = &Context> {
+        let priority = self
+            .contexts
+            .get(context.0)
+            .map_or(i32::MAX, |declared| declared.priority);
+        self.active
+            .iter()
+            .filter_map(|active| self.contexts.get(active.0))
+            .filter(move |active| active.priority > priority)
+    }
+}
@@ -0,0 +1,488 @@
+//! On-screen cursor driven by a [`Gamepad`], e.g. for media-center UIs.
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+#[cfg(feature = "touchpad")]
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+use crate::{Button, GamepadState, ResponseCurve, Stick};
+#[cfg(feature = "touchpad")]
+use crate::{FingerId, TouchRect, TouchpadAction, TouchpadEvent};
+
+/// What happens to a [`CursorEmulator`] reaching the edge of the screen.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorEdges {
+    /// The cursor stops at the edge.
+    #[default]
+    Clamp,
+    /// The cursor reappears at the opposite edge.
+    Wrap,
+}
+
+/// How a [`CursorEmulator`] follows the touchpad.
+///
+/// The pointer [`Stick`] moves the cursor in both modes, and dragging two
+/// fingers scrolls in both modes.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorMode {
+    /// Dragging a finger moves the cursor by as much, the width of the
+    /// touchpad spanning the width of the screen, like a laptop touchpad.
+    #[default]
+    Relative,
+    /// The cursor jumps to where a finger is, mapped from the
+    /// `touch_region` of the [`CursorConfig`] onto the screen, like a
+    /// graphics tablet.
+    Absolute,
+}
+
+/// Configuration of a [`CursorEmulator`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursorConfig {
+    /// Size of the screen `[width, height]`, in pixels.
+    pub screen: [f32; 2],
+    /// [`Stick`] moving the cursor.
+    pub pointer_stick: Stick,
+    /// Speed of the cursor with the pointer [`Stick`] fully deflected, in
+    /// pixels per second.
+    pub max_speed: f32,
+    /// [`ResponseCurve`] applied to the deflection of the pointer
+    /// [`Stick`].
+    pub curve: ResponseCurve,
+    /// Time the pointer [`Stick`] has to be held deflected to reach the full
+    /// speed. [`Duration::ZERO`] disables the acceleration.
+    pub ramp_time: Duration,
+    /// Fraction of the speed at which the cursor starts moving, in the
+    /// `[0.0, 1.0]` range, growing to `1.0` over the [`ramp_time`].
+    ///
+    /// [`ramp_time`]: Self::ramp_time
+    pub ramp_start: f32,
+    /// [`Stick`] scrolling, see [`CursorEmulator::scroll_delta`].
+    pub scroll_stick: Stick,
+    /// Scroll speed with the scroll [`Stick`] fully deflected, in scroll
+    /// units per second.
+    pub scroll_speed: f32,
+    /// [`Button`] clicking, see [`CursorEmulator::clicked`].
+    pub click: Button,
+    /// What happens at the edges of the screen.
+    pub edges: CursorEdges,
+    /// Region of the touchpad mapped onto the whole screen in
+    /// [`CursorMode::Absolute`].
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    pub touch_region: TouchRect,
+    /// Scroll units per touchpad height dragged with two fingers.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    pub touch_scroll: f32,
+}
+
+impl CursorConfig {
+    /// Creates a [`CursorConfig`] for a screen of the given size, moving the
+    /// cursor with the right [`Stick`], scrolling with the left one and
+    /// clicking with [`Button::A`].
+    #[must_use]
+    #[inline]
+    pub const fn new(screen: [f32; 2]) -> Self {
+        Self {
+            screen,
+            pointer_stick: Stick::Right,
+            max_speed: 1200.0,
+            curve: ResponseCurve::Power(2.0),
+            ramp_time: Duration::from_millis(400),
+            ramp_start: 0.4,
+            scroll_stick: Stick::Left,
+            scroll_speed: 20.0,
+            click: Button::A,
+            edges: CursorEdges::Clamp,
+            #[cfg(feature = "touchpad")]
+            touch_region: TouchRect::new([0.0, 0.0], [1.0, 1.0]),
+            #[cfg(feature = "touchpad")]
+            touch_scroll: 10.0,
+        }
+    }
+}
+
+/// Model of an on-screen cursor driven by a [`Gamepad`]: moved by a
+/// [`Stick`] with acceleration, or by the touchpad (see [`CursorMode`]),
+/// with a click [`Button`] and scrolling.
+///
+/// It doesn't move the mouse of the OS: the application draws the cursor at
+/// its [`position`] and handles the [`clicked`] and [`scroll_delta`] itself.
+/// Feed it a [`GamepadState`] every frame with [`update`], and the touchpad
+/// [`Event`]s with `touch` (under the `touchpad` feature).
+///
+/// # Examples
+///
+/// ```
+/// # use core::time::Duration;
+/// # use girl::{CursorConfig, CursorEdges, CursorEmulator, prelude::*};
+/// let mut config = CursorConfig::new([1000.0, 500.0]);
+/// config.max_speed = 100.0;
+/// config.curve = girl::ResponseCurve::Linear;
+/// config.ramp_time = Duration::from_secs(1);
+/// config.ramp_start = 0.5;
+/// let mut cursor = CursorEmulator::new(config);
+/// assert_eq!(cursor.position(), [500.0, 250.0]);
+///
+/// let tick = Duration::from_millis(500);
+/// let mut state = GamepadState::default();
+/// state.right_stick = [1.0, 0.0];
+/// // accelerating from half the speed...
+/// cursor.update(&state, tick);
+/// assert_eq!(cursor.position(), [537.5, 250.0]);
+/// cursor.update(&state, tick);
+/// assert_eq!(cursor.position(), [587.5, 250.0]);
+/// // ...to the full speed
+/// cursor.update(&state, tick);
+/// assert_eq!(cursor.position(), [637.5, 250.0]);
+///
+/// // clamped at the edge
+/// state.right_stick = [0.0, 1.0];
+/// for _ in 0..20 {
+///     cursor.update(&state, tick);
+/// }
+/// assert_eq!(cursor.position(), [637.5, 500.0]);
+///
+/// // or wrapped around
+/// config.edges = CursorEdges::Wrap;
+/// cursor.set_config(config);
+/// cursor.update(&state, tick);
+/// assert_eq!(cursor.position(), [637.5, 50.0]);
+///
+/// // clicks and scrolling are reported for one update
+/// state.right_stick = [0.0, 0.0];
+/// state.left_stick = [0.0, -0.5];
+/// state.buttons = Button::A;
+/// cursor.update(&state, tick);
+/// assert!(cursor.clicked());
+/// assert_eq!(cursor.scroll_delta(), [0.0, -5.0]);
+/// state.left_stick = [0.0, 0.0];
+/// cursor.update(&state, tick);
+/// assert!(!cursor.clicked());
+/// assert_eq!(cursor.scroll_delta(), [0.0, 0.0]);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Event`]: crate::Event
+/// [`position`]: Self::position
+/// [`clicked`]: Self::clicked
+/// [`scroll_delta`]: Self::scroll_delta
+/// [`update`]: Self::update
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    not(feature = "touchpad"),
+    expect(
+        missing_copy_implementations,
+        reason = "not `Copy` with `touchpad`"
+    )
+)]
+pub struct CursorEmulator {
+    /// Configuration.
+    config: CursorConfig,
+    /// How the cursor follows the touchpad.
+    mode: CursorMode,
+    /// Position of the cursor, in pixels.
+    position: [f32; 2],
+    /// How long the pointer [`Stick`] has been deflected.
+    held: Duration,
+    /// [`Button`]s pressed at the previous update.
+    previous_buttons: Button,
+    /// Whether the click [`Button`] was pressed at the last update.
+    clicked: bool,
+    /// Scrolling of the last update.
+    scroll: [f32; 2],
+    /// Scrolling with the touchpad since the last update.
+    pending_scroll: [f32; 2],
+    /// Positions of the fingers on the touchpads, by touchpad index and
+    /// [`FingerId`].
+    #[cfg(feature = "touchpad")]
+    fingers: BTreeMap<(u32, FingerId), [f32; 2]>,
+}
+
+impl CursorEmulator {
+    /// Creates a [`CursorEmulator`] in [`CursorMode::Relative`], with the
+    /// cursor in the middle of the screen.
+    #[must_use]
+    #[inline]
+    pub fn new(config: CursorConfig) -> Self {
+        Self {
+            config,
+            mode: CursorMode::Relative,
+            position: config.screen.map(|size| size / 2.0),
+            held: Duration::ZERO,
+            previous_buttons: Button::empty(),
+            clicked: false,
+            scroll: [0.0; 2],
+            pending_scroll: [0.0; 2],
+            #[cfg(feature = "touchpad")]
+            fingers: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the [`CursorConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> &CursorConfig {
+        &self.config
+    }
+
+    /// Replaces the [`CursorConfig`], e.g. when the screen is resized,
+    /// keeping the cursor on the screen.
+    #[inline]
+    pub fn set_config(&mut self, config: CursorConfig) {
+        self.config = config;
+        self.position = self.fit(self.position);
+    }
+
+    /// Gets the [`CursorMode`].
+    #[must_use]
+    #[inline]
+    pub const fn mode(&self) -> CursorMode {
+        self.mode
+    }
+
+    /// Switches the [`CursorMode`], effective from the next touch.
+    #[inline]
+    pub const fn set_mode(&mut self, mode: CursorMode) {
+        self.mode = mode;
+    }
+
+    /// Gets the position of the cursor `[x, y]`, in pixels from the top left
+    /// corner of the screen.
+    #[must_use]
+    #[inline]
+    pub const fn position(&self) -> [f32; 2] {
+        self.position
+    }
+
+    /// Moves the cursor to the `position`, e.g. to the focused UI element.
+    #[inline]
+    pub fn set_position(&mut self, position: [f32; 2]) {
+        self.position = self.fit(position);
+    }
+
+    /// Checks if the click [`Button`] was pressed at the last [`update`].
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub const fn clicked(&self) -> bool {
+        self.clicked
+    }
+
+    /// Gets the scrolling `[x, y]` of the last [`update`], in scroll units,
+    /// from the scroll [`Stick`] and two-finger drags on the touchpad.
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub const fn scroll_delta(&self) -> [f32; 2] {
+        self.scroll
+    }
+
+    /// Advances the cursor by `dt` with the [`GamepadState`] of the frame.
+    #[inline]
+    pub fn update(&mut self, state: &GamepadState, dt: Duration) {
+        let seconds = dt.as_secs_f32();
+
+        let [x, y] = state.stick(self.config.pointer_stick).map(narrow);
+        let magnitude = x.hypot(y).min(1.0);
+        if magnitude > 0.0 {
+            self.held = self.held.saturating_add(dt);
+            let speed = self.config.max_speed
+                * narrow(self.config.curve.apply(f64::from(magnitude)))
+                * self.ramp();
+            let [px, py] = self.position;
+            let step = speed * seconds / magnitude;
+            self.position =
+                self.fit([x.mul_add(step, px), y.mul_add(step, py)]);
+        } else {
+            self.held = Duration::ZERO;
+        }
+
+        let [scroll_x, scroll_y] =
+            state.stick(self.config.scroll_stick).map(narrow);
+        let [pending_x, pending_y] = self.pending_scroll;
+        let rate = self.config.scroll_speed * seconds;
+        self.scroll = [
+            scroll_x.mul_add(rate, pending_x),
+            scroll_y.mul_add(rate, pending_y),
+        ];
+        self.pending_scroll = [0.0; 2];
+
+        let click = self.config.click;
+        self.clicked = state.buttons.intersects(click)
+            && !self.previous_buttons.intersects(click);
+        self.previous_buttons = state.buttons;
+    }
+
+    /// Feeds a touchpad event, moving the cursor with one finger and
+    /// scrolling with two, see [`CursorMode`]. Fingers are told apart by
+    /// their [`TouchpadEvent::finger_id`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{
+    /// #     CursorConfig, CursorEmulator, CursorMode, TouchRect, prelude::*,
+    /// # };
+    /// # use girl::TouchpadEvent;
+    /// # use girl::TouchpadAction::{Moved, Released, Touched};
+    /// let mut config = CursorConfig::new([1000.0, 500.0]);
+    /// config.touch_region = TouchRect::new([0.0, 0.0], [0.5, 1.0]);
+    /// let mut cursor = CursorEmulator::new(config);
+    /// let touch = |finger, position, action| {
+    ///     TouchpadEvent::new(0, 0, finger, position, 1.0, action)
+    /// };
+    ///
+    /// // relative: moved by the drag, not by where the finger lands
+    /// cursor.touch(&touch(0, [0.5, 0.5], Touched));
+    /// assert_eq!(cursor.position(), [500.0, 250.0]);
+    /// cursor.touch(&touch(0, [0.75, 0.5], Moved));
+    /// assert_eq!(cursor.position(), [750.0, 250.0]);
+    /// cursor.touch(&touch(0, [0.75, 0.5], Released));
+    ///
+    /// // absolute: the left half of the touchpad maps onto the screen
+    /// cursor.set_mode(CursorMode::Absolute);
+    /// cursor.touch(&touch(0, [0.25, 0.5], Touched));
+    /// assert_eq!(cursor.position(), [500.0, 250.0]);
+    /// cursor.touch(&touch(0, [0.8, 0.0], Moved));
+    /// assert_eq!(cursor.position(), [1000.0, 0.0]);
+    ///
+    /// // two fingers scroll instead
+    /// cursor.touch(&touch(1, [0.9, 0.0], Touched));
+    /// cursor.touch(&touch(1, [0.9, 0.2], Moved));
+    /// assert_eq!(cursor.position(), [1000.0, 0.0]);
+    /// cursor.update(&GamepadState::default(), Default::default());
+    /// assert_eq!(cursor.scroll_delta(), [0.0, 1.0]);
+    /// ```
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[inline]
+    pub fn touch(&mut self, event: &TouchpadEvent) {
+        let key = (event.idx, event.finger_id);
+        let previous = match event.action {
+            TouchpadAction::Released => {
+                _ = self.fingers.remove(&key);
+                return;
+            }
+            TouchpadAction::Touched | TouchpadAction::Moved => {
+                self.fingers.insert(key, event.position)
+            }
+        };
+
+        let fingers = self.fingers.len();
+        if fingers > 1 {
+            if let Some([old_x, old_y]) = previous {
+                #[expect(
+                    clippy::cast_precision_loss,
+                    reason = "a handful of fingers"
+                )]
+                let share = self.config.touch_scroll / fingers as f32;
+                let [x, y] = event.position;
+                let [pending_x, pending_y] = self.pending_scroll;
+                self.pending_scroll = [
+                    (x - old_x).mul_add(share, pending_x),
+                    (y - old_y).mul_add(share, pending_y),
+                ];
+            }
+            return;
+        }
+
+        match self.mode {
+            CursorMode::Relative => {
+                if let Some([old_x, old_y]) = previous {
+                    let [x, y] = event.position;
+                    let [width, height] = self.config.screen;
+                    let [px, py] = self.position;
+                    self.position = self.fit([
+                        (x - old_x).mul_add(width, px),
+                        (y - old_y).mul_add(height, py),
+                    ]);
+                }
+            }
+            CursorMode::Absolute => {
+                let TouchRect { min: [min_x, min_y], max: [max_x, max_y] } =
+                    self.config.touch_region;
+                let [x, y] = event.position;
+                let [width, height] = self.config.screen;
+                self.position = [
+                    span(x, min_x, max_x) * width,
+                    span(y, min_y, max_y) * height,
+                ];
+            }
+        }
+    }
+
+    /// Stops the acceleration and forgets the pressed [`Button`]s and the
+    /// fingers on the touchpad, e.g. when another [`Gamepad`] takes over.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    #[cfg_attr(
+        not(feature = "touchpad"),
+        expect(
+            clippy::missing_const_for_fn,
+            reason = "not const with touchpad"
+        )
+    )]
+    pub fn reset(&mut self) {
+        self.held = Duration::ZERO;
+        self.previous_buttons = Button::empty();
+        self.clicked = false;
+        self.scroll = [0.0; 2];
+        self.pending_scroll = [0.0; 2];
+        #[cfg(feature = "touchpad")]
+        self.fingers.clear();
+    }
+
+    /// Gets the fraction of the speed reached with the pointer [`Stick`]
+    /// held deflected so far.
+    fn ramp(&self) -> f32 {
+        if self.config.ramp_time.is_zero() {
+            return 1.0;
+        }
+        let start = self.config.ramp_start.clamp(0.0, 1.0);
+        let progress = (self.held.as_secs_f32()
+            / self.config.ramp_time.as_secs_f32())
+        .min(1.0);
+        (1.0 - start).mul_add(progress, start)
+    }
+
+    /// Keeps the `position` on the screen, see [`CursorEdges`].
+    fn fit(&self, position: [f32; 2]) -> [f32; 2] {
+        let [x, y] = position;
+        let [width, height] = self.config.screen.map(|size| size.max(0.0));
+        match self.config.edges {
+            CursorEdges::Clamp => [x.clamp(0.0, width), y.clamp(0.0, height)],
+            CursorEdges::Wrap => [wrap(x, width), wrap(y, height)],
+        }
+    }
+}
+
+/// Gets where `at` is between `low` and `high`, from `0.0` to `1.0`.
+#[cfg(feature = "touchpad")]
+fn span(at: f32, low: f32, high: f32) -> f32 {
+    ((at - low) / (high - low).max(f32::EPSILON)).clamp(0.0, 1.0)
+}
+
+/// Wraps `value` into the `[0.0, size)` range.
+fn wrap(value: f32, size: f32) -> f32 {
+    if size > 0.0 { value.rem_euclid(size) } else { 0.0 }
+}
+
+/// Converts a [`Stick`] or [`ResponseCurve`] value to `f32`.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "values in the `[-1.0, 1.0]` range"
+)]
+const fn narrow(value: f64) -> f32 {
+    value as f32
+}
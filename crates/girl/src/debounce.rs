@@ -0,0 +1,260 @@
+//! Coalescing connection changes of flaky controllers, see
+//! [`Girl::set_connection_debounce`].
+//!
+//! [`Girl::set_connection_debounce`]: crate::Girl::set_connection_debounce
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Event, Guid, logging::diag};
+
+/// Holds back the connection changes of a controller until they settle,
+/// e.g. behind a flaky USB hub or cable connecting and disconnecting it
+/// many times a second.
+///
+/// Controllers are told apart by their [`Guid`]. The first connection of
+/// a controller not seen before is reported right away. Any later change
+/// starts a burst, held back until no change arrived for the [`window`].
+/// A settled burst reports the [`Event::ControllerDeviceRemoved`] of the
+/// controller reported connected before it, if any, then the
+/// [`Event::ControllerDeviceAdded`] of its latest connection, if it ends
+/// connected. Bursts of more changes than a single replug are preceded by
+/// [`Event::ConnectionFlapping`].
+///
+/// Used by [`Girl`] once enabled with [`Girl::set_connection_debounce`],
+/// but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{ConnectionDebounce, Guid, prelude::*};
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+/// let mut debounce = ConnectionDebounce::new(Duration::from_millis(500));
+/// let guid: Guid = "030000005e0400008e02000010010000".parse()?;
+/// let added = Event::ControllerDeviceAdded { which: 0 };
+/// let removed = |which| Event::ControllerDeviceRemoved { which };
+///
+/// // the first connection isn't delayed
+/// assert!(debounce.observe(guid, added, at(0)).is_some());
+///
+/// // a flapping burst, ending connected
+/// for (ms, which) in [(1000, 1), (1050, 2), (1100, 3), (1150, 4)] {
+///     assert!(debounce.observe(guid, removed(which), at(ms)).is_none());
+///     assert!(debounce.observe(guid, added, at(ms + 10)).is_none());
+/// }
+/// assert!(debounce.is_settling(guid));
+/// assert!(debounce.poll(at(1659)).is_empty());
+/// let [
+///     Event::ConnectionFlapping { which: 0, count: 8 },
+///     Event::ControllerDeviceRemoved { which: 1 },
+///     Event::ControllerDeviceAdded { which: 0 },
+/// ] = debounce.poll(at(1660))[..]
+/// else {
+///     panic!("not coalesced");
+/// };
+/// assert!(!debounce.is_settling(guid));
+///
+/// // a genuine quick replug is only delayed
+/// assert!(debounce.observe(guid, removed(5), at(5000)).is_none());
+/// assert!(debounce.observe(guid, added, at(5200)).is_none());
+/// let [
+///     Event::ControllerDeviceRemoved { which: 5 },
+///     Event::ControllerDeviceAdded { which: 0 },
+/// ] = debounce.poll(at(5700))[..]
+/// else {
+///     panic!("not replugged");
+/// };
+///
+/// // a burst ending disconnected
+/// assert!(debounce.observe(guid, removed(6), at(6000)).is_none());
+/// assert!(debounce.observe(guid, added, at(6010)).is_none());
+/// assert!(debounce.observe(guid, removed(7), at(6020)).is_none());
+/// let [
+///     Event::ConnectionFlapping { which: 7, count: 3 },
+///     Event::ControllerDeviceRemoved { which: 6 },
+/// ] = debounce.poll(at(6520))[..]
+/// else {
+///     panic!("not disconnected");
+/// };
+///
+/// // other events are never held back
+/// let press = Event::ControllerButtonDown { which: 7, button: Button::A };
+/// assert!(debounce.observe(guid, press, at(7000)).is_some());
+///
+/// // nor anything once disabled
+/// let mut debounce = ConnectionDebounce::disabled();
+/// assert!(debounce.observe(guid, removed(8), at(0)).is_some());
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`window`]: Self::window
+/// [`Girl`]: crate::Girl
+/// [`Girl::set_connection_debounce`]: crate::Girl::set_connection_debounce
+#[derive(Debug, Clone)]
+pub struct ConnectionDebounce {
+    /// Time without changes after which a burst settles, [`None`] if
+    /// nothing is held back.
+    window: Option<Duration>,
+    /// Controllers seen so far.
+    devices: BTreeMap<Guid, Device>,
+}
+
+/// Connection of a controller seen by [`ConnectionDebounce`].
+#[derive(Debug, Clone, Copy)]
+struct Device {
+    /// Whether the controller was last reported connected.
+    connected: bool,
+    /// Changes held back since, if any.
+    burst: Option<Burst>,
+}
+
+/// Connection changes of a controller held back by [`ConnectionDebounce`].
+#[derive(Debug, Clone, Copy)]
+struct Burst {
+    /// Instance ID of the controller reported connected before the burst,
+    /// once it was removed.
+    removed: Option<u32>,
+    /// Latest change, [`Event::ControllerDeviceAdded`] or
+    /// [`Event::ControllerDeviceRemoved`].
+    latest: Event,
+    /// When the latest change arrived.
+    at: Instant,
+    /// Number of changes.
+    count: u32,
+}
+
+impl Default for ConnectionDebounce {
+    #[inline]
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl ConnectionDebounce {
+    /// Creates a [`ConnectionDebounce`] settling bursts after `window`
+    /// without changes.
+    #[must_use]
+    #[inline]
+    pub const fn new(window: Duration) -> Self {
+        Self { window: Some(window), devices: BTreeMap::new() }
+    }
+
+    /// Creates a [`ConnectionDebounce`] holding nothing back, the default
+    /// of [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    #[must_use]
+    #[inline]
+    pub const fn disabled() -> Self {
+        Self { window: None, devices: BTreeMap::new() }
+    }
+
+    /// Gets the time without changes after which a burst settles, [`None`]
+    /// if disabled.
+    #[must_use]
+    #[inline]
+    pub const fn window(&self) -> Option<Duration> {
+        self.window
+    }
+
+    /// Observes the [`Event`] of the controller with the [`Guid`] at
+    /// `now`, returning it if it is reported right away, or [`None`] if
+    /// it is held back until the burst settles, see [`poll`].
+    ///
+    /// Only [`Event::ControllerDeviceAdded`] and
+    /// [`Event::ControllerDeviceRemoved`] are held back.
+    ///
+    /// [`poll`]: Self::poll
+    #[inline]
+    pub fn observe(
+        &mut self,
+        guid: Guid,
+        event: Event,
+        now: Instant,
+    ) -> Option<Event> {
+        if self.window.is_none() {
+            return Some(event);
+        }
+        let connected = matches!(event, Event::ControllerDeviceAdded { .. });
+        if !connected && !matches!(event, Event::ControllerDeviceRemoved { .. })
+        {
+            return Some(event);
+        }
+        let Some(device) = self.devices.get_mut(&guid) else {
+            let device = Device { connected, burst: None };
+            _ = self.devices.insert(guid, device);
+            return Some(event);
+        };
+        let burst = device.burst.get_or_insert(Burst {
+            removed: None,
+            latest: event,
+            at: now,
+            count: 0,
+        });
+        if let Event::ControllerDeviceRemoved { which } = event
+            && device.connected
+            && burst.removed.is_none()
+        {
+            burst.removed = Some(which);
+        }
+        burst.latest = event;
+        burst.at = now;
+        burst.count = burst.count.saturating_add(1);
+        None
+    }
+
+    /// Settles the bursts without changes for the [`window`] at `now`,
+    /// returning the [`Event`]s to report for them.
+    ///
+    /// [`window`]: Self::window
+    #[inline]
+    pub fn poll(&mut self, now: Instant) -> Vec<Event> {
+        let mut events = Vec::new();
+        let Some(window) = self.window else {
+            return events;
+        };
+        for (guid, device) in &mut self.devices {
+            let Some(burst) = device.burst else {
+                continue;
+            };
+            if now.saturating_duration_since(burst.at) < window {
+                continue;
+            }
+            device.burst = None;
+            let connected =
+                matches!(burst.latest, Event::ControllerDeviceAdded { .. });
+            if burst.count > 2 {
+                let which = burst.latest.which().unwrap_or_default();
+                let count = burst.count;
+                diag!(warn, "controller {guid} flapped {count} times");
+                events.push(Event::ConnectionFlapping { which, count });
+            }
+            if let Some(which) = burst.removed {
+                events.push(Event::ControllerDeviceRemoved { which });
+            }
+            if connected {
+                events.push(burst.latest);
+            }
+            device.connected = connected;
+        }
+        events
+    }
+
+    /// Checks whether connection changes of the controller with the
+    /// [`Guid`] are held back.
+    #[must_use]
+    #[inline]
+    pub fn is_settling(&self, guid: Guid) -> bool {
+        self.devices.get(&guid).is_some_and(|device| device.burst.is_some())
+    }
+
+    /// Forgets all the controllers, dropping the changes held back, so
+    /// their next connections are reported right away.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.devices.clear();
+    }
+}
@@ -0,0 +1,133 @@
+//! Deduplication of hotplug events reported by both SDL2 joystick and game
+//! controller subsystems.
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::time::Instant;
+
+/// Kind of a hotplug event.
+#[expect(clippy::exhaustive_enums, reason = "devices either come or go")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Hotplug {
+    /// Device connected. `which` is the device index.
+    Added,
+    /// Device disconnected. `which` is the instance ID.
+    Removed,
+}
+
+/// SDL2 subsystem that reported a hotplug event.
+#[expect(clippy::exhaustive_enums, reason = "SDL2 has only these two levels")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EventLevel {
+    /// Joystick subsystem (`SDL_JOYDEVICE*`).
+    Joystick,
+    /// Game controller subsystem (`SDL_CONTROLLERDEVICE*`).
+    Controller,
+}
+
+/// Merges the joystick- and controller-level hotplug events SDL2 reports
+/// for the same game controller into one.
+///
+/// The first event of a pair is reported and remembered, the matching event
+/// of the other level arriving within the `window` is dropped and consumes
+/// the remembered one. Pairs may arrive in either order, and unpaired events
+/// (e.g. from platforms that only report one level) are always reported.
+///
+/// Used by [`Girl`] to translate joystick-level events without doubles.
+///
+/// [`Girl`]: crate::Girl
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HotplugDedup {
+    /// How long a reported event waits for its counterpart.
+    window: Duration,
+    /// Reported events waiting for their counterparts, oldest first.
+    recent: VecDeque<(Hotplug, u32, EventLevel, Instant)>,
+}
+
+impl Default for HotplugDedup {
+    #[inline]
+    fn default() -> Self {
+        Self { window: Self::DEFAULT_WINDOW, recent: VecDeque::new() }
+    }
+}
+
+impl HotplugDedup {
+    /// How long a reported event waits for its counterpart.
+    pub(crate) const DEFAULT_WINDOW: Duration = Duration::from_secs(1);
+
+    /// Observes a hotplug event at `now`, returning whether it should be
+    /// reported.
+    #[inline]
+    pub(crate) fn observe(
+        &mut self,
+        kind: Hotplug,
+        which: u32,
+        level: EventLevel,
+        now: Instant,
+    ) -> bool {
+        let window = self.window;
+        self.recent
+            .retain(|&(.., seen)| now.saturating_duration_since(seen) < window);
+
+        let counterpart = self.recent.iter().position(|entry| {
+            let (seen_kind, seen_which, seen_level, _) = *entry;
+            seen_kind == kind && seen_which == which && seen_level != level
+        });
+        if let Some(index) = counterpart {
+            _ = self.recent.remove(index);
+            return false;
+        }
+
+        self.recent.push_back((kind, which, level, now));
+        true
+    }
+}
+
+/// Unit tests of [`HotplugDedup`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{
+        EventLevel::{Controller, Joystick},
+        Hotplug::{Added, Removed},
+        HotplugDedup,
+    };
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Pairs are merged in either order, each pair only once.
+    #[test]
+    fn merges_pairs() {
+        let mut dedup = HotplugDedup::default();
+        let start = Instant::now();
+
+        assert!(dedup.observe(Added, 0, Controller, at(start, 0)), "first");
+        assert!(!dedup.observe(Added, 0, Joystick, at(start, 1)), "paired");
+
+        assert!(dedup.observe(Removed, 7, Joystick, at(start, 10)), "first");
+        assert!(
+            !dedup.observe(Removed, 7, Controller, at(start, 11)),
+            "paired"
+        );
+
+        // a quick reconnect is still reported, as the pair was consumed
+        assert!(dedup.observe(Added, 0, Joystick, at(start, 20)), "reconnect");
+        assert!(!dedup.observe(Added, 0, Controller, at(start, 21)), "paired");
+    }
+
+    /// A counterpart arriving after the window is reported again.
+    #[test]
+    fn reports_late_counterparts() {
+        let mut dedup = HotplugDedup::default();
+        let start = Instant::now();
+
+        assert!(dedup.observe(Removed, 8, Controller, at(start, 100)), "first");
+        assert!(dedup.observe(Removed, 8, Joystick, at(start, 1200)), "late");
+    }
+}
@@ -0,0 +1,221 @@
+//! Per-hardware default [`InputProfile`]s applied to new connections.
+
+use alloc::collections::BTreeMap;
+
+use crate::{Button, ControllerType, Error, Guid, InputProfile};
+
+/// Stick deadzone of Switch Pro Controllers and of the pads without a
+/// dedicated default profile.
+const CONSERVATIVE_DEADZONE: f64 = 0.15;
+
+/// Default [`InputProfile`]s of [`Gamepad`]s, applied by [`Girl`] when a
+/// [`Gamepad`] is opened, including on reconnect.
+///
+/// Profiles are looked up by [`Guid`] prefix first, then by
+/// [`ControllerType`], falling back to the [`fallback`] profile. Profiles
+/// applied with [`Gamepad::apply_profile`] take precedence over all of them,
+/// see [`Girl::set_default_profile`].
+///
+/// The built-in defaults ([`DefaultProfiles::new`]):
+///
+/// - Switch Pro Controller: [`Button::A`] and [`Button::B`] swapped to match
+///   the labels, and a larger stick deadzone;
+/// - Xbox and `PlayStation` controllers: [`InputProfile::new`];
+/// - virtual controllers, processed by their driver already:
+///   [`InputProfile::new`];
+/// - other pads: conservative stick deadzones.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{DefaultProfiles, Guid, prelude::*};
+/// let mut defaults = DefaultProfiles::new();
+/// let guid = Guid::default();
+///
+/// let pro = defaults.profile_for(guid, ControllerType::SwitchPro).unwrap();
+/// assert_eq!(pro.remapped(Button::A), Button::B);
+/// assert_eq!(pro.left_stick.deadzone, 0.15);
+///
+/// let ps5 = defaults.profile_for(guid, ControllerType::Ps5).unwrap();
+/// assert_eq!(*ps5, InputProfile::new());
+///
+/// let other = defaults.profile_for(guid, ControllerType::Unknown).unwrap();
+/// assert_eq!(other.left_stick.deadzone, 0.15);
+///
+/// // GUID prefixes take precedence over the controller type
+/// let mut custom = InputProfile::new();
+/// custom.right_stick.invert_y = true;
+/// defaults.set_guid_prefix("030000004c050000", custom.clone())?;
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// let ps5 = defaults.profile_for(guid, ControllerType::Ps5).unwrap();
+/// assert_eq!(*ps5, custom);
+///
+/// defaults.set_enabled(false);
+/// assert!(defaults.profile_for(guid, ControllerType::Ps5).is_none());
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`fallback`]: Self::set_fallback
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::apply_profile`]: crate::Gamepad::apply_profile
+/// [`Girl`]: crate::Girl
+/// [`Girl::set_default_profile`]: crate::Girl::set_default_profile
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefaultProfiles {
+    /// Whether the defaults are applied at all.
+    enabled: bool,
+    /// Profiles per lowercase [`Guid`] prefix, longest prefix first.
+    by_guid: Vec<(String, InputProfile)>,
+    /// Profiles per [`ControllerType`].
+    by_type: BTreeMap<ControllerType, InputProfile>,
+    /// Profile of the [`ControllerType`]s without one.
+    fallback: InputProfile,
+}
+
+impl Default for DefaultProfiles {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DefaultProfiles {
+    /// Creates the built-in [`DefaultProfiles`], see [`DefaultProfiles`].
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        let mut switch_pro = conservative();
+        switch_pro.remap = vec![(Button::A, Button::B), (Button::B, Button::A)];
+
+        let standard = [
+            ControllerType::Xbox360,
+            ControllerType::XboxOne,
+            ControllerType::Ps3,
+            ControllerType::Ps4,
+            ControllerType::Ps5,
+            ControllerType::Virtual,
+        ]
+        .map(|ty| (ty, InputProfile::new()));
+        let mut by_type: BTreeMap<_, _> = standard.into_iter().collect();
+        drop(by_type.insert(ControllerType::SwitchPro, switch_pro));
+
+        Self {
+            enabled: true,
+            by_guid: vec![],
+            by_type,
+            fallback: conservative(),
+        }
+    }
+
+    /// Checks if the defaults are applied, see [`set_enabled`].
+    ///
+    /// [`set_enabled`]: Self::set_enabled
+    #[must_use]
+    #[inline]
+    pub const fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets whether the defaults are applied. When disabled, new connections
+    /// start with [`InputProfile::new`] unless a profile was applied to them
+    /// before.
+    #[inline]
+    pub const fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Sets the profile of the [`ControllerType`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] if the `profile` is invalid, see
+    /// [`InputProfile::validate`].
+    #[inline]
+    pub fn set(
+        &mut self,
+        controller_type: ControllerType,
+        profile: InputProfile,
+    ) -> Result<(), Error> {
+        profile.validate()?;
+        drop(self.by_type.insert(controller_type, profile));
+        Ok(())
+    }
+
+    /// Sets the profile of [`Gamepad`]s whose [`Guid`] starts with the
+    /// hexadecimal `prefix`, taking precedence over the [`ControllerType`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `prefix` is empty or not
+    /// hexadecimal, or [`Error::InvalidProfile`] if the `profile` is invalid.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn set_guid_prefix(
+        &mut self,
+        prefix: &str,
+        profile: InputProfile,
+    ) -> Result<(), Error> {
+        if prefix.is_empty() || !prefix.chars().all(|ch| ch.is_ascii_hexdigit())
+        {
+            return Err(Error::InvalidArgument { what: "guid prefix" });
+        }
+        profile.validate()?;
+
+        let prefix = prefix.to_ascii_lowercase();
+        self.by_guid.retain(|entry| entry.0 != prefix);
+        let at =
+            self.by_guid.partition_point(|entry| entry.0.len() >= prefix.len());
+        self.by_guid.insert(at, (prefix, profile));
+        Ok(())
+    }
+
+    /// Sets the profile of the [`ControllerType`]s without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] if the `profile` is invalid, see
+    /// [`InputProfile::validate`].
+    #[inline]
+    pub fn set_fallback(&mut self, profile: InputProfile) -> Result<(), Error> {
+        profile.validate()?;
+        self.fallback = profile;
+        Ok(())
+    }
+
+    /// Gets the profile for a [`Gamepad`] with the given [`Guid`] and
+    /// [`ControllerType`], [`None`] if the defaults are disabled.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn profile_for(
+        &self,
+        guid: Guid,
+        controller_type: ControllerType,
+    ) -> Option<&InputProfile> {
+        if !self.enabled {
+            return None;
+        }
+        let hex = guid.to_string();
+        let by_guid = self
+            .by_guid
+            .iter()
+            .find(|entry| hex.starts_with(entry.0.as_str()))
+            .map(|entry| &entry.1);
+        Some(
+            by_guid
+                .or_else(|| self.by_type.get(&controller_type))
+                .unwrap_or(&self.fallback),
+        )
+    }
+}
+
+/// Creates [`InputProfile::new`] with [`CONSERVATIVE_DEADZONE`] sticks.
+const fn conservative() -> InputProfile {
+    let mut profile = InputProfile::new();
+    profile.left_stick.deadzone = CONSERVATIVE_DEADZONE;
+    profile.right_stick.deadzone = CONSERVATIVE_DEADZONE;
+    profile
+}
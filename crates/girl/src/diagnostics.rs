@@ -0,0 +1,677 @@
+//! Scripted controller test walking the user through every control.
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+
+use crate::{Button, Event, Gamepad, InputBind, Stick, Trigger};
+
+/// Control of a controller tested by [`Diagnostics`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Control {
+    /// A single [`Button`].
+    Button(Button),
+    /// Both axes of a [`Stick`].
+    Stick(Stick),
+    /// A [`Trigger`].
+    Trigger(Trigger),
+    /// The touchpads, any of them.
+    Touchpad,
+}
+
+/// What [`Diagnostics`] asks the user to do, rendered by the application
+/// however it wants, see [`Diagnostics::prompt`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticPrompt {
+    /// Let go of every control, so stick drift can be measured.
+    Rest,
+    /// Press the [`Button`].
+    PressButton(Button),
+    /// Move the [`Stick`] all the way up, down, left and right.
+    MoveStick(Stick),
+    /// Pull the [`Trigger`] all the way.
+    PullTrigger(Trigger),
+    /// Touch the touchpad.
+    TouchTouchpad,
+}
+
+/// Thresholds and timeouts of [`Diagnostics`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticsConfig {
+    /// How long the user has to complete a step before it times out.
+    pub step_timeout: Duration,
+    /// How long the controls must be left alone to measure stick drift.
+    pub rest_time: Duration,
+    /// Stick deflection or trigger value counted as reaching the end of its
+    /// range.
+    pub full_range: f64,
+    /// Stick deflection at rest reported as drift.
+    pub drift_threshold: f64,
+}
+
+impl Default for DiagnosticsConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiagnosticsConfig {
+    /// Creates the default [`DiagnosticsConfig`]: 10 second steps, 1 second
+    /// of rest, a full range of `0.95` and a drift threshold of `0.1`.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            step_timeout: Duration::from_secs(10),
+            rest_time: Duration::from_secs(1),
+            full_range: 0.95,
+            drift_threshold: 0.1,
+        }
+    }
+}
+
+/// How a step of [`Diagnostics`] ended.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepOutcome {
+    /// The control responded over its full range in time.
+    Passed,
+    /// The step timed out, see the [`Anomaly`] reported for it.
+    TimedOut,
+    /// The step was skipped, by the user or for a missing capability.
+    Skipped,
+}
+
+/// Observations of a [`Control`] tested by [`Diagnostics`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlReport {
+    /// Tested [`Control`].
+    pub control: Control,
+    /// How its step ended.
+    pub outcome: StepOutcome,
+    /// Range reached from `0.0` to `1.0`: the smallest of the four extremes
+    /// of a [`Stick`], the farthest pull of a [`Trigger`], and `1.0` for a
+    /// pressed [`Button`] or touched touchpad.
+    pub range: f64,
+    /// Time from the prompt to the first response of the control, if any.
+    pub latency: Option<Duration>,
+}
+
+/// Problem found by [`Diagnostics`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anomaly {
+    /// The control never responded before its step timed out.
+    Dead(Control),
+    /// The control responded, but never reached its full range.
+    LimitedRange {
+        /// Control with the limited range.
+        control: Control,
+        /// Range reached, see [`ControlReport::range`].
+        reached: f64,
+    },
+    /// The [`Stick`] was off center while the controls were left alone.
+    Drift {
+        /// Drifting [`Stick`].
+        stick: Stick,
+        /// Largest deflection at rest.
+        offset: f64,
+    },
+}
+
+/// Outcome of [`Diagnostics`], e.g. to attach to a support ticket.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticReport {
+    /// Every tested [`Control`], in the order of the steps.
+    pub controls: Vec<ControlReport>,
+    /// Every problem found, in the order found.
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl DiagnosticReport {
+    /// Checks if no [`Anomaly`] was found.
+    #[must_use]
+    #[inline]
+    pub const fn passed(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Gets the [`ControlReport`] of the [`Control`], if tested.
+    #[must_use]
+    #[inline]
+    pub fn control(&self, control: Control) -> Option<&ControlReport> {
+        self.controls.iter().find(|report| report.control == control)
+    }
+}
+
+/// Step of [`Diagnostics`], with whether the controller has the control.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    /// Measuring stick drift with the controls left alone.
+    Rest,
+    /// Testing a [`Control`] the controller has, or skipping it.
+    Control(Control, bool),
+}
+
+/// Progress of the current step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Current {
+    /// Current step.
+    step: Step,
+    /// When the step started, on the clock of [`Diagnostics::observe`].
+    started: Option<Duration>,
+    /// When the control first responded.
+    responded: Option<Duration>,
+    /// Farthest deflections reached: left, right, up and down for a
+    /// [`Stick`], the first one only for a [`Trigger`].
+    extremes: [f64; 4],
+}
+
+/// State machine walking the user through testing every control of a
+/// controller, e.g. for a support screen.
+///
+/// The user presses each [`Button`], moves each [`Stick`] to its extremes,
+/// pulls each [`Trigger`] fully and touches the touchpad, after leaving the
+/// controls alone to measure stick drift.
+///
+/// Feed it the [`Event`]s of the controller with [`observe`] and [`tick`]
+/// it to time steps out, rendering its [`prompt`] meanwhile. Once every
+/// step ended it yields a [`DiagnosticReport`] of the range and latency of
+/// every control and of the anomalies found: dead controls, limited ranges
+/// and drift. Steps of controls the controller lacks are skipped, and the
+/// user can [`skip`] any other one.
+///
+/// Times are on the caller's clock, e.g. the time since the test started
+/// or `SimGirl::now`.
+///
+/// # Examples
+///
+/// A scripted test with `SimGirl`:
+///
+/// ```
+/// # #[cfg(feature = "sim")]
+/// # {
+/// # use std::time::Duration;
+/// # use girl::{
+/// #     Anomaly, Control, DiagnosticPrompt as Prompt, Diagnostics,
+/// #     DiagnosticsConfig, SimGirl, StepOutcome, prelude::*,
+/// # };
+/// let mut sim = SimGirl::new();
+/// let id = sim.connect();
+/// let config = DiagnosticsConfig::new();
+/// let buttons = Button::A | Button::B;
+/// let mut diagnostics = Diagnostics::new(id.raw(), config, buttons, false);
+///
+/// let run = |sim: &mut SimGirl, diagnostics: &mut Diagnostics| {
+///     sim.advance(Duration::from_millis(100));
+///     while let Some(event) = sim.event() {
+///         diagnostics.observe(&event, sim.now());
+///     }
+///     diagnostics.tick(sim.now());
+/// };
+///
+/// // drift is measured at rest
+/// assert_eq!(diagnostics.prompt(), Some(Prompt::Rest));
+/// for _ in 0..11 {
+///     run(&mut sim, &mut diagnostics);
+/// }
+/// assert_eq!(diagnostics.prompt(), Some(Prompt::PressButton(Button::A)));
+/// sim.press(id, Button::A);
+/// run(&mut sim, &mut diagnostics);
+///
+/// // B is dead, nothing happens until its step times out
+/// let press_b = Some(Prompt::PressButton(Button::B));
+/// while diagnostics.prompt() == press_b {
+///     run(&mut sim, &mut diagnostics);
+/// }
+///
+/// // the left stick doesn't go all the way down
+/// let move_left = Some(Prompt::MoveStick(Stick::Left));
+/// assert_eq!(diagnostics.prompt(), move_left);
+/// for position in [[-1.0, 0.0], [1.0, 0.0], [0.0, -1.0], [0.0, 0.7]] {
+///     sim.move_stick(id, Stick::Left, position);
+///     run(&mut sim, &mut diagnostics);
+/// }
+/// sim.move_stick(id, Stick::Left, [0.0, 0.0]);
+/// while diagnostics.prompt() == move_left {
+///     run(&mut sim, &mut diagnostics);
+/// }
+///
+/// // a healthy right stick and triggers
+/// for position in [[-1.0, 0.0], [1.0, 0.0], [0.0, -1.0], [0.0, 1.0]] {
+///     sim.move_stick(id, Stick::Right, position);
+///     run(&mut sim, &mut diagnostics);
+/// }
+/// for trigger in [Trigger::Left, Trigger::Right] {
+///     assert_eq!(diagnostics.prompt(), Some(Prompt::PullTrigger(trigger)));
+///     sim.move_trigger(id, trigger, 1.0);
+///     run(&mut sim, &mut diagnostics);
+/// }
+///
+/// // no touchpad, so its step is skipped
+/// assert!(diagnostics.is_finished());
+/// let report = diagnostics.finish();
+/// let touchpad = report.control(Control::Touchpad).unwrap();
+/// assert_eq!(touchpad.outcome, StepOutcome::Skipped);
+/// let a = report.control(Control::Button(Button::A)).unwrap();
+/// assert_eq!(a.outcome, StepOutcome::Passed);
+/// assert_eq!(a.latency, Some(Duration::from_millis(100)));
+///
+/// let [dead, limited] = report.anomalies.as_slice() else {
+///     panic!("unexpected anomalies: {:?}", report.anomalies);
+/// };
+/// assert_eq!(*dead, Anomaly::Dead(Control::Button(Button::B)));
+/// let Anomaly::LimitedRange { control, reached } = *limited else {
+///     panic!("not limited: {limited:?}");
+/// };
+/// assert_eq!(control, Control::Stick(Stick::Left));
+/// assert!((reached - 0.7).abs() < 1e-9);
+/// # }
+/// ```
+///
+/// A full pass, with a drifting stick at rest:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{
+/// #     Anomaly, Control, Diagnostics, DiagnosticsConfig, StepOutcome,
+/// #     prelude::*,
+/// # };
+/// let config = DiagnosticsConfig::new();
+/// let mut diagnostics = Diagnostics::new(0, config, Button::A, false);
+/// let ms = Duration::from_millis;
+///
+/// let drift = Event::stick_motion(0, Stick::Right, [0.15, 0.0]);
+/// diagnostics.observe(&drift, ms(0));
+/// diagnostics.tick(ms(1000));
+/// diagnostics.observe(&Event::button_down(0, Button::A), ms(1250));
+/// for stick in [Stick::Left, Stick::Right] {
+///     for offset in [[-1.0, 0.0], [1.0, 0.0], [0.0, -1.0], [0.0, 1.0]] {
+///         let motion = Event::stick_motion(0, stick, offset);
+///         diagnostics.observe(&motion, ms(1500));
+///     }
+/// }
+/// for trigger in [Trigger::Left, Trigger::Right] {
+///     diagnostics.observe(&Event::trigger_motion(0, trigger, 1.0), ms(1750));
+/// }
+///
+/// assert!(diagnostics.is_finished());
+/// let report = diagnostics.finish();
+/// let passed = |control| {
+///     report.control(control).map(|control| control.outcome)
+///         == Some(StepOutcome::Passed)
+/// };
+/// assert!(passed(Control::Button(Button::A)));
+/// assert!(passed(Control::Stick(Stick::Right)));
+/// assert!(passed(Control::Trigger(Trigger::Left)));
+/// let [Anomaly::Drift { stick: Stick::Right, .. }] = *report.anomalies else {
+///     panic!("no drift: {:?}", report.anomalies);
+/// };
+/// ```
+///
+/// [`observe`]: Self::observe
+/// [`tick`]: Self::tick
+/// [`prompt`]: Self::prompt
+/// [`skip`]: Self::skip
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostics {
+    /// Instance ID of the tested controller.
+    which: u32,
+    /// Thresholds and timeouts.
+    config: DiagnosticsConfig,
+    /// Steps after the current one.
+    steps: VecDeque<Step>,
+    /// Current step, [`None`] once every step ended.
+    current: Option<Current>,
+    /// Last position of every [`Stick`].
+    sticks: [[f64; 2]; 2],
+    /// Largest deflection of every [`Stick`] at rest.
+    rest: [f64; 2],
+    /// Outcome of the steps ended so far.
+    report: DiagnosticReport,
+}
+
+impl Diagnostics {
+    /// Creates [`Diagnostics`] of the controller with the instance ID,
+    /// testing the `buttons` and, if it has one, the touchpad. Steps of the
+    /// other [`Button`]s and of a missing touchpad are skipped.
+    #[must_use]
+    #[inline]
+    pub fn new(
+        which: u32,
+        config: DiagnosticsConfig,
+        buttons: Button,
+        touchpad: bool,
+    ) -> Self {
+        let mut steps = VecDeque::from([Step::Rest]);
+        steps.extend(Button::all().iter().map(|button| {
+            Step::Control(Control::Button(button), buttons.contains(button))
+        }));
+        for stick in [Stick::Left, Stick::Right] {
+            steps.push_back(Step::Control(Control::Stick(stick), true));
+        }
+        for trigger in [Trigger::Left, Trigger::Right] {
+            steps.push_back(Step::Control(Control::Trigger(trigger), true));
+        }
+        steps.push_back(Step::Control(Control::Touchpad, touchpad));
+
+        let mut this = Self {
+            which,
+            config,
+            steps,
+            current: None,
+            sticks: [[0.0f64; 2]; 2],
+            rest: [0.0f64; 2],
+            report: DiagnosticReport::default(),
+        };
+        this.advance(None);
+        this
+    }
+
+    /// Creates [`Diagnostics`] of the [`Gamepad`], testing the [`Button`]s
+    /// bound by its mapping, see [`Gamepad::bind_for_button`], and its
+    /// touchpad if it has one.
+    #[must_use]
+    #[inline]
+    pub fn for_gamepad(gamepad: &Gamepad, config: DiagnosticsConfig) -> Self {
+        let buttons = Button::all()
+            .iter()
+            .filter(|&button| {
+                gamepad
+                    .bind_for_button(button)
+                    .is_ok_and(|bind| bind != InputBind::None)
+            })
+            .collect();
+        #[cfg(feature = "touchpad")]
+        let touchpad = gamepad.num_touchpads() > 0;
+        #[cfg(not(feature = "touchpad"))]
+        let touchpad = false;
+        Self::new(gamepad.id().raw(), config, buttons, touchpad)
+    }
+
+    /// Gets what the user is asked to do, [`None`] once every step ended.
+    #[must_use]
+    #[inline]
+    pub fn prompt(&self) -> Option<DiagnosticPrompt> {
+        Some(match self.current?.step {
+            Step::Rest => DiagnosticPrompt::Rest,
+            Step::Control(Control::Button(button), _) => {
+                DiagnosticPrompt::PressButton(button)
+            }
+            Step::Control(Control::Stick(stick), _) => {
+                DiagnosticPrompt::MoveStick(stick)
+            }
+            Step::Control(Control::Trigger(trigger), _) => {
+                DiagnosticPrompt::PullTrigger(trigger)
+            }
+            Step::Control(Control::Touchpad, _) => {
+                DiagnosticPrompt::TouchTouchpad
+            }
+        })
+    }
+
+    /// Gets how long is left of the current step at `at`, e.g. for a
+    /// countdown. [`None`] once every step ended.
+    #[must_use]
+    #[inline]
+    pub fn time_left(&self, at: Duration) -> Option<Duration> {
+        let current = self.current?;
+        let length = if current.step == Step::Rest {
+            self.config.rest_time
+        } else {
+            self.config.step_timeout
+        };
+        let elapsed = current
+            .started
+            .map_or(Duration::ZERO, |started| at.saturating_sub(started));
+        Some(length.saturating_sub(elapsed))
+    }
+
+    /// Checks if every step ended.
+    #[must_use]
+    #[inline]
+    pub const fn is_finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Gets the outcome of the steps ended so far.
+    #[must_use]
+    #[inline]
+    pub const fn report(&self) -> &DiagnosticReport {
+        &self.report
+    }
+
+    /// Observes an [`Event`] at `at`, ending the current step if the
+    /// control tested responded over its full range. [`Event`]s of other
+    /// controllers are ignored.
+    #[inline]
+    pub fn observe(&mut self, event: &Event, at: Duration) {
+        if event.which() != Some(self.which) {
+            return;
+        }
+        if let Event::ControllerStickMotion { stick, offset, .. } = *event {
+            let [x, y] = offset;
+            let (last, rest) = match stick {
+                Stick::Left => (&mut self.sticks[0], &mut self.rest[0]),
+                Stick::Right => (&mut self.sticks[1], &mut self.rest[1]),
+            };
+            *last = offset;
+            if let Some(Current { step: Step::Rest, .. }) = self.current {
+                *rest = rest.max(x.hypot(y));
+            }
+        }
+        let Some(current) = self.current.as_mut() else {
+            return;
+        };
+        let started = *current.started.get_or_insert(at);
+        let Step::Control(control, true) = current.step else {
+            self.tick(at);
+            return;
+        };
+        let Some(range) = response(control, event, &mut current.extremes)
+        else {
+            self.tick(at);
+            return;
+        };
+        let latency = at.saturating_sub(started);
+        let latency = *current.responded.get_or_insert(latency);
+        if range >= self.config.full_range {
+            self.end(control, StepOutcome::Passed, range, Some(latency), at);
+        } else {
+            self.tick(at);
+        }
+    }
+
+    /// Advances the clock to `at`, ending the rest step once its time is up
+    /// and timing out the current step.
+    #[inline]
+    pub fn tick(&mut self, at: Duration) {
+        let Some(current) = self.current.as_mut() else {
+            return;
+        };
+        let started = *current.started.get_or_insert(at);
+        let elapsed = at.saturating_sub(started);
+        let step = current.step;
+        match step {
+            Step::Rest => {
+                if elapsed >= self.config.rest_time {
+                    self.end_rest(at);
+                }
+            }
+            Step::Control(control, _) => {
+                if elapsed >= self.config.step_timeout {
+                    let range = range_of(control, &current.extremes);
+                    let latency = current.responded;
+                    self.end(
+                        control,
+                        StepOutcome::TimedOut,
+                        range,
+                        latency,
+                        at,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Skips the current step at `at`, e.g. for a control the user knows to
+    /// be missing. Skipping the rest step skips drift detection.
+    #[inline]
+    pub fn skip(&mut self, at: Duration) {
+        let Some(current) = self.current else {
+            return;
+        };
+        if let Step::Control(control, _) = current.step {
+            self.end(control, StepOutcome::Skipped, 0.0, None, at);
+        } else {
+            self.advance(Some(at));
+        }
+    }
+
+    /// Ends the diagnostics, skipping the steps left, and yields the
+    /// [`DiagnosticReport`].
+    #[must_use]
+    #[inline]
+    pub fn finish(mut self) -> DiagnosticReport {
+        while let Some(Current { step, .. }) = self.current {
+            if let Step::Control(control, _) = step {
+                let outcome = StepOutcome::Skipped;
+                self.end(control, outcome, 0.0, None, Duration::ZERO);
+            } else {
+                self.advance(None);
+            }
+        }
+        self.report
+    }
+
+    /// Ends the rest step at `at`, reporting drifting [`Stick`]s.
+    fn end_rest(&mut self, at: Duration) {
+        for (stick, offset) in
+            [Stick::Left, Stick::Right].into_iter().zip(self.rest)
+        {
+            if offset >= self.config.drift_threshold {
+                self.report.anomalies.push(Anomaly::Drift { stick, offset });
+            }
+        }
+        self.advance(Some(at));
+    }
+
+    /// Ends the step of the [`Control`] at `at`, reporting it.
+    fn end(
+        &mut self,
+        control: Control,
+        outcome: StepOutcome,
+        range: f64,
+        latency: Option<Duration>,
+        at: Duration,
+    ) {
+        let report = ControlReport { control, outcome, range, latency };
+        self.report.controls.push(report);
+        if outcome == StepOutcome::TimedOut {
+            let anomaly = if latency.is_none() {
+                Anomaly::Dead(control)
+            } else {
+                Anomaly::LimitedRange { control, reached: range }
+            };
+            self.report.anomalies.push(anomaly);
+        }
+        self.advance(Some(at));
+    }
+
+    /// Starts the next step at `at`, skipping those of missing controls.
+    fn advance(&mut self, at: Option<Duration>) {
+        self.current = None;
+        while let Some(step) = self.steps.pop_front() {
+            if let Step::Control(control, false) = step {
+                let outcome = StepOutcome::Skipped;
+                let report = ControlReport {
+                    control,
+                    outcome,
+                    range: 0.0,
+                    latency: None,
+                };
+                self.report.controls.push(report);
+                continue;
+            }
+            if step == Step::Rest {
+                self.rest = self.sticks.map(|[x, y]| x.hypot(y));
+            }
+            self.current = Some(Current {
+                step,
+                started: at,
+                responded: None,
+                extremes: [0.0f64; 4],
+            });
+            return;
+        }
+    }
+}
+
+/// Records the response of the [`Control`] to the [`Event`] in its
+/// `extremes`, returning the range reached so far, or [`None`] if the
+/// [`Event`] is not of the [`Control`].
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn response(
+    control: Control,
+    event: &Event,
+    extremes: &mut [f64; 4],
+) -> Option<f64> {
+    match (control, *event) {
+        (
+            Control::Button(button),
+            Event::ControllerButtonDown { button: pressed, .. }
+            | Event::ControllerButtonDownWithState { button: pressed, .. },
+        ) if pressed == button => {
+            extremes[0] = 1.0f64;
+        }
+        (
+            Control::Stick(stick),
+            Event::ControllerStickMotion {
+                stick: moved, offset: [x, y], ..
+            },
+        ) if moved == stick => {
+            extremes[0] = extremes[0].max(-x);
+            extremes[1] = extremes[1].max(x);
+            extremes[2] = extremes[2].max(-y);
+            extremes[3] = extremes[3].max(y);
+        }
+        (
+            Control::Trigger(trigger),
+            Event::ControllerTriggerMotion { trigger: pulled, offset, .. },
+        ) if pulled == trigger => {
+            extremes[0] = extremes[0].max(offset);
+        }
+        #[cfg(feature = "touchpad")]
+        (Control::Touchpad, Event::ControllerTouchpad(touch))
+            if touch.action == crate::TouchpadAction::Touched =>
+        {
+            extremes[0] = 1.0f64;
+        }
+        _ => return None,
+    }
+    Some(range_of(control, extremes))
+}
+
+/// Gets the range of the [`Control`] reached with the `extremes`.
+fn range_of(control: Control, extremes: &[f64; 4]) -> f64 {
+    match control {
+        Control::Stick(_) => {
+            extremes.iter().copied().fold(f64::INFINITY, f64::min).max(0.0)
+        }
+        Control::Button(_) | Control::Trigger(_) | Control::Touchpad => {
+            extremes[0]
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Delivery of [`Sensor`] updates at a divided rate.
+
+use alloc::collections::BTreeMap;
+
+use crate::{Error, Event, Sensor};
+
+/// How a [`SensorDivider`] folds the skipped [`Sensor`] updates into the
+/// delivered one.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorAccumulation {
+    /// Skipped updates are dropped, only the latest one is delivered.
+    #[default]
+    Latest,
+    /// The updates are averaged, e.g. for an accelerometer.
+    Average,
+    /// The updates are added up, integrating e.g. a gyroscope in units of
+    /// its sample period: multiply by the period to get an angle.
+    Sum,
+}
+
+/// Delivers only every n-th [`Event::ControllerSensorUpdated`] of every
+/// device and [`Sensor`], e.g. to run game logic at 60 Hz off a 250 Hz
+/// gyroscope while keeping event-driven delivery.
+///
+/// Every [`Sensor`] has its own divisor and [`SensorAccumulation`], and
+/// updates are counted per controller instance ID independently. Other
+/// [`Event`]s pass through.
+///
+/// Used by [`Girl::set_sensor_event_divisor`], but can also be driven
+/// directly.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{SensorAccumulation, SensorDivider, prelude::*};
+/// let mut divider = SensorDivider::new();
+/// divider.set_divisor(Sensor::Gyroscope, 4)?;
+/// divider.set_accumulation(Sensor::Gyroscope, SensorAccumulation::Sum);
+/// divider.set_divisor(Sensor::Accelerometer, 2)?;
+/// let average = SensorAccumulation::Average;
+/// divider.set_accumulation(Sensor::Accelerometer, average);
+/// assert!(divider.set_divisor(Sensor::Gyroscope, 0).is_err());
+///
+/// let update = |which, sensor, value| Event::ControllerSensorUpdated {
+///     which,
+///     sensor,
+///     data: [value, 0.0, -value],
+/// };
+///
+/// // a burst of 10 gyroscope updates is integrated into 2 events
+/// let delivered: Vec<_> = (1..=10)
+///     .filter_map(|step| {
+///         divider.feed(update(1, Sensor::Gyroscope, f64::from(step)))
+///     })
+///     .collect();
+/// let [first, second] = delivered[..] else { panic!("not divided") };
+/// let Event::ControllerSensorUpdated { data, .. } = first else {
+///     panic!("not a sensor update");
+/// };
+/// assert_eq!(data, [10.0, 0.0, -10.0]);
+/// let Event::ControllerSensorUpdated { data, .. } = second else {
+///     panic!("not a sensor update");
+/// };
+/// assert_eq!(data, [26.0, 0.0, -26.0]);
+///
+/// // every device and sensor is counted on its own
+/// assert!(divider.feed(update(2, Sensor::Gyroscope, 1.0)).is_none());
+/// assert!(divider.feed(update(1, Sensor::Accelerometer, 1.0)).is_none());
+/// let Some(Event::ControllerSensorUpdated { data, .. }) =
+///     divider.feed(update(1, Sensor::Accelerometer, 3.0))
+/// else {
+///     panic!("not delivered");
+/// };
+/// assert_eq!(data, [2.0, 0.0, -2.0]);
+///
+/// // other sensors and events pass through
+/// assert!(divider.feed(update(1, Sensor::LeftGyroscope, 1.0)).is_some());
+/// assert!(divider.feed(Event::Quit).is_some());
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::set_sensor_event_divisor`]: crate::Girl::set_sensor_event_divisor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SensorDivider {
+    /// Divisor and [`SensorAccumulation`] of every configured [`Sensor`].
+    settings: BTreeMap<Sensor, (u32, SensorAccumulation)>,
+    /// Updates skipped so far and their sum, by controller instance ID and
+    /// [`Sensor`].
+    pending: BTreeMap<(u32, Sensor), (u32, [f64; 3])>,
+}
+
+impl SensorDivider {
+    /// Creates a [`SensorDivider`] delivering every update.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { settings: BTreeMap::new(), pending: BTreeMap::new() }
+    }
+
+    /// Sets the divisor of the [`Sensor`], delivering only every `divisor`-th
+    /// update of every device. A `divisor` of `1` delivers every update.
+    ///
+    /// Updates skipped under the previous divisor are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `divisor` is `0`.
+    #[inline]
+    pub fn set_divisor(
+        &mut self,
+        sensor: Sensor,
+        divisor: u32,
+    ) -> Result<(), Error> {
+        if divisor == 0 {
+            return Err(Error::InvalidArgument { what: "divisor" });
+        }
+        let accumulation = self.accumulation(sensor);
+        _ = self.settings.insert(sensor, (divisor, accumulation));
+        self.pending.retain(|&(_, pending), _| pending != sensor);
+        Ok(())
+    }
+
+    /// Gets the divisor of the [`Sensor`], `1` unless set with
+    /// [`set_divisor`].
+    ///
+    /// [`set_divisor`]: Self::set_divisor
+    #[must_use]
+    #[inline]
+    pub fn divisor(&self, sensor: Sensor) -> u32 {
+        self.settings.get(&sensor).map_or(1, |&(divisor, _)| divisor)
+    }
+
+    /// Sets how the skipped updates of the [`Sensor`] are folded into the
+    /// delivered one.
+    #[inline]
+    pub fn set_accumulation(
+        &mut self,
+        sensor: Sensor,
+        accumulation: SensorAccumulation,
+    ) {
+        let divisor = self.divisor(sensor);
+        _ = self.settings.insert(sensor, (divisor, accumulation));
+    }
+
+    /// Gets how the skipped updates of the [`Sensor`] are folded into the
+    /// delivered one, [`SensorAccumulation::Latest`] unless set with
+    /// [`set_accumulation`].
+    ///
+    /// [`set_accumulation`]: Self::set_accumulation
+    #[must_use]
+    #[inline]
+    pub fn accumulation(&self, sensor: Sensor) -> SensorAccumulation {
+        self.settings
+            .get(&sensor)
+            .map_or(SensorAccumulation::Latest, |&(_, accumulation)| {
+                accumulation
+            })
+    }
+
+    /// Feeds an [`Event`], returning it unless it is a skipped
+    /// [`Sensor`] update.
+    #[inline]
+    pub fn feed(&mut self, event: Event) -> Option<Event> {
+        if let Event::ControllerDeviceRemoved { which: removed } = event {
+            self.pending.retain(|&(which, _), _| which != removed);
+        }
+        let Event::ControllerSensorUpdated { which, sensor, data } = event
+        else {
+            return Some(event);
+        };
+        let (divisor, accumulation) = self
+            .settings
+            .get(&sensor)
+            .copied()
+            .unwrap_or((1, SensorAccumulation::Latest));
+        if divisor <= 1 {
+            return Some(event);
+        }
+
+        let pending = self.pending.entry((which, sensor)).or_default();
+        pending.0 = pending.0.saturating_add(1);
+        for (total, value) in pending.1.iter_mut().zip(data) {
+            *total += value;
+        }
+        if pending.0 < divisor {
+            return None;
+        }
+        let (skipped, totals) = self.pending.remove(&(which, sensor))?;
+        let delivered = match accumulation {
+            SensorAccumulation::Latest => data,
+            SensorAccumulation::Average => {
+                totals.map(|total| total / f64::from(skipped))
+            }
+            SensorAccumulation::Sum => totals,
+        };
+        Some(Event::ControllerSensorUpdated { which, sensor, data: delivered })
+    }
+}
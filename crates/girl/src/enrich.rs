@@ -0,0 +1,151 @@
+//! [`Event`]s enriched with device metadata, e.g. for logging pipelines.
+
+use core::fmt;
+
+use crate::{ConnectionKind, Event, FormFactor, Gamepad, Guid};
+
+/// Metadata of a [`Gamepad`], cached by [`Girl`] when it is opened.
+///
+/// [`Girl`]: crate::Girl
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceInfo {
+    /// Name of the [`Gamepad`], see [`Gamepad::name`].
+    pub name: String,
+    /// [`Guid`] of the [`Gamepad`], see [`Gamepad::guid`].
+    pub guid: Guid,
+    /// [`FormFactor`] of the [`Gamepad`], see [`Gamepad::form_factor`].
+    pub form_factor: FormFactor,
+    /// How the [`Gamepad`] is connected, see [`Gamepad::connection_kind`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub connection: ConnectionKind,
+}
+
+/// Displays the name, [`FormFactor`], [`ConnectionKind`] (if known) and
+/// [`Guid`] of the device.
+impl fmt::Display for DeviceInfo {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.name.is_empty() { "Gamepad" } else { &self.name };
+        write!(f, "{name} ({}, ", self.form_factor.name())?;
+        if self.connection != ConnectionKind::Unknown {
+            write!(f, "{}, ", self.connection)?;
+        }
+        write!(f, "{})", self.guid)
+    }
+}
+
+impl DeviceInfo {
+    /// Creates a new [`DeviceInfo`] of an unknown [`ConnectionKind`], see
+    /// [`with_connection`].
+    ///
+    /// [`with_connection`]: Self::with_connection
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        name: String,
+        guid: Guid,
+        form_factor: FormFactor,
+    ) -> Self {
+        let connection = ConnectionKind::Unknown;
+        Self { name, guid, form_factor, connection }
+    }
+
+    /// Sets the [`ConnectionKind`] of the device.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ConnectionKind, DeviceInfo, prelude::*};
+    /// let guid = "050000004c050000e60c000000810000".parse()?;
+    /// let name = "PS5 Controller".to_owned();
+    /// let device = DeviceInfo::new(name, guid, FormFactor::Standard)
+    ///     .with_connection(ConnectionKind::Bluetooth);
+    /// assert_eq!(
+    ///     device.to_string(),
+    ///     "PS5 Controller (Standard, Bluetooth, \
+    ///      050000004c050000e60c000000810000)",
+    /// );
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_connection(mut self, connection: ConnectionKind) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// Reads the [`DeviceInfo`] of the [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub fn from_gamepad(gamepad: &Gamepad) -> Self {
+        Self::new(gamepad.name(), gamepad.guid(), gamepad.form_factor())
+            .with_connection(gamepad.connection_kind())
+    }
+}
+
+/// [`Event`] with the [`DeviceInfo`] of the device it originates from, see
+/// [`Girl::enrich`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{DeviceInfo, EnrichedEvent, prelude::*};
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// let name = "PS5 Controller".to_owned();
+/// let device = DeviceInfo::new(name, guid, FormFactor::Standard);
+/// let event = Event::ControllerDeviceRemoved { which: 3 };
+///
+/// let enriched = EnrichedEvent::new(event, Some(device));
+/// assert_eq!(
+///     enriched.to_string(),
+///     "#3 PS5 Controller (Standard, 030000004c050000e60c000000010000): \
+///      ControllerDeviceRemoved { which: 3 }",
+/// );
+/// assert_eq!(EnrichedEvent::new(Event::Quit, None).to_string(), "Quit");
+///
+/// # #[cfg(feature = "serde")]
+/// # {
+/// let json = serde_json::to_string(&enriched).unwrap();
+/// assert!(json.contains(r#""guid":"030000004c050000e60c000000010000""#));
+/// # }
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::enrich`]: crate::Girl::enrich
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnrichedEvent {
+    /// The [`Event`] itself.
+    pub event: Event,
+    /// Metadata of the device the [`Event`] originates from, if it has one
+    /// and it is known.
+    pub device: Option<DeviceInfo>,
+}
+
+/// Displays the [`EnrichedEvent`] as a single log line.
+impl fmt::Display for EnrichedEvent {
+    #[inline]
+    #[expect(clippy::use_debug, reason = "`Event` has no `Display`")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(which) = self.event.which() {
+            write!(f, "#{which} ")?;
+            match self.device.as_ref() {
+                Some(device) => write!(f, "{device}: ")?,
+                None => f.write_str("(unknown device): ")?,
+            }
+        }
+        write!(f, "{:?}", self.event)
+    }
+}
+
+impl EnrichedEvent {
+    /// Creates a new [`EnrichedEvent`].
+    #[must_use]
+    #[inline]
+    pub const fn new(event: Event, device: Option<DeviceInfo>) -> Self {
+        Self { event, device }
+    }
+}
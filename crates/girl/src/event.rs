@@ -1,19 +1,55 @@
 //! Input event types and conversion from SDL events.
 
+use core::time::Duration;
+
+#[cfg(feature = "sdl2")]
 use sdl2::{controller::Axis as SdlAxis, event::Event as SdlEvent};
 
-#[cfg(feature = "sensors")]
-use crate::Sensor;
 #[cfg(feature = "touchpad")]
 use crate::TouchpadEvent;
+use crate::{Button, Direction, RemapChanges, Stick, Trigger};
+#[cfg(feature = "sdl2")]
 use crate::{
-    Button, Gamepad, Stick, Trigger,
-    gamepad::{input::AXIS_MAX, map},
+    Gamepad, NavigationAction,
+    normalize::{self, apply_deadzone},
 };
+#[cfg(feature = "sensors")]
+use crate::{Sensor, gamepad::sensors::SENSOR_DEADZONE};
 
 /// Input events that can be processed by the library.
+///
+/// # Fabricating events
+///
+/// To test code consuming [`Event`]s without a controller, create them
+/// with the constructors like [`Event::button_down`], or the touchpad and
+/// sensor ones with the `EventBuilder` of the `testing` feature. These are
+/// the supported way to fabricate [`Event`]s, as variants may get more
+/// fields. The `which` controller instance ID of an [`Event`] is the raw
+/// value of the [`GamepadId`] of its [`Gamepad`], see
+/// [`GamepadId::from_raw`].
+///
+/// ```
+/// # use girl::prelude::*;
+/// let pad = GamepadId::from_raw(0);
+/// let events = [
+///     Event::device_added(pad.raw()),
+///     Event::button_down(pad.raw(), Button::A),
+///     Event::stick_motion(pad.raw(), Stick::Left, [0.0, 1.0]),
+///     Event::button_up(pad.raw(), Button::A),
+/// ];
+/// let presses = events
+///     .iter()
+///     .filter(|event| matches!(event, Event::ControllerButtonDown { .. }))
+///     .count();
+/// assert_eq!(presses, 1);
+/// assert!(events.iter().all(|event| event.which() == Some(pad.raw())));
+/// ```
+///
+/// [`GamepadId`]: crate::GamepadId
+/// [`GamepadId::from_raw`]: crate::GamepadId::from_raw
 #[non_exhaustive]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// Application quit requested.
     Quit,
@@ -46,6 +82,22 @@ pub enum Event {
         button: Button,
     },
 
+    /// Button pressed, with the other [`Button`]s held at that moment.
+    ///
+    /// Reported instead of [`Event::ControllerButtonDown`] once enabled with
+    /// [`Girl::set_button_state_events`].
+    ///
+    /// [`Girl::set_button_state_events`]: crate::Girl::set_button_state_events
+    ControllerButtonDownWithState {
+        /// Controller instance ID.
+        which: u32,
+        /// Button that was pressed.
+        button: Button,
+        /// Other buttons held when the `button` was pressed, including the
+        /// ones pressed earlier in the same batch of events.
+        held: Button,
+    },
+
     /// Button released.
     ControllerButtonUp {
         /// Controller instance ID.
@@ -72,6 +124,19 @@ pub enum Event {
         which: u32,
     },
 
+    /// What changed in the SDL2 mapping of a controller, reported right
+    /// after its [`ControllerDeviceRemapped`] if anything did, see
+    /// [`MappingSnapshot`].
+    ///
+    /// [`ControllerDeviceRemapped`]: Self::ControllerDeviceRemapped
+    /// [`MappingSnapshot`]: crate::MappingSnapshot
+    ControllerRemapDiff {
+        /// Controller instance ID.
+        which: u32,
+        /// What changed.
+        changes: RemapChanges,
+    },
+
     /// Steam controller handle updated.
     ControllerSteamHandleUpdate {
         /// Controller instance ID.
@@ -94,10 +159,619 @@ pub enum Event {
         /// Sensor data `[x, y, z]`.
         data: [f64; 3],
     },
+
+    /// Stick pushed towards a [`Direction`], synthesized when stick navigation
+    /// is enabled with [`Girl::set_stick_navigation`].
+    ///
+    /// [`Girl::set_stick_navigation`]: crate::Girl::set_stick_navigation
+    StickDirectionPressed {
+        /// Controller instance ID.
+        which: u32,
+        /// Which stick was pushed.
+        stick: Stick,
+        /// Direction the stick was pushed towards.
+        direction: Direction,
+    },
+
+    /// Stick held towards a [`Direction`] long enough to auto-repeat.
+    StickDirectionRepeat {
+        /// Controller instance ID.
+        which: u32,
+        /// Which stick is held.
+        stick: Stick,
+        /// Direction the stick is held towards.
+        direction: Direction,
+    },
+
+    /// Stick returned from a [`Direction`].
+    StickDirectionReleased {
+        /// Controller instance ID.
+        which: u32,
+        /// Which stick was released.
+        stick: Stick,
+        /// Direction the stick was released from.
+        direction: Direction,
+    },
+
+    /// [`Stick`] magnitude crossed into another tier, synthesized when tiers
+    /// are set with [`Gamepad::set_stick_tiers`].
+    ///
+    /// [`Gamepad::set_stick_tiers`]: crate::Gamepad::set_stick_tiers
+    StickTierChanged {
+        /// Controller instance ID.
+        which: u32,
+        /// Which stick changed tier.
+        stick: Stick,
+        /// Previous tier, `0` being below the lowest threshold.
+        from: usize,
+        /// New tier.
+        to: usize,
+    },
+
+    /// Rumble effect finished, either because its duration passed or because
+    /// it was stopped early.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    RumbleFinished {
+        /// Controller instance ID.
+        which: u32,
+        /// Whether the effect was stopped before its duration passed, e.g.
+        /// with [`Gamepad::end_rumble`].
+        ///
+        /// [`Gamepad::end_rumble`]: crate::Gamepad::end_rumble
+        cancelled: bool,
+    },
+
+    /// Synthetic marker reporting that events were dropped by a bounded
+    /// [`EventQueue`] before this point.
+    ///
+    /// [`EventQueue`]: crate::EventQueue
+    Overflow {
+        /// Number of events dropped since the previous marker.
+        dropped: u64,
+    },
+
+    /// Audio output device matched with a controller changed, see
+    /// [`Gamepad::audio_device_hint`] for the new one.
+    ///
+    /// [`Gamepad::audio_device_hint`]: crate::Gamepad::audio_device_hint
+    ControllerAudioDeviceChanged {
+        /// Controller instance ID.
+        which: u32,
+    },
+
+    /// Battery queries of a controller failed three times in a row, so its
+    /// [`Gamepad::power`] stays unknown. Reported once until a query
+    /// succeeds again.
+    ///
+    /// [`Gamepad::power`]: crate::Gamepad::power
+    PowerQueryFailed {
+        /// Controller instance ID.
+        which: u32,
+    },
+
+    /// No input arrived for the configured threshold, see
+    /// [`Girl::set_idle_config`]. Reported once until [`InputResumed`].
+    ///
+    /// [`Girl::set_idle_config`]: crate::Girl::set_idle_config
+    /// [`InputResumed`]: Self::InputResumed
+    InputIdle {
+        /// How long no input arrived.
+        after: Duration,
+    },
+
+    /// Input arrived again after [`InputIdle`].
+    ///
+    /// [`InputIdle`]: Self::InputIdle
+    InputResumed,
+
+    /// A controller without input is powered off in a minute unless input
+    /// arrives, see [`Girl::set_power_off_idle`].
+    ///
+    /// [`Girl::set_power_off_idle`]: crate::Girl::set_power_off_idle
+    PoweringOffIdle {
+        /// Controller instance ID.
+        which: u32,
+    },
+
+    /// A controller connected and disconnected repeatedly within the
+    /// window set with [`Girl::set_connection_debounce`], e.g. behind a
+    /// flaky USB hub. Reported once its connection settles, right before
+    /// the [`ControllerDeviceRemoved`] and [`ControllerDeviceAdded`] the
+    /// changes are coalesced into.
+    ///
+    /// [`Girl::set_connection_debounce`]:
+    ///     crate::Girl::set_connection_debounce
+    /// [`ControllerDeviceRemoved`]: Self::ControllerDeviceRemoved
+    /// [`ControllerDeviceAdded`]: Self::ControllerDeviceAdded
+    ConnectionFlapping {
+        /// Device index if the controller ended connected, or instance ID
+        /// if it ended disconnected.
+        which: u32,
+        /// Number of connection changes coalesced.
+        count: u32,
+    },
+
+    /// Controller handles kept failing at once, e.g. after a driver reset or
+    /// a USB hub power event. Call [`Girl::reinitialize`] to recover.
+    ///
+    /// Only detected from consecutive failures to resolve the handles, as
+    /// SDL2 reports no event of its own for it.
+    ///
+    /// [`Girl::reinitialize`]: crate::Girl::reinitialize
+    SubsystemReset,
+
+    /// The application is about to go into the background, e.g. on Android
+    /// or in Steam Deck gaming mode. Stop rumble and disable sensors to save
+    /// power, or let [`Girl::set_background_policy`] do it.
+    ///
+    /// [`Girl::set_background_policy`]: crate::Girl::set_background_policy
+    AppBackgrounded,
+
+    /// The application is back in the foreground after [`AppBackgrounded`].
+    ///
+    /// [`AppBackgrounded`]: Self::AppBackgrounded
+    AppForegrounded,
+
+    /// A [`Sensor`] whose enable failed was enabled on a retry, see
+    /// [`Girl::set_sensor_retry_window`].
+    ///
+    /// [`Girl::set_sensor_retry_window`]: crate::Girl::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    SensorEnabled {
+        /// Controller instance ID.
+        which: u32,
+        /// Type of sensor.
+        sensor: Sensor,
+    },
+
+    /// Retries of a failed [`Sensor`] enable kept failing for the whole
+    /// window, see [`Girl::set_sensor_retry_window`].
+    ///
+    /// [`Girl::set_sensor_retry_window`]: crate::Girl::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    SensorEnableFailed {
+        /// Controller instance ID.
+        which: u32,
+        /// Type of sensor.
+        sensor: Sensor,
+    },
+}
+
+/// Category of an [`Event`], see [`Event::kind`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventKind {
+    /// Controller input, e.g. a button press, stick motion or a sensor
+    /// update.
+    Input,
+    /// A controller connected or disconnected.
+    Device,
+    /// Any other notification, e.g. about power, remaps or the
+    /// application.
+    Housekeeping,
+}
+
+/// [`Event`] with a sequence number assigned by [`Girl`].
+///
+/// Sequence numbers increase monotonically by one for every [`Event`]
+/// translated by a [`Girl`], so gaps reveal dropped [`Event`]s once they
+/// flow through channels or bounded queues.
+///
+/// [`Girl`]: crate::Girl
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct SequencedEvent {
+    /// Sequence number.
+    pub seq: u64,
+    /// The [`Event`] itself.
+    pub event: Event,
+}
+
+impl SequencedEvent {
+    /// Creates a new [`SequencedEvent`].
+    #[must_use]
+    #[inline]
+    pub const fn new(seq: u64, event: Event) -> Self {
+        Self { seq, event }
+    }
+
+    /// Counts the [`Event`]s missing between the [`SequencedEvent`] with the
+    /// `previous` sequence number and this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SequencedEvent, prelude::*};
+    /// let event = SequencedEvent::new(10, Event::Quit);
+    /// assert_eq!(event.missed_since(9), 0);
+    /// assert_eq!(event.missed_since(6), 3);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn missed_since(&self, previous: u64) -> u64 {
+        self.seq.saturating_sub(previous).saturating_sub(1)
+    }
 }
 
 impl Event {
+    /// Creates an [`Event::ControllerButtonDown`] of the controller with the
+    /// `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::button_down(0, Button::A);
+    /// assert!(matches!(event, Event::ControllerButtonDown {
+    ///     which: 0,
+    ///     button: Button::A
+    /// }));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn button_down(which: u32, button: Button) -> Self {
+        Self::ControllerButtonDown { which, button }
+    }
+
+    /// Creates an [`Event::ControllerButtonUp`] of the controller with the
+    /// `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::button_up(0, Button::B);
+    /// assert!(matches!(event, Event::ControllerButtonUp {
+    ///     which: 0,
+    ///     button: Button::B
+    /// }));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn button_up(which: u32, button: Button) -> Self {
+        Self::ControllerButtonUp { which, button }
+    }
+
+    /// Creates an [`Event::ControllerStickMotion`] of the controller with the
+    /// `which` instance ID, the `offset` being `[x, y]` in `-1.0..=1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::stick_motion(0, Stick::Right, [0.5, -1.0]);
+    /// let Event::ControllerStickMotion { stick, offset, .. } = event else {
+    ///     panic!("not a stick motion");
+    /// };
+    /// assert_eq!((stick, offset), (Stick::Right, [0.5, -1.0]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn stick_motion(
+        which: u32,
+        stick: Stick,
+        offset: [f64; 2],
+    ) -> Self {
+        Self::ControllerStickMotion { which, stick, offset }
+    }
+
+    /// Creates an [`Event::ControllerTriggerMotion`] of the controller with
+    /// the `which` instance ID, the `offset` being in `0.0..=1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let pull = Event::trigger_motion(0, Trigger::Left, 0.75);
+    /// let Event::ControllerTriggerMotion { trigger, offset, .. } = pull else {
+    ///     panic!("not a trigger motion");
+    /// };
+    /// assert_eq!((trigger, offset), (Trigger::Left, 0.75));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn trigger_motion(
+        which: u32,
+        trigger: Trigger,
+        offset: f64,
+    ) -> Self {
+        Self::ControllerTriggerMotion { which, trigger, offset }
+    }
+
+    /// Creates an [`Event::ControllerDeviceAdded`] of the controller with the
+    /// `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::device_added(2);
+    /// assert!(matches!(event, Event::ControllerDeviceAdded { which: 2 }));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn device_added(which: u32) -> Self {
+        Self::ControllerDeviceAdded { which }
+    }
+
+    /// Creates an [`Event::ControllerDeviceRemoved`] of the controller with
+    /// the `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::device_removed(2);
+    /// assert!(matches!(event, Event::ControllerDeviceRemoved { which: 2 }));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn device_removed(which: u32) -> Self {
+        Self::ControllerDeviceRemoved { which }
+    }
+
+    /// Creates an [`Event::ControllerDeviceRemapped`] of the controller with
+    /// the `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::device_remapped(2);
+    /// assert!(matches!(event, Event::ControllerDeviceRemapped { which: 2 }));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn device_remapped(which: u32) -> Self {
+        Self::ControllerDeviceRemapped { which }
+    }
+
+    /// Creates an [`Event::ControllerSensorUpdated`] of the controller with
+    /// the `which` instance ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let rotation = [0.0, 1.0, 0.0];
+    /// let event = Event::sensor_updated(0, Sensor::Gyroscope, rotation);
+    /// let Event::ControllerSensorUpdated { sensor, data, .. } = event else {
+    ///     panic!("not a sensor update");
+    /// };
+    /// assert_eq!((sensor, data), (Sensor::Gyroscope, rotation));
+    /// ```
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub const fn sensor_updated(
+        which: u32,
+        sensor: Sensor,
+        data: [f64; 3],
+    ) -> Self {
+        Self::ControllerSensorUpdated { which, sensor, data }
+    }
+
+    /// Creates an [`Event::ControllerTouchpad`] from a [`TouchpadEvent`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{TouchpadEvent, prelude::*};
+    /// let action = TouchpadAction::Touched;
+    /// let touch = TouchpadEvent::new(0, 0, 0, [0.5, 0.5], 1.0, action);
+    /// let event = Event::touchpad(touch);
+    /// assert_eq!(event.which(), Some(0));
+    /// ```
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn touchpad(event: TouchpadEvent) -> Self {
+        Self::ControllerTouchpad(event)
+    }
+
+    /// Creates a stick navigation [`Event`] from a [`StickNavigator`]
+    /// transition.
+    ///
+    /// [`StickNavigator`]: crate::StickNavigator
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "extracted conversion")]
+    pub(crate) const fn from_navigation(
+        which: u32,
+        stick: Stick,
+        action: NavigationAction,
+        direction: Direction,
+    ) -> Self {
+        match action {
+            NavigationAction::Pressed => {
+                Self::StickDirectionPressed { which, stick, direction }
+            }
+            NavigationAction::Repeat => {
+                Self::StickDirectionRepeat { which, stick, direction }
+            }
+            NavigationAction::Released => {
+                Self::StickDirectionReleased { which, stick, direction }
+            }
+        }
+    }
+
+    /// Gets the controller instance ID the [`Event`] originates from.
+    ///
+    /// Returns [`None`] for [`Event`]s not tied to a controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let event = Event::ControllerButtonDown { which: 3, button: Button::A };
+    /// assert_eq!(event.which(), Some(3));
+    /// assert_eq!(Event::Quit.which(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn which(&self) -> Option<u32> {
+        match *self {
+            Self::ControllerStickMotion { which, .. }
+            | Self::ControllerTriggerMotion { which, .. }
+            | Self::ControllerButtonDown { which, .. }
+            | Self::ControllerButtonDownWithState { which, .. }
+            | Self::ControllerButtonUp { which, .. }
+            | Self::ControllerDeviceAdded { which }
+            | Self::ControllerDeviceRemoved { which }
+            | Self::ControllerDeviceRemapped { which }
+            | Self::ControllerRemapDiff { which, .. }
+            | Self::ControllerSteamHandleUpdate { which }
+            | Self::ControllerAudioDeviceChanged { which }
+            | Self::PowerQueryFailed { which }
+            | Self::PoweringOffIdle { which }
+            | Self::ConnectionFlapping { which, .. }
+            | Self::StickDirectionPressed { which, .. }
+            | Self::StickDirectionRepeat { which, .. }
+            | Self::StickDirectionReleased { which, .. }
+            | Self::StickTierChanged { which, .. } => Some(which),
+            #[cfg(feature = "touchpad")]
+            Self::ControllerTouchpad(TouchpadEvent { which, .. }) => {
+                Some(which)
+            }
+            #[cfg(feature = "sensors")]
+            Self::ControllerSensorUpdated { which, .. }
+            | Self::SensorEnabled { which, .. }
+            | Self::SensorEnableFailed { which, .. } => Some(which),
+            #[cfg(feature = "rumble")]
+            Self::RumbleFinished { which, .. } => Some(which),
+            Self::Quit
+            | Self::Overflow { .. }
+            | Self::InputIdle { .. }
+            | Self::InputResumed
+            | Self::SubsystemReset
+            | Self::AppBackgrounded
+            | Self::AppForegrounded => None,
+        }
+    }
+
+    /// Gets the [`EventKind`] of the [`Event`], e.g. to tell input from
+    /// housekeeping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{EventKind, prelude::*};
+    /// let motion = Event::stick_motion(0, Stick::Left, [0.5, 0.0]);
+    /// assert_eq!(motion.kind(), EventKind::Input);
+    /// assert_eq!(Event::device_removed(0).kind(), EventKind::Device);
+    /// let remapped = Event::ControllerDeviceRemapped { which: 0 };
+    /// assert_eq!(remapped.kind(), EventKind::Housekeeping);
+    /// let power = Event::PowerQueryFailed { which: 0 };
+    /// assert_eq!(power.kind(), EventKind::Housekeeping);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn kind(&self) -> EventKind {
+        if self.is_input() {
+            EventKind::Input
+        } else if matches!(
+            *self,
+            Self::ControllerDeviceAdded { .. }
+                | Self::ControllerDeviceRemoved { .. }
+        ) {
+            EventKind::Device
+        } else {
+            EventKind::Housekeeping
+        }
+    }
+
+    /// Gets a mutable reference to the controller instance ID the [`Event`]
+    /// originates from, see [`which`].
+    ///
+    /// [`which`]: Self::which
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binds the instance ID mutably"
+    )]
+    pub(crate) const fn which_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            Self::ControllerStickMotion { which, .. }
+            | Self::ControllerTriggerMotion { which, .. }
+            | Self::ControllerButtonDown { which, .. }
+            | Self::ControllerButtonDownWithState { which, .. }
+            | Self::ControllerButtonUp { which, .. }
+            | Self::ControllerDeviceAdded { which }
+            | Self::ControllerDeviceRemoved { which }
+            | Self::ControllerDeviceRemapped { which }
+            | Self::ControllerRemapDiff { which, .. }
+            | Self::ControllerSteamHandleUpdate { which }
+            | Self::ControllerAudioDeviceChanged { which }
+            | Self::PowerQueryFailed { which }
+            | Self::PoweringOffIdle { which }
+            | Self::ConnectionFlapping { which, .. }
+            | Self::StickDirectionPressed { which, .. }
+            | Self::StickDirectionRepeat { which, .. }
+            | Self::StickDirectionReleased { which, .. }
+            | Self::StickTierChanged { which, .. } => Some(which),
+            #[cfg(feature = "touchpad")]
+            Self::ControllerTouchpad(TouchpadEvent { which, .. }) => {
+                Some(which)
+            }
+            #[cfg(feature = "sensors")]
+            Self::ControllerSensorUpdated { which, .. }
+            | Self::SensorEnabled { which, .. }
+            | Self::SensorEnableFailed { which, .. } => Some(which),
+            #[cfg(feature = "rumble")]
+            Self::RumbleFinished { which, .. } => Some(which),
+            Self::Quit
+            | Self::Overflow { .. }
+            | Self::InputIdle { .. }
+            | Self::InputResumed
+            | Self::SubsystemReset
+            | Self::AppBackgrounded
+            | Self::AppForegrounded => None,
+        }
+    }
+
+    /// Checks if the [`Event`] reports controller input, as opposed to
+    /// connection changes and other device-level notifications.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn is_input(&self) -> bool {
+        #[cfg(feature = "touchpad")]
+        if let Self::ControllerTouchpad(_) = *self {
+            return true;
+        }
+        #[cfg(feature = "sensors")]
+        if let Self::ControllerSensorUpdated { .. } = *self {
+            return true;
+        }
+        matches!(
+            *self,
+            Self::ControllerStickMotion { .. }
+                | Self::ControllerTriggerMotion { .. }
+                | Self::ControllerButtonDown { .. }
+                | Self::ControllerButtonDownWithState { .. }
+                | Self::ControllerButtonUp { .. }
+                | Self::StickDirectionPressed { .. }
+                | Self::StickDirectionRepeat { .. }
+                | Self::StickDirectionReleased { .. }
+                | Self::StickTierChanged { .. }
+        )
+    }
+
     /// Converts from [`SdlEvent`] to [`Event`].
+    ///
+    /// Stick motion reports only the moved axis, the other one is `0.0`, see
+    /// [`Translator`] for full stick positions.
+    ///
+    /// [`Translator`]: crate::compat::Translator
+    #[cfg(feature = "sdl2")]
     #[expect(clippy::too_many_lines, reason = "not much we can do")]
     #[must_use]
     #[inline]
@@ -112,11 +786,7 @@ impl Event {
             } => Self::ControllerStickMotion {
                 which,
                 stick: Stick::Left,
-                offset: if axis == SdlAxis::LeftX {
-                    [0.0, map(value.into(), Gamepad::STICK_DEADZONE, AXIS_MAX)]
-                } else {
-                    [map(value.into(), Gamepad::STICK_DEADZONE, AXIS_MAX), 0.0]
-                },
+                offset: stick_offset(axis == SdlAxis::LeftX, value),
             },
             SdlEvent::ControllerAxisMotion {
                 timestamp: _,
@@ -126,11 +796,7 @@ impl Event {
             } => Self::ControllerStickMotion {
                 which,
                 stick: Stick::Right,
-                offset: if axis == SdlAxis::LeftX {
-                    [map(value.into(), Gamepad::STICK_DEADZONE, AXIS_MAX), 0.0]
-                } else {
-                    [0.0, map(value.into(), Gamepad::STICK_DEADZONE, AXIS_MAX)]
-                },
+                offset: stick_offset(axis == SdlAxis::RightX, value),
             },
             SdlEvent::ControllerAxisMotion {
                 timestamp: _,
@@ -140,7 +806,7 @@ impl Event {
             } => Self::ControllerTriggerMotion {
                 which,
                 trigger: Trigger::Left,
-                offset: map(value.into(), 0.0, AXIS_MAX),
+                offset: normalize::trigger(value),
             },
             SdlEvent::ControllerAxisMotion {
                 timestamp: _,
@@ -150,7 +816,7 @@ impl Event {
             } => Self::ControllerTriggerMotion {
                 which,
                 trigger: Trigger::Right,
-                offset: map(value.into(), 0.0, AXIS_MAX),
+                offset: normalize::trigger(value),
             },
             SdlEvent::ControllerButtonDown { timestamp: _, which, button } => {
                 Self::ControllerButtonDown {
@@ -201,14 +867,21 @@ impl Event {
             } => Self::ControllerSensorUpdated {
                 which,
                 sensor: Sensor::from_sdl(sensor),
-                data: data.map(|x| map(f64::from(x), 0.01, 1.)),
+                data: data
+                    .map(|x| apply_deadzone(f64::from(x), SENSOR_DEADZONE)),
             },
+            #[cfg(not(feature = "sensors"))]
+            SdlEvent::ControllerSensorUpdated { .. } => return None,
+            SdlEvent::AppWillEnterBackground { timestamp: _ } => {
+                Self::AppBackgrounded
+            }
+            SdlEvent::AppDidEnterForeground { timestamp: _ } => {
+                Self::AppForegrounded
+            }
             SdlEvent::AppTerminating { .. }
             | SdlEvent::AppLowMemory { .. }
-            | SdlEvent::AppWillEnterBackground { .. }
             | SdlEvent::AppDidEnterBackground { .. }
             | SdlEvent::AppWillEnterForeground { .. }
-            | SdlEvent::AppDidEnterForeground { .. }
             | SdlEvent::Display { .. }
             | SdlEvent::Window { .. }
             | SdlEvent::KeyDown { .. }
@@ -247,3 +920,51 @@ impl Event {
         })
     }
 }
+
+#[cfg(feature = "touchpad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+impl From<TouchpadEvent> for Event {
+    #[inline]
+    fn from(event: TouchpadEvent) -> Self {
+        Self::touchpad(event)
+    }
+}
+
+/// Takes the [`TouchpadEvent`] out of an [`Event::ControllerTouchpad`],
+/// giving back any other [`Event`] as the error.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchpadEvent, prelude::*};
+/// let action = TouchpadAction::Moved;
+/// let touch = TouchpadEvent::new(2, 0, 1, [0.5, 0.5], 0.5, action);
+/// let event: Event = touch.into();
+/// assert_eq!(TouchpadEvent::try_from(event).ok(), Some(touch));
+///
+/// let quit = TouchpadEvent::try_from(Event::Quit);
+/// assert!(matches!(quit, Err(Event::Quit)));
+/// ```
+#[cfg(feature = "touchpad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+impl TryFrom<Event> for TouchpadEvent {
+    type Error = Event;
+
+    #[inline]
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        if let Event::ControllerTouchpad(touch) = event {
+            Ok(touch)
+        } else {
+            Err(event)
+        }
+    }
+}
+
+/// Maps a raw stick axis `value` to an `[x, y]` offset with only the moved
+/// axis set.
+#[cfg(feature = "sdl2")]
+fn stick_offset(horizontal: bool, value: i16) -> [f64; 2] {
+    let mapped =
+        apply_deadzone(normalize::axis(value), Gamepad::STICK_DEADZONE);
+    if horizontal { [mapped, 0.0] } else { [0.0, mapped] }
+}
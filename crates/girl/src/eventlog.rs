@@ -0,0 +1,465 @@
+//! [`Event`] logs in the JSON Lines format, e.g. for external analysis.
+//!
+//! A log is a header line, see [`LogHeader`], followed by one line per
+//! [`LogRecord`]. Every line is a JSON object whose `kind` field is either
+//! `"header"` or `"event"`, so the log loads directly into e.g. `pandas`
+//! with `read_json(path, lines=True)`.
+
+use std::{
+    io::{self, BufRead, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{DeviceInfo, EnrichedEvent, Event, Girl};
+
+/// First line of an [`Event`] log, see [`EventLogWriter`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogHeader {
+    /// Version of the log format, see [`LogHeader::FORMAT`].
+    pub format: u32,
+    /// Version of `girl` the log was written by.
+    pub crate_version: String,
+    /// Version of SDL2 the log was written with.
+    pub sdl_version: String,
+}
+
+impl LogHeader {
+    /// Version of the log format written by this version of `girl`.
+    ///
+    /// Bumped whenever the schema changes in a way that breaks existing
+    /// readers. [`EventLogReader`] rejects logs of newer formats.
+    pub const FORMAT: u32 = 1;
+
+    /// Creates a new [`LogHeader`] of the current [`FORMAT`].
+    ///
+    /// [`FORMAT`]: Self::FORMAT
+    #[must_use]
+    #[inline]
+    pub const fn new(crate_version: String, sdl_version: String) -> Self {
+        Self { format: Self::FORMAT, crate_version, sdl_version }
+    }
+
+    /// Creates a [`LogHeader`] describing the running versions of `girl` and
+    /// SDL2.
+    #[must_use]
+    #[inline]
+    pub fn current() -> Self {
+        Self::new(
+            env!("CARGO_PKG_VERSION").to_owned(),
+            sdl2::version::version().to_string(),
+        )
+    }
+}
+
+/// [`Event`] line of an [`Event`] log, see [`EventLogWriter`].
+///
+/// # Examples
+///
+/// The schema is stable within a [`LogHeader::FORMAT`]:
+///
+/// ```
+/// # use girl::{
+/// #     DeviceInfo, Event, EventLogReader, EventLogWriter, FormFactor,
+/// #     LogHeader, LogRecord, Stick,
+/// # };
+/// let golden = include_str!(concat!(
+///     env!("CARGO_MANIFEST_DIR"),
+///     "/tests/golden/event_log.jsonl",
+/// ));
+///
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// let name = "PS5 Controller".to_owned();
+/// let device = DeviceInfo::new(name, guid, FormFactor::Standard);
+/// let at = 1_700_000_000_000;
+/// let records = [
+///     LogRecord::new(0, at, Event::ControllerDeviceAdded { which: 0 }, None),
+///     LogRecord::new(
+///         1,
+///         at + 16,
+///         Event::ControllerStickMotion {
+///             which: 0,
+///             stick: Stick::Left,
+///             offset: [0.5, -1.0],
+///         },
+///         Some(device),
+///     ),
+///     LogRecord::new(2, at + 20, Event::Quit, None),
+/// ];
+///
+/// let header = LogHeader::new("0.0.2".to_owned(), "2.30.0".to_owned());
+/// let mut log = EventLogWriter::with_header(Vec::new(), &header).unwrap();
+/// for record in &records {
+///     log.write_record(record).unwrap();
+/// }
+/// let written = log.into_inner().unwrap();
+/// assert_eq!(String::from_utf8(written).unwrap(), golden);
+///
+/// // and reads back
+/// let reader = EventLogReader::new(golden.as_bytes()).unwrap();
+/// assert_eq!(reader.header(), &header);
+/// let read: Vec<_> = reader.map(Result::unwrap).collect();
+/// assert_eq!(read.len(), records.len());
+/// for (read, record) in read.iter().zip(&records) {
+///     assert_eq!(read.seq, record.seq);
+///     assert_eq!(read.timestamp_ms, record.timestamp_ms);
+///     assert_eq!(read.device, record.device);
+///     assert_eq!(format!("{:?}", read.event), format!("{:?}", record.event));
+/// }
+/// # Ok::<(), girl::Error>(())
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LogRecord {
+    /// Sequence number of the [`Event`], see [`SequencedEvent`].
+    ///
+    /// [`SequencedEvent`]: crate::SequencedEvent
+    pub seq: u64,
+    /// When the [`Event`] was logged, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Metadata of the device the [`Event`] originates from, see
+    /// [`EnrichedEvent::device`].
+    pub device: Option<DeviceInfo>,
+    /// The [`Event`] itself.
+    pub event: Event,
+}
+
+impl LogRecord {
+    /// Creates a new [`LogRecord`].
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        seq: u64,
+        timestamp_ms: u64,
+        event: Event,
+        device: Option<DeviceInfo>,
+    ) -> Self {
+        Self { seq, timestamp_ms, device, event }
+    }
+
+    /// Creates a [`LogRecord`] of an [`EnrichedEvent`] logged now.
+    #[must_use]
+    #[inline]
+    pub fn now(seq: u64, enriched: EnrichedEvent) -> Self {
+        let timestamp_ms =
+            SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| {
+                u64::try_from(since.as_millis()).unwrap_or(u64::MAX)
+            });
+        Self::new(seq, timestamp_ms, enriched.event, enriched.device)
+    }
+}
+
+/// Line of an [`Event`] log, as read.
+#[derive(serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Line {
+    /// First line, see [`LogHeader`].
+    Header(LogHeader),
+    /// Any other line, see [`LogRecord`].
+    Event(LogRecord),
+}
+
+/// Line of an [`Event`] log, as written. Mirrors [`Line`] without cloning.
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LineRef<'line> {
+    /// First line, see [`LogHeader`].
+    Header(&'line LogHeader),
+    /// Any other line, see [`LogRecord`].
+    Event(&'line LogRecord),
+}
+
+/// Writer of [`Event`] logs in the JSON Lines format.
+///
+/// Writes the [`LogHeader`] on creation, then one line per [`LogRecord`].
+/// The underlying writer is flushed every [`flush_every`] records, so wrap
+/// unbuffered writers, e.g. [`File`]s, in a [`BufWriter`]. Call [`flush`] or
+/// [`into_inner`] before dropping to not lose the last records.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{fs::File, io::BufWriter};
+/// # use girl::EventLogWriter;
+/// let path = std::env::temp_dir().join("girl-event-log-doctest.jsonl");
+/// let file = BufWriter::new(File::create(&path).unwrap());
+/// let mut log = EventLogWriter::new(file).unwrap();
+/// let mut girl = girl::Girl::new()?;
+///
+/// loop {
+///     girl.update();
+///     for event in girl.tee_events(&mut log) {
+///         println!("{event:?}");
+///     }
+///     if let Some(err) = log.take_error() {
+///         eprintln!("event log failed: {err}");
+///     }
+///     # break;
+/// }
+/// log.flush().unwrap();
+/// # std::fs::remove_file(&path).unwrap();
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`flush_every`]: Self::set_flush_every
+/// [`flush`]: Self::flush
+/// [`into_inner`]: Self::into_inner
+/// [`File`]: std::fs::File
+/// [`BufWriter`]: std::io::BufWriter
+#[derive(Debug)]
+pub struct EventLogWriter<W: Write> {
+    /// Underlying writer.
+    inner: W,
+    /// Number of records written between flushes.
+    flush_every: usize,
+    /// Number of records written since the last flush.
+    unflushed: usize,
+    /// First error of [`TeeEvents`] not yet taken.
+    error: Option<io::Error>,
+}
+
+impl<W: Write> EventLogWriter<W> {
+    /// Default number of records written between flushes.
+    pub const FLUSH_EVERY: usize = 64;
+
+    /// Creates a new [`EventLogWriter`], writing the [`LogHeader::current`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header cannot be written.
+    #[inline]
+    pub fn new(inner: W) -> io::Result<Self> {
+        Self::with_header(inner, &LogHeader::current())
+    }
+
+    /// Creates a new [`EventLogWriter`], writing the given `header`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header cannot be written.
+    #[inline]
+    pub fn with_header(inner: W, header: &LogHeader) -> io::Result<Self> {
+        let mut log = Self {
+            inner,
+            flush_every: Self::FLUSH_EVERY,
+            unflushed: 0,
+            error: None,
+        };
+        log.write_line(&LineRef::Header(header))?;
+        Ok(log)
+    }
+
+    /// Sets the number of records written between flushes of the underlying
+    /// writer, `0` meaning to only flush on [`flush`].
+    ///
+    /// [`flush`]: Self::flush
+    #[inline]
+    pub const fn set_flush_every(&mut self, records: usize) {
+        self.flush_every = records;
+    }
+
+    /// Gets the number of records written between flushes, see
+    /// [`set_flush_every`].
+    ///
+    /// [`set_flush_every`]: Self::set_flush_every
+    #[must_use]
+    #[inline]
+    pub const fn flush_every(&self) -> usize {
+        self.flush_every
+    }
+
+    /// Writes a [`LogRecord`] as one line, flushing if due.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be written or flushed.
+    #[inline]
+    pub fn write_record(&mut self, record: &LogRecord) -> io::Result<()> {
+        self.write_line(&LineRef::Event(record))?;
+        self.unflushed = self.unflushed.saturating_add(1);
+        if self.flush_every != 0 && self.unflushed >= self.flush_every {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying writer cannot be flushed.
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.unflushed = 0;
+        self.inner.flush()
+    }
+
+    /// Takes the first error encountered while logging with
+    /// [`Girl::tee_events`], if any.
+    ///
+    /// Logging stops at the first error until it is taken, while the
+    /// [`Event`]s keep flowing.
+    #[must_use]
+    #[inline]
+    pub const fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flushes and unwraps the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying writer cannot be flushed.
+    #[inline]
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Writes a [`LineRef`] followed by a line break.
+    fn write_line(&mut self, line: &LineRef<'_>) -> io::Result<()> {
+        serde_json::to_writer(&mut self.inner, line)?;
+        self.inner.write_all(b"\n")
+    }
+}
+
+/// Reader of [`Event`] logs written by [`EventLogWriter`], e.g. for replay.
+///
+/// Iterates over the [`LogRecord`]s after the [`LogHeader`]. Blank lines are
+/// skipped, malformed ones are reported as [`io::ErrorKind::InvalidData`]
+/// errors without stopping the iteration.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::EventLogReader;
+/// let log = concat!(
+///     r#"{"kind":"header","format":1,"#,
+///     r#""crate_version":"0.0.2","sdl_version":"2.30.0"}"#,
+///     "\n",
+///     r#"{"kind":"event","seq":7,"timestamp_ms":0,"#,
+///     r#""device":null,"event":"Quit"}"#,
+///     "\n\ngarbage\n",
+/// );
+///
+/// let reader = EventLogReader::new(log.as_bytes()).unwrap();
+/// assert_eq!(reader.header().sdl_version, "2.30.0");
+/// let events: Vec<_> = reader.events().collect();
+/// assert!(matches!(events[0], Ok(girl::Event::Quit)));
+/// assert!(events[1].is_err());
+///
+/// // logs of unknown formats are rejected upfront
+/// let newer = concat!(
+///     r#"{"kind":"header","format":99,"#,
+///     r#""crate_version":"9.0.0","sdl_version":"3.0.0"}"#,
+/// );
+/// assert!(EventLogReader::new(newer.as_bytes()).is_err());
+/// ```
+#[derive(Debug)]
+pub struct EventLogReader<R: BufRead> {
+    /// Lines after the [`LogHeader`].
+    lines: io::Lines<R>,
+    /// The [`LogHeader`] of the log.
+    header: LogHeader,
+}
+
+impl<R: BufRead> EventLogReader<R> {
+    /// Creates a new [`EventLogReader`], reading the [`LogHeader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header cannot be read, is malformed, or is of
+    /// a newer [`LogHeader::FORMAT`].
+    #[inline]
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        let first = lines.next().transpose()?.unwrap_or_default();
+        let Line::Header(header) = serde_json::from_str(&first)? else {
+            return Err(invalid("event log does not start with a header"));
+        };
+        if header.format > LogHeader::FORMAT {
+            return Err(invalid("event log format is newer than supported"));
+        }
+        Ok(Self { lines, header })
+    }
+
+    /// Gets the [`LogHeader`] of the log.
+    #[must_use]
+    #[inline]
+    pub const fn header(&self) -> &LogHeader {
+        &self.header
+    }
+
+    /// Turns the [`EventLogReader`] into an iterator over just the
+    /// [`Event`]s.
+    #[inline]
+    pub fn events(self) -> impl Iterator<Item = io::Result<Event>> {
+        self.map(|record| record.map(|read| read.event))
+    }
+}
+
+impl<R: BufRead> Iterator for EventLogReader<R> {
+    type Item = io::Result<LogRecord>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = loop {
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => break line,
+                Err(err) => return Some(Err(err)),
+            }
+        };
+        Some(match serde_json::from_str(&line) {
+            Ok(Line::Event(record)) => Ok(record),
+            Ok(Line::Header(_)) => Err(invalid("unexpected event log header")),
+            Err(err) => Err(err.into()),
+        })
+    }
+}
+
+/// Iterator over the [`Event`]s of a [`Girl`], logging each one.
+///
+/// Can be obtained from [`Girl::tee_events`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct TeeEvents<'girl, 'log, W: Write> {
+    /// The [`Girl`] to take [`Event`]s from.
+    girl: &'girl mut Girl,
+    /// The log to write [`Event`]s to.
+    log: &'log mut EventLogWriter<W>,
+}
+
+impl<'girl, 'log, W: Write> TeeEvents<'girl, 'log, W> {
+    /// Creates a new [`TeeEvents`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(
+        girl: &'girl mut Girl,
+        log: &'log mut EventLogWriter<W>,
+    ) -> Self {
+        Self { girl, log }
+    }
+}
+
+impl<W: Write> Iterator for TeeEvents<'_, '_, W> {
+    type Item = Event;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let sequenced = self.girl.sequenced_event()?;
+        if self.log.error.is_none() {
+            let enriched = self.girl.enrich(&sequenced.event);
+            let record = LogRecord::now(sequenced.seq, enriched);
+            if let Err(err) = self.log.write_record(&record) {
+                self.log.error = Some(err);
+            }
+        }
+        Some(sequenced.event)
+    }
+}
+
+/// Creates an [`io::ErrorKind::InvalidData`] error.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,254 @@
+//! Physical inputs of the underlying joystick bound to [`Gamepad`] inputs.
+
+#[cfg(feature = "sdl2")]
+use sdl2::{
+    controller::Axis as SdlAxis,
+    sys::{
+        self as sdl2_sys, SDL_GameControllerBindType as SdlBindType,
+        SDL_GameControllerButtonBind as SdlBind,
+    },
+};
+
+#[cfg(feature = "sdl2")]
+use crate::{
+    Button, Error, Gamepad, Stick, Trigger,
+    gamepad::profile::check_single_button,
+};
+
+/// Physical input of the underlying joystick a [`Gamepad`] input is read
+/// from, as mapped by SDL2, see [`Gamepad::bind_for_button`].
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "SDL2 mappings only bind buttons, axes and hats"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputBind {
+    /// Joystick button by index.
+    Button(u8),
+    /// Joystick axis by index.
+    Axis {
+        /// Index of the axis.
+        index: u8,
+        /// Whether the mapping inverts the axis, i.e. `~` in the SDL2
+        /// mapping string.
+        inverted: bool,
+    },
+    /// Direction of a joystick hat.
+    Hat {
+        /// Index of the hat.
+        index: u8,
+        /// Direction bits of the hat: 1 up, 2 right, 4 down and 8 left.
+        mask: u8,
+    },
+    /// Not bound to any physical input.
+    None,
+}
+
+#[cfg(feature = "sdl2")]
+impl InputBind {
+    /// Converts from SDL2 bind, with the axis `inverted` according to the
+    /// mapping.
+    ///
+    /// Indices out of range are reported as [`InputBind::None`], SDL2 never
+    /// maps those.
+    #[must_use]
+    fn from_sdl(bind: SdlBind, inverted: bool) -> Self {
+        let bound = match bind.bindType {
+            SdlBindType::SDL_CONTROLLER_BINDTYPE_NONE => None,
+            SdlBindType::SDL_CONTROLLER_BINDTYPE_BUTTON => {
+                // SAFETY: the bind type says the union holds a button.
+                #[expect(unsafe_code, reason = "ffi with sdl2")]
+                let button = unsafe { bind.value.button };
+                u8::try_from(button).ok().map(Self::Button)
+            }
+            SdlBindType::SDL_CONTROLLER_BINDTYPE_AXIS => {
+                // SAFETY: the bind type says the union holds an axis.
+                #[expect(unsafe_code, reason = "ffi with sdl2")]
+                let axis = unsafe { bind.value.axis };
+                u8::try_from(axis)
+                    .ok()
+                    .map(|index| Self::Axis { index, inverted })
+            }
+            SdlBindType::SDL_CONTROLLER_BINDTYPE_HAT => {
+                // SAFETY: the bind type says the union holds a hat.
+                #[expect(unsafe_code, reason = "ffi with sdl2")]
+                let hat = unsafe { bind.value.hat };
+                u8::try_from(hat.hat)
+                    .ok()
+                    .zip(u8::try_from(hat.hat_mask).ok())
+                    .map(|(index, mask)| Self::Hat { index, mask })
+            }
+        };
+        bound.unwrap_or(Self::None)
+    }
+}
+
+/// Reading SDL2 mapping strings.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "feature gated")]
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+impl InputBind {
+    /// Finds the [`InputBind`] of the input `name` in the SDL2 `mapping`,
+    /// e.g. of `"leftshoulder"`, or [`InputBind::None`] if it isn't bound.
+    ///
+    /// The `mapping` starts with the GUID and the name, as SDL2 reports it.
+    #[must_use]
+    #[cfg_attr(
+        not(feature = "sim"),
+        expect(clippy::single_call_fn, reason = "feature gated")
+    )]
+    pub(crate) fn in_mapping(mapping: &str, name: &str) -> Self {
+        mapping
+            .split(',')
+            .skip(2)
+            .find_map(|field| {
+                let (input, bound) = field.split_once(':')?;
+                (input.trim_start_matches(['+', '-']) == name)
+                    .then(|| Self::parse(bound))
+            })
+            .flatten()
+            .unwrap_or(Self::None)
+    }
+
+    /// Parses a bind of an SDL2 mapping string, e.g. `b1`, `a2~` or `h0.4`.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn parse(bound: &str) -> Option<Self> {
+        let bound = bound.trim_start_matches(['+', '-']);
+        if let Some(index) = bound.strip_prefix('b') {
+            return index.parse().ok().map(Self::Button);
+        }
+        if let Some(axis) = bound.strip_prefix('a') {
+            let (index, inverted) = axis
+                .strip_suffix('~')
+                .map_or((axis, false), |index| (index, true));
+            return index
+                .parse()
+                .ok()
+                .map(|index| Self::Axis { index, inverted });
+        }
+        let (index, mask) = bound.strip_prefix('h')?.split_once('.')?;
+        Some(Self::Hat { index: index.parse().ok()?, mask: mask.parse().ok()? })
+    }
+}
+
+/// Physical inputs bound to [`Gamepad`] inputs.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Gets the physical input of the underlying joystick the [`Button`] is
+    /// read from, e.g. to highlight it on a controller test screen.
+    ///
+    /// Reflects the SDL2 mapping only, [`Button`] remaps of the
+    /// [`InputProfile`] are not applied. Two [`Button`]s with the same
+    /// [`InputBind`] share one physical input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not exactly one [`Button`] is given, or if the
+    /// controller is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{InputBind, prelude::*};
+    /// let girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let a = gamepad.bind_for_button(Button::A)?;
+    /// if gamepad.bind_for_button(Button::B)? == a {
+    ///     println!("A and B share {a:?}");
+    /// }
+    /// if let InputBind::Axis { index, .. } =
+    ///     gamepad.bind_for_trigger(Trigger::Left)?
+    /// {
+    ///     println!("the left trigger is axis {index}");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`InputProfile`]: crate::InputProfile
+    #[inline]
+    pub fn bind_for_button(&self, button: Button) -> Result<InputBind, Error> {
+        check_single_button(button)?;
+        let raw = self.raw("bind_for_button")?;
+        let Some(sdl_button) = button.into_sdl() else {
+            return Err(Error::InvalidArgument { what: "button" });
+        };
+
+        // SAFETY: SDL is alive, pointer is valid
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let bind = unsafe {
+            sdl2_sys::SDL_GameControllerGetBindForButton(
+                raw,
+                sdl_button.to_ll(),
+            )
+        };
+        let inverted = self.inverted(&sdl_button.string());
+        Ok(InputBind::from_sdl(bind, inverted))
+    }
+
+    /// Gets the physical inputs of the underlying joystick the `x` and `y`
+    /// axes of the [`Stick`] are read from, see [`bind_for_button`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
+    ///
+    /// [`bind_for_button`]: Self::bind_for_button
+    #[inline]
+    pub fn bind_for_stick(
+        &self,
+        stick: Stick,
+    ) -> Result<[InputBind; 2], Error> {
+        let (x, y) = stick.into_sdl_axis_pair();
+        Ok([self.bind_for_axis(x)?, self.bind_for_axis(y)?])
+    }
+
+    /// Gets the physical input of the underlying joystick the [`Trigger`] is
+    /// read from, see [`bind_for_button`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
+    ///
+    /// [`bind_for_button`]: Self::bind_for_button
+    #[inline]
+    pub fn bind_for_trigger(
+        &self,
+        trigger: Trigger,
+    ) -> Result<InputBind, Error> {
+        self.bind_for_axis(trigger.into_sdl_axis())
+    }
+
+    /// Gets the physical input of the underlying joystick the [`SdlAxis`] is
+    /// read from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
+    fn bind_for_axis(&self, axis: SdlAxis) -> Result<InputBind, Error> {
+        let raw = self.raw("bind_for_axis")?;
+
+        // SAFETY: SDL is alive, pointer is valid
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let bind = unsafe {
+            sdl2_sys::SDL_GameControllerGetBindForAxis(raw, axis.to_ll())
+        };
+        Ok(InputBind::from_sdl(bind, self.inverted(&axis.string())))
+    }
+
+    /// Checks if the SDL2 mapping inverts the joystick axis bound to the
+    /// input named `name` in mapping strings, e.g. `lefty:a1~`.
+    fn inverted(&self, name: &str) -> bool {
+        matches!(
+            InputBind::in_mapping(&self.mapping(), name),
+            InputBind::Axis { inverted: true, .. }
+        )
+    }
+}
@@ -0,0 +1,113 @@
+//! Output and input capabilities of a [`Gamepad`], see
+//! [`Gamepad::supports`].
+
+#[cfg(feature = "sdl2")]
+use crate::Gamepad;
+
+/// Capabilities of a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Checks if the [`Gamepad`] supports the [`Capability`]: the controller
+    /// has it, and `girl` was built with the feature of the [`Capability`].
+    ///
+    /// With the `stubs` feature, the methods of a disabled feature still
+    /// compile, returning [`Error::FeatureDisabled`], so code shared across
+    /// builds can check this instead of gating every call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::Capability;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// # #[cfg(any(feature = "rumble", feature = "stubs"))]
+    /// if gamepad.supports(Capability::Rumble) {
+    ///     gamepad.set_rumble(1000, 1000, Duration::from_millis(100))?;
+    /// }
+    /// if gamepad.supports(Capability::Led) {
+    ///     gamepad.set_led(0, 255, 0)?;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Error::FeatureDisabled`]: crate::Error::FeatureDisabled
+    #[must_use]
+    #[inline]
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Rumble => {
+                cfg!(feature = "rumble") && self.gp.has_rumble()
+            }
+            Capability::RumbleTriggers => {
+                cfg!(feature = "rumble") && self.gp.has_rumble_triggers()
+            }
+            Capability::Led => self.has_led(),
+            Capability::Sensors => {
+                #[cfg(feature = "sensors")]
+                {
+                    !self.available_sensors().is_empty()
+                }
+                #[cfg(not(feature = "sensors"))]
+                {
+                    false
+                }
+            }
+            Capability::Touchpad => {
+                #[cfg(feature = "touchpad")]
+                {
+                    self.has_touchpads()
+                }
+                #[cfg(not(feature = "touchpad"))]
+                {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Capability of a [`Gamepad`], see [`Gamepad::supports`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Capability {
+    /// Rumble of the motors, with the `rumble` feature.
+    Rumble,
+    /// Rumble of the triggers, with the `rumble` feature.
+    RumbleTriggers,
+    /// LED, always available.
+    Led,
+    /// Any sensor, e.g. a gyroscope, with the `sensors` feature.
+    Sensors,
+    /// Any touchpad, with the `touchpad` feature.
+    Touchpad,
+}
+
+impl Capability {
+    /// Gets the cargo feature the [`Capability`] needs, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::Capability;
+    /// assert_eq!(Capability::RumbleTriggers.feature(), Some("rumble"));
+    /// assert_eq!(Capability::Led.feature(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn feature(self) -> Option<&'static str> {
+        match self {
+            Self::Rumble | Self::RumbleTriggers => Some("rumble"),
+            Self::Led => None,
+            Self::Sensors => Some("sensors"),
+            Self::Touchpad => Some("touchpad"),
+        }
+    }
+}
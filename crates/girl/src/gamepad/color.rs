@@ -0,0 +1,294 @@
+//! LED colors and their correction for the nonlinear LED response.
+
+#[cfg(feature = "sdl2")]
+use crate::{Error, Gamepad};
+
+/// Default gamma of [`Gamepad::set_led_srgb`].
+#[cfg(feature = "sdl2")]
+pub(crate) const DEFAULT_LED_GAMMA: f32 = 2.2;
+
+/// An 8-bit RGB color, e.g. of a [`Gamepad`] LED.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::prelude::*;
+/// let team = Rgb::new(255, 128, 0);
+/// assert_eq!(<[u8; 3]>::from(team), [255, 128, 0]);
+/// assert_eq!(Rgb::from([255, 128, 0]), team);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rgb {
+    /// Red channel.
+    pub red: u8,
+    /// Green channel.
+    pub green: u8,
+    /// Blue channel.
+    pub blue: u8,
+}
+
+impl Rgb {
+    /// Creates an [`Rgb`] color from its channels.
+    #[must_use]
+    #[inline]
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Applies a power-law `gamma` to every channel, e.g. `2.2` to turn an
+    /// in-game sRGB color into the roughly linear intensity an LED expects.
+    ///
+    /// A `gamma` of `1.0` leaves the color unchanged, larger values darken
+    /// the midtones while keeping black and full channels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let color = Rgb::new(255, 128, 64);
+    /// assert_eq!(color.gamma_corrected(2.2), Rgb::new(255, 56, 12));
+    /// assert_eq!(color.gamma_corrected(1.0), color);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    #[inline]
+    pub fn gamma_corrected(self, gamma: f32) -> Self {
+        self.map(|channel| to_channel(from_channel(channel).powf(gamma)))
+    }
+
+    /// Converts the sRGB color to linear light with the exact sRGB transfer
+    /// function, every channel in `0.0..=1.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let [red, green, blue] = Rgb::new(255, 128, 0).to_linear();
+    /// assert!((red - 1.0).abs() < 1e-6);
+    /// assert!((green - 0.2159).abs() < 1e-4);
+    /// assert!(blue.abs() < 1e-6);
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    #[inline]
+    pub fn to_linear(self) -> [f32; 3] {
+        [self.red, self.green, self.blue].map(|channel| {
+            let value = from_channel(channel);
+            if value <= 0.040_45 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    /// Converts linear light, every channel in `0.0..=1.0`, to an sRGB color,
+    /// the inverse of [`to_linear`]. Out of range channels are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// assert_eq!(Rgb::from_linear([1.0, 0.5, 0.0]), Rgb::new(255, 188, 0));
+    /// assert_eq!(Rgb::from_linear([0.214, 2.0, -1.0]), Rgb::new(127, 255, 0));
+    ///
+    /// let color = Rgb::new(12, 128, 240);
+    /// assert_eq!(Rgb::from_linear(color.to_linear()), color);
+    /// ```
+    ///
+    /// [`to_linear`]: Self::to_linear
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    #[inline]
+    pub fn from_linear(linear: [f32; 3]) -> Self {
+        let [red, green, blue] = linear.map(|value| {
+            to_channel(if value <= 0.003_130_8 {
+                value * 12.92
+            } else {
+                1.055f32.mul_add(value.powf(1.0 / 2.4), -0.055)
+            })
+        });
+        Self { red, green, blue }
+    }
+
+    /// Scales all channels down so none exceeds `limit` of full brightness,
+    /// `limit` being in `0.0..=1.0`. Scaling them together preserves the
+    /// hue, unlike clipping every channel on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let team = Rgb::new(255, 128, 0);
+    /// assert_eq!(team.capped(0.5), Rgb::new(128, 64, 0));
+    /// assert_eq!(team.capped(1.0), team);
+    ///
+    /// // dim enough colors are unchanged
+    /// assert_eq!(Rgb::new(100, 50, 0).capped(0.5), Rgb::new(100, 50, 0));
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[must_use]
+    #[inline]
+    pub fn capped(self, limit: f32) -> Self {
+        let brightest = from_channel(self.red.max(self.green).max(self.blue));
+        let ceiling = limit.clamp(0.0, 1.0);
+        if brightest <= ceiling {
+            return self;
+        }
+        let scale = ceiling / brightest;
+        self.map(|channel| to_channel(from_channel(channel) * scale))
+    }
+
+    /// Applies `convert` to every channel.
+    #[cfg(feature = "std")]
+    fn map<F: Fn(u8) -> u8>(self, convert: F) -> Self {
+        Self {
+            red: convert(self.red),
+            green: convert(self.green),
+            blue: convert(self.blue),
+        }
+    }
+}
+
+impl From<[u8; 3]> for Rgb {
+    #[inline]
+    fn from([red, green, blue]: [u8; 3]) -> Self {
+        Self { red, green, blue }
+    }
+}
+
+impl From<Rgb> for [u8; 3] {
+    #[inline]
+    fn from(color: Rgb) -> Self {
+        [color.red, color.green, color.blue]
+    }
+}
+
+/// Gamma-corrected LED colors.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Sets the LED to an sRGB color, e.g. an in-game team color, corrected
+    /// for the nonlinear LED response so it doesn't look washed out.
+    ///
+    /// Applies the [`led_gamma`] with [`Rgb::gamma_corrected`], then the
+    /// [`led_brightness_limit`] with [`Rgb::capped`], and sets the result
+    /// like [`set_led`], which writes its values unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Gamepad`] doesn't have an LED or the operation
+    /// fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.has_led() {
+    ///     // keep the lightbar at most at half brightness to save battery
+    ///     gamepad.set_led_brightness_limit(0.5)?;
+    ///     gamepad.set_led_srgb(Rgb::new(255, 128, 0))?;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`led_gamma`]: Self::led_gamma
+    /// [`led_brightness_limit`]: Self::led_brightness_limit
+    /// [`set_led`]: Self::set_led
+    #[inline]
+    pub fn set_led_srgb(&mut self, color: Rgb) -> Result<(), Error> {
+        let Rgb { red, green, blue } = color
+            .gamma_corrected(self.led_gamma)
+            .capped(self.led_brightness_limit);
+        self.set_led(red, green, blue)
+    }
+
+    /// Sets the gamma applied by [`set_led_srgb`], `2.2` by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `gamma` isn't a positive
+    /// finite number.
+    ///
+    /// [`set_led_srgb`]: Self::set_led_srgb
+    #[inline]
+    pub fn set_led_gamma(&mut self, gamma: f32) -> Result<(), Error> {
+        if !gamma.is_finite() || gamma <= 0.0 {
+            return Err(Error::InvalidArgument { what: "gamma" });
+        }
+        self.led_gamma = gamma;
+        Ok(())
+    }
+
+    /// Gets the gamma applied by [`set_led_srgb`].
+    ///
+    /// [`set_led_srgb`]: Self::set_led_srgb
+    #[must_use]
+    #[inline]
+    pub const fn led_gamma(&self) -> f32 {
+        self.led_gamma
+    }
+
+    /// Limits the brightest channel set by [`set_led_srgb`] to `limit` of
+    /// full brightness, scaling all channels to preserve the hue, e.g. since
+    /// a lightbar at full white drains the battery noticeably. A `limit` of
+    /// `1.0`, the default, disables the cap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `limit` isn't in
+    /// `0.0..=1.0`.
+    ///
+    /// [`set_led_srgb`]: Self::set_led_srgb
+    #[inline]
+    pub fn set_led_brightness_limit(
+        &mut self,
+        limit: f32,
+    ) -> Result<(), Error> {
+        if !(0.0..=1.0).contains(&limit) {
+            return Err(Error::InvalidArgument { what: "limit" });
+        }
+        self.led_brightness_limit = limit;
+        Ok(())
+    }
+
+    /// Gets the brightness limit applied by [`set_led_srgb`].
+    ///
+    /// [`set_led_srgb`]: Self::set_led_srgb
+    #[must_use]
+    #[inline]
+    pub const fn led_brightness_limit(&self) -> f32 {
+        self.led_brightness_limit
+    }
+}
+
+/// Converts an 8-bit channel to `0.0..=1.0`.
+#[cfg(feature = "std")]
+fn from_channel(channel: u8) -> f32 {
+    f32::from(channel) / 255.0
+}
+
+/// Converts a channel in `0.0..=1.0` to 8 bits, clamping out of range
+/// values.
+#[cfg(feature = "std")]
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "clamped to `0.0..=255.0` and rounded"
+)]
+fn to_channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,371 @@
+//! Report of everything a [`Gamepad`] exposes, for debugging.
+
+use core::fmt;
+
+use bitflags::parser;
+
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{
+    Button, ButtonAxis, ControllerType, GamepadId, GamepadState, Guid,
+    InputProfile, PowerLevel, ResponseCurve, Stick, StickClamp, Trigger,
+};
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, normalize};
+#[cfg(feature = "touchpad")]
+use crate::{TouchpadAction, TouchpadState};
+
+/// Report of everything a [`Gamepad`] exposes.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Captures a [`GamepadDump`] of the identity, capabilities, input,
+    /// touchpad fingers, sensor readings and [`InputProfile`] at once, e.g.
+    /// to attach to a bug report.
+    ///
+    /// Reads that fail, e.g. of a disabled [`Sensor`], are captured as
+    /// errors inside the [`GamepadDump`]. Refreshes the touchpad fingers
+    /// like [`touchpad`] does, consuming their changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let dump = gamepad.dump();
+    /// assert_eq!(dump.id, gamepad.id());
+    /// eprintln!("{dump}");
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Sensor`]: crate::Sensor
+    /// [`touchpad`]: Self::touchpad
+    #[must_use]
+    #[inline]
+    pub fn dump(&mut self) -> GamepadDump {
+        let raw_buttons = Button::all()
+            .iter()
+            .filter(|&button| self.pressed(button))
+            .collect();
+        let raw_trigger = |trigger: Trigger| {
+            normalize::trigger(self.gp.axis(trigger.into_sdl_axis()))
+        };
+        let raw = GamepadState {
+            connected: self.connected(),
+            buttons: raw_buttons,
+            left_stick: self.stick_with_deadzone(Stick::Left, 0.0),
+            right_stick: self.stick_with_deadzone(Stick::Right, 0.0),
+            left_trigger: raw_trigger(Trigger::Left),
+            right_trigger: raw_trigger(Trigger::Right),
+        };
+        GamepadDump {
+            id: self.id(),
+            name: self.name(),
+            guid: self.guid(),
+            serial: self.serial(),
+            controller_type: self.controller_type(),
+            power: self.power(),
+            has_led: self.has_led(),
+            #[cfg(feature = "rumble")]
+            has_rumble: self.has_rumble(),
+            #[cfg(feature = "rumble")]
+            has_rumble_triggers: self.has_rumble_triggers(),
+            state: self.state(),
+            raw,
+            #[cfg(feature = "touchpad")]
+            touchpad: self.dump_touchpad(),
+            #[cfg(feature = "sensors")]
+            sensors: self.dump_sensors(),
+            profile: self.current_profile(),
+        }
+    }
+
+    /// Refreshes the touchpad fingers, returning the ones touching.
+    ///
+    /// # Errors
+    ///
+    /// Returns the message of the error if the touchpads can't be read.
+    #[cfg(feature = "touchpad")]
+    fn dump_touchpad(&mut self) -> Result<Vec<TouchpadState>, String> {
+        drop(self.touchpad().map_err(|err| err.to_string())?);
+        Ok((0..self.num_touchpads())
+            .filter_map(|touchpad| self.touchpad_state(touchpad).ok())
+            .flatten()
+            .filter(|finger| finger.action != TouchpadAction::Released)
+            .copied()
+            .collect())
+    }
+
+    /// Reads every available [`Sensor`].
+    #[cfg(feature = "sensors")]
+    fn dump_sensors(&self) -> Vec<SensorDump> {
+        self.available_sensors()
+            .into_iter()
+            .map(|sensor| {
+                let enabled = self.gp.sensor_enabled(sensor.into_sdl());
+                let reading = if enabled {
+                    self.sensor(sensor).map_err(|err| err.to_string())
+                } else {
+                    Err("not enabled".to_owned())
+                };
+                SensorDump { sensor, enabled, reading }
+            })
+            .collect()
+    }
+}
+
+/// Report of everything a [`Gamepad`] exposes, returned by
+/// [`Gamepad::dump`] and [`Girl::dump_all`].
+///
+/// Displayed as a readable multi-line report.
+///
+/// [`Girl::dump_all`]: crate::Girl::dump_all
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadDump {
+    /// [`GamepadId`] of the [`Gamepad`].
+    pub id: GamepadId,
+    /// Name reported by SDL2, see [`Gamepad::name`].
+    pub name: String,
+    /// [`Guid`] of the [`Gamepad`].
+    pub guid: Guid,
+    /// Serial number, if available.
+    pub serial: Option<String>,
+    /// [`ControllerType`] of the [`Gamepad`].
+    pub controller_type: ControllerType,
+    /// [`PowerLevel`], if available.
+    pub power: Option<PowerLevel>,
+    /// Whether the [`Gamepad`] has an LED, see [`Gamepad::has_led`].
+    pub has_led: bool,
+    /// Whether the [`Gamepad`] has rumble motors.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    pub has_rumble: bool,
+    /// Whether the [`Gamepad`] has rumble motors in its triggers.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    pub has_rumble_triggers: bool,
+    /// Input as read with the [`InputProfile`], see [`Gamepad::state`].
+    pub state: GamepadState,
+    /// Input before the [`InputProfile`]: physical [`Button`]s and analog
+    /// values without deadzones.
+    pub raw: GamepadState,
+    /// Fingers touching the touchpads, or why they couldn't be read.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    pub touchpad: Result<Vec<TouchpadState>, String>,
+    /// Available [`Sensor`]s and their readings.
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    pub sensors: Vec<SensorDump>,
+    /// Active [`InputProfile`].
+    pub profile: InputProfile,
+}
+
+/// Renders a readable multi-line report.
+impl fmt::Display for GamepadDump {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.name.is_empty() { "Gamepad" } else { &self.name };
+        writeln!(f, "{name} {}", self.id)?;
+        writeln!(f, "  guid: {}", self.guid)?;
+        writeln!(f, "  type: {}", self.controller_type.name())?;
+        writeln!(f, "  serial: {}", self.serial.as_deref().unwrap_or("none"))?;
+        writeln!(f, "  connected: {}", yes_no(self.state.connected))?;
+        match self.power {
+            Some(power) => writeln!(f, "  power: {}", power.name())?,
+            None => writeln!(f, "  power: unknown")?,
+        }
+        writeln!(f, "  led: {}", yes_no(self.has_led))?;
+        #[cfg(feature = "rumble")]
+        {
+            writeln!(f, "  rumble: {}", yes_no(self.has_rumble))?;
+            let triggers = yes_no(self.has_rumble_triggers);
+            writeln!(f, "  trigger rumble: {triggers}")?;
+        }
+        write!(f, "  buttons: ")?;
+        write_buttons(f, self.state.buttons)?;
+        write!(f, "\n  physical buttons: ")?;
+        write_buttons(f, self.raw.buttons)?;
+        writeln!(f)?;
+        for (label, stick) in [("left", Stick::Left), ("right", Stick::Right)] {
+            let [x, y] = self.state.stick(stick);
+            let [rx, ry] = self.raw.stick(stick);
+            writeln!(
+                f,
+                "  {label} stick: [{x:.3}, {y:.3}] (raw [{rx:.3}, {ry:.3}])",
+            )?;
+        }
+        for (label, trigger) in
+            [("left", Trigger::Left), ("right", Trigger::Right)]
+        {
+            let value = self.state.trigger(trigger);
+            let raw = self.raw.trigger(trigger);
+            writeln!(f, "  {label} trigger: {value:.3} (raw {raw:.3})")?;
+        }
+        #[cfg(feature = "touchpad")]
+        self.fmt_touchpad(f)?;
+        #[cfg(feature = "sensors")]
+        self.fmt_sensors(f)?;
+        self.fmt_profile(f)
+    }
+}
+
+impl GamepadDump {
+    /// Renders the touchpad fingers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the [`fmt::Formatter`] fails.
+    #[cfg(feature = "touchpad")]
+    fn fmt_touchpad(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.touchpad.as_deref() {
+            Ok(&[]) => writeln!(f, "  touchpad: no fingers"),
+            Ok(fingers) => fingers.iter().try_for_each(|finger| {
+                let [x, y] = finger.position;
+                let pressure = finger.pressure;
+                write!(f, "  touchpad {} ", finger.touchpad)?;
+                write!(f, "finger {}: ", finger.finger)?;
+                writeln!(f, "[{x:.3}, {y:.3}], pressure {pressure:.3}")
+            }),
+            Err(err) => writeln!(f, "  touchpad: error: {err}"),
+        }
+    }
+
+    /// Renders the sensor readings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the [`fmt::Formatter`] fails.
+    #[cfg(feature = "sensors")]
+    fn fmt_sensors(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.sensors.is_empty() {
+            return writeln!(f, "  sensors: none");
+        }
+        self.sensors.iter().try_for_each(|dump| {
+            write!(f, "  sensor {}: ", dump.sensor.name())?;
+            if !dump.enabled {
+                return writeln!(f, "disabled");
+            }
+            match dump.reading.as_ref() {
+                Ok(&[x, y, z]) => writeln!(f, "[{x:.3}, {y:.3}, {z:.3}]"),
+                Err(err) => writeln!(f, "error: {err}"),
+            }
+        })
+    }
+
+    /// Renders the [`InputProfile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the [`fmt::Formatter`] fails.
+    fn fmt_profile(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let profile = &self.profile;
+        writeln!(f, "  profile:")?;
+        for (label, stick) in [("left", Stick::Left), ("right", Stick::Right)] {
+            let settings = profile.stick(stick);
+            write!(
+                f,
+                "    {label} stick: deadzone {:.3}, invert y {}, clamp {}, ",
+                settings.deadzone,
+                yes_no(settings.invert_y),
+                clamp_name(settings.clamp),
+            )?;
+            match settings.curve {
+                ResponseCurve::Linear => writeln!(f, "curve linear")?,
+                ResponseCurve::Power(power) => {
+                    writeln!(f, "curve power {power:.3}")?;
+                }
+            }
+        }
+        for (label, trigger) in
+            [("left", Trigger::Left), ("right", Trigger::Right)]
+        {
+            let settings = profile.trigger(trigger);
+            writeln!(
+                f,
+                "    {label} trigger: deadzone {:.3}, threshold {:.3}",
+                settings.deadzone, settings.threshold,
+            )?;
+        }
+        write!(f, "    remap:")?;
+        if profile.remap.is_empty() {
+            write!(f, " none")?;
+        }
+        for &(from, to) in &profile.remap {
+            write!(f, " ")?;
+            write_buttons(f, from)?;
+            write!(f, " -> ")?;
+            write_buttons(f, to)?;
+        }
+        write!(f, "\n    button axes:")?;
+        if profile.button_axes.is_empty() {
+            write!(f, " none")?;
+        }
+        for &(button, source) in &profile.button_axes {
+            write!(f, " ")?;
+            write_buttons(f, button)?;
+            match source {
+                ButtonAxis::Trigger(Trigger::Left) => {
+                    write!(f, " <- left trigger")?;
+                }
+                ButtonAxis::Trigger(Trigger::Right) => {
+                    write!(f, " <- right trigger")?;
+                }
+                ButtonAxis::Joystick(axis) => {
+                    write!(f, " <- joystick axis {axis}")?;
+                }
+            }
+        }
+        writeln!(f, "\n    button threshold: {:.3}", profile.button_threshold)
+    }
+}
+
+/// Reading of a [`Sensor`] in a [`GamepadDump`].
+#[cfg(feature = "sensors")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorDump {
+    /// The [`Sensor`].
+    pub sensor: Sensor,
+    /// Whether the [`Sensor`] is enabled, see [`Gamepad::enable_sensor`].
+    pub enabled: bool,
+    /// Current reading, see [`Gamepad::sensor`], or why it couldn't be
+    /// read.
+    pub reading: Result<[f64; 3], String>,
+}
+
+/// Renders [`Button`]s as `A | B`, or `none`.
+///
+/// # Errors
+///
+/// Returns an error if writing to the [`fmt::Formatter`] fails.
+fn write_buttons(f: &mut fmt::Formatter<'_>, buttons: Button) -> fmt::Result {
+    if buttons.is_empty() {
+        f.write_str("none")
+    } else {
+        parser::to_writer(&buttons, f)
+    }
+}
+
+/// Gets the name of a [`StickClamp`], e.g. `unit circle`.
+#[expect(clippy::single_call_fn, reason = "extracted conversion")]
+const fn clamp_name(clamp: StickClamp) -> &'static str {
+    match clamp {
+        StickClamp::None => "none",
+        StickClamp::UnitCircle => "unit circle",
+        StickClamp::SquareToCircle => "square to circle",
+    }
+}
+
+/// Renders a [`bool`] as `yes` or `no`.
+const fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
@@ -0,0 +1,399 @@
+//! Timed output effects of a [`Gamepad`], advanced by [`Gamepad::tick`].
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::Error;
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, OutputCommand};
+
+/// LED color used for flashing by [`Gamepad::identify`].
+#[cfg(feature = "sdl2")]
+const IDENTIFY_COLOR: [u8; 3] = [255, 255, 255];
+
+/// Timed output effects of a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Plays the [`Identify`] pattern on the [`Gamepad`] for `duration`, so
+    /// players can tell which physical controller it is: a short double-pulse
+    /// rumble and, if the [`Gamepad`] has an LED, flashing it white at 2 Hz.
+    ///
+    /// Does whatever the hardware supports and succeeds even if it has
+    /// neither rumble nor LED. The effect is non-blocking and advanced by
+    /// [`tick`], which [`Girl::tick`] calls for the [`Gamepad`]s it tracks.
+    /// Afterwards the previous LED color is restored.
+    ///
+    /// The `duration` is validated as an [`EffectDuration`], so
+    /// [`Duration::ZERO`] stops a playing pattern right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `duration` is longer than
+    /// [`EffectDuration::MAX`], or an error if setting the rumble or LED
+    /// fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.identify(Duration::from_secs(1))?;
+    /// while gamepad.identifying() {
+    ///     gamepad.tick(Instant::now())?;
+    ///     # break;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`tick`]: Self::tick
+    /// [`Girl::tick`]: crate::Girl::tick
+    #[inline]
+    pub fn identify(&mut self, duration: Duration) -> Result<(), Error> {
+        self.identify_at(duration, Instant::now())
+    }
+
+    /// Starts [`identify`] at `now`, e.g. on the clock of [`Girl::tick`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`identify`].
+    ///
+    /// [`identify`]: Self::identify
+    /// [`Girl::tick`]: crate::Girl::tick
+    pub(crate) fn identify_at(
+        &mut self,
+        duration: Duration,
+        now: Instant,
+    ) -> Result<(), Error> {
+        let effect = EffectDuration::new(duration)?;
+        if effect.is_stop() {
+            return self.cancel_identify();
+        }
+        let restore = match self.identify.take() {
+            Some(previous) => previous.restore,
+            None => self.led,
+        };
+        self.identify = Some(IdentifyState {
+            pattern: Identify::new(now, effect.duration()),
+            restore,
+            pulses: 0,
+            lit: false,
+        });
+        self.tick(now)
+    }
+
+    /// Checks if the [`Identify`] pattern started by [`identify`] is still
+    /// playing.
+    ///
+    /// [`identify`]: Self::identify
+    #[must_use]
+    #[inline]
+    pub const fn identifying(&self) -> bool {
+        self.identify.is_some()
+    }
+
+    /// Stops the [`Identify`] pattern started by [`identify`], restoring the
+    /// previous LED color.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if restoring the LED color fails.
+    ///
+    /// [`identify`]: Self::identify
+    pub(crate) fn cancel_identify(&mut self) -> Result<(), Error> {
+        let Some(state) = self.identify.take() else {
+            return Ok(());
+        };
+        if state.lit {
+            self.identify_led(state.restore.unwrap_or_default())?;
+        }
+        Ok(())
+    }
+
+    /// Advances timed effects, such as [`identify`], to `now`.
+    ///
+    /// [`Girl::tick`] calls it for the [`Gamepad`]s it tracks, other
+    /// [`Gamepad`]s should call it regularly while effects are playing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if setting the rumble or LED fails.
+    ///
+    /// [`identify`]: Self::identify
+    /// [`Girl::tick`]: crate::Girl::tick
+    #[inline]
+    pub fn tick(&mut self, now: Instant) -> Result<(), Error> {
+        let Some(mut state) = self.identify.take() else {
+            return Ok(());
+        };
+
+        if state.pattern.finished(now) {
+            if state.lit {
+                self.identify_led(state.restore.unwrap_or_default())?;
+            }
+            return Ok(());
+        }
+
+        #[cfg(feature = "rumble")]
+        if self.gp.has_rumble() {
+            let pulses = state.pattern.pulses_started(now);
+            if pulses > state.pulses {
+                state.pulses = pulses;
+                self.set_rumble(u16::MAX, u16::MAX, Identify::PULSE_LENGTH)?;
+            }
+        }
+
+        if self.gp.has_led() {
+            let lit = state.pattern.led_on(now);
+            if lit != state.lit {
+                state.lit = lit;
+                self.identify_led(if lit {
+                    IDENTIFY_COLOR
+                } else {
+                    state.restore.unwrap_or_default()
+                })?;
+            }
+        }
+
+        self.identify = Some(state);
+        Ok(())
+    }
+
+    /// Sets the LED color for the [`Identify`] pattern, bypassing
+    /// [`set_led`] which defers colors while identifying.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if SDL2 fails to set the LED color.
+    ///
+    /// [`set_led`]: Self::set_led
+    fn identify_led(&mut self, color: [u8; 3]) -> Result<(), Error> {
+        let [red, green, blue] = color;
+        let result =
+            self.gp.set_led(red, green, blue).map_err(Error::sdl("identify"));
+        self.failures.observe("identify", &result);
+        self.journal_command(OutputCommand::Led(color.into()), &result);
+        result
+    }
+}
+
+/// Timing of the [`Gamepad::identify`] pattern, independent of any
+/// [`Gamepad`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::Identify;
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+/// let pattern = Identify::new(start, Duration::from_secs(1));
+///
+/// // double pulse at the start
+/// assert_eq!(pattern.pulses_started(at(0)), 1);
+/// assert_eq!(pattern.pulses_started(at(250)), 2);
+/// assert_eq!(pattern.pulses_started(at(900)), 2);
+///
+/// // flashing at 2 Hz
+/// assert!(pattern.led_on(at(100)));
+/// assert!(!pattern.led_on(at(300)));
+/// assert!(pattern.led_on(at(600)));
+///
+/// assert!(!pattern.finished(at(999)));
+/// assert!(pattern.finished(at(1000)));
+/// assert!(!pattern.led_on(at(1100)));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Identify {
+    /// When the pattern started.
+    start: Instant,
+    /// How long the pattern plays.
+    duration: Duration,
+}
+
+#[cfg(feature = "std")]
+impl Identify {
+    /// Period of the LED flashing.
+    pub const FLASH_PERIOD: Duration = Duration::from_millis(500);
+    /// Length of a single rumble pulse.
+    pub const PULSE_LENGTH: Duration = Duration::from_millis(100);
+    /// Time between the starts of the two rumble pulses.
+    pub const PULSE_PERIOD: Duration = Duration::from_millis(200);
+
+    /// Creates a new [`Identify`] pattern starting at `start` and playing for
+    /// `duration`.
+    #[must_use]
+    #[inline]
+    pub const fn new(start: Instant, duration: Duration) -> Self {
+        Self { start, duration }
+    }
+
+    /// Checks if the pattern is over at `now`.
+    #[must_use]
+    #[inline]
+    pub fn finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+
+    /// Counts the rumble pulses (at most two) that should have started by
+    /// `now`. Pulses that wouldn't start before the pattern ends are skipped.
+    #[must_use]
+    #[inline]
+    pub fn pulses_started(&self, now: Instant) -> u8 {
+        let elapsed = now
+            .saturating_duration_since(self.start)
+            .min(self.duration.saturating_sub(Duration::from_nanos(1)));
+        if self.duration.is_zero() {
+            0
+        } else if elapsed >= Self::PULSE_PERIOD {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Checks if the LED should be lit at `now`.
+    #[must_use]
+    #[inline]
+    pub fn led_on(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.start);
+        let period = Self::FLASH_PERIOD.as_nanos();
+        elapsed < self.duration
+            && elapsed
+                .as_nanos()
+                .checked_rem(period)
+                .is_some_and(|phase| phase < period.saturating_div(2))
+    }
+}
+
+/// Validated length of a timed effect, the policy shared by every API taking
+/// one, such as [`Gamepad::set_rumble`], [`Gamepad::set_rumble_triggers`]
+/// and [`Gamepad::identify`]:
+///
+/// - [`Duration::ZERO`] stops the effect immediately.
+/// - Other durations are rounded up to whole milliseconds, so a short one never
+///   turns into zero, which SDL2 plays forever.
+/// - Durations longer than [`MAX`] are rejected, instead of being silently cut
+///   short by SDL2. Longer effects are set again before they end.
+///
+/// Timeouts, such as the one of [`Gamepad::wait_rumble_complete`], aren't
+/// effects: [`Duration::ZERO`] checks once without waiting, and any longer
+/// one, up to [`Duration::MAX`], is fine.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{EffectDuration, Error};
+/// let ms = Duration::from_millis;
+/// let table = [
+///     (Duration::ZERO, Some(0)),
+///     (Duration::from_nanos(1), Some(1)),
+///     (Duration::from_micros(500), Some(1)),
+///     (ms(1), Some(1)),
+///     (Duration::from_micros(1500), Some(2)),
+///     (EffectDuration::MAX, Some(0xffff)),
+///     (EffectDuration::MAX + Duration::from_nanos(1), None),
+///     (ms(u32::MAX.into()), None),
+///     (ms(u64::from(u32::MAX) + 1), None),
+///     (Duration::MAX, None),
+/// ];
+/// for (duration, millis) in table {
+///     match (EffectDuration::new(duration), millis) {
+///         (Ok(effect), Some(millis)) => assert_eq!(effect.millis(), millis),
+///         (Err(Error::InvalidArgument { what: "duration" }), None) => {}
+///         (result, _) => panic!("{duration:?}: {result:?}"),
+///     }
+/// }
+///
+/// assert!(EffectDuration::new(Duration::ZERO)?.is_stop());
+/// assert_eq!(EffectDuration::new(ms(5))?.duration(), ms(5));
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Gamepad::set_rumble`]: crate::Gamepad::set_rumble
+/// [`Gamepad::set_rumble_triggers`]: crate::Gamepad::set_rumble_triggers
+/// [`Gamepad::wait_rumble_complete`]: crate::Gamepad::wait_rumble_complete
+/// [`MAX`]: Self::MAX
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EffectDuration {
+    /// Length in milliseconds, zero to stop.
+    millis: u32,
+}
+
+impl EffectDuration {
+    /// Longest effect SDL2 plays, `SDL_MAX_RUMBLE_DURATION_MS`.
+    pub const MAX: Duration = Duration::from_millis(0xffff);
+    /// Stops the effect immediately.
+    pub const STOP: Self = Self { millis: 0 };
+
+    /// Validates the `duration` of an effect.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `duration` is longer than
+    /// [`MAX`].
+    ///
+    /// [`MAX`]: Self::MAX
+    #[inline]
+    pub fn new(duration: Duration) -> Result<Self, Error> {
+        if duration > Self::MAX {
+            return Err(Error::InvalidArgument { what: "duration" });
+        }
+        let millis = duration.as_nanos().div_ceil(1_000_000);
+        Ok(Self { millis: millis.try_into().unwrap_or(u32::MAX) })
+    }
+
+    /// Checks if the effect is stopped immediately.
+    #[must_use]
+    #[inline]
+    pub const fn is_stop(self) -> bool {
+        self.millis == 0
+    }
+
+    /// Gets the length in whole milliseconds, as SDL2 takes it.
+    #[must_use]
+    #[inline]
+    pub const fn millis(self) -> u32 {
+        self.millis
+    }
+
+    /// Gets the length, rounded up to whole milliseconds.
+    #[must_use]
+    #[inline]
+    pub fn duration(self) -> Duration {
+        Duration::from_millis(self.millis.into())
+    }
+}
+
+/// Progress of a playing [`Identify`] pattern.
+#[cfg(feature = "sdl2")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IdentifyState {
+    /// Timing of the pattern.
+    pattern: Identify,
+    /// LED color to restore afterwards, if known.
+    restore: Option<[u8; 3]>,
+    /// Rumble pulses started so far.
+    pulses: u8,
+    /// Whether the LED is currently lit white.
+    lit: bool,
+}
+
+#[cfg(feature = "sdl2")]
+impl IdentifyState {
+    /// Replaces the LED color to restore after the pattern.
+    #[inline]
+    pub(crate) const fn set_restore(&mut self, color: [u8; 3]) {
+        self.restore = Some(color);
+    }
+}
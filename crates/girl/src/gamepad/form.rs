@@ -0,0 +1,461 @@
+//! [`Gamepad`] controller type, form factor and grip orientation.
+
+#[cfg(feature = "sdl2")]
+use sdl2::sys::{self as sdl2_sys, SDL_GameControllerType as SdlType};
+
+#[cfg(feature = "sdl2")]
+use crate::Gamepad;
+use crate::{Button, Misc1Role};
+
+/// Directional [`Button`] clusters, each listed clockwise starting from the
+/// top one.
+const CLUSTERS: [[Button; 4]; 2] = [
+    [Button::DPadUp, Button::DPadRight, Button::DPadDown, Button::DPadLeft],
+    [Button::Y, Button::B, Button::A, Button::X],
+];
+
+/// SDL2 controller types with a dedicated [`ControllerType`], others are
+/// [`ControllerType::Other`].
+#[cfg(feature = "sdl2")]
+const SDL_TYPES: [(SdlType, ControllerType); 11] = [
+    (SdlType::SDL_CONTROLLER_TYPE_UNKNOWN, ControllerType::Unknown),
+    (SdlType::SDL_CONTROLLER_TYPE_XBOX360, ControllerType::Xbox360),
+    (SdlType::SDL_CONTROLLER_TYPE_XBOXONE, ControllerType::XboxOne),
+    (SdlType::SDL_CONTROLLER_TYPE_PS3, ControllerType::Ps3),
+    (SdlType::SDL_CONTROLLER_TYPE_PS4, ControllerType::Ps4),
+    (SdlType::SDL_CONTROLLER_TYPE_PS5, ControllerType::Ps5),
+    (
+        SdlType::SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_PRO,
+        ControllerType::SwitchPro,
+    ),
+    (
+        SdlType::SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_LEFT,
+        ControllerType::JoyConLeft,
+    ),
+    (
+        SdlType::SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_RIGHT,
+        ControllerType::JoyConRight,
+    ),
+    (
+        SdlType::SDL_CONTROLLER_TYPE_NINTENDO_SWITCH_JOYCON_PAIR,
+        ControllerType::JoyConPair,
+    ),
+    (SdlType::SDL_CONTROLLER_TYPE_VIRTUAL, ControllerType::Virtual),
+];
+
+/// [`Gamepad`] form factor and grip orientation.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Gets the [`ControllerType`] of the [`Gamepad`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.controller_type() == ControllerType::Ps5 {
+    ///     println!("PS5 controller connected");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn controller_type(&self) -> ControllerType {
+        self.controller_type
+    }
+
+    /// Gets the [`FormFactor`] of the [`Gamepad`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.form_factor().is_joycon_half() {
+    ///     gamepad.set_sideways_mode(true);
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn form_factor(&self) -> FormFactor {
+        self.controller_type.form_factor()
+    }
+
+    /// Gets what [`Button::Misc1`] does on the [`Gamepad`], from its
+    /// [`ControllerType`], see [`Misc1Role`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::Misc1Role;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let role = gamepad.misc1_role();
+    /// if role != Misc1Role::Unknown {
+    ///     println!("press {} to take a photo", role.label());
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn misc1_role(&self) -> Misc1Role {
+        self.controller_type.misc1_role()
+    }
+
+    /// Checks if sideways mode is enabled, see [`set_sideways_mode`].
+    ///
+    /// [`set_sideways_mode`]: Self::set_sideways_mode
+    #[must_use]
+    #[inline]
+    pub const fn sideways_mode(&self) -> bool {
+        self.sideways
+    }
+
+    /// Sets whether a single Joy-Con half is held sideways.
+    ///
+    /// When enabled, [`Stick`] positions and directional [`Button`]s are
+    /// rotated by 90° to match the grip, see [`FormFactor::rotate_stick`]
+    /// and [`FormFactor::rotate_buttons`]. The rotation is applied to the
+    /// physical input first, so the deadzones, response curves, `y` axis
+    /// inversion and [`Button`] remaps of the [`InputProfile`] all see the
+    /// input as held.
+    ///
+    /// Has no effect unless the [`form_factor`] is a Joy-Con half.
+    ///
+    /// [`Stick`]: crate::Stick
+    /// [`InputProfile`]: crate::InputProfile
+    /// [`form_factor`]: Self::form_factor
+    #[inline]
+    pub const fn set_sideways_mode(&mut self, sideways: bool) {
+        self.sideways = sideways;
+    }
+
+    /// Gets the [`FormFactor`] to rotate the input with, which is
+    /// [`FormFactor::Standard`] unless sideways mode is enabled.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn grip(&self) -> FormFactor {
+        if self.sideways { self.form_factor() } else { FormFactor::Standard }
+    }
+
+    /// Detects the [`ControllerType`] reported by SDL2.
+    #[must_use]
+    #[inline]
+    pub(crate) fn detect_controller_type(&self) -> ControllerType {
+        let Ok(raw) = self.raw("controller_type") else {
+            return ControllerType::Unknown;
+        };
+        // SAFETY: SDL is alive, pointer is valid
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let ty = unsafe { sdl2_sys::SDL_GameControllerGetType(raw) };
+        ControllerType::from_sdl(ty)
+    }
+}
+
+/// Type of a [`Gamepad`], as detected by SDL2.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControllerType {
+    /// Xbox 360 controller.
+    Xbox360,
+    /// Xbox One or Series controller.
+    XboxOne,
+    /// `PlayStation` 3 controller.
+    Ps3,
+    /// `PlayStation` 4 controller.
+    Ps4,
+    /// `PlayStation` 5 controller.
+    Ps5,
+    /// Nintendo Switch Pro Controller.
+    SwitchPro,
+    /// Single left Joy-Con.
+    JoyConLeft,
+    /// Single right Joy-Con.
+    JoyConRight,
+    /// Pair of Joy-Cons acting as one controller.
+    JoyConPair,
+    /// Virtual controller.
+    Virtual,
+    /// Known to SDL2, but without a dedicated variant.
+    Other,
+    /// Type is unknown.
+    Unknown,
+}
+
+impl ControllerType {
+    /// Gets the [`FormFactor`] of this controller type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// assert_eq!(ControllerType::Ps5.form_factor(), FormFactor::Standard);
+    /// assert_eq!(
+    ///     ControllerType::JoyConLeft.form_factor(),
+    ///     FormFactor::JoyConLeft,
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn form_factor(self) -> FormFactor {
+        match self {
+            Self::JoyConLeft => FormFactor::JoyConLeft,
+            Self::JoyConRight => FormFactor::JoyConRight,
+            Self::Unknown => FormFactor::Unknown,
+            Self::Xbox360
+            | Self::XboxOne
+            | Self::Ps3
+            | Self::Ps4
+            | Self::Ps5
+            | Self::SwitchPro
+            | Self::JoyConPair
+            | Self::Virtual
+            | Self::Other => FormFactor::Standard,
+        }
+    }
+
+    /// Checks if controllers of this type can be powered off by the host,
+    /// see [`Gamepad::request_power_off`].
+    ///
+    /// Only Switch controllers accept a sleep command over HID.
+    ///
+    /// `DualShock` 4 and `DualSense` controllers are deliberately not
+    /// supported, over Bluetooth either: they have no HID command to power
+    /// off, and only do once the operating system drops their Bluetooth
+    /// link, e.g. through `BlueZ` or the Windows Bluetooth API. Neither
+    /// SDL2 nor HIDAPI expose that, and pairing-level control is out of
+    /// scope for `girl`, so [`Gamepad::request_power_off`] reports
+    /// [`Error::NotSupported`] for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// assert!(ControllerType::SwitchPro.can_power_off());
+    /// assert!(ControllerType::JoyConLeft.can_power_off());
+    /// assert!(!ControllerType::Ps4.can_power_off());
+    /// assert!(!ControllerType::Ps5.can_power_off());
+    /// ```
+    ///
+    /// [`Gamepad::request_power_off`]: crate::Gamepad::request_power_off
+    /// [`Error::NotSupported`]: crate::Error::NotSupported
+    #[must_use]
+    #[inline]
+    pub const fn can_power_off(self) -> bool {
+        match self {
+            Self::SwitchPro | Self::JoyConLeft | Self::JoyConRight => true,
+            Self::Xbox360
+            | Self::XboxOne
+            | Self::Ps3
+            | Self::Ps4
+            | Self::Ps5
+            | Self::JoyConPair
+            | Self::Virtual
+            | Self::Other
+            | Self::Unknown => false,
+        }
+    }
+
+    /// Gets what [`Button::Misc1`] does on controllers of this type, see
+    /// [`Misc1Role`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{Misc1Role, prelude::*};
+    /// assert_eq!(ControllerType::Ps5.misc1_role(), Misc1Role::MicMute);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn misc1_role(self) -> Misc1Role {
+        match self {
+            Self::XboxOne => Misc1Role::Share,
+            Self::Ps5 => Misc1Role::MicMute,
+            // the capture button is on the left Joy-Con
+            Self::SwitchPro | Self::JoyConLeft | Self::JoyConPair => {
+                Misc1Role::Capture
+            }
+            Self::Xbox360
+            | Self::Ps3
+            | Self::Ps4
+            | Self::JoyConRight
+            | Self::Virtual
+            | Self::Other
+            | Self::Unknown => Misc1Role::Unknown,
+        }
+    }
+
+    /// Parses the [`name`] of a [`ControllerType`], e.g. `"Ps5"`.
+    ///
+    /// [`name`]: Self::name
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "extracted conversion")]
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        SDL_TYPES
+            .iter()
+            .map(|&(_, known)| known)
+            .chain([Self::Other])
+            .find(|known| known.name() == name)
+    }
+
+    /// Gets the name of the variant, e.g. `"Ps5"`, as stored in the
+    /// [`DeviceHistory`] file format.
+    ///
+    /// [`DeviceHistory`]: crate::DeviceHistory
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Xbox360 => "Xbox360",
+            Self::XboxOne => "XboxOne",
+            Self::Ps3 => "Ps3",
+            Self::Ps4 => "Ps4",
+            Self::Ps5 => "Ps5",
+            Self::SwitchPro => "SwitchPro",
+            Self::JoyConLeft => "JoyConLeft",
+            Self::JoyConRight => "JoyConRight",
+            Self::JoyConPair => "JoyConPair",
+            Self::Virtual => "Virtual",
+            Self::Other => "Other",
+            Self::Unknown => "Unknown",
+        }
+    }
+
+    /// Converts from [`SdlType`].
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "extracted conversion")]
+    fn from_sdl(ty: SdlType) -> Self {
+        SDL_TYPES
+            .iter()
+            .find(|&&(sdl, _)| sdl == ty)
+            .map_or(Self::Other, |&(_, known)| known)
+    }
+}
+
+/// Physical form factor of a [`Gamepad`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FormFactor {
+    /// Two-handed controller, including a pair of Joy-Cons.
+    Standard,
+    /// Single left Joy-Con.
+    JoyConLeft,
+    /// Single right Joy-Con.
+    JoyConRight,
+    /// Form factor is unknown.
+    Unknown,
+}
+
+impl FormFactor {
+    /// Gets the name of the variant, e.g. `"JoyConLeft"`.
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::JoyConLeft => "JoyConLeft",
+            Self::JoyConRight => "JoyConRight",
+            Self::Unknown => "Unknown",
+        }
+    }
+
+    /// Checks if it is a single Joy-Con half.
+    #[must_use]
+    #[inline]
+    pub const fn is_joycon_half(self) -> bool {
+        matches!(self, Self::JoyConLeft | Self::JoyConRight)
+    }
+
+    /// Rotates a [`Stick`] position of a Joy-Con half held sideways.
+    ///
+    /// The left half is turned counterclockwise and the right half
+    /// clockwise, so pushing the stick towards the top of the Joy-Con
+    /// reports left and right respectively. Other form factors are left
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let up = [0.0, -1.0];
+    /// assert_eq!(FormFactor::JoyConLeft.rotate_stick(up), [-1.0, 0.0]);
+    /// assert_eq!(FormFactor::JoyConRight.rotate_stick(up), [1.0, 0.0]);
+    /// assert_eq!(FormFactor::Standard.rotate_stick(up), up);
+    ///
+    /// let right = [1.0, 0.0];
+    /// assert_eq!(FormFactor::JoyConLeft.rotate_stick(right), [0.0, -1.0]);
+    /// assert_eq!(FormFactor::JoyConRight.rotate_stick(right), [0.0, 1.0]);
+    /// ```
+    ///
+    /// [`Stick`]: crate::Stick
+    #[must_use]
+    #[inline]
+    pub const fn rotate_stick(self, [x, y]: [f64; 2]) -> [f64; 2] {
+        match self {
+            Self::JoyConLeft => [y, -x],
+            Self::JoyConRight => [-y, x],
+            Self::Standard | Self::Unknown => [x, y],
+        }
+    }
+
+    /// Rotates the D-pad and face [`Button`]s of a Joy-Con half held
+    /// sideways, the same way as [`rotate_stick`]. Other [`Button`]s and
+    /// form factors are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let left = FormFactor::JoyConLeft;
+    /// assert_eq!(left.rotate_buttons(Button::DPadUp), Button::DPadLeft);
+    /// assert_eq!(left.rotate_buttons(Button::DPadLeft), Button::DPadDown);
+    ///
+    /// let right = FormFactor::JoyConRight;
+    /// let pressed = Button::Y | Button::Start;
+    /// assert_eq!(right.rotate_buttons(pressed), Button::B | Button::Start);
+    /// assert_eq!(right.rotate_buttons(Button::A), Button::X);
+    /// ```
+    ///
+    /// [`rotate_stick`]: Self::rotate_stick
+    #[must_use]
+    #[inline]
+    pub fn rotate_buttons(self, buttons: Button) -> Button {
+        // clockwise steps to rotate by
+        let steps = match self {
+            Self::JoyConLeft => 3,
+            Self::JoyConRight => 1,
+            Self::Standard | Self::Unknown => return buttons,
+        };
+        let mut rotated = buttons;
+        for cluster in CLUSTERS {
+            let targets = cluster.iter().cycle().skip(steps);
+            for (&from, &to) in cluster.iter().zip(targets) {
+                rotated.set(to, buttons.contains(from));
+            }
+        }
+        rotated
+    }
+}
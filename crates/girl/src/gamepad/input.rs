@@ -1,25 +1,29 @@
 //! [`Gamepad`] input types.
 
-use sdl2::{
-    controller::{Axis as SdlAxis, Button as SdlButton},
-    sys::SDL_JOYSTICK_AXIS_MAX,
-};
-
-use crate::{Gamepad, gamepad::map};
+#[cfg(feature = "sdl2")]
+use sdl2::controller::{Axis as SdlAxis, Button as SdlButton};
 
-/// Maximum value for analog axis inputs.
-pub(crate) const AXIS_MAX: f64 = SDL_JOYSTICK_AXIS_MAX as f64;
+#[cfg(feature = "sdl2")]
+use crate::{
+    Gamepad, GamepadId, GamepadRead, GamepadState,
+    normalize::{self, apply_deadzone, clamp_stick},
+};
 
 /// [`Gamepad`] inputs.
 // TODO: Try remove on next Rust version update.
 #[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
 #[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+#[expect(clippy::same_name_method, reason = "implements `GamepadRead`")]
 impl Gamepad {
-    /// Gets the current position of an analog [`Stick`] with default
-    /// [`STICK_DEADZONE`] threshold.
+    /// Gets the current position of an analog [`Stick`] with the configured
+    /// deadzone ([`STICK_DEADZONE`] by default, or adaptive),
+    /// [`ResponseCurve`], `y` axis inversion and output smoothing (see
+    /// [`set_stick_deadzone`], [`enable_adaptive_deadzone`],
+    /// [`set_stick_curve`], [`set_invert_y`] and [`set_stick_smoothing`]).
     ///
     /// Values are in the range `[-1.0, 1.0]`, where `x` is from left to right
-    /// and `y` is from **top** to **bottom**.
+    /// and `y` is from **top** to **bottom** (unless inverted).
     ///
     /// ```text
     ///           -1.0
@@ -36,7 +40,7 @@ impl Gamepad {
     /// # Examples
     ///
     /// ```
-    /// # use girl::Stick;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -48,14 +52,54 @@ impl Gamepad {
     /// ```
     ///
     /// [`STICK_DEADZONE`]: Self::STICK_DEADZONE
+    /// [`ResponseCurve`]: crate::ResponseCurve
+    /// [`set_stick_deadzone`]: Self::set_stick_deadzone
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    /// [`set_stick_curve`]: Self::set_stick_curve
+    /// [`set_invert_y`]: Self::set_invert_y
+    /// [`set_stick_smoothing`]: Self::set_stick_smoothing
     #[must_use]
     #[inline]
     pub fn stick(&self, stick: Stick) -> [f64; 2] {
-        self.stick_with_deadzone(stick, Self::STICK_DEADZONE)
+        let raw = self.stick_with_deadzone(stick, 0.0);
+        let mut profile = *self.profile.stick(stick);
+        if let Some(deadzone) = self.adapt(stick, raw) {
+            profile.deadzone = deadzone;
+        }
+        clamp_stick(self.smooth(stick, profile.apply(raw)))
+    }
+
+    /// Gets how far an analog [`Stick`] is pushed, in the range `[0.0, 1.0]`,
+    /// regardless of the direction.
+    ///
+    /// The magnitude of the position reported by [`stick`], so the
+    /// configured settings apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let magnitude = gamepad.stick_magnitude(Stick::Left);
+    /// let speed = if magnitude > 0.8 { "run" } else { "walk" };
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    #[must_use]
+    #[inline]
+    pub fn stick_magnitude(&self, stick: Stick) -> f64 {
+        let [x, y] = self.stick(stick);
+        x.hypot(y).min(1.0)
     }
 
     /// Gets the current position of an analog [`Stick`] with the provided
-    /// `deadzone` threshold.
+    /// `deadzone` threshold, ignoring the rest of the configured settings
+    /// except for [`set_sideways_mode`].
     ///
     /// Values are in the range `[-1.0, 1.0]`, where `x` is from left to right
     /// and `y` is from **top** to **bottom**.
@@ -75,7 +119,7 @@ impl Gamepad {
     /// # Examples
     ///
     /// ```
-    /// # use girl::Stick;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -85,25 +129,31 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`set_sideways_mode`]: Self::set_sideways_mode
     #[must_use]
     #[inline]
     pub fn stick_with_deadzone(&self, stick: Stick, deadzone: f64) -> [f64; 2] {
+        if self.suspended_for_query() {
+            return [0.0, 0.0];
+        }
         let (x, y) = stick.into_sdl_axis_pair();
-        [
-            map(self.gp.axis(x).into(), deadzone, AXIS_MAX),
-            map(self.gp.axis(y).into(), deadzone, AXIS_MAX),
-        ]
+        self.grip().rotate_stick([
+            apply_deadzone(normalize::axis(self.gp.axis(x)), deadzone),
+            apply_deadzone(normalize::axis(self.gp.axis(y)), deadzone),
+        ])
     }
 
-    /// Gets the current value of a [`Trigger`].
+    /// Gets the current value of a [`Trigger`] with the configured deadzone
+    /// (see [`set_trigger_deadzone`]).
     ///
-    /// Value is in the range `[-1.0, 1.0]`, where `0.0` is the rest position
-    /// and `1.0` is fully pressed.
+    /// Value is in the range `[0.0, 1.0]`, where `0.0` is the rest position
+    /// and `1.0` is fully pressed, see [`normalize::trigger`].
     ///
     /// # Examples
     ///
     /// ```
-    /// # use girl::Trigger;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -113,20 +163,28 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`set_trigger_deadzone`]: Self::set_trigger_deadzone
     #[must_use]
     #[inline]
     pub fn trigger(&self, trigger: Trigger) -> f64 {
-        map(self.gp.axis(trigger.into_sdl_axis()).into(), 0.0, AXIS_MAX)
+        if self.suspended_for_query() {
+            return 0.0;
+        }
+        let axis = self.gp.axis(trigger.into_sdl_axis());
+        self.profile.trigger(trigger).apply(normalize::trigger(axis))
     }
 
     /// Gets the current state of the specified [`Button`]\(s).
     ///
-    /// Allows to query multiple [`Button`]\(s) at once.
+    /// Allows to query multiple [`Button`]\(s) at once. Physical buttons are
+    /// reported as their remapped counterparts (see [`remap_button`]), after
+    /// rotating them for a Joy-Con held sideways (see [`set_sideways_mode`]).
     ///
     /// # Examples
     ///
     /// ```
-    /// # use girl::Button;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -141,13 +199,71 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`remap_button`]: Self::remap_button
+    /// [`set_sideways_mode`]: Self::set_sideways_mode
     #[must_use]
     #[inline]
     pub fn buttons(&self, buttons: Button) -> Button {
-        buttons
+        if self.suspended_for_query() {
+            return Button::empty();
+        }
+        let grip = self.grip();
+        if self.profile.remap.is_empty() && !grip.is_joycon_half() {
+            return buttons
+                .iter()
+                .filter(|&button| self.pressed(button))
+                .collect();
+        }
+
+        let held: Button = Button::all()
             .iter()
-            .filter(|button: &Button| self.gp.button(button.into_sdl()))
-            .collect()
+            .filter(|&button| self.pressed(button))
+            .collect();
+        self.logical(held) & buttons
+    }
+
+    /// Gets the [`Button`]s pressed at any point between the two last
+    /// [`Girl::update`]s, even if they were released before the second one.
+    ///
+    /// Unlike [`buttons`], which samples the current state, this is backed by
+    /// the button [`Event`]s, so no tap is lost regardless of how rarely
+    /// [`Girl::update`] is called. Every tap is reported after exactly one
+    /// [`Girl::update`], with the same remaps as [`buttons`]. Always empty
+    /// in eventless mode (see [`Girl::new_eventless`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     if gamepad.tapped_since_update().contains(Button::A) {
+    ///         // fire, even if A was released before this update
+    ///     }
+    ///     std::thread::sleep(std::time::Duration::from_millis(100));
+    ///     # break;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::new_eventless`]: crate::Girl::new_eventless
+    /// [`buttons`]: Self::buttons
+    /// [`Event`]: crate::Event
+    #[must_use]
+    #[inline]
+    pub fn tapped_since_update(&self) -> Button {
+        if self.suspended_for_query() {
+            return Button::empty();
+        }
+        let tapped = self.shared().taps().taps(self.id());
+        self.logical(tapped)
     }
 
     /// Checks if all specified [`Button`]\(s) are currently pressed.
@@ -157,7 +273,7 @@ impl Gamepad {
     /// # Examples
     ///
     /// ```
-    /// # use girl::Button;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -172,6 +288,394 @@ impl Gamepad {
     pub fn buttons_pressed(&self, buttons: Button) -> bool {
         self.buttons(buttons) == buttons
     }
+
+    /// Checks if all specified [`Button`]\(s) are currently released, i.e.
+    /// none of them is pressed.
+    ///
+    /// [`Button::empty()`] is always released. Reads the held [`Button`]s
+    /// once, like [`buttons_exactly`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // leave the menu once both shoulders are let go
+    /// let shoulders = Button::LeftShoulder | Button::RightShoulder;
+    /// if gamepad.buttons_released(shoulders) {}
+    /// assert!(gamepad.buttons_released(Button::empty()));
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`buttons_exactly`]: Self::buttons_exactly
+    #[must_use]
+    #[inline]
+    pub fn buttons_released(&self, buttons: Button) -> bool {
+        !self.buttons(Button::all()).intersects(buttons)
+    }
+
+    /// Checks if none of the specified [`Button`]\(s) is currently
+    /// pressed, the same as [`buttons_released`].
+    ///
+    /// [`Button::empty()`] is never pressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // only accept a confirmation without modifiers held
+    /// let modifiers = Button::LeftShoulder | Button::RightShoulder;
+    /// if gamepad.buttons_pressed(Button::A)
+    ///     && gamepad.buttons_none_pressed(modifiers)
+    /// {}
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`buttons_released`]: Self::buttons_released
+    #[must_use]
+    #[inline]
+    pub fn buttons_none_pressed(&self, buttons: Button) -> bool {
+        self.buttons_released(buttons)
+    }
+
+    /// Checks if exactly the specified [`Button`]\(s) are currently
+    /// pressed, and nothing else.
+    ///
+    /// [`Button::empty()`] checks that no [`Button`] is pressed at all.
+    /// Reads the held [`Button`]s once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // a chord that doesn't trigger while mashing every button
+    /// if gamepad.buttons_exactly(Button::Back | Button::Start) {}
+    /// if gamepad.buttons_exactly(Button::empty()) {
+    ///     // nothing is held
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn buttons_exactly(&self, buttons: Button) -> bool {
+        self.buttons(Button::all()) == buttons
+    }
+
+    /// Gets how far the specified [`Button`]\(s) are pressed, in the range
+    /// `[0.0, 1.0]`.
+    ///
+    /// Digital [`Button`]s report `0.0` or `1.0`, while the ones backed by an
+    /// analog source (see [`set_button_axis_source`]) report its value, e.g.
+    /// the analog face buttons of a `GameCube` controller adapter. Remaps
+    /// apply like in [`buttons`]. Multiple [`Button`]s report the least
+    /// pressed one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ButtonAxis, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let source = ButtonAxis::Joystick(6);
+    /// gamepad.set_button_axis_source(Button::A, Some(source))?;
+    /// let pressure = gamepad.button_value(Button::A);
+    /// assert!((0.0..=1.0).contains(&pressure));
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_button_axis_source`]: Self::set_button_axis_source
+    /// [`buttons`]: Self::buttons
+    #[must_use]
+    #[inline]
+    pub fn button_value(&self, buttons: Button) -> f64 {
+        if self.suspended_for_query() || buttons.is_empty() {
+            return 0.0;
+        }
+        buttons
+            .iter()
+            .map(|logical| {
+                Button::all()
+                    .iter()
+                    .filter(|&physical| self.logical(physical) == logical)
+                    .map(|physical| self.physical_value(physical))
+                    .fold(0.0f64, f64::max)
+            })
+            .fold(1.0f64, f64::min)
+    }
+
+    /// Gets the [`ButtonChanges`] since the previous call, as raw bits.
+    ///
+    /// The [`Button`]s reported on the previous call are stored on the
+    /// [`Gamepad`] and refreshed on every call. The diff is O(number of
+    /// [`Button`] bits) and doesn't allocate, which makes it suitable for
+    /// per-frame input sampling, e.g. in rollback netcode. The first call
+    /// reports all held [`Button`]s as pressed, and disconnecting reports
+    /// them as released, so a reconnected [`Gamepad`] starts from scratch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     let changes = gamepad.buttons_changed();
+    ///     if changes.pressed.contains(Button::A) {
+    ///         // jump
+    ///     }
+    ///     # break;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn buttons_changed(&mut self) -> ButtonChanges {
+        let current = self.buttons(Button::all());
+        self.button_tracker.changes(current)
+    }
+
+    /// Converts physical [`Button`]s to the logical ones, rotating them for
+    /// sideways mode and then remapping them.
+    fn logical(&self, physical: Button) -> Button {
+        let rotated = self.grip().rotate_buttons(physical);
+        if self.profile.remap.is_empty() {
+            return rotated;
+        }
+        rotated.iter().map(|button| self.profile.remapped(button)).collect()
+    }
+
+    /// Checks if the physical single-bit [`Button`] is pressed.
+    pub(crate) fn pressed(&self, button: Button) -> bool {
+        if let Some(source) = self.profile.button_axis(button) {
+            return self.axis_value(source) >= self.profile.button_threshold;
+        }
+        button.into_sdl().is_some_and(|sdl| self.gp.button(sdl))
+    }
+
+    /// Gets how far the physical single-bit [`Button`] is pressed.
+    fn physical_value(&self, button: Button) -> f64 {
+        match self.profile.button_axis(button) {
+            Some(source) => self.axis_value(source),
+            None if self.pressed(button) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Reads an analog [`Button`] source, in the range `[0.0, 1.0]`.
+    fn axis_value(&self, source: ButtonAxis) -> f64 {
+        let raw = match source {
+            ButtonAxis::Trigger(trigger) => {
+                self.gp.axis(trigger.into_sdl_axis())
+            }
+            ButtonAxis::Joystick(index) => self.joy.axis(index).unwrap_or(0),
+        };
+        normalize::trigger(raw)
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl GamepadRead for Gamepad {
+    #[inline]
+    fn button_value(&self, buttons: Button) -> f64 {
+        Self::button_value(self, buttons)
+    }
+
+    #[inline]
+    fn buttons(&self, buttons: Button) -> Button {
+        Self::buttons(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_changed(&mut self) -> ButtonChanges {
+        Self::buttons_changed(self)
+    }
+
+    #[inline]
+    fn buttons_exactly(&self, buttons: Button) -> bool {
+        Self::buttons_exactly(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_none_pressed(&self, buttons: Button) -> bool {
+        Self::buttons_none_pressed(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_pressed(&self, buttons: Button) -> bool {
+        Self::buttons_pressed(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_released(&self, buttons: Button) -> bool {
+        Self::buttons_released(self, buttons)
+    }
+
+    #[inline]
+    fn connected(&self) -> bool {
+        Self::connected(self)
+    }
+
+    #[inline]
+    fn id(&self) -> GamepadId {
+        Self::id(self)
+    }
+
+    #[inline]
+    fn state(&self) -> GamepadState {
+        Self::state(self)
+    }
+
+    #[inline]
+    fn stick(&self, stick: Stick) -> [f64; 2] {
+        Self::stick(self, stick)
+    }
+
+    #[inline]
+    fn trigger(&self, trigger: Trigger) -> f64 {
+        Self::trigger(self, trigger)
+    }
+}
+
+/// [`Button`]s that changed between two polls, see
+/// [`Gamepad::buttons_changed`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ButtonChanges {
+    /// [`Button`]s that were pressed.
+    pub pressed: Button,
+    /// [`Button`]s that were released.
+    pub released: Button,
+}
+
+impl ButtonChanges {
+    /// Computes the [`ButtonChanges`] from the `previous` to the `current`
+    /// held [`Button`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ButtonChanges, prelude::*};
+    /// let frames = [Button::A, Button::A | Button::B, Button::B, Button::B];
+    /// let mut previous = Button::empty();
+    /// let mut changes = vec![];
+    /// for current in frames {
+    ///     changes.push(ButtonChanges::between(previous, current));
+    ///     previous = current;
+    /// }
+    ///
+    /// assert_eq!(changes[0].pressed, Button::A);
+    /// assert_eq!(changes[1].pressed, Button::B);
+    /// assert_eq!(changes[2].released, Button::A);
+    /// assert!(changes[3].is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn between(previous: Button, current: Button) -> Self {
+        Self {
+            pressed: current.difference(previous),
+            released: previous.difference(current),
+        }
+    }
+
+    /// Checks if nothing changed.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.pressed.is_empty() && self.released.is_empty()
+    }
+}
+
+/// Held [`Button`]s of the previous sample, reporting the
+/// [`ButtonChanges`] of every new one, see [`Gamepad::buttons_changed`].
+///
+/// Samples are plain [`Button`]s, so the tracker can be driven without a
+/// controller, e.g. from recorded input.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{ButtonTracker, prelude::*};
+/// let mut tracker = ButtonTracker::new();
+///
+/// // the first sample reports every held button as pressed
+/// let changes = tracker.changes(Button::A | Button::B);
+/// assert_eq!(changes.pressed, Button::A | Button::B);
+/// assert!(changes.released.is_empty());
+///
+/// // the same sample again reports nothing
+/// assert!(tracker.changes(Button::A | Button::B).is_empty());
+///
+/// // presses and releases in the same sample are both reported
+/// let changes = tracker.changes(Button::B | Button::X);
+/// assert_eq!(changes.pressed, Button::X);
+/// assert_eq!(changes.released, Button::A);
+///
+/// // a disconnected controller holds nothing, releasing everything
+/// let changes = tracker.changes(Button::empty());
+/// assert_eq!(changes.released, Button::B | Button::X);
+/// assert_eq!(tracker.previous(), Button::empty());
+///
+/// // resetting forgets the previous sample
+/// _ = tracker.changes(Button::Y);
+/// tracker.reset();
+/// assert_eq!(tracker.changes(Button::Y).pressed, Button::Y);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ButtonTracker {
+    /// [`Button`]s held in the previous sample.
+    previous: Button,
+}
+
+impl ButtonTracker {
+    /// Creates a [`ButtonTracker`] as if no [`Button`] was held.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { previous: Button::empty() }
+    }
+
+    /// Gets the [`Button`]s held in the previous sample.
+    #[must_use]
+    #[inline]
+    pub const fn previous(&self) -> Button {
+        self.previous
+    }
+
+    /// Takes the `current` held [`Button`]s, returning the
+    /// [`ButtonChanges`] since the previous sample.
+    #[inline]
+    pub const fn changes(&mut self, current: Button) -> ButtonChanges {
+        let changes = ButtonChanges::between(self.previous, current);
+        self.previous = current;
+        changes
+    }
+
+    /// Forgets the previous sample, so that the next one reports every
+    /// held [`Button`] as pressed.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.previous = Button::empty();
+    }
 }
 
 /// Analog sticks on a [`Gamepad`].
@@ -181,6 +685,7 @@ impl Gamepad {
               major update"
 )]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stick {
     /// Left analog stick.
     Left,
@@ -188,6 +693,7 @@ pub enum Stick {
     Right,
 }
 
+#[cfg(feature = "sdl2")]
 impl Stick {
     /// Converts to [`SdlAxis`] pair.
     #[must_use]
@@ -200,6 +706,24 @@ impl Stick {
     }
 }
 
+/// Names in SDL2 mapping strings.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "feature gated")]
+#[cfg(feature = "sim")]
+impl Stick {
+    /// Gets the names of the `x` and `y` axes of the [`Stick`] in SDL2
+    /// mapping strings, e.g. `"leftx"`.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn mapping_names(self) -> [&'static str; 2] {
+        match self {
+            Self::Left => ["leftx", "lefty"],
+            Self::Right => ["rightx", "righty"],
+        }
+    }
+}
+
 /// Triggers on a [`Gamepad`].
 #[expect(
     clippy::exhaustive_enums,
@@ -207,6 +731,7 @@ impl Stick {
               major update"
 )]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Trigger {
     /// Left trigger.
     Left,
@@ -214,6 +739,7 @@ pub enum Trigger {
     Right,
 }
 
+#[cfg(feature = "sdl2")]
 impl Trigger {
     /// Converts to [`SdlAxis`].
     #[must_use]
@@ -226,9 +752,87 @@ impl Trigger {
     }
 }
 
+/// Names in SDL2 mapping strings.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "feature gated")]
+#[cfg(feature = "sim")]
+impl Trigger {
+    /// Gets the name of the [`Trigger`] in SDL2 mapping strings, e.g.
+    /// `"lefttrigger"`.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn mapping_name(self) -> &'static str {
+        match self {
+            Self::Left => "lefttrigger",
+            Self::Right => "righttrigger",
+        }
+    }
+}
+
+/// Single analog axes on a [`Gamepad`], see [`Gamepad::read_fresh`].
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "if gamepads get more axes in the future, we'll add them in a \
+              major update"
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    /// Horizontal axis of the left [`Stick`].
+    LeftX,
+    /// Vertical axis of the left [`Stick`].
+    LeftY,
+    /// Horizontal axis of the right [`Stick`].
+    RightX,
+    /// Vertical axis of the right [`Stick`].
+    RightY,
+    /// Left [`Trigger`].
+    LeftTrigger,
+    /// Right [`Trigger`].
+    RightTrigger,
+}
+
+#[cfg(feature = "sdl2")]
+impl Axis {
+    /// Converts to [`SdlAxis`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn into_sdl(self) -> SdlAxis {
+        match self {
+            Self::LeftX => SdlAxis::LeftX,
+            Self::LeftY => SdlAxis::LeftY,
+            Self::RightX => SdlAxis::RightX,
+            Self::RightY => SdlAxis::RightY,
+            Self::LeftTrigger => SdlAxis::TriggerLeft,
+            Self::RightTrigger => SdlAxis::TriggerRight,
+        }
+    }
+}
+
+/// Analog source of a [`Button`], see [`Gamepad::set_button_axis_source`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonAxis {
+    /// A [`Trigger`] axis, e.g. of a controller mapping analog shoulder
+    /// buttons as triggers.
+    Trigger(Trigger),
+    /// Axis of the underlying joystick by index, e.g. an extra axis exposing
+    /// an analog face button. Negative values read as released.
+    Joystick(u32),
+}
+
 bitflags::bitflags! {
     /// Gamepad buttons.
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[derive(
+        Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(transparent)
+    )]
     pub struct Button: u32 {
         /// A button (typically bottom button on the right side).
         ///
@@ -312,7 +916,9 @@ bitflags::bitflags! {
         ///
         /// "Share" on the Xbox Series X/S controller, "Microphone" on the
         /// PlayStation 5 controller, "Capture button" on the Nintendo Switch
-        /// controller.
+        /// controller, see [`Gamepad::misc1_role`].
+        ///
+        /// [`Gamepad::misc1_role`]: crate::Gamepad::misc1_role
         const Misc1 = 1 << 15;
 
         /// Paddle 1.
@@ -352,6 +958,7 @@ bitflags::bitflags! {
     }
 }
 
+#[cfg(feature = "sdl2")]
 impl Button {
     /// Converts from SDL button.
     #[must_use]
@@ -383,10 +990,17 @@ impl Button {
     }
 
     /// Converts to SDL button.
+    ///
+    /// Returns [`None`] unless exactly one bit is set, which is a bug in
+    /// debug builds.
     #[must_use]
     #[inline]
-    pub(crate) fn into_sdl(self) -> SdlButton {
-        bitflags::bitflags_match!(self, {
+    pub(crate) fn into_sdl(self) -> Option<SdlButton> {
+        debug_assert!(
+            self.bits().is_power_of_two(),
+            "use only with single button bit set, got {self:?}"
+        );
+        Some(bitflags::bitflags_match!(self, {
             Self::A => SdlButton::A,
             Self::B => SdlButton::B,
             Self::X => SdlButton::X,
@@ -408,7 +1022,47 @@ impl Button {
             Self::Paddle3 => SdlButton::Paddle3,
             Self::Paddle4 => SdlButton::Paddle4,
             Self::Touchpad => SdlButton::Touchpad,
-            _ => unreachable!("use only with single button bit set"),
-        })
+            _ => return None,
+        }))
+    }
+}
+
+/// Names in SDL2 mapping strings.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "feature gated")]
+#[cfg(feature = "sim")]
+impl Button {
+    /// Gets the name of the single-bit [`Button`] in SDL2 mapping strings,
+    /// e.g. `"leftshoulder"`.
+    ///
+    /// Returns [`None`] unless exactly one bit is set.
+    #[must_use]
+    #[inline]
+    pub(crate) fn mapping_name(self) -> Option<&'static str> {
+        Some(bitflags::bitflags_match!(self, {
+            Self::A => "a",
+            Self::B => "b",
+            Self::X => "x",
+            Self::Y => "y",
+            Self::Back => "back",
+            Self::Guide => "guide",
+            Self::Start => "start",
+            Self::LeftStick => "leftstick",
+            Self::RightStick => "rightstick",
+            Self::LeftShoulder => "leftshoulder",
+            Self::RightShoulder => "rightshoulder",
+            Self::DPadUp => "dpup",
+            Self::DPadDown => "dpdown",
+            Self::DPadLeft => "dpleft",
+            Self::DPadRight => "dpright",
+            Self::Misc1 => "misc1",
+            Self::Paddle1 => "paddle1",
+            Self::Paddle2 => "paddle2",
+            Self::Paddle3 => "paddle3",
+            Self::Paddle4 => "paddle4",
+            Self::Touchpad => "touchpad",
+            _ => return None,
+        }))
     }
 }
@@ -0,0 +1,371 @@
+//! Journal of the commands sent to a [`Gamepad`], e.g. to debug haptics.
+
+#[cfg(feature = "sdl2")]
+use core::cell::Ref;
+use core::{fmt, time::Duration};
+use std::time::Instant;
+
+use crate::Rgb;
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+#[cfg(feature = "sdl2")]
+use crate::{Error, Gamepad};
+
+/// Command journaling of a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Starts or stops recording the commands sent to the [`Gamepad`] into
+    /// a [`CommandJournal`], e.g. to see what a rumble design actually
+    /// sent. Stopping drops the recorded commands, starting again begins
+    /// a new [`CommandJournal`].
+    ///
+    /// Off by default, see [`Girl::set_command_journal`] to turn it on for
+    /// every [`Gamepad`]. Only this [`Gamepad`] handle records, not others
+    /// opened for the same controller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.set_command_journal(true);
+    /// _ = gamepad.set_led(255, 0, 0);
+    /// # #[cfg(feature = "rumble")]
+    /// _ = gamepad.set_rumble(1000, 1000, Duration::from_millis(100));
+    ///
+    /// let journal = gamepad.command_journal().expect("recording");
+    /// for record in journal.iter() {
+    ///     println!("{record}");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::set_command_journal`]: crate::Girl::set_command_journal
+    #[inline]
+    pub fn set_command_journal(&mut self, enabled: bool) {
+        let journal = self.journal.get_mut();
+        if enabled {
+            _ = journal.get_or_insert_with(|| {
+                Box::new(CommandJournal::new(Instant::now()))
+            });
+        } else {
+            *journal = None;
+        }
+    }
+
+    /// Gets the [`CommandJournal`] of the [`Gamepad`], [`None`] unless
+    /// recording, see [`set_command_journal`].
+    ///
+    /// Nothing is recorded while the returned [`Ref`] is held.
+    ///
+    /// [`set_command_journal`]: Self::set_command_journal
+    #[must_use]
+    #[inline]
+    pub fn command_journal(&self) -> Option<Ref<'_, CommandJournal>> {
+        Ref::filter_map(self.journal.try_borrow().ok()?, Option::as_deref).ok()
+    }
+
+    /// Records a command sent with its `result`, if recording.
+    pub(crate) fn journal_command(
+        &self,
+        command: OutputCommand,
+        result: &Result<(), Error>,
+    ) {
+        if let Ok(mut journal) = self.journal.try_borrow_mut()
+            && let Some(journal) = journal.as_deref_mut()
+        {
+            journal.record(Instant::now(), command, result.is_ok());
+        }
+    }
+}
+
+/// Command sent to a [`Gamepad`], recorded in its [`CommandJournal`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputCommand {
+    /// Rumble of the motors, after [`RumbleFold`], see
+    /// [`Gamepad::set_rumble`].
+    ///
+    /// [`RumbleFold`]: crate::RumbleFold
+    /// [`Gamepad::set_rumble`]: crate::Gamepad::set_rumble
+    #[cfg(feature = "rumble")]
+    Rumble {
+        /// Intensity of the low frequency motor.
+        low: u16,
+        /// Intensity of the high frequency motor.
+        high: u16,
+        /// Duration of the effect.
+        duration: Duration,
+    },
+    /// Rumble of the triggers, see [`Gamepad::set_rumble_triggers`].
+    ///
+    /// [`Gamepad::set_rumble_triggers`]:
+    ///     crate::Gamepad::set_rumble_triggers
+    #[cfg(feature = "rumble")]
+    RumbleTriggers {
+        /// Intensity of the left trigger.
+        left: u16,
+        /// Intensity of the right trigger.
+        right: u16,
+        /// Duration of the effect.
+        duration: Duration,
+    },
+    /// LED color, set with [`Gamepad::set_led`] or by an effect such as
+    /// [`Gamepad::identify`].
+    ///
+    /// [`Gamepad::set_led`]: crate::Gamepad::set_led
+    /// [`Gamepad::identify`]: crate::Gamepad::identify
+    Led(Rgb),
+    /// [`Sensor`] enabled or disabled, see [`Gamepad::enable_sensor`].
+    ///
+    /// [`Gamepad::enable_sensor`]: crate::Gamepad::enable_sensor
+    #[cfg(feature = "sensors")]
+    Sensor {
+        /// The [`Sensor`].
+        sensor: Sensor,
+        /// Whether it was enabled, or disabled.
+        enabled: bool,
+    },
+}
+
+/// Compact form for logs, e.g. `led #ff8000`, `rumble 1000/0:100ms` or
+/// `enable Gyroscope`.
+impl fmt::Display for OutputCommand {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "rumble")]
+            Self::Rumble { low, high, duration } => {
+                write!(f, "rumble {low}/{high}:{}ms", duration.as_millis())
+            }
+            #[cfg(feature = "rumble")]
+            Self::RumbleTriggers { left, right, duration } => {
+                let millis = duration.as_millis();
+                write!(f, "trigger rumble {left}/{right}:{millis}ms")
+            }
+            Self::Led(Rgb { red, green, blue }) => {
+                write!(f, "led #{red:02x}{green:02x}{blue:02x}")
+            }
+            #[cfg(feature = "sensors")]
+            Self::Sensor { sensor, enabled } => {
+                let action = if enabled { "enable" } else { "disable" };
+                write!(f, "{action} {}", sensor.name())
+            }
+        }
+    }
+}
+
+/// [`OutputCommand`] recorded in a [`CommandJournal`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandRecord {
+    /// When it was sent, since the [`CommandJournal`] started, see
+    /// [`CommandJournal::started`].
+    pub at: Duration,
+    /// The [`OutputCommand`].
+    pub command: OutputCommand,
+    /// Whether the controller took it.
+    pub succeeded: bool,
+}
+
+/// Compact form for logs, e.g. `+1.250s led #ff8000` or with
+/// ` failed` appended for an [`OutputCommand`] the controller refused.
+impl fmt::Display for CommandRecord {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { at, command, succeeded } = *self;
+        write!(f, "+{:.3}s {command}", at.as_secs_f64())?;
+        if !succeeded {
+            f.write_str(" failed")?;
+        }
+        Ok(())
+    }
+}
+
+/// Latest [`OutputCommand`]s sent to a [`Gamepad`], see
+/// [`Gamepad::set_command_journal`].
+///
+/// Holds up to [`CAPACITY`] [`CommandRecord`]s inline, recording evicts
+/// the oldest one once full, so nothing is allocated per command.
+/// Collect the records to serialize them with the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{CommandJournal, OutputCommand, prelude::*};
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+/// let mut journal = CommandJournal::new(start);
+/// assert!(journal.is_empty());
+///
+/// // a scripted LED fade, and a refused color
+/// let led = |red, blue| OutputCommand::Led(Rgb::new(red, 0, blue));
+/// for step in 0..4_u8 {
+///     journal.record(at(u64::from(step) * 100), led(step * 50, 0), true);
+/// }
+/// journal.record(at(400), led(0, 255), false);
+///
+/// let sent: Vec<_> = journal.iter().map(|record| record.command).collect();
+/// assert_eq!(sent[1], led(50, 0));
+/// assert_eq!(journal.len(), 5);
+/// let last = journal.iter().last().unwrap();
+/// assert_eq!(last.at, Duration::from_millis(400));
+/// assert!(!last.succeeded);
+/// assert_eq!(last.to_string(), "+0.400s led #0000ff failed");
+///
+/// // the last 150 ms only
+/// let window = Duration::from_millis(150);
+/// assert_eq!(journal.recent(at(450), window).count(), 2);
+///
+/// // once full, the oldest are evicted
+/// let capacity = u64::try_from(CommandJournal::CAPACITY).unwrap();
+/// for ms in 500..500 + capacity {
+///     journal.record(at(ms), led(0, 0), true);
+/// }
+/// assert_eq!(journal.len(), CommandJournal::CAPACITY);
+/// let oldest = journal.iter().next().unwrap();
+/// assert_eq!(oldest.at, Duration::from_millis(500));
+/// let newest = journal.iter().last().unwrap();
+/// assert_eq!(newest.at, Duration::from_millis(499 + capacity));
+///
+/// journal.clear();
+/// assert_eq!(journal.iter().count(), 0);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::set_command_journal`]: crate::Gamepad::set_command_journal
+/// [`CAPACITY`]: Self::CAPACITY
+#[derive(Clone)]
+pub struct CommandJournal {
+    /// Storage, the records outside the recorded ones being leftovers.
+    records: [CommandRecord; Self::CAPACITY],
+    /// Index of the oldest record.
+    head: usize,
+    /// Number of records.
+    len: usize,
+    /// When recording started.
+    started: Instant,
+}
+
+impl fmt::Debug for CommandJournal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandJournal")
+            .field("records", &self.iter().collect::<Vec<_>>())
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CommandJournal {
+    /// Maximum number of [`CommandRecord`]s kept.
+    pub const CAPACITY: usize = 256;
+
+    /// Creates an empty [`CommandJournal`] started at `started`.
+    #[must_use]
+    #[inline]
+    pub const fn new(started: Instant) -> Self {
+        let unused = CommandRecord {
+            at: Duration::ZERO,
+            command: OutputCommand::Led(Rgb::new(0, 0, 0)),
+            succeeded: false,
+        };
+        Self { records: [unused; Self::CAPACITY], head: 0, len: 0, started }
+    }
+
+    /// Gets when recording started, the origin of [`CommandRecord::at`].
+    #[must_use]
+    #[inline]
+    pub const fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Gets the number of [`CommandRecord`]s.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if nothing was recorded.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Records the [`OutputCommand`] sent at `now`, evicting the oldest
+    /// [`CommandRecord`] if full.
+    #[inline]
+    pub fn record(
+        &mut self,
+        now: Instant,
+        command: OutputCommand,
+        succeeded: bool,
+    ) {
+        let at = now.saturating_duration_since(self.started);
+        let record = CommandRecord { at, command, succeeded };
+        let full = self.len == Self::CAPACITY;
+        let tail = if full { self.head } else { self.slot(self.len) };
+        if let Some(slot) = self.records.get_mut(tail) {
+            *slot = record;
+        }
+        if full {
+            self.head = self.slot(1);
+        } else {
+            self.len = self.len.saturating_add(1);
+        }
+    }
+
+    /// Iterates over the [`CommandRecord`]s, oldest first.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &CommandRecord> {
+        let (wrapped, oldest) = self.records.split_at(self.head);
+        oldest.iter().chain(wrapped).take(self.len)
+    }
+
+    /// Iterates over the [`CommandRecord`]s sent within `window` before
+    /// `now`, oldest first.
+    #[inline]
+    pub fn recent(
+        &self,
+        now: Instant,
+        window: Duration,
+    ) -> impl Iterator<Item = &CommandRecord> {
+        let since =
+            now.saturating_duration_since(self.started).saturating_sub(window);
+        self.iter().filter(move |record| record.at >= since)
+    }
+
+    /// Drops all the [`CommandRecord`]s.
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Gets the index of the record `offset` records after the oldest
+    /// one, wrapping around. `offset` is at most [`CAPACITY`].
+    ///
+    /// [`CAPACITY`]: Self::CAPACITY
+    const fn slot(&self, offset: usize) -> usize {
+        let at = self.head.saturating_add(offset);
+        if at >= Self::CAPACITY {
+            at.saturating_sub(Self::CAPACITY)
+        } else {
+            at
+        }
+    }
+}
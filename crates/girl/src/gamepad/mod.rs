@@ -1,26 +1,79 @@
 //! [`Gamepad`] and related types.
 
+pub(crate) mod bind;
+pub(crate) mod capability;
+pub(crate) mod color;
+#[cfg(feature = "std")]
+pub(crate) mod dump;
+pub(crate) mod effects;
+pub(crate) mod form;
 pub(crate) mod input;
+#[cfg(feature = "std")]
+pub(crate) mod journal;
+#[cfg(feature = "rumble")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+pub(crate) mod pattern;
+#[cfg(feature = "sdl2")]
+pub(crate) mod poweroff;
+#[cfg(feature = "std")]
+pub(crate) mod profile;
 #[cfg(feature = "rumble")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
 pub(crate) mod rumble;
-#[cfg(feature = "sensors")]
-#[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+#[cfg(any(feature = "sensors", feature = "stubs"))]
 pub(crate) mod sensors;
-#[cfg(feature = "touchpad")]
-#[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+pub(crate) mod state;
+#[cfg(feature = "stubs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stubs")))]
+pub(crate) mod stubs;
+#[cfg(any(feature = "touchpad", feature = "stubs"))]
 pub(crate) mod touchpad;
 
-use core::{cmp, fmt, hash};
+#[cfg(feature = "sensors")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "sdl2")]
+use alloc::sync::Arc;
+#[cfg(feature = "sdl2")]
+use core::{
+    cell::{Cell, RefCell},
+    cmp,
+    ffi::CStr,
+    hash,
+    time::Duration,
+};
+use core::{
+    fmt,
+    ops::RangeInclusive,
+    str::{self, FromStr},
+};
 
+#[cfg(feature = "sdl2")]
 use sdl2::{
     controller::GameController as SdlController,
-    joystick::{Joystick as SdlJoystick, PowerLevel as SdlPowerLevel},
+    joystick::{
+        Guid as SdlGuid, Joystick as SdlJoystick, PowerLevel as SdlPowerLevel,
+    },
+    sys as sdl2_sys,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
 
 use crate::Error;
+#[cfg(feature = "rumble")]
+use crate::RumbleFold;
+#[cfg(feature = "sdl2")]
+use crate::{
+    AdaptiveDeadzone, ButtonTracker, CommandJournal, ConnectionKind,
+    ControllerType, DeviceInfo, ErrorCode, InputOverrides, InputProfile,
+    OutputCommand, PowerCache, SmoothingConfig, Stick, StickSmoother,
+    gamepad::{color::DEFAULT_LED_GAMMA, effects::IdentifyState},
+    logging::FailureLog,
+    shared::Shared,
+};
+#[cfg(feature = "sensors")]
+use crate::{JoyConSide, Orientation, Sensor};
 #[cfg(feature = "touchpad")]
-use crate::TouchpadState;
+use crate::{TouchpadSizes, TouchpadTracker};
 
 /// Represents a physical game controller.
 ///
@@ -41,6 +94,7 @@ use crate::TouchpadState;
 ///
 /// [`Girl::gamepad`]: crate::Girl::gamepad
 /// [`Girl::gamepads_connected`]: crate::Girl::gamepads_connected
+#[cfg(feature = "sdl2")]
 pub struct Gamepad {
     /// SDL2 game controller handle.
     gp: SdlController,
@@ -48,24 +102,138 @@ pub struct Gamepad {
     /// SDL2 joystick handle.
     joy: SdlJoystick,
 
-    /// Touchpad state for each touchpad and finger.
+    /// Identifier assigned by the owning [`Girl`], see [`Gamepad::id`].
+    ///
+    /// [`Girl`]: crate::Girl
+    id: GamepadId,
+
+    /// Input tuning settings.
+    profile: InputProfile,
+
+    /// Settings overridden on this [`Gamepad`], see
+    /// [`Gamepad::input_overrides`].
+    overrides: InputOverrides,
+
+    /// [`Button`]s reported by the previous [`Gamepad::buttons_changed`].
+    ///
+    /// [`Button`]: crate::Button
+    button_tracker: ButtonTracker,
+
+    /// Last LED color set with [`Gamepad::set_led`].
+    led: Option<[u8; 3]>,
+
+    /// Gamma applied by [`Gamepad::set_led_srgb`].
+    led_gamma: f32,
+
+    /// Brightness cap applied by [`Gamepad::set_led_srgb`].
+    led_brightness_limit: f32,
+
+    /// Playing [`Gamepad::identify`] pattern.
+    identify: Option<IdentifyState>,
+
+    /// State shared with the owning [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    shared: Arc<Shared>,
+
+    /// Controller type detected on creation.
+    controller_type: ControllerType,
+
+    /// Whether a Joy-Con half is held sideways, see
+    /// [`Gamepad::set_sideways_mode`].
+    sideways: bool,
+
+    /// Output smoothing of the left and right [`Stick`]s, see
+    /// [`Gamepad::set_stick_smoothing`].
+    smoothing: [Cell<Option<StickSmoother>>; 2],
+
+    /// Adaptive deadzones of the left and right [`Stick`]s, see
+    /// [`Gamepad::enable_adaptive_deadzone`].
+    adaptive: [Cell<Option<AdaptiveDeadzone>>; 2],
+
+    /// Latest battery query, so that a streak of failures is logged once,
+    /// see [`Gamepad::power`].
+    power_cache: Cell<PowerCache>,
+
+    /// Operations failing in a row, so that each streak is logged once.
+    failures: FailureLog,
+
+    /// Preferred Joy-Con for [`Gamepad::primary_gyroscope`] and
+    /// [`Gamepad::primary_accelerometer`].
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    preferred_side: JoyConSide,
+
+    /// Orientation estimates by gyroscope, see [`Gamepad::sensor_world`].
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    orientations: BTreeMap<Sensor, Orientation>,
+
+    /// Touchpad state for each touchpad and finger, with the withheld
+    /// touches, see [`Gamepad::set_touchpad_rejection`].
     #[cfg(feature = "touchpad")]
     #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
-    touchpads: Vec<Vec<TouchpadState>>,
+    touchpads: TouchpadTracker,
+
+    /// Physical touchpad sizes per [`ControllerType`], see
+    /// [`Gamepad::touchpad_physical_size`].
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    touchpad_sizes: TouchpadSizes,
+
+    /// Folding of the rumble channels on a single motor, see
+    /// [`Gamepad::set_rumble_fold`].
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    rumble_fold: RumbleFold,
+
+    /// Commands sent while recording, see
+    /// [`Gamepad::set_command_journal`].
+    journal: RefCell<Option<Box<CommandJournal>>>,
 }
 
+/// Settings of a [`Gamepad`] carried over to a new handle of the same
+/// device, see [`Gamepad::settings`].
+#[cfg(feature = "sdl2")]
+#[derive(Debug, Clone)]
+pub(crate) struct PadSettings {
+    /// Input settings.
+    profile: InputProfile,
+    /// Whether a Joy-Con half is held sideways.
+    sideways: bool,
+    /// Last LED color set.
+    led: Option<[u8; 3]>,
+    /// Gamma applied by [`Gamepad::set_led_srgb`].
+    led_gamma: f32,
+    /// Brightness cap applied by [`Gamepad::set_led_srgb`].
+    led_brightness_limit: f32,
+    /// Output smoothing of the left and right [`Stick`]s.
+    smoothing: [Option<SmoothingConfig>; 2],
+    /// Learned deadzones of the left and right [`Stick`]s.
+    adaptive: [Option<AdaptiveDeadzone>; 2],
+    /// Preferred Joy-Con of the primary sensors.
+    #[cfg(feature = "sensors")]
+    preferred_side: JoyConSide,
+    /// Physical touchpad sizes per [`ControllerType`].
+    #[cfg(feature = "touchpad")]
+    touchpad_sizes: TouchpadSizes,
+}
+
+#[cfg(feature = "sdl2")]
 impl fmt::Debug for Gamepad {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Gamepad")
             .field("gp_id", &self.gp.instance_id())
             .field("joy_id", &self.joy.instance_id())
+            .field("connection", &self.connection_kind())
             .finish_non_exhaustive()
     }
 }
 
 /// Displays the name of the [`Gamepad`] (or just "Gamepad" if not found), its
-/// power level (if available), and its internal SDL2 instance ID.
+/// power level (if available), its internal SDL2 instance ID, and how it is
+/// connected (if known).
 ///
 /// # Examples
 ///
@@ -76,12 +244,13 @@ impl fmt::Debug for Gamepad {
 ///
 /// println!("{gamepad}");
 /// // example output:
-/// // PS4 Controller (Power: Wired)
+/// // PS4 Controller (Power: Wired), connected as #0 over USB
 /// # }
 /// # Ok::<(), girl::Error>(())
 /// ```
 ///
 /// [`Gamepad`]: crate::Gamepad
+#[cfg(feature = "sdl2")]
 impl fmt::Display for Gamepad {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -91,46 +260,140 @@ impl fmt::Display for Gamepad {
             write!(f, " ({power})")?;
         }
         write!(f, ", connected as #{}", self.gp.instance_id())?;
+        let connection = self.connection_kind();
+        if connection != ConnectionKind::Unknown {
+            write!(f, " over {connection}")?;
+        }
         Ok(())
     }
 }
 
+#[cfg(feature = "sdl2")]
+#[expect(clippy::same_name_method, reason = "implements `GamepadRead`")]
 impl Gamepad {
+    /// Default minimal time between the controller updates of
+    /// [`read_fresh`], the report interval of most controllers over USB.
+    ///
+    /// [`read_fresh`]: Self::read_fresh
+    pub const FRESH_READ_INTERVAL: Duration = Duration::from_millis(1);
     /// Default deadzone value for analog sticks.
-    pub const STICK_DEADZONE: f64 = 0.1;
+    pub const STICK_DEADZONE: f64 = profile::STICK_DEADZONE;
 
     /// Creates a [`Gamepad`] from SDL controller and joystick handles.
-    #[must_use]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
     #[inline]
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
     #[cfg_attr(
         not(feature = "touchpad"),
-        expect(
-            clippy::missing_const_for_fn,
-            clippy::unnecessary_wraps,
-            reason = "feature gated"
-        )
+        expect(clippy::unnecessary_wraps, reason = "feature gated")
     )]
     pub(crate) fn from_sdl(
         controller: SdlController,
         joystick: SdlJoystick,
-    ) -> Option<Self> {
-        #[cfg_attr(
-            not(feature = "touchpad"),
-            expect(unused_mut, reason = "feature gated")
-        )]
+        shared: Arc<Shared>,
+    ) -> Result<Self, Error> {
         let mut this = Self {
+            id: GamepadId::from_raw(controller.instance_id()),
             joy: joystick,
+            profile: InputProfile::new(),
+            overrides: InputOverrides::empty(),
+            button_tracker: ButtonTracker::new(),
+            led: None,
+            led_gamma: DEFAULT_LED_GAMMA,
+            led_brightness_limit: 1.0,
+            identify: None,
+            shared,
+            controller_type: ControllerType::Unknown,
+            sideways: false,
+            smoothing: [Cell::new(None), Cell::new(None)],
+            adaptive: [Cell::new(None), Cell::new(None)],
+            power_cache: Cell::new(PowerCache::new()),
+            failures: FailureLog::default(),
+            #[cfg(feature = "sensors")]
+            preferred_side: JoyConSide::Right,
+            #[cfg(feature = "sensors")]
+            orientations: BTreeMap::new(),
             #[cfg(feature = "touchpad")]
-            touchpads: vec![],
+            touchpads: TouchpadTracker::new(0, 0),
+            #[cfg(feature = "touchpad")]
+            touchpad_sizes: TouchpadSizes::new(),
+            #[cfg(feature = "rumble")]
+            rumble_fold: RumbleFold::Max,
+            journal: RefCell::new(None),
             gp: controller,
         };
+        this.set_command_journal(this.shared.journal_commands());
+        this.id = this.shared.ids().assign(this.id.raw(), this.guid());
+        this.controller_type = this.detect_controller_type();
+        (this.profile, this.overrides) =
+            this.shared.initial_profile(this.guid(), this.controller_type);
 
         #[cfg(feature = "touchpad")]
         {
-            this.touchpads = this.touchpads_init().ok()?;
+            this.touchpads = this.touchpads_init()?;
         }
 
-        Some(this)
+        Ok(this)
+    }
+
+    /// Gets the [`GamepadId`] of the [`Gamepad`].
+    ///
+    /// The ID is unique for the lifetime of the connection and is the same
+    /// value reported as `which` in [`Event`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// println!("{}", gamepad.id());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Event`]: crate::Event
+    #[must_use]
+    #[inline]
+    pub const fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    /// Gets the [`Guid`] of the [`Gamepad`].
+    ///
+    /// Unlike [`GamepadId`], the [`Guid`] identifies the controller model and
+    /// stays the same across reconnects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// println!("{}", gamepad.guid());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn guid(&self) -> Guid {
+        Guid::from_sdl(self.joy.guid())
+    }
+
+    /// Gets the SDL2 mapping string currently used by the [`Gamepad`].
+    ///
+    /// The format is described in the SDL2 [docs].
+    ///
+    /// [docs]: https://wiki.libsdl.org/SDL2/SDL_GameControllerAddMapping
+    #[must_use]
+    #[inline]
+    pub fn mapping(&self) -> String {
+        self.gp.mapping()
     }
 
     /// Checks if the controller is currently connected.
@@ -165,6 +428,32 @@ impl Gamepad {
         self.gp.attached()
     }
 
+    /// Checks if input is suspended with [`Girl::suspend_input`].
+    ///
+    /// While suspended, all input queries report neutral values: no
+    /// [`Button`]s pressed, centered [`Stick`]s and released [`Trigger`]s.
+    ///
+    /// [`Girl::suspend_input`]: crate::Girl::suspend_input
+    /// [`Button`]: crate::Button
+    /// [`Trigger`]: crate::Trigger
+    #[must_use]
+    #[inline]
+    pub fn input_suspended(&self) -> bool {
+        self.shared.suspended()
+    }
+
+    /// Checks if input is suspended, at the start of every input query, after
+    /// warning about a stalled [`Girl::update`] (see
+    /// [`Girl::enable_stall_detection`]).
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::enable_stall_detection`]: crate::Girl::enable_stall_detection
+    #[inline]
+    pub(crate) fn suspended_for_query(&self) -> bool {
+        self.shared.check_stall();
+        self.input_suspended()
+    }
+
     /// Gets the name of the [`Gamepad`] or an empty string if the name is not
     /// found.
     ///
@@ -185,12 +474,119 @@ impl Gamepad {
         self.gp.name()
     }
 
+    /// Gets the serial number of the [`Gamepad`], if available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Some(serial) = gamepad.serial() {
+    ///     println!("{serial}");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn serial(&self) -> Option<String> {
+        let raw = self.raw("serial").ok()?;
+
+        // SAFETY: `raw` is valid, SDL reports unavailable serials as null.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let ptr = unsafe { sdl2_sys::SDL_GameControllerGetSerial(raw) };
+        if ptr.is_null() {
+            return None;
+        }
+
+        // SAFETY: checked for null above, and copied before any other SDL
+        //         call.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let serial = unsafe { CStr::from_ptr(ptr) };
+        Some(serial.to_string_lossy().into_owned())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// Checks if the [`Gamepad`] is a virtual controller created by Steam
+    /// Input rather than the physical one, whose touchpad and sensors are
+    /// then unavailable.
+    ///
+    /// Decided by the [`SteamInputHeuristics`] set with
+    /// [`Girl::set_steam_heuristics`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.is_steam_virtual() {
+    ///     println!("disable Steam Input for gyro support");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::set_steam_heuristics`]: crate::Girl::set_steam_heuristics
+    /// [`SteamInputHeuristics`]: crate::SteamInputHeuristics
+    #[must_use]
+    #[inline]
+    pub fn is_steam_virtual(&self) -> bool {
+        let steam_handle = self.raw("is_steam_virtual").is_ok_and(|raw| {
+            // SAFETY: SDL is alive, pointer is valid
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            let handle =
+                unsafe { sdl2_sys::SDL_GameControllerGetSteamHandle(raw) };
+            handle != 0
+        });
+        let device = DeviceInfo::from_gamepad(self);
+        self.shared.steam().is_virtual(&device, steam_handle)
+    }
+
+    /// Gets how the [`Gamepad`] is connected, USB or Bluetooth, e.g. for
+    /// support reports, as some features are limited over Bluetooth.
+    ///
+    /// Read from the bus type SDL2 encodes in the [`Guid`] where available,
+    /// otherwise decided by the [`ConnectionHeuristics`] set with
+    /// [`Girl::set_connection_heuristics`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::ConnectionKind;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.connection_kind() == ConnectionKind::Bluetooth {
+    ///     println!("plug the controller in for trigger effects");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`ConnectionHeuristics`]: crate::ConnectionHeuristics
+    /// [`Girl::set_connection_heuristics`]:
+    ///     crate::Girl::set_connection_heuristics
+    #[must_use]
+    #[inline]
+    pub fn connection_kind(&self) -> ConnectionKind {
+        let name = self.name();
+        self.shared.connection().infer(self.guid(), &name, self.power())
+    }
+
     /// Gets the current [`PowerLevel`] of the [`Gamepad`], if available.
     ///
+    /// A failed query is logged once until a query succeeds again, see
+    /// [`Gamepad::try_power`] for its [`Error`].
+    ///
     /// # Examples
     ///
     /// ```
-    /// # use girl::PowerLevel;
+    /// # use girl::prelude::*;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
@@ -210,7 +606,31 @@ impl Gamepad {
     #[must_use]
     #[inline]
     pub fn power(&self) -> Option<PowerLevel> {
-        self.joy.power_level().ok().map(PowerLevel::from_sdl)
+        let mut cache = self.power_cache.get();
+        let power = cache.observe(&self.try_power());
+        self.power_cache.set(cache);
+        power
+    }
+
+    /// Gets the current [`PowerLevel`] of the [`Gamepad`], reporting why it
+    /// is not available.
+    ///
+    /// Unlike [`Gamepad::power`], failures are not logged. [`Girl::update`]
+    /// reports [`Event::PowerQueryFailed`] once they persist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 fails to query the battery, e.g. because the
+    /// controller is no longer valid.
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Event::PowerQueryFailed`]: crate::Event::PowerQueryFailed
+    #[inline]
+    pub fn try_power(&self) -> Result<PowerLevel, Error> {
+        self.joy
+            .power_level()
+            .map(PowerLevel::from_sdl)
+            .map_err(Error::sdl("power"))
     }
 
     /// Query whether the [`Gamepad`] has an LED.
@@ -248,22 +668,234 @@ impl Gamepad {
         green: u8,
         blue: u8,
     ) -> Result<(), Error> {
-        self.gp
-            .set_led(red, green, blue)
-            .map_err(|err| Error::SdlError(err.to_string()))
+        let color = [red, green, blue];
+        if let Some(identify) = self.identify.as_mut() {
+            // shown once identification finishes
+            identify.set_restore(color);
+            self.led = Some(color);
+            return Ok(());
+        }
+        let result = if self.gp.has_led() {
+            self.gp.set_led(red, green, blue).map_err(Error::sdl("set_led"))
+        } else {
+            Err(Error::sdl_static(
+                "set_led",
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ))
+        };
+        self.failures.observe("set_led", &result);
+        self.journal_command(OutputCommand::Led(color.into()), &result);
+        result?;
+        self.led = Some(color);
+        Ok(())
+    }
+
+    /// Gets the player index of the [`Gamepad`], if set, see
+    /// [`set_player_index`].
+    ///
+    /// [`set_player_index`]: Self::set_player_index
+    #[must_use]
+    #[inline]
+    pub fn player_index(&self) -> Option<u32> {
+        let raw = self.raw("player_index").ok()?;
+
+        // SAFETY: SDL is alive, pointer is valid
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let index = unsafe { sdl2_sys::SDL_GameControllerGetPlayerIndex(raw) };
+        u32::try_from(index).ok()
+    }
+
+    /// Sets the player index of the [`Gamepad`], e.g. shown by the player
+    /// LEDs of `PlayStation` and Switch controllers, or clears it with
+    /// [`None`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index is too large for SDL2, or if the
+    /// controller is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.set_player_index(Some(1))?;
+    /// assert_eq!(gamepad.player_index(), Some(1));
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_player_index(
+        &mut self,
+        index: Option<u32>,
+    ) -> Result<(), Error> {
+        let raw = self.raw("set_player_index")?;
+        let index = index.map_or(Ok(-1i32), |player| {
+            i32::try_from(player)
+                .ok()
+                .ok_or(Error::InvalidArgument { what: "player index" })
+        })?;
+
+        // SAFETY: SDL is alive, pointer is valid
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        unsafe {
+            sdl2_sys::SDL_GameControllerSetPlayerIndex(raw, index);
+        }
+        Ok(())
+    }
+
+    /// Gets the raw SDL game controller pointer, tagging errors with `op`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
+    #[inline]
+    pub(crate) fn raw(
+        &self,
+        op: &'static str,
+    ) -> Result<*mut sdl2_sys::SDL_GameController, Error> {
+        #[expect(
+            clippy::cast_possible_wrap,
+            reason = "it was just cast from i32 to u32 by sdl2 crate, we're \
+                      casting it back"
+        )]
+        let id = self.gp.instance_id() as i32;
+
+        // SAFETY: SDL is alive, `id` is valid, and SDL handles any errors,
+        //         return value is checked for null.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let res = unsafe { sdl2_sys::SDL_GameControllerFromInstanceID(id) };
+
+        self.shared.record_device_access(!res.is_null());
+        let result = if res.is_null() {
+            Err(Error::sdl_static(
+                op,
+                ErrorCode::Disconnected,
+                Error::DISCONNECTED,
+            ))
+        } else {
+            Ok(res)
+        };
+        self.failures.observe(op, &result);
+        result
+    }
+
+    /// Saves the settings to carry over to a later handle of the same
+    /// device with [`restore`], e.g. across [`Girl::reinitialize`].
+    ///
+    /// [`restore`]: Self::restore
+    /// [`Girl::reinitialize`]: crate::Girl::reinitialize
+    pub(crate) fn settings(&self) -> PadSettings {
+        PadSettings {
+            profile: self.profile.clone(),
+            sideways: self.sideways,
+            led: self.led,
+            led_gamma: self.led_gamma,
+            led_brightness_limit: self.led_brightness_limit,
+            smoothing: [Stick::Left, Stick::Right]
+                .map(|stick| self.stick_smoothing(stick)),
+            adaptive: [Stick::Left, Stick::Right]
+                .map(|stick| self.estimator(stick).get()),
+            #[cfg(feature = "sensors")]
+            preferred_side: self.preferred_side,
+            #[cfg(feature = "touchpad")]
+            touchpad_sizes: self.touchpad_sizes.clone(),
+        }
+    }
+
+    /// Applies the [`settings`] saved from a previous handle of the same
+    /// device, including its learned deadzones.
+    ///
+    /// Restoring the LED color is best-effort.
+    ///
+    /// [`settings`]: Self::settings
+    pub(crate) fn restore(&mut self, settings: PadSettings) {
+        self.profile = settings.profile;
+        self.sideways = settings.sideways;
+        self.led_gamma = settings.led_gamma;
+        self.led_brightness_limit = settings.led_brightness_limit;
+        for (stick, (smoothing, adaptive)) in [Stick::Left, Stick::Right]
+            .into_iter()
+            .zip(settings.smoothing.into_iter().zip(settings.adaptive))
+        {
+            self.set_stick_smoothing(stick, smoothing);
+            self.estimator(stick).set(adaptive);
+        }
+        #[cfg(feature = "sensors")]
+        {
+            self.preferred_side = settings.preferred_side;
+        }
+        #[cfg(feature = "touchpad")]
+        {
+            self.touchpad_sizes = settings.touchpad_sizes;
+        }
+        if let Some([red, green, blue]) = settings.led {
+            drop(self.set_led(red, green, blue));
+        }
+    }
+
+    /// Gets the [`InputProfile`] the input is read with.
+    pub(crate) const fn profile(&self) -> &InputProfile {
+        &self.profile
+    }
+
+    /// Gets the [`InputProfile`] the input is read with, to change it.
+    pub(crate) const fn profile_mut(&mut self) -> &mut InputProfile {
+        &mut self.profile
+    }
+
+    /// Gets the adaptive deadzone state of the [`Stick`], see
+    /// [`Gamepad::enable_adaptive_deadzone`].
+    pub(crate) const fn estimator(
+        &self,
+        stick: Stick,
+    ) -> &Cell<Option<AdaptiveDeadzone>> {
+        match stick {
+            Stick::Left => &self.adaptive[0],
+            Stick::Right => &self.adaptive[1],
+        }
+    }
+
+    /// Gets the state shared with the owning [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    pub(crate) const fn shared(&self) -> &Arc<Shared> {
+        &self.shared
+    }
+
+    /// Gets the SDL2 game controller handle.
+    pub(crate) const fn controller(&self) -> &SdlController {
+        &self.gp
+    }
+
+    /// Gets the smoothing state of the [`Stick`], see
+    /// [`Gamepad::set_stick_smoothing`].
+    pub(crate) const fn smoother(
+        &self,
+        stick: Stick,
+    ) -> &Cell<Option<StickSmoother>> {
+        match stick {
+            Stick::Left => &self.smoothing[0],
+            Stick::Right => &self.smoothing[1],
+        }
     }
 }
 
+#[cfg(feature = "sdl2")]
 impl PartialEq for Gamepad {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.gp.instance_id() == other.gp.instance_id()
-            && self.joy.instance_id() == other.joy.instance_id()
+        self.id == other.id && self.joy.instance_id() == other.joy.instance_id()
     }
 }
 
+#[cfg(feature = "sdl2")]
 impl Eq for Gamepad {}
 
+#[cfg(feature = "sdl2")]
 impl PartialOrd for Gamepad {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
@@ -271,29 +903,252 @@ impl PartialOrd for Gamepad {
     }
 }
 
+#[cfg(feature = "sdl2")]
 impl Ord for Gamepad {
     #[inline]
     fn cmp(&self, other: &Self) -> cmp::Ordering {
-        (self.gp.instance_id(), self.joy.instance_id())
-            .cmp(&(other.gp.instance_id(), other.joy.instance_id()))
+        (self.id, self.joy.instance_id())
+            .cmp(&(other.id, other.joy.instance_id()))
     }
 }
 
+#[cfg(feature = "sdl2")]
 impl hash::Hash for Gamepad {
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        self.gp.instance_id().hash(state);
+        self.id.hash(state);
         self.joy.instance_id().hash(state);
     }
 }
 
+/// Identifier of a connected [`Gamepad`].
+///
+/// Wraps the SDL2 instance ID of the device, which is the same value reported
+/// as `which` in [`Event`]s, and a generation telling apart devices that
+/// reuse an instance ID, e.g. after a subsystem reset.
+/// Two [`GamepadId`]s are only equal if both match, so state cached per
+/// [`GamepadId`] never carries over to a different device. Get the
+/// [`GamepadId`] of the `which` of an [`Event`] with [`Girl::resolve_id`].
+///
+/// [`Event`]: crate::Event
+/// [`Girl::resolve_id`]: crate::Girl::resolve_id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadId {
+    /// SDL2 instance ID.
+    raw: u32,
+    /// Times the instance ID was reused by a different device.
+    #[cfg_attr(feature = "serde", serde(default))]
+    generation: u32,
+}
+
+/// Formatted as `#` followed by the raw instance ID, and the generation
+/// after a `.` unless it is the first one.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::GamepadId;
+/// assert_eq!(GamepadId::from_raw(3).to_string(), "#3");
+/// assert_eq!(GamepadId::with_generation(3, 1).to_string(), "#3.1");
+/// ```
+impl fmt::Display for GamepadId {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.raw)?;
+        if self.generation != 0 {
+            write!(f, ".{}", self.generation)?;
+        }
+        Ok(())
+    }
+}
+
+impl GamepadId {
+    /// Creates a [`GamepadId`] of the first generation from a raw SDL2
+    /// instance ID, such as the `which` field of an [`Event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let id = girl::GamepadId::from_raw(3);
+    /// assert_eq!(id.raw(), 3);
+    /// assert_eq!(id.generation(), 0);
+    /// assert_eq!(id.to_string(), "#3");
+    /// ```
+    ///
+    /// [`Event`]: crate::Event
+    #[must_use]
+    #[inline]
+    pub const fn from_raw(which: u32) -> Self {
+        Self::with_generation(which, 0)
+    }
+
+    /// Creates a [`GamepadId`] from a raw SDL2 instance ID and generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::GamepadId;
+    /// let reused = GamepadId::with_generation(3, 1);
+    /// assert_eq!(reused.raw(), 3);
+    /// assert_ne!(reused, GamepadId::from_raw(3));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_generation(which: u32, generation: u32) -> Self {
+        Self { raw: which, generation }
+    }
+
+    /// Returns the raw SDL2 instance ID.
+    #[must_use]
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.raw
+    }
+
+    /// Returns the generation, `0` unless the instance ID was reused by a
+    /// different device.
+    #[must_use]
+    #[inline]
+    pub const fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// Globally unique identifier of a [`Gamepad`] model.
+///
+/// Formatted and parsed as 32 lowercase hexadecimal digits, same as in SDL2
+/// mapping strings.
+///
+/// # Examples
+///
+/// ```
+/// let guid: girl::Guid = "030000004c050000e60c000000010000".parse()?;
+/// assert_eq!(guid.to_string(), "030000004c050000e60c000000010000");
+/// assert!("not a guid".parse::<girl::Guid>().is_err());
+/// # Ok::<(), girl::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid([u8; 16]);
+
+impl fmt::Display for Guid {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl FromStr for Guid {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 32 || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(Error::InvalidArgument { what: "guid" });
+        }
+
+        let mut bytes = [0; 16];
+        for (byte, digits) in bytes.iter_mut().zip(s.as_bytes().chunks_exact(2))
+        {
+            *byte = str::from_utf8(digits)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(Error::InvalidArgument { what: "guid" })?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// Serializes the [`Guid`] as a string, see [`Display`](fmt::Display).
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Guid {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes the [`Guid`] from a string, see [`FromStr`].
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Guid {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Guid {
+    /// Creates a [`Guid`] from raw bytes.
+    #[must_use]
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the [`Guid`].
+    #[must_use]
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Gets the USB vendor and product IDs encoded in the [`Guid`], if it
+    /// has them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let guid: girl::Guid = "030000004c050000e60c000000010000".parse()?;
+    /// assert_eq!(guid.vendor_product(), Some((0x054c, 0x0ce6)));
+    /// assert_eq!(girl::Guid::default().vendor_product(), None);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn vendor_product(&self) -> Option<(u16, u16)> {
+        let [_, _, _, _, v0, v1, z0, z1, p0, p1, z2, z3, ..] = self.0;
+        let vendor = u16::from(v0) | (u16::from(v1) << 8u32);
+        if vendor == 0 || z0 != 0 || z1 != 0 || z2 != 0 || z3 != 0 {
+            return None;
+        }
+        Some((vendor, u16::from(p0) | (u16::from(p1) << 8u32)))
+    }
+
+    /// Converts from [`SdlGuid`].
+    #[cfg(feature = "sdl2")]
+    #[must_use]
+    #[inline]
+    pub(crate) fn from_sdl(guid: SdlGuid) -> Self {
+        Self(guid.raw().data)
+    }
+}
+
 /// Battery power level of a [`Gamepad`].
+///
+/// The derived [`Ord`] is for ordering collections only: it puts
+/// [`Unknown`] below [`Empty`] and [`Wired`] above [`Full`], so comparisons
+/// like `power <= PowerLevel::Low` are wrong for them. Compare battery
+/// levels with [`is_at_most`] instead.
+///
+/// [`Unknown`]: Self::Unknown
+/// [`Empty`]: Self::Empty
+/// [`Wired`]: Self::Wired
+/// [`Full`]: Self::Full
+/// [`is_at_most`]: Self::is_at_most
 #[expect(
     clippy::exhaustive_enums,
     reason = "if we get more power levels in the sdl2 updates, we'll add them \
               in a major update"
 )]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerLevel {
     /// Power level unknown.
     Unknown,
@@ -330,7 +1185,117 @@ impl fmt::Display for PowerLevel {
 }
 
 impl PowerLevel {
+    /// Gets the name of the variant, e.g. `"Full"`, as stored in the
+    /// [`DeviceHistory`] file format.
+    ///
+    /// [`DeviceHistory`]: crate::DeviceHistory
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::Empty => "Empty",
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::Full => "Full",
+            Self::Wired => "Wired",
+        }
+    }
+
+    /// Gets the range of battery percentages the [`PowerLevel`] stands for,
+    /// as estimated by SDL2: up to 5% for [`Empty`], 20% for [`Low`], 70%
+    /// for [`Medium`] and 100% for [`Full`].
+    ///
+    /// Returns [`None`] for [`Unknown`] and [`Wired`], which aren't battery
+    /// levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::PowerLevel;
+    /// assert_eq!(PowerLevel::Low.percent_estimate(), Some(6..=20));
+    /// assert_eq!(PowerLevel::Full.percent_estimate(), Some(71..=100));
+    /// assert_eq!(PowerLevel::Wired.percent_estimate(), None);
+    /// ```
+    ///
+    /// [`Empty`]: Self::Empty
+    /// [`Low`]: Self::Low
+    /// [`Medium`]: Self::Medium
+    /// [`Full`]: Self::Full
+    /// [`Unknown`]: Self::Unknown
+    /// [`Wired`]: Self::Wired
+    #[must_use]
+    #[inline]
+    pub const fn percent_estimate(self) -> Option<RangeInclusive<u8>> {
+        match self {
+            Self::Empty => Some(0..=5),
+            Self::Low => Some(6..=20),
+            Self::Medium => Some(21..=70),
+            Self::Full => Some(71..=100),
+            Self::Unknown | Self::Wired => None,
+        }
+    }
+
+    /// Checks if the battery level is at most the `threshold` one, e.g. to
+    /// warn about a low battery.
+    ///
+    /// Returns [`None`] if either is [`Unknown`] or [`Wired`], which aren't
+    /// battery levels, unlike the derived [`Ord`] which orders them below
+    /// [`Empty`] and above [`Full`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::PowerLevel;
+    /// use PowerLevel::{Empty, Full, Low, Medium, Unknown, Wired};
+    ///
+    /// assert_eq!(Empty.is_at_most(Low), Some(true));
+    /// assert_eq!(Low.is_at_most(Low), Some(true));
+    /// assert_eq!(Medium.is_at_most(Low), Some(false));
+    ///
+    /// // unlike `Unknown <= Low` and `Wired > Full`
+    /// let levels = [Unknown, Empty, Low, Medium, Full, Wired];
+    /// for level in levels {
+    ///     for unrelated in [Unknown, Wired] {
+    ///         assert_eq!(level.is_at_most(unrelated), None);
+    ///         assert_eq!(unrelated.is_at_most(level), None);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`Unknown`]: Self::Unknown
+    /// [`Wired`]: Self::Wired
+    /// [`Empty`]: Self::Empty
+    /// [`Full`]: Self::Full
+    #[must_use]
+    #[inline]
+    pub const fn is_at_most(self, threshold: Self) -> Option<bool> {
+        match (self.battery_rank(), threshold.battery_rank()) {
+            (Some(level), Some(threshold)) => Some(level <= threshold),
+            _ => None,
+        }
+    }
+
+    /// Gets the rank of the battery level, from 0 for [`Empty`] to 3 for
+    /// [`Full`], [`None`] for [`Unknown`] and [`Wired`].
+    ///
+    /// [`Empty`]: Self::Empty
+    /// [`Full`]: Self::Full
+    /// [`Unknown`]: Self::Unknown
+    /// [`Wired`]: Self::Wired
+    const fn battery_rank(self) -> Option<u8> {
+        match self {
+            Self::Empty => Some(0),
+            Self::Low => Some(1),
+            Self::Medium => Some(2),
+            Self::Full => Some(3),
+            Self::Unknown | Self::Wired => None,
+        }
+    }
+
     /// Converts from [`SdlPowerLevel`].
+    #[cfg(feature = "sdl2")]
     #[must_use]
     #[inline]
     #[expect(clippy::single_call_fn, reason = "extracted conversion")]
@@ -345,9 +1310,3 @@ impl PowerLevel {
         }
     }
 }
-
-/// Maps a raw input value with deadzone and normalization.
-pub(crate) fn map(value: f64, threshold: f64, max: f64) -> f64 {
-    let value = value / max;
-    if value.abs() < threshold { 0. } else { value }
-}
@@ -0,0 +1,393 @@
+//! Rumble patterns in a compact textual form, see [`RumblePattern`].
+
+use core::{fmt, str::FromStr, time::Duration};
+
+use crate::{EffectDuration, Error};
+
+/// Sequence of rumble [`RumbleStep`]s, optionally repeated, written in a
+/// compact textual form, e.g. for designers pasting patterns into a tool
+/// without recompiling.
+///
+/// Steps are `low/high:duration`, separated by commas: the intensities of
+/// the low and high frequency motors in `0..=1`, and how long the step
+/// lasts, in whole milliseconds (`120ms`) or seconds (`1.5s`). Every step
+/// follows the [`EffectDuration`] policy, but can't be zero. An `xN`
+/// suffix plays the steps `N` times. Whitespace around tokens is ignored.
+///
+/// [`Display`] writes the canonical form, parsed back into the same
+/// [`RumblePattern`]: intensities with up to 5 decimals, durations in
+/// milliseconds, and no suffix for a single play.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::RumblePattern;
+/// let pattern = RumblePattern::parse("0.8/0.2:120ms, 0/0:80ms, x3")?;
+/// assert_eq!(pattern.steps().len(), 2);
+/// assert_eq!(pattern.steps()[0].low, 52428);
+/// assert_eq!(pattern.repeat(), 3);
+/// assert_eq!(pattern.duration(), Duration::from_millis(600));
+/// assert_eq!(pattern.to_string(), "0.8/0.2:120ms, 0/0:80ms, x3");
+///
+/// // round-trips through the canonical form
+/// let texts = ["1/1:1.5s", "0.123456/0.5:1ms, 1/0:20s, x2", " 0/1:7ms "];
+/// for text in texts {
+///     let pattern: RumblePattern = text.parse()?;
+///     assert_eq!(RumblePattern::parse(&pattern.to_string())?, pattern);
+/// }
+/// assert_eq!(RumblePattern::parse("1/1:1.5s")?.to_string(), "1/1:1500ms");
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Display`]: fmt::Display
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RumblePattern {
+    /// Steps of a single play, never empty.
+    steps: Vec<RumbleStep>,
+    /// Number of plays, at least `1`.
+    repeat: u32,
+}
+
+/// Step of a [`RumblePattern`], the arguments of a single
+/// [`Gamepad::set_rumble`] call.
+///
+/// [`Gamepad::set_rumble`]: crate::Gamepad::set_rumble
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RumbleStep {
+    /// Intensity of the low frequency motor.
+    pub low: u16,
+    /// Intensity of the high frequency motor.
+    pub high: u16,
+    /// How long the step lasts, never [`EffectDuration::STOP`].
+    pub duration: EffectDuration,
+}
+
+/// Writes the canonical form, e.g. `0.8/0.2:120ms, 0/0:80ms, x3`.
+impl fmt::Display for RumblePattern {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, step) in self.steps.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{step}")?;
+        }
+        if self.repeat > 1 {
+            write!(f, ", x{}", self.repeat)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the textual form, see [`RumblePattern::parse`].
+impl FromStr for RumblePattern {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl RumblePattern {
+    /// Longest total duration of a [`RumblePattern`], repeats included.
+    pub const MAX_DURATION: Duration = Duration::from_secs(60);
+
+    /// Creates a [`RumblePattern`] playing the `steps` `repeat` times.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if there are no `steps`, any of
+    /// them lasts no time, `repeat` is zero, or the pattern is longer than
+    /// [`MAX_DURATION`].
+    ///
+    /// [`MAX_DURATION`]: Self::MAX_DURATION
+    #[inline]
+    pub fn new(steps: Vec<RumbleStep>, repeat: u32) -> Result<Self, Error> {
+        let pattern = Self { steps, repeat };
+        if pattern.steps.is_empty()
+            || pattern.steps.iter().any(|step| step.duration.is_stop())
+            || repeat == 0
+            || pattern.duration() > Self::MAX_DURATION
+        {
+            return Err(Error::InvalidArgument { what: "rumble pattern" });
+        }
+        Ok(pattern)
+    }
+
+    /// Parses a [`RumblePattern`] from its textual form, e.g.
+    /// `0.8/0.2:120ms, 0/0:80ms, x3`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSyntax`] with the byte position of the
+    /// offending token, and why it was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{RumblePattern, prelude::*};
+    /// let expected = "expected `low/high:duration`";
+    /// let table = [
+    ///     ("", 0, expected),
+    ///     ("0.8:120ms", 0, expected),
+    ///     ("0.8/0.2:120ms, oops", 15, expected),
+    ///     ("0.8/abc:120ms", 4, "invalid intensity"),
+    ///     ("0.8/1.5:120ms", 4, "intensity outside 0 to 1"),
+    ///     ("-0.1/0:120ms", 0, "intensity outside 0 to 1"),
+    ///     ("0/0:120", 4, "expected a duration in ms or s"),
+    ///     ("0/0:fast ms", 4, "invalid duration"),
+    ///     ("0/0:70s", 4, "duration longer than EffectDuration::MAX"),
+    ///     ("0/0:0ms", 4, "duration of zero"),
+    ///     ("0/0:1ms, x0", 10, "repeat count of zero"),
+    ///     ("0/0:1ms, xy", 10, "invalid repeat count"),
+    ///     ("0/0:1ms, x2, 0/0:1ms", 13, "nothing may follow the repeat count"),
+    ///     ("x2", 0, "no steps"),
+    ///     ("1/1:60s, x2", 0, "longer than RumblePattern::MAX_DURATION"),
+    /// ];
+    /// for (text, at, why) in table {
+    ///     let Err(Error::InvalidSyntax { position, reason, .. }) =
+    ///         RumblePattern::parse(text)
+    ///     else {
+    ///         panic!("{text:?} parsed");
+    ///     };
+    ///     assert_eq!((position, reason), (at, why), "{text:?}");
+    /// }
+    ///
+    /// let err = RumblePattern::parse("0.8/1.5:120ms").unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid rumble pattern at 4: intensity outside 0 to 1",
+    /// );
+    /// ```
+    #[inline]
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut steps = Vec::new();
+        let mut repeat = None;
+        let mut at = 0usize;
+        for token in text.split(',') {
+            let (part, position) = trim(token, at);
+            at = at.saturating_add(token.len()).saturating_add(1);
+            if repeat.is_some() {
+                return Err(syntax(
+                    position,
+                    "nothing may follow the repeat count",
+                ));
+            }
+            if let Some(count) = part.strip_prefix('x') {
+                repeat = Some(parse_repeat(count, position.saturating_add(1))?);
+            } else {
+                steps.push(parse_step(part, position)?);
+            }
+        }
+        if steps.is_empty() {
+            return Err(syntax(0, "no steps"));
+        }
+        let pattern = Self { steps, repeat: repeat.unwrap_or(1) };
+        if pattern.duration() > Self::MAX_DURATION {
+            return Err(syntax(0, "longer than RumblePattern::MAX_DURATION"));
+        }
+        Ok(pattern)
+    }
+
+    /// Gets the [`RumbleStep`]s of a single play.
+    #[must_use]
+    #[inline]
+    pub const fn steps(&self) -> &[RumbleStep] {
+        self.steps.as_slice()
+    }
+
+    /// Gets how many times the [`RumbleStep`]s are played.
+    #[must_use]
+    #[inline]
+    pub const fn repeat(&self) -> u32 {
+        self.repeat
+    }
+
+    /// Gets the total duration, repeats included.
+    #[must_use]
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.steps
+            .iter()
+            .fold(Duration::ZERO, |total, step| {
+                total.saturating_add(step.duration.duration())
+            })
+            .saturating_mul(self.repeat)
+    }
+
+    /// Gets the [`RumbleStep`] playing `elapsed` after the start, or
+    /// [`None`] once the [`RumblePattern`] is over, e.g. to preview it from
+    /// a frame loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use girl::RumblePattern;
+    /// let pattern = RumblePattern::parse("1/0:100ms, 0/1:50ms, x2")?;
+    /// let at = |ms| pattern.step_at(Duration::from_millis(ms));
+    /// assert_eq!(at(0).map(|step| step.low), Some(u16::MAX));
+    /// assert_eq!(at(120).map(|step| step.high), Some(u16::MAX));
+    /// assert_eq!(at(150).map(|step| step.low), Some(u16::MAX));
+    /// assert!(at(300).is_none());
+    ///
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    /// let start = Instant::now();
+    /// let mut playing = None;
+    /// while let Some(step) = pattern.step_at(start.elapsed()) {
+    ///     if playing != Some(step) {
+    ///         let length = step.duration.duration();
+    ///         gamepad.set_rumble(step.low, step.high, length)?;
+    ///         playing = Some(step);
+    ///     }
+    ///     girl.update();
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn step_at(&self, elapsed: Duration) -> Option<RumbleStep> {
+        if elapsed >= self.duration() {
+            return None;
+        }
+        let cycle = self.duration().checked_div(self.repeat)?;
+        let mut phase = elapsed.as_nanos().checked_rem(cycle.as_nanos())?;
+        for step in &self.steps {
+            let length = step.duration.duration().as_nanos();
+            if phase < length {
+                return Some(*step);
+            }
+            phase = phase.saturating_sub(length);
+        }
+        None
+    }
+}
+
+/// Writes the canonical form, e.g. `0.8/0.2:120ms`.
+impl fmt::Display for RumbleStep {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low, high) = (intensity(self.low), intensity(self.high));
+        write!(f, "{low}/{high}:{}ms", self.duration.millis())
+    }
+}
+
+impl RumbleStep {
+    /// Creates a new [`RumbleStep`].
+    #[must_use]
+    #[inline]
+    pub const fn new(low: u16, high: u16, duration: EffectDuration) -> Self {
+        Self { low, high, duration }
+    }
+}
+
+/// Creates the [`Error::InvalidSyntax`] of a [`RumblePattern`].
+const fn syntax(position: usize, reason: &'static str) -> Error {
+    Error::InvalidSyntax { what: "rumble pattern", position, reason }
+}
+
+/// Trims the whitespace around a `token` found at `at`, returning it with
+/// its new position.
+fn trim(token: &str, at: usize) -> (&str, usize) {
+    let start = token.trim_start();
+    let skipped = token.len().saturating_sub(start.len());
+    (start.trim_end(), at.saturating_add(skipped))
+}
+
+/// Parses a `low/high:duration` step found at `at`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSyntax`] if the step or any of its parts is
+/// malformed.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn parse_step(step: &str, at: usize) -> Result<RumbleStep, Error> {
+    let expected = || syntax(at, "expected `low/high:duration`");
+    let (levels, length) = step.split_once(':').ok_or_else(expected)?;
+    let (low, high) = levels.split_once('/').ok_or_else(expected)?;
+    let high_at = at.saturating_add(low.len()).saturating_add(1);
+    let length_at = at.saturating_add(levels.len()).saturating_add(1);
+    Ok(RumbleStep {
+        low: parse_intensity(low, at)?,
+        high: parse_intensity(high, high_at)?,
+        duration: parse_duration(length, length_at)?,
+    })
+}
+
+/// Parses an intensity in `0..=1` found at `at`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSyntax`] if it isn't a number in `0..=1`.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "checked to be in `0.0..=1.0` and rounded"
+)]
+fn parse_intensity(text: &str, at: usize) -> Result<u16, Error> {
+    let (number, position) = trim(text, at);
+    let value = number
+        .parse::<f64>()
+        .ok()
+        .ok_or_else(|| syntax(position, "invalid intensity"))?;
+    if !(0.0f64..=1.0f64).contains(&value) {
+        return Err(syntax(position, "intensity outside 0 to 1"));
+    }
+    Ok((value * f64::from(u16::MAX)).round() as u16)
+}
+
+/// Parses a duration in `ms` or `s` found at `at`, following the
+/// [`EffectDuration`] policy.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSyntax`] if it isn't a duration in `ms` or `s`,
+/// is zero or is longer than [`EffectDuration::MAX`].
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn parse_duration(text: &str, at: usize) -> Result<EffectDuration, Error> {
+    let (length, position) = trim(text, at);
+    let invalid = || syntax(position, "invalid duration");
+    let duration = if let Some(millis) = length.strip_suffix("ms") {
+        let millis = millis.trim_end().parse().ok().ok_or_else(invalid)?;
+        Duration::from_millis(millis)
+    } else if let Some(secs) = length.strip_suffix('s') {
+        let secs = secs.trim_end().parse().ok().ok_or_else(invalid)?;
+        Duration::try_from_secs_f64(secs).ok().ok_or_else(invalid)?
+    } else {
+        return Err(syntax(position, "expected a duration in ms or s"));
+    };
+    let effect = EffectDuration::new(duration).ok().ok_or_else(|| {
+        syntax(position, "duration longer than EffectDuration::MAX")
+    })?;
+    if effect.is_stop() {
+        return Err(syntax(position, "duration of zero"));
+    }
+    Ok(effect)
+}
+
+/// Parses the count of an `xN` suffix found at `at`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSyntax`] if it isn't a positive number.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn parse_repeat(count: &str, at: usize) -> Result<u32, Error> {
+    match count.parse() {
+        Ok(0) => Err(syntax(at, "repeat count of zero")),
+        Ok(repeat) => Ok(repeat),
+        Err(_) => Err(syntax(at, "invalid repeat count")),
+    }
+}
+
+/// Formats an intensity in `0..=1` with up to 5 decimals, enough to parse
+/// back into the same value.
+fn intensity(value: u16) -> String {
+    let text = format!("{:.5}", f64::from(value) / f64::from(u16::MAX));
+    text.trim_end_matches('0').trim_end_matches('.').to_owned()
+}
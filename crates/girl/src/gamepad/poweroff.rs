@@ -0,0 +1,163 @@
+//! Powering off a [`Gamepad`] from the host.
+
+#[cfg(feature = "power-off")]
+use sdl2::sys as sdl2_sys;
+
+#[cfg(feature = "power-off")]
+use crate::logging::diag;
+use crate::{Error, Gamepad, PowerLevel};
+
+/// Output report putting a Switch controller to sleep over Bluetooth: the
+/// neutral rumble data and the "set HCI state" subcommand with the
+/// "disconnect" argument, zero-padded to the report size.
+#[cfg(feature = "power-off")]
+const SWITCH_SLEEP: [u8; 49] = [
+    0x01, 0x00, 0x00, 0x01, 0x40, 0x40, 0x00, 0x01, 0x40, 0x40, 0x06, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00,
+];
+
+/// Powering off a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+impl Gamepad {
+    /// Checks if [`request_power_off`] can power off the [`Gamepad`]: the
+    /// `power-off` feature is enabled, its [`ControllerType`] can power off
+    /// (see [`ControllerType::can_power_off`]), and it isn't [`Wired`].
+    ///
+    /// [`request_power_off`]: Self::request_power_off
+    /// [`ControllerType`]: crate::ControllerType
+    /// [`ControllerType::can_power_off`]:
+    ///     crate::ControllerType::can_power_off
+    /// [`Wired`]: PowerLevel::Wired
+    #[must_use]
+    #[inline]
+    pub fn can_power_off(&self) -> bool {
+        cfg!(feature = "power-off")
+            && self.controller_type().can_power_off()
+            && !matches!(self.try_power(), Ok(PowerLevel::Wired))
+    }
+
+    /// Asks the [`Gamepad`] to power off, or to go to sleep, e.g. when the
+    /// player is done. It disconnects shortly after, and
+    /// [`Event::ControllerDeviceRemoved`] is reported as usual.
+    ///
+    /// The command is written to the HID device directly, as SDL2 has no
+    /// API for it, which requires the `power-off` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotSupported`] if the [`Gamepad`] can't be powered
+    /// off, see [`can_power_off`], or [`Error::SdlError`] if writing the
+    /// command fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::ErrorCode;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// match gamepad.request_power_off() {
+    ///     Ok(()) => println!("goodbye"),
+    ///     Err(err) if err.code() == ErrorCode::NotSupported => {
+    ///         println!("turn {gamepad} off by hand");
+    ///     }
+    ///     Err(err) => return Err(err),
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Event::ControllerDeviceRemoved`]:
+    ///     crate::Event::ControllerDeviceRemoved
+    /// [`can_power_off`]: Self::can_power_off
+    #[inline]
+    pub fn request_power_off(&mut self) -> Result<(), Error> {
+        let op = "request_power_off";
+        if !self.can_power_off() {
+            return Err(Error::NotSupported { op });
+        }
+        #[cfg(feature = "power-off")]
+        {
+            let result = self.write_sleep(op);
+            self.failures.observe(op, &result);
+            result?;
+            diag!(info, "requested gamepad {} to power off", self.id());
+            Ok(())
+        }
+        #[cfg(not(feature = "power-off"))]
+        {
+            Err(Error::NotSupported { op })
+        }
+    }
+
+    /// Writes [`SWITCH_SLEEP`] to the HID device of the [`Gamepad`], opened
+    /// again by its path next to the handle of SDL2.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the [`Gamepad`] has no HID path or SDL2 fails
+    /// to open or write to it.
+    #[cfg(feature = "power-off")]
+    fn write_sleep(&self, op: &'static str) -> Result<(), Error> {
+        let raw = self.raw(op)?;
+
+        // SAFETY: initialization is reference counted with the HIDAPI
+        //         drivers of SDL2, and undone below.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let status = unsafe { sdl2_sys::SDL_hid_init() };
+        if status != 0i32 {
+            return Err(Error::sdl(op)(sdl2::get_error()));
+        }
+
+        // SAFETY: SDL is alive, pointer is valid, return value is checked
+        //         for null and used before any other SDL call.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let path = unsafe { sdl2_sys::SDL_GameControllerPath(raw) };
+        let result = if path.is_null() {
+            Err(Error::NotSupported { op })
+        } else {
+            // SAFETY: `path` is a valid C string, the device is checked for
+            //         null below.
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            let device = unsafe { sdl2_sys::SDL_hid_open_path(path, 0i32) };
+            let written = if device.is_null() {
+                -1i32
+            } else {
+                // SAFETY: `device` is open, written at most the length of
+                //         the report.
+                #[expect(unsafe_code, reason = "ffi with sdl2")]
+                let count = unsafe {
+                    sdl2_sys::SDL_hid_write(
+                        device,
+                        SWITCH_SLEEP.as_ptr(),
+                        SWITCH_SLEEP.len(),
+                    )
+                };
+                // SAFETY: `device` is open and not used afterwards.
+                #[expect(unsafe_code, reason = "ffi with sdl2")]
+                unsafe {
+                    sdl2_sys::SDL_hid_close(device);
+                }
+                count
+            };
+            if written < 0i32 {
+                Err(Error::sdl(op)(sdl2::get_error()))
+            } else {
+                Ok(())
+            }
+        };
+
+        // SAFETY: paired with the `SDL_hid_init` above.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        unsafe {
+            _ = sdl2_sys::SDL_hid_exit();
+        }
+        result
+    }
+}
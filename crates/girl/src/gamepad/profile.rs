@@ -0,0 +1,937 @@
+//! Input tuning of a [`Gamepad`] and its [`InputProfile`] bundle.
+
+use crate::{
+    AdaptiveDeadzoneConfig, Button, ButtonAxis, Error, Stick, Trigger,
+    normalize::apply_deadzone,
+};
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, InputConfig, InputOverrides};
+
+/// Current version of the [`InputProfile`] format.
+pub(crate) const PROFILE_VERSION: u32 = 2;
+
+/// Default deadzone value for analog sticks, see
+/// [`Gamepad::STICK_DEADZONE`].
+pub(crate) const STICK_DEADZONE: f64 = 0.1;
+
+/// Input tuning of a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Sets the deadzone of the [`Stick`] used by [`stick`].
+    ///
+    /// If the deadzone is adaptive (see [`enable_adaptive_deadzone`]), it
+    /// starts learning over from this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `deadzone` is not in the
+    /// `[0.0, 1.0)` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.set_stick_deadzone(Stick::Left, 0.15)?;
+    /// assert!(gamepad.set_stick_deadzone(Stick::Left, 1.5).is_err());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    #[inline]
+    pub fn set_stick_deadzone(
+        &mut self,
+        stick: Stick,
+        deadzone: f64,
+    ) -> Result<(), Error> {
+        check_deadzone(deadzone)?;
+        self.profile.stick_mut(stick).deadzone = deadzone;
+        self.estimator(stick).set(None);
+        self.overrides =
+            self.overrides.union(InputOverrides::of_stick(stick).0);
+        Ok(())
+    }
+
+    /// Sets whether the `y` axis of the [`Stick`] is inverted in [`stick`].
+    ///
+    /// [`stick`]: Self::stick
+    #[inline]
+    pub const fn set_invert_y(&mut self, stick: Stick, invert: bool) {
+        self.profile.stick_mut(stick).invert_y = invert;
+        self.overrides =
+            self.overrides.union(InputOverrides::of_stick(stick).2);
+    }
+
+    /// Sets the [`StickClamp`] of the [`Stick`] used by [`stick`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{StickClamp, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.set_stick_clamp(Stick::Left, StickClamp::SquareToCircle);
+    /// let [x, y] = gamepad.stick(Stick::Left);
+    /// assert!(x.hypot(y) <= 1.0 + 1e-9);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    #[inline]
+    pub const fn set_stick_clamp(&mut self, stick: Stick, clamp: StickClamp) {
+        self.profile.stick_mut(stick).clamp = clamp;
+    }
+
+    /// Sets the [`ResponseCurve`] of the [`Stick`] used by [`stick`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the curve is not valid, see
+    /// [`ResponseCurve`].
+    ///
+    /// [`stick`]: Self::stick
+    #[inline]
+    pub fn set_stick_curve(
+        &mut self,
+        stick: Stick,
+        curve: ResponseCurve,
+    ) -> Result<(), Error> {
+        curve.check()?;
+        self.profile.stick_mut(stick).curve = curve;
+        self.overrides =
+            self.overrides.union(InputOverrides::of_stick(stick).1);
+        Ok(())
+    }
+
+    /// Sets the deadzone of the [`Trigger`] used by [`trigger`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `deadzone` is not in the
+    /// `[0.0, 1.0)` range.
+    ///
+    /// [`trigger`]: Self::trigger
+    #[inline]
+    pub fn set_trigger_deadzone(
+        &mut self,
+        trigger: Trigger,
+        deadzone: f64,
+    ) -> Result<(), Error> {
+        check_deadzone(deadzone)?;
+        self.profile.trigger_mut(trigger).deadzone = deadzone;
+        self.overrides =
+            self.overrides.union(InputOverrides::of_trigger(trigger).0);
+        Ok(())
+    }
+
+    /// Sets the value at which [`trigger_pressed`] reports the [`Trigger`] as
+    /// pressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `threshold` is not in the
+    /// `(0.0, 1.0]` range.
+    ///
+    /// [`trigger_pressed`]: Self::trigger_pressed
+    #[inline]
+    pub fn set_trigger_threshold(
+        &mut self,
+        trigger: Trigger,
+        threshold: f64,
+    ) -> Result<(), Error> {
+        check_threshold(threshold)?;
+        self.profile.trigger_mut(trigger).threshold = threshold;
+        self.overrides =
+            self.overrides.union(InputOverrides::of_trigger(trigger).1);
+        Ok(())
+    }
+
+    /// Checks whether the [`Trigger`] is pulled past its threshold (see
+    /// [`set_trigger_threshold`]).
+    ///
+    /// [`set_trigger_threshold`]: Self::set_trigger_threshold
+    #[must_use]
+    #[inline]
+    pub fn trigger_pressed(&self, trigger: Trigger) -> bool {
+        self.trigger(trigger) >= self.profile.trigger(trigger).threshold
+    }
+
+    /// Makes the physical `from` [`Button`] report as `to` in [`buttons`].
+    ///
+    /// Remapping `from` again replaces the previous remap, remapping it onto
+    /// itself removes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if either [`Button`] has more or
+    /// less than one bit set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // Nintendo-style confirm button
+    /// gamepad.remap_button(Button::A, Button::B)?;
+    /// gamepad.remap_button(Button::B, Button::A)?;
+    /// let combo = Button::A | Button::B;
+    /// assert!(gamepad.remap_button(combo, Button::X).is_err());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`buttons`]: Self::buttons
+    #[inline]
+    pub fn remap_button(
+        &mut self,
+        from: Button,
+        to: Button,
+    ) -> Result<(), Error> {
+        check_single_button(from)?;
+        check_single_button(to)?;
+        self.profile.remap.retain(|&(physical, _)| physical != from);
+        if from != to {
+            self.profile.remap.push((from, to));
+        }
+        Ok(())
+    }
+
+    /// Makes the physical [`Button`] read from an analog source in
+    /// [`button_value`], or from its digital state again if `source` is
+    /// [`None`].
+    ///
+    /// SDL2 mappings flatten analog buttons to digital ones, or expose their
+    /// values on extra axes which vary between controllers, hence the
+    /// mapping. [`buttons`] reports a [`Button`] backed by an analog source as
+    /// pressed at or above the [`InputProfile::button_threshold`]. Button
+    /// [`Event`]s keep reporting the digital state seen by SDL2.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the [`Button`] has more or less
+    /// than one bit set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ButtonAxis, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let pressure = ButtonAxis::Trigger(Trigger::Right);
+    /// gamepad.set_button_axis_source(Button::RightShoulder, Some(pressure))?;
+    /// let combo = Button::A | Button::B;
+    /// assert!(gamepad.set_button_axis_source(combo, Some(pressure)).is_err());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`button_value`]: Self::button_value
+    /// [`buttons`]: Self::buttons
+    /// [`InputProfile::button_threshold`]: InputProfile::button_threshold
+    /// [`Event`]: crate::Event
+    #[inline]
+    pub fn set_button_axis_source(
+        &mut self,
+        button: Button,
+        source: Option<ButtonAxis>,
+    ) -> Result<(), Error> {
+        check_single_button(button)?;
+        self.profile.button_axes.retain(|&(physical, _)| physical != button);
+        if let Some(axis) = source {
+            self.profile.button_axes.push((button, axis));
+        }
+        Ok(())
+    }
+
+    /// Gets the analog source of the physical [`Button`], see
+    /// [`set_button_axis_source`].
+    ///
+    /// [`set_button_axis_source`]: Self::set_button_axis_source
+    #[must_use]
+    #[inline]
+    pub fn button_axis_source(&self, button: Button) -> Option<ButtonAxis> {
+        self.profile.button_axis(button)
+    }
+
+    /// Applies all the settings of the [`InputProfile`] at once.
+    ///
+    /// Every field is validated with the same rules as the individual
+    /// setters. Nothing is applied if any field is invalid.
+    ///
+    /// The profile is remembered by the [`Guid`] of the [`Gamepad`], so
+    /// [`Gamepad`]s of the same model opened later, including on reconnect,
+    /// start with it instead of their default profile (see
+    /// [`Girl::set_default_profile`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] listing **all** the invalid fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let mut profile = gamepad.current_profile();
+    /// profile.left_stick.invert_y = true;
+    /// gamepad.apply_profile(&profile)?;
+    /// assert_eq!(gamepad.current_profile(), profile);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Guid`]: crate::Guid
+    /// [`Girl::set_default_profile`]: crate::Girl::set_default_profile
+    #[inline]
+    pub fn apply_profile(
+        &mut self,
+        profile: &InputProfile,
+    ) -> Result<(), Error> {
+        profile.validate()?;
+        self.profile.clone_from(profile);
+        self.overrides = InputOverrides::all();
+        drop(self.shared().applied().insert(self.guid(), profile.clone()));
+        Ok(())
+    }
+
+    /// Gets the settings overridden on this [`Gamepad`], which
+    /// [`Girl::propagate_default_input_config`] doesn't touch.
+    ///
+    /// Every setter of a setting overrides it, and [`apply_profile`]
+    /// overrides all of them, see [`InputConfig`] for the precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{InputOverrides, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.clear_input_overrides();
+    /// gamepad.set_invert_y(Stick::Right, true);
+    /// assert_eq!(gamepad.input_overrides(), InputOverrides::RightInvertY);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::propagate_default_input_config`]:
+    ///     crate::Girl::propagate_default_input_config
+    /// [`apply_profile`]: Self::apply_profile
+    #[must_use]
+    #[inline]
+    pub const fn input_overrides(&self) -> InputOverrides {
+        self.overrides
+    }
+
+    /// Forgets the overridden settings, so that the next
+    /// [`Girl::propagate_default_input_config`] applies all of them.
+    ///
+    /// [`Girl::propagate_default_input_config`]:
+    ///     crate::Girl::propagate_default_input_config
+    #[inline]
+    pub const fn clear_input_overrides(&mut self) {
+        self.overrides = InputOverrides::empty();
+    }
+
+    /// Applies the settings of the [`InputConfig`] not overridden on this
+    /// [`Gamepad`], restarting the adaptive deadzones it changes.
+    #[inline]
+    pub(crate) fn apply_input_config(&mut self, config: &InputConfig) {
+        let before = [Stick::Left, Stick::Right]
+            .map(|stick| self.profile.stick(stick).deadzone);
+        config.apply(&mut self.profile, self.overrides);
+        for (stick, deadzone) in
+            [Stick::Left, Stick::Right].into_iter().zip(before)
+        {
+            if self.profile.stick(stick).deadzone.to_bits()
+                != deadzone.to_bits()
+            {
+                self.estimator(stick).set(None);
+            }
+        }
+    }
+
+    /// Gets all the current settings as an [`InputProfile`], with the
+    /// deadzones learned so far if adaptive (see
+    /// [`enable_adaptive_deadzone`]).
+    ///
+    /// [`enable_adaptive_deadzone`]: Self::enable_adaptive_deadzone
+    #[must_use]
+    #[inline]
+    pub fn current_profile(&self) -> InputProfile {
+        let mut profile = self.profile.clone();
+        for stick in [Stick::Left, Stick::Right] {
+            profile.stick_mut(stick).deadzone = self.deadzone(stick);
+        }
+        profile
+    }
+}
+
+/// Bundle of all the input tuning of a [`Gamepad`].
+///
+/// Can be saved and loaded with `serde` (under the `serde` feature). Missing
+/// fields are filled with defaults when loading, so profiles saved by older
+/// versions keep loading. Profiles saved before a [`Button`] was renamed are
+/// loaded with `girl::config::load_profile` (under the `config` feature).
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{ResponseCurve, prelude::*};
+/// let mut profile = InputProfile::default();
+/// profile.left_stick.deadzone = 0.2;
+/// profile.right_stick.curve = ResponseCurve::Power(2.0);
+/// profile.remap.push((Button::A, Button::B));
+/// assert!(profile.validate().is_ok());
+///
+/// // all violations are reported at once
+/// profile.left_stick.deadzone = -1.0;
+/// profile.right_trigger.threshold = 0.0;
+/// profile.remap.push((Button::A | Button::X, Button::Y));
+/// let Err(girl::Error::InvalidProfile(invalid)) = profile.validate() else {
+///     unreachable!();
+/// };
+/// assert_eq!(invalid, [
+///     "left_stick.deadzone",
+///     "right_trigger.threshold",
+///     "remap",
+/// ]);
+/// ```
+///
+/// Saving and loading:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// # use girl::{AdaptiveDeadzoneConfig, prelude::*};
+/// let mut profile = InputProfile::default();
+/// profile.left_stick.invert_y = true;
+/// profile.remap.push((Button::A, Button::B));
+/// // learned deadzones are saved along with their configuration
+/// profile.right_stick.deadzone = 0.07;
+/// profile.right_stick.adaptive = Some(AdaptiveDeadzoneConfig::new());
+///
+/// let saved = serde_json::to_string(&profile).unwrap();
+/// let loaded: InputProfile = serde_json::from_str(&saved).unwrap();
+/// assert_eq!(loaded, profile);
+///
+/// // missing fields are filled with defaults
+/// let minimal: InputProfile = serde_json::from_str("{}").unwrap();
+/// assert_eq!(minimal, InputProfile::default());
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InputProfile {
+    /// Version of the profile format.
+    pub version: u32,
+    /// Settings of the left [`Stick`].
+    pub left_stick: StickProfile,
+    /// Settings of the right [`Stick`].
+    pub right_stick: StickProfile,
+    /// Settings of the left [`Trigger`].
+    pub left_trigger: TriggerProfile,
+    /// Settings of the right [`Trigger`].
+    pub right_trigger: TriggerProfile,
+    /// Physical to logical [`Button`] remaps.
+    pub remap: Vec<(Button, Button)>,
+    /// Analog sources of physical [`Button`]s, see
+    /// [`Gamepad::set_button_axis_source`].
+    pub button_axes: Vec<(Button, ButtonAxis)>,
+    /// Value at which a [`Button`] backed by an analog source counts as
+    /// pressed, in the `(0.0, 1.0]` range.
+    pub button_threshold: f64,
+}
+
+impl Default for InputProfile {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputProfile {
+    /// Creates the default [`InputProfile`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            left_stick: StickProfile::new(),
+            right_stick: StickProfile::new(),
+            left_trigger: TriggerProfile::new(),
+            right_trigger: TriggerProfile::new(),
+            remap: Vec::new(),
+            button_axes: Vec::new(),
+            button_threshold: 0.5,
+        }
+    }
+
+    /// Validates all the settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] listing **all** the invalid fields.
+    #[inline]
+    pub fn validate(&self) -> Result<(), Error> {
+        let checks = [
+            ("left_stick.deadzone", check_deadzone(self.left_stick.deadzone)),
+            ("left_stick.curve", self.left_stick.curve.check()),
+            ("left_stick.adaptive", check_adaptive(self.left_stick.adaptive)),
+            ("right_stick.deadzone", check_deadzone(self.right_stick.deadzone)),
+            ("right_stick.curve", self.right_stick.curve.check()),
+            ("right_stick.adaptive", check_adaptive(self.right_stick.adaptive)),
+            (
+                "left_trigger.deadzone",
+                check_deadzone(self.left_trigger.deadzone),
+            ),
+            (
+                "left_trigger.threshold",
+                check_threshold(self.left_trigger.threshold),
+            ),
+            (
+                "right_trigger.deadzone",
+                check_deadzone(self.right_trigger.deadzone),
+            ),
+            (
+                "right_trigger.threshold",
+                check_threshold(self.right_trigger.threshold),
+            ),
+            ("remap", self.check_remap()),
+            ("button_axes", self.check_button_axes()),
+            ("button_threshold", check_threshold(self.button_threshold)),
+        ];
+
+        let invalid: Vec<_> = checks
+            .into_iter()
+            .filter_map(|(field, check)| check.is_err().then_some(field))
+            .collect();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidProfile(invalid))
+        }
+    }
+
+    /// Gets the settings of a [`Stick`].
+    #[must_use]
+    #[inline]
+    pub const fn stick(&self, stick: Stick) -> &StickProfile {
+        match stick {
+            Stick::Left => &self.left_stick,
+            Stick::Right => &self.right_stick,
+        }
+    }
+
+    /// Gets the settings of a [`Trigger`].
+    #[must_use]
+    #[inline]
+    pub const fn trigger(&self, trigger: Trigger) -> &TriggerProfile {
+        match trigger {
+            Trigger::Left => &self.left_trigger,
+            Trigger::Right => &self.right_trigger,
+        }
+    }
+
+    /// Gets the logical [`Button`] the physical `button` reports as.
+    #[must_use]
+    #[inline]
+    pub fn remapped(&self, button: Button) -> Button {
+        self.remap
+            .iter()
+            .find(|&&(physical, _)| physical == button)
+            .map_or(button, |&(_, logical)| logical)
+    }
+
+    /// Gets the analog source of the physical `button`, if any.
+    #[must_use]
+    #[inline]
+    pub fn button_axis(&self, button: Button) -> Option<ButtonAxis> {
+        self.button_axes
+            .iter()
+            .find(|&&(physical, _)| physical == button)
+            .map(|&(_, source)| source)
+    }
+
+    /// Gets the settings of a [`Stick`] mutably.
+    #[cfg(feature = "sdl2")]
+    pub(crate) const fn stick_mut(
+        &mut self,
+        stick: Stick,
+    ) -> &mut StickProfile {
+        match stick {
+            Stick::Left => &mut self.left_stick,
+            Stick::Right => &mut self.right_stick,
+        }
+    }
+
+    /// Gets the settings of a [`Trigger`] mutably.
+    #[cfg(feature = "sdl2")]
+    pub(crate) const fn trigger_mut(
+        &mut self,
+        trigger: Trigger,
+    ) -> &mut TriggerProfile {
+        match trigger {
+            Trigger::Left => &mut self.left_trigger,
+            Trigger::Right => &mut self.right_trigger,
+        }
+    }
+
+    /// Checks that the remaps only contain single [`Button`]s and every
+    /// physical [`Button`] is remapped at most once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if a remap is not of single
+    /// [`Button`]s or remaps the same physical [`Button`] twice.
+    fn check_remap(&self) -> Result<(), Error> {
+        let mut seen = Button::empty();
+        for &(from, to) in &self.remap {
+            check_single_button(from)?;
+            check_single_button(to)?;
+            if seen.contains(from) {
+                return Err(Error::InvalidArgument { what: "remap" });
+            }
+            seen |= from;
+        }
+        Ok(())
+    }
+
+    /// Checks that the analog sources are of single [`Button`]s and every
+    /// physical [`Button`] has at most one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if an analog source is not of a
+    /// single [`Button`] or the same physical [`Button`] has two.
+    fn check_button_axes(&self) -> Result<(), Error> {
+        let mut seen = Button::empty();
+        for &(button, _) in &self.button_axes {
+            check_single_button(button)?;
+            if seen.contains(button) {
+                return Err(Error::InvalidArgument { what: "button_axes" });
+            }
+            seen |= button;
+        }
+        Ok(())
+    }
+}
+
+/// Settings of a [`Stick`], part of an [`InputProfile`].
+///
+/// Raw [`Stick`] positions go through the settings in this order, see
+/// [`apply`]:
+///
+/// 1. [`deadzone`], per axis;
+/// 2. [`clamp`], on the whole position;
+/// 3. [`curve`], per axis;
+/// 4. [`invert_y`].
+///
+/// [`apply`]: Self::apply
+/// [`deadzone`]: Self::deadzone
+/// [`clamp`]: Self::clamp
+/// [`curve`]: Self::curve
+/// [`invert_y`]: Self::invert_y
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StickProfile {
+    /// Deadzone, in the `[0.0, 1.0)` range.
+    pub deadzone: f64,
+    /// Whether the `y` axis is inverted.
+    pub invert_y: bool,
+    /// Clamping or mapping of the position applied after the deadzone.
+    pub clamp: StickClamp,
+    /// Response curve applied after the clamping.
+    pub curve: ResponseCurve,
+    /// Adaptive deadzone, learning the [`deadzone`] from the noise at rest,
+    /// see [`Gamepad::enable_adaptive_deadzone`]. [`None`] keeps it fixed.
+    ///
+    /// [`deadzone`]: Self::deadzone
+    pub adaptive: Option<AdaptiveDeadzoneConfig>,
+}
+
+impl Default for StickProfile {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StickProfile {
+    /// Creates the default [`StickProfile`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            deadzone: STICK_DEADZONE,
+            invert_y: false,
+            clamp: StickClamp::None,
+            curve: ResponseCurve::Linear,
+            adaptive: None,
+        }
+    }
+
+    /// Applies the deadzone, [`StickClamp`], [`ResponseCurve`] and `y` axis
+    /// inversion to a raw [`Stick`] position with axes in the `[-1.0, 1.0]`
+    /// range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{StickClamp, StickProfile};
+    /// let mut profile = StickProfile::new();
+    /// profile.invert_y = true;
+    /// assert_eq!(profile.apply([0.01, 0.5]), [0.0, -0.5]);
+    ///
+    /// profile.clamp = StickClamp::UnitCircle;
+    /// let [x, y] = profile.apply([1.0, 1.0]);
+    /// assert!((x.hypot(y) - 1.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn apply(&self, raw: [f64; 2]) -> [f64; 2] {
+        let position = raw.map(|value| apply_deadzone(value, self.deadzone));
+        let [x, y] =
+            self.clamp.apply(position).map(|value| self.curve.apply(value));
+        [x, if self.invert_y { -y } else { y }]
+    }
+}
+
+/// Settings of a [`Trigger`], part of an [`InputProfile`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TriggerProfile {
+    /// Deadzone, in the `[0.0, 1.0)` range.
+    pub deadzone: f64,
+    /// Value at which the trigger counts as pressed, in the `(0.0, 1.0]`
+    /// range.
+    pub threshold: f64,
+}
+
+impl Default for TriggerProfile {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TriggerProfile {
+    /// Creates the default [`TriggerProfile`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { deadzone: 0.0, threshold: 0.5 }
+    }
+
+    /// Applies the deadzone to a raw [`Trigger`] value in the `[0.0, 1.0]`
+    /// range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::TriggerProfile;
+    /// let mut profile = TriggerProfile::new();
+    /// profile.deadzone = 0.1;
+    ///
+    /// assert_eq!(profile.apply(0.05), 0.0);
+    /// assert_eq!(profile.apply(0.5), 0.5);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn apply(&self, raw: f64) -> f64 {
+        apply_deadzone(raw, self.deadzone)
+    }
+}
+
+/// Clamping of [`Stick`] positions, for controllers reporting magnitudes
+/// above `1.0` on diagonals (square gates).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StickClamp {
+    /// Positions are passed through unchanged.
+    #[default]
+    None,
+
+    /// Magnitudes above `1.0` are scaled down to `1.0`, keeping the
+    /// direction.
+    UnitCircle,
+
+    /// The square `[-1.0, 1.0]²` is smoothly mapped onto the unit disk, so
+    /// the corners end up on the circle and the edges keep their length.
+    SquareToCircle,
+}
+
+impl StickClamp {
+    /// Applies the clamping to a [`Stick`] position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::StickClamp;
+    /// let magnitude = |[x, y]: [f64; 2]| x.hypot(y);
+    /// let corner = [1.0, 1.0];
+    ///
+    /// assert_eq!(StickClamp::None.apply(corner), corner);
+    /// let unit = StickClamp::UnitCircle.apply(corner);
+    /// assert!((magnitude(unit) - 1.0).abs() < 1e-9);
+    /// let mapped = StickClamp::SquareToCircle.apply(corner);
+    /// assert!((magnitude(mapped) - 1.0).abs() < 1e-9);
+    ///
+    /// // the axes and the inside of the disk are not affected
+    /// assert_eq!(StickClamp::UnitCircle.apply([0.3, -0.4]), [0.3, -0.4]);
+    /// assert_eq!(StickClamp::SquareToCircle.apply([0.0, -1.0]), [0.0, -1.0]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn apply(self, position: [f64; 2]) -> [f64; 2] {
+        let [x, y] = position;
+        match self {
+            Self::None => position,
+            Self::UnitCircle => {
+                let magnitude = x.hypot(y);
+                if magnitude > 1.0 {
+                    [x / magnitude, y / magnitude]
+                } else {
+                    position
+                }
+            }
+            Self::SquareToCircle => {
+                let [cx, cy] = position.map(|value| value.clamp(-1.0, 1.0));
+                [
+                    cx * (cy * cy).mul_add(-0.5, 1.0).sqrt(),
+                    cy * (cx * cx).mul_add(-0.5, 1.0).sqrt(),
+                ]
+            }
+        }
+    }
+}
+
+/// Response curve applied to [`Stick`] values after the deadzone.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResponseCurve {
+    /// Values are passed through unchanged.
+    #[default]
+    Linear,
+
+    /// Values are raised to the given (finite, positive) power, keeping their
+    /// sign. Powers above `1.0` give finer control near the center.
+    Power(f64),
+}
+
+impl ResponseCurve {
+    /// Applies the curve to a value in the `[-1.0, 1.0]` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::ResponseCurve;
+    /// assert_eq!(ResponseCurve::Linear.apply(-0.5), -0.5);
+    /// assert_eq!(ResponseCurve::Power(2.0).apply(-0.5), -0.25);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn apply(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Power(power) => value.abs().powf(power).copysign(value),
+        }
+    }
+
+    /// Checks that the curve is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the power is not finite and
+    /// positive.
+    const fn check(self) -> Result<(), Error> {
+        match self {
+            Self::Linear => Ok(()),
+            Self::Power(power) if power.is_finite() && power > 0.0 => Ok(()),
+            Self::Power(_) => Err(Error::InvalidArgument { what: "curve" }),
+        }
+    }
+}
+
+/// Checks that a deadzone is in the `[0.0, 1.0)` range.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArgument`] if it is not.
+const fn check_deadzone(deadzone: f64) -> Result<(), Error> {
+    if deadzone >= 0.0 && deadzone < 1.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument { what: "deadzone" })
+    }
+}
+
+/// Checks that an adaptive deadzone configuration, if any, is valid.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArgument`] if the configuration is not valid.
+const fn check_adaptive(
+    config: Option<AdaptiveDeadzoneConfig>,
+) -> Result<(), Error> {
+    match config.as_ref() {
+        Some(config) => config.check(),
+        None => Ok(()),
+    }
+}
+
+/// Checks that a threshold is in the `(0.0, 1.0]` range.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArgument`] if the `threshold` is out of range.
+pub(crate) const fn check_threshold(threshold: f64) -> Result<(), Error> {
+    if threshold > 0.0 && threshold <= 1.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument { what: "threshold" })
+    }
+}
+
+/// Checks that exactly one [`Button`] bit is set.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArgument`] if the `button` has no or several
+/// bits set.
+pub(crate) const fn check_single_button(button: Button) -> Result<(), Error> {
+    if button.bits().is_power_of_two() {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument { what: "button" })
+    }
+}
@@ -1,8 +1,15 @@
 //! Rumble capabilities of a [`Gamepad`].
 
 use core::time::Duration;
+use std::{thread, time::Instant};
 
-use crate::{Error, Gamepad};
+use crate::{
+    ControllerType, EffectDuration, Error, ErrorCode, Gamepad, Girl,
+    OutputCommand,
+};
+
+/// Longest sleep between checks in [`Gamepad::wait_rumble_complete`].
+const WAIT_STEP: Duration = Duration::from_millis(10);
 
 /// Rumble capabilities of a [`Gamepad`].
 #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
@@ -20,13 +27,88 @@ impl Gamepad {
         self.gp.has_rumble()
     }
 
+    /// Gets the [`RumbleCaps`] of the [`Gamepad`], from what SDL2 reports
+    /// and what is known about its [`ControllerType`], see
+    /// [`RumbleCaps::detect`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let caps = gamepad.rumble_capabilities();
+    /// if gamepad.has_rumble() && !caps.dual_motor {
+    ///     println!("{gamepad} rumbles with a single motor");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn rumble_capabilities(&self) -> RumbleCaps {
+        RumbleCaps::detect(
+            self.controller_type(),
+            self.has_rumble(),
+            self.has_rumble_triggers(),
+        )
+    }
+
+    /// Gets the [`RumbleFold`] applied by [`set_rumble`], see
+    /// [`set_rumble_fold`].
+    ///
+    /// [`set_rumble`]: Self::set_rumble
+    /// [`set_rumble_fold`]: Self::set_rumble_fold
+    #[must_use]
+    #[inline]
+    pub const fn rumble_fold(&self) -> RumbleFold {
+        self.rumble_fold
+    }
+
+    /// Sets how [`set_rumble`] folds the high-frequency channel into the
+    /// low-frequency one on a [`Gamepad`] with a single motor, see
+    /// [`RumbleCaps::dual_motor`]. [`RumbleFold::Max`] by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::RumbleFold;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // leave the channels to the driver
+    /// gamepad.set_rumble_fold(RumbleFold::Off);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_rumble`]: Self::set_rumble
+    #[inline]
+    pub const fn set_rumble_fold(&mut self, fold: RumbleFold) {
+        self.rumble_fold = fold;
+    }
+
     /// Sets the rumble intensity and duration. Automatically resets back to
     /// zero after `duration` has passed.
     ///
+    /// On a [`Gamepad`] with a single motor, the channels are folded by the
+    /// [`RumbleFold`], see [`set_rumble_fold`].
+    ///
+    /// The effect is tracked (see [`rumble_remaining`]) and reported with
+    /// [`Event::RumbleFinished`] by [`Girl::update`] once it ends. Setting
+    /// both intensities, or the `duration`, to zero stops it early.
+    ///
+    /// The `duration` is validated as an [`EffectDuration`]: it is rounded
+    /// up to whole milliseconds, and at most [`EffectDuration::MAX`].
+    ///
     /// # Errors
     ///
-    /// Returns an error if the [`Gamepad`] doesn't support rumble or the
-    /// operation fails.
+    /// Returns [`Error::InvalidArgument`] if the `duration` is longer than
+    /// [`EffectDuration::MAX`], or an error if the [`Gamepad`] doesn't
+    /// support rumble or the operation fails, hinting at Bluetooth if the
+    /// [`Gamepad`] is on it, see [`ConnectionKind::hint`].
     ///
     /// # Examples
     ///
@@ -42,6 +124,11 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`set_rumble_fold`]: Self::set_rumble_fold
+    /// [`rumble_remaining`]: Self::rumble_remaining
+    /// [`Event::RumbleFinished`]: crate::Event::RumbleFinished
+    /// [`ConnectionKind::hint`]: crate::ConnectionKind::hint
     #[inline]
     pub fn set_rumble(
         &mut self,
@@ -49,19 +136,109 @@ impl Gamepad {
         high_frequency_rumble: u16,
         duration: Duration,
     ) -> Result<(), Error> {
-        self.gp
-            .set_rumble(
+        let effect = EffectDuration::new(duration)?;
+        let (low, high) = if effect.is_stop() {
+            (0, 0)
+        } else {
+            self.rumble_fold.apply(
+                self.rumble_capabilities(),
                 low_frequency_rumble,
                 high_frequency_rumble,
-                duration.as_millis().try_into().unwrap_or(u32::MAX),
             )
-            .map_err(|err| Error::SdlError(err.to_string()))
+        };
+        let result = if self.gp.has_rumble() {
+            self.gp
+                .set_rumble(low, high, effect.millis())
+                .map_err(Error::sdl("set_rumble"))
+                .map_err(|err| self.connection_kind().hint(err, "rumble"))
+        } else {
+            Err(Error::sdl_static(
+                "set_rumble",
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ))
+        };
+        self.failures.observe("set_rumble", &result);
+        let command =
+            OutputCommand::Rumble { low, high, duration: effect.duration() };
+        self.journal_command(command, &result);
+        result?;
+
+        let id = self.id();
+        if low == 0 && high == 0 {
+            _ = self.shared().rumble().cancel(id);
+        } else {
+            self.shared().rumble().start(id, Instant::now(), effect.duration());
+        }
+        Ok(())
+    }
+
+    /// Gets the time left until the rumble effect set with [`set_rumble`]
+    /// ends.
+    ///
+    /// Returns [`None`] if no effect is running.
+    ///
+    /// [`set_rumble`]: Self::set_rumble
+    #[must_use]
+    #[inline]
+    pub fn rumble_remaining(&self) -> Option<Duration> {
+        self.shared().rumble().remaining(self.id(), Instant::now())
+    }
+
+    /// Blocks until the rumble effect set with [`set_rumble`] ends, calling
+    /// [`Girl::update`] meanwhile so that [`Event::RumbleFinished`] is
+    /// reported as usual.
+    ///
+    /// Returns `false` if the effect is still running after `timeout`. A
+    /// `timeout` of [`Duration::ZERO`] checks once without waiting.
+    /// Meant for script-like tools, games should react to
+    /// [`Event::RumbleFinished`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.has_rumble() {
+    ///     gamepad.set_rumble(1000, 1000, Duration::from_millis(100))?;
+    ///     assert!(gamepad.wait_rumble_complete(&mut girl, Duration::MAX));
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_rumble`]: Self::set_rumble
+    /// [`Event::RumbleFinished`]: crate::Event::RumbleFinished
+    #[inline]
+    pub fn wait_rumble_complete(
+        &mut self,
+        girl: &mut Girl,
+        timeout: Duration,
+    ) -> bool {
+        let start = Instant::now();
+        loop {
+            _ = girl.update();
+            let Some(remaining) = self.rumble_remaining() else {
+                return true;
+            };
+            let waited = start.elapsed();
+            if waited >= timeout {
+                return false;
+            }
+            thread::sleep(
+                remaining.min(WAIT_STEP).min(timeout.saturating_sub(waited)),
+            );
+        }
     }
 
     /// Stops rumble effects.
     ///
     /// Analogous to [`set_rumble`] with `low_frequency_rumble` and
-    /// `high_frequency_rumble` set to zero.
+    /// `high_frequency_rumble` set to zero, so a running effect is reported
+    /// with [`Event::RumbleFinished`] with `cancelled` set.
     ///
     /// # Errors
     ///
@@ -84,9 +261,10 @@ impl Gamepad {
     /// ```
     ///
     /// [`set_rumble`]: Self::set_rumble
+    /// [`Event::RumbleFinished`]: crate::Event::RumbleFinished
     #[inline]
     pub fn end_rumble(&mut self) -> Result<(), Error> {
-        self.set_rumble(0, 0, Duration::from_millis(1))
+        self.set_rumble(0, 0, Duration::ZERO)
     }
 
     /// Query whether the gamepad has trigger rumble support.
@@ -96,12 +274,16 @@ impl Gamepad {
         self.gp.has_rumble_triggers()
     }
 
-    /// Sets rumble intensity for the triggers.
+    /// Sets rumble intensity for the triggers. Automatically resets back to
+    /// zero after `duration` has passed, which is validated as an
+    /// [`EffectDuration`], so a zero `duration` stops it early.
     ///
     /// # Errors
     ///
-    /// Returns an error if the [`Gamepad`] doesn't support trigger rumble or
-    /// the operation fails.
+    /// Returns [`Error::InvalidArgument`] if the `duration` is longer than
+    /// [`EffectDuration::MAX`], or an error if the [`Gamepad`] doesn't
+    /// support trigger rumble or the operation fails, hinting at Bluetooth
+    /// if the [`Gamepad`] is on it, see [`ConnectionKind::hint`].
     ///
     /// # Examples
     ///
@@ -117,6 +299,8 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`ConnectionKind::hint`]: crate::ConnectionKind::hint
     #[inline]
     pub fn set_rumble_triggers(
         &mut self,
@@ -124,13 +308,34 @@ impl Gamepad {
         right_trigger_rumble: u16,
         duration: Duration,
     ) -> Result<(), Error> {
-        self.gp
-            .set_rumble_triggers(
-                left_trigger_rumble,
-                right_trigger_rumble,
-                duration.as_millis().try_into().unwrap_or(u32::MAX),
-            )
-            .map_err(|err| Error::SdlError(err.to_string()))
+        let effect = EffectDuration::new(duration)?;
+        let (left, right) = if effect.is_stop() {
+            (0, 0)
+        } else {
+            (left_trigger_rumble, right_trigger_rumble)
+        };
+        let result = if self.gp.has_rumble_triggers() {
+            self.gp
+                .set_rumble_triggers(left, right, effect.millis())
+                .map_err(Error::sdl("set_rumble_triggers"))
+                .map_err(|err| {
+                    self.connection_kind().hint(err, "trigger rumble")
+                })
+        } else {
+            Err(Error::sdl_static(
+                "set_rumble_triggers",
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ))
+        };
+        self.failures.observe("set_rumble_triggers", &result);
+        let command = OutputCommand::RumbleTriggers {
+            left,
+            right,
+            duration: effect.duration(),
+        };
+        self.journal_command(command, &result);
+        result
     }
 
     /// Stops trigger rumble effects.
@@ -161,6 +366,196 @@ impl Gamepad {
     /// [`set_rumble_triggers`]: Self::set_rumble_triggers
     #[inline]
     pub fn end_rumble_triggers(&mut self) -> Result<(), Error> {
-        self.set_rumble_triggers(0, 0, Duration::from_millis(1))
+        self.set_rumble_triggers(0, 0, Duration::ZERO)
+    }
+}
+
+/// Rumble hardware of a [`Gamepad`], see [`Gamepad::rumble_capabilities`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{RumbleCaps, prelude::*};
+/// let caps = RumbleCaps::detect(ControllerType::XboxOne, true, true);
+/// assert!(caps.dual_motor && caps.trigger_actuators);
+///
+/// // a single Joy-Con has one actuator, and none without rumble at all
+/// let joycon = RumbleCaps::detect(ControllerType::JoyConLeft, true, false);
+/// assert!(!joycon.dual_motor);
+/// let none = RumbleCaps::detect(ControllerType::Ps4, false, false);
+/// assert_eq!(none, RumbleCaps::NONE);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RumbleCaps {
+    /// Whether separate low and high-frequency motors are driven by
+    /// [`Gamepad::set_rumble`].
+    pub dual_motor: bool,
+    /// Whether the triggers have their own actuators, driven by
+    /// [`Gamepad::set_rumble_triggers`].
+    pub trigger_actuators: bool,
+    /// Number of amplitude bits the hardware actually resolves, if known.
+    /// Rumble intensities are always given as [`u16`], finer steps than
+    /// this are lost.
+    pub amplitude_resolution_hint: Option<u8>,
+}
+
+impl RumbleCaps {
+    /// No rumble hardware at all.
+    pub const NONE: Self = Self::new(false, false, None);
+
+    /// Creates new [`RumbleCaps`].
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        dual_motor: bool,
+        trigger_actuators: bool,
+        amplitude_resolution_hint: Option<u8>,
+    ) -> Self {
+        Self { dual_motor, trigger_actuators, amplitude_resolution_hint }
+    }
+
+    /// Gets the rumble hardware known for a [`ControllerType`], or [`None`]
+    /// if unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{RumbleCaps, prelude::*};
+    /// use ControllerType as CT;
+    /// let table = [
+    ///     (CT::Xbox360, Some((true, false, Some(8)))),
+    ///     (CT::XboxOne, Some((true, true, Some(8)))),
+    ///     (CT::Ps3, Some((true, false, Some(8)))),
+    ///     (CT::Ps4, Some((true, false, Some(8)))),
+    ///     (CT::Ps5, Some((true, false, Some(8)))),
+    ///     (CT::SwitchPro, Some((true, false, None))),
+    ///     (CT::JoyConLeft, Some((false, false, None))),
+    ///     (CT::JoyConRight, Some((false, false, None))),
+    ///     (CT::JoyConPair, Some((true, false, None))),
+    ///     (CT::Virtual, None),
+    ///     (CT::Other, None),
+    ///     (CT::Unknown, None),
+    /// ];
+    /// for (controller_type, expected) in table {
+    ///     let expected = expected.map(|(dual, triggers, bits)| {
+    ///         RumbleCaps::new(dual, triggers, bits)
+    ///     });
+    ///     assert_eq!(RumbleCaps::known(controller_type), expected);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn known(controller_type: ControllerType) -> Option<Self> {
+        Some(match controller_type {
+            ControllerType::Xbox360
+            | ControllerType::Ps3
+            | ControllerType::Ps4
+            | ControllerType::Ps5 => Self::new(true, false, Some(8)),
+            // impulse triggers
+            ControllerType::XboxOne => Self::new(true, true, Some(8)),
+            // HD rumble, with one actuator per Joy-Con
+            ControllerType::SwitchPro | ControllerType::JoyConPair => {
+                Self::new(true, false, None)
+            }
+            ControllerType::JoyConLeft | ControllerType::JoyConRight => {
+                Self::new(false, false, None)
+            }
+            ControllerType::Virtual
+            | ControllerType::Other
+            | ControllerType::Unknown => return None,
+        })
+    }
+
+    /// Detects the rumble hardware from what SDL2 reports, whether the
+    /// controller has rumble and trigger rumble, and what is [`known`] for
+    /// its [`ControllerType`].
+    ///
+    /// SDL2 only tells whether rumble works at all, so an unknown
+    /// [`ControllerType`] is assumed to have two motors, as the SDL2 API
+    /// does. The trigger actuators are the ones SDL2 can drive.
+    ///
+    /// [`known`]: Self::known
+    #[must_use]
+    #[inline]
+    pub const fn detect(
+        controller_type: ControllerType,
+        has_rumble: bool,
+        has_rumble_triggers: bool,
+    ) -> Self {
+        if !has_rumble {
+            return Self::new(false, has_rumble_triggers, None);
+        }
+        match Self::known(controller_type) {
+            Some(known) => Self::new(
+                known.dual_motor,
+                has_rumble_triggers,
+                known.amplitude_resolution_hint,
+            ),
+            None => Self::new(true, has_rumble_triggers, None),
+        }
+    }
+}
+
+/// How [`Gamepad::set_rumble`] combines the low and high-frequency
+/// channels on a [`Gamepad`] with a single motor, see
+/// [`Gamepad::set_rumble_fold`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{RumbleCaps, RumbleFold};
+/// let single = RumbleCaps::new(false, false, None);
+/// let dual = RumbleCaps::new(true, false, None);
+///
+/// // only the high-frequency channel is rumbling
+/// assert_eq!(RumbleFold::Max.apply(single, 0, 40_000), (40_000, 0));
+/// assert_eq!(RumbleFold::Off.apply(single, 0, 40_000), (0, 40_000));
+///
+/// // both are
+/// assert_eq!(RumbleFold::Max.apply(single, 30_000, 40_000), (40_000, 0));
+/// assert_eq!(RumbleFold::Sum.apply(single, 30_000, 40_000), (u16::MAX, 0));
+/// assert_eq!(RumbleFold::Sum.apply(single, 1_000, 2_000), (3_000, 0));
+///
+/// // two motors play both channels as they are
+/// for fold in [RumbleFold::Off, RumbleFold::Max, RumbleFold::Sum] {
+///     assert_eq!(fold.apply(dual, 30_000, 40_000), (30_000, 40_000));
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RumbleFold {
+    /// Leaves both channels as they are, so the driver may silently drop
+    /// the high-frequency one.
+    Off,
+    /// Plays the stronger channel on the low-frequency motor.
+    #[default]
+    Max,
+    /// Plays the sum of both channels on the low-frequency motor,
+    /// saturating.
+    Sum,
+}
+
+impl RumbleFold {
+    /// Folds the `low` and `high`-frequency intensities for the rumble
+    /// hardware `caps`, returning the ones to play.
+    #[must_use]
+    #[inline]
+    pub const fn apply(
+        self,
+        caps: RumbleCaps,
+        low: u16,
+        high: u16,
+    ) -> (u16, u16) {
+        if caps.dual_motor {
+            return (low, high);
+        }
+        match self {
+            Self::Off => (low, high),
+            Self::Max => (if low > high { low } else { high }, 0),
+            Self::Sum => (low.saturating_add(high), 0),
+        }
     }
 }
@@ -1,10 +1,27 @@
 //! Sensor data for a [`Gamepad`].
+//!
+//! [`Gamepad`]: crate::Gamepad
 
+#[cfg(feature = "sensors")]
+use core::time::Duration;
+#[cfg(feature = "sensors")]
+use std::{thread, time::Instant};
+
+#[cfg(feature = "sensors")]
 use sdl2::sensor::SensorType as SdlSensorType;
 
-use crate::{Error, Gamepad};
+#[cfg(feature = "sensors")]
+use crate::{
+    Error, ErrorCode, Gamepad, Orientation, OutputCommand, logging::diag,
+    normalize::apply_deadzone,
+};
+
+/// Magnitude below which sensor readings are reported as `0.0`.
+#[cfg(feature = "sensors")]
+pub(crate) const SENSOR_DEADZONE: f64 = 0.01;
 
 /// Sensor data for a [`Gamepad`].
+#[cfg(feature = "sensors")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
 // TODO: Try remove on next Rust version update.
 #[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
@@ -20,33 +37,241 @@ impl Gamepad {
         self.gp.has_sensor(sensor_type.into_sdl())
     }
 
+    /// Lists the [`Sensor`]s the [`Gamepad`] has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// println!("{gamepad} has {:?}", gamepad.available_sensors());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn available_sensors(&self) -> Vec<Sensor> {
+        Sensor::ALL
+            .into_iter()
+            .filter(|&sensor| self.has_sensor(sensor))
+            .collect()
+    }
+
+    /// Picks the gyroscope to use, see [`Sensor::primary_gyroscope`] and
+    /// [`set_preferred_side`].
+    ///
+    /// Returns [`None`] if the [`Gamepad`] has no gyroscope.
+    ///
+    /// [`set_preferred_side`]: Self::set_preferred_side
+    #[must_use]
+    #[inline]
+    pub fn primary_gyroscope(&self) -> Option<Sensor> {
+        let available = self.available_sensors();
+        Sensor::primary_gyroscope(&available, self.preferred_side)
+    }
+
+    /// Picks the accelerometer to use, see [`Sensor::primary_accelerometer`]
+    /// and [`set_preferred_side`].
+    ///
+    /// Returns [`None`] if the [`Gamepad`] has no accelerometer.
+    ///
+    /// [`set_preferred_side`]: Self::set_preferred_side
+    #[must_use]
+    #[inline]
+    pub fn primary_accelerometer(&self) -> Option<Sensor> {
+        let available = self.available_sensors();
+        Sensor::primary_accelerometer(&available, self.preferred_side)
+    }
+
+    /// Gets the Joy-Con preferred by [`primary_gyroscope`] and
+    /// [`primary_accelerometer`] when both are paired.
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    /// [`primary_accelerometer`]: Self::primary_accelerometer
+    #[must_use]
+    #[inline]
+    pub const fn preferred_side(&self) -> JoyConSide {
+        self.preferred_side
+    }
+
+    /// Sets the Joy-Con preferred by [`primary_gyroscope`] and
+    /// [`primary_accelerometer`] when both are paired,
+    /// [`JoyConSide::Right`] by default.
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    /// [`primary_accelerometer`]: Self::primary_accelerometer
+    #[inline]
+    pub const fn set_preferred_side(&mut self, side: JoyConSide) {
+        self.preferred_side = side;
+    }
+
     /// Enables a [`Sensor`] on the [`Gamepad`].
     ///
+    /// Enabling fails on some controllers until they finish initializing,
+    /// e.g. on Bluetooth `DualSense` controllers right after
+    /// [`Event::ControllerDeviceAdded`]. Failed enables are retried by
+    /// [`Girl::update`] once enabled with [`Girl::set_sensor_retry_window`],
+    /// or retry them right away with [`enable_sensor_with_retry`].
+    ///
     /// # Errors
     ///
     /// Returns an [`Error`] if the sensor is not available or fails to
-    /// enable.
+    /// enable, hinting at Bluetooth if the [`Gamepad`] is on it, see
+    /// [`ConnectionKind::hint`].
     ///
     /// # Examples
     ///
     /// ```
-    /// # use girl::Sensor;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
     ///
-    /// if gamepad.has_sensor(Sensor::Gyroscope) {
-    ///     gamepad.enable_sensor(Sensor::Gyroscope)?;
+    /// // works with combined sensors as well as with Joy-Cons
+    /// if let Some(gyroscope) = gamepad.primary_gyroscope() {
+    ///     gamepad.enable_sensor(gyroscope)?;
     ///     // read sensor data later
     /// }
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`Event::ControllerDeviceAdded`]: crate::Event::ControllerDeviceAdded
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::set_sensor_retry_window`]: crate::Girl::set_sensor_retry_window
+    /// [`enable_sensor_with_retry`]: Self::enable_sensor_with_retry
+    /// [`ConnectionKind::hint`]: crate::ConnectionKind::hint
     #[inline]
     pub fn enable_sensor(&self, sensor: Sensor) -> Result<(), Error> {
-        self.gp
-            .sensor_set_enabled(sensor.into_sdl(), true)
-            .map_err(|err| Error::SdlError(err.to_string()))
+        let enabled = self.try_enable_sensor(sensor);
+        if enabled.is_err() {
+            self.shared().sensor_failed(self.id(), sensor);
+        }
+        enabled
+    }
+
+    /// Enables a [`Sensor`] like [`enable_sensor`], trying up to `attempts`
+    /// times (at least once) and waiting `backoff` between the attempts,
+    /// doubled after each of them. Blocks the thread while waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`Error`] of the last attempt if all of them fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Some(gyroscope) = gamepad.primary_gyroscope() {
+    ///     let backoff = Duration::from_millis(20);
+    ///     gamepad.enable_sensor_with_retry(gyroscope, 3, backoff)?;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`enable_sensor`]: Self::enable_sensor
+    #[inline]
+    pub fn enable_sensor_with_retry(
+        &self,
+        sensor: Sensor,
+        attempts: u32,
+        backoff: Duration,
+    ) -> Result<(), Error> {
+        let mut delay = backoff;
+        let mut attempt = 1;
+        loop {
+            match self.try_enable_sensor(sensor) {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt >= attempts => return Err(err),
+                Err(err) => {
+                    diag!(debug, "retrying to enable {sensor:?}: {err}");
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2);
+                    attempt = attempt.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Enables a [`Sensor`] without queueing it for retries on failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the [`Gamepad`] has no such [`Sensor`] or
+    /// SDL2 fails to enable it.
+    #[inline]
+    pub(crate) fn try_enable_sensor(
+        &self,
+        sensor: Sensor,
+    ) -> Result<(), Error> {
+        let result = if self.gp.has_sensor(sensor.into_sdl()) {
+            self.gp
+                .sensor_set_enabled(sensor.into_sdl(), true)
+                .map_err(Error::sdl("enable_sensor"))
+                .map_err(|err| self.connection_kind().hint(err, "sensor rates"))
+        } else {
+            Err(Error::sdl_static(
+                "enable_sensor",
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ))
+        };
+        self.failures.observe("enable_sensor", &result);
+        let command = OutputCommand::Sensor { sensor, enabled: true };
+        self.journal_command(command, &result);
+        result
+    }
+
+    /// Disables a [`Sensor`] enabled by [`enable_sensor`], saving power.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the sensor is not available or fails to
+    /// disable.
+    ///
+    /// [`enable_sensor`]: Self::enable_sensor
+    #[inline]
+    pub fn disable_sensor(&self, sensor: Sensor) -> Result<(), Error> {
+        let result = self
+            .gp
+            .sensor_set_enabled(sensor.into_sdl(), false)
+            .map_err(Error::sdl("disable_sensor"));
+        self.failures.observe("disable_sensor", &result);
+        let command = OutputCommand::Sensor { sensor, enabled: false };
+        self.journal_command(command, &result);
+        result
+    }
+
+    /// Checks if a [`Sensor`] is enabled, see [`enable_sensor`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Some(gyroscope) = gamepad.primary_gyroscope() {
+    ///     gamepad.enable_sensor(gyroscope)?;
+    ///     assert!(gamepad.sensor_enabled(gyroscope));
+    ///     gamepad.disable_sensor(gyroscope)?;
+    ///     assert!(!gamepad.sensor_enabled(gyroscope));
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`enable_sensor`]: Self::enable_sensor
+    #[must_use]
+    #[inline]
+    pub fn sensor_enabled(&self, sensor: Sensor) -> bool {
+        self.gp.sensor_enabled(sensor.into_sdl())
     }
 
     /// Gets current [`Sensor`] data.
@@ -62,14 +287,13 @@ impl Gamepad {
     /// # Examples
     ///
     /// ```
-    /// # use girl::Sensor;
     /// let mut girl = girl::Girl::new()?;
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
     ///
-    /// if gamepad.has_sensor(Sensor::Gyroscope) {
-    ///     gamepad.enable_sensor(Sensor::Gyroscope)?;
-    ///     let [x, y, z] = gamepad.sensor(Sensor::Gyroscope)?;
+    /// if let Some(gyroscope) = gamepad.primary_gyroscope() {
+    ///     gamepad.enable_sensor(gyroscope)?;
+    ///     let [x, y, z] = gamepad.sensor(gyroscope)?;
     ///     // apply movement to a character, etc.
     /// }
     /// # }
@@ -79,22 +303,108 @@ impl Gamepad {
     /// [`enable_sensor`]: Self::enable_sensor
     #[inline]
     pub fn sensor(&self, sensor: Sensor) -> Result<[f64; 3], Error> {
+        if self.suspended_for_query() {
+            return Ok([0.0; 3]);
+        }
         let mut data = [0.; 3];
-        self.gp
-            .sensor_get_data(sensor.into_sdl(), &mut data)
-            .map_err(|err| Error::SdlError(err.to_string()))?;
-        Ok(data.map(|x| super::map(f64::from(x), 0.01, 1.)))
+        let result = if self.gp.has_sensor(sensor.into_sdl()) {
+            self.shared().count_ffi_call();
+            self.gp
+                .sensor_get_data(sensor.into_sdl(), &mut data)
+                .map_err(Error::sdl("sensor"))
+        } else {
+            Err(Error::sdl_static(
+                "sensor",
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ))
+        };
+        self.failures.observe("sensor", &result);
+        result?;
+        Ok(data.map(|x| apply_deadzone(f64::from(x), SENSOR_DEADZONE)))
+    }
+
+    /// Gets current [`Sensor`] data rotated from the controller's body frame
+    /// into a gravity-aligned world frame, e.g. to turn the camera around
+    /// world up with the gyroscope however the controller is held.
+    ///
+    /// Reads both the gyroscope and the accelerometer of the same side and
+    /// feeds them to an [`Orientation`] estimate kept per side, so both must
+    /// be enabled with [`enable_sensor`], and this should be called every
+    /// frame to keep the estimate up to date. See [`Orientation`] for the
+    /// frame conventions and [`reset_world_yaw`] to re-center the heading.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] for [`Sensor::Unknown`], or an
+    /// [`Error`] if either [`Sensor`] of the side is not available or fails
+    /// to read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// gamepad.enable_sensor(Sensor::Gyroscope)?;
+    /// gamepad.enable_sensor(Sensor::Accelerometer)?;
+    /// // every frame
+    /// let [_, yaw_rate, _] = gamepad.sensor_world(Sensor::Gyroscope)?;
+    /// // turn the camera around world up by `yaw_rate * dt`
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Orientation`]: crate::Orientation
+    /// [`enable_sensor`]: Self::enable_sensor
+    /// [`reset_world_yaw`]: Self::reset_world_yaw
+    #[inline]
+    pub fn sensor_world(&mut self, sensor: Sensor) -> Result<[f64; 3], Error> {
+        let (gyroscope, accelerometer) = match sensor {
+            Sensor::Unknown => {
+                return Err(Error::InvalidArgument { what: "sensor" });
+            }
+            Sensor::Gyroscope | Sensor::Accelerometer => {
+                (Sensor::Gyroscope, Sensor::Accelerometer)
+            }
+            Sensor::LeftGyroscope | Sensor::LeftAccelerometer => {
+                (Sensor::LeftGyroscope, Sensor::LeftAccelerometer)
+            }
+            Sensor::RightGyroscope | Sensor::RightAccelerometer => {
+                (Sensor::RightGyroscope, Sensor::RightAccelerometer)
+            }
+        };
+        let gyro = self.sensor(gyroscope)?;
+        let accel = self.sensor(accelerometer)?;
+        let orientation = self.orientations.entry(gyroscope).or_default();
+        orientation.push(gyro, accel, Instant::now());
+        let reading = if sensor == gyroscope { gyro } else { accel };
+        Ok(orientation.to_world(reading))
+    }
+
+    /// Makes the current heading of the controller the yaw reference of
+    /// [`sensor_world`], e.g. when the player re-centers the camera.
+    ///
+    /// [`sensor_world`]: Self::sensor_world
+    #[inline]
+    pub fn reset_world_yaw(&mut self) {
+        self.orientations.values_mut().for_each(Orientation::reset_yaw);
     }
 }
 
 /// Sensors available on [`Gamepad`]s.
-#[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+///
+/// [`Gamepad`]: crate::Gamepad
+#[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[expect(
     clippy::exhaustive_enums,
     reason = "if gamepads get more sensors in the future, we'll add them in a \
               major update"
 )]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sensor {
     /// Unknown sensor type.
     Unknown,
@@ -118,8 +428,135 @@ pub enum Sensor {
     RightAccelerometer,
 }
 
+/// Side of a Joy-Con in a pair.
+#[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[expect(clippy::exhaustive_enums, reason = "Joy-Cons come in pairs")]
+pub enum JoyConSide {
+    /// Left Joy-Con.
+    Left,
+    /// Right Joy-Con.
+    Right,
+}
+
 impl Sensor {
+    /// All known [`Sensor`]s, i.e. all but [`Sensor::Unknown`].
+    pub const ALL: [Self; 6] = [
+        Self::Gyroscope,
+        Self::LeftGyroscope,
+        Self::RightGyroscope,
+        Self::Accelerometer,
+        Self::LeftAccelerometer,
+        Self::RightAccelerometer,
+    ];
+
+    /// Gets the name of the variant, e.g. `"Gyroscope"`.
+    #[cfg(feature = "sensors")]
+    #[must_use]
+    #[inline]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::Gyroscope => "Gyroscope",
+            Self::LeftGyroscope => "LeftGyroscope",
+            Self::RightGyroscope => "RightGyroscope",
+            Self::Accelerometer => "Accelerometer",
+            Self::LeftAccelerometer => "LeftAccelerometer",
+            Self::RightAccelerometer => "RightAccelerometer",
+        }
+    }
+
+    /// Picks the gyroscope to use among the `available` [`Sensor`]s: the
+    /// combined [`Sensor::Gyroscope`] if present, otherwise the one of the
+    /// `preferred` Joy-Con, otherwise the one of the other Joy-Con.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{JoyConSide::*, Sensor::{self, *}};
+    /// let pick = Sensor::primary_gyroscope;
+    ///
+    /// let all = [Gyroscope, LeftGyroscope, RightGyroscope];
+    /// assert_eq!(pick(&all, Left), Some(Gyroscope));
+    ///
+    /// // paired Joy-Cons
+    /// let pair = [LeftGyroscope, RightGyroscope, LeftAccelerometer];
+    /// assert_eq!(pick(&pair, Left), Some(LeftGyroscope));
+    /// assert_eq!(pick(&pair, Right), Some(RightGyroscope));
+    ///
+    /// // a single Joy-Con
+    /// let left = [LeftGyroscope, LeftAccelerometer];
+    /// assert_eq!(pick(&left, Right), Some(LeftGyroscope));
+    /// let right = [RightGyroscope, RightAccelerometer];
+    /// assert_eq!(pick(&right, Left), Some(RightGyroscope));
+    ///
+    /// assert_eq!(pick(&[Accelerometer], Left), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn primary_gyroscope(
+        available: &[Self],
+        preferred: JoyConSide,
+    ) -> Option<Self> {
+        Self::primary(
+            available,
+            [Self::Gyroscope, Self::LeftGyroscope, Self::RightGyroscope],
+            preferred,
+        )
+    }
+
+    /// Picks the accelerometer to use among the `available` [`Sensor`]s, the
+    /// same way as [`primary_gyroscope`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{JoyConSide::*, Sensor::{self, *}};
+    /// let pair = [LeftAccelerometer, RightAccelerometer];
+    /// assert_eq!(
+    ///     Sensor::primary_accelerometer(&pair, Left),
+    ///     Some(LeftAccelerometer),
+    /// );
+    /// assert_eq!(
+    ///     Sensor::primary_accelerometer(&[Accelerometer, Gyroscope], Left),
+    ///     Some(Accelerometer),
+    /// );
+    /// ```
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    #[must_use]
+    #[inline]
+    pub fn primary_accelerometer(
+        available: &[Self],
+        preferred: JoyConSide,
+    ) -> Option<Self> {
+        Self::primary(
+            available,
+            [
+                Self::Accelerometer,
+                Self::LeftAccelerometer,
+                Self::RightAccelerometer,
+            ],
+            preferred,
+        )
+    }
+
+    /// Picks the combined, `preferred` side or other side [`Sensor`] out of
+    /// `[combined, left, right]`, whichever is `available` first.
+    fn primary(
+        available: &[Self],
+        [combined, left, right]: [Self; 3],
+        preferred: JoyConSide,
+    ) -> Option<Self> {
+        let candidates = match preferred {
+            JoyConSide::Left => [combined, left, right],
+            JoyConSide::Right => [combined, right, left],
+        };
+        candidates.into_iter().find(|sensor| available.contains(sensor))
+    }
+
     /// Converts from [`SdlSensorType`].
+    #[cfg(feature = "sensors")]
     #[must_use]
     #[inline]
     #[expect(clippy::single_call_fn, reason = "extracted conversion")]
@@ -136,9 +573,10 @@ impl Sensor {
     }
 
     /// Converts to [`SdlSensorType`].
+    #[cfg(feature = "sensors")]
     #[must_use]
     #[inline]
-    const fn into_sdl(self) -> SdlSensorType {
+    pub(crate) const fn into_sdl(self) -> SdlSensorType {
         match self {
             Self::Unknown => SdlSensorType::Unknown,
             Self::Gyroscope => SdlSensorType::Gyroscope,
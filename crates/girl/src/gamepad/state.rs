@@ -0,0 +1,242 @@
+//! Snapshot of [`Gamepad`] input state.
+
+#[cfg(feature = "sdl2")]
+use crate::Gamepad;
+use crate::{Button, Stick, Trigger};
+
+/// Snapshot of [`Gamepad`] input state.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+#[expect(clippy::same_name_method, reason = "implements `GamepadRead`")]
+impl Gamepad {
+    /// Takes a [`GamepadState`] snapshot of all the digital and analog inputs
+    /// at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let state = gamepad.state();
+    /// if state.buttons.contains(Button::A) {
+    ///     let [x, y] = state.stick(Stick::Left);
+    ///     // apply movement to a character, etc.
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> GamepadState {
+        GamepadState {
+            connected: self.connected(),
+            buttons: self.buttons(Button::all()),
+            left_stick: self.stick(Stick::Left),
+            right_stick: self.stick(Stick::Right),
+            left_trigger: self.trigger(Trigger::Left),
+            right_trigger: self.trigger(Trigger::Right),
+        }
+    }
+
+    /// Gets the slot of the [`Gamepad`] in [`Girl::snapshot_all`], its
+    /// player index, if any.
+    ///
+    /// [`Girl::snapshot_all`]: crate::Girl::snapshot_all
+    pub(crate) fn player_slot(&self) -> Option<usize> {
+        let index = self.player_index()?;
+        usize::try_from(index).ok()
+    }
+}
+
+/// Snapshot of [`Gamepad`] input state.
+///
+/// Returned by [`Gamepad::state`]. Values follow the same conventions as
+/// [`Gamepad::stick`], [`Gamepad::trigger`] and [`Gamepad::buttons`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct GamepadState {
+    /// Whether the [`Gamepad`] was connected when the snapshot was taken.
+    pub connected: bool,
+    /// Currently pressed [`Button`]s.
+    pub buttons: Button,
+    /// Position of the left [`Stick`] `[x, y]`.
+    pub left_stick: [f64; 2],
+    /// Position of the right [`Stick`] `[x, y]`.
+    pub right_stick: [f64; 2],
+    /// Value of the left [`Trigger`].
+    pub left_trigger: f64,
+    /// Value of the right [`Trigger`].
+    pub right_trigger: f64,
+}
+
+impl GamepadState {
+    /// Gets the position of the given [`Stick`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let state = GamepadState::default();
+    /// assert_eq!(state.stick(Stick::Left), [0.0, 0.0]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn stick(&self, stick: Stick) -> [f64; 2] {
+        match stick {
+            Stick::Left => self.left_stick,
+            Stick::Right => self.right_stick,
+        }
+    }
+
+    /// Gets the value of the given [`Trigger`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let state = GamepadState::default();
+    /// assert_eq!(state.trigger(Trigger::Right), 0.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn trigger(&self, trigger: Trigger) -> f64 {
+        match trigger {
+            Trigger::Left => self.left_trigger,
+            Trigger::Right => self.right_trigger,
+        }
+    }
+
+    /// Merges snapshots of several [`Gamepad`]s into one, the same way as
+    /// [`MergedGamepad`] does: [`Button`]s pressed on any of them, the
+    /// largest deflection of every [`Stick`] axis (see
+    /// [`MergedGamepad::merge_sticks`]) and the largest [`Trigger`] values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut first = GamepadState::default();
+    /// first.connected = true;
+    /// first.buttons = Button::A;
+    /// first.left_stick = [0.9, 0.0];
+    /// first.right_trigger = 0.3;
+    ///
+    /// let mut second = GamepadState::default();
+    /// second.buttons = Button::Start;
+    /// second.left_stick = [-0.1, -0.5];
+    /// second.right_trigger = 0.7;
+    ///
+    /// let merged = GamepadState::merge([first, second]);
+    /// assert!(merged.connected);
+    /// assert_eq!(merged.buttons, Button::A | Button::Start);
+    /// assert_eq!(merged.stick(Stick::Left), [0.9, -0.5]);
+    /// assert_eq!(merged.trigger(Trigger::Right), 0.7);
+    ///
+    /// assert_eq!(GamepadState::merge([]), GamepadState::default());
+    /// ```
+    ///
+    /// [`MergedGamepad`]: crate::MergedGamepad
+    /// [`MergedGamepad::merge_sticks`]: crate::MergedGamepad::merge_sticks
+    #[must_use]
+    #[inline]
+    pub fn merge<I>(states: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        states.into_iter().fold(Self::default(), |merged, state| Self {
+            connected: merged.connected || state.connected,
+            buttons: merged.buttons | state.buttons,
+            left_stick: merge_sticks([merged.left_stick, state.left_stick]),
+            right_stick: merge_sticks([merged.right_stick, state.right_stick]),
+            left_trigger: merged.left_trigger.max(state.left_trigger),
+            right_trigger: merged.right_trigger.max(state.right_trigger),
+        })
+    }
+
+    /// Checks if all specified [`Button`]\(s) were pressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut state = GamepadState::default();
+    /// state.buttons = Button::A | Button::B;
+    ///
+    /// assert!(state.buttons_pressed(Button::A));
+    /// assert!(!state.buttons_pressed(Button::A | Button::X));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn buttons_pressed(&self, buttons: Button) -> bool {
+        self.buttons.contains(buttons)
+    }
+}
+
+/// Merges [`Stick`] positions by taking the value with the largest
+/// magnitude of every axis separately, see [`MergedGamepad::merge_sticks`].
+///
+/// [`MergedGamepad::merge_sticks`]: crate::MergedGamepad::merge_sticks
+pub(crate) fn merge_sticks<I>(positions: I) -> [f64; 2]
+where
+    I: IntoIterator<Item = [f64; 2]>,
+{
+    positions.into_iter().fold([0.0, 0.0], |[x, y], [px, py]| {
+        [
+            if px.abs() > x.abs() { px } else { x },
+            if py.abs() > y.abs() { py } else { y },
+        ]
+    })
+}
+
+/// Fills `out` with the [`GamepadState`]s of `pads` by slot, see
+/// [`Girl::snapshot_all`], returning how many were written.
+///
+/// Each pad goes to its own `slot` if it has one and it is within `out`,
+/// pads without one to the lowest free slots in order, until `out` is
+/// full. Pads without a `state` are skipped, leaving their slots free.
+///
+/// [`Girl::snapshot_all`]: crate::Girl::snapshot_all
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+#[cfg_attr(
+    not(all(feature = "sdl2", feature = "sim")),
+    expect(clippy::single_call_fn, reason = "shared with `SimGirl`")
+)]
+pub(crate) fn fill_slots<'pad, T: 'pad>(
+    out: &mut [Option<GamepadState>],
+    pads: impl Iterator<Item = &'pad T> + Clone,
+    slot: impl Fn(&T) -> Option<usize>,
+    state: impl Fn(&T) -> Option<GamepadState>,
+) -> usize {
+    out.fill(None);
+    let mut written: usize = 0;
+    for pad in pads.clone() {
+        if let Some(entry) = slot(pad).and_then(|index| out.get_mut(index))
+            && entry.is_none()
+        {
+            *entry = state(pad);
+            written = written.saturating_add(usize::from(entry.is_some()));
+        }
+    }
+
+    let mut free = 0;
+    for pad in pads.filter(|pad| slot(pad).is_none()) {
+        let Some(snapshot) = state(pad) else {
+            continue;
+        };
+        while out.get(free).is_some_and(Option::is_some) {
+            free = free.saturating_add(1);
+        }
+        let Some(entry) = out.get_mut(free) else {
+            break;
+        };
+        *entry = Some(snapshot);
+        written = written.saturating_add(1);
+    }
+    written
+}
@@ -0,0 +1,279 @@
+//! Methods of the capability features disabled at build time, compiled
+//! with the `stubs` feature, see [`Gamepad::supports`].
+//!
+//! [`Gamepad::supports`]: crate::Gamepad::supports
+
+#[cfg(not(feature = "rumble"))]
+use core::time::Duration;
+
+#[cfg(any(
+    not(feature = "rumble"),
+    not(feature = "sensors"),
+    not(feature = "touchpad")
+))]
+use crate::Error;
+use crate::Gamepad;
+#[cfg(not(feature = "sensors"))]
+use crate::Sensor;
+#[cfg(not(feature = "touchpad"))]
+use crate::TouchpadState;
+
+/// Stubs of the methods of disabled capability features, reporting the
+/// capability as missing and failing with [`Error::FeatureDisabled`], so
+/// code shared across builds compiles regardless of the features.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{Capability, prelude::*};
+/// fn disabled<T>(result: Result<T, Error>, name: &str) -> bool {
+///     let Err(Error::FeatureDisabled { feature }) = result else {
+///         return false;
+///     };
+///     feature == name
+/// }
+///
+/// let mut girl = girl::Girl::new()?;
+/// # if girl.gamepad(0).is_some() {
+/// let mut gamepad = girl.gamepad(0).unwrap();
+///
+/// # #[cfg(not(feature = "rumble"))]
+/// # {
+/// let effect = Duration::from_millis(100);
+/// assert!(!gamepad.supports(Capability::Rumble));
+/// assert!(disabled(gamepad.set_rumble(1000, 1000, effect), "rumble"));
+/// assert!(disabled(gamepad.set_rumble_triggers(1, 1, effect), "rumble"));
+/// assert!(disabled(gamepad.end_rumble(), "rumble"));
+/// # }
+/// # #[cfg(not(feature = "sensors"))]
+/// # {
+/// assert!(!gamepad.supports(Capability::Sensors));
+/// assert!(gamepad.available_sensors().is_empty());
+/// let gyro = Sensor::Gyroscope;
+/// assert!(disabled(gamepad.enable_sensor(gyro), "sensors"));
+/// assert!(disabled(gamepad.sensor(gyro), "sensors"));
+/// # }
+/// # #[cfg(not(feature = "touchpad"))]
+/// # {
+/// assert!(!gamepad.supports(Capability::Touchpad));
+/// assert_eq!(gamepad.num_touchpads(), 0);
+/// assert!(disabled(gamepad.touchpad(), "touchpad"));
+/// assert!(disabled(gamepad.touchpad_state(0), "touchpad"));
+/// # }
+/// # }
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Error::FeatureDisabled`]: crate::Error::FeatureDisabled
+#[cfg_attr(docsrs, doc(cfg(feature = "stubs")))]
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(
+    clippy::multiple_inherent_impl,
+    reason = "feature gated and documented"
+)]
+impl Gamepad {
+    /// Query whether the [`Gamepad`] has rumble support, never without the
+    /// `rumble` feature.
+    #[cfg(not(feature = "rumble"))]
+    #[must_use]
+    #[inline]
+    pub const fn has_rumble(&self) -> bool {
+        false
+    }
+
+    /// Stub of rumble, needing the `rumble` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "rumble"))]
+    #[inline]
+    pub const fn set_rumble(
+        &mut self,
+        _low_frequency_rumble: u16,
+        _high_frequency_rumble: u16,
+        _duration: Duration,
+    ) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "rumble" })
+    }
+
+    /// Gets the time left until the rumble effect ends, never any without
+    /// the `rumble` feature.
+    #[cfg(not(feature = "rumble"))]
+    #[must_use]
+    #[inline]
+    pub const fn rumble_remaining(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Stub of stopping rumble, needing the `rumble` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "rumble"))]
+    #[inline]
+    pub const fn end_rumble(&mut self) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "rumble" })
+    }
+
+    /// Query whether the gamepad has trigger rumble support, never without
+    /// the `rumble` feature.
+    #[cfg(not(feature = "rumble"))]
+    #[must_use]
+    #[inline]
+    pub const fn has_rumble_triggers(&self) -> bool {
+        false
+    }
+
+    /// Stub of trigger rumble, needing the `rumble` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "rumble"))]
+    #[inline]
+    pub const fn set_rumble_triggers(
+        &mut self,
+        _left_trigger_rumble: u16,
+        _right_trigger_rumble: u16,
+        _duration: Duration,
+    ) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "rumble" })
+    }
+
+    /// Stub of stopping trigger rumble, needing the `rumble` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "rumble"))]
+    #[inline]
+    pub const fn end_rumble_triggers(&mut self) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "rumble" })
+    }
+
+    /// Query whether the gamepad has a specific sensor, never without the
+    /// `sensors` feature.
+    #[cfg(not(feature = "sensors"))]
+    #[must_use]
+    #[inline]
+    pub const fn has_sensor(&self, _sensor_type: Sensor) -> bool {
+        false
+    }
+
+    /// Lists the [`Sensor`]s the [`Gamepad`] has, none without the
+    /// `sensors` feature.
+    #[cfg(not(feature = "sensors"))]
+    #[must_use]
+    #[inline]
+    pub const fn available_sensors(&self) -> Vec<Sensor> {
+        Vec::new()
+    }
+
+    /// Picks the gyroscope to use, never any without the `sensors`
+    /// feature.
+    #[cfg(not(feature = "sensors"))]
+    #[must_use]
+    #[inline]
+    pub const fn primary_gyroscope(&self) -> Option<Sensor> {
+        None
+    }
+
+    /// Picks the accelerometer to use, never any without the `sensors`
+    /// feature.
+    #[cfg(not(feature = "sensors"))]
+    #[must_use]
+    #[inline]
+    pub const fn primary_accelerometer(&self) -> Option<Sensor> {
+        None
+    }
+
+    /// Stub of enabling a [`Sensor`], needing the `sensors` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "sensors"))]
+    #[inline]
+    pub const fn enable_sensor(&self, _sensor: Sensor) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "sensors" })
+    }
+
+    /// Stub of disabling a [`Sensor`], needing the `sensors` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "sensors"))]
+    #[inline]
+    pub const fn disable_sensor(&self, _sensor: Sensor) -> Result<(), Error> {
+        Err(Error::FeatureDisabled { feature: "sensors" })
+    }
+
+    /// Checks if a [`Sensor`] is enabled, never without the `sensors`
+    /// feature.
+    #[cfg(not(feature = "sensors"))]
+    #[must_use]
+    #[inline]
+    pub const fn sensor_enabled(&self, _sensor: Sensor) -> bool {
+        false
+    }
+
+    /// Stub of reading a [`Sensor`], needing the `sensors` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "sensors"))]
+    #[inline]
+    pub const fn sensor(&self, _sensor: Sensor) -> Result<[f64; 3], Error> {
+        Err(Error::FeatureDisabled { feature: "sensors" })
+    }
+
+    /// Checks if the [`Gamepad`] has any touchpads, never without the
+    /// `touchpad` feature.
+    #[cfg(not(feature = "touchpad"))]
+    #[must_use]
+    #[inline]
+    pub const fn has_touchpads(&self) -> bool {
+        false
+    }
+
+    /// Gets the number of touchpads on the [`Gamepad`], none without the
+    /// `touchpad` feature.
+    #[cfg(not(feature = "touchpad"))]
+    #[must_use]
+    #[inline]
+    pub const fn num_touchpads(&self) -> usize {
+        0
+    }
+
+    /// Stub of reading the fingers on one touchpad, needing the `touchpad`
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "touchpad"))]
+    #[inline]
+    pub const fn touchpad_state(
+        &self,
+        _touchpad: usize,
+    ) -> Result<&[TouchpadState], Error> {
+        Err(Error::FeatureDisabled { feature: "touchpad" })
+    }
+
+    /// Stub of reading the touchpads, needing the `touchpad` feature.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::FeatureDisabled`].
+    #[cfg(not(feature = "touchpad"))]
+    #[inline]
+    pub const fn touchpad(&mut self) -> Result<Vec<TouchpadState>, Error> {
+        Err(Error::FeatureDisabled { feature: "touchpad" })
+    }
+}
@@ -1,10 +1,20 @@
 //! Touchpad data for a [`Gamepad`].
 
+#[cfg(feature = "touchpad")]
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::{cmp, fmt, time::Duration};
+
+#[cfg(feature = "touchpad")]
 use sdl2::{event::Event as SdlEvent, sys as sdl2_sys};
 
-use crate::{Error, Gamepad};
+#[cfg(feature = "touchpad")]
+use crate::{
+    ControllerType, Error, Gamepad, TouchRejection, TouchRejector,
+    logging::diag,
+};
 
 /// SDL2 released state constant.
+#[cfg(feature = "touchpad")]
 #[expect(
     clippy::cast_possible_truncation,
     reason = "these constants should've been `Uint8` in the first place"
@@ -12,6 +22,7 @@ use crate::{Error, Gamepad};
 const RELEASED: u8 = sdl2_sys::SDL_RELEASED as u8;
 
 /// SDL2 pressed state constant.
+#[cfg(feature = "touchpad")]
 #[expect(
     clippy::cast_possible_truncation,
     reason = "these constants should've been `Uint8` in the first place"
@@ -19,6 +30,7 @@ const RELEASED: u8 = sdl2_sys::SDL_RELEASED as u8;
 const PRESSED: u8 = sdl2_sys::SDL_PRESSED as u8;
 
 /// Touchpad data for a [`Gamepad`].
+#[cfg(feature = "touchpad")]
 #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
 // TODO: Try remove on next Rust version update.
 #[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
@@ -27,11 +39,134 @@ const PRESSED: u8 = sdl2_sys::SDL_PRESSED as u8;
     reason = "feature gated and documented"
 )]
 impl Gamepad {
-    /// Query whether the [`Gamepad`] has touchpads.
+    /// Checks if the [`Gamepad`] has any touchpads.
+    #[must_use]
+    #[inline]
+    pub const fn has_touchpads(&self) -> bool {
+        self.touchpads.num_touchpads() != 0
+    }
+
+    /// Gets the number of touchpads on the [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub const fn num_touchpads(&self) -> usize {
+        self.touchpads.num_touchpads()
+    }
+
+    /// Gets the physical size `[width, height]` of the given `touchpad`, in
+    /// millimeters, if known.
+    ///
+    /// SDL2 doesn't report touchpad dimensions, so the size is looked up by
+    /// the [`ControllerType`] in the [`TouchpadSizes`], see
+    /// [`set_touchpad_sizes`]. Returns [`None`] if the size is unknown or
+    /// `touchpad` is not less than [`num_touchpads`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Some(size) = gamepad.touchpad_physical_size(0) {
+    ///     for finger in gamepad.touchpad()? {
+    ///         let [x, y] = finger.position_mm(size);
+    ///         println!("finger {} at {x:.1} mm, {y:.1} mm", finger.finger);
+    ///     }
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_touchpad_sizes`]: Self::set_touchpad_sizes
+    /// [`num_touchpads`]: Self::num_touchpads
     #[must_use]
     #[inline]
-    pub const fn has_touchpads(&self) -> usize {
-        self.touchpads.len()
+    pub fn touchpad_physical_size(&self, touchpad: usize) -> Option<[f32; 2]> {
+        if touchpad >= self.num_touchpads() {
+            return None;
+        }
+        self.touchpad_sizes.get(self.controller_type())
+    }
+
+    /// Gets the [`TouchpadSizes`] used by [`touchpad_physical_size`].
+    ///
+    /// [`touchpad_physical_size`]: Self::touchpad_physical_size
+    #[must_use]
+    #[inline]
+    pub const fn touchpad_sizes(&self) -> &TouchpadSizes {
+        &self.touchpad_sizes
+    }
+
+    /// Sets the [`TouchpadSizes`] used by [`touchpad_physical_size`],
+    /// overriding the built-in ones, see [`TouchpadSizes::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{TouchpadSizes, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let mut sizes = TouchpadSizes::new();
+    /// sizes.set(ControllerType::Ps5, Some([59.0, 27.5]));
+    /// gamepad.set_touchpad_sizes(sizes);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`touchpad_physical_size`]: Self::touchpad_physical_size
+    #[inline]
+    pub fn set_touchpad_sizes(&mut self, sizes: TouchpadSizes) {
+        self.touchpad_sizes = sizes;
+    }
+
+    /// Gets the cached [`TouchpadState`]s of every finger on the given
+    /// `touchpad`, as of the last [`touchpad`] call, without querying the
+    /// [`Gamepad`].
+    ///
+    /// Unlike [`touchpad`], which reports changes, the cached states describe
+    /// fingers as they are: [`TouchpadAction::Touched`] or
+    /// [`TouchpadAction::Released`], never [`TouchpadAction::Moved`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `touchpad` is not less than
+    /// [`num_touchpads`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// _ = gamepad.touchpad()?;
+    /// for touchpad in 0..gamepad.num_touchpads() {
+    ///     let touching = gamepad
+    ///         .touchpad_state(touchpad)?
+    ///         .iter()
+    ///         .filter(|finger| finger.action == TouchpadAction::Touched)
+    ///         .count();
+    ///     println!("{touching} finger(s) on touchpad {touchpad}");
+    /// }
+    /// assert!(gamepad.touchpad_state(gamepad.num_touchpads()).is_err());
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`touchpad`]: Self::touchpad
+    /// [`num_touchpads`]: Self::num_touchpads
+    #[inline]
+    pub fn touchpad_state(
+        &self,
+        touchpad: usize,
+    ) -> Result<&[TouchpadState], Error> {
+        self.touchpads
+            .fingers(touchpad)
+            .ok_or(Error::InvalidArgument { what: "touchpad" })
     }
 
     /// Gets the current [`TouchpadState`]\(s).
@@ -49,9 +184,15 @@ impl Gamepad {
     ///
     /// If no touchpads are touched, returns an empty [`Vec`].
     ///
+    /// Unless the [`Girl`] is eventless (see [`Girl::new_eventless`]), the
+    /// fingers are tracked from the touchpad [`Event`]s as [`Girl::update`]
+    /// pumps them, so no FFI calls are made. Otherwise, every finger is
+    /// queried from the [`Gamepad`].
+    ///
     /// # Errors
     ///
-    /// Returns an [`Error`] if the [`Gamepad`] is no longer valid.
+    /// Returns an [`Error`] if the [`Gamepad`] is no longer valid, only
+    /// checked in eventless mode.
     ///
     /// # Examples
     ///
@@ -60,7 +201,7 @@ impl Gamepad {
     /// # if girl.gamepad(0).is_some() {
     /// let mut gamepad = girl.gamepad(0).unwrap();
     ///
-    /// if gamepad.has_touchpads() > 0 {
+    /// if gamepad.has_touchpads() {
     ///     let touchpads = gamepad.touchpad()?;
     ///     for touchpad in touchpads {
     ///         // do something with touchpad state values
@@ -75,115 +216,55 @@ impl Gamepad {
     /// # }
     /// # Ok::<(), girl::Error>(())
     /// ```
+    ///
+    /// [`Girl`]: crate::Girl
+    /// [`Girl::new_eventless`]: crate::Girl::new_eventless
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Event`]: crate::Event
     #[inline]
     pub fn touchpad(&mut self) -> Result<Vec<TouchpadState>, Error> {
-        let raw = self.raw()?;
-
-        let mut states = vec![];
-
-        for (touchpad_idx, touchpad) in self.touchpads.iter_mut().enumerate() {
-            for (finger_idx, prev) in touchpad.iter_mut().enumerate() {
-                use self::TouchpadAction as TA;
-
-                #[expect(
-                    clippy::cast_possible_truncation,
-                    clippy::cast_possible_wrap,
-                    reason = "ok to cast"
-                )]
-                let (finger, idx) = (finger_idx as i32, touchpad_idx as i32);
-
-                let mut position = [0.0, 0.0];
-                let mut pressure = 0.0;
-                let mut state = 0;
-
-                // SAFETY: SDL2 is still alive, all the pointers are valid.
-                #[expect(unsafe_code, reason = "ffi with sdl2")]
-                let res = unsafe {
-                    sdl2_sys::SDL_GameControllerGetTouchpadFinger(
-                        raw,
-                        idx,
-                        finger,
-                        &raw mut state,
-                        &raw mut position[0],
-                        &raw mut position[1],
-                        &raw mut pressure,
-                    )
-                };
-
-                if res != 0i32 {
-                    continue;
-                }
+        if self.suspended_for_query() {
+            return Ok(vec![]);
+        }
 
-                let action = match state {
-                    RELEASED => TA::Released,
-                    PRESSED => TA::Touched,
-                    _ => unreachable!("unknown touchpad state: {state}"),
-                };
-
-                let event_type = if action == prev.action {
-                    // only report the first release event
-                    if action == TA::Released {
-                        continue;
-                    }
-
-                    // don't report the same event twice
-                    #[expect(
-                        clippy::float_cmp,
-                        reason = "want this to be the same as the sdl2 logic"
-                    )]
-                    if position == prev.position && pressure == prev.pressure {
-                        continue;
-                    }
-
-                    // otherwise, report the repeated touch as a move event
-                    TA::Moved
-                } else if action == TA::Touched {
-                    TA::Touched
-                } else {
-                    TA::Released
-                };
-
-                prev.action = action;
-                prev.position = position;
-                prev.pressure = pressure;
-
-                states.push(TouchpadState {
-                    touchpad: touchpad_idx,
-                    finger: finger_idx,
-                    position,
-                    pressure,
-                    action: event_type,
-                });
-            }
+        if self.shared().event_driven() {
+            let id = self.id();
+            let shared = Arc::clone(self.shared());
+            let snapshots = shared.touchpads();
+            let changes = self.touchpad_changes(|touchpad, finger| {
+                snapshots.get(&id)?.finger(touchpad, finger)
+            });
+            drop(snapshots);
+            return Ok(changes);
         }
 
-        Ok(states)
+        let raw = self.raw("touchpad")?;
+        let shared = Arc::clone(self.shared());
+        Ok(self.touchpad_changes(|touchpad, finger| {
+            shared.count_ffi_call();
+            poll_finger(raw, touchpad, finger)
+        }))
     }
 
-    /// Gets the raw SDL game controller pointer.
+    /// Updates the cached [`TouchpadState`]s with the current finger states
+    /// from `sample`, returning the changes, see [`TouchpadState::update`].
     ///
-    /// # Errors
+    /// Samples withheld by the [`TouchRejection`] of their touchpad aren't
+    /// cached, see [`set_touchpad_rejection`].
     ///
-    /// Returns an error if the controller is no longer valid.
-    #[inline]
-    fn raw(&self) -> Result<*mut sdl2_sys::SDL_GameController, Error> {
-        #[expect(
-            clippy::cast_possible_wrap,
-            reason = "it was just cast from i32 to u32 by sdl2 crate, we're \
-                      casting it back"
-        )]
-        let id = self.gp.instance_id() as i32;
-
-        // SAFETY: SDL is alive, `id` is valid, and SDL handles any errors,
-        //         return value is checked for null.
-        #[expect(unsafe_code, reason = "ffi with sdl2")]
-        let res = unsafe { sdl2_sys::SDL_GameControllerFromInstanceID(id) };
-
-        if res.is_null() {
-            Err(Error::SdlError(sdl2::get_error()))
-        } else {
-            Ok(res)
-        }
+    /// [`set_touchpad_rejection`]: Self::set_touchpad_rejection
+    fn touchpad_changes<F>(&mut self, sample: F) -> Vec<TouchpadState>
+    where
+        F: FnMut(usize, usize) -> Option<TouchpadState>,
+    {
+        let shared = Arc::clone(self.shared());
+        let rejections = shared.touch_rejection();
+        let disabled = TouchRejection::disabled();
+        let id = self.id;
+        self.touchpads.changes(
+            |touchpad| rejections.get(&(id, touchpad)).unwrap_or(&disabled),
+            sample,
+        )
     }
 
     /// Creates touchpad state storage.
@@ -192,10 +273,8 @@ impl Gamepad {
     ///
     /// Returns an error if the controller is no longer valid.
     #[inline]
-    pub(crate) fn touchpads_init(
-        &self,
-    ) -> Result<Vec<Vec<TouchpadState>>, Error> {
-        let raw = self.raw()?;
+    pub(crate) fn touchpads_init(&self) -> Result<TouchpadTracker, Error> {
+        let raw = self.raw("touchpads_init")?;
 
         // SAFETY: SDL is alive, pointer is valid
         #[expect(unsafe_code, reason = "ffi with sdl2")]
@@ -221,20 +300,508 @@ impl Gamepad {
         )]
         let fingers = if fingers < 0i32 { 0 } else { fingers as usize };
 
-        Ok(vec![vec![TouchpadState::default(); fingers]; touchpads])
+        Ok(TouchpadTracker::new(touchpads, fingers))
+    }
+}
+
+/// Cached [`TouchpadState`]s of every finger on the touchpads of a
+/// [`Gamepad`], reporting their changes, see [`Gamepad::touchpad`].
+///
+/// Every finger is updated with [`TouchpadState::update`], after the
+/// [`TouchRejector`] of the [`Gamepad`] admits its sample, see
+/// [`Gamepad::set_touchpad_rejection`]. The changes are then given a
+/// stable [`FingerId`] by a [`FingerTracker`], which follows fingers SDL2
+/// moves to another slot. Samples are plain [`TouchpadState`]s, so the
+/// tracker can be driven without a controller.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchRejection, TouchpadTracker, prelude::*};
+/// use TouchpadAction as TA;
+///
+/// let mut tracker = TouchpadTracker::new(1, 2);
+/// let disabled = TouchRejection::disabled();
+/// let mut step = |samples: &[(usize, [f32; 2], TA)]| -> Vec<_> {
+///     tracker
+///         .changes(
+///             |_| &disabled,
+///             |touchpad, finger| {
+///                 let &(_, position, action) =
+///                     samples.iter().find(|sample| sample.0 == finger)?;
+///                 Some(TouchpadState::new(
+///                     touchpad, finger, position, 1.0, action,
+///                 ))
+///             },
+///         )
+///         .into_iter()
+///         .map(|change| (change.finger, change.action))
+///         .collect()
+/// };
+///
+/// // untouched fingers report nothing
+/// assert!(step(&[(0, [0.0; 2], TA::Released)]).is_empty());
+/// // a touch is reported once, then only when it moves
+/// assert_eq!(step(&[(0, [0.5; 2], TA::Touched)]), [(0, TA::Touched)]);
+/// assert!(step(&[(0, [0.5; 2], TA::Touched)]).is_empty());
+/// assert_eq!(step(&[(0, [0.6; 2], TA::Moved)]), [(0, TA::Moved)]);
+/// // fingers are tracked separately, in index order
+/// assert_eq!(
+///     step(&[(1, [0.2; 2], TA::Touched), (0, [0.7; 2], TA::Moved)]),
+///     [(0, TA::Moved), (1, TA::Touched),]
+/// );
+/// // a release is reported once, without a move to its position
+/// assert_eq!(step(&[(0, [0.9; 2], TA::Released)]), [(0, TA::Released)]);
+/// assert!(step(&[(0, [0.9; 2], TA::Released)]).is_empty());
+/// // missing samples leave fingers as they are
+/// assert!(step(&[]).is_empty());
+/// assert_eq!(tracker.fingers(0).unwrap()[1].action, TA::Touched);
+/// assert!(tracker.fingers(1).is_none());
+/// ```
+///
+/// Touches withheld by a [`TouchRejection`] are never cached:
+///
+/// ```
+/// # use girl::{TouchRejection, TouchpadTracker, prelude::*};
+/// use TouchpadAction as TA;
+///
+/// let mut tracker = TouchpadTracker::new(1, 1);
+/// let rejection = TouchRejection::new();
+/// let corner = |action| TouchpadState::new(0, 0, [0.05, 0.9], 1.0, action);
+/// for action in [TA::Touched, TA::Moved, TA::Released] {
+///     let changes =
+///         tracker.changes(|_| &rejection, |_, _| Some(corner(action)));
+///     assert!(changes.is_empty());
+/// }
+/// assert_eq!(tracker.fingers(0).unwrap()[0].action, TA::Released);
+/// ```
+///
+/// Lifting the first of two fingers, after which SDL2 reports the other
+/// one in the first slot, only releases the lifted finger:
+///
+/// ```
+/// # use girl::{TouchRejection, TouchpadTracker, prelude::*};
+/// use TouchpadAction as TA;
+///
+/// let mut tracker = TouchpadTracker::new(1, 2);
+/// let disabled = TouchRejection::disabled();
+/// let mut step = |samples: [([f32; 2], TA); 2]| -> Vec<_> {
+///     tracker
+///         .changes(
+///             |_| &disabled,
+///             |touchpad, finger| {
+///                 let (position, action) = samples[finger];
+///                 Some(TouchpadState::new(
+///                     touchpad, finger, position, 1.0, action,
+///                 ))
+///             },
+///         )
+///         .into_iter()
+///         .map(|change| (change.finger_id, change.action))
+///         .collect()
+/// };
+///
+/// let (left, right) = ([0.2, 0.5], [0.8, 0.5]);
+/// let both = step([(left, TA::Touched), (right, TA::Touched)]);
+/// let [(first, TA::Touched), (second, TA::Touched)] = both[..] else {
+///     panic!("not touched: {both:?}");
+/// };
+/// assert_ne!(first, second);
+///
+/// // the second finger moves to the first slot
+/// let lifted = step([(right, TA::Touched), (right, TA::Released)]);
+/// assert_eq!(lifted, [(first, TA::Released)]);
+/// let moved = step([([0.7, 0.5], TA::Touched), (right, TA::Released)]);
+/// assert_eq!(moved, [(second, TA::Moved)]);
+/// assert_eq!(tracker.fingers(0).unwrap()[0].finger_id, second);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::set_touchpad_rejection`]:
+///     crate::Gamepad::set_touchpad_rejection
+#[cfg(feature = "touchpad")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchpadTracker {
+    /// Cached state of every finger, by touchpad and finger index.
+    fingers: Vec<Vec<TouchpadState>>,
+    /// Withheld touches.
+    rejector: TouchRejector,
+    /// Identities of the touching fingers.
+    identities: FingerTracker,
+}
+
+#[cfg(feature = "touchpad")]
+impl TouchpadTracker {
+    /// Creates a [`TouchpadTracker`] of `touchpads` touchpads with
+    /// `fingers` fingers each, none of them touching.
+    #[must_use]
+    #[inline]
+    pub fn new(touchpads: usize, fingers: usize) -> Self {
+        let fingers = (0..touchpads)
+            .map(|touchpad| {
+                (0..fingers)
+                    .map(|finger| TouchpadState {
+                        touchpad,
+                        finger,
+                        ..TouchpadState::default()
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            fingers,
+            rejector: TouchRejector::new(),
+            identities: FingerTracker::new(),
+        }
+    }
+
+    /// Gets the number of touchpads.
+    #[must_use]
+    #[inline]
+    pub const fn num_touchpads(&self) -> usize {
+        self.fingers.len()
+    }
+
+    /// Gets the cached [`TouchpadState`]s of every finger on the given
+    /// `touchpad`, [`None`] if it is not less than [`num_touchpads`].
+    ///
+    /// [`num_touchpads`]: Self::num_touchpads
+    #[must_use]
+    #[inline]
+    pub fn fingers(&self, touchpad: usize) -> Option<&[TouchpadState]> {
+        self.fingers.get(touchpad).map(Vec::as_slice)
+    }
+
+    /// Updates the cached [`TouchpadState`]s with the current finger states
+    /// from `sample`, by touchpad and finger index, returning the changes
+    /// in index order, see [`TouchpadState::update`], with their
+    /// [`FingerId`]s, see [`FingerTracker::identify`].
+    ///
+    /// Fingers without a sample are left as they are, and samples withheld
+    /// by the [`TouchRejection`] of their touchpad aren't cached.
+    #[inline]
+    pub fn changes<'rejection, R, F>(
+        &mut self,
+        mut rejection: R,
+        mut sample: F,
+    ) -> Vec<TouchpadState>
+    where
+        R: FnMut(usize) -> &'rejection TouchRejection,
+        F: FnMut(usize, usize) -> Option<TouchpadState>,
+    {
+        let mut changes = vec![];
+        for (touchpad_idx, touchpad) in self.fingers.iter_mut().enumerate() {
+            let touch_rejection = rejection(touchpad_idx);
+            for (finger_idx, prev) in touchpad.iter_mut().enumerate() {
+                if let Some(current) = sample(touchpad_idx, finger_idx)
+                    && self.rejector.admit_state(
+                        touch_rejection,
+                        prev,
+                        &current,
+                    )
+                    && let Some(change) = prev.update(&current)
+                {
+                    changes.extend(self.identities.identify(change));
+                }
+            }
+            for (finger_idx, cached) in touchpad.iter_mut().enumerate() {
+                if let Some(id) =
+                    self.identities.finger(touchpad_idx, finger_idx)
+                {
+                    cached.finger_id = id;
+                }
+            }
+        }
+        changes
+    }
+}
+
+/// Stable identity of a finger on a touchpad, from touching it until
+/// released, see `FingerTracker` with the `touchpad` feature.
+///
+/// Unlike the finger index, i.e. the slot SDL2 reports the finger in, it
+/// stays the same when SDL2 moves the finger to another slot, so
+/// gestures can follow fingers by it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerId(u32);
+
+impl FingerId {
+    /// Creates a [`FingerId`] from its raw value.
+    #[must_use]
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// Gets the raw value.
+    #[must_use]
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Gives the fingers on the touchpads of a [`Gamepad`] [`FingerId`]s
+/// which don't change when SDL2 moves them to another slot.
+///
+/// Touchpads report a fixed number of slots, and when the first of two
+/// fingers is lifted, the controller may report the remaining finger in
+/// the freed slot. By slot, that looks like the first finger jumping to
+/// the second one, which is then released. A finger in a slot that jumps
+/// farther than [`SHIFT_DISTANCE`] to within it of a finger in another
+/// slot is taken for that finger instead: the finger of the slot is
+/// released, the other one moves to the slot, and the release of its old
+/// slot is dropped. Fingers only ever move to lower slots, which SDL2
+/// reports first.
+///
+/// A [`FingerId`] is assigned when a finger touches, and kept until it is
+/// released. Used by [`TouchpadTracker`] and by [`Girl`] for
+/// [`Event::ControllerTouchpad`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{FingerTracker, prelude::*};
+/// use TouchpadAction as TA;
+///
+/// let mut fingers = FingerTracker::new();
+/// let mut replay = |slot, x, action| -> Vec<_> {
+///     let change = TouchpadState::new(0, slot, [x, 0.5], 1.0, action);
+///     fingers
+///         .identify(change)
+///         .into_iter()
+///         .map(|finger| (finger.finger_id, finger.finger, finger.action))
+///         .collect()
+/// };
+///
+/// // two fingers touch
+/// let [(first, 0, TA::Touched)] = replay(0, 0.2, TA::Touched)[..] else {
+///     panic!("first not touched");
+/// };
+/// let [(second, 1, TA::Touched)] = replay(1, 0.8, TA::Touched)[..] else {
+///     panic!("second not touched");
+/// };
+/// assert_ne!(first, second);
+///
+/// // the first one is lifted, and the second one moves to its slot
+/// assert_eq!(replay(0, 0.8, TA::Moved), [(first, 0, TA::Released)]);
+/// assert!(replay(1, 0.8, TA::Released).is_empty());
+///
+/// // the survivor keeps moving, and is released as itself
+/// assert_eq!(replay(0, 0.75, TA::Moved), [(second, 0, TA::Moved)]);
+/// assert_eq!(replay(0, 0.75, TA::Released), [(second, 0, TA::Released)]);
+///
+/// // a fast swipe isn't mistaken for a shift without another finger near
+/// let [(third, 0, TA::Touched)] = replay(0, 0.1, TA::Touched)[..] else {
+///     panic!("third not touched");
+/// };
+/// assert!(third != first && third != second);
+/// let [(fourth, 1, TA::Touched)] = replay(1, 0.3, TA::Touched)[..] else {
+///     panic!("fourth not touched");
+/// };
+/// assert_eq!(replay(0, 0.9, TA::Moved), [(third, 0, TA::Moved)]);
+/// assert_eq!(fingers.finger(0, 0), Some(third));
+/// assert_eq!(fingers.finger(0, 1), Some(fourth));
+/// assert_eq!(fingers.finger(1, 0), None);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`SHIFT_DISTANCE`]: Self::SHIFT_DISTANCE
+/// [`Girl`]: crate::Girl
+/// [`Event::ControllerTouchpad`]: crate::Event::ControllerTouchpad
+#[cfg(feature = "touchpad")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FingerTracker {
+    /// Fingers of the slots, by touchpad and finger index.
+    slots: BTreeMap<(usize, usize), Slot>,
+    /// Raw value of the last assigned [`FingerId`].
+    last: u32,
+}
+
+/// Finger in a slot of a touchpad, tracked by [`FingerTracker`].
+#[cfg(feature = "touchpad")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Slot {
+    /// Identity of the finger.
+    id: FingerId,
+    /// Last position of the finger.
+    position: [f32; 2],
+    /// Whether the finger moved to another slot, so the release of this
+    /// one is dropped.
+    vacated: bool,
+}
+
+#[cfg(feature = "touchpad")]
+impl FingerTracker {
+    /// Largest normalized distance between a finger and the slot it moves
+    /// to for the move to be taken for a slot shift.
+    pub const SHIFT_DISTANCE: f32 = 0.1;
+
+    /// Creates a [`FingerTracker`] without fingers.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { slots: BTreeMap::new(), last: 0 }
+    }
+
+    /// Gets the [`FingerId`] of the finger touching the slot `finger` of
+    /// the given `touchpad`, if any.
+    #[must_use]
+    #[inline]
+    pub fn finger(&self, touchpad: usize, finger: usize) -> Option<FingerId> {
+        self.slots
+            .get(&(touchpad, finger))
+            .filter(|slot| !slot.vacated)
+            .map(|slot| slot.id)
+    }
+
+    /// Identifies the finger of the `change` of a slot, returning the
+    /// changes to report with their [`FingerId`]s: none for the release
+    /// of a slot whose finger moved to another one, and a release of the
+    /// finger of the slot before the move of the finger moving into it.
+    #[inline]
+    pub fn identify(&mut self, change: TouchpadState) -> Vec<TouchpadState> {
+        let key = (change.touchpad, change.finger);
+        let tracked = self.slots.get(&key).filter(|slot| !slot.vacated);
+        let current = match (change.action, tracked.copied()) {
+            (TouchpadAction::Released, _) => {
+                let released = self.slots.remove(&key);
+                if released.is_some_and(|slot| slot.vacated) {
+                    return vec![];
+                }
+                let finger_id =
+                    released.map_or(change.finger_id, |slot| slot.id);
+                return vec![TouchpadState { finger_id, ..change }];
+            }
+            (TouchpadAction::Touched, _) | (TouchpadAction::Moved, None) => {
+                let id = self.assign();
+                let slot =
+                    Slot { id, position: change.position, vacated: false };
+                _ = self.slots.insert(key, slot);
+                let action = TouchpadAction::Touched;
+                return vec![TouchpadState { finger_id: id, action, ..change }];
+            }
+            (TouchpadAction::Moved, Some(current)) => current,
+        };
+
+        let Some((from, shifted)) = self.shifted(key, change.position, current)
+        else {
+            let slot = Slot { position: change.position, ..current };
+            _ = self.slots.insert(key, slot);
+            return vec![TouchpadState { finger_id: current.id, ..change }];
+        };
+        _ = self.slots.insert(from, Slot { vacated: true, ..shifted });
+        let slot = Slot { position: change.position, ..shifted };
+        _ = self.slots.insert(key, slot);
+
+        let mut changes = vec![TouchpadState {
+            finger_id: current.id,
+            position: current.position,
+            pressure: 0.0,
+            action: TouchpadAction::Released,
+            ..change
+        }];
+        #[expect(
+            clippy::float_cmp,
+            reason = "same as the unchanged check of `TouchpadState::update`"
+        )]
+        if shifted.position != change.position {
+            changes.push(TouchpadState { finger_id: shifted.id, ..change });
+        }
+        changes
+    }
+
+    /// Identifies the finger of a [`TouchpadEvent`] like [`identify`],
+    /// returning the [`TouchpadEvent`]s to report.
+    ///
+    /// [`identify`]: Self::identify
+    #[inline]
+    pub fn identify_event(
+        &mut self,
+        event: TouchpadEvent,
+    ) -> Vec<TouchpadEvent> {
+        let change = TouchpadState {
+            touchpad: usize::try_from(event.idx).unwrap_or(usize::MAX),
+            finger: usize::try_from(event.finger).unwrap_or(usize::MAX),
+            finger_id: event.finger_id,
+            position: event.position,
+            pressure: event.pressure,
+            action: event.action,
+        };
+        self.identify(change)
+            .into_iter()
+            .map(|finger| TouchpadEvent {
+                finger_id: finger.finger_id,
+                position: finger.position,
+                pressure: finger.pressure,
+                action: finger.action,
+                ..event
+            })
+            .collect()
+    }
+
+    /// Forgets all the fingers, e.g. once the [`Gamepad`] disconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Assigns the next [`FingerId`].
+    const fn assign(&mut self) -> FingerId {
+        self.last = self.last.wrapping_add(1);
+        FingerId(self.last)
+    }
+
+    /// Finds the finger of another slot of the touchpad of the slot `key`
+    /// nearest to `position`, if the `current` finger of the slot jumped
+    /// there, see [`SHIFT_DISTANCE`].
+    ///
+    /// [`SHIFT_DISTANCE`]: Self::SHIFT_DISTANCE
+    fn shifted(
+        &self,
+        key: (usize, usize),
+        position: [f32; 2],
+        current: Slot,
+    ) -> Option<((usize, usize), Slot)> {
+        if distance(position, current.position) <= Self::SHIFT_DISTANCE {
+            return None;
+        }
+        let (touchpad, finger) = key;
+        self.slots
+            .iter()
+            .filter(|&(&(pad, other), slot)| {
+                pad == touchpad && other != finger && !slot.vacated
+            })
+            .map(|(&other, &slot)| {
+                (other, slot, distance(position, slot.position))
+            })
+            .filter(|&(_, _, apart)| apart <= Self::SHIFT_DISTANCE)
+            .min_by(|left, right| left.2.total_cmp(&right.2))
+            .map(|(other, slot, _)| (other, slot))
     }
 }
 
 /// Touchpad event with position, pressure, and action.
+#[cfg(feature = "touchpad")]
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TouchpadEvent {
     /// Controller instance ID.
     pub which: u32,
     /// Touchpad index.
     pub idx: u32,
-    /// Finger index.
+    /// Finger index, the slot SDL2 reports the finger in.
     pub finger: u32,
+    /// Stable identity of the finger, the finger index until [`Girl`]
+    /// assigns one, see [`FingerTracker`].
+    ///
+    /// [`Girl`]: crate::Girl
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub finger_id: FingerId,
     /// Normalized position `[x, y]` where both values range from 0.0 to 1.0.
     pub position: [f32; 2],
     /// Normalized pressure from 0.0 to 1.0.
@@ -243,9 +810,26 @@ pub struct TouchpadEvent {
     pub action: TouchpadAction,
 }
 
+/// Compact form for logs and test failures, e.g.
+/// `#2 0:1 Moved (0.500, 0.250) p=1.000` for finger 1 of touchpad 0 of
+/// controller 2.
+#[cfg(feature = "touchpad")]
+impl fmt::Display for TouchpadEvent {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            which, idx, finger, position: [x, y], pressure, action, ..
+        } = *self;
+        let action = action.name();
+        write!(f, "#{which} {idx}:{finger} {action} ({x:.3}, {y:.3}) ")?;
+        write!(f, "p={pressure:.3}")
+    }
+}
+
 /// Type of touchpad action.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[expect(clippy::exhaustive_enums, reason = "no more actions possible")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TouchpadAction {
     /// Finger touched the touchpad.
     Touched,
@@ -256,7 +840,81 @@ pub enum TouchpadAction {
     Moved,
 }
 
+impl TouchpadAction {
+    /// Gets the name of the variant, e.g. `"Moved"`, as written by the
+    /// [`Display`] implementation of [`TouchpadState`].
+    ///
+    /// [`Display`]: fmt::Display
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Touched => "Touched",
+            Self::Released => "Released",
+            Self::Moved => "Moved",
+        }
+    }
+
+    /// Converts from the SDL2 finger state, which is either `SDL_PRESSED` or
+    /// `SDL_RELEASED`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnexpectedSdlValue`] for any other `state`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// for state in 0..=u8::MAX {
+    ///     match TouchpadAction::from_sdl_state(state) {
+    ///         Ok(action) => assert!(matches!(
+    ///             (state, action),
+    ///             (0, TouchpadAction::Released) | (1, TouchpadAction::Touched)
+    ///         )),
+    ///         Err(Error::UnexpectedSdlValue { value, .. }) => {
+    ///             assert_eq!(value, i64::from(state));
+    ///         }
+    ///         Err(err) => panic!("unexpected error: {err}"),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "touchpad")]
+    #[inline]
+    pub fn from_sdl_state(state: u8) -> Result<Self, Error> {
+        match state {
+            RELEASED => Ok(Self::Released),
+            PRESSED => Ok(Self::Touched),
+            _ => Err(Error::UnexpectedSdlValue {
+                context: "touchpad state",
+                value: i64::from(state),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "touchpad")]
 impl TouchpadEvent {
+    /// Creates a new [`TouchpadEvent`].
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        which: u32,
+        idx: u32,
+        finger: u32,
+        position: [f32; 2],
+        pressure: f32,
+        action: TouchpadAction,
+    ) -> Self {
+        Self {
+            which,
+            idx,
+            finger,
+            finger_id: FingerId(finger),
+            position,
+            pressure,
+            action,
+        }
+    }
+
     /// Converts from SDL event.
     #[must_use]
     #[inline]
@@ -275,6 +933,7 @@ impl TouchpadEvent {
                 which,
                 idx: touchpad,
                 finger,
+                finger_id: FingerId(finger),
                 position: [x, y],
                 pressure,
                 action: TouchpadAction::Touched,
@@ -291,6 +950,7 @@ impl TouchpadEvent {
                 which,
                 idx: touchpad,
                 finger,
+                finger_id: FingerId(finger),
                 position: [x, y],
                 pressure,
                 action: TouchpadAction::Released,
@@ -307,6 +967,7 @@ impl TouchpadEvent {
                 which,
                 idx: touchpad,
                 finger,
+                finger_id: FingerId(finger),
                 position: [x, y],
                 pressure,
                 action: TouchpadAction::Moved,
@@ -359,23 +1020,61 @@ impl TouchpadEvent {
             | SdlEvent::RenderDeviceReset { .. }
             | SdlEvent::LocaleChanged { .. }
             | SdlEvent::User { .. }
-            | SdlEvent::Unknown { .. } => return None,
-            #[cfg(feature = "sensors")]
-            SdlEvent::ControllerSensorUpdated { .. } => return None,
+            | SdlEvent::Unknown { .. }
+            | SdlEvent::ControllerSensorUpdated { .. } => return None,
         })
     }
 }
 
+/// Touchpad and finger indices with the action of a [`TouchpadState`],
+/// without its float fields, so usable as a hash or ordered map key, see
+/// [`TouchpadState::key`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use girl::{TouchpadAction as TA, prelude::*};
+/// let states = [
+///     TouchpadState::new(0, 0, [0.1, 0.5], 1.0, TA::Moved),
+///     TouchpadState::new(0, 0, [0.2, 0.5], 1.0, TA::Moved),
+///     TouchpadState::new(0, 1, [0.9, 0.5], 1.0, TA::Touched),
+/// ];
+///
+/// let mut counts = HashMap::new();
+/// for state in &states {
+///     *counts.entry(state.key()).or_insert(0) += 1;
+/// }
+/// assert_eq!(counts[&states[0].key()], 2);
+/// assert_eq!(counts[&states[2].key()], 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TouchpadStateKey {
+    /// Touchpad index.
+    pub touchpad: usize,
+    /// Finger index.
+    pub finger: usize,
+    /// Type of touch action.
+    pub action: TouchpadAction,
+}
+
 /// Touchpad state for each touchpad and finger.
 ///
-/// Returned by [`Gamepad::touchpad`].
+/// Returned by [`Gamepad::touchpad`] and [`Gamepad::touchpad_state`].
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct TouchpadState {
     /// Touchpad index.
     pub touchpad: usize,
-    /// Finger index.
+    /// Finger index, the slot SDL2 reports the finger in.
     pub finger: usize,
+    /// Stable identity of the finger, assigned by `TouchpadTracker` and
+    /// the default one until then, see `FingerTracker`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub finger_id: FingerId,
     /// Normalized position [x, y] where both values range from 0.0 to 1.0.
     pub position: [f32; 2],
     /// Normalized pressure from 0.0 to 1.0.
@@ -383,3 +1082,461 @@ pub struct TouchpadState {
     /// Type of touch action.
     pub action: TouchpadAction,
 }
+
+/// Compact form for logs and test failures, e.g.
+/// `0:1 Moved (0.500, 0.250) p=1.000` for finger 1 of touchpad 0.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::prelude::*;
+/// let action = TouchpadAction::Moved;
+/// let finger = TouchpadState::new(0, 1, [0.5, 0.25], 1.0, action);
+/// assert_eq!(finger.to_string(), "0:1 Moved (0.500, 0.250) p=1.000");
+/// ```
+impl fmt::Display for TouchpadState {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            touchpad, finger, position: [x, y], pressure, action, ..
+        } = *self;
+        let action = action.name();
+        write!(f, "{touchpad}:{finger} {action} ({x:.3}, {y:.3}) ")?;
+        write!(f, "p={pressure:.3}")
+    }
+}
+
+impl TouchpadState {
+    /// Creates a new [`TouchpadState`].
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        touchpad: usize,
+        finger: usize,
+        position: [f32; 2],
+        pressure: f32,
+        action: TouchpadAction,
+    ) -> Self {
+        Self {
+            touchpad,
+            finger,
+            finger_id: FingerId(0),
+            position,
+            pressure,
+            action,
+        }
+    }
+
+    /// Gets the [`TouchpadStateKey`] of the finger, to group or look up
+    /// states regardless of their position and pressure.
+    #[must_use]
+    #[inline]
+    pub const fn key(&self) -> TouchpadStateKey {
+        TouchpadStateKey {
+            touchpad: self.touchpad,
+            finger: self.finger,
+            action: self.action,
+        }
+    }
+
+    /// Checks if both states are of the same finger with the same action,
+    /// and their positions and pressures differ by at most `epsilon`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{TouchpadAction as TA, prelude::*};
+    /// let at = |x: f32| TouchpadState::new(0, 0, [x, 0.5], 1.0, TA::Moved);
+    ///
+    /// assert!(at(0.1 + 0.2).approx_eq(&at(0.3), 1e-6));
+    /// assert!(!at(0.3).approx_eq(&at(0.31), 1e-3));
+    /// let mut released = at(0.3);
+    /// released.action = TA::Released;
+    /// assert!(!at(0.3).approx_eq(&released, 1.0));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn approx_eq(&self, other: &Self, epsilon: f32) -> bool {
+        let close = |left: f32, right: f32| (left - right).abs() <= epsilon;
+        let [x, y] = self.position;
+        let [other_x, other_y] = other.position;
+        self.key() == other.key()
+            && close(x, other_x)
+            && close(y, other_y)
+            && close(self.pressure, other.pressure)
+    }
+
+    /// Compares the touchpad and then finger indices, e.g. to sort states
+    /// with [`slice::sort_by`] despite their float fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let finger = |touchpad, finger| {
+    ///     let action = TouchpadAction::Touched;
+    ///     TouchpadState::new(touchpad, finger, [0.5, 0.5], 1.0, action)
+    /// };
+    ///
+    /// let mut fingers = [finger(1, 0), finger(0, 1), finger(0, 0)];
+    /// fingers.sort_by(TouchpadState::cmp_by_index);
+    /// let indices = fingers.map(|state| (state.touchpad, state.finger));
+    /// assert_eq!(indices, [(0, 0), (0, 1), (1, 0)]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn cmp_by_index(&self, other: &Self) -> cmp::Ordering {
+        (self.touchpad, self.finger).cmp(&(other.touchpad, other.finger))
+    }
+
+    /// Updates the cached state of a finger with its current `sample`,
+    /// returning the change to report, if any.
+    ///
+    /// A [`TouchpadAction::Touched`] or [`TouchpadAction::Released`] change
+    /// is reported once, and a [`TouchpadAction::Moved`] one whenever the
+    /// position or pressure of a touching finger changes. A `sample` is
+    /// touching unless it is [`TouchpadAction::Released`]. The index of the
+    /// touchpad and finger of the cached state is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{TouchpadAction as TA, TouchpadState};
+    /// let sample = |x, act| TouchpadState::new(0, 0, [x, 0.5], 1.0, act);
+    /// let mut finger = TouchpadState::default();
+    ///
+    /// assert_eq!(finger.update(&sample(0.1, TA::Released)), None);
+    /// let touched = finger.update(&sample(0.1, TA::Touched)).unwrap();
+    /// assert_eq!(touched.action, TA::Touched);
+    /// assert_eq!(finger.update(&sample(0.1, TA::Touched)), None);
+    /// let moved = finger.update(&sample(0.2, TA::Touched)).unwrap();
+    /// assert!(moved.approx_eq(&sample(0.2, TA::Moved), 1e-6));
+    /// let released = finger.update(&sample(0.2, TA::Released)).unwrap();
+    /// assert_eq!(released.action, TA::Released);
+    /// assert_eq!(finger.update(&sample(0.2, TA::Released)), None);
+    /// ```
+    #[inline]
+    pub fn update(&mut self, sample: &Self) -> Option<Self> {
+        use self::TouchpadAction as TA;
+
+        let current = if sample.action == TA::Released {
+            TA::Released
+        } else {
+            TA::Touched
+        };
+
+        let action = if current == self.action {
+            // only report the first release event
+            if current == TA::Released {
+                return None;
+            }
+
+            // don't report the same event twice
+            #[expect(
+                clippy::float_cmp,
+                reason = "want this to be the same as the sdl2 logic"
+            )]
+            if sample.position == self.position
+                && sample.pressure == self.pressure
+            {
+                return None;
+            }
+
+            // otherwise, report the repeated touch as a move event
+            TA::Moved
+        } else {
+            current
+        };
+
+        self.action = current;
+        self.position = sample.position;
+        self.pressure = sample.pressure;
+
+        Some(Self { action, ..*self })
+    }
+
+    /// Converts the normalized position to millimeters from the top left
+    /// corner of a touchpad of the given physical `size`, see
+    /// `Gamepad::touchpad_physical_size` with the `touchpad` feature.
+    #[must_use]
+    #[inline]
+    pub const fn position_mm(&self, size: [f32; 2]) -> [f32; 2] {
+        let [x, y] = self.position;
+        let [width, height] = size;
+        [x * width, y * height]
+    }
+
+    /// Gets the velocity of the finger in millimeters per second, moving
+    /// from the `previous` state `elapsed` time ago on a touchpad of the
+    /// given physical `size`.
+    ///
+    /// Unlike normalized velocities, these compare across devices: a 3 cm
+    /// swipe is a 3 cm swipe on any touchpad. Returns [`None`] if no time
+    /// has `elapsed`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::prelude::*;
+    /// let size = [60.0, 33.0]; // a DualSense touchpad
+    /// let at = |x: f32, y: f32| {
+    ///     TouchpadState::new(0, 0, [x, y], 0.5, TouchpadAction::Moved)
+    /// };
+    /// let close = |[ax, ay]: [f32; 2], [bx, by]: [f32; 2]| {
+    ///     (ax - bx).abs() < 1e-3 && (ay - by).abs() < 1e-3
+    /// };
+    ///
+    /// // a left to right swipe across half of the touchpad in 100 ms
+    /// let trace = [at(0.25, 0.5), at(0.5, 0.5), at(0.75, 0.5)];
+    /// assert!(close(trace[0].position_mm(size), [15.0, 16.5]));
+    /// assert!(close(trace[2].position_mm(size), [45.0, 16.5]));
+    ///
+    /// let step = Duration::from_millis(50);
+    /// for pair in trace.windows(2) {
+    ///     let velocity = pair[1].velocity_mm(&pair[0], step, size).unwrap();
+    ///     assert!(close(velocity, [300.0, 0.0]));
+    /// }
+    /// assert_eq!(trace[1].velocity_mm(&trace[0], Duration::ZERO, size), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn velocity_mm(
+        &self,
+        previous: &Self,
+        elapsed: Duration,
+        size: [f32; 2],
+    ) -> Option<[f32; 2]> {
+        if elapsed.is_zero() {
+            return None;
+        }
+        let seconds = elapsed.as_secs_f32();
+        let [x, y] = self.position_mm(size);
+        let [prev_x, prev_y] = previous.position_mm(size);
+        Some([(x - prev_x) / seconds, (y - prev_y) / seconds])
+    }
+}
+
+/// Physical touchpad sizes `[width, height]` in millimeters per
+/// [`ControllerType`], see [`Gamepad::touchpad_physical_size`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchpadSizes, prelude::*};
+/// let mut sizes = TouchpadSizes::new();
+/// assert_eq!(sizes.get(ControllerType::Ps5), Some(TouchpadSizes::DUALSENSE));
+/// assert_eq!(sizes.get(ControllerType::XboxOne), None);
+///
+/// sizes.set(ControllerType::Ps5, Some([59.0, 27.5]));
+/// sizes.set(ControllerType::Ps4, None);
+/// assert_eq!(sizes.get(ControllerType::Ps5), Some([59.0, 27.5]));
+/// assert_eq!(sizes.get(ControllerType::Ps4), None);
+/// ```
+#[cfg(feature = "touchpad")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TouchpadSizes(BTreeMap<ControllerType, [f32; 2]>);
+
+#[cfg(feature = "touchpad")]
+impl Default for TouchpadSizes {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "touchpad")]
+impl TouchpadSizes {
+    /// Approximate touchpad size of a PS5 controller, in millimeters.
+    pub const DUALSENSE: [f32; 2] = [60.0, 33.0];
+    /// Approximate touchpad size of a PS4 controller, in millimeters.
+    pub const DUALSHOCK_4: [f32; 2] = [62.0, 28.0];
+
+    /// Creates [`TouchpadSizes`] with the built-in sizes of PS4 and PS5
+    /// controllers.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        let mut sizes = Self::empty();
+        sizes.set(ControllerType::Ps4, Some(Self::DUALSHOCK_4));
+        sizes.set(ControllerType::Ps5, Some(Self::DUALSENSE));
+        sizes
+    }
+
+    /// Creates [`TouchpadSizes`] without any sizes.
+    #[must_use]
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    /// Gets the touchpad size of the [`ControllerType`], if known.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, controller_type: ControllerType) -> Option<[f32; 2]> {
+        self.0.get(&controller_type).copied()
+    }
+
+    /// Sets the touchpad size of the [`ControllerType`], or forgets it if
+    /// [`None`].
+    #[inline]
+    pub fn set(
+        &mut self,
+        controller_type: ControllerType,
+        size: Option<[f32; 2]>,
+    ) {
+        _ = match size {
+            Some(known) => self.0.insert(controller_type, known),
+            None => self.0.remove(&controller_type),
+        };
+    }
+}
+
+/// Current state of every finger on the touchpads of a [`Gamepad`], tracked
+/// from [`TouchpadEvent`]s.
+///
+/// [`Girl`] keeps one per [`Gamepad`] up to date as SDL2 queues the
+/// [`TouchpadEvent`]s, so [`Gamepad::touchpad`] doesn't have to query every
+/// finger. Sampling it reports the same changes as querying the fingers.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchpadAction as TA, TouchpadEvent, TouchpadSnapshot};
+/// # use girl::prelude::*;
+/// let event = |x, action| TouchpadEvent::new(0, 0, 0, [x, 0.5], 1.0, action);
+/// let sample = |x, action| TouchpadState::new(0, 0, [x, 0.5], 1.0, action);
+///
+/// // events of every frame, and the finger as queried after them
+/// let frames = [
+///     (vec![event(0.1, TA::Touched)], sample(0.1, TA::Touched)),
+///     (
+///         vec![event(0.2, TA::Moved), event(0.3, TA::Moved)],
+///         sample(0.3, TA::Touched),
+///     ),
+///     (vec![], sample(0.3, TA::Touched)),
+///     (vec![event(0.3, TA::Released)], sample(0.3, TA::Released)),
+///     (vec![], sample(0.3, TA::Released)),
+/// ];
+///
+/// let mut snapshot = TouchpadSnapshot::new();
+/// let mut tracked = TouchpadState::default();
+/// let mut queried = TouchpadState::default();
+/// let mut reported = vec![];
+/// for (events, current) in frames {
+///     for event in &events {
+///         snapshot.observe(event);
+///     }
+///     let from_events =
+///         snapshot.finger(0, 0).and_then(|finger| tracked.update(&finger));
+///     let from_queries = queried.update(&current);
+///     assert_eq!(from_events, from_queries);
+///     reported.push(from_events.map(|finger| finger.action));
+/// }
+/// assert_eq!(reported, [
+///     Some(TA::Touched),
+///     Some(TA::Moved),
+///     None,
+///     Some(TA::Released),
+///     None
+/// ],);
+/// ```
+///
+/// [`Girl`]: crate::Girl
+#[cfg(feature = "touchpad")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchpadSnapshot {
+    /// Current state of every finger seen, by touchpad and finger index.
+    fingers: BTreeMap<(u32, u32), TouchpadState>,
+}
+
+#[cfg(feature = "touchpad")]
+impl TouchpadSnapshot {
+    /// Creates an empty [`TouchpadSnapshot`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { fingers: BTreeMap::new() }
+    }
+
+    /// Records the finger state of the [`TouchpadEvent`].
+    #[inline]
+    pub fn observe(&mut self, event: &TouchpadEvent) {
+        let action = if event.action == TouchpadAction::Released {
+            TouchpadAction::Released
+        } else {
+            TouchpadAction::Touched
+        };
+        let state = TouchpadState::new(
+            usize::try_from(event.idx).unwrap_or(usize::MAX),
+            usize::try_from(event.finger).unwrap_or(usize::MAX),
+            event.position,
+            event.pressure,
+            action,
+        );
+        _ = self.fingers.insert((event.idx, event.finger), state);
+    }
+
+    /// Gets the current state of a finger, [`None`] if it was never seen.
+    #[must_use]
+    #[inline]
+    pub fn finger(
+        &self,
+        touchpad: usize,
+        finger: usize,
+    ) -> Option<TouchpadState> {
+        let key = (u32::try_from(touchpad).ok()?, u32::try_from(finger).ok()?);
+        self.fingers.get(&key).copied()
+    }
+}
+
+/// Gets the distance between two normalized positions.
+#[cfg(feature = "touchpad")]
+fn distance([x, y]: [f32; 2], [other_x, other_y]: [f32; 2]) -> f32 {
+    (x - other_x).hypot(y - other_y)
+}
+
+/// Queries the current state of a finger from SDL2.
+#[cfg(feature = "touchpad")]
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn poll_finger(
+    raw: *mut sdl2_sys::SDL_GameController,
+    touchpad: usize,
+    finger: usize,
+) -> Option<TouchpadState> {
+    let (idx, finger_idx) =
+        (i32::try_from(touchpad).ok()?, i32::try_from(finger).ok()?);
+    let mut position = [0.0, 0.0];
+    let mut pressure = 0.0;
+    let mut state = 0;
+
+    // SAFETY: SDL2 is still alive, all the pointers are valid.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let res = unsafe {
+        sdl2_sys::SDL_GameControllerGetTouchpadFinger(
+            raw,
+            idx,
+            finger_idx,
+            &raw mut state,
+            &raw mut position[0],
+            &raw mut position[1],
+            &raw mut pressure,
+        )
+    };
+
+    if res != 0i32 {
+        return None;
+    }
+
+    match TouchpadAction::from_sdl_state(state) {
+        Ok(action) => Some(TouchpadState::new(
+            touchpad, finger, position, pressure, action,
+        )),
+        Err(err) => {
+            // a driver quirk shouldn't take the whole poll down
+            diag!(warn, "skipping touchpad finger: {err}");
+            None
+        }
+    }
+}
@@ -3,9 +3,60 @@
 //! This module provides the main interface for detecting and managing
 //! connected [`Gamepad`]s.
 
-use core::fmt;
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+use core::{ffi::CStr, fmt, iter, mem, time::Duration};
+#[cfg(feature = "eventlog")]
+use std::io::Write;
+use std::{
+    sync::{PoisonError, RwLock, mpsc},
+    thread,
+    time::{Instant, SystemTime},
+};
 
-use crate::{Error, Event, gamepad::Gamepad};
+use sdl2::{
+    event::{Event as SdlEvent, EventWatch},
+    sys as sdl2_sys,
+};
+
+#[cfg(feature = "sdl2-interop")]
+use crate::ErrorCode;
+use crate::{
+    AggregateGamepad, AudioIdentity, AudioMatcher, BackgroundPolicy, Button,
+    ConnectionDebounce, ConnectionHeuristics, ConnectionQuality,
+    ControllerType, DefaultProfiles, DeviceHistory, DeviceInfo, EffectDuration,
+    EnrichedEvent, Error, Event, EventMiddleware, EventQueue, GamepadDump,
+    GamepadId, GamepadMut, GamepadRef, GamepadSource, GamepadState, GirlHandle,
+    Guid, Hints, IdleConfig, InputConfig, InputContexts, InputProfile,
+    LedBinding, LedBindingId, LedBindings, MappingSnapshot, MiddlewareChain,
+    MiddlewareId, Misc1Suppression, NavigationConfig, PadSetup, PerfCounters,
+    QualityReport, RecoveryReport, SequencedEvent, SetupReport,
+    SteamInputHeuristics, SteamInputStatus, Stick, StickNavigator, StickTiers,
+    UpdateSummary, WearMetrics, audio,
+    compat::Translator,
+    gamepad::{Gamepad, PadSettings, state::fill_slots},
+    handle::{Request, SharedStates},
+    instance::InstanceGuard,
+    is_initialized,
+    logging::{FailureLog, diag},
+    middleware::Scope,
+    openerror::LastOpenError,
+    queue::EVENT_CAPACITY,
+    shared::{InputRecorder, Shared},
+    stages::{StageCtx, Stages},
+};
+#[cfg(feature = "eventlog")]
+use crate::{EventLogWriter, TeeEvents};
+#[cfg(feature = "touchpad")]
+use crate::{FingerTracker, TouchRejection, TouchRejector};
+#[cfg(feature = "sensors")]
+use crate::{Sensor, SensorAccumulation, retry::SensorRetry};
+
+/// How often [`Girl::event_blocking`] polls in eventless mode, see
+/// [`Girl::new_eventless`].
+const EVENTLESS_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Main gamepad manager.
 ///
@@ -37,8 +88,114 @@ pub struct Girl {
     gcs: sdl2::GameControllerSubsystem,
     /// SDL2 joystick subsystem.
     jcs: sdl2::JoystickSubsystem,
-    /// SDL2 event pump for processing input events.
-    event_pump: sdl2::EventPump,
+    /// SDL2 event pump for processing input events, [`None`] in eventless
+    /// mode, see [`Girl::new_eventless`].
+    event_pump: Option<sdl2::EventPump>,
+    /// Records taps and touchpad fingers as SDL2 queues them, [`None`] in
+    /// eventless mode, see [`Gamepad::tapped_since_update`] and
+    /// [`Gamepad::touchpad`].
+    #[expect(dead_code, reason = "only kept alive, removed on drop")]
+    input_watch: Option<EventWatch<'static, InputRecorder>>,
+    /// [`Gamepad`]s opened by [`Girl`] itself, refreshed on every
+    /// [`Girl::update`].
+    pads: BTreeMap<GamepadId, Gamepad>,
+    /// Sending end of the [`GirlHandle`] request queue, cloned into every new
+    /// [`GirlHandle`].
+    requests_tx: mpsc::Sender<Request>,
+    /// Receiving end of the [`GirlHandle`] request queue.
+    requests_rx: mpsc::Receiver<Request>,
+    /// Latest [`GamepadState`] snapshots shared with [`GirlHandle`]s.
+    states: SharedStates,
+    /// Original SDL2 mappings of the [`Guid`]s with overridden mappings.
+    mapping_overrides: BTreeMap<Guid, Option<String>>,
+    /// Sequence number of the next translated [`Event`].
+    next_seq: u64,
+    /// Stick navigation configuration, see [`Girl::set_stick_navigation`].
+    navigation: BTreeMap<Stick, NavigationConfig>,
+    /// Per-[`Gamepad`] stick navigation state.
+    navigators: BTreeMap<(GamepadId, Stick), StickNavigator>,
+    /// Per-[`Gamepad`] stick tier state, see [`Gamepad::set_stick_tiers`].
+    tiers: BTreeMap<(GamepadId, Stick), StickTiers>,
+    /// Synthesized [`Event`]s waiting to be returned before SDL2 ones.
+    pending: VecDeque<Event>,
+    /// State shared with every [`Gamepad`].
+    shared: Arc<Shared>,
+    /// [`Button`]s held when input was suspended, see
+    /// [`Girl::suspend_input`].
+    held_on_suspend: BTreeMap<GamepadId, Button>,
+    /// Pending [`Gamepad::identify`] calls with their start times and
+    /// durations, see [`Girl::identify_all_sequentially`].
+    identify_schedule: VecDeque<(GamepadId, Instant, Duration)>,
+    /// Translates SDL2 events into [`Event`]s.
+    translator: Translator,
+    /// Holds back connection changes of flaky [`Gamepad`]s, see
+    /// [`Girl::set_connection_debounce`].
+    connection_debounce: ConnectionDebounce,
+    /// LED colors bound to [`Gamepad`] input, see
+    /// [`Girl::add_led_binding`].
+    led_bindings: LedBindings,
+    /// Time of the time-driven layer, advanced by [`Girl::tick`].
+    clock: Instant,
+    /// Whether [`Girl::update`] ticks with the wall clock, see
+    /// [`Girl::set_auto_tick`].
+    auto_tick: bool,
+    /// Estimates the connection quality, see [`Girl::connection_quality`].
+    connection: ConnectionQuality,
+    /// Metadata of every [`Gamepad`] opened by [`Girl`] itself, kept after
+    /// disconnects, see [`Girl::enrich`].
+    devices: BTreeMap<GamepadId, DeviceInfo>,
+    /// Attached [`DeviceHistory`], see [`Girl::attach_history`].
+    history: Option<DeviceHistory>,
+    /// Attached [`WearMetrics`], see [`Girl::attach_wear`].
+    wear: Option<WearMetrics>,
+    /// Work done since the last [`Girl::update`], if counted, see
+    /// [`Girl::set_perf_counters`].
+    perf: Option<PerfCounters>,
+    /// Work done before the last [`Girl::update`], see
+    /// [`Girl::perf_totals`].
+    perf_total: PerfCounters,
+    /// Last SDL2 mappings of the [`Gamepad`]s, see
+    /// [`Girl::mapping_snapshot`].
+    mappings: BTreeMap<GamepadId, MappingSnapshot>,
+    /// Input contexts reset every frame, see [`Girl::contexts`].
+    contexts: InputContexts,
+    /// Matches [`Gamepad`]s with audio devices, see
+    /// [`Girl::set_audio_matcher`].
+    audio_matcher: AudioMatcher,
+    /// Names of the audio output devices, see
+    /// [`Girl::audio_output_devices`].
+    audio_outputs: Vec<String>,
+    /// Processes [`Event`]s before sequencing, see
+    /// [`Girl::add_middleware`].
+    middleware: MiddlewareChain,
+    /// Built-in trackers run around the [`MiddlewareChain`].
+    stages: Stages,
+    /// [`Event`]s that came out of the [`Stages`], ready for sequencing.
+    processed: VecDeque<Event>,
+    /// Sequenced [`Event`]s waiting to be polled, see
+    /// [`Girl::dropped_events`].
+    queue: EventQueue<EVENT_CAPACITY>,
+    /// [`Event`]s sequenced since the last [`Girl::update`].
+    summary: UpdateSummary,
+    /// Why the latest [`Gamepad`] open failed, see
+    /// [`Girl::last_open_error`].
+    last_open_error: LastOpenError,
+    /// Operations failing in a row, so that each streak is logged once.
+    failures: FailureLog,
+    /// Withheld touches per [`Gamepad`], see
+    /// [`Gamepad::set_touchpad_rejection`].
+    #[cfg(feature = "touchpad")]
+    touch_rejectors: BTreeMap<GamepadId, TouchRejector>,
+    /// Identities of the fingers on the touchpads per [`Gamepad`], see
+    /// [`TouchpadEvent::finger_id`].
+    #[cfg(feature = "touchpad")]
+    finger_trackers: BTreeMap<GamepadId, FingerTracker>,
+    /// Marks the [`Girl`] as existing, see [`is_initialized`]. Declared
+    /// last to be released only after SDL2 is shut down.
+    ///
+    /// [`is_initialized`]: crate::is_initialized
+    #[expect(dead_code, reason = "only held to be dropped")]
+    instance: InstanceGuard,
 }
 
 impl fmt::Debug for Girl {
@@ -48,25 +205,247 @@ impl fmt::Debug for Girl {
             .field("gamepad_subsystem", &self.gcs)
             .field("joystick_subsystem", &self.jcs)
             .field("event_pump", &"...")
-            .finish()
+            .field("pads", &self.pads)
+            .finish_non_exhaustive()
     }
 }
 
+#[expect(clippy::same_name_method, reason = "implements `GamepadSource`")]
 impl Girl {
     /// Initializes a new gamepad input manager.
     ///
+    /// Must be called on the main thread on platforms where SDL2 requires
+    /// it for events (macOS), use [`new_eventless`] elsewhere.
+    ///
     /// # Errors
     ///
-    /// Returns an error if SDL2 or its controller subsystems fail to
-    /// initialize.
+    /// Returns [`Error::NotMainThread`] if called off the main thread where
+    /// that is not supported, [`Error::AlreadyInitialized`] if another
+    /// [`Girl`] exists (see [`is_initialized`]), or an error if SDL2 or its
+    /// controller subsystems fail to initialize.
+    ///
+    /// [`new_eventless`]: Self::new_eventless
+    /// [`is_initialized`]: crate::is_initialized
     #[inline]
     pub fn new() -> Result<Self, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            use core::ffi::c_int;
+
+            #[expect(unsafe_code, reason = "ffi with libc")]
+            unsafe extern "C" {
+                fn pthread_main_np() -> c_int;
+            }
+
+            // SAFETY: `pthread_main_np` has no preconditions, it only
+            // compares the calling thread with the initial one.
+            #[expect(unsafe_code, reason = "ffi with libc")]
+            let main = unsafe { pthread_main_np() };
+            if main == 0 {
+                diag!(error, "girl must be initialized on the main thread");
+                return Err(Error::NotMainThread);
+            }
+        }
+        Self::init(true)
+    }
+
+    /// Initializes a new gamepad input manager without an SDL2 event pump,
+    /// which is safe to do on any thread.
+    ///
+    /// In this polling mode [`update`] refreshes the controller state
+    /// directly, and only [`Event`]s synthesized by [`Girl`] itself (e.g.
+    /// stick navigation) are reported. [`Gamepad`] state queries work as
+    /// usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlreadyInitialized`] if another [`Girl`] exists
+    /// (see [`is_initialized`]), or an error if SDL2 or its controller
+    /// subsystems fail to initialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// std::thread::spawn(|| {
+    ///     let mut girl = girl::Girl::new_eventless()?;
+    ///     girl.update();
+    ///     if let Some(gamepad) = girl.gamepad(0) {
+    ///         println!("{:?}", gamepad.state());
+    ///     }
+    ///     Ok::<(), girl::Error>(())
+    /// })
+    /// .join()
+    /// .unwrap()?;
+    ///
+    /// # #[cfg(target_os = "macos")]
+    /// # std::thread::spawn(|| {
+    /// #     let err = girl::Girl::new().unwrap_err();
+    /// #     assert!(matches!(err, girl::Error::NotMainThread));
+    /// # })
+    /// # .join()
+    /// # .unwrap();
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`is_initialized`]: crate::is_initialized
+    #[inline]
+    pub fn new_eventless() -> Result<Self, Error> {
+        Self::init(false)
+    }
+
+    /// Initializes SDL2 and the [`Girl`] state, with or without an event
+    /// pump.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlreadyInitialized`] if another [`Girl`] exists, or
+    /// an error if SDL2 or its controller subsystems fail to initialize.
+    fn init(with_events: bool) -> Result<Self, Error> {
+        let instance = InstanceGuard::acquire()?;
         let sdl2 = sdl2::init().map_err(Error::Sdl2Init)?;
         let gamepad_subsys = sdl2.game_controller().map_err(Error::Sdl2Init)?;
         let joystick_subsys = sdl2.joystick().map_err(Error::Sdl2Init)?;
-        let event_pump = sdl2.event_pump().map_err(Error::Sdl2Init)?;
+        let shared = Arc::new(Shared::default());
+        let (event_pump, input_watch) = if with_events {
+            let event_pump = sdl2.event_pump().map_err(Error::Sdl2Init)?;
+            let recorder = InputRecorder::new(Arc::downgrade(&shared));
+            let events = sdl2.event().map_err(Error::Sdl2Init)?;
+            let watch = events.add_event_watch(recorder);
+            shared.set_event_driven();
+            (Some(event_pump), Some(watch))
+        } else {
+            // nothing would drain the SDL2 event queue
+            gamepad_subsys.set_event_state(false);
+            joystick_subsys.set_event_state(false);
+            (None, None)
+        };
+
+        let (requests_tx, requests_rx) = mpsc::channel();
+        let clock = Instant::now();
+
+        Ok(Self {
+            gcs: gamepad_subsys,
+            jcs: joystick_subsys,
+            event_pump,
+            input_watch,
+            pads: BTreeMap::new(),
+            requests_tx,
+            requests_rx,
+            states: Arc::new(RwLock::new(BTreeMap::new())),
+            mapping_overrides: BTreeMap::new(),
+            next_seq: 0,
+            navigation: BTreeMap::new(),
+            navigators: BTreeMap::new(),
+            tiers: BTreeMap::new(),
+            pending: VecDeque::new(),
+            shared,
+            held_on_suspend: BTreeMap::new(),
+            identify_schedule: VecDeque::new(),
+            translator: Translator::new(),
+            connection_debounce: ConnectionDebounce::disabled(),
+            led_bindings: LedBindings::default(),
+            clock,
+            auto_tick: true,
+            connection: ConnectionQuality::new(),
+            devices: BTreeMap::new(),
+            history: None,
+            wear: None,
+            perf: None,
+            perf_total: PerfCounters::new(),
+            mappings: BTreeMap::new(),
+            contexts: InputContexts::new(),
+            audio_matcher: AudioMatcher::new(),
+            audio_outputs: Vec::new(),
+            middleware: MiddlewareChain::new(),
+            stages: Stages::new(clock),
+            processed: VecDeque::new(),
+            queue: EventQueue::new(),
+            summary: UpdateSummary::new(),
+            last_open_error: LastOpenError::default(),
+            failures: FailureLog::default(),
+            #[cfg(feature = "touchpad")]
+            touch_rejectors: BTreeMap::new(),
+            #[cfg(feature = "touchpad")]
+            finger_trackers: BTreeMap::new(),
+            instance,
+        })
+    }
+
+    /// Initializes a new gamepad input manager, setting the SDL2 [`Hints`]
+    /// first.
+    ///
+    /// Prefer this over the `set_*` hint methods, as hints only apply to
+    /// [`Gamepad`]s opened after they are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlreadyInitialized`] without setting the hints if
+    /// another [`Girl`] exists (see [`is_initialized`]), or an error if SDL2
+    /// rejects a hint, or SDL2 or its controller subsystems fail to
+    /// initialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::Hints;
+    /// let mut hints = Hints::new();
+    /// hints.ps5_enhanced_reports = Some(true);
+    /// let mut girl = girl::Girl::with_hints(hints)?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // DualSense sensors now report at the full rate
+    /// # #[cfg(feature = "sensors")]
+    /// if let Some(gyro) = gamepad.primary_gyroscope() {
+    ///     gamepad.enable_sensor(gyro)?;
+    ///     let [pitch, yaw, roll] = gamepad.sensor(gyro)?;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`is_initialized`]: crate::is_initialized
+    #[inline]
+    pub fn with_hints(hints: Hints) -> Result<Self, Error> {
+        // don't change the hints of the existing one
+        if is_initialized() {
+            return Err(Error::AlreadyInitialized);
+        }
+        hints.apply()?;
+        Self::new()
+    }
+
+    /// Checks if a [`Girl`] currently exists in the process, the same as
+    /// [`girl::is_initialized`].
+    ///
+    /// [`girl::is_initialized`]: crate::is_initialized
+    #[must_use]
+    #[inline]
+    pub fn is_initialized() -> bool {
+        is_initialized()
+    }
 
-        Ok(Self { gcs: gamepad_subsys, jcs: joystick_subsys, event_pump })
+    /// Creates a [`GirlHandle`] for controlling [`Gamepad`]s and reading their
+    /// state from other threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// let handle = girl.handle();
+    ///
+    /// std::thread::spawn(move || {
+    ///     for (id, state) in handle.states() {
+    ///         println!("{id}: {state:?}");
+    ///     }
+    /// });
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn handle(&self) -> GirlHandle {
+        GirlHandle::new(self.requests_tx.clone(), Arc::clone(&self.states))
     }
 
     /// Polls for the next available input [`Event`].
@@ -75,103 +454,3715 @@ impl Girl {
     #[must_use]
     #[inline]
     pub fn event(&mut self) -> Option<Event> {
-        self.event_pump.poll_event().as_ref().and_then(Event::from_sdl)
+        self.sequenced_event().map(|sequenced| sequenced.event)
     }
 
-    /// Waits for and returns the next input [`Event`].
+    /// Appends all currently available input [`Event`]s to `out`, returning
+    /// how many were appended.
+    ///
+    /// Goes through the same path as [`event`], so suppression and sequence
+    /// numbering apply identically. `out` is not cleared, so a buffer reused
+    /// across frames does not allocate once it has grown large enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// let mut events = Vec::with_capacity(64);
+    ///
+    /// loop {
+    ///     events.clear();
+    ///     let count = girl.drain_into(&mut events);
+    ///     assert_eq!(count, events.len());
+    ///     for event in &events {
+    ///         println!("{event:?}");
+    ///     }
+    ///     # break;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    #[inline]
+    pub fn drain_into(&mut self, out: &mut Vec<Event>) -> usize {
+        let before = out.len();
+        out.extend(iter::from_fn(|| self.event()));
+        out.len().saturating_sub(before)
+    }
+
+    /// Attaches the cached [`DeviceInfo`] of the device the [`Event`]
+    /// originates from, without querying SDL2.
+    ///
+    /// Metadata is cached when [`update`] opens a [`Gamepad`] and kept after it
+    /// disconnects, so [`Event`]s translated before a disconnect are still
+    /// enriched with the last known metadata.
+    ///
+    /// # Examples
     ///
-    /// Blocks until an [`Event`] is available.
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     while let Some(event) = girl.event() {
+    ///         println!("{}", girl.enrich(&event));
+    ///     }
+    ///     # break;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
     #[must_use]
     #[inline]
-    pub fn event_blocking(&mut self) -> Event {
-        loop {
-            if let Some(ev) = Event::from_sdl(&self.event_pump.wait_event()) {
-                return ev;
-            }
+    pub fn enrich(&self, event: &Event) -> EnrichedEvent {
+        let device = event
+            .which()
+            .and_then(|which| self.devices.get(&self.shared.id(which)));
+        EnrichedEvent::new(*event, device.cloned())
+    }
+
+    /// Iterates over the available input [`Event`]s like [`event`] does,
+    /// writing each one to the `log` along the way.
+    ///
+    /// Each [`Event`] is [`enrich`]ed and logged with its sequence number and
+    /// a timestamp. Write errors don't interrupt the [`Event`]s, see
+    /// [`EventLogWriter::take_error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::EventLogWriter;
+    /// let mut girl = girl::Girl::new()?;
+    /// let mut log = EventLogWriter::new(Vec::new()).unwrap();
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     for event in girl.tee_events(&mut log) {
+    ///         println!("{event:?}");
+    ///     }
+    ///     # break;
+    /// }
+    ///
+    /// let written = log.into_inner().unwrap();
+    /// assert!(written.starts_with(br#"{"kind":"header""#));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`enrich`]: Self::enrich
+    #[cfg(feature = "eventlog")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "eventlog")))]
+    #[inline]
+    pub const fn tee_events<'log, W: Write>(
+        &mut self,
+        log: &'log mut EventLogWriter<W>,
+    ) -> TeeEvents<'_, 'log, W> {
+        TeeEvents::new(self, log)
+    }
+
+    /// Attaches a [`DeviceHistory`], kept up to date by [`update`] with every
+    /// connected [`Gamepad`] from now on.
+    ///
+    /// Replaces the previously attached [`DeviceHistory`], if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::DeviceHistory;
+    /// let path = std::env::temp_dir().join("girl-attach-doctest.txt");
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.attach_history(DeviceHistory::load(&path));
+    ///
+    /// if let Some(history) = girl.history()
+    ///     && let Some(last) = history.recent(1).pop()
+    /// {
+    ///     println!("last used: {} ({:?})", last.display_name(), last.power);
+    /// }
+    /// loop {
+    ///     girl.update();
+    ///     # break;
+    /// }
+    ///
+    /// if let Some(history) = girl.detach_history() {
+    ///     history.save(&path).unwrap();
+    /// }
+    /// # std::fs::remove_file(&path).unwrap();
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[inline]
+    pub fn attach_history(&mut self, mut history: DeviceHistory) {
+        let now = SystemTime::now();
+        for pad in self.pads.values() {
+            history.observe(pad, now);
         }
+        self.history = Some(history);
     }
 
-    /// Gathers pending input events from [`Gamepad`] devices.
+    /// Gets the attached [`DeviceHistory`], see [`attach_history`].
     ///
-    /// Should be called regularly in your application's main loop, as otherwise
-    /// the [`Gamepad`] will report same inputs over and over again.
+    /// [`attach_history`]: Self::attach_history
+    #[must_use]
     #[inline]
-    pub fn update(&mut self) {
-        self.event_pump.pump_events();
-        debug_assert!(self.gcs.event_state(), "unhandled events");
+    pub const fn history(&self) -> Option<&DeviceHistory> {
+        self.history.as_ref()
     }
 
-    /// Returns an iterator over all connected [`Gamepad`]s.
+    /// Gets the attached [`DeviceHistory`] mutably, e.g. to assign
+    /// nicknames, see [`attach_history`].
+    ///
+    /// [`attach_history`]: Self::attach_history
+    #[must_use]
     #[inline]
-    pub const fn gamepads_connected(&self) -> ConnectedGamepads<'_> {
-        ConnectedGamepads { gcs: &self.gcs, jcs: &self.jcs, idx: 0 }
+    pub const fn history_mut(&mut self) -> Option<&mut DeviceHistory> {
+        self.history.as_mut()
+    }
+
+    /// Detaches the [`DeviceHistory`], e.g. to save it, see
+    /// [`attach_history`].
+    ///
+    /// [`attach_history`]: Self::attach_history
+    #[inline]
+    pub const fn detach_history(&mut self) -> Option<DeviceHistory> {
+        self.history.take()
+    }
+
+    /// Attaches [`WearMetrics`], kept up to date with the usage of every
+    /// connected [`Gamepad`] from now on.
+    ///
+    /// Replaces the previously attached [`WearMetrics`], if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::WearMetrics;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.attach_wear(WearMetrics::new());
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     while let Some(_event) = girl.event() {}
+    ///     # break;
+    /// }
+    ///
+    /// if let Some(wear) = girl.detach_wear() {
+    ///     for (guid, device) in wear.devices() {
+    ///         println!("{guid}: {} presses", device.button_presses);
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn attach_wear(&mut self, mut wear: WearMetrics) {
+        let now = Instant::now();
+        for (&id, pad) in &self.pads {
+            wear.connect(id, pad.guid(), now);
+        }
+        self.wear = Some(wear);
     }
 
-    /// Gets a specific [`Gamepad`] by its `index`.
+    /// Gets the attached [`WearMetrics`], see [`attach_wear`].
     ///
-    /// Returns [`None`] if no [`Gamepad`] is connected at the given `index`.
+    /// [`attach_wear`]: Self::attach_wear
     #[must_use]
     #[inline]
-    pub fn gamepad(&self, index: u32) -> Option<Gamepad> {
-        let gc = self.gcs.open(index).ok()?;
-        let js = self.jcs.open(index).ok()?;
-        Gamepad::from_sdl(gc, js)
+    pub const fn wear(&self) -> Option<&WearMetrics> {
+        self.wear.as_ref()
     }
 
-    // /// Returns the latest [`TouchpadEvent`], if any.
-    // #[must_use]
-    // #[inline]
-    // pub fn touchpad(&mut self) -> Option<TouchpadEvent> {
-    //     let mut tp = None;
-    //     while let Some(event) = self.event() {
-    //         if let Some(tpn) = TouchpadEvent::from_event(event) {
-    //             tp = Some(tpn);
-    //         }
-    //     }
-    //     tp
-    // }
-}
+    /// Detaches the [`WearMetrics`], counting the time connected so far,
+    /// e.g. to save them, see [`attach_wear`].
+    ///
+    /// [`attach_wear`]: Self::attach_wear
+    #[inline]
+    pub fn detach_wear(&mut self) -> Option<WearMetrics> {
+        let mut wear = self.wear.take()?;
+        let now = Instant::now();
+        for &id in self.pads.keys() {
+            wear.disconnect(id, now);
+        }
+        Some(wear)
+    }
 
-/// Iterator over all connected [`Gamepad`]s.
-///
-/// Can be obtained from [`Girl::gamepads_connected`].
-#[derive(Debug, Clone)]
-#[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct ConnectedGamepads<'girl> {
-    /// Reference to the game controller subsystem.
-    gcs: &'girl sdl2::GameControllerSubsystem,
-    /// Reference to the joystick subsystem.
-    jcs: &'girl sdl2::JoystickSubsystem,
-    /// Current index being iterated.
-    idx: u32,
-}
+    /// Gets the [`InputContexts`] whose frame is started by every [`update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// let contexts = girl.contexts_mut();
+    /// let gameplay = contexts.declare("gameplay", 0, Button::A | Button::B);
+    /// let pause = contexts.declare("pause", 10, Button::A | Button::Start);
+    /// let mut paused = false;
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     girl.contexts_mut().activate(gameplay);
+    ///     if paused {
+    ///         girl.contexts_mut().activate(pause);
+    ///     }
+    ///
+    ///     if let Some(gamepad) = girl.gamepad(0) {
+    ///         let contexts = girl.contexts();
+    ///         if contexts.pressed(pause, &gamepad, Button::A) {
+    ///             // confirm the menu entry
+    ///         }
+    ///         if contexts.pressed(gameplay, &gamepad, Button::A) {
+    ///             // jump, never while paused
+    ///         }
+    ///         paused ^= contexts.pressed(gameplay, &gamepad, Button::Start);
+    ///     }
+    ///     # break;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub const fn contexts(&self) -> &InputContexts {
+        &self.contexts
+    }
 
-impl Iterator for ConnectedGamepads<'_> {
-    type Item = Gamepad;
+    /// Gets the [`InputContexts`] mutably, see [`contexts`].
+    ///
+    /// [`contexts`]: Self::contexts
+    #[must_use]
+    #[inline]
+    pub const fn contexts_mut(&mut self) -> &mut InputContexts {
+        &mut self.contexts
+    }
 
+    /// Adds an [`EventMiddleware`] processing every [`Event`] after the ones
+    /// already added, returning the handle to remove it with.
+    ///
+    /// Middleware runs after the [`Event`]s are translated and suppressed
+    /// while input is suspended, but before they are sequenced, so
+    /// [`event`], [`sequenced_event`] and [`event_blocking`] only see what
+    /// comes out of it. Polled input, e.g. [`Gamepad::buttons`], is not
+    /// affected, and neither is the state [`Girl`] tracks per [`Gamepad`]
+    /// from the raw SDL2 events.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ButtonRemap, MiddlewareCtx, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// let mut remap = ButtonRemap::new();
+    /// remap.set(Button::A, Button::B)?;
+    /// let remap = girl.add_middleware(Box::new(remap));
+    /// let log = |event: Event, _: &mut MiddlewareCtx<'_>| {
+    ///     println!("{event:?}");
+    ///     Some(event)
+    /// };
+    /// let _log = girl.add_middleware(Box::new(log));
+    ///
+    /// // logs presses of the physical A button as B
+    /// while girl.event().is_some() {}
+    ///
+    /// assert!(girl.remove_middleware(remap).is_some());
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`event_blocking`]: Self::event_blocking
+    /// [`sequenced_event`]: Self::sequenced_event
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        // skip over non-gamepads
-        while !self.gcs.is_game_controller(self.idx) {
-            self.idx = self.idx.checked_add(1)?;
+    pub fn add_middleware(
+        &mut self,
+        middleware: Box<dyn EventMiddleware>,
+    ) -> MiddlewareId {
+        self.middleware.add(middleware)
+    }
+
+    /// Retries failed [`Gamepad::enable_sensor`] calls on every [`update`]
+    /// for `window`, or stops retrying if [`None`], the default.
+    ///
+    /// Reports [`Event::SensorEnabled`] once a retry succeeds, or
+    /// [`Event::SensorEnableFailed`] once the window passes. Retries of
+    /// [`Gamepad`]s disconnecting meanwhile are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_sensor_retry_window(Some(Duration::from_secs(2)));
+    /// assert_eq!(girl.sensor_retry_window(), Some(Duration::from_secs(2)));
+    ///
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    /// if let Some(gyroscope) = gamepad.primary_gyroscope() {
+    ///     // retried in the background if the controller isn't ready yet
+    ///     _ = gamepad.enable_sensor(gyroscope);
+    /// }
+    /// # }
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::SensorEnableFailed { which, sensor } = event {
+    ///         eprintln!("no {sensor:?} on gamepad {which}");
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[inline]
+    pub fn set_sensor_retry_window(&mut self, window: Option<Duration>) {
+        let retry = SensorRetry::new(window, SensorRetry::DEFAULT_INTERVAL);
+        self.stages.set_sensor_retry(retry);
+        self.shared.set_retry_sensors(window.is_some());
+    }
+
+    /// Gets the window set with [`set_sensor_retry_window`].
+    ///
+    /// [`set_sensor_retry_window`]: Self::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub const fn sensor_retry_window(&self) -> Option<Duration> {
+        self.stages.sensor_retry().window()
+    }
+
+    /// Delivers only every `divisor`-th [`Event::ControllerSensorUpdated`]
+    /// of the [`Sensor`], counting the updates of every [`Gamepad`]
+    /// independently. A `divisor` of `1` delivers every update again.
+    ///
+    /// Skipped updates are dropped unless folded into the delivered one
+    /// with [`set_sensor_accumulation`]. See [`SensorDivider`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `divisor` is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SensorAccumulation, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// // 250 Hz down to ~60 Hz, integrating the skipped updates
+    /// girl.set_sensor_event_divisor(Sensor::Gyroscope, 4)?;
+    /// let sum = SensorAccumulation::Sum;
+    /// girl.set_sensor_accumulation(Sensor::Gyroscope, sum);
+    /// assert_eq!(girl.sensor_event_divisor(Sensor::Gyroscope), 4);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_sensor_accumulation`]: Self::set_sensor_accumulation
+    /// [`SensorDivider`]: crate::SensorDivider
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[inline]
+    pub fn set_sensor_event_divisor(
+        &mut self,
+        sensor: Sensor,
+        divisor: u32,
+    ) -> Result<(), Error> {
+        self.stages.divider_mut().set_divisor(sensor, divisor)
+    }
+
+    /// Gets the divisor set with [`set_sensor_event_divisor`], `1` by
+    /// default.
+    ///
+    /// [`set_sensor_event_divisor`]: Self::set_sensor_event_divisor
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub fn sensor_event_divisor(&self, sensor: Sensor) -> u32 {
+        self.stages.divider().divisor(sensor)
+    }
+
+    /// Sets how the sensor updates skipped with
+    /// [`set_sensor_event_divisor`] are folded into the delivered one, e.g.
+    /// averaged for an accelerometer or integrated for a gyroscope.
+    ///
+    /// [`set_sensor_event_divisor`]: Self::set_sensor_event_divisor
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[inline]
+    pub fn set_sensor_accumulation(
+        &mut self,
+        sensor: Sensor,
+        accumulation: SensorAccumulation,
+    ) {
+        self.stages.divider_mut().set_accumulation(sensor, accumulation);
+    }
+
+    /// Gets how the skipped sensor updates are folded into the delivered
+    /// one, see [`set_sensor_accumulation`].
+    ///
+    /// [`set_sensor_accumulation`]: Self::set_sensor_accumulation
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub fn sensor_accumulation(&self, sensor: Sensor) -> SensorAccumulation {
+        self.stages.divider().accumulation(sensor)
+    }
+
+    /// Removes an [`EventMiddleware`] added with [`add_middleware`],
+    /// returning it.
+    ///
+    /// Returns [`None`] if it was already removed.
+    ///
+    /// [`add_middleware`]: Self::add_middleware
+    #[inline]
+    pub fn remove_middleware(
+        &mut self,
+        id: MiddlewareId,
+    ) -> Option<Box<dyn EventMiddleware>> {
+        self.middleware.remove(id)
+    }
+
+    /// Polls for the next available input [`Event`] along with its sequence
+    /// number.
+    ///
+    /// Every translated [`Event`] gets a sequence number as it comes out of
+    /// the middleware, including the ones returned by [`event`] and
+    /// [`event_blocking`], so gaps in the sequence observed by a consumer
+    /// mean some [`Event`]s were dropped on the way. [`Girl`] holds at most
+    /// 256 sequenced [`Event`]s, dropping the oldest ones when they are not
+    /// polled in time and returning an [`Event::Overflow`] marker in their
+    /// place, see [`dropped_events`].
+    ///
+    /// Returns [`None`] if no events are currently available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// let mut last = None;
+    /// while let Some(sequenced) = girl.sequenced_event() {
+    ///     if let Some(prev) = last {
+    ///         assert_eq!(sequenced.missed_since(prev), 0);
+    ///     }
+    ///     last = Some(sequenced.seq);
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`event_blocking`]: Self::event_blocking
+    /// [`dropped_events`]: Self::dropped_events
+    #[must_use]
+    #[inline]
+    pub fn sequenced_event(&mut self) -> Option<SequencedEvent> {
+        let suspended = self.input_suspended();
+        loop {
+            if let Some(sequenced) = self.queue.pop() {
+                return Some(sequenced);
+            }
+            if let Some(event) = self.pending.pop_front() {
+                self.process(suspended, event);
+                continue;
+            }
+            let Some(sdl_event) = self.event_pump.as_mut()?.poll_event() else {
+                if !self.flush_motion(suspended) {
+                    return None;
+                }
+                continue;
+            };
+            if let Some(event) = self.translate(&sdl_event) {
+                self.process(suspended, event);
+            }
         }
-        let gc = self.gcs.open(self.idx).ok()?;
-        let js = self.jcs.open(self.idx).ok()?;
-        let gamepad = Gamepad::from_sdl(gc, js);
-        self.idx = self.idx.checked_add(1)?;
-        gamepad
     }
 
+    /// Gets the total number of sequenced [`Event`]s dropped because they
+    /// were not polled in time, see [`sequenced_event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.update();
+    /// if girl.dropped_events() > 0 {
+    ///     println!("poll events more often");
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`sequenced_event`]: Self::sequenced_event
+    #[must_use]
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.len();
-        (len, Some(len))
+    pub const fn dropped_events(&self) -> u64 {
+        self.queue.dropped()
     }
-}
 
-impl ExactSizeIterator for ConnectedGamepads<'_> {
+    /// Waits for and returns the next input [`Event`].
+    ///
+    /// Blocks until an [`Event`] is available. In eventless mode (see
+    /// [`new_eventless`]), keeps calling [`update`] until [`Girl`] itself
+    /// synthesizes an [`Event`].
+    ///
+    /// [`new_eventless`]: Self::new_eventless
+    /// [`update`]: Self::update
+    #[must_use]
     #[inline]
-    fn len(&self) -> usize {
-        self.gcs.num_joysticks().unwrap_or(0) as usize
+    pub fn event_blocking(&mut self) -> Event {
+        loop {
+            let suspended = self.input_suspended();
+            if let Some(sequenced) = self.queue.pop() {
+                return sequenced.event;
+            }
+            if let Some(event) = self.pending.pop_front() {
+                self.process(suspended, event);
+                continue;
+            }
+            let Some(polled) =
+                self.event_pump.as_mut().map(sdl2::EventPump::poll_event)
+            else {
+                thread::sleep(EVENTLESS_POLL_INTERVAL);
+                _ = self.update();
+                continue;
+            };
+            // only wait once the coalesced stick motion is out
+            let sdl_event = match polled {
+                Some(sdl_event) => sdl_event,
+                None if self.flush_motion(suspended) => continue,
+                None => match self.event_pump.as_mut() {
+                    Some(pump) => pump.wait_event(),
+                    None => continue,
+                },
+            };
+            if let Some(event) = self.translate(&sdl_event) {
+                self.process(suspended, event);
+            }
+        }
     }
+
+    /// Injects an [`Event`] as if it came from SDL2, e.g. to bridge input
+    /// received over the network, so code reading [`Event`]s can't tell
+    /// it apart from local input.
+    ///
+    /// The [`Event`] goes through the same translation as the SDL2 events,
+    /// so button presses update the held [`Button`]s and are reported as
+    /// [`Event::ControllerButtonDownWithState`] if enabled with
+    /// [`set_button_state_events`], and stick motion updates the cached
+    /// stick positions. It is then recorded right away like the ones SDL2
+    /// queues, so an injected press shows in [`just_pressed`] and
+    /// [`Gamepad::tapped_since_update`] after the next [`update`], and
+    /// touchpad [`Event`]s update the fingers `Gamepad::touchpad` reports
+    /// with the `touchpad` feature. Finally it goes through the same
+    /// filtering, middleware and sequencing as the [`Event`]s of SDL2 when
+    /// taken with [`event`] or [`drain_into`]. State read from SDL2
+    /// directly, e.g. [`Gamepad::buttons`], is not affected.
+    ///
+    /// # Ordering
+    ///
+    /// Injected [`Event`]s are delivered in the order they were injected,
+    /// after the [`Event`]s already taken off the SDL2 queue or synthesized
+    /// by [`Girl`], e.g. during [`update`], and before the ones still in
+    /// the SDL2 queue. Use `inject_sdl_event`, with the `sdl2-interop`
+    /// feature, to deliver an event after the ones SDL2 queued before it
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// let mut events = Vec::new();
+    /// girl.update();
+    /// _ = girl.drain_into(&mut events);
+    ///
+    /// let first = girl.gamepads().next().map(|gamepad| gamepad.id().raw());
+    /// let which = first.unwrap_or_default();
+    /// let press = Event::ControllerButtonDown { which, button: Button::A };
+    /// girl.inject(press);
+    ///
+    /// // seen by the trackers
+    /// let id = girl.resolve_id(which);
+    /// assert!(!girl.just_pressed(id, Button::A));
+    /// girl.update();
+    /// assert!(girl.just_pressed(id, Button::A));
+    /// if let Some(gamepad) = girl.gamepad_ref(id) {
+    ///     assert!(gamepad.tapped_since_update().contains(Button::A));
+    /// }
+    ///
+    /// // delivered and recorded like local input
+    /// events.clear();
+    /// # #[cfg(not(feature = "eventlog"))]
+    /// _ = girl.drain_into(&mut events);
+    /// # #[cfg(feature = "eventlog")]
+    /// # let mut log = girl::EventLogWriter::new(Vec::new()).unwrap();
+    /// # #[cfg(feature = "eventlog")]
+    /// events.extend(girl.tee_events(&mut log));
+    /// let pressed = |event: &Event| {
+    ///     matches!(*event, Event::ControllerButtonDown {
+    ///         which: from,
+    ///         button: Button::A,
+    ///     } if from == which)
+    /// };
+    /// assert!(events.iter().any(pressed));
+    /// # #[cfg(feature = "eventlog")]
+    /// # {
+    /// let log = log.into_inner().unwrap();
+    /// let read = girl::EventLogReader::new(&log[..]).unwrap();
+    /// let logged: Vec<_> = read.events().collect::<Result<_, _>>().unwrap();
+    /// assert!(logged.iter().any(pressed));
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// Presses are translated like the ones of SDL2:
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// girl.update();
+    /// while girl.event().is_some() {}
+    /// girl.set_button_state_events(true);
+    ///
+    /// let down = |button| Event::ControllerButtonDown { which: 0, button };
+    /// girl.inject(down(Button::LeftShoulder));
+    /// girl.inject(down(Button::Y));
+    /// let events: Vec<_> = std::iter::from_fn(|| girl.event()).collect();
+    /// assert!(matches!(events[..], [
+    ///     Event::ControllerButtonDownWithState {
+    ///         which: 0,
+    ///         button: Button::LeftShoulder,
+    ///         ..
+    ///     },
+    ///     Event::ControllerButtonDownWithState {
+    ///         which: 0,
+    ///         button: Button::Y,
+    ///         held: Button::LeftShoulder,
+    ///     },
+    /// ]));
+    ///
+    /// girl.update();
+    /// let id = girl.resolve_id(0);
+    /// assert!(girl.just_pressed(id, Button::LeftShoulder | Button::Y));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`event`]: Self::event
+    /// [`drain_into`]: Self::drain_into
+    /// [`just_pressed`]: Self::just_pressed
+    /// [`set_button_state_events`]: Self::set_button_state_events
+    #[inline]
+    pub fn inject(&mut self, event: Event) {
+        let event = self.translator.observe(event);
+        self.shared.record_input(&event);
+        if let Some(event) = self.refine(event) {
+            self.pending.push_back(event);
+        }
+    }
+
+    /// Injects an SDL2 event into the SDL2 queue, behind the ones already
+    /// queued, e.g. to bridge input received over the network. It is
+    /// recorded and translated exactly like a local one, see [`inject`]
+    /// to inject an [`Event`] directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in eventless mode (see [`new_eventless`]), as
+    /// nothing would take the event off the queue, or if SDL2 fails to
+    /// queue it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sdl2::{controller::Button as SdlButton, event::Event as SdlEvent};
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// let press = SdlEvent::ControllerButtonDown {
+    ///     timestamp: 0,
+    ///     which: 0,
+    ///     button: SdlButton::A,
+    /// };
+    /// girl.inject_sdl_event(press)?;
+    ///
+    /// let mut events = Vec::new();
+    /// _ = girl.drain_into(&mut events);
+    /// assert!(events.iter().any(|event| matches!(
+    ///     *event,
+    ///     Event::ControllerButtonDown { which: 0, button: Button::A },
+    /// )));
+    ///
+    /// drop(girl);
+    /// let mut girl = Girl::new_eventless()?;
+    /// let quit = SdlEvent::Quit { timestamp: 0 };
+    /// assert!(girl.inject_sdl_event(quit).is_err());
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`inject`]: Self::inject
+    /// [`new_eventless`]: Self::new_eventless
+    #[cfg(feature = "sdl2-interop")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2-interop")))]
+    #[inline]
+    pub fn inject_sdl_event(&mut self, event: SdlEvent) -> Result<(), Error> {
+        let op = "inject_sdl_event";
+        let result = if self.event_pump.is_none() {
+            Err(Error::sdl_static(op, ErrorCode::Sdl, "eventless mode"))
+        } else {
+            self.gcs
+                .sdl()
+                .event()
+                .and_then(|events| events.push_event(event))
+                .map_err(Error::sdl(op))
+        };
+        self.failures.observe(op, &result);
+        result
+    }
+
+    /// Translates an SDL2 event into an [`Event`], observing it for the
+    /// audio device matching first, queueing the [`Event`]s after the first
+    /// if the SDL2 event ends the coalescing of a stick motion, see
+    /// [`set_stick_coalescing`].
+    ///
+    /// [`set_stick_coalescing`]: Self::set_stick_coalescing
+    fn translate(&mut self, sdl_event: &SdlEvent) -> Option<Event> {
+        let started = self.perf.is_some().then(Instant::now);
+        self.observe_audio(sdl_event);
+        let translated = self.translator.feed(sdl_event);
+        if let (Some(perf), Some(start)) = (self.perf.as_mut(), started) {
+            perf.events_pumped = perf.events_pumped.saturating_add(1);
+            perf.count_queue_latency(sdl_event.get_timestamp());
+            if !translated.is_empty() {
+                perf.events_translated =
+                    perf.events_translated.saturating_add(1);
+            }
+            perf.translation_time =
+                perf.translation_time.saturating_add(start.elapsed());
+        }
+        let timestamp = sdl_event.get_timestamp();
+        let mut first = None;
+        for event in translated {
+            let Some(event) = self.observe_translated(timestamp, event) else {
+                continue;
+            };
+            if first.is_none() {
+                first = Some(event);
+            } else {
+                self.pending.push_back(event);
+            }
+        }
+        first
+    }
+
+    /// Processes the stick motion the [`Translator`] held back for
+    /// coalescing once the SDL2 queue is drained, returning whether there
+    /// was one, see [`set_stick_coalescing`].
+    ///
+    /// [`set_stick_coalescing`]: Self::set_stick_coalescing
+    fn flush_motion(&mut self, suspended: bool) -> bool {
+        let Some((timestamp, event)) = self.translator.flush_timed() else {
+            return false;
+        };
+        if let Some(event) = self.observe_translated(timestamp, event) {
+            self.process(suspended, event);
+        }
+        true
+    }
+
+    /// Observes an [`Event`] translated from an SDL2 event reported at the
+    /// `timestamp` for [`connection_quality`], then [`refine`]s it.
+    ///
+    /// [`connection_quality`]: Self::connection_quality
+    /// [`refine`]: Self::refine
+    fn observe_translated(
+        &mut self,
+        timestamp: u32,
+        event: Event,
+    ) -> Option<Event> {
+        let at = Duration::from_millis(timestamp.into());
+        self.connection.observe(&event, at);
+        self.refine(event)
+    }
+
+    /// Filters a translated or injected [`Event`] like the ones of SDL2,
+    /// reacting to the ones changing the state of [`Girl`].
+    fn refine(&mut self, event: Event) -> Option<Event> {
+        #[cfg(feature = "touchpad")]
+        let event = self.reject_touch(event)?;
+        #[cfg(feature = "touchpad")]
+        let event = self.identify_finger(event)?;
+        let event = self.debounce_connection(event)?;
+        if let Event::ControllerDeviceRemapped { which } = event {
+            self.diff_mapping(which);
+        }
+        Some(event)
+    }
+
+    /// Withholds touchpad [`Event`]s by the [`TouchRejection`] of their
+    /// touchpad, see [`Gamepad::set_touchpad_rejection`].
+    #[cfg(feature = "touchpad")]
+    fn reject_touch(&mut self, event: Event) -> Option<Event> {
+        let Event::ControllerTouchpad(touch) = event else {
+            return Some(event);
+        };
+        let id = self.shared.id(touch.which);
+        let touchpad = usize::try_from(touch.idx).unwrap_or(usize::MAX);
+        let rejections = self.shared.touch_rejection();
+        let rejection = rejections.get(&(id, touchpad));
+        if rejection.is_none() && !self.touch_rejectors.contains_key(&id) {
+            return Some(event);
+        }
+        let disabled = TouchRejection::disabled();
+        let touch = self
+            .touch_rejectors
+            .entry(id)
+            .or_default()
+            .filter(rejection.unwrap_or(&disabled), &touch);
+        drop(rejections);
+        touch.map(Event::ControllerTouchpad)
+    }
+
+    /// Gives touchpad [`Event`]s the stable [`FingerId`] of their finger,
+    /// queueing the move of a finger SDL2 moved to another slot after the
+    /// release of the finger it replaced, see [`FingerTracker`].
+    #[cfg(feature = "touchpad")]
+    fn identify_finger(&mut self, event: Event) -> Option<Event> {
+        let Event::ControllerTouchpad(touch) = event else {
+            return Some(event);
+        };
+        let id = self.shared.id(touch.which);
+        let tracker = self.finger_trackers.entry(id).or_default();
+        let mut identified = tracker
+            .identify_event(touch)
+            .into_iter()
+            .map(Event::ControllerTouchpad);
+        let first = identified.next();
+        self.pending.extend(identified);
+        first
+    }
+
+    /// Holds back the connection changes of flaky [`Gamepad`]s, see
+    /// [`set_connection_debounce`].
+    ///
+    /// [`set_connection_debounce`]: Self::set_connection_debounce
+    fn debounce_connection(&mut self, event: Event) -> Option<Event> {
+        if self.connection_debounce.window().is_none() {
+            return Some(event);
+        }
+        let guid = if let Event::ControllerDeviceAdded { which } = event {
+            self.jcs.device_guid(which).ok().map(Guid::from_sdl)
+        } else if let Event::ControllerDeviceRemoved { which } = event {
+            self.devices.get(&self.shared.id(which)).map(|info| info.guid)
+        } else {
+            return Some(event);
+        };
+        let Some(guid) = guid else {
+            return Some(event);
+        };
+        self.connection_debounce.observe(guid, event, Instant::now())
+    }
+
+    /// Takes a new [`MappingSnapshot`] of the remapped [`Gamepad`], queueing
+    /// [`Event::ControllerRemapDiff`] if it differs from the last one.
+    fn diff_mapping(&mut self, which: u32) {
+        let id = self.shared.id(which);
+        let Some(snapshot) =
+            self.pads.get(&id).and_then(|pad| MappingSnapshot::of(pad).ok())
+        else {
+            return;
+        };
+        let Some(previous) = self.mappings.insert(id, snapshot) else {
+            return;
+        };
+        let Some(current) = self.mappings.get(&id) else {
+            return;
+        };
+        let changes = previous.diff(current);
+        if !changes.is_empty() {
+            diag!(debug, "gamepad {id} remapped: {changes:?}");
+            self.pending
+                .push_back(Event::ControllerRemapDiff { which, changes });
+        }
+    }
+
+    /// Runs a translated or synthesized [`Event`] through the [`Stages`] and
+    /// the middleware unless it is suppressed, letting the [`Stages`] react
+    /// to it, and queueing what comes out sequenced.
+    fn process(&mut self, suspended: bool, event: Event) {
+        if !Self::suppressed(suspended, &event) {
+            let now = Instant::now();
+            let scope = Scope::new(now, &self.devices, Some(&self.shared));
+            let (middleware, processed) =
+                (&mut self.middleware, &mut self.processed);
+            let perf = self.perf.as_mut();
+            self.stages.run(scope, event, middleware, processed, perf);
+            let pads = &mut self.pads;
+            let mut ctx =
+                StageCtx::new(now, pads, &self.shared, &mut self.pending);
+            self.stages.react(&mut ctx);
+        }
+        while let Some(processed) = self.processed.pop_front() {
+            let sequenced = self.sequence(processed);
+            self.queue.push(sequenced);
+        }
+    }
+
+    /// Translates and [`process`]es the [`Event`]s [`Girl`] and SDL2 queued,
+    /// summarizing the ones added, see [`update`].
+    ///
+    /// [`process`]: Self::process
+    /// [`update`]: Self::update
+    fn translate_queued(&mut self) -> UpdateSummary {
+        let suspended = self.input_suspended();
+        self.summary = UpdateSummary::new();
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                self.process(suspended, event);
+                continue;
+            }
+            let Some(pump) = self.event_pump.as_mut() else {
+                break;
+            };
+            let Some(sdl_event) = pump.poll_event() else {
+                if !self.flush_motion(suspended) {
+                    break;
+                }
+                continue;
+            };
+            if let Some(event) = self.translate(&sdl_event) {
+                self.process(suspended, event);
+            }
+        }
+        self.summary
+    }
+
+    /// Matches [`Gamepad`]s with audio devices again if the SDL2 event
+    /// reports an audio device change.
+    fn observe_audio(&mut self, sdl_event: &SdlEvent) {
+        if let SdlEvent::AudioDeviceAdded { .. }
+        | SdlEvent::AudioDeviceRemoved { .. } = *sdl_event
+        {
+            self.match_audio_devices();
+        }
+    }
+
+    /// Lists the audio output devices and matches the [`Gamepad`]s with
+    /// them, queueing [`Event::ControllerAudioDeviceChanged`] for changed
+    /// matches.
+    fn match_audio_devices(&mut self) {
+        self.audio_outputs = audio::output_devices();
+        let identities: Vec<_> =
+            self.pads.values().map(AudioIdentity::of).collect();
+        let matched =
+            self.audio_matcher.assign(&identities, &self.audio_outputs);
+
+        let mut devices = self.shared.audio_devices();
+        let previous = mem::take(&mut *devices);
+        for (&id, device) in self.pads.keys().zip(matched) {
+            if let Some(name) =
+                device.and_then(|index| self.audio_outputs.get(index))
+            {
+                diag!(debug, "gamepad {id} exposes audio device {name:?}");
+                drop(devices.insert(id, name.clone()));
+            }
+            if devices.get(&id) != previous.get(&id) {
+                let which = id.raw();
+                self.pending
+                    .push_back(Event::ControllerAudioDeviceChanged { which });
+            }
+        }
+    }
+
+    /// Checks if the [`Event`] must be dropped because input is `suspended`.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn suppressed(suspended: bool, event: &Event) -> bool {
+        let suppressed = suspended && event.is_input();
+        if suppressed {
+            diag!(trace, "suppressed {event:?} while input is suspended");
+        }
+        suppressed
+    }
+
+    /// Assigns the next sequence number to the [`Event`].
+    fn sequence(&mut self, event: Event) -> SequencedEvent {
+        if let Event::ControllerDeviceAdded { which } = event {
+            diag!(info, "gamepad #{which} connected");
+        }
+        if let Event::ControllerDeviceRemoved { which } = event {
+            diag!(info, "gamepad #{which} disconnected");
+        }
+        if let Some(wear) = self.wear.as_mut() {
+            wear.observe(&event);
+        }
+        self.summary.observe(&event);
+
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        SequencedEvent::new(seq, event)
+    }
+
+    /// Gathers pending input events from [`Gamepad`] devices.
+    ///
+    /// Should be called regularly in your application's main loop, as otherwise
+    /// the [`Gamepad`] will report same inputs over and over again.
+    ///
+    /// Also executes [`Command`]s queued through [`GirlHandle`]s, refreshes
+    /// the [`GamepadState`]s they observe, reports stick tier changes (see
+    /// [`Gamepad::set_stick_tiers`]) and publishes the [`Button`]s tapped
+    /// since the previous call (see [`Gamepad::tapped_since_update`]). Also
+    /// keeps the attached [`DeviceHistory`] up to date, see
+    /// [`attach_history`], and starts a new frame of the [`InputContexts`],
+    /// see [`contexts`].
+    ///
+    /// Unless disabled with [`set_auto_tick`], then also advances the
+    /// time-driven layer to now, see [`tick`].
+    ///
+    /// Starts counting the work done over again, see [`perf_counters`].
+    ///
+    /// Finally translates the [`Event`]s queued so far, returning what
+    /// happened this cycle, e.g. to skip processing input when nothing did.
+    /// The [`UpdateSummary`] is tallied while translating, and the
+    /// [`Event`]s are still taken with [`event`] or [`drain_into`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// girl.update();
+    /// while girl.event().is_some() {}
+    ///
+    /// // empty cycle
+    /// let summary = girl.update();
+    /// assert!(summary.is_empty());
+    /// assert!(!summary.any_input && !summary.device_changes);
+    ///
+    /// // axis only
+    /// girl.inject(Event::stick_motion(0, Stick::Left, [0.0, 0.5]));
+    /// let summary = girl.update();
+    /// assert!(summary.any_input && !summary.device_changes);
+    /// assert_eq!(summary.events, 1);
+    /// let motion = girl.event();
+    /// assert!(matches!(motion, Some(Event::ControllerStickMotion { .. })));
+    ///
+    /// // device change only
+    /// girl.inject(Event::device_removed(7));
+    /// let summary = girl.update();
+    /// assert!(!summary.any_input && summary.device_changes);
+    /// assert_eq!(summary.events, 1);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`attach_history`]: Self::attach_history
+    /// [`contexts`]: Self::contexts
+    /// [`drain_into`]: Self::drain_into
+    /// [`event`]: Self::event
+    /// [`perf_counters`]: Self::perf_counters
+    /// [`set_auto_tick`]: Self::set_auto_tick
+    /// [`tick`]: Self::tick
+    /// [`Command`]: crate::Command
+    #[inline]
+    pub fn update(&mut self) -> UpdateSummary {
+        if let Some(perf) = self.perf.as_mut() {
+            let ffi_calls = self.shared.take_ffi_calls();
+            perf.ffi_calls = perf.ffi_calls.saturating_add(ffi_calls);
+            self.perf_total.merge(&mem::take(perf));
+        }
+        if let Some(pump) = self.event_pump.as_mut() {
+            pump.pump_events();
+            debug_assert!(self.gcs.event_state(), "unhandled events");
+        } else {
+            self.gcs.update();
+        }
+        let now = Instant::now();
+        self.shared.watchdog().updated(now);
+        self.shared.taps().publish();
+        self.contexts.begin_frame();
+        if self.shared.take_reset_suspected() {
+            diag!(warn, "controller handles keep failing, reopen them");
+            self.pending.push_back(Event::SubsystemReset);
+        }
+        let settled = self.connection_debounce.poll(now);
+        self.pending.extend(settled);
+
+        self.refresh_pads();
+        let pads = &mut self.pads;
+        let mut ctx = StageCtx::new(now, pads, &self.shared, &mut self.pending);
+        self.stages.update(&mut ctx);
+        self.execute_requests();
+        self.publish_states();
+        if !self.input_suspended() {
+            self.update_tiers();
+        }
+
+        if self.auto_tick {
+            self.tick(now.saturating_duration_since(self.clock));
+        }
+        self.translate_queued()
+    }
+
+    /// Updates like [`update`], then takes all the [`Event`]s into `events`
+    /// like [`drain_into`], returning the [`UpdateSummary`] of [`update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// let sdl = sdl2::init().map_err(girl::Error::Sdl2Init)?;
+    /// let sdl_events = sdl.event().map_err(girl::Error::Sdl2Init)?;
+    /// let mut events = Vec::new();
+    ///
+    /// // empty cycle
+    /// let summary = girl.update_and_collect(&mut events);
+    /// assert!(summary.is_empty() && !summary.any_input);
+    ///
+    /// // axis only
+    /// let motion = SdlEvent::ControllerAxisMotion {
+    ///     timestamp: 0,
+    ///     which: 0,
+    ///     axis: Axis::TriggerLeft,
+    ///     value: i16::MAX,
+    /// };
+    /// sdl_events.push_event(motion).unwrap();
+    /// let summary = girl.update_and_collect(&mut events);
+    /// assert!(summary.any_input && !summary.device_changes);
+    /// assert_eq!(summary.events, 1);
+    /// assert!(matches!(events[..], [Event::ControllerTriggerMotion { .. }]));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`drain_into`]: Self::drain_into
+    #[inline]
+    pub fn update_and_collect(
+        &mut self,
+        events: &mut Vec<Event>,
+    ) -> UpdateSummary {
+        let summary = self.update();
+        _ = self.drain_into(events);
+        summary
+    }
+
+    /// Advances the time-driven layer by `dt`, separately from pumping
+    /// events with [`update`], e.g. from the fixed timestep of a simulation.
+    ///
+    /// The layer runs on its own clock, starting when the [`Girl`] is
+    /// created and advanced only by the supplied `dt`, so it behaves the
+    /// same however often [`update`] is called and can be tested without
+    /// waiting for real time. It includes timed effects of tracked
+    /// [`Gamepad`]s (see [`Gamepad::tick`] and
+    /// [`identify_all_sequentially`]), `Event::RumbleFinished`, repeats
+    /// of stick navigation (see [`set_stick_navigation`]), idle tracking
+    /// (see [`set_idle_config`]), powering off idle [`Gamepad`]s (see
+    /// [`set_power_off_idle`]) and LED colors bound to input (see
+    /// [`add_led_binding`]). Rumble started on a [`Gamepad`] directly
+    /// is timed with the wall clock, so keep the sum of `dt` close to real
+    /// time to report it finished in time.
+    ///
+    /// [`update`] ticks with the time since the previous tick by default,
+    /// disable that with [`set_auto_tick`] to call this instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{IdleConfig, prelude::*};
+    /// let mut girl = Girl::new_eventless()?;
+    /// girl.set_auto_tick(false);
+    /// let mut config = IdleConfig::new();
+    /// config.threshold = Some(Duration::from_secs(5));
+    /// girl.set_idle_config(config);
+    ///
+    /// let dt = Duration::from_millis(100);
+    /// for _ in 0..49 {
+    ///     girl.tick(dt);
+    /// }
+    /// assert!(girl.event().is_none());
+    /// assert_eq!(girl.idle_time(), Duration::from_millis(4900));
+    ///
+    /// // exactly 5 seconds in, however long this took for real
+    /// girl.tick(dt);
+    /// let Some(Event::InputIdle { after }) = girl.event() else {
+    ///     panic!("not idle");
+    /// };
+    /// assert_eq!(after, Duration::from_secs(5));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`identify_all_sequentially`]: Self::identify_all_sequentially
+    /// [`set_stick_navigation`]: Self::set_stick_navigation
+    /// [`set_idle_config`]: Self::set_idle_config
+    /// [`set_power_off_idle`]: Self::set_power_off_idle
+    /// [`add_led_binding`]: Self::add_led_binding
+    /// [`set_auto_tick`]: Self::set_auto_tick
+    #[inline]
+    pub fn tick(&mut self, dt: Duration) {
+        self.clock = self.clock.checked_add(dt).unwrap_or(self.clock);
+        let now = self.clock;
+        self.tick_effects(now);
+        if !self.input_suspended() {
+            self.update_navigation(now);
+        }
+        let pads = &mut self.pads;
+        let mut ctx = StageCtx::new(now, pads, &self.shared, &mut self.pending);
+        self.stages.tick(&mut ctx);
+    }
+
+    /// Sets whether [`update`] also advances the time-driven layer with the
+    /// wall clock, see [`tick`]. Enabled by default, disable it to drive
+    /// [`tick`] with your own `dt`.
+    ///
+    /// [`update`]: Self::update
+    /// [`tick`]: Self::tick
+    #[inline]
+    pub const fn set_auto_tick(&mut self, enabled: bool) {
+        self.auto_tick = enabled;
+    }
+
+    /// Checks if [`update`] advances the time-driven layer, see
+    /// [`set_auto_tick`].
+    ///
+    /// [`update`]: Self::update
+    /// [`set_auto_tick`]: Self::set_auto_tick
+    #[must_use]
+    #[inline]
+    pub const fn auto_tick(&self) -> bool {
+        self.auto_tick
+    }
+
+    /// Sets the minimal time between two controller updates of
+    /// [`Gamepad::read_fresh`], across all [`Gamepad`]s,
+    /// [`Gamepad::FRESH_READ_INTERVAL`] by default. Reads in between return
+    /// the state of the last update, so the driver isn't hammered by reads
+    /// in a tight loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new_eventless()?;
+    /// assert_eq!(girl.fresh_read_interval(), Gamepad::FRESH_READ_INTERVAL);
+    ///
+    /// girl.set_fresh_read_interval(Duration::from_micros(250));
+    /// assert_eq!(girl.fresh_read_interval(), Duration::from_micros(250));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_fresh_read_interval(&mut self, interval: Duration) {
+        self.shared.set_fresh_read_interval(interval);
+    }
+
+    /// Gets the interval set with [`set_fresh_read_interval`].
+    ///
+    /// [`set_fresh_read_interval`]: Self::set_fresh_read_interval
+    #[must_use]
+    #[inline]
+    pub fn fresh_read_interval(&self) -> Duration {
+        self.shared.fresh_read_interval()
+    }
+
+    /// Sets whether the work done by [`Girl`] is counted, see
+    /// [`perf_counters`]. Disabled by default, and then costs little more
+    /// than a check per SDL2 event.
+    ///
+    /// Disabling keeps the session totals, see [`perf_totals`].
+    ///
+    /// [`perf_counters`]: Self::perf_counters
+    /// [`perf_totals`]: Self::perf_totals
+    #[inline]
+    pub fn set_perf_counters(&mut self, enabled: bool) {
+        match (enabled, self.perf.take()) {
+            (true, Some(perf)) => self.perf = Some(perf),
+            (true, None) => {
+                // reads while disabled aren't counted
+                _ = self.shared.take_ffi_calls();
+                self.perf = Some(PerfCounters::new());
+            }
+            (false, Some(mut perf)) => {
+                let ffi_calls = self.shared.take_ffi_calls();
+                perf.ffi_calls = perf.ffi_calls.saturating_add(ffi_calls);
+                self.perf_total.merge(&perf);
+            }
+            (false, None) => {}
+        }
+    }
+
+    /// Checks if the work done by [`Girl`] is counted, see
+    /// [`set_perf_counters`].
+    ///
+    /// [`set_perf_counters`]: Self::set_perf_counters
+    #[must_use]
+    #[inline]
+    pub const fn perf_counters_enabled(&self) -> bool {
+        self.perf.is_some()
+    }
+
+    /// Gets the work done since the last [`update`], e.g. to tell how much
+    /// of the frame budget input handling takes.
+    ///
+    /// Empty unless enabled with [`set_perf_counters`]. Serialize it with
+    /// the `serde` feature for telemetry.
+    ///
+    /// # Examples
+    ///
+    /// A scripted batch of SDL2 events, one of them not for controllers:
+    ///
+    /// ```
+    /// # use sdl2::{controller::Button, event::Event as SdlEvent};
+    /// # use girl::{MiddlewareCtx, prelude::*};
+    /// let mut girl = Girl::new()?;
+    /// let sdl = sdl2::init().map_err(girl::Error::Sdl2Init)?;
+    /// let events = sdl.event().map_err(girl::Error::Sdl2Init)?;
+    /// let pass = |event: Event, _: &mut MiddlewareCtx<'_>| Some(event);
+    /// let pass = girl.add_middleware(Box::new(pass));
+    /// girl.set_perf_counters(true);
+    /// girl.update();
+    ///
+    /// let press = |button| SdlEvent::ControllerButtonDown {
+    ///     timestamp: 0,
+    ///     which: 0,
+    ///     button,
+    /// };
+    /// let joystick =
+    ///     SdlEvent::JoyButtonDown { timestamp: 0, which: 0, button_idx: 0 };
+    /// for event in [press(Button::A), joystick, press(Button::B)] {
+    ///     events.push_event(event).unwrap();
+    /// }
+    /// while girl.event().is_some() {}
+    ///
+    /// let counters = girl.perf_counters();
+    /// assert_eq!(counters.events_pumped, 3);
+    /// assert_eq!(counters.events_translated, 2);
+    /// assert_eq!(counters.allocations, 2);
+    /// assert_eq!(counters.ffi_calls, 0);
+    /// assert!(counters.max_queue_latency <= counters.queue_latency);
+    /// assert!(counters.middleware_time.contains_key(&pass));
+    ///
+    /// // counted over again from every update, but kept in the totals
+    /// girl.update();
+    /// assert_eq!(girl.perf_counters().events_pumped, 0);
+    /// assert_eq!(girl.perf_totals().events_translated, 2);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_perf_counters`]: Self::set_perf_counters
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub fn perf_counters(&self) -> PerfCounters {
+        let Some(perf) = self.perf.as_ref() else {
+            return PerfCounters::new();
+        };
+        let mut counters = perf.clone();
+        counters.ffi_calls =
+            counters.ffi_calls.saturating_add(self.shared.ffi_calls());
+        counters
+    }
+
+    /// Gets the work done since [`set_perf_counters`] first enabled
+    /// counting, see [`perf_counters`].
+    ///
+    /// [`perf_counters`]: Self::perf_counters
+    /// [`set_perf_counters`]: Self::set_perf_counters
+    #[must_use]
+    #[inline]
+    pub fn perf_totals(&self) -> PerfCounters {
+        let mut totals = self.perf_total.clone();
+        if self.perf.is_some() {
+            totals.merge(&self.perf_counters());
+        }
+        totals
+    }
+
+    /// Starts scheduled [`Gamepad::identify`] calls, advances timed effects
+    /// of tracked [`Gamepad`]s and reports finished rumble effects.
+    fn tick_effects(&mut self, now: Instant) {
+        let leds_paused = self.stages.background().leds_paused();
+        while let Some(&(id, start, duration)) = self.identify_schedule.front()
+            && start <= now
+            && !leds_paused
+        {
+            _ = self.identify_schedule.pop_front();
+            if let Some(pad) = self.pads.get_mut(&id) {
+                // identification is best-effort
+                drop(pad.identify_at(duration, now));
+            }
+        }
+
+        for pad in self.pads.values_mut() {
+            // effects are best-effort, the pad may be gone already
+            drop(pad.tick(now));
+        }
+
+        if !leds_paused && !self.led_bindings.is_empty() {
+            for (&id, pad) in &mut self.pads {
+                if !pad.has_led() {
+                    continue;
+                }
+                let state = pad.state();
+                _ = self.led_bindings.apply(now, id, &state, |color| {
+                    pad.set_led(color.red, color.green, color.blue)
+                });
+            }
+        }
+
+        #[cfg(feature = "rumble")]
+        {
+            let mut rumble = self.shared.rumble();
+            rumble.drain_finished(now, |id, cancelled| {
+                let which = id.raw();
+                let finished = Event::RumbleFinished { which, cancelled };
+                self.pending.push_back(finished);
+            });
+            let played = rumble.take_played();
+            drop(rumble);
+            if let Some(wear) = self.wear.as_mut() {
+                for (id, time) in played {
+                    wear.add_rumble(id, time);
+                }
+            }
+        }
+    }
+
+    /// Recovers from a controller subsystem reset, e.g. after a driver reset
+    /// invalidated every controller handle at once, see
+    /// [`Event::SubsystemReset`].
+    ///
+    /// Closes every [`Gamepad`] tracked by [`Girl`], tears the SDL2
+    /// controller and joystick subsystems down and initializes them again,
+    /// then opens the connected devices, carrying the settings of each one
+    /// over to the device with the same [`Guid`]: its [`InputProfile`],
+    /// learned deadzones, stick smoothing, sideways mode, preferred Joy-Con,
+    /// touchpad sizes and LED color. The [`set_mapping_override`]s are
+    /// applied again.
+    ///
+    /// Tearing the subsystems down invalidates every [`Gamepad`] handle, so
+    /// [`Girl`] must own the only ones: drop the others first and obtain
+    /// them again with [`gamepad`]. [`reopen_gamepads`] recovers without
+    /// tearing the subsystems down.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if [`Gamepad`] handles outside of
+    /// [`Girl`] are alive, or an error if SDL2 fails to initialize the
+    /// subsystems again or to enumerate the devices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::SubsystemReset = event {
+    ///         let report = girl.reinitialize()?;
+    ///         for (_, guid) in &report.lost {
+    ///             println!("controller {guid} needs to be reconnected");
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`set_mapping_override`]: Self::set_mapping_override
+    /// [`gamepad`]: Self::gamepad
+    /// [`reopen_gamepads`]: Self::reopen_gamepads
+    #[inline]
+    pub fn reinitialize(&mut self) -> Result<RecoveryReport, Error> {
+        // every `Gamepad` shares the state, so the count tells if any is
+        // left outside of `self.pads`
+        if Arc::strong_count(&self.shared) > self.pads.len().saturating_add(1) {
+            let what = "Gamepad handles outside of Girl are alive";
+            return Err(Error::InvalidArgument { what });
+        }
+        diag!(warn, "reinitializing the controller subsystems");
+        let overrides: Vec<_> = self
+            .mapping_overrides
+            .keys()
+            .filter_map(|guid| self.mapping_for_guid(guid))
+            .collect();
+        let previous = self.close_pads();
+        let op = "reinitialize";
+        let result = self.restart_subsystems(op);
+        self.failures.observe(op, &result);
+        result?;
+        for mapping in &overrides {
+            let added = self.gcs.add_mapping(mapping).map_err(Error::sdl(op));
+            self.failures.observe(op, &added);
+        }
+        self.reopen(op, previous)
+    }
+
+    /// Recovers from a controller subsystem reset like [`reinitialize`],
+    /// without tearing the SDL2 subsystems down.
+    ///
+    /// Closes every [`Gamepad`] tracked by [`Girl`] and opens the connected
+    /// ones again, carrying the settings of each one over to the device with
+    /// the same [`Guid`]. [`Gamepad`] handles held outside of [`Girl`] stay
+    /// alive, but must be obtained again with [`gamepad`] to read the
+    /// reopened devices. If reopening the devices doesn't help, drop them
+    /// and call [`reinitialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 fails to enumerate the devices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::SubsystemReset = event {
+    ///         let report = girl.reopen_gamepads()?;
+    ///         for (_, guid) in &report.lost {
+    ///             println!("controller {guid} needs to be reconnected");
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`reinitialize`]: Self::reinitialize
+    /// [`gamepad`]: Self::gamepad
+    #[inline]
+    pub fn reopen_gamepads(&mut self) -> Result<RecoveryReport, Error> {
+        diag!(warn, "reopening controllers");
+        let previous = self.close_pads();
+        self.reopen("reopen_gamepads", previous)
+    }
+
+    /// Closes every [`Gamepad`] tracked by [`Girl`] and forgets their
+    /// state, returning the [`Guid`] and settings of each.
+    fn close_pads(&mut self) -> Vec<(GamepadId, Guid, PadSettings)> {
+        let previous = mem::take(&mut self.pads);
+        if let Some(wear) = self.wear.as_mut() {
+            let now = Instant::now();
+            for &id in previous.keys() {
+                wear.disconnect(id, now);
+            }
+        }
+        self.navigators.clear();
+        self.tiers.clear();
+        self.mappings.clear();
+        self.identify_schedule.clear();
+        for &id in previous.keys() {
+            self.stages.closed(id);
+        }
+        self.connection_debounce.clear();
+        #[cfg(feature = "touchpad")]
+        self.touch_rejectors.clear();
+        #[cfg(feature = "touchpad")]
+        self.finger_trackers.clear();
+        self.translator.reset();
+        previous
+            .iter()
+            .map(|(&id, pad)| (id, self.device_guid(id, pad), pad.settings()))
+            .collect()
+    }
+
+    /// Quits the SDL2 controller and joystick subsystems and initializes
+    /// them again, once no [`Gamepad`] is left to use them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 fails to initialize them again.
+    fn restart_subsystems(&self, op: &'static str) -> Result<(), Error> {
+        // SDL2 counts how many times each subsystem is initialized: the
+        // controller one once by `self.gcs`, and the joystick one by both
+        // `self.gcs` and `self.jcs`, so quit and initialize each in turn
+        for flags in
+            [sdl2_sys::SDL_INIT_GAMECONTROLLER, sdl2_sys::SDL_INIT_JOYSTICK]
+        {
+            // SAFETY: SDL is alive and `self.pads` is empty, the caller
+            //         checked that no other `Gamepad` uses the subsystems.
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            unsafe {
+                sdl2_sys::SDL_QuitSubSystem(flags);
+            }
+        }
+        for flags in
+            [sdl2_sys::SDL_INIT_JOYSTICK, sdl2_sys::SDL_INIT_GAMECONTROLLER]
+        {
+            // SAFETY: SDL is alive, as `self.gcs` keeps it initialized.
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            let status = unsafe { sdl2_sys::SDL_InitSubSystem(flags) };
+            if status < 0i32 {
+                return Err(Error::sdl(op)(sdl2::get_error()));
+            }
+        }
+        if self.event_pump.is_none() {
+            // nothing would drain the SDL2 event queue
+            self.gcs.set_event_state(false);
+            self.jcs.set_event_state(false);
+        }
+        Ok(())
+    }
+
+    /// Opens the connected devices after [`close_pads`], restoring the
+    /// settings of the `previous` [`Gamepad`]s by [`Guid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 fails to enumerate the devices.
+    ///
+    /// [`close_pads`]: Self::close_pads
+    fn reopen(
+        &mut self,
+        op: &'static str,
+        previous: Vec<(GamepadId, Guid, PadSettings)>,
+    ) -> Result<RecoveryReport, Error> {
+        self.gcs.update();
+        let result = self.gcs.num_joysticks().map_err(Error::sdl(op));
+        self.failures.observe(op, &result);
+        _ = result?;
+        self.shared.clear_device_failures();
+        self.shared.ids().reset();
+        self.refresh_pads();
+
+        let reopened = self
+            .pads
+            .iter()
+            .map(|(&id, pad)| (id, self.device_guid(id, pad)))
+            .collect();
+        let pads = &mut self.pads;
+        let report = RecoveryReport::pair(previous, reopened, |id, saved| {
+            if let Some(pad) = pads.get_mut(&id) {
+                pad.restore(saved);
+            }
+        });
+        self.led_bindings.reassign(&report.recovered);
+
+        diag!(
+            info,
+            "recovered {} controllers, lost {}",
+            report.recovered.len(),
+            report.lost.len(),
+        );
+        Ok(report)
+    }
+
+    /// Gets the [`Guid`] of the [`Gamepad`] with the [`GamepadId`], as
+    /// recorded on connection.
+    fn device_guid(&self, id: GamepadId, pad: &Gamepad) -> Guid {
+        self.devices.get(&id).map_or_else(|| pad.guid(), |info| info.guid)
+    }
+
+    /// Plays [`Gamepad::identify`] on every connected [`Gamepad`] in turn,
+    /// each for `gap`, so players can tell which controller is which.
+    ///
+    /// Replaces any identification sequence still in progress. Progress is
+    /// driven by [`tick`]. A `gap` of [`Duration::ZERO`], or longer than
+    /// [`EffectDuration::MAX`], just stops the sequence, see
+    /// [`EffectDuration`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.update();
+    /// girl.identify_all_sequentially(Duration::from_secs(1));
+    /// loop {
+    ///     girl.update();
+    ///     // render "player N, your controller is vibrating" prompt
+    ///     # break;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`tick`]: Self::tick
+    #[inline]
+    pub fn identify_all_sequentially(&mut self, gap: Duration) {
+        if !EffectDuration::new(gap).is_ok_and(|effect| !effect.is_stop()) {
+            self.identify_schedule.clear();
+            return;
+        }
+        let now = self.clock;
+        self.identify_schedule = self
+            .pads
+            .keys()
+            .zip(0u32..)
+            .filter_map(|(&id, turn)| {
+                let start = now.checked_add(gap.checked_mul(turn)?)?;
+                Some((id, start, gap))
+            })
+            .collect();
+    }
+
+    /// Drops disconnected [`Gamepad`]s from the internal cache and opens newly
+    /// connected ones.
+    fn refresh_pads(&mut self) {
+        if let Some(history) = self.history.as_mut() {
+            let now = SystemTime::now();
+            for pad in self.pads.values() {
+                history.observe(pad, now);
+            }
+        }
+        let connected = self.pads.len();
+        let mut wear = self.wear.as_mut();
+        let mappings = &mut self.mappings;
+        let stages = &mut self.stages;
+        let led_bindings = &mut self.led_bindings;
+        #[cfg(feature = "touchpad")]
+        let (shared, touch_rejectors, finger_trackers) = (
+            &self.shared,
+            &mut self.touch_rejectors,
+            &mut self.finger_trackers,
+        );
+        self.pads.retain(|&id, pad| {
+            let alive = pad.connected();
+            if !alive {
+                drop(mappings.remove(&id));
+                stages.closed(id);
+                led_bindings.forget(id);
+            }
+            #[cfg(feature = "touchpad")]
+            if !alive {
+                drop(touch_rejectors.remove(&id));
+                drop(finger_trackers.remove(&id));
+                shared.touch_rejection().retain(|&(of, _), _| of != id);
+            }
+            if !alive && let Some(wear) = wear.as_deref_mut() {
+                wear.disconnect(id, Instant::now());
+            }
+            alive
+        });
+        let mut changed = self.pads.len() != connected;
+
+        let count = self.gcs.num_joysticks().unwrap_or(0);
+        for index in 0..count {
+            if !self.gcs.is_game_controller(index) {
+                continue;
+            }
+            let Some(id) = device_instance_id(index)
+                .map(|instance| self.shared.id(instance.raw()))
+            else {
+                continue;
+            };
+
+            if !self.pads.contains_key(&id)
+                && let Some(pad) = self.gamepad(index)
+            {
+                let info = DeviceInfo::from_gamepad(&pad);
+                drop(self.devices.insert(pad.id(), info));
+                if let Some(history) = self.history.as_mut() {
+                    history.observe(&pad, SystemTime::now());
+                }
+                if let Some(metrics) = self.wear.as_mut() {
+                    metrics.connect(pad.id(), pad.guid(), Instant::now());
+                }
+                if let Ok(snapshot) = MappingSnapshot::of(&pad) {
+                    drop(self.mappings.insert(pad.id(), snapshot));
+                }
+                self.stages.opened(&pad);
+                drop(self.pads.insert(pad.id(), pad));
+                changed = true;
+            }
+        }
+        if changed {
+            self.match_audio_devices();
+        }
+    }
+
+    /// Executes [`Command`]s queued through [`GirlHandle`]s.
+    ///
+    /// [`Command`]: crate::Command
+    fn execute_requests(&mut self) {
+        while let Ok(request) = self.requests_rx.try_recv() {
+            let result = self
+                .pads
+                .get_mut(&request.id)
+                .ok_or(Error::GamepadNotFound(request.id))
+                .and_then(|pad| request.command.execute(pad));
+            if let Some(reply) = request.reply {
+                // the receipt may have been dropped, that's fine
+                drop(reply.send(result));
+            }
+        }
+    }
+
+    /// Publishes fresh [`GamepadState`]s to [`GirlHandle`]s, if there are any.
+    fn publish_states(&self) {
+        if Arc::strong_count(&self.states) == 1 {
+            return;
+        }
+
+        let mut states =
+            self.states.write().unwrap_or_else(PoisonError::into_inner);
+        states.clear();
+        states.extend(self.pads.iter().map(|(&id, pad)| (id, pad.state())));
+    }
+
+    /// Feeds current [`Stick`] positions into the [`StickNavigator`]s,
+    /// queueing the resulting [`Event`]s.
+    fn update_navigation(&mut self, now: Instant) {
+        let pads = &self.pads;
+        self.navigators.retain(|&(id, _), _| pads.contains_key(&id));
+
+        for (&id, pad) in pads {
+            for (&stick, &config) in &self.navigation {
+                let pending = &mut self.pending;
+                self.navigators
+                    .entry((id, stick))
+                    .or_insert_with(|| StickNavigator::new(config))
+                    .update(pad.stick(stick), now, |action, direction| {
+                        pending.push_back(Event::from_navigation(
+                            id.raw(),
+                            stick,
+                            action,
+                            direction,
+                        ));
+                    });
+            }
+        }
+    }
+
+    /// Feeds current [`Stick`] magnitudes into the [`StickTiers`] set with
+    /// [`Gamepad::set_stick_tiers`], queueing the resulting [`Event`]s.
+    ///
+    /// Tiers of disconnected [`Gamepad`]s are dropped along with their
+    /// thresholds.
+    fn update_tiers(&mut self) {
+        let pads = &self.pads;
+        let mut thresholds = self.shared.stick_tiers();
+        thresholds.retain(|&(id, _), _| pads.contains_key(&id));
+        self.tiers.retain(|key, tiers| {
+            thresholds.get(key).is_some_and(|set| set == tiers.thresholds())
+        });
+
+        for (&(id, stick), set) in thresholds.iter() {
+            let Some(pad) = pads.get(&id) else {
+                continue;
+            };
+            let tiers = self
+                .tiers
+                .entry((id, stick))
+                .or_insert_with(|| StickTiers::new(set));
+            if let Some((from, to)) = tiers.update(pad.stick_magnitude(stick)) {
+                let which = id.raw();
+                self.pending.push_back(Event::StickTierChanged {
+                    which,
+                    stick,
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    /// Sets whether button presses are reported as
+    /// [`Event::ControllerButtonDownWithState`], carrying the other
+    /// [`Button`]s held at that moment, instead of
+    /// [`Event::ControllerButtonDown`]. Disabled by default.
+    ///
+    /// The held [`Button`]s are tracked as events are translated, so presses
+    /// earlier in the same batch are included, unlike [`Gamepad::buttons`]
+    /// which only reports the state as of the last [`update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_button_state_events(true);
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::ControllerButtonDownWithState {
+    ///         button: Button::Y,
+    ///         held,
+    ///         ..
+    ///     } = event
+    ///         && held.contains(Button::LeftShoulder)
+    ///     {
+    ///         // trigger the shortcut
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[inline]
+    pub const fn set_button_state_events(&mut self, enabled: bool) {
+        self.translator.set_button_state(enabled);
+    }
+
+    /// Checks if button presses are reported as
+    /// [`Event::ControllerButtonDownWithState`], see
+    /// [`set_button_state_events`].
+    ///
+    /// [`set_button_state_events`]: Self::set_button_state_events
+    #[must_use]
+    #[inline]
+    pub const fn button_state_events(&self) -> bool {
+        self.translator.button_state()
+    }
+
+    /// Sets whether the motion of a stick reported at the same timestamp is
+    /// coalesced into a single [`Event::ControllerStickMotion`] with the
+    /// final `[x, y]` position, instead of one per moved axis. Disabled by
+    /// default.
+    ///
+    /// The coalesced motion is reported once the next SDL2 event is
+    /// translated or the SDL2 queue is drained, so it is never held back
+    /// past the [`update`] it was pumped in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sdl2::{controller::Axis, event::Event as SdlEvent};
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// let sdl = sdl2::init().map_err(girl::Error::Sdl2Init)?;
+    /// let events = sdl.event().map_err(girl::Error::Sdl2Init)?;
+    /// girl.set_stick_coalescing(true);
+    ///
+    /// for axis in [Axis::LeftX, Axis::LeftY] {
+    ///     let motion = SdlEvent::ControllerAxisMotion {
+    ///         timestamp: 7,
+    ///         which: 0,
+    ///         axis,
+    ///         value: i16::MAX,
+    ///     };
+    ///     events.push_event(motion).unwrap();
+    /// }
+    /// let moved: Vec<_> = std::iter::from_fn(|| girl.event()).collect();
+    /// let [Event::ControllerStickMotion { offset, .. }] = moved[..] else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(offset, [1.0, 1.0]);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[inline]
+    pub const fn set_stick_coalescing(&mut self, enabled: bool) {
+        self.translator.set_coalescing(enabled);
+    }
+
+    /// Checks if the motion of a stick reported at the same timestamp is
+    /// coalesced, see [`set_stick_coalescing`].
+    ///
+    /// [`set_stick_coalescing`]: Self::set_stick_coalescing
+    #[must_use]
+    #[inline]
+    pub const fn stick_coalescing(&self) -> bool {
+        self.translator.coalescing()
+    }
+
+    /// Sets the [`AudioMatcher`] matching [`Gamepad`]s with the audio
+    /// devices they expose, matching them again right away, see
+    /// [`Gamepad::audio_device_hint`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{AudioMatcher, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// let mut matcher = AudioMatcher::new();
+    /// matcher.set_keywords(ControllerType::Ps5, vec!["DualSense".to_owned()]);
+    /// girl.set_audio_matcher(matcher);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_audio_matcher(&mut self, matcher: AudioMatcher) {
+        self.audio_matcher = matcher;
+        self.match_audio_devices();
+    }
+
+    /// Gets the [`AudioMatcher`], see [`set_audio_matcher`].
+    ///
+    /// [`set_audio_matcher`]: Self::set_audio_matcher
+    #[must_use]
+    #[inline]
+    pub const fn audio_matcher(&self) -> &AudioMatcher {
+        &self.audio_matcher
+    }
+
+    /// Gets the names of the audio output devices, as of the last audio
+    /// device change or [`Gamepad`] connection.
+    ///
+    /// Empty unless the SDL2 audio subsystem is initialized by the app.
+    #[must_use]
+    #[inline]
+    pub fn audio_output_devices(&self) -> &[String] {
+        &self.audio_outputs
+    }
+
+    /// Enables or disables (with [`None`]) synthesis of discrete
+    /// [`Event::StickDirectionPressed`], [`Event::StickDirectionRepeat`] and
+    /// [`Event::StickDirectionReleased`] events from the given [`Stick`] of
+    /// every [`Gamepad`], for driving menus like a D-pad.
+    ///
+    /// Positions are sampled on every [`tick`], so repeats are only as
+    /// precise as the tick rate. Disabling navigation releases any held
+    /// directions. See [`StickNavigator`] for the exact semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{NavigationConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_stick_navigation(Stick::Left, Some(NavigationConfig::new()));
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::StickDirectionPressed { direction, .. }
+    ///     | Event::StickDirectionRepeat { direction, .. } = event
+    ///     {
+    ///         // move menu selection towards `direction`
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`tick`]: Self::tick
+    #[inline]
+    pub fn set_stick_navigation(
+        &mut self,
+        stick: Stick,
+        config: Option<NavigationConfig>,
+    ) {
+        let pending = &mut self.pending;
+        self.navigators.retain(|&(id, navigated), navigator| {
+            if navigated != stick {
+                return true;
+            }
+            if let Some(direction) = navigator.reset() {
+                pending.push_back(Event::StickDirectionReleased {
+                    which: id.raw(),
+                    stick,
+                    direction,
+                });
+            }
+            false
+        });
+
+        match config {
+            Some(config) => _ = self.navigation.insert(stick, config),
+            None => _ = self.navigation.remove(&stick),
+        }
+    }
+
+    /// Suspends controller input, e.g. when the application window loses
+    /// focus.
+    ///
+    /// While suspended:
+    /// - [`event`] and friends drop button, axis, touchpad, sensor and stick
+    ///   navigation [`Event`]s, while device connection [`Event`]s still flow;
+    /// - [`Gamepad`] input queries report neutral values (see
+    ///   [`Gamepad::input_suspended`]), while connection and power queries keep
+    ///   working;
+    /// - active rumble is stopped. It is not restarted on resume.
+    ///
+    /// Does nothing if input is already suspended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.suspend_input();
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     assert!(!matches!(event, Event::ControllerButtonDown { .. }));
+    /// }
+    /// # if let Some(gamepad) = girl.gamepad(0) {
+    /// assert_eq!(gamepad.buttons(Button::all()), Button::empty());
+    /// # }
+    ///
+    /// // buttons held through the whole suspension don't produce new
+    /// // `ControllerButtonDown` events, changed ones are reported
+    /// girl.resume_input();
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    #[inline]
+    pub fn suspend_input(&mut self) {
+        if self.input_suspended() {
+            return;
+        }
+
+        self.held_on_suspend = self
+            .pads
+            .iter()
+            .map(|(&id, pad)| (id, pad.buttons(Button::all())))
+            .collect();
+        self.shared.set_suspended(true);
+
+        #[cfg(feature = "rumble")]
+        for pad in self.pads.values_mut() {
+            // rumble is best-effort, pads without it simply fail here
+            drop(pad.end_rumble());
+            drop(pad.end_rumble_triggers());
+        }
+    }
+
+    /// Resumes controller input suspended with [`suspend_input`].
+    ///
+    /// Input that arrived while suspended is discarded. Instead, the
+    /// difference between the [`Button`]s held on suspend and the ones held
+    /// now is reported as synthesized [`Event::ControllerButtonDown`] and
+    /// [`Event::ControllerButtonUp`] events, so a [`Button`] held through the
+    /// whole suspension is neither reported pressed again nor lost.
+    ///
+    /// Does nothing if input is not suspended.
+    ///
+    /// [`suspend_input`]: Self::suspend_input
+    #[inline]
+    pub fn resume_input(&mut self) {
+        if !self.input_suspended() {
+            return;
+        }
+
+        if let Some(pump) = self.event_pump.as_mut() {
+            pump.pump_events();
+        }
+        let controller_input = [
+            (
+                sdl2_sys::SDL_EventType::SDL_CONTROLLERAXISMOTION,
+                sdl2_sys::SDL_EventType::SDL_CONTROLLERBUTTONUP,
+            ),
+            (
+                sdl2_sys::SDL_EventType::SDL_CONTROLLERTOUCHPADDOWN,
+                sdl2_sys::SDL_EventType::SDL_CONTROLLERSENSORUPDATE,
+            ),
+        ];
+        for (first, last) in controller_input {
+            // SAFETY: SDL is alive, the ranges only cover controller input
+            //         events.
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            unsafe {
+                sdl2_sys::SDL_FlushEvents(first as u32, last as u32);
+            }
+        }
+        self.shared.set_suspended(false);
+
+        let held_on_suspend = mem::take(&mut self.held_on_suspend);
+        for (&id, pad) in &self.pads {
+            let before = held_on_suspend.get(&id).copied().unwrap_or_default();
+            let now = pad.buttons(Button::all());
+            let which = id.raw();
+            for button in before.difference(now) {
+                let event = self.translator.button(which, button, false);
+                self.pending.push_back(event);
+            }
+            for button in now.difference(before) {
+                let event = self.translator.button(which, button, true);
+                self.pending.push_back(event);
+            }
+        }
+    }
+
+    /// Checks if all the [`Button`]s were pressed on the device with the
+    /// [`GamepadId`] at any point between the two last [`update`]s, as
+    /// reported by its [`Event`]s.
+    ///
+    /// Unlike [`Gamepad::tapped_since_update`], the [`Button`]s are not
+    /// remapped, so the device doesn't need to be open. In eventless mode
+    /// (see [`new_eventless`]), only presses injected with [`inject`] are
+    /// reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    ///
+    /// girl.update();
+    /// for pad in girl.gamepads() {
+    ///     if girl.just_pressed(pad.id(), Button::Start) {
+    ///         println!("{pad} joined");
+    ///     }
+    /// }
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`new_eventless`]: Self::new_eventless
+    /// [`inject`]: Self::inject
+    #[must_use]
+    #[inline]
+    pub fn just_pressed(&self, id: GamepadId, buttons: Button) -> bool {
+        self.shared.taps().taps(id).contains(buttons)
+    }
+
+    /// Checks if input is suspended with [`suspend_input`].
+    ///
+    /// [`suspend_input`]: Self::suspend_input
+    #[must_use]
+    #[inline]
+    pub fn input_suspended(&self) -> bool {
+        self.shared.suspended()
+    }
+
+    /// Returns an iterator opening all connected [`Gamepad`]s, each an
+    /// independent handle like the ones of [`gamepad`].
+    ///
+    /// Stops at the first [`Gamepad`] failing to open, see
+    /// [`last_open_error`] for why.
+    ///
+    /// Use [`gamepads`] and [`gamepads_mut`] to iterate the [`Gamepad`]s
+    /// tracked by [`Girl`] instead.
+    ///
+    /// [`gamepad`]: Self::gamepad
+    /// [`last_open_error`]: Self::last_open_error
+    /// [`gamepads`]: Self::gamepads
+    /// [`gamepads_mut`]: Self::gamepads_mut
+    #[inline]
+    pub const fn gamepads_connected(&self) -> ConnectedGamepads<'_> {
+        ConnectedGamepads {
+            gcs: &self.gcs,
+            jcs: &self.jcs,
+            shared: &self.shared,
+            last_error: &self.last_open_error,
+            idx: 0,
+        }
+    }
+
+    /// Gets the [`GamepadId`] of the device with the raw instance ID, e.g.
+    /// the `which` of an [`Event`], in its current generation, see
+    /// [`GamepadId`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::ControllerButtonDown { which, button } = event
+    ///         && let Some(pad) = girl.gamepad_ref(girl.resolve_id(which))
+    ///     {
+    ///         println!("{button:?} pressed on {pad}");
+    ///     }
+    /// }
+    ///
+    /// // only instance IDs reused by another device start a new generation
+    /// assert_eq!(girl.resolve_id(7), GamepadId::from_raw(7));
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn resolve_id(&self, which: u32) -> GamepadId {
+        self.shared.id(which)
+    }
+
+    /// Checks if the [`GamepadId`] is of the current generation, so state
+    /// tracked per raw instance ID is its own.
+    fn is_current(&self, id: GamepadId) -> bool {
+        self.shared.id(id.raw()) == id
+    }
+
+    /// Gets a view of the [`Gamepad`] with the given [`GamepadId`] tracked
+    /// by [`Girl`], reflecting every configuration set through
+    /// [`gamepad_mut`].
+    ///
+    /// Returns [`None`] if no such [`Gamepad`] was connected as of the
+    /// latest [`update`].
+    ///
+    /// [`gamepad_mut`]: Self::gamepad_mut
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub fn gamepad_ref(&self, id: GamepadId) -> Option<GamepadRef<'_>> {
+        self.pads.get(&id).map(GamepadRef::new)
+    }
+
+    /// Gets a mutable view of the [`Gamepad`] with the given [`GamepadId`]
+    /// tracked by [`Girl`], see [`GamepadMut`].
+    ///
+    /// Returns [`None`] if no such [`Gamepad`] was connected as of the
+    /// latest [`update`].
+    ///
+    /// # Examples
+    ///
+    /// Configuration set through the view sticks to the tracked
+    /// [`Gamepad`]:
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.update();
+    /// # if let Some(id) = girl.any_gamepad().ids().next() {
+    /// girl.gamepad_mut(id).unwrap().set_stick_deadzone(Stick::Left, 0.2)?;
+    ///
+    /// girl.update();
+    /// let gamepad = girl.gamepad_ref(id).unwrap();
+    /// let profile = gamepad.current_profile();
+    /// assert_eq!(profile.stick(Stick::Left).deadzone, 0.2);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// To mutate one [`Gamepad`] while going over all of them, collect the
+    /// ids first:
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.update();
+    ///
+    /// let ids: Vec<_> = girl.gamepads().map(|gamepad| gamepad.id()).collect();
+    /// for id in ids {
+    ///     let pressed = girl
+    ///         .gamepad_ref(id)
+    ///         .is_some_and(|gamepad| gamepad.buttons_pressed(Button::A));
+    ///     if pressed && let Some(mut gamepad) = girl.gamepad_mut(id) {
+    ///         gamepad.set_sideways_mode(true);
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub fn gamepad_mut(&mut self, id: GamepadId) -> Option<GamepadMut<'_>> {
+        self.pads.get_mut(&id).map(GamepadMut::new)
+    }
+
+    /// Iterates views of the [`Gamepad`]s tracked by [`Girl`], as of the
+    /// latest [`update`], see [`gamepad_ref`].
+    ///
+    /// [`update`]: Self::update
+    /// [`gamepad_ref`]: Self::gamepad_ref
+    #[inline]
+    pub fn gamepads(&self) -> impl Iterator<Item = GamepadRef<'_>> {
+        self.pads.values().map(GamepadRef::new)
+    }
+
+    /// Iterates mutable views of the [`Gamepad`]s tracked by [`Girl`], as
+    /// of the latest [`update`], see [`gamepad_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.update();
+    ///
+    /// for mut gamepad in girl.gamepads_mut() {
+    ///     gamepad.set_stick_deadzone(Stick::Left, 0.15)?;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`gamepad_mut`]: Self::gamepad_mut
+    #[inline]
+    pub fn gamepads_mut(&mut self) -> impl Iterator<Item = GamepadMut<'_>> {
+        self.pads.values_mut().map(GamepadMut::new)
+    }
+
+    /// Captures a [`GamepadDump`] of every connected [`Gamepad`], see
+    /// [`Gamepad::dump`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let girl = girl::Girl::new()?;
+    ///
+    /// for dump in girl.dump_all() {
+    ///     eprintln!("{dump}");
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn dump_all(&self) -> Vec<GamepadDump> {
+        self.gamepads_connected().map(|mut gamepad| gamepad.dump()).collect()
+    }
+
+    /// Takes a [`GamepadState`] snapshot of every [`Gamepad`] tracked by
+    /// [`Girl`] into `out`, indexed by player slot, returning how many were
+    /// written. Doesn't allocate, e.g. for polling at a fixed tick.
+    ///
+    /// A [`Gamepad`] goes to the slot of its [`Gamepad::player_index`], or
+    /// without one, to the lowest free slot in [`GamepadId`] order. Slots
+    /// without a connected [`Gamepad`] are [`None`].
+    ///
+    /// If `out` is too short, the [`Gamepad`]s with a slot past its end or
+    /// finding no free one are left out, so fewer are written than
+    /// [`gamepads`] yields, see [`snapshot_all_vec`] to get them all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// let mut players = [None; 4];
+    ///
+    /// girl.update();
+    /// let written = girl.snapshot_all(&mut players);
+    /// assert_eq!(written, players.iter().flatten().count());
+    /// assert!(written <= girl.gamepads().count());
+    /// if let Some(state) = players[0] {
+    ///     println!("player one: {state:?}");
+    /// }
+    ///
+    /// // nothing fits an empty slice
+    /// assert_eq!(girl.snapshot_all(&mut []), 0);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`gamepads`]: Self::gamepads
+    /// [`snapshot_all_vec`]: Self::snapshot_all_vec
+    #[inline]
+    pub fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+        fill_slots(out, self.pads.values(), Gamepad::player_slot, |pad| {
+            Some(pad.state()).filter(|state| state.connected)
+        })
+    }
+
+    /// Takes a [`GamepadState`] snapshot of every [`Gamepad`] tracked by
+    /// [`Girl`] like [`snapshot_all`], into a [`Vec`] long enough for all
+    /// of them.
+    ///
+    /// [`snapshot_all`]: Self::snapshot_all
+    #[must_use]
+    #[inline]
+    pub fn snapshot_all_vec(&self) -> Vec<Option<GamepadState>> {
+        let slots = self
+            .pads
+            .values()
+            .filter_map(Gamepad::player_slot)
+            .max()
+            .map_or(0, |slot| slot.saturating_add(1));
+        let mut out = vec![None; slots.max(self.pads.len())];
+        _ = self.snapshot_all(&mut out);
+        out
+    }
+
+    /// Reads input of every [`Gamepad`] tracked by [`Girl`] as one, see
+    /// [`AggregateGamepad`].
+    ///
+    /// Useful before players are assigned their controllers, e.g. on the
+    /// title screen. Reflects the [`Gamepad`]s connected as of the latest
+    /// [`update`].
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub const fn any_gamepad(&self) -> AggregateGamepad<'_> {
+        AggregateGamepad::new(&self.pads)
+    }
+
+    /// Gets the [`GamepadId`] of the [`Gamepad`] that most recently produced
+    /// deliberate input, see [`ActivityTracker`].
+    ///
+    /// Activity is observed in the [`Event`]s returned by [`event`] and
+    /// friends. Returns [`None`] if no [`Gamepad`] was active within the
+    /// idle timeout, see [`set_idle_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// // title screen: any controller may press "start"
+    /// girl.update();
+    /// while let Some(_event) = girl.event() {}
+    /// if girl.any_gamepad().buttons_pressed(Button::Start)
+    ///     && let Some(id) = girl.last_active_gamepad()
+    /// {
+    ///     // lock player one onto `id`
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`set_idle_timeout`]: Self::set_idle_timeout
+    /// [`ActivityTracker`]: crate::ActivityTracker
+    #[must_use]
+    #[inline]
+    pub fn last_active_gamepad(&self) -> Option<GamepadId> {
+        let last = self.stages.idle().activity().last_active(Instant::now())?;
+        Some(self.shared.id(last.raw()))
+    }
+
+    /// Estimates the wireless connection quality of the [`Gamepad`] with
+    /// the [`GamepadId`] from the timing of its reports, e.g. to tell
+    /// players that "lag" is Bluetooth packet loss. See
+    /// [`ConnectionQuality`] for how it is estimated.
+    ///
+    /// Reports are observed as SDL2 delivers them to [`event`] and friends,
+    /// so it is always [`None`] in eventless mode (see [`new_eventless`]).
+    /// Also returns [`None`] if the [`Gamepad`] is disconnected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ConnectionRating, prelude::*};
+    /// let mut girl = Girl::new()?;
+    /// while let Some(_event) = girl.event() {}
+    /// # if girl.gamepad(0).is_some() {
+    /// let id = girl.gamepad(0).unwrap().id();
+    ///
+    /// if let Some(report) = girl.connection_quality(id)
+    ///     && report.rating == ConnectionRating::Poor
+    /// {
+    ///     eprintln!("weak signal, {} reports lost", report.dropout_count);
+    /// }
+    /// # }
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`new_eventless`]: Self::new_eventless
+    #[must_use]
+    #[inline]
+    pub fn connection_quality(&self, id: GamepadId) -> Option<QualityReport> {
+        // SAFETY: SDL is alive, and the call has no other preconditions.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let ticks = unsafe { sdl2_sys::SDL_GetTicks() };
+        let now = Duration::from_millis(ticks.into());
+        self.connection.report(id, now).filter(|_| self.is_current(id))
+    }
+
+    /// Sets how long [`last_active_gamepad`] remembers a [`Gamepad`] without
+    /// input, [`ActivityTracker::DEFAULT_IDLE_TIMEOUT`] by default.
+    ///
+    /// [`last_active_gamepad`]: Self::last_active_gamepad
+    /// [`ActivityTracker::DEFAULT_IDLE_TIMEOUT`]:
+    ///     crate::ActivityTracker::DEFAULT_IDLE_TIMEOUT
+    #[inline]
+    pub const fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.stages.idle_mut().set_idle_timeout(idle_timeout);
+    }
+
+    /// Gets how long no input arrived from any [`Gamepad`], see
+    /// [`IdleTracker`] for what counts as input.
+    ///
+    /// Input is observed in the [`Event`]s returned by [`event`] and
+    /// friends. The time is that of the last [`tick`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// girl.update();
+    /// while let Some(_event) = girl.event() {}
+    /// let dimmed = girl.idle_time() > Duration::from_secs(120);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`event`]: Self::event
+    /// [`tick`]: Self::tick
+    /// [`IdleTracker`]: crate::IdleTracker
+    #[must_use]
+    #[inline]
+    pub fn idle_time(&self) -> Duration {
+        self.stages.idle().tracker().idle_time(self.clock)
+    }
+
+    /// Gets how long no input arrived from the [`Gamepad`] with the given
+    /// [`GamepadId`], or since it connected, see [`idle_time`].
+    ///
+    /// Returns [`None`] if the [`Gamepad`] is not connected.
+    ///
+    /// [`idle_time`]: Self::idle_time
+    #[must_use]
+    #[inline]
+    pub fn idle_time_of(&self, id: GamepadId) -> Option<Duration> {
+        self.stages.idle().tracker().idle_time_of(id, self.clock)
+    }
+
+    /// Checks if any input arrived at or after `since`, a coarser check
+    /// than [`update_and_collect`], see [`idle_time`].
+    ///
+    /// Input is timed with the clock of [`tick`], which follows the wall
+    /// clock unless disabled with [`set_auto_tick`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Instant;
+    /// # use sdl2::{controller::Button, event::Event as SdlEvent};
+    /// # use girl::prelude::*;
+    /// let mut girl = Girl::new()?;
+    /// let sdl = sdl2::init().map_err(girl::Error::Sdl2Init)?;
+    /// let events = sdl.event().map_err(girl::Error::Sdl2Init)?;
+    /// let frame = Instant::now();
+    ///
+    /// girl.update();
+    /// while girl.event().is_some() {}
+    /// assert!(!girl.had_activity_since(frame));
+    ///
+    /// let press = SdlEvent::ControllerButtonDown {
+    ///     timestamp: 0,
+    ///     which: 0,
+    ///     button: Button::A,
+    /// };
+    /// events.push_event(press).unwrap();
+    /// girl.update();
+    /// while girl.event().is_some() {}
+    /// assert!(girl.had_activity_since(frame));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update_and_collect`]: Self::update_and_collect
+    /// [`idle_time`]: Self::idle_time
+    /// [`tick`]: Self::tick
+    /// [`set_auto_tick`]: Self::set_auto_tick
+    #[must_use]
+    #[inline]
+    pub fn had_activity_since(&self, since: Instant) -> bool {
+        self.stages.idle().tracker().last_input() >= since
+    }
+
+    /// Sets the [`IdleConfig`], e.g. to report [`Event::InputIdle`] and
+    /// [`Event::InputResumed`] when the input goes idle and resumes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{IdleConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// let mut config = IdleConfig::new();
+    /// config.threshold = Some(Duration::from_secs(300));
+    /// girl.set_idle_config(config);
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     while let Some(event) = girl.event() {
+    ///         match event {
+    ///             Event::InputIdle { .. } => println!("dimming"),
+    ///             Event::InputResumed => println!("waking up"),
+    ///             _ => {}
+    ///         }
+    ///     }
+    ///     # break;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub const fn set_idle_config(&mut self, config: IdleConfig) {
+        self.stages.idle_mut().set_config(config);
+    }
+
+    /// Gets the [`IdleConfig`], see [`set_idle_config`].
+    ///
+    /// [`set_idle_config`]: Self::set_idle_config
+    #[must_use]
+    #[inline]
+    pub const fn idle_config(&self) -> IdleConfig {
+        self.stages.idle().tracker().config()
+    }
+
+    /// Powers off [`Gamepad`]s without input for `after`, or stops doing so
+    /// if [`None`], the default.
+    ///
+    /// Reports [`Event::PoweringOffIdle`] a minute before, and input in
+    /// between cancels the power off. Only [`Gamepad`]s able to power off
+    /// are tracked, see [`Gamepad::can_power_off`]. Idle times are those of
+    /// [`idle_time_of`], advanced by [`tick`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_power_off_idle(Some(Duration::from_secs(600)));
+    /// assert_eq!(girl.power_off_idle(), Some(Duration::from_secs(600)));
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::PoweringOffIdle { which } = event {
+    ///         println!("gamepad {which} powers off in a minute");
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`idle_time_of`]: Self::idle_time_of
+    /// [`tick`]: Self::tick
+    #[inline]
+    pub fn set_power_off_idle(&mut self, after: Option<Duration>) {
+        self.stages.idle_mut().set_power_off_idle(after);
+    }
+
+    /// Gets the idle time set with [`set_power_off_idle`].
+    ///
+    /// [`set_power_off_idle`]: Self::set_power_off_idle
+    #[must_use]
+    #[inline]
+    pub const fn power_off_idle(&self) -> Option<Duration> {
+        self.stages.idle().power_off_idle()
+    }
+
+    /// Holds back connection changes of a controller until none arrived for
+    /// `window`, or stops doing so if [`None`], the default.
+    ///
+    /// Meant for flaky USB hubs and cables connecting and disconnecting a
+    /// controller many times a second: the changes are coalesced into the
+    /// final state, reported as [`Event::ControllerDeviceRemoved`] and
+    /// [`Event::ControllerDeviceAdded`] once it settles, preceded by
+    /// [`Event::ConnectionFlapping`] if there were more changes than a
+    /// single replug. The first connection of a controller not seen before
+    /// isn't delayed. Controllers are told apart by their [`Guid`]. The
+    /// [`Gamepad`]s themselves still follow every change. See
+    /// [`ConnectionDebounce`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// let window = Duration::from_millis(500);
+    /// girl.set_connection_debounce(Some(window));
+    /// assert_eq!(girl.connection_debounce(), Some(window));
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::ConnectionFlapping { which, count } = event {
+    ///         println!("gamepad {which} flapped {count} times, check cables");
+    ///     }
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_connection_debounce(&mut self, window: Option<Duration>) {
+        self.connection_debounce = window
+            .map_or_else(ConnectionDebounce::disabled, ConnectionDebounce::new);
+    }
+
+    /// Gets the window set with [`set_connection_debounce`].
+    ///
+    /// [`set_connection_debounce`]: Self::set_connection_debounce
+    #[must_use]
+    #[inline]
+    pub const fn connection_debounce(&self) -> Option<Duration> {
+        self.connection_debounce.window()
+    }
+
+    /// Starts or stops recording a [`CommandJournal`] on every [`Gamepad`]:
+    /// the ones [`Girl`] tracks, and the ones opened from now on, see
+    /// [`Gamepad::set_command_journal`]. Off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_command_journal(true);
+    /// assert!(girl.command_journal());
+    ///
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    /// _ = gamepad.set_led(0, 0, 255);
+    ///
+    /// let journal = gamepad.command_journal().expect("recording");
+    /// let window = Duration::from_secs(1);
+    /// for record in journal.recent(Instant::now(), window) {
+    ///     println!("{record}");
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`CommandJournal`]: crate::CommandJournal
+    #[inline]
+    pub fn set_command_journal(&mut self, enabled: bool) {
+        self.shared.set_journal_commands(enabled);
+        for pad in self.pads.values_mut() {
+            pad.set_command_journal(enabled);
+        }
+    }
+
+    /// Checks if [`Gamepad`]s record a [`CommandJournal`], see
+    /// [`set_command_journal`].
+    ///
+    /// [`CommandJournal`]: crate::CommandJournal
+    /// [`set_command_journal`]: Self::set_command_journal
+    #[must_use]
+    #[inline]
+    pub fn command_journal(&self) -> bool {
+        self.shared.journal_commands()
+    }
+
+    /// Colors the LED of the [`Gamepad`] with the [`GamepadId`] with what
+    /// `binding` returns for its [`GamepadState`], returning a handle to
+    /// remove it with.
+    ///
+    /// Evaluated on every [`tick`], the color is only set when it changes,
+    /// and at most once per [`led_binding_interval`], so bindings don't
+    /// flood the controller with LED updates. [`Gamepad`]s without an LED
+    /// are skipped, as is everything while LEDs are paused in the
+    /// background, see [`set_background_policy`].
+    ///
+    /// A `binding` returns [`None`] to leave the color to the bindings
+    /// added before it, the last added one returning a color wins, see
+    /// [`LedBindings`]. Bindings are dropped once their [`Gamepad`]
+    /// disconnects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let id = girl.gamepad(0).unwrap().id();
+    ///
+    /// // red and green follow the triggers
+    /// let triggers = |state: &GamepadState| {
+    ///     let channel = |value: f64| (value * 255.0) as u8;
+    ///     Some(Rgb::new(
+    ///         channel(state.left_trigger),
+    ///         channel(state.right_trigger),
+    ///         0,
+    ///     ))
+    /// };
+    /// let binding = girl.add_led_binding(id, Box::new(triggers));
+    /// girl.update();
+    ///
+    /// // the LED keeps its last color
+    /// drop(girl.remove_led_binding(binding));
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`tick`]: Self::tick
+    /// [`led_binding_interval`]: Self::led_binding_interval
+    /// [`set_background_policy`]: Self::set_background_policy
+    #[inline]
+    pub fn add_led_binding(
+        &mut self,
+        id: GamepadId,
+        binding: LedBinding,
+    ) -> LedBindingId {
+        self.led_bindings.add(id, binding)
+    }
+
+    /// Removes a binding added with [`add_led_binding`], returning it. The
+    /// LED keeps its last color.
+    ///
+    /// Returns [`None`] if it was already removed.
+    ///
+    /// [`add_led_binding`]: Self::add_led_binding
+    #[inline]
+    pub fn remove_led_binding(
+        &mut self,
+        id: LedBindingId,
+    ) -> Option<LedBinding> {
+        self.led_bindings.remove(id)
+    }
+
+    /// Sets the minimal time between two colors set on a [`Gamepad`] by
+    /// its bindings, [`LedBindings::DEFAULT_INTERVAL`] by default, see
+    /// [`add_led_binding`].
+    ///
+    /// [`add_led_binding`]: Self::add_led_binding
+    #[inline]
+    pub fn set_led_binding_interval(&mut self, interval: Duration) {
+        self.led_bindings.set_interval(interval);
+    }
+
+    /// Gets the minimal time between two colors set on a [`Gamepad`] by
+    /// its bindings, see [`set_led_binding_interval`].
+    ///
+    /// [`set_led_binding_interval`]: Self::set_led_binding_interval
+    #[must_use]
+    #[inline]
+    pub const fn led_binding_interval(&self) -> Duration {
+        self.led_bindings.interval()
+    }
+
+    /// Sets what is paused while the application is in the background,
+    /// between [`Event::AppBackgrounded`] and [`Event::AppForegrounded`].
+    /// Nothing by default, see [`BackgroundPolicy::disabled`].
+    ///
+    /// Rumble is stopped, LED animations are paused and enabled sensors
+    /// are disabled, then enabled again in the foreground, also on a
+    /// controller that reconnected in the meantime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::{iter, mem};
+    /// # use sdl2::sys as sdl2_sys;
+    /// # use girl::{BackgroundPolicy, prelude::*};
+    /// # use sdl2_sys::SDL_EventType::{
+    /// #     SDL_APP_DIDENTERFOREGROUND, SDL_APP_WILLENTERBACKGROUND,
+    /// # };
+    /// let mut girl = Girl::new()?;
+    /// girl.set_background_policy(BackgroundPolicy::new());
+    ///
+    /// // what SDL2 reports on Android or in Steam Deck gaming mode
+    /// let push = |kind: sdl2_sys::SDL_EventType| {
+    ///     // SAFETY: app events carry nothing but their type
+    ///     let mut event: sdl2_sys::SDL_Event = unsafe { mem::zeroed() };
+    ///     event.type_ = kind as u32;
+    ///     assert_eq!(unsafe { sdl2_sys::SDL_PushEvent(&mut event) }, 1);
+    /// };
+    ///
+    /// push(SDL_APP_WILLENTERBACKGROUND);
+    /// let events: Vec<_> = iter::from_fn(|| girl.event()).collect();
+    /// assert!(events.iter().any(|e| matches!(e, Event::AppBackgrounded)));
+    /// assert!(girl.backgrounded());
+    ///
+    /// push(SDL_APP_DIDENTERFOREGROUND);
+    /// let events: Vec<_> = iter::from_fn(|| girl.event()).collect();
+    /// assert!(events.iter().any(|e| matches!(e, Event::AppForegrounded)));
+    /// assert!(!girl.backgrounded());
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub const fn set_background_policy(&mut self, policy: BackgroundPolicy) {
+        self.stages.background_mut().set_policy(policy);
+    }
+
+    /// Gets the [`BackgroundPolicy`], see [`set_background_policy`].
+    ///
+    /// [`set_background_policy`]: Self::set_background_policy
+    #[must_use]
+    #[inline]
+    pub const fn background_policy(&self) -> BackgroundPolicy {
+        self.stages.background().policy()
+    }
+
+    /// Sets the [`Misc1Role`]s whose [`Button::Misc1`] [`Event`]s are
+    /// dropped, e.g. since the operating system reserves the capture
+    /// button. [`Misc1Suppression::platform`] by default.
+    ///
+    /// Polled [`Button`]s, e.g. [`Gamepad::buttons`], still include
+    /// [`Button::Misc1`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::Misc1Suppression;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// // handle every press, the player rebinds the button anyway
+    /// girl.set_misc1_suppression(Misc1Suppression::none());
+    /// assert_eq!(girl.misc1_suppression(), Misc1Suppression::none());
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Misc1Role`]: crate::Misc1Role
+    #[inline]
+    pub const fn set_misc1_suppression(
+        &mut self,
+        suppression: Misc1Suppression,
+    ) {
+        self.stages.misc1_mut().set_suppression(suppression);
+    }
+
+    /// Gets the [`Misc1Suppression`], see [`set_misc1_suppression`].
+    ///
+    /// [`set_misc1_suppression`]: Self::set_misc1_suppression
+    #[must_use]
+    #[inline]
+    pub const fn misc1_suppression(&self) -> Misc1Suppression {
+        self.stages.misc1().suppression()
+    }
+
+    /// Checks if the application is in the background, between
+    /// [`Event::AppBackgrounded`] and [`Event::AppForegrounded`].
+    #[must_use]
+    #[inline]
+    pub const fn backgrounded(&self) -> bool {
+        self.stages.background().backgrounded()
+    }
+
+    /// Enables a one-shot warning when input is queried from a [`Gamepad`]
+    /// more than `threshold` after the last [`update`], which leaves input
+    /// frozen. Off by default.
+    ///
+    /// The warning goes through the `tracing` or `log` feature, once per
+    /// stall. Checking costs an atomic load while disabled, and a clock read
+    /// while enabled. See also [`time_since_update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.enable_stall_detection(Duration::from_millis(500));
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     // query gamepads, warned about if `update` is forgotten
+    ///     # break;
+    /// }
+    /// assert!(girl.time_since_update() < Duration::from_secs(60));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`update`]: Self::update
+    /// [`time_since_update`]: Self::time_since_update
+    #[inline]
+    pub fn enable_stall_detection(&mut self, threshold: Duration) {
+        self.shared.watchdog().set_threshold(Some(threshold));
+    }
+
+    /// Disables the warning enabled with [`enable_stall_detection`].
+    ///
+    /// [`enable_stall_detection`]: Self::enable_stall_detection
+    #[inline]
+    pub fn disable_stall_detection(&mut self) {
+        self.shared.watchdog().set_threshold(None);
+    }
+
+    /// Gets the time elapsed since the last [`update`], or since the
+    /// [`Girl`] was created if it was never called.
+    ///
+    /// [`update`]: Self::update
+    #[must_use]
+    #[inline]
+    pub fn time_since_update(&self) -> Duration {
+        self.shared.watchdog().time_since_update(Instant::now())
+    }
+
+    /// Sets the default [`InputProfile`] of the [`ControllerType`], applied to
+    /// [`Gamepad`]s opened from now on, see [`DefaultProfiles`].
+    ///
+    /// A profile applied with [`Gamepad::apply_profile`] takes precedence for
+    /// all [`Gamepad`]s with the same [`Guid`], including on reconnect, until
+    /// it is forgotten with [`forget_profile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] if the `profile` is invalid, see
+    /// [`InputProfile::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// let mut profile = InputProfile::new();
+    /// profile.left_stick.deadzone = 0.2;
+    /// girl.set_default_profile(ControllerType::Ps5, profile.clone())?;
+    ///
+    /// for gamepad in girl.gamepads() {
+    ///     println!("{gamepad}: {:?}", gamepad.current_profile());
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`forget_profile`]: Self::forget_profile
+    #[inline]
+    pub fn set_default_profile(
+        &mut self,
+        controller_type: ControllerType,
+        profile: InputProfile,
+    ) -> Result<(), Error> {
+        self.shared.defaults().set(controller_type, profile)
+    }
+
+    /// Gets the [`DefaultProfiles`] applied to [`Gamepad`]s opened from now
+    /// on.
+    #[must_use]
+    #[inline]
+    pub fn default_profiles(&self) -> DefaultProfiles {
+        self.shared.defaults().clone()
+    }
+
+    /// Replaces the [`DefaultProfiles`] applied to [`Gamepad`]s opened from
+    /// now on, see [`set_default_profile`].
+    ///
+    /// [`set_default_profile`]: Self::set_default_profile
+    #[inline]
+    pub fn set_default_profiles(&mut self, defaults: DefaultProfiles) {
+        *self.shared.defaults() = defaults;
+    }
+
+    /// Sets whether [`DefaultProfiles`] are applied to [`Gamepad`]s opened
+    /// from now on, see [`DefaultProfiles::set_enabled`].
+    #[inline]
+    pub fn set_default_profiles_enabled(&mut self, enabled: bool) {
+        self.shared.defaults().set_enabled(enabled);
+    }
+
+    /// Sets the [`InputConfig`] applied to [`Gamepad`]s opened from now on,
+    /// including on reconnect, over their default profile.
+    ///
+    /// Connected [`Gamepad`]s keep their settings until
+    /// [`propagate_default_input_config`]. See [`InputConfig`] for the
+    /// precedence of the settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] if the `config` is invalid, see
+    /// [`InputConfig::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{InputConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    ///
+    /// let mut config = InputConfig::new();
+    /// config.invert_y = Some(true);
+    /// girl.set_default_input_config(config)?;
+    /// assert_eq!(girl.default_input_config(), config);
+    ///
+    /// # if girl.gamepad(0).is_some() {
+    /// {
+    ///     let mut gamepad = girl.gamepad(0).unwrap();
+    ///     gamepad.clear_input_overrides();
+    ///     gamepad.set_invert_y(Stick::Left, false);
+    /// }
+    /// girl.propagate_default_input_config();
+    ///
+    /// let gamepad = girl.gamepad(0).unwrap();
+    /// let profile = gamepad.current_profile();
+    /// // overridden on the pad
+    /// assert!(!profile.left_stick.invert_y);
+    /// assert!(profile.right_stick.invert_y);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`propagate_default_input_config`]: Self::propagate_default_input_config
+    #[inline]
+    pub fn set_default_input_config(
+        &mut self,
+        config: InputConfig,
+    ) -> Result<(), Error> {
+        config.validate()?;
+        *self.shared.input_config() = config;
+        Ok(())
+    }
+
+    /// Gets the [`InputConfig`] applied to [`Gamepad`]s opened from now on,
+    /// see [`set_default_input_config`].
+    ///
+    /// [`set_default_input_config`]: Self::set_default_input_config
+    #[must_use]
+    #[inline]
+    pub fn default_input_config(&self) -> InputConfig {
+        *self.shared.input_config()
+    }
+
+    /// Applies the [`InputConfig`] to the connected [`Gamepad`]s too, except
+    /// the settings overridden on each of them, see
+    /// [`Gamepad::input_overrides`].
+    #[inline]
+    pub fn propagate_default_input_config(&mut self) {
+        let config = self.default_input_config();
+        for pad in self.pads.values_mut() {
+            pad.apply_input_config(&config);
+        }
+    }
+
+    /// Applies the [`PadSetup`] to every connected [`Gamepad`], see
+    /// [`Gamepad::apply`], reporting the outcome per [`Gamepad`].
+    #[inline]
+    pub fn apply_to_all(
+        &mut self,
+        setup: &PadSetup,
+    ) -> BTreeMap<GamepadId, SetupReport> {
+        self.pads.iter_mut().map(|(&id, pad)| (id, pad.apply(setup))).collect()
+    }
+
+    /// Gets the [`SteamInputHeuristics`] of [`Gamepad::is_steam_virtual`].
+    #[must_use]
+    #[inline]
+    pub fn steam_heuristics(&self) -> SteamInputHeuristics {
+        self.shared.steam().clone()
+    }
+
+    /// Replaces the [`SteamInputHeuristics`] of
+    /// [`Gamepad::is_steam_virtual`], e.g. to force the verdict of a
+    /// controller they misjudge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// let guid = "03000000de280000ff11000001000000".parse()?;
+    ///
+    /// let mut heuristics = girl.steam_heuristics();
+    /// heuristics.force(guid, false);
+    /// girl.set_steam_heuristics(heuristics);
+    /// assert_eq!(girl.steam_heuristics().forced(guid), Some(false));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_steam_heuristics(&mut self, heuristics: SteamInputHeuristics) {
+        *self.shared.steam() = heuristics;
+    }
+
+    /// Gets the [`ConnectionHeuristics`] of [`Gamepad::connection_kind`].
+    #[must_use]
+    #[inline]
+    pub fn connection_heuristics(&self) -> ConnectionHeuristics {
+        self.shared.connection().clone()
+    }
+
+    /// Replaces the [`ConnectionHeuristics`] of
+    /// [`Gamepad::connection_kind`], e.g. to force the [`ConnectionKind`]
+    /// of a controller they misjudge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::ConnectionKind;
+    /// let mut girl = girl::Girl::new()?;
+    /// let guid = "000000004c050000e60c000000010000".parse()?;
+    ///
+    /// let mut heuristics = girl.connection_heuristics();
+    /// heuristics.force(guid, ConnectionKind::Bluetooth);
+    /// girl.set_connection_heuristics(heuristics);
+    /// let forced = girl.connection_heuristics().forced(guid);
+    /// assert_eq!(forced, Some(ConnectionKind::Bluetooth));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`ConnectionKind`]: crate::ConnectionKind
+    #[inline]
+    pub fn set_connection_heuristics(
+        &mut self,
+        heuristics: ConnectionHeuristics,
+    ) {
+        *self.shared.connection() = heuristics;
+    }
+
+    /// Reports which connected [`Gamepad`]s are virtualized by Steam Input,
+    /// see [`Gamepad::is_steam_virtual`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.update();
+    ///
+    /// let status = girl.steam_input();
+    /// if status.any_virtual() {
+    ///     println!("disable Steam Input for gyro and touchpad support");
+    /// }
+    /// for id in status.virtualized() {
+    ///     println!("{id} is virtual");
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn steam_input(&self) -> SteamInputStatus {
+        let mut status = SteamInputStatus::default();
+        for (&id, pad) in &self.pads {
+            _ = status.devices.insert(id, pad.is_steam_virtual());
+        }
+        status
+    }
+
+    /// Forgets the [`InputProfile`] last applied with
+    /// [`Gamepad::apply_profile`] to [`Gamepad`]s with the given [`Guid`],
+    /// so the ones opened from now on start with their default profile.
+    ///
+    /// Returns the forgotten [`InputProfile`], if any.
+    #[inline]
+    pub fn forget_profile(&mut self, guid: &Guid) -> Option<InputProfile> {
+        self.shared.applied().remove(guid)
+    }
+
+    /// Sets [`Hints::ps5_enhanced_reports`], see [`with_hints`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HintAfterOpen`] if any [`Gamepad`] is already open, or
+    /// an error if SDL2 rejects the hint.
+    ///
+    /// [`with_hints`]: Self::with_hints
+    #[inline]
+    pub fn set_ps5_enhanced_reports(&self, enabled: bool) -> Result<(), Error> {
+        self.set_hint("ps5_enhanced_reports", Hints {
+            ps5_enhanced_reports: Some(enabled),
+            ..Hints::new()
+        })
+    }
+
+    /// Sets [`Hints::ps5_player_leds`], see [`with_hints`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HintAfterOpen`] if any [`Gamepad`] is already open, or
+    /// an error if SDL2 rejects the hint.
+    ///
+    /// [`with_hints`]: Self::with_hints
+    #[inline]
+    pub fn set_ps5_player_leds(&self, enabled: bool) -> Result<(), Error> {
+        self.set_hint("ps5_player_leds", Hints {
+            ps5_player_leds: Some(enabled),
+            ..Hints::new()
+        })
+    }
+
+    /// Sets [`Hints::ps_rumble_emulation`], see [`with_hints`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HintAfterOpen`] if any [`Gamepad`] is already open, or
+    /// an error if SDL2 rejects the hint.
+    ///
+    /// [`with_hints`]: Self::with_hints
+    #[inline]
+    pub fn set_ps_rumble_emulation(&self, enabled: bool) -> Result<(), Error> {
+        self.set_hint("ps_rumble_emulation", Hints {
+            ps_rumble_emulation: Some(enabled),
+            ..Hints::new()
+        })
+    }
+
+    /// Applies the [`Hints`] unless a [`Gamepad`] is already open.
+    ///
+    /// Every open [`Gamepad`] holds the [`Shared`] state, so it is only
+    /// unshared while none are open.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HintAfterOpen`] if a [`Gamepad`] is open, or an
+    /// error if SDL2 rejects a hint.
+    fn set_hint(&self, hint: &'static str, hints: Hints) -> Result<(), Error> {
+        if Arc::strong_count(&self.shared) > 1 {
+            diag!(warn, "{hint} set after opening gamepads");
+            return Err(Error::HintAfterOpen { hint });
+        }
+        hints.apply()
+    }
+
+    /// Opens a specific [`Gamepad`] by its device `index`.
+    ///
+    /// Returns [`None`] if no [`Gamepad`] is connected at the given `index`,
+    /// or it fails to open, see [`last_open_error`] for why.
+    ///
+    /// The [`Gamepad`] is an independent handle with independent state:
+    /// configuration set on it, e.g. deadzones, doesn't affect the
+    /// [`Gamepad`] tracked by [`Girl`] for the same controller, nor the
+    /// other way around. Use [`gamepad_ref`] and [`gamepad_mut`] to work
+    /// with the tracked one instead.
+    ///
+    /// [`last_open_error`]: Self::last_open_error
+    /// [`gamepad_ref`]: Self::gamepad_ref
+    /// [`gamepad_mut`]: Self::gamepad_mut
+    #[must_use]
+    #[inline]
+    pub fn gamepad(&self, index: u32) -> Option<Gamepad> {
+        open_gamepad(
+            &self.gcs,
+            &self.jcs,
+            &self.shared,
+            index,
+            &self.last_open_error,
+        )
+    }
+
+    /// Gets why the latest [`Gamepad`] open of [`gamepad`] or
+    /// [`gamepads_connected`] failed, e.g. the SDL2 error, to find out
+    /// why they returned [`None`].
+    ///
+    /// Cleared by the next successful open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let girl = girl::Girl::new()?;
+    /// if girl.gamepad(0).is_none()
+    ///     && let Some(err) = girl.last_open_error()
+    /// {
+    ///     println!("no gamepad: {err}");
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`gamepad`]: Self::gamepad
+    /// [`gamepads_connected`]: Self::gamepads_connected
+    #[must_use]
+    #[inline]
+    pub fn last_open_error(&self) -> Option<Error> {
+        self.last_open_error.get()
+    }
+
+    /// Overrides the SDL2 mapping of [`Gamepad`]s with the given [`Guid`].
+    ///
+    /// `mapping` is an SDL2 mapping string without the leading GUID, i.e.
+    /// `"name,a:b0,b:b1,..."`. The format is described in the SDL2 [docs].
+    ///
+    /// The override applies immediately to already connected [`Gamepad`]s
+    /// without reopening them, so their state is preserved, and SDL2 reports
+    /// an [`Event::ControllerDeviceRemapped`] for each of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 rejects the mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // swap A and B
+    /// girl.set_mapping_override(
+    ///     &gamepad.guid(),
+    ///     "Fixed Gamepad,a:b1,b:b0,x:b2,y:b3,platform:Linux",
+    /// )?;
+    /// println!("{}", girl.export_mapping(&gamepad));
+    ///
+    /// girl.clear_mapping_override(&gamepad.guid())?;
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [docs]: https://wiki.libsdl.org/SDL2/SDL_GameControllerAddMapping
+    #[inline]
+    pub fn set_mapping_override(
+        &mut self,
+        guid: &Guid,
+        mapping: &str,
+    ) -> Result<(), Error> {
+        let original = self.mapping_for_guid(guid);
+        let result = self
+            .gcs
+            .add_mapping(&format!("{guid},{mapping}"))
+            .map_err(Error::sdl("set_mapping_override"));
+        self.failures.observe("set_mapping_override", &result);
+        _ = result?;
+        // keep the very first original when overriding multiple times
+        _ = self.mapping_overrides.entry(*guid).or_insert(original);
+        Ok(())
+    }
+
+    /// Restores the SDL2 mapping that was used before
+    /// [`set_mapping_override`] for the given [`Guid`].
+    ///
+    /// Does nothing if the mapping wasn't overridden. If SDL2 had no mapping
+    /// for the [`Guid`] before the override, the override stays in effect, as
+    /// SDL2 doesn't support removing mappings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 rejects the original mapping.
+    ///
+    /// [`set_mapping_override`]: Self::set_mapping_override
+    #[inline]
+    pub fn clear_mapping_override(&mut self, guid: &Guid) -> Result<(), Error> {
+        if let Some(Some(original)) = self.mapping_overrides.remove(guid) {
+            let result = self
+                .gcs
+                .add_mapping(&original)
+                .map_err(Error::sdl("clear_mapping_override"));
+            self.failures.observe("clear_mapping_override", &result);
+            _ = result?;
+        }
+        Ok(())
+    }
+
+    /// Exports the SDL2 mapping currently used for the [`Gamepad`], including
+    /// the leading GUID, suitable for [`set_mapping_override`] round-trips
+    /// (after stripping the GUID) or for saving into a mapping database.
+    ///
+    /// [`set_mapping_override`]: Self::set_mapping_override
+    #[must_use]
+    #[inline]
+    pub fn export_mapping(&self, gamepad: &Gamepad) -> String {
+        self.mapping_for_guid(&gamepad.guid())
+            .unwrap_or_else(|| gamepad.mapping())
+    }
+
+    /// Gets the last [`MappingSnapshot`] of the [`Gamepad`], taken when it
+    /// was opened and again whenever it is remapped.
+    ///
+    /// Whenever a remap changes it, [`Event::ControllerRemapDiff`] reports
+    /// what changed right after [`Event::ControllerDeviceRemapped`].
+    ///
+    /// Returns [`None`] if the [`Gamepad`] isn't connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{Event, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    /// let snapshot = girl.mapping_snapshot(gamepad.id()).unwrap();
+    /// println!("bound: {:?}", snapshot.bound_buttons());
+    ///
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::ControllerRemapDiff { changes, .. } = event {
+    ///         println!("rebind {:?} in the settings", changes.rebound);
+    ///     }
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn mapping_snapshot(&self, id: GamepadId) -> Option<&MappingSnapshot> {
+        self.mappings.get(&id)
+    }
+
+    /// Looks up the SDL2 mapping for the given [`Guid`].
+    ///
+    /// Returns [`None`] if SDL2 has no mapping for it.
+    #[expect(clippy::unused_self, reason = "requires SDL2 to be alive")]
+    fn mapping_for_guid(&self, guid: &Guid) -> Option<String> {
+        let raw_guid = sdl2_sys::SDL_JoystickGUID { data: *guid.as_bytes() };
+
+        // SAFETY: SDL is alive, the GUID is passed by value.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let raw =
+            unsafe { sdl2_sys::SDL_GameControllerMappingForGUID(raw_guid) };
+        if raw.is_null() {
+            return None;
+        }
+
+        // SAFETY: `raw` is a non-null, nul-terminated string allocated by SDL.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let mapping =
+            unsafe { CStr::from_ptr(raw) }.to_string_lossy().into_owned();
+
+        // SAFETY: `raw` was allocated by SDL and is not used afterwards.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        unsafe {
+            sdl2_sys::SDL_free(raw.cast());
+        }
+
+        Some(mapping)
+    }
+
+    // /// Returns the latest [`TouchpadEvent`], if any.
+    // #[must_use]
+    // #[inline]
+    // pub fn touchpad(&mut self) -> Option<TouchpadEvent> {
+    //     let mut tp = None;
+    //     while let Some(event) = self.event() {
+    //         if let Some(tpn) = TouchpadEvent::from_event(event) {
+    //             tp = Some(tpn);
+    //         }
+    //     }
+    //     tp
+    // }
+}
+
+impl GamepadSource for Girl {
+    type Gamepad = Gamepad;
+
+    #[inline]
+    fn event(&mut self) -> Option<Event> {
+        Self::event(self)
+    }
+
+    #[inline]
+    fn gamepad_by_id(&self, id: GamepadId) -> Option<&Gamepad> {
+        self.pads.get(&id)
+    }
+
+    #[inline]
+    fn gamepad_by_id_mut(&mut self, id: GamepadId) -> Option<&mut Gamepad> {
+        self.pads.get_mut(&id)
+    }
+
+    #[inline]
+    fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+        Self::snapshot_all(self, out)
+    }
+}
+
+/// Iterator over all connected [`Gamepad`]s.
+///
+/// Can be obtained from [`Girl::gamepads_connected`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ConnectedGamepads<'girl> {
+    /// Reference to the game controller subsystem.
+    gcs: &'girl sdl2::GameControllerSubsystem,
+    /// Reference to the joystick subsystem.
+    jcs: &'girl sdl2::JoystickSubsystem,
+    /// State shared with every [`Gamepad`].
+    shared: &'girl Arc<Shared>,
+    /// Why the latest open failed, see [`Girl::last_open_error`].
+    last_error: &'girl LastOpenError,
+    /// Current index being iterated.
+    idx: u32,
+}
+
+impl Iterator for ConnectedGamepads<'_> {
+    type Item = Gamepad;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip over non-gamepads
+        while !self.gcs.is_game_controller(self.idx) {
+            self.idx = self.idx.checked_add(1)?;
+        }
+        let gamepad = open_gamepad(
+            self.gcs,
+            self.jcs,
+            self.shared,
+            self.idx,
+            self.last_error,
+        )?;
+        self.idx = self.idx.checked_add(1)?;
+        Some(gamepad)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ConnectedGamepads<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.gcs.num_joysticks().unwrap_or(0) as usize
+    }
+}
+
+/// Opens the [`Gamepad`] at the device `index`, recording why it fails in
+/// `last_error`, or clearing it, see [`Girl::last_open_error`].
+fn open_gamepad(
+    gcs: &sdl2::GameControllerSubsystem,
+    jcs: &sdl2::JoystickSubsystem,
+    shared: &Arc<Shared>,
+    index: u32,
+    last_error: &LastOpenError,
+) -> Option<Gamepad> {
+    let result =
+        gcs.open(index).map_err(Error::sdl("gamepad")).and_then(|gc| {
+            let js = jcs.open(index).map_err(Error::sdl("gamepad"))?;
+            Gamepad::from_sdl(gc, js, Arc::clone(shared))
+        });
+    last_error.record(index, result)
+}
+
+/// Gets the instance ID of the joystick at the given device `index`.
+///
+/// Returns [`None`] if there is no such device.
+pub(crate) fn device_instance_id(index: u32) -> Option<GamepadId> {
+    let index = i32::try_from(index).ok()?;
+
+    // SAFETY: SDL is alive, invalid indices are reported as `-1`.
+    #[expect(unsafe_code, reason = "ffi with sdl2")]
+    let id = unsafe { sdl2_sys::SDL_JoystickGetDeviceInstanceID(index) };
+
+    u32::try_from(id).ok().map(GamepadId::from_raw)
 }
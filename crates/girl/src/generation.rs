@@ -0,0 +1,108 @@
+//! Generations of [`GamepadId`]s telling apart devices reusing an instance
+//! ID.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::{GamepadId, Guid, logging::diag};
+
+/// Assigns the generation of the [`GamepadId`] of every opened device.
+///
+/// SDL2 never reuses an instance ID within a session, but a subsystem reset
+/// may start over, handing out the IDs of the previous devices again. The
+/// generation of an instance ID is incremented when it is assigned again:
+///
+/// - for the first time since a [`reset`];
+/// - to a device with another [`Guid`] than the previous one.
+///
+/// Used by [`Girl`], which resets it in [`Girl::reinitialize`].
+///
+/// [`reset`]: Self::reset
+/// [`Girl`]: crate::Girl
+/// [`Girl::reinitialize`]: crate::Girl::reinitialize
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct IdGenerations {
+    /// Current generation and [`Guid`] of every instance ID assigned.
+    assigned: BTreeMap<u32, (u32, Guid)>,
+    /// Instance IDs assigned before the last [`reset`], not since.
+    ///
+    /// [`reset`]: Self::reset
+    stale: BTreeSet<u32>,
+}
+
+impl IdGenerations {
+    /// Assigns the [`GamepadId`] of a device with the raw instance ID and
+    /// [`Guid`], in a new generation if the instance ID was reused.
+    #[inline]
+    pub(crate) fn assign(&mut self, which: u32, guid: Guid) -> GamepadId {
+        let stale = self.stale.remove(&which);
+        let Some(entry) = self.assigned.get_mut(&which) else {
+            _ = self.assigned.insert(which, (0, guid));
+            return GamepadId::from_raw(which);
+        };
+        if stale || entry.1 != guid {
+            *entry = (entry.0.wrapping_add(1), guid);
+            let id = GamepadId::with_generation(which, entry.0);
+            diag!(debug, "instance id {which} reused, now gamepad {id}");
+        }
+        GamepadId::with_generation(which, entry.0)
+    }
+
+    /// Gets the [`GamepadId`] last assigned to the raw instance ID, e.g.
+    /// the `which` of an [`Event`], or one of the first generation if none
+    /// was.
+    ///
+    /// [`Event`]: crate::Event
+    #[must_use]
+    #[inline]
+    pub(crate) fn current(&self, which: u32) -> GamepadId {
+        self.assigned.get(&which).map_or_else(
+            || GamepadId::from_raw(which),
+            |&(generation, _)| GamepadId::with_generation(which, generation),
+        )
+    }
+
+    /// Marks every instance ID assigned so far as stale after a subsystem
+    /// reset, so it starts a new generation when assigned again.
+    #[inline]
+    pub(crate) fn reset(&mut self) {
+        self.stale = self.assigned.keys().copied().collect();
+    }
+}
+
+/// Unit tests of [`IdGenerations`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use super::IdGenerations;
+    use crate::{GamepadId, Guid};
+
+    /// Reopening the same device keeps its [`GamepadId`].
+    #[test]
+    fn keeps_reopened() {
+        let mut ids = IdGenerations::default();
+        let pad = Guid::from_bytes([1; 16]);
+
+        let first = ids.assign(0, pad);
+        assert_eq!(first, GamepadId::from_raw(0), "first generation");
+        assert_eq!(ids.assign(0, pad), first, "reopened in a new generation");
+        assert_eq!(ids.current(0), first, "current generation");
+    }
+
+    /// Instance IDs reused after a reset or by another device get a new
+    /// generation.
+    #[test]
+    fn increments_reused() {
+        let mut ids = IdGenerations::default();
+        let pad = Guid::from_bytes([1; 16]);
+        let first = ids.assign(0, pad);
+
+        ids.reset();
+        let second = ids.assign(0, pad);
+        assert_ne!(second, first, "reused after a reset");
+        assert_eq!((second.raw(), second.generation()), (0, 1), "generation");
+        assert_eq!(ids.current(0), second, "current generation");
+
+        let other = Guid::from_bytes([2; 16]);
+        assert_eq!(ids.assign(0, other).generation(), 2, "another device");
+    }
+}
@@ -0,0 +1,277 @@
+//! Thread-safe access to a [`Girl`] from other threads.
+//!
+//! SDL2 requires all of the device I/O to happen on the thread that owns the
+//! event pump. [`GirlHandle`] splits the work: mutating operations are sent as
+//! [`Command`]s over a channel and executed during [`Girl::update`] on the
+//! owning thread, while the latest [`GamepadState`] snapshots are published
+//! for reading from anywhere.
+//!
+//! [`Girl`]: crate::Girl
+//! [`Girl::update`]: crate::Girl::update
+
+use alloc::{collections::BTreeMap, sync::Arc};
+#[cfg(feature = "rumble")]
+use core::time::Duration;
+use std::sync::{PoisonError, RwLock, mpsc};
+
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{Error, Gamepad, GamepadId, GamepadState};
+
+/// Latest [`GamepadState`] snapshots shared between [`Girl`] and its
+/// [`GirlHandle`]s.
+///
+/// [`Girl`]: crate::Girl
+pub(crate) type SharedStates = Arc<RwLock<BTreeMap<GamepadId, GamepadState>>>;
+
+/// Operation on a [`Gamepad`] that can be sent through a [`GirlHandle`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    /// Calls [`Gamepad::set_rumble`].
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    Rumble {
+        /// Low frequency motor intensity.
+        low_frequency: u16,
+        /// High frequency motor intensity.
+        high_frequency: u16,
+        /// Duration of the rumble.
+        duration: Duration,
+    },
+
+    /// Calls [`Gamepad::set_rumble_triggers`].
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    RumbleTriggers {
+        /// Left trigger motor intensity.
+        left: u16,
+        /// Right trigger motor intensity.
+        right: u16,
+        /// Duration of the rumble.
+        duration: Duration,
+    },
+
+    /// Calls [`Gamepad::set_led`].
+    Led {
+        /// Red component.
+        red: u8,
+        /// Green component.
+        green: u8,
+        /// Blue component.
+        blue: u8,
+    },
+
+    /// Calls [`Gamepad::enable_sensor`].
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    EnableSensor(Sensor),
+}
+
+impl Command {
+    /// Executes the [`Command`] on the given [`Gamepad`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying [`Gamepad`] operation fails.
+    #[inline]
+    pub(crate) fn execute(self, gamepad: &mut Gamepad) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "rumble")]
+            Self::Rumble { low_frequency, high_frequency, duration } => {
+                gamepad.set_rumble(low_frequency, high_frequency, duration)
+            }
+            #[cfg(feature = "rumble")]
+            Self::RumbleTriggers { left, right, duration } => {
+                gamepad.set_rumble_triggers(left, right, duration)
+            }
+            Self::Led { red, green, blue } => gamepad.set_led(red, green, blue),
+            #[cfg(feature = "sensors")]
+            Self::EnableSensor(sensor) => gamepad.enable_sensor(sensor),
+        }
+    }
+}
+
+/// [`Command`] addressed to a [`Gamepad`], queued for the owning thread.
+#[derive(Debug)]
+pub(crate) struct Request {
+    /// Target [`Gamepad`].
+    pub id: GamepadId,
+    /// Operation to execute.
+    pub command: Command,
+    /// Where to send the result, if anyone is interested.
+    pub reply: Option<mpsc::Sender<Result<(), Error>>>,
+}
+
+/// Cloneable, thread-safe handle to a [`Girl`].
+///
+/// Can be obtained from [`Girl::handle`]. [`Command`]s sent through the handle
+/// are executed during the next [`Girl::update`] on the thread that owns the
+/// [`Girl`]. [`GamepadState`] snapshots are refreshed on every
+/// [`Girl::update`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::thread;
+/// # use girl::Command;
+/// let mut girl = girl::Girl::new()?;
+/// let handle = girl.handle();
+///
+/// let red = Command::Led { red: 255, green: 0, blue: 0 };
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         let handle = handle.clone();
+///         s.spawn(move || {
+///             for _ in 0..100 {
+///                 for (id, state) in handle.states() {
+///                     if state.right_trigger > 0.5 {
+///                         handle.send(id, red);
+///                     }
+///                 }
+///             }
+///         });
+///     }
+///     for _ in 0..100 {
+///         girl.update();
+///     }
+/// });
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::handle`]: crate::Girl::handle
+/// [`Girl::update`]: crate::Girl::update
+#[derive(Debug, Clone)]
+pub struct GirlHandle {
+    /// Queue of [`Request`]s executed by the owning [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    requests: mpsc::Sender<Request>,
+    /// Latest [`GamepadState`] snapshots.
+    states: SharedStates,
+}
+
+impl GirlHandle {
+    /// Creates a new [`GirlHandle`] from its parts.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "keeps the fields private")]
+    pub(crate) const fn new(
+        requests: mpsc::Sender<Request>,
+        states: SharedStates,
+    ) -> Self {
+        Self { requests, states }
+    }
+
+    /// Sends a [`Command`] to the [`Gamepad`] with the given [`GamepadId`]
+    /// without waiting for the result (fire-and-forget).
+    ///
+    /// The [`Command`] is silently dropped if the owning [`Girl`] no longer
+    /// exists or the [`Gamepad`] is not connected.
+    ///
+    /// [`Girl`]: crate::Girl
+    #[inline]
+    pub fn send(&self, id: GamepadId, command: Command) {
+        // nobody to report the error to
+        drop(self.requests.send(Request { id, command, reply: None }));
+    }
+
+    /// Sends a [`Command`] to the [`Gamepad`] with the given [`GamepadId`]
+    /// and returns a [`CommandReceipt`] to retrieve the result with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{Command, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// let handle = girl.handle();
+    ///
+    /// let led = Command::Led { red: 0, green: 0, blue: 255 };
+    /// let receipt = handle.request(GamepadId::from_raw(u32::MAX), led);
+    /// girl.update();
+    /// assert!(matches!(receipt.wait(), Err(girl::Error::GamepadNotFound(_))));
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use = "use `send` if the result is not needed"]
+    #[inline]
+    pub fn request(&self, id: GamepadId, command: Command) -> CommandReceipt {
+        let (reply, result) = mpsc::channel();
+        // if sending fails, `result` reports `Error::Closed`
+        drop(self.requests.send(Request { id, command, reply: Some(reply) }));
+        CommandReceipt { result }
+    }
+
+    /// Gets the latest [`GamepadState`] of the [`Gamepad`] with the given
+    /// [`GamepadId`], as of the last [`Girl::update`].
+    ///
+    /// Returns [`None`] if no such [`Gamepad`] is connected.
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    #[must_use]
+    #[inline]
+    pub fn state(&self, id: GamepadId) -> Option<GamepadState> {
+        self.states
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .copied()
+    }
+
+    /// Gets the latest [`GamepadState`]s of all connected [`Gamepad`]s, as of
+    /// the last [`Girl::update`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    #[must_use]
+    #[inline]
+    pub fn states(&self) -> Vec<(GamepadId, GamepadState)> {
+        self.states
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(&id, &state)| (id, state))
+            .collect()
+    }
+}
+
+/// Result of a [`Command`] sent with [`GirlHandle::request`].
+#[derive(Debug)]
+#[must_use = "dropping the receipt discards the result"]
+pub struct CommandReceipt {
+    /// Receiving end of the result channel.
+    result: mpsc::Receiver<Result<(), Error>>,
+}
+
+impl CommandReceipt {
+    /// Blocks until the [`Command`] is executed and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the executed [`Command`], or [`Error::Closed`] if
+    /// the owning [`Girl`] was dropped before executing it.
+    ///
+    /// [`Girl`]: crate::Girl
+    #[inline]
+    pub fn wait(self) -> Result<(), Error> {
+        self.result.recv().unwrap_or(Err(Error::Closed))
+    }
+
+    /// Checks whether the [`Command`] was executed, without blocking.
+    ///
+    /// Returns [`None`] if the [`Command`] is still pending.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`wait`].
+    ///
+    /// [`wait`]: Self::wait
+    #[must_use]
+    #[inline]
+    pub fn try_wait(&self) -> Option<Result<(), Error>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => Some(Err(Error::Closed)),
+        }
+    }
+}
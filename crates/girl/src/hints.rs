@@ -0,0 +1,190 @@
+//! SDL2 hints for controller features that must be chosen before a
+//! [`Gamepad`] is opened.
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+use sdl2::hint;
+
+use crate::{Error, logging::diag};
+
+/// SDL2 hint enabling enhanced reports of PS5 controllers.
+const PS5_ENHANCED_REPORTS: &str = "SDL_JOYSTICK_HIDAPI_PS5_RUMBLE";
+
+/// SDL2 hint lighting up the player LEDs of PS5 controllers.
+const PS5_PLAYER_LEDS: &str = "SDL_JOYSTICK_HIDAPI_PS5_PLAYER_LED";
+
+/// SDL2 hint enabling enhanced reports of PS4 controllers.
+const PS4_ENHANCED_REPORTS: &str = "SDL_JOYSTICK_HIDAPI_PS4_RUMBLE";
+
+/// SDL2 hint polling joysticks on a dedicated SDL2 thread.
+const JOYSTICK_THREAD: &str = "SDL_JOYSTICK_THREAD";
+
+/// SDL2 hints applied by [`Girl::with_hints`].
+///
+/// Fields left as [`None`] keep the SDL2 defaults.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::Hints;
+/// let mut hints = Hints::new();
+/// hints.ps5_enhanced_reports = Some(true);
+/// let girl = girl::Girl::with_hints(hints)?;
+///
+/// assert_eq!(Hints::current().ps5_enhanced_reports, Some(true));
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::with_hints`]: crate::Girl::with_hints
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Hints {
+    /// Enables enhanced reports of PS5 controllers, required for full rate
+    /// sensors, adaptive triggers, rumble and the lightbar.
+    pub ps5_enhanced_reports: Option<bool>,
+    /// Lights up the player number LEDs of PS5 controllers.
+    pub ps5_player_leds: Option<bool>,
+    /// Enables enhanced reports of PS4 controllers, required for rumble and
+    /// full rate sensors over Bluetooth.
+    pub ps_rumble_emulation: Option<bool>,
+    /// Polls `XInput` and raw input on a dedicated SDL2 thread on Windows,
+    /// see [`use_joystick_thread`].
+    ///
+    /// [`use_joystick_thread`]: Self::use_joystick_thread
+    pub joystick_thread: Option<bool>,
+}
+
+impl Hints {
+    /// Creates [`Hints`] keeping all the SDL2 defaults.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            ps5_enhanced_reports: None,
+            ps5_player_leds: None,
+            ps_rumble_emulation: None,
+            joystick_thread: None,
+        }
+    }
+
+    /// Sets whether SDL2 polls `XInput` and raw input on a dedicated thread
+    /// on Windows, ignored elsewhere.
+    ///
+    /// Without it, devices are only polled when the events are pumped, in
+    /// [`Girl::update`], so input waits for the next frame whenever the
+    /// application is busy. The dedicated thread polls them as they report
+    /// input, cutting the worst case latency at the cost of a thread
+    /// waking up for every report, i.e. some CPU time and power. Measure
+    /// the difference with the [`max_queue_latency`] of the [`PerfCounters`].
+    ///
+    /// The events still arrive through the same SDL2 event queue, in the
+    /// order the devices reported them, so nothing else changes.
+    ///
+    /// Only applies when set before SDL2 initializes its joystick
+    /// subsystem, i.e. with [`Girl::with_hints`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(windows)]
+    /// # {
+    /// # use sdl2::{controller::Button as Sdl, event::Event as SdlEvent};
+    /// # use girl::{Hints, prelude::*};
+    /// let hints = Hints::new().use_joystick_thread(true);
+    /// let mut girl = Girl::with_hints(hints)?;
+    /// assert_eq!(Hints::current().joystick_thread, Some(true));
+    ///
+    /// // input still arrives through the same queue, in order
+    /// let sdl = sdl2::init().map_err(Error::Sdl2Init)?;
+    /// let events = sdl.event().map_err(Error::Sdl2Init)?;
+    /// girl.update();
+    /// let press = |button| SdlEvent::ControllerButtonDown {
+    ///     timestamp: 0,
+    ///     which: 0,
+    ///     button,
+    /// };
+    /// for button in [Sdl::A, Sdl::B, Sdl::X] {
+    ///     events.push_event(press(button)).unwrap();
+    /// }
+    /// let mut pressed = Vec::new();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::ControllerButtonDown { button, .. } = event {
+    ///         pressed.push(button);
+    ///     }
+    /// }
+    /// assert_eq!(pressed, [Button::A, Button::B, Button::X]);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::with_hints`]: crate::Girl::with_hints
+    /// [`PerfCounters`]: crate::PerfCounters
+    /// [`max_queue_latency`]: crate::PerfCounters::max_queue_latency
+    #[must_use]
+    #[inline]
+    pub const fn use_joystick_thread(mut self, enabled: bool) -> Self {
+        self.joystick_thread = Some(enabled);
+        self
+    }
+
+    /// Reads the [`Hints`] currently set in SDL2.
+    ///
+    /// Hints that are not set or not boolean are reported as [`None`].
+    #[must_use]
+    #[inline]
+    pub fn current() -> Self {
+        Self {
+            ps5_enhanced_reports: get(PS5_ENHANCED_REPORTS),
+            ps5_player_leds: get(PS5_PLAYER_LEDS),
+            ps_rumble_emulation: get(PS4_ENHANCED_REPORTS),
+            joystick_thread: get(JOYSTICK_THREAD),
+        }
+    }
+
+    /// Sets the [`Some`] hints in SDL2.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL2 rejects a hint.
+    #[inline]
+    pub(crate) fn apply(self) -> Result<(), Error> {
+        let hints = [
+            (PS5_ENHANCED_REPORTS, self.ps5_enhanced_reports),
+            (PS5_PLAYER_LEDS, self.ps5_player_leds),
+            (PS4_ENHANCED_REPORTS, self.ps_rumble_emulation),
+            (JOYSTICK_THREAD, self.joystick_thread),
+        ];
+        for (name, value) in hints {
+            if let Some(enabled) = value {
+                set(name, enabled)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sets a boolean SDL2 hint.
+///
+/// # Errors
+///
+/// Returns an error if SDL2 rejects the hint.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn set(name: &str, enabled: bool) -> Result<(), Error> {
+    if hint::set(name, if enabled { "1" } else { "0" }) {
+        Ok(())
+    } else {
+        let err = Error::sdl("set_hint")(sdl2::get_error());
+        diag!(warn, "failed to set {name}: {err}");
+        Err(err)
+    }
+}
+
+/// Gets a boolean SDL2 hint.
+fn get(name: &str) -> Option<bool> {
+    match hint::get(name)?.as_str() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
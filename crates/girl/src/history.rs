@@ -0,0 +1,364 @@
+//! Persistent history of the [`Gamepad`]s seen across runs.
+
+use alloc::collections::BTreeMap;
+use core::{cmp::Reverse, fmt, time::Duration};
+use std::{
+    fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ControllerType, Gamepad, Guid, PowerLevel, logging::diag};
+
+/// First line of the [`DeviceHistory`] file format.
+const HEADER: &str = "girl device history 1";
+
+/// A [`Gamepad`] remembered by [`DeviceHistory`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceRecord {
+    /// [`Guid`] of the [`Gamepad`], see [`Gamepad::guid`].
+    pub guid: Guid,
+    /// Name of the [`Gamepad`], see [`Gamepad::name`].
+    pub name: String,
+    /// [`ControllerType`] of the [`Gamepad`], see
+    /// [`Gamepad::controller_type`].
+    pub controller_type: ControllerType,
+    /// When the [`Gamepad`] was last seen connected.
+    pub last_seen: SystemTime,
+    /// Last known [`PowerLevel`] of the [`Gamepad`], see [`Gamepad::power`].
+    pub power: PowerLevel,
+    /// Nickname assigned by the user, see [`DeviceHistory::set_nickname`].
+    pub nickname: Option<String>,
+}
+
+impl DeviceRecord {
+    /// Creates a new [`DeviceRecord`] with an unknown [`PowerLevel`] and no
+    /// nickname.
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        guid: Guid,
+        name: String,
+        controller_type: ControllerType,
+        last_seen: SystemTime,
+    ) -> Self {
+        Self {
+            guid,
+            name,
+            controller_type,
+            last_seen,
+            power: PowerLevel::Unknown,
+            nickname: None,
+        }
+    }
+
+    /// Gets the nickname, or the name if there is none.
+    #[must_use]
+    #[inline]
+    pub fn display_name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Parses a line of the [`DeviceHistory`] file format.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let guid = fields.next()?.parse().ok()?;
+        let secs = fields.next()?.parse().ok()?;
+        let controller_type = ControllerType::from_name(fields.next()?)?;
+        let power = power_from_name(fields.next()?)?;
+        let name = unescape(fields.next()?);
+        let nickname = fields.next().map(unescape);
+
+        let mut record = Self::new(
+            guid,
+            name,
+            controller_type,
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs))?,
+        );
+        record.power = power;
+        record.nickname = nickname;
+        Some(record)
+    }
+}
+
+/// Formats the [`DeviceRecord`] as a line of the [`DeviceHistory`] file
+/// format, without the line break.
+impl fmt::Display for DeviceRecord {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self
+            .last_seen
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        write!(
+            f,
+            "{}\t{secs}\t{}\t{}\t{}",
+            self.guid,
+            self.controller_type.name(),
+            self.power.name(),
+            escape(&self.name),
+        )?;
+        if let Some(nickname) = self.nickname.as_deref() {
+            write!(f, "\t{}", escape(nickname))?;
+        }
+        Ok(())
+    }
+}
+
+/// History of the [`Gamepad`]s seen across runs, e.g. to show the last used
+/// controller before it reconnects.
+///
+/// Kept up to date by [`Girl`] once attached with [`Girl::attach_history`],
+/// and persisted with [`save`] and [`load`]. Loading is best-effort:
+/// unreadable files and malformed records are skipped instead of failing.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use girl::{DeviceHistory, DeviceRecord, prelude::*};
+/// let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+/// let ps5 = "030000004c050000e60c000000010000".parse()?;
+/// let xbox = "030000005e040000130b000011050000".parse()?;
+///
+/// let mut history = DeviceHistory::new();
+/// let mut record = DeviceRecord::new(
+///     ps5,
+///     "PS5 Controller".to_owned(),
+///     ControllerType::Ps5,
+///     at(1_000),
+/// );
+/// record.power = PowerLevel::Low;
+/// _ = history.insert(record);
+/// _ = history.insert(DeviceRecord::new(
+///     xbox,
+///     "Xbox Series X Controller".to_owned(),
+///     ControllerType::XboxOne,
+///     at(2_000),
+/// ));
+/// assert!(history.set_nickname(ps5, Some("couch\tpad".to_owned())));
+///
+/// let recent = history.recent(1);
+/// assert_eq!(recent[0].guid, xbox);
+/// assert_eq!(history.by_guid(ps5).unwrap().display_name(), "couch\tpad");
+///
+/// // round trip through a file
+/// let path = std::env::temp_dir().join("girl-history-doctest.txt");
+/// history.save(&path).unwrap();
+/// assert_eq!(DeviceHistory::load(&path), history);
+///
+/// // a truncated file keeps the intact records
+/// let text = history.to_string();
+/// let truncated = &text[..text.len() - 30];
+/// std::fs::write(&path, truncated).unwrap();
+/// let loaded = DeviceHistory::load(&path);
+/// assert_eq!(loaded.by_guid(ps5), history.by_guid(ps5));
+/// assert!(loaded.by_guid(xbox).is_none());
+///
+/// // so does garbage, and a missing file is an empty history
+/// assert!(DeviceHistory::parse("\u{0}garbage\n\t\t").is_empty());
+/// std::fs::remove_file(&path).unwrap();
+/// assert!(DeviceHistory::load(&path).is_empty());
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::attach_history`]: crate::Girl::attach_history
+/// [`save`]: Self::save
+/// [`load`]: Self::load
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceHistory {
+    /// Remembered [`Gamepad`]s by [`Guid`].
+    records: BTreeMap<Guid, DeviceRecord>,
+}
+
+/// Formats the [`DeviceHistory`] in the file format used by
+/// [`DeviceHistory::save`].
+impl fmt::Display for DeviceHistory {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        self.records.values().try_for_each(|record| writeln!(f, "{record}"))
+    }
+}
+
+impl DeviceHistory {
+    /// Creates an empty [`DeviceHistory`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { records: BTreeMap::new() }
+    }
+
+    /// Checks if no [`Gamepad`] is remembered.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Gets the record of the [`Gamepad`] with the [`Guid`].
+    #[must_use]
+    #[inline]
+    pub fn by_guid(&self, guid: Guid) -> Option<&DeviceRecord> {
+        self.records.get(&guid)
+    }
+
+    /// Gets up to `count` records, most recently seen first.
+    #[must_use]
+    #[inline]
+    pub fn recent(&self, count: usize) -> Vec<&DeviceRecord> {
+        let mut records: Vec<_> = self.records.values().collect();
+        records.sort_by_key(|record| Reverse(record.last_seen));
+        records.truncate(count);
+        records
+    }
+
+    /// Sets or clears the nickname of the [`Gamepad`] with the [`Guid`].
+    ///
+    /// Returns `false` if the [`Gamepad`] is not remembered.
+    #[inline]
+    pub fn set_nickname(
+        &mut self,
+        guid: Guid,
+        nickname: Option<String>,
+    ) -> bool {
+        let Some(record) = self.records.get_mut(&guid) else {
+            return false;
+        };
+        record.nickname = nickname;
+        true
+    }
+
+    /// Inserts a record, replacing and returning the one with the same
+    /// [`Guid`].
+    #[inline]
+    pub fn insert(&mut self, record: DeviceRecord) -> Option<DeviceRecord> {
+        self.records.insert(record.guid, record)
+    }
+
+    /// Forgets the [`Gamepad`] with the [`Guid`], returning its record.
+    #[inline]
+    pub fn remove(&mut self, guid: Guid) -> Option<DeviceRecord> {
+        self.records.remove(&guid)
+    }
+
+    /// Records that the [`Gamepad`] was seen at `now`, keeping its nickname
+    /// and, if it is not reported anymore, its last [`PowerLevel`].
+    #[inline]
+    pub fn observe(&mut self, gamepad: &Gamepad, now: SystemTime) {
+        let record = self.records.entry(gamepad.guid()).or_insert_with(|| {
+            DeviceRecord::new(
+                gamepad.guid(),
+                gamepad.name(),
+                gamepad.controller_type(),
+                now,
+            )
+        });
+        record.last_seen = now;
+        if let Some(power) = gamepad.power() {
+            record.power = power;
+        }
+    }
+
+    /// Parses the file format used by [`save`], skipping malformed records.
+    ///
+    /// [`save`]: Self::save
+    #[must_use]
+    #[inline]
+    pub fn parse(text: &str) -> Self {
+        let mut history = Self::new();
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if line == HEADER {
+                continue;
+            }
+            match DeviceRecord::parse(line) {
+                Some(record) => drop(history.insert(record)),
+                None => diag!(warn, "skipped device history record {line:?}"),
+            }
+        }
+        history
+    }
+
+    /// Loads a [`DeviceHistory`] saved with [`save`].
+    ///
+    /// Best-effort: a missing or unreadable file is an empty
+    /// [`DeviceHistory`], and malformed records are skipped.
+    ///
+    /// [`save`]: Self::save
+    #[must_use]
+    #[inline]
+    pub fn load(path: &Path) -> Self {
+        match fs::read(path) {
+            Ok(bytes) => Self::parse(&String::from_utf8_lossy(&bytes)),
+            Err(err) => {
+                if err.kind() != io::ErrorKind::NotFound {
+                    diag!(warn, "failed to read device history: {err}");
+                }
+                Self::new()
+            }
+        }
+    }
+
+    /// Saves the [`DeviceHistory`] to the file at `path`.
+    ///
+    /// Writes a temporary file next to it first, so an interrupted save does
+    /// not corrupt a previously saved [`DeviceHistory`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[inline]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.to_string())?;
+        fs::rename(tmp, path)
+    }
+}
+
+/// Parses the name of a [`PowerLevel`], see [`PowerLevel::name`].
+#[expect(clippy::single_call_fn, reason = "extracted conversion")]
+fn power_from_name(name: &str) -> Option<PowerLevel> {
+    Some(match name {
+        "Unknown" => PowerLevel::Unknown,
+        "Empty" => PowerLevel::Empty,
+        "Low" => PowerLevel::Low,
+        "Medium" => PowerLevel::Medium,
+        "Full" => PowerLevel::Full,
+        "Wired" => PowerLevel::Wired,
+        _ => return None,
+    })
+}
+
+/// Escapes backslashes, tabs and line breaks of a field.
+fn escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Reverts [`escape`], keeping unknown escapes as is.
+fn unescape(field: &str) -> String {
+    let mut out = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
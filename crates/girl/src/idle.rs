@@ -0,0 +1,286 @@
+//! Detection of idle periods without controller input.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Event, GamepadId};
+
+/// Configuration of idle detection, see [`IdleTracker`] and
+/// [`Girl::set_idle_config`].
+///
+/// [`Girl::set_idle_config`]: crate::Girl::set_idle_config
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdleConfig {
+    /// How long without input until [`Event::InputIdle`] is reported.
+    /// [`None`] never reports it, while idle times are still tracked.
+    pub threshold: Option<Duration>,
+    /// Smallest stick deflection or trigger value counted as input, so
+    /// analog noise doesn't keep the input awake.
+    pub analog_threshold: f64,
+    /// Whether sensor updates count as input. They are reported
+    /// continuously by a controller lying on a table, so they don't by
+    /// default.
+    pub sensors: bool,
+}
+
+impl Default for IdleConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdleConfig {
+    /// Creates the default [`IdleConfig`]: no [`Event::InputIdle`], an
+    /// analog threshold of `0.1`, and sensor updates not counting as input.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { threshold: None, analog_threshold: 0.1, sensors: false }
+    }
+}
+
+/// Tracks how long the input of all [`Gamepad`]s and of each one has been
+/// idle, e.g. to dim the UI of a handheld.
+///
+/// Reports [`Event::InputIdle`] once when no input arrived for the
+/// [`IdleConfig::threshold`], and [`Event::InputResumed`] once with the
+/// next input. Input is any input [`Event`] except the ones filtered out by
+/// the [`IdleConfig`]. A connecting controller counts as input too, e.g. a
+/// sleeping Bluetooth controller waking up, while connection changes and
+/// other notifications don't.
+///
+/// Used by [`Girl::idle_time`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{IdleConfig, IdleTracker, prelude::*};
+/// let start = Instant::now();
+/// let secs = Duration::from_secs;
+/// let at = |after| start + secs(after);
+/// let mut config = IdleConfig::new();
+/// config.threshold = Some(secs(60));
+/// let mut tracker = IdleTracker::new(config, start);
+///
+/// let (one, two) = (GamepadId::from_raw(1), GamepadId::from_raw(2));
+/// _ = tracker.observe(&Event::ControllerDeviceAdded { which: 1 }, at(0));
+/// _ = tracker.observe(&Event::ControllerDeviceAdded { which: 2 }, at(0));
+/// let press = Event::ControllerButtonDown { which: 1, button: Button::A };
+/// assert!(tracker.observe(&press, at(10)).is_none());
+///
+/// // idle times are global and per device
+/// assert_eq!(tracker.idle_time(at(40)), secs(30));
+/// assert_eq!(tracker.idle_time_of(one, at(40)), Some(secs(30)));
+/// assert_eq!(tracker.idle_time_of(two, at(40)), Some(secs(40)));
+///
+/// // noise doesn't count
+/// let drift = Event::ControllerStickMotion {
+///     which: 2,
+///     stick: Stick::Left,
+///     offset: [0.05, 0.0],
+/// };
+/// _ = tracker.observe(&drift, at(50));
+/// assert_eq!(tracker.idle_time_of(two, at(50)), Some(secs(50)));
+///
+/// // going idle is reported once
+/// assert!(tracker.poll(at(69)).is_none());
+/// let Some(Event::InputIdle { after }) = tracker.poll(at(70)) else {
+///     panic!("not reported");
+/// };
+/// assert_eq!(after, secs(60));
+/// assert!(tracker.poll(at(100)).is_none());
+/// assert!(tracker.is_idle());
+///
+/// // and so is resuming, with any device
+/// let resumed = tracker.observe(&press, at(110));
+/// assert!(matches!(resumed, Some(Event::InputResumed)));
+/// assert!(tracker.observe(&press, at(111)).is_none());
+/// assert_eq!(tracker.idle_time(at(111)), Duration::ZERO);
+/// assert_eq!(tracker.idle_time_of(two, at(111)), Some(secs(111)));
+///
+/// // disconnected devices are forgotten
+/// _ = tracker.observe(&Event::ControllerDeviceRemoved { which: 2 }, at(112));
+/// assert_eq!(tracker.idle_time_of(two, at(112)), None);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Girl::idle_time`]: crate::Girl::idle_time
+#[derive(Debug, Clone)]
+pub struct IdleTracker {
+    /// Configuration.
+    config: IdleConfig,
+    /// When the last input arrived, or when tracking started.
+    last: Instant,
+    /// When the last input of each connected [`Gamepad`] arrived, or when it
+    /// connected.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    devices: BTreeMap<GamepadId, Instant>,
+    /// Whether [`Event::InputIdle`] was reported since the last input.
+    idle: bool,
+}
+
+impl IdleTracker {
+    /// Creates a new [`IdleTracker`], idle since `now`.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: IdleConfig, now: Instant) -> Self {
+        Self { config, last: now, devices: BTreeMap::new(), idle: false }
+    }
+
+    /// Gets the [`IdleConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> IdleConfig {
+        self.config
+    }
+
+    /// Sets the [`IdleConfig`].
+    #[inline]
+    pub const fn set_config(&mut self, config: IdleConfig) {
+        self.config = config;
+    }
+
+    /// Checks whether [`Event::InputIdle`] was reported since the last
+    /// input.
+    #[must_use]
+    #[inline]
+    pub const fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Observes an [`Event`] at `now`, returning [`Event::InputResumed`] if
+    /// it is the first input since [`Event::InputIdle`].
+    ///
+    /// The [`Event`] is attributed to the first generation of the
+    /// [`GamepadId`] of its `which`, see [`observe_as`] for another one.
+    ///
+    /// [`observe_as`]: Self::observe_as
+    #[inline]
+    pub fn observe(&mut self, event: &Event, now: Instant) -> Option<Event> {
+        self.observe_as(event, event.which().map(GamepadId::from_raw), now)
+    }
+
+    /// Observes an [`Event`] at `now` like [`observe`], attributing it to
+    /// the [`Gamepad`] with the [`GamepadId`], e.g. one resolved with
+    /// [`Girl::resolve_id`], so a device reusing an instance ID starts
+    /// with its own idle time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::{Duration, Instant};
+    /// # use girl::{IdleConfig, IdleTracker, prelude::*};
+    /// let start = Instant::now();
+    /// let at = |secs| start + Duration::from_secs(secs);
+    /// let mut idle = IdleTracker::new(IdleConfig::new(), start);
+    /// let old = GamepadId::from_raw(1);
+    /// let new = GamepadId::with_generation(1, 1);
+    ///
+    /// let added = Event::ControllerDeviceAdded { which: 1 };
+    /// _ = idle.observe_as(&added, Some(old), at(0));
+    /// let removed = Event::ControllerDeviceRemoved { which: 1 };
+    /// _ = idle.observe_as(&removed, Some(old), at(5));
+    /// _ = idle.observe_as(&added, Some(new), at(10));
+    ///
+    /// assert_eq!(idle.idle_time_of(old, at(20)), None);
+    /// let idle_time = idle.idle_time_of(new, at(20));
+    /// assert_eq!(idle_time, Some(Duration::from_secs(10)));
+    /// ```
+    ///
+    /// [`observe`]: Self::observe
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl::resolve_id`]: crate::Girl::resolve_id
+    #[inline]
+    pub fn observe_as(
+        &mut self,
+        event: &Event,
+        id: Option<GamepadId>,
+        now: Instant,
+    ) -> Option<Event> {
+        if let Event::ControllerDeviceRemoved { .. } = *event {
+            if let Some(id) = id {
+                _ = self.devices.remove(&id);
+            }
+            return None;
+        }
+        if !self.counts(event) {
+            return None;
+        }
+        if let Some(id) = id {
+            _ = self.devices.insert(id, now);
+        }
+        self.last = now;
+        self.idle.then(|| {
+            self.idle = false;
+            Event::InputResumed
+        })
+    }
+
+    /// Gets when the last input arrived, or when tracking started if none
+    /// did.
+    #[must_use]
+    #[inline]
+    pub const fn last_input(&self) -> Instant {
+        self.last
+    }
+
+    /// Checks for the [`IdleConfig::threshold`] being crossed at `now`,
+    /// returning [`Event::InputIdle`] once it is.
+    #[inline]
+    pub fn poll(&mut self, now: Instant) -> Option<Event> {
+        let after = self.idle_time(now);
+        let crossed = self.config.threshold.is_some_and(|at| after >= at);
+        (!self.idle && crossed).then(|| {
+            self.idle = true;
+            Event::InputIdle { after }
+        })
+    }
+
+    /// Gets how long no input arrived from any [`Gamepad`] at `now`.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn idle_time(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last)
+    }
+
+    /// Gets how long no input arrived from the [`Gamepad`] with the given
+    /// [`GamepadId`] at `now`, or since it connected.
+    ///
+    /// Returns [`None`] if the [`Gamepad`] is not connected.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn idle_time_of(
+        &self,
+        id: GamepadId,
+        now: Instant,
+    ) -> Option<Duration> {
+        let last = self.devices.get(&id)?;
+        Some(now.saturating_duration_since(*last))
+    }
+
+    /// Checks whether the [`Event`] counts as input.
+    fn counts(&self, event: &Event) -> bool {
+        #[cfg(feature = "sensors")]
+        if let Event::ControllerSensorUpdated { .. } = *event {
+            return self.config.sensors;
+        }
+        if let Event::ControllerStickMotion { offset: [x, y], .. } = *event {
+            return x.hypot(y) >= self.config.analog_threshold;
+        }
+        if let Event::ControllerTriggerMotion { offset, .. } = *event {
+            return offset >= self.config.analog_threshold;
+        }
+        matches!(*event, Event::ControllerDeviceAdded { .. })
+            || event.is_input()
+    }
+}
@@ -0,0 +1,244 @@
+//! Input settings shared by all [`Gamepad`]s, see
+//! [`Girl::set_default_input_config`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+//! [`Girl::set_default_input_config`]: crate::Girl::set_default_input_config
+
+use crate::{Error, InputProfile, ResponseCurve, Stick, Trigger};
+
+/// Input settings applied to every [`Gamepad`] when it is opened, including
+/// on reconnect, see [`Girl::set_default_input_config`].
+///
+/// Settings left as [`None`] keep the value of the layer below. The layers
+/// of a setting, from the lowest precedence to the highest:
+///
+/// | Layer                   | Set with                                 |
+/// | ----------------------- | ---------------------------------------- |
+/// | Built-in default        | [`InputProfile::new`]                    |
+/// | Controller type default | [`Girl::set_default_profile`]            |
+/// | Global default          | [`Girl::set_default_input_config`]       |
+/// | Per-pad override        | [`Gamepad`] setters, e.g. [`set_invert_y`] |
+///
+/// A profile applied with [`Gamepad::apply_profile`] overrides every
+/// setting, and is remembered across reconnects. The other per-pad
+/// overrides only last for the connection.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{DefaultProfiles, Guid, InputConfig, InputOverrides};
+/// # use girl::prelude::*;
+/// // built-in default
+/// let mut layered = InputProfile::new();
+/// assert_eq!(layered.left_stick.deadzone, 0.1);
+///
+/// // controller type default
+/// let defaults = DefaultProfiles::new();
+/// let pro = ControllerType::SwitchPro;
+/// layered.clone_from(defaults.profile_for(Guid::default(), pro).unwrap());
+/// assert_eq!(layered.left_stick.deadzone, 0.15);
+/// assert!(!layered.left_stick.invert_y);
+///
+/// // global default
+/// let mut config = InputConfig::new();
+/// config.stick_deadzone = Some(0.2);
+/// config.invert_y = Some(true);
+/// config.apply(&mut layered, InputOverrides::empty());
+/// assert_eq!(layered.left_stick.deadzone, 0.2);
+/// assert!(layered.right_stick.invert_y);
+/// // unset settings keep the controller type default
+/// assert_eq!(layered.remapped(Button::A), Button::B);
+///
+/// // per-pad override of the left stick deadzone
+/// layered.left_stick.deadzone = 0.05;
+/// let overrides = InputOverrides::LeftStickDeadzone;
+/// config.stick_deadzone = Some(0.25);
+/// config.apply(&mut layered, overrides);
+/// assert_eq!(layered.left_stick.deadzone, 0.05);
+/// assert_eq!(layered.right_stick.deadzone, 0.25);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::apply_profile`]: crate::Gamepad::apply_profile
+/// [`set_invert_y`]: crate::Gamepad::set_invert_y
+/// [`Girl::set_default_profile`]: crate::Girl::set_default_profile
+/// [`Girl::set_default_input_config`]: crate::Girl::set_default_input_config
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InputConfig {
+    /// Deadzone of both [`Stick`]s, in the `[0.0, 1.0)` range.
+    pub stick_deadzone: Option<f64>,
+    /// [`ResponseCurve`] of both [`Stick`]s.
+    pub stick_curve: Option<ResponseCurve>,
+    /// Whether the `y` axis of both [`Stick`]s is inverted.
+    pub invert_y: Option<bool>,
+    /// Deadzone of both [`Trigger`]s, in the `[0.0, 1.0)` range.
+    pub trigger_deadzone: Option<f64>,
+    /// Value at which both [`Trigger`]s count as pressed, in the
+    /// `(0.0, 1.0]` range.
+    pub trigger_threshold: Option<f64>,
+}
+
+impl InputConfig {
+    /// Creates an [`InputConfig`] setting nothing.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            stick_deadzone: None,
+            stick_curve: None,
+            invert_y: None,
+            trigger_deadzone: None,
+            trigger_threshold: None,
+        }
+    }
+
+    /// Validates all the settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] listing **all** the invalid fields,
+    /// as named in [`InputProfile`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::InputConfig;
+    /// let mut config = InputConfig::new();
+    /// config.stick_deadzone = Some(1.5);
+    /// let Err(girl::Error::InvalidProfile(invalid)) = config.validate() else {
+    ///     unreachable!();
+    /// };
+    /// assert_eq!(invalid, ["left_stick.deadzone", "right_stick.deadzone"]);
+    /// ```
+    #[inline]
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut profile = InputProfile::new();
+        self.apply(&mut profile, InputOverrides::empty());
+        profile.validate()
+    }
+
+    /// Applies the settings to the `profile`, except the `overridden` ones.
+    #[inline]
+    pub fn apply(
+        &self,
+        profile: &mut InputProfile,
+        overridden: InputOverrides,
+    ) {
+        for stick in [Stick::Left, Stick::Right] {
+            let settings = InputOverrides::of_stick(stick);
+            let target = profile.stick_mut(stick);
+            set(
+                &mut target.deadzone,
+                self.stick_deadzone,
+                overridden,
+                settings.0,
+            );
+            set(&mut target.curve, self.stick_curve, overridden, settings.1);
+            set(&mut target.invert_y, self.invert_y, overridden, settings.2);
+        }
+        for trigger in [Trigger::Left, Trigger::Right] {
+            let settings = InputOverrides::of_trigger(trigger);
+            let target = profile.trigger_mut(trigger);
+            set(
+                &mut target.deadzone,
+                self.trigger_deadzone,
+                overridden,
+                settings.0,
+            );
+            set(
+                &mut target.threshold,
+                self.trigger_threshold,
+                overridden,
+                settings.1,
+            );
+        }
+    }
+}
+
+bitflags::bitflags! {
+    /// Settings of a [`Gamepad`] overridden individually, which changes of
+    /// the [`InputConfig`] don't touch, see
+    /// [`Girl::propagate_default_input_config`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl::propagate_default_input_config`]:
+    ///     crate::Girl::propagate_default_input_config
+    #[derive(
+        Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+    )]
+    pub struct InputOverrides: u16 {
+        /// Deadzone of the left [`Stick`].
+        const LeftStickDeadzone = 1 << 0;
+        /// Deadzone of the right [`Stick`].
+        const RightStickDeadzone = 1 << 1;
+        /// [`ResponseCurve`] of the left [`Stick`].
+        const LeftStickCurve = 1 << 2;
+        /// [`ResponseCurve`] of the right [`Stick`].
+        const RightStickCurve = 1 << 3;
+        /// Inverted `y` axis of the left [`Stick`].
+        const LeftInvertY = 1 << 4;
+        /// Inverted `y` axis of the right [`Stick`].
+        const RightInvertY = 1 << 5;
+        /// Deadzone of the left [`Trigger`].
+        const LeftTriggerDeadzone = 1 << 6;
+        /// Deadzone of the right [`Trigger`].
+        const RightTriggerDeadzone = 1 << 7;
+        /// Threshold of the left [`Trigger`].
+        const LeftTriggerThreshold = 1 << 8;
+        /// Threshold of the right [`Trigger`].
+        const RightTriggerThreshold = 1 << 9;
+    }
+}
+
+impl InputOverrides {
+    /// Gets the deadzone, curve and inverted `y` axis settings of a
+    /// [`Stick`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn of_stick(stick: Stick) -> (Self, Self, Self) {
+        match stick {
+            Stick::Left => (
+                Self::LeftStickDeadzone,
+                Self::LeftStickCurve,
+                Self::LeftInvertY,
+            ),
+            Stick::Right => (
+                Self::RightStickDeadzone,
+                Self::RightStickCurve,
+                Self::RightInvertY,
+            ),
+        }
+    }
+
+    /// Gets the deadzone and threshold settings of a [`Trigger`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn of_trigger(trigger: Trigger) -> (Self, Self) {
+        match trigger {
+            Trigger::Left => {
+                (Self::LeftTriggerDeadzone, Self::LeftTriggerThreshold)
+            }
+            Trigger::Right => {
+                (Self::RightTriggerDeadzone, Self::RightTriggerThreshold)
+            }
+        }
+    }
+}
+
+/// Sets the `target` to the `value`, if any, unless the `setting` is
+/// `overridden`.
+fn set<T>(
+    target: &mut T,
+    value: Option<T>,
+    overridden: InputOverrides,
+    setting: InputOverrides,
+) {
+    if let Some(new) = value
+        && !overridden.contains(setting)
+    {
+        *target = new;
+    }
+}
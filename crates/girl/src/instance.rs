@@ -0,0 +1,109 @@
+//! Process-wide guard against more than one [`Girl`] at a time.
+//!
+//! [`Girl`]: crate::Girl
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::Error;
+
+/// Whether a [`Girl`] exists.
+///
+/// [`Girl`]: crate::Girl
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Marks a [`Girl`] as existing until dropped, see [`is_initialized`].
+///
+/// Owned by the [`Girl`] itself, so it is released once the [`Girl`] is
+/// dropped, even if SDL2 handles outlive it, e.g. leaked [`Gamepad`]s.
+///
+/// [`Girl`]: crate::Girl
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug)]
+pub(crate) struct InstanceGuard(());
+
+impl InstanceGuard {
+    /// Acquires the guard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::AlreadyInitialized`] if a [`Girl`] exists.
+    ///
+    /// [`Girl`]: crate::Girl
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    pub(crate) fn acquire() -> Result<Self, Error> {
+        INITIALIZED
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map(|_| Self(()))
+            .map_err(|_initialized| Error::AlreadyInitialized)
+    }
+}
+
+impl Drop for InstanceGuard {
+    #[inline]
+    fn drop(&mut self) {
+        INITIALIZED.store(false, Ordering::Release);
+    }
+}
+
+/// Checks if a [`Girl`] currently exists in the process.
+///
+/// Only one [`Girl`] can exist at a time, as two would run two SDL2 event
+/// pumps fighting over the same events. Creating another one fails with
+/// [`Error::AlreadyInitialized`] until the existing one is dropped, which
+/// is the way to intentionally re-create it, e.g. to start over with other
+/// [`Hints`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::prelude::*;
+/// assert!(!girl::is_initialized());
+/// let girl = Girl::new()?;
+/// assert!(girl::is_initialized());
+/// assert!(matches!(Girl::new(), Err(Error::AlreadyInitialized)));
+///
+/// // drop the first one to create another
+/// drop(girl);
+/// assert!(!girl::is_initialized());
+/// let girl = Girl::new()?;
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// Of concurrent constructors, only one succeeds:
+///
+/// ```
+/// # use std::{sync::{Arc, Barrier}, thread};
+/// # use girl::prelude::*;
+/// let barrier = Arc::new(Barrier::new(8));
+/// let threads: Vec<_> = (0..8)
+///     .map(|_| {
+///         let barrier = Arc::clone(&barrier);
+///         thread::spawn(move || {
+///             barrier.wait();
+///             let girl = Girl::new_eventless();
+///             // keep the winner alive until every thread tried
+///             barrier.wait();
+///             match girl {
+///                 Ok(_) => true,
+///                 Err(Error::AlreadyInitialized) => false,
+///                 Err(err) => panic!("{err}"),
+///             }
+///         })
+///     })
+///     .collect();
+/// let created = threads
+///     .into_iter()
+///     .map(|thread| thread.join().unwrap())
+///     .filter(|&created| created)
+///     .count();
+/// assert_eq!(created, 1);
+/// assert!(!girl::is_initialized());
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Hints`]: crate::Hints
+#[must_use]
+#[inline]
+pub fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
@@ -0,0 +1,321 @@
+//! Driving [`Gamepad`] LEDs from their input, see
+//! [`Girl::add_led_binding`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+//! [`Girl::add_led_binding`]: crate::Girl::add_led_binding
+
+use alloc::collections::BTreeMap;
+use core::{fmt, time::Duration};
+use std::time::Instant;
+
+use crate::{
+    Error, GamepadId, GamepadState, Rgb, logging::diag, ratelimit::RateLimiter,
+};
+
+/// Color of a [`Gamepad`] LED computed from its input, or [`None`] to leave
+/// it as is, see [`Girl::add_led_binding`].
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Girl::add_led_binding`]: crate::Girl::add_led_binding
+pub type LedBinding = Box<dyn Fn(&GamepadState) -> Option<Rgb>>;
+
+/// Handle of a binding added to [`LedBindings`], see
+/// [`Girl::add_led_binding`].
+///
+/// [`Girl::add_led_binding`]: crate::Girl::add_led_binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LedBindingId(u64);
+
+/// LED colors of [`Gamepad`]s computed from their [`GamepadState`], e.g.
+/// tinting the LED by how far the triggers are pulled.
+///
+/// Every binding is a closure returning the color of a single [`Gamepad`],
+/// or [`None`] to leave it to the bindings added before it: of the
+/// bindings of a [`Gamepad`], the last added one returning a color wins.
+/// If none does, the LED is left as it is.
+///
+/// Colors are only set when they change by more than the [`tolerance`] in
+/// any channel, and at most once per [`interval`], as every call goes to
+/// the controller, over Bluetooth too. A color held back by the
+/// [`interval`] is set by a later [`apply`] once it passes.
+///
+/// Used by [`Girl::tick`] once added with [`Girl::add_led_binding`], and
+/// by [`SimGirl::advance`], skipping [`Gamepad`]s without an LED, but can
+/// also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{LedBindings, prelude::*};
+/// let mut bindings = LedBindings::new(Duration::from_millis(50), 2);
+/// let pad = GamepadId::from_raw(1);
+/// // red follows the left trigger
+/// let red = |state: &GamepadState| {
+///     Some(Rgb::new((state.left_trigger * 255.0) as u8, 0, 0))
+/// };
+/// let binding = bindings.add(pad, Box::new(red));
+///
+/// let mut state = GamepadState::default();
+/// state.left_trigger = 0.5;
+/// bindings.apply(Instant::now(), pad, &state, |color| {
+///     println!("LED of gamepad {pad} is now {color:?}");
+///     Ok(())
+/// });
+/// drop(bindings.remove(binding));
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`tolerance`]: Self::tolerance
+/// [`interval`]: Self::interval
+/// [`apply`]: Self::apply
+/// [`Girl::tick`]: crate::Girl::tick
+/// [`Girl::add_led_binding`]: crate::Girl::add_led_binding
+/// [`SimGirl::advance`]: crate::SimGirl::advance
+pub struct LedBindings {
+    /// Added bindings, in order.
+    entries: Vec<Binding>,
+    /// What was last set on every [`Gamepad`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    outputs: BTreeMap<GamepadId, Output>,
+    /// [`LedBindingId`] of the next added binding.
+    next_id: u64,
+    /// Minimal time between two colors set on a [`Gamepad`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    interval: Duration,
+    /// Largest channel difference considered unchanged.
+    tolerance: u8,
+}
+
+/// Binding added to [`LedBindings`].
+struct Binding {
+    /// Handle returned when adding it.
+    id: LedBindingId,
+    /// [`Gamepad`] whose LED it colors.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pad: GamepadId,
+    /// The binding itself.
+    color: LedBinding,
+}
+
+/// What [`LedBindings`] set on a [`Gamepad`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug, Clone, Copy)]
+struct Output {
+    /// Limits how often a color is set.
+    limiter: RateLimiter,
+    /// Color set last, if any.
+    last: Option<Rgb>,
+}
+
+impl Default for LedBindings {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_INTERVAL, Self::DEFAULT_TOLERANCE)
+    }
+}
+
+impl fmt::Debug for LedBindings {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedBindings")
+            .field(
+                "ids",
+                &self.entries.iter().map(|entry| entry.id).collect::<Vec<_>>(),
+            )
+            .field("outputs", &self.outputs)
+            .field("interval", &self.interval)
+            .field("tolerance", &self.tolerance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LedBindings {
+    /// Minimal time between two colors set on a [`Gamepad`] used by
+    /// [`Girl`], 20 times per second.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl`]: crate::Girl
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(50);
+    /// Largest channel difference considered unchanged used by [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    pub const DEFAULT_TOLERANCE: u8 = 2;
+
+    /// Creates [`LedBindings`] without bindings, setting a color at most
+    /// once per `interval` and only when a channel changes by more than
+    /// `tolerance`.
+    #[must_use]
+    #[inline]
+    pub const fn new(interval: Duration, tolerance: u8) -> Self {
+        Self {
+            entries: Vec::new(),
+            outputs: BTreeMap::new(),
+            next_id: 0,
+            interval,
+            tolerance,
+        }
+    }
+
+    /// Gets the minimal time between two colors set on a [`Gamepad`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Sets the minimal time between two colors set on a [`Gamepad`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+        for output in self.outputs.values_mut() {
+            let mut limiter = RateLimiter::new(interval);
+            if let Some(last) = output.limiter.last() {
+                limiter.mark(last);
+            }
+            output.limiter = limiter;
+        }
+    }
+
+    /// Gets the largest channel difference considered unchanged.
+    #[must_use]
+    #[inline]
+    pub const fn tolerance(&self) -> u8 {
+        self.tolerance
+    }
+
+    /// Adds a binding coloring the LED of the [`Gamepad`] with the
+    /// [`GamepadId`], after the ones already added, returning its handle.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn add(&mut self, pad: GamepadId, color: LedBinding) -> LedBindingId {
+        let id = LedBindingId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.entries.push(Binding { id, pad, color });
+        id
+    }
+
+    /// Removes a binding, returning it.
+    ///
+    /// Returns [`None`] if it was already removed.
+    #[inline]
+    pub fn remove(&mut self, id: LedBindingId) -> Option<LedBinding> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index).color)
+    }
+
+    /// Checks whether no binding was added.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the color of the [`Gamepad`] with the [`GamepadId`] for its
+    /// `state`, the one of the last added binding returning one.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn color(&self, pad: GamepadId, state: &GamepadState) -> Option<Rgb> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.pad == pad)
+            .find_map(|entry| (entry.color)(state))
+    }
+
+    /// Sets the [`color`] of the [`Gamepad`] with the [`GamepadId`] for
+    /// its `state` at `now` with `set_led`, unless it is unchanged or the
+    /// [`interval`] hasn't passed yet, returning whether it did.
+    ///
+    /// A color failing to be set is tried again after the [`interval`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`color`]: Self::color
+    /// [`interval`]: Self::interval
+    #[inline]
+    pub fn apply<F>(
+        &mut self,
+        now: Instant,
+        pad: GamepadId,
+        state: &GamepadState,
+        set_led: F,
+    ) -> bool
+    where
+        F: FnOnce(Rgb) -> Result<(), Error>,
+    {
+        let Some(color) = self.color(pad, state) else {
+            return false;
+        };
+        let (interval, tolerance) = (self.interval, self.tolerance);
+        let output = self.outputs.entry(pad).or_insert_with(|| Output {
+            limiter: RateLimiter::new(interval),
+            last: None,
+        });
+        if output.last.is_some_and(|last| close(last, color, tolerance))
+            || !output.limiter.ready(now)
+        {
+            return false;
+        }
+        match set_led(color) {
+            Ok(()) => {
+                output.last = Some(color);
+                true
+            }
+            Err(err) => {
+                diag!(debug, "failed to set bound LED of gamepad {pad}: {err}");
+                false
+            }
+        }
+    }
+
+    /// Moves the bindings of every `(from, to)` pair of [`GamepadId`]s to
+    /// `to`, e.g. once [`Girl::reinitialize`] reopens the [`Gamepad`]s,
+    /// dropping the bindings of the other [`Gamepad`]s. Colors are set
+    /// again regardless of what was set before.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl::reinitialize`]: crate::Girl::reinitialize
+    #[inline]
+    pub fn reassign(&mut self, moves: &[(GamepadId, GamepadId)]) {
+        self.entries.retain_mut(|entry| {
+            let Some(&(_, to)) =
+                moves.iter().find(|&&(from, _)| from == entry.pad)
+            else {
+                return false;
+            };
+            entry.pad = to;
+            true
+        });
+        self.outputs.clear();
+    }
+
+    /// Drops the bindings of the [`Gamepad`] with the [`GamepadId`], e.g.
+    /// once it disconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn forget(&mut self, pad: GamepadId) {
+        self.entries.retain(|entry| entry.pad != pad);
+        _ = self.outputs.remove(&pad);
+    }
+}
+
+/// Checks if no channel of the colors differs by more than `tolerance`.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+const fn close(first: Rgb, second: Rgb, tolerance: u8) -> bool {
+    first.red.abs_diff(second.red) <= tolerance
+        && first.green.abs_diff(second.green) <= tolerance
+        && first.blue.abs_diff(second.blue) <= tolerance
+}
@@ -1,52 +1,663 @@
-#![cfg_attr(doc, doc = include_str!("../README.md"))]
+#![cfg_attr(all(doc, feature = "sdl2"), doc = include_str!("../README.md"))]
 //! ## Feature flags
 #![cfg_attr(
     feature = "document-features",
     cfg_attr(doc, doc = ::document_features::document_features!())
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    not(feature = "sdl2"),
+    allow(
+        rustdoc::broken_intra_doc_links,
+        reason = "the core types link to `Girl` and `Gamepad`"
+    )
+)]
+
+#[cfg(any(feature = "alloc", test))]
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
 
+#[cfg(feature = "std")]
+mod activity;
+#[cfg(feature = "std")]
+mod adaptive;
+#[cfg(feature = "std")]
+mod aim;
+#[cfg(feature = "sdl2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+pub mod app;
+#[cfg(feature = "sdl2")]
+mod audio;
+#[cfg(feature = "sdl2")]
+mod background;
+#[cfg(feature = "std")]
+mod bus;
+#[cfg(feature = "sdl2-interop")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2-interop")))]
+pub mod compat;
+#[cfg(all(feature = "sdl2", not(feature = "sdl2-interop")))]
+#[expect(unreachable_pub, reason = "only public with `sdl2-interop`")]
+#[expect(
+    clippy::allow_attributes,
+    reason = "rustdoc lints are not checked by rustc"
+)]
+#[allow(rustdoc::private_doc_tests, reason = "only public with `sdl2-interop`")]
+mod compat;
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+pub mod config;
+#[cfg(feature = "sdl2")]
+mod connection;
+#[cfg(feature = "sdl2")]
+mod contexts;
+#[cfg(feature = "std")]
+mod cursor;
+#[cfg(feature = "sdl2")]
+mod debounce;
+#[cfg(feature = "sdl2")]
+mod dedup;
+#[cfg(feature = "std")]
+mod defaults;
+#[cfg(feature = "sdl2")]
+mod diagnostics;
+#[cfg(feature = "sensors")]
+mod divider;
+#[cfg(feature = "sdl2")]
+mod enrich;
 mod event;
+#[cfg(feature = "eventlog")]
+mod eventlog;
 mod gamepad;
+#[cfg(feature = "sdl2")]
 mod gamepadmanager;
-
+#[cfg(feature = "sdl2")]
+mod generation;
+#[cfg(feature = "sdl2")]
+mod handle;
+#[cfg(feature = "sdl2")]
+mod hints;
+#[cfg(feature = "sdl2")]
+mod history;
+#[cfg(feature = "std")]
+mod idle;
+#[cfg(feature = "sdl2")]
+mod inputconfig;
+#[cfg(feature = "sdl2")]
+mod instance;
+#[cfg(feature = "std")]
+mod ledbinding;
+#[cfg(feature = "std")]
+mod logging;
+#[cfg(feature = "sdl2")]
+mod merged;
+#[cfg(feature = "sdl2")]
+mod middleware;
+mod misc1;
+mod navigation;
+pub mod normalize;
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+mod openerror;
+#[cfg(feature = "std")]
+mod orientation;
+#[cfg(feature = "sdl2")]
+mod perf;
+#[cfg(feature = "sdl2")]
+mod polled;
+#[cfg(feature = "sdl2")]
+mod power;
+#[cfg(feature = "sdl2")]
+mod poweroff;
+pub mod prelude;
+mod queue;
+#[cfg(feature = "std")]
+mod ratelimit;
+#[cfg(feature = "std")]
+mod recovery;
+#[cfg(feature = "touchpad")]
+mod rejection;
+mod remap;
+#[cfg(feature = "sensors")]
+mod retry;
+pub mod ring;
+#[cfg(feature = "std")]
+mod setup;
+#[cfg(feature = "sdl2")]
+mod shared;
+#[cfg(feature = "sim")]
+mod sim;
+#[cfg(feature = "std")]
+mod smoothing;
+#[cfg(feature = "std")]
+mod snap;
+mod source;
+#[cfg(feature = "sdl2")]
+mod stages;
+#[cfg(feature = "sdl2")]
+mod steam;
+#[cfg(feature = "sdl2")]
+mod summary;
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "textinput")]
+mod textinput;
+#[cfg(feature = "std")]
+mod tiers;
 mod unused {
     //! Only used for documentation.
     #[cfg(feature = "document-features")]
     use document_features as _;
+    // Only used in doctests.
+    #[cfg(test)]
+    use serde_json as _;
     // Not actually used, dev-dependency for example/demo.
     #[cfg(test)]
     use tracing_subscriber as _;
 }
+#[cfg(feature = "sdl2")]
+mod view;
+#[cfg(feature = "sdl2")]
+mod watchdog;
+#[cfg(feature = "sdl2")]
+mod wear;
 
-// TODO: logging
-#[cfg(feature = "tracing")]
-use tracing as _;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
 
-#[cfg(feature = "sensors")]
-#[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
-pub use crate::gamepad::sensors::Sensor;
+// `tracing` takes precedence if both are enabled.
+#[cfg(all(feature = "log", feature = "tracing"))]
+use log as _;
+#[cfg(feature = "sdl2")]
+use sdl2::{IntegerOrSdlError, controller::AddMappingError};
+
+#[cfg(feature = "eventlog")]
+#[cfg_attr(docsrs, doc(cfg(feature = "eventlog")))]
+pub use crate::eventlog::{
+    EventLogReader, EventLogWriter, LogHeader, LogRecord, TeeEvents,
+};
+#[cfg(any(feature = "sensors", feature = "stubs"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+pub use crate::gamepad::sensors::{JoyConSide, Sensor};
+#[cfg(any(feature = "touchpad", feature = "stubs"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "touchpad", feature = "stubs"))))]
+pub use crate::gamepad::touchpad::{
+    FingerId, TouchpadAction, TouchpadState, TouchpadStateKey,
+};
 #[cfg(feature = "touchpad")]
 #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
 pub use crate::gamepad::touchpad::{
-    TouchpadAction, TouchpadEvent, TouchpadState,
+    FingerTracker, TouchpadEvent, TouchpadSizes, TouchpadSnapshot,
+    TouchpadTracker,
+};
+#[cfg(feature = "rumble")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+pub use crate::gamepad::{
+    pattern::{RumblePattern, RumbleStep},
+    rumble::{RumbleCaps, RumbleFold},
 };
+#[cfg(feature = "touchpad")]
+#[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+pub use crate::rejection::{TouchRect, TouchRejection, TouchRejector};
+#[cfg(feature = "sim")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sim")))]
+pub use crate::sim::{SimDevice, SimGamepad, SimGirl};
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use crate::testing::EventBuilder;
+#[cfg(feature = "textinput")]
+#[cfg_attr(docsrs, doc(cfg(feature = "textinput")))]
+pub use crate::textinput::{
+    EdgeWrap, KeyboardLayout, OnScreenKeyboardModel, TextEvent,
+};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub use crate::{
-    event::Event,
+    activity::ActivityTracker,
+    adaptive::{AdaptiveDeadzone, AdaptiveDeadzoneConfig},
+    aim::{
+        FlickStick, FlickStickConfig, GyroAcceleration, GyroAim, GyroAimConfig,
+    },
+    bus::{ConnectionHeuristics, ConnectionKind},
+    cursor::{CursorConfig, CursorEdges, CursorEmulator, CursorMode},
+    defaults::DefaultProfiles,
     gamepad::{
-        Gamepad, PowerLevel,
-        input::{Button, Stick, Trigger},
+        dump::GamepadDump,
+        effects::Identify,
+        journal::{CommandJournal, CommandRecord, OutputCommand},
+        profile::{
+            InputProfile, ResponseCurve, StickClamp, StickProfile,
+            TriggerProfile,
+        },
+    },
+    idle::{IdleConfig, IdleTracker},
+    ledbinding::{LedBinding, LedBindingId, LedBindings},
+    navigation::StickNavigator,
+    orientation::Orientation,
+    recovery::RecoveryReport,
+    remap::MappingSnapshot,
+    setup::{PadSetup, SetupOp, SetupReport},
+    smoothing::{SmoothingConfig, StickSmoother},
+    snap::StickSnapConfig,
+    tiers::StickTiers,
+};
+#[cfg(feature = "sdl2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+pub use crate::{
+    audio::{AudioIdentity, AudioMatcher},
+    background::BackgroundPolicy,
+    connection::{ConnectionQuality, ConnectionRating, QualityReport},
+    contexts::{AxisMode, ContextId, InputAxis, InputContexts},
+    debounce::ConnectionDebounce,
+    dedup::{EventLevel, Hotplug},
+    diagnostics::{
+        Anomaly, Control, ControlReport, DiagnosticPrompt, DiagnosticReport,
+        Diagnostics, DiagnosticsConfig, StepOutcome,
     },
+    enrich::{DeviceInfo, EnrichedEvent},
+    gamepad::Gamepad,
     gamepadmanager::{ConnectedGamepads, Girl},
+    handle::{Command, CommandReceipt, GirlHandle},
+    hints::Hints,
+    history::{DeviceHistory, DeviceRecord},
+    inputconfig::{InputConfig, InputOverrides},
+    instance::is_initialized,
+    merged::{AggregateGamepad, MergedGamepad},
+    middleware::{
+        AxisThreshold, ButtonRemap, EventMiddleware, MiddlewareChain,
+        MiddlewareCtx, MiddlewareId,
+    },
+    perf::PerfCounters,
+    polled::PolledGamepad,
+    power::PowerCache,
+    steam::{SteamInputHeuristics, SteamInputStatus},
+    summary::UpdateSummary,
+    view::{GamepadMut, GamepadRef},
+    wear::{DeviceWear, WearMetrics},
+};
+#[cfg(feature = "sensors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+pub use crate::{
+    divider::{SensorAccumulation, SensorDivider},
+    gamepad::dump::SensorDump,
+};
+pub use crate::{
+    event::{Event, EventKind, SequencedEvent},
+    gamepad::{
+        GamepadId, Guid, PowerLevel,
+        bind::InputBind,
+        capability::Capability,
+        color::Rgb,
+        effects::EffectDuration,
+        form::{ControllerType, FormFactor},
+        input::{
+            Axis, Button, ButtonAxis, ButtonChanges, ButtonTracker, Stick,
+            Trigger,
+        },
+        state::GamepadState,
+    },
+    misc1::{Misc1Role, Misc1Suppression},
+    navigation::{Direction, NavigationAction, NavigationConfig},
+    queue::EventQueue,
+    remap::RemapChanges,
+    source::{GamepadRead, GamepadSource},
 };
 
 /// Error types that can occur when working with gamepad input.
+///
+/// Without the `alloc` feature, the variants carrying messages don't exist
+/// and every [`Error`] is code-only: its variant and [`Error::code`] tell
+/// what went wrong.
 #[non_exhaustive]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Error {
     /// SDL2 failed to initialize.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     Sdl2Init(String),
 
     /// An error occurred in the SDL2 subsystem.
-    SdlError(String),
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    SdlError {
+        /// Operation that failed, e.g. `"set_led"`.
+        op: &'static str,
+        /// Category of the failure, see [`Error::code`].
+        code: ErrorCode,
+        /// Error message reported by SDL2, or a static one where the cause
+        /// is known, e.g. a disconnected [`Gamepad`].
+        message: Cow<'static, str>,
+    },
+
+    /// The [`Girl`] owning a [`GirlHandle`] was dropped before executing a
+    /// [`Command`].
+    Closed,
+
+    /// No connected [`Gamepad`] has the given [`GamepadId`].
+    GamepadNotFound(GamepadId),
+
+    /// An invalid argument was provided.
+    InvalidArgument {
+        /// What was invalid.
+        what: &'static str,
+    },
+
+    /// An [`InputProfile`] has invalid fields.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    InvalidProfile(Vec<&'static str>),
+
+    /// SDL2 reported a value outside of its documented range.
+    UnexpectedSdlValue {
+        /// Where the value was reported, e.g. `"touchpad state"`.
+        context: &'static str,
+        /// The unexpected value.
+        value: i64,
+    },
+
+    /// Another [`Girl`] exists in the process, see [`is_initialized`].
+    AlreadyInitialized,
+
+    /// [`Girl::new`] was called off the main thread on a platform where
+    /// SDL2 events only work on the main thread. Use
+    /// [`Girl::new_eventless`] to read input from other threads.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    NotMainThread,
+
+    /// A [`Hints`] field was set after a [`Gamepad`] was opened, so it
+    /// would not apply to it.
+    HintAfterOpen {
+        /// Name of the [`Hints`] field, e.g. `"ps5_enhanced_reports"`.
+        hint: &'static str,
+    },
+
+    /// The controller or platform doesn't support the operation, e.g.
+    /// [`Gamepad::request_power_off`] on a wired controller.
+    NotSupported {
+        /// Operation that isn't supported, e.g. `"request_power_off"`.
+        op: &'static str,
+    },
+
+    /// A textual form, e.g. of a rumble pattern, failed to parse.
+    InvalidSyntax {
+        /// What was parsed, e.g. `"rumble pattern"`.
+        what: &'static str,
+        /// Byte position of the offending token.
+        position: usize,
+        /// Why the token was rejected, e.g. `"invalid intensity"`.
+        reason: &'static str,
+    },
+
+    /// The operation needs a cargo feature `girl` was built without, e.g.
+    /// [`Gamepad::set_rumble`] without `rumble`, as compiled with the
+    /// `stubs` feature, see [`Gamepad::supports`].
+    ///
+    /// [`Gamepad::set_rumble`]: crate::Gamepad::set_rumble
+    FeatureDisabled {
+        /// The disabled feature, e.g. `"rumble"`.
+        feature: &'static str,
+    },
+}
+
+/// Cheap category of an [`Error`], see [`Error::code`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorCode {
+    /// The controller or platform doesn't support the operation, e.g. rumble
+    /// on a controller without motors.
+    NotSupported,
+    /// The [`Gamepad`] or the [`Girl`] is gone.
+    Disconnected,
+    /// An argument, textual form, [`InputProfile`] or [`Hints`] field was
+    /// rejected.
+    InvalidArgument,
+    /// SDL2 failed for another reason.
+    Sdl,
+    /// SDL2 or its controller subsystems failed to initialize, or a
+    /// [`Girl`] already exists.
+    Init,
+}
+
+/// Renders a human-readable description of the [`Error`].
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// let err = girl::Error::SdlError {
+///     op: "set_led",
+///     code: girl::ErrorCode::Sdl,
+///     message: "Invalid device".into(),
+/// };
+/// assert_eq!(err.to_string(), "SDL error in set_led: Invalid device");
+/// # }
+/// ```
+impl fmt::Display for Error {
+    #[inline]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "binds the messages by reference"
+    )]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::Sdl2Init(message) => {
+                write!(f, "SDL initialization failed: {message}")
+            }
+            #[cfg(feature = "alloc")]
+            Self::SdlError { op, message, .. } => {
+                write!(f, "SDL error in {op}: {message}")
+            }
+            Self::Closed => f.write_str("girl was dropped"),
+            Self::GamepadNotFound(id) => write!(f, "gamepad {id} not found"),
+            Self::InvalidArgument { what } => {
+                write!(f, "invalid argument: {what}")
+            }
+            #[cfg(feature = "alloc")]
+            Self::InvalidProfile(fields) => {
+                write!(f, "invalid profile fields: {}", fields.join(", "))
+            }
+            Self::UnexpectedSdlValue { context, value } => {
+                write!(f, "unexpected SDL value in {context}: {value}")
+            }
+            Self::AlreadyInitialized => f.write_str(
+                "girl is already initialized, drop the existing `Girl` first",
+            ),
+            #[cfg(feature = "alloc")]
+            Self::NotMainThread => f.write_str(
+                "SDL events require the main thread on this platform, use \
+                 `Girl::new_eventless` on other threads",
+            ),
+            Self::HintAfterOpen { hint } => {
+                write!(f, "{hint} must be set before opening gamepads")
+            }
+            Self::NotSupported { op } => {
+                write!(f, "{op} is not supported by this controller")
+            }
+            Self::InvalidSyntax { what, position, reason } => {
+                write!(f, "invalid {what} at {position}: {reason}")
+            }
+            Self::FeatureDisabled { feature } => {
+                write!(f, "girl was built without the `{feature}` feature")
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Message of the [`Error::SdlError`]s of operations on a disconnected
+    /// [`Gamepad`].
+    #[cfg(any(feature = "sdl2", feature = "sim"))]
+    pub(crate) const DISCONNECTED: &'static str = "gamepad disconnected";
+    /// Message SDL2 reports for operations a controller doesn't support,
+    /// reused where [`Gamepad`] knows it upfront.
+    #[cfg(any(feature = "sdl2", feature = "sim"))]
+    pub(crate) const UNSUPPORTED: &'static str =
+        "That operation is not supported";
+
+    /// Creates a closure mapping an SDL2 error to [`Error::SdlError`] tagged
+    /// with the failing operation, for use with [`Result::map_err`].
+    ///
+    /// The message SDL2 already allocated is moved in, not formatted again.
+    /// Failures are not logged, callers log them once per streak with
+    /// [`FailureLog`].
+    ///
+    /// [`FailureLog`]: crate::logging::FailureLog
+    #[cfg(feature = "sdl2")]
+    #[inline]
+    pub(crate) fn sdl<E: SdlFailure>(
+        op: &'static str,
+    ) -> impl FnOnce(E) -> Self {
+        move |err| {
+            let message = err.into_message();
+            let code = if message == Self::UNSUPPORTED {
+                ErrorCode::NotSupported
+            } else {
+                ErrorCode::Sdl
+            };
+            Self::SdlError { op, code, message: Cow::Owned(message) }
+        }
+    }
+
+    /// Creates an [`Error::SdlError`] with a static `message`, without
+    /// allocating.
+    #[cfg(any(feature = "sdl2", feature = "sim"))]
+    #[inline]
+    pub(crate) const fn sdl_static(
+        op: &'static str,
+        code: ErrorCode,
+        message: &'static str,
+    ) -> Self {
+        Self::SdlError { op, code, message: Cow::Borrowed(message) }
+    }
+
+    /// Gets the [`ErrorCode`] of the [`Error`], for matching on its category
+    /// without inspecting messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ErrorCode, prelude::*};
+    /// let err = Error::GamepadNotFound(GamepadId::from_raw(3));
+    /// assert_eq!(err.code(), ErrorCode::Disconnected);
+    ///
+    /// let err = Error::FeatureDisabled { feature: "rumble" };
+    /// assert_eq!(err.code(), ErrorCode::NotSupported);
+    /// let message = "girl was built without the `rumble` feature";
+    /// assert_eq!(err.to_string(), message);
+    /// # #[cfg(feature = "alloc")] {
+    ///
+    /// let err = Error::SdlError {
+    ///     op: "set_rumble",
+    ///     code: ErrorCode::NotSupported,
+    ///     message: "That operation is not supported".into(),
+    /// };
+    /// assert_eq!(err.code(), ErrorCode::NotSupported);
+    /// # }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn code(&self) -> ErrorCode {
+        match *self {
+            #[cfg(feature = "alloc")]
+            Self::Sdl2Init(_) | Self::NotMainThread => ErrorCode::Init,
+            #[cfg(feature = "alloc")]
+            Self::SdlError { code, .. } => code,
+            #[cfg(feature = "alloc")]
+            Self::InvalidProfile(_) => ErrorCode::InvalidArgument,
+            Self::AlreadyInitialized => ErrorCode::Init,
+            Self::Closed | Self::GamepadNotFound(_) => ErrorCode::Disconnected,
+            Self::InvalidArgument { .. }
+            | Self::HintAfterOpen { .. }
+            | Self::InvalidSyntax { .. } => ErrorCode::InvalidArgument,
+            Self::UnexpectedSdlValue { .. } => ErrorCode::Sdl,
+            Self::NotSupported { .. } | Self::FeatureDisabled { .. } => {
+                ErrorCode::NotSupported
+            }
+        }
+    }
+
+    /// Gets the failing operation of an [`Error::SdlError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "sdl2")] {
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if let Err(err) = gamepad.set_led(255, 255, 255) {
+    ///     println!("{} failed: {err}", err.sdl_op().unwrap_or("girl"));
+    /// }
+    /// # }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn sdl_op(&self) -> Option<&'static str> {
+        match *self {
+            #[cfg(feature = "alloc")]
+            Self::SdlError { op, .. } => Some(op),
+            #[cfg(feature = "alloc")]
+            Self::Sdl2Init(_)
+            | Self::InvalidProfile(_)
+            | Self::NotMainThread => None,
+            Self::AlreadyInitialized
+            | Self::Closed
+            | Self::GamepadNotFound(_)
+            | Self::InvalidArgument { .. }
+            | Self::UnexpectedSdlValue { .. }
+            | Self::HintAfterOpen { .. }
+            | Self::NotSupported { .. }
+            | Self::InvalidSyntax { .. }
+            | Self::FeatureDisabled { .. } => None,
+        }
+    }
+}
+
+/// Error of an SDL2 call, turned into the message of an
+/// [`Error::SdlError`] without copying the [`String`] SDL2 reported.
+#[cfg(feature = "sdl2")]
+pub(crate) trait SdlFailure {
+    /// Takes the message out of the error.
+    fn into_message(self) -> String;
+}
+
+#[cfg(feature = "sdl2")]
+impl SdlFailure for String {
+    #[inline]
+    fn into_message(self) -> String {
+        self
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl SdlFailure for IntegerOrSdlError {
+    #[inline]
+    fn into_message(self) -> String {
+        if let Self::SdlError(message) = self {
+            message
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl SdlFailure for AddMappingError {
+    #[inline]
+    fn into_message(self) -> String {
+        if let Self::SdlError(message) = self {
+            message
+        } else {
+            self.to_string()
+        }
+    }
 }
@@ -0,0 +1,67 @@
+//! Internal diagnostics through either `tracing` or `log`.
+//!
+//! Call sites go through [`diag!`] so that messages are formatted the same
+//! way regardless of the backend. If both the `tracing` and `log` features
+//! are enabled, `tracing` takes precedence.
+#![expect(
+    clippy::arbitrary_source_item_ordering,
+    reason = "`diag!` can only be re-exported after its definition"
+)]
+
+#[cfg(feature = "sdl2")]
+use alloc::collections::BTreeSet;
+#[cfg(feature = "sdl2")]
+use core::cell::RefCell;
+
+#[cfg(feature = "sdl2")]
+use crate::Error;
+
+/// Emits a diagnostic at the given level (`trace`, `debug`, `info`, `warn` or
+/// `error`) through the enabled logging backend, if any.
+macro_rules! diag {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($arg)+);
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        ::log::$level!($($arg)+);
+        #[cfg(not(any(feature = "tracing", feature = "log")))]
+        {
+            // keep the arguments "used" without a backend
+            _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+pub(crate) use diag;
+
+/// Operations whose latest attempt failed, so that a streak of failures of
+/// an operation is logged once rather than every frame, e.g. rumble sent to
+/// a controller that was just unplugged.
+#[cfg(feature = "sdl2")]
+#[derive(Debug, Default)]
+pub(crate) struct FailureLog {
+    /// Failing operations, see [`Error::sdl_op`].
+    failing: RefCell<BTreeSet<&'static str>>,
+}
+
+#[cfg(feature = "sdl2")]
+impl FailureLog {
+    /// Observes the `result` of `op`, logging its error if it starts a
+    /// streak of failures. Only a success of `op` re-arms it.
+    pub(crate) fn observe<T>(
+        &self,
+        op: &'static str,
+        result: &Result<T, Error>,
+    ) {
+        let Ok(mut failing) = self.failing.try_borrow_mut() else {
+            return;
+        };
+        if let Err(err) = result.as_ref() {
+            if failing.insert(op) {
+                diag!(warn, "{err}");
+            }
+        } else {
+            _ = failing.remove(op);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! Several physical [`Gamepad`]s acting as one logical player.
+
+use alloc::collections::BTreeMap;
+use core::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "rumble")]
+use core::time::Duration;
+
+use crate::{
+    Button, Error, Event, Gamepad, GamepadId, GamepadState, Stick, Trigger,
+    gamepad::state::merge_sticks,
+};
+
+/// Next synthetic [`GamepadId`] handed out to a [`MergedGamepad`].
+///
+/// SDL2 instance IDs count up from zero, so synthetic ones count down from
+/// the top of the range to stay clear of them.
+static NEXT_MERGED_ID: AtomicU32 = AtomicU32::new(u32::MAX);
+
+/// Several [`Gamepad`]s acting as one, e.g. for a "copilot" accessibility
+/// mode where two people control the same player.
+///
+/// Reading input merges all the members:
+/// - [`buttons`] are pressed if pressed on any member;
+/// - [`stick`] takes the largest deflection of every axis separately (see
+///   [`merge_sticks`]);
+/// - [`trigger`] takes the largest value.
+///
+/// Output (LED and rumble) is fanned out to every connected member.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{MergedGamepad, prelude::*};
+/// let mut girl = girl::Girl::new()?;
+/// # if let (Some(first), Some(second)) = (girl.gamepad(0), girl.gamepad(1)) {
+/// let mut player = MergedGamepad::new(vec![first, second]);
+///
+/// if player.buttons_pressed(Button::A) {
+///     let [x, y] = player.stick(Stick::Left);
+///     // move the player
+/// }
+///
+/// girl.update();
+/// while let Some(event) = girl.event() {
+///     // events of both members appear to come from `player.id()`
+///     let event = player.route(event);
+/// }
+/// # }
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`buttons`]: Self::buttons
+/// [`stick`]: Self::stick
+/// [`trigger`]: Self::trigger
+/// [`merge_sticks`]: Self::merge_sticks
+#[derive(Debug)]
+pub struct MergedGamepad {
+    /// Synthetic [`GamepadId`] of the merged [`Gamepad`].
+    id: GamepadId,
+    /// Members of the merged [`Gamepad`].
+    members: Vec<Gamepad>,
+}
+
+impl MergedGamepad {
+    /// Merges the given [`Gamepad`]s into one, with a fresh synthetic
+    /// [`GamepadId`].
+    #[must_use]
+    #[inline]
+    pub fn new(members: Vec<Gamepad>) -> Self {
+        let id = NEXT_MERGED_ID.fetch_sub(1, Ordering::Relaxed);
+        Self { id: GamepadId::from_raw(id), members }
+    }
+
+    /// Gets the synthetic [`GamepadId`] of the merged [`Gamepad`], used as
+    /// `which` in [`Event`]s passed through [`route`].
+    ///
+    /// [`route`]: Self::route
+    #[must_use]
+    #[inline]
+    pub const fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    /// Gets the members of the merged [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub const fn members(&self) -> &[Gamepad] {
+        self.members.as_slice()
+    }
+
+    /// Adds a [`Gamepad`] to the members, e.g. to replace a reconnected one.
+    #[inline]
+    pub fn push(&mut self, gamepad: Gamepad) {
+        self.members.push(gamepad);
+    }
+
+    /// Removes disconnected members, returning how many were removed.
+    #[inline]
+    pub fn remove_disconnected(&mut self) -> usize {
+        let before = self.members.len();
+        self.members.retain(Gamepad::connected);
+        before.saturating_sub(self.members.len())
+    }
+
+    /// Checks if any of the members is connected.
+    #[must_use]
+    #[inline]
+    pub fn connected(&self) -> bool {
+        self.members.iter().any(Gamepad::connected)
+    }
+
+    /// Checks if the [`Event`] comes from one of the members.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, which: u32) -> bool {
+        self.members.iter().any(|pad| pad.id().raw() == which)
+    }
+
+    /// Rewrites `which` of [`Event`]s coming from the members to [`id`],
+    /// leaving other [`Event`]s unchanged.
+    ///
+    /// [`id`]: Self::id
+    #[must_use]
+    #[inline]
+    pub fn route(&self, mut event: Event) -> Event {
+        if let Some(which) = event.which_mut()
+            && self.contains(*which)
+        {
+            *which = self.id.raw();
+        }
+        event
+    }
+
+    /// Gets the specified [`Button`]\(s) pressed on any of the members.
+    ///
+    /// See [`Gamepad::buttons`].
+    #[must_use]
+    #[inline]
+    pub fn buttons(&self, buttons: Button) -> Button {
+        merge_buttons(&self.members, buttons)
+    }
+
+    /// Checks if all specified [`Button`]\(s) are pressed, possibly on
+    /// different members.
+    ///
+    /// See [`Gamepad::buttons_pressed`].
+    #[must_use]
+    #[inline]
+    pub fn buttons_pressed(&self, buttons: Button) -> bool {
+        self.buttons(buttons) == buttons
+    }
+
+    /// Gets the position of the [`Stick`] with the largest deflection of
+    /// every axis among the members.
+    ///
+    /// See [`Gamepad::stick`] and [`merge_sticks`].
+    ///
+    /// [`merge_sticks`]: Self::merge_sticks
+    #[must_use]
+    #[inline]
+    pub fn stick(&self, stick: Stick) -> [f64; 2] {
+        Self::merge_sticks(self.members.iter().map(|pad| pad.stick(stick)))
+    }
+
+    /// Gets the largest value of the [`Trigger`] among the members.
+    ///
+    /// See [`Gamepad::trigger`].
+    #[must_use]
+    #[inline]
+    pub fn trigger(&self, trigger: Trigger) -> f64 {
+        merge_triggers(&self.members, trigger)
+    }
+
+    /// Merges [`Stick`] positions by taking the value with the largest
+    /// magnitude of every axis separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::MergedGamepad;
+    /// let merged = MergedGamepad::merge_sticks([[0.5, -0.2], [-0.8, 0.1]]);
+    /// assert_eq!(merged, [-0.8, -0.2]);
+    ///
+    /// // a disconnected member reports a centered stick and doesn't matter
+    /// let merged = MergedGamepad::merge_sticks([[0.0, 0.0], [0.3, 0.4]]);
+    /// assert_eq!(merged, [0.3, 0.4]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn merge_sticks<I>(positions: I) -> [f64; 2]
+    where
+        I: IntoIterator<Item = [f64; 2]>,
+    {
+        merge_sticks(positions)
+    }
+
+    /// Sets the LED color on every connected member that has one.
+    ///
+    /// See [`Gamepad::set_led`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, after attempting every member.
+    #[inline]
+    pub fn set_led(
+        &mut self,
+        red: u8,
+        green: u8,
+        blue: u8,
+    ) -> Result<(), Error> {
+        self.fan_out(Gamepad::has_led, |pad| pad.set_led(red, green, blue))
+    }
+
+    /// Sets the rumble on every connected member that supports it.
+    ///
+    /// See [`Gamepad::set_rumble`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, after attempting every member.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    #[inline]
+    pub fn set_rumble(
+        &mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        let (low, high) = (low_frequency_rumble, high_frequency_rumble);
+        self.fan_out(Gamepad::has_rumble, |pad| {
+            pad.set_rumble(low, high, duration)
+        })
+    }
+
+    /// Stops rumble on every connected member that supports it.
+    ///
+    /// See [`Gamepad::end_rumble`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, after attempting every member.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    #[inline]
+    pub fn end_rumble(&mut self) -> Result<(), Error> {
+        self.fan_out(Gamepad::has_rumble, Gamepad::end_rumble)
+    }
+
+    /// Runs `f` on every connected member `supports` returns `true` for,
+    /// returning the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `f` returned, after running it on every
+    /// member.
+    fn fan_out<S, F>(&mut self, supports: S, mut f: F) -> Result<(), Error>
+    where
+        S: Fn(&Gamepad) -> bool,
+        F: FnMut(&mut Gamepad) -> Result<(), Error>,
+    {
+        let mut result = Ok(());
+        for pad in &mut self.members {
+            if !pad.connected() || !supports(pad) {
+                continue;
+            }
+            let outcome = f(pad);
+            if result.is_ok() {
+                result = outcome;
+            }
+        }
+        result
+    }
+}
+
+/// Input of every connected [`Gamepad`] read as one, e.g. so any controller
+/// can drive the title screen menu.
+///
+/// Returned by [`Girl::any_gamepad`]. Input is merged the same way as for a
+/// [`MergedGamepad`], reading the [`Gamepad`]s tracked by [`Girl`] on every
+/// call, so it reflects the latest [`Girl::update`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::prelude::*;
+/// let mut girl = girl::Girl::new()?;
+///
+/// girl.update();
+/// let menu = girl.any_gamepad();
+/// if menu.buttons_pressed(Button::A) {
+///     // confirm
+/// }
+/// let [x, y] = menu.stick(Stick::Left);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::any_gamepad`]: crate::Girl::any_gamepad
+/// [`Girl::update`]: crate::Girl::update
+#[derive(Debug, Clone, Copy)]
+pub struct AggregateGamepad<'girl> {
+    /// [`Gamepad`]s tracked by [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    pads: &'girl BTreeMap<GamepadId, Gamepad>,
+}
+
+impl<'girl> AggregateGamepad<'girl> {
+    /// Creates a new [`AggregateGamepad`] reading the given [`Gamepad`]s.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "keeps the fields private")]
+    pub(crate) const fn new(pads: &'girl BTreeMap<GamepadId, Gamepad>) -> Self {
+        Self { pads }
+    }
+
+    /// Checks if any [`Gamepad`] is connected.
+    #[must_use]
+    #[inline]
+    pub fn connected(&self) -> bool {
+        self.pads.values().any(Gamepad::connected)
+    }
+
+    /// Gets the [`GamepadId`]s of the aggregated [`Gamepad`]s.
+    #[inline]
+    pub fn ids(&self) -> impl Iterator<Item = GamepadId> + use<'girl> {
+        self.pads.keys().copied()
+    }
+
+    /// Gets the specified [`Button`]\(s) pressed on any [`Gamepad`].
+    ///
+    /// See [`Gamepad::buttons`].
+    #[must_use]
+    #[inline]
+    pub fn buttons(&self, buttons: Button) -> Button {
+        merge_buttons(self.pads.values(), buttons)
+    }
+
+    /// Checks if all specified [`Button`]\(s) are pressed, possibly on
+    /// different [`Gamepad`]s.
+    ///
+    /// See [`Gamepad::buttons_pressed`].
+    #[must_use]
+    #[inline]
+    pub fn buttons_pressed(&self, buttons: Button) -> bool {
+        self.buttons(buttons) == buttons
+    }
+
+    /// Gets the position of the [`Stick`] with the largest deflection of
+    /// every axis among the [`Gamepad`]s.
+    ///
+    /// See [`Gamepad::stick`] and [`MergedGamepad::merge_sticks`].
+    #[must_use]
+    #[inline]
+    pub fn stick(&self, stick: Stick) -> [f64; 2] {
+        let positions = self.pads.values().map(|pad| pad.stick(stick));
+        MergedGamepad::merge_sticks(positions)
+    }
+
+    /// Gets the largest value of the [`Trigger`] among the [`Gamepad`]s.
+    ///
+    /// See [`Gamepad::trigger`].
+    #[must_use]
+    #[inline]
+    pub fn trigger(&self, trigger: Trigger) -> f64 {
+        merge_triggers(self.pads.values(), trigger)
+    }
+
+    /// Takes a merged [`GamepadState`] snapshot, see [`GamepadState::merge`].
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> GamepadState {
+        GamepadState::merge(self.pads.values().map(Gamepad::state))
+    }
+}
+
+/// Gets the specified [`Button`]\(s) pressed on any of the `pads`.
+fn merge_buttons<'pad, I>(pads: I, buttons: Button) -> Button
+where
+    I: IntoIterator<Item = &'pad Gamepad>,
+{
+    pads.into_iter()
+        .map(|pad| pad.buttons(buttons))
+        .fold(Button::empty(), |merged, pressed| merged | pressed)
+}
+
+/// Gets the largest value of the [`Trigger`] among the `pads`.
+fn merge_triggers<'pad, I>(pads: I, trigger: Trigger) -> f64
+where
+    I: IntoIterator<Item = &'pad Gamepad>,
+{
+    pads.into_iter().map(|pad| pad.trigger(trigger)).fold(0.0, f64::max)
+}
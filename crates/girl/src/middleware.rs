@@ -0,0 +1,547 @@
+//! Pluggable processing of [`Event`]s between translation and delivery.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::fmt;
+use std::time::Instant;
+
+use crate::{
+    Button, DeviceInfo, Error, Event, GamepadId, PerfCounters, Stick, Trigger,
+    gamepad::profile::{check_single_button, check_threshold},
+    shared::Shared,
+};
+
+/// Step of processing every [`Event`], see [`Girl::add_middleware`].
+///
+/// Closures taking an [`Event`] and a [`MiddlewareCtx`] are middleware too.
+///
+/// [`Girl::add_middleware`]: crate::Girl::add_middleware
+pub trait EventMiddleware {
+    /// Processes an [`Event`], returning it unchanged, a rewritten one, or
+    /// [`None`] to drop it.
+    ///
+    /// Extra [`Event`]s can be emitted through the [`MiddlewareCtx`], they
+    /// follow the returned one.
+    fn process(
+        &mut self,
+        event: Event,
+        ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event>;
+}
+
+impl<F> EventMiddleware for F
+where
+    F: FnMut(Event, &mut MiddlewareCtx<'_>) -> Option<Event>,
+{
+    #[inline]
+    fn process(
+        &mut self,
+        event: Event,
+        ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        self(event, ctx)
+    }
+}
+
+/// What an [`EventMiddleware`] can see and do besides rewriting its
+/// [`Event`].
+///
+/// Only exposes read-only metadata, so the caches [`Girl`] keeps per
+/// [`Gamepad`] always reflect what the controllers reported.
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Girl`]: crate::Girl
+#[derive(Debug)]
+pub struct MiddlewareCtx<'ctx> {
+    /// When the [`Event`] is processed.
+    now: Instant,
+    /// Metadata of the [`Gamepad`]s, see [`Girl::enrich`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl::enrich`]: crate::Girl::enrich
+    devices: &'ctx BTreeMap<GamepadId, DeviceInfo>,
+    /// State shared with the [`Gamepad`]s, resolving the [`GamepadId`]s of
+    /// the `which` of [`Event`]s for the built-in stages of [`Girl`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl`]: crate::Girl
+    shared: Option<&'ctx Shared>,
+    /// [`Event`]s emitted while processing.
+    emitted: Vec<Event>,
+}
+
+impl MiddlewareCtx<'_> {
+    /// Emits an extra [`Event`] after the processed one.
+    ///
+    /// It is passed to the [`EventMiddleware`]s added after the emitting one
+    /// only.
+    #[inline]
+    pub fn emit(&mut self, event: Event) {
+        self.emitted.push(event);
+    }
+
+    /// Gets when the [`Event`] is processed.
+    #[must_use]
+    #[inline]
+    pub const fn now(&self) -> Instant {
+        self.now
+    }
+
+    /// Gets the metadata of the [`Gamepad`] with the given [`GamepadId`],
+    /// kept after it disconnects.
+    ///
+    /// Always [`None`] for a [`MiddlewareChain`] driven directly.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn device(&self, id: GamepadId) -> Option<&DeviceInfo> {
+        self.devices.get(&id)
+    }
+
+    /// Gets the [`GamepadId`] of the device with the raw instance ID, e.g.
+    /// the `which` of an [`Event`].
+    pub(crate) fn id(&self, which: u32) -> GamepadId {
+        self.shared.map_or_else(
+            || GamepadId::from_raw(which),
+            |shared| shared.id(which),
+        )
+    }
+}
+
+/// Handle of an [`EventMiddleware`] added to a [`MiddlewareChain`], see
+/// [`Girl::add_middleware`].
+///
+/// [`Girl::add_middleware`]: crate::Girl::add_middleware
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiddlewareId(u64);
+
+/// [`EventMiddleware`]s run in the order they were added.
+///
+/// Used by [`Girl::add_middleware`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{cell::RefCell, rc::Rc, time::Instant};
+/// # use girl::{ButtonRemap, MiddlewareChain, prelude::*};
+/// # use girl::MiddlewareCtx;
+/// let recorded = Rc::new(RefCell::new(Vec::new()));
+/// let log = Rc::clone(&recorded);
+/// let record = move |event: Event, _: &mut MiddlewareCtx<'_>| {
+///     if let Event::ControllerButtonDown { button, .. } = event {
+///         log.borrow_mut().push(button);
+///     }
+///     Some(event)
+/// };
+/// let mut remap = ButtonRemap::new();
+/// remap.set(Button::A, Button::B)?;
+///
+/// // remapping first records the logical button
+/// let mut chain = MiddlewareChain::new();
+/// let first = chain.add(Box::new(remap.clone()));
+/// let second = chain.add(Box::new(record.clone()));
+/// let press = Event::ControllerButtonDown { which: 1, button: Button::A };
+/// _ = chain.process(press, Instant::now());
+/// assert_eq!(*recorded.borrow(), [Button::B]);
+///
+/// // recording first records the physical one
+/// _ = chain.remove(first);
+/// let _third = chain.add(Box::new(remap));
+/// let out = chain.process(press, Instant::now());
+/// assert_eq!(*recorded.borrow(), [Button::B, Button::A]);
+/// let [Event::ControllerButtonDown { button, .. }] = out[..] else {
+///     panic!("not remapped");
+/// };
+/// assert_eq!(button, Button::B);
+///
+/// // dropped events don't reach later middleware
+/// assert!(chain.remove(second).is_some());
+/// let mut chain = MiddlewareChain::new();
+/// let drop = |_: Event, _: &mut MiddlewareCtx<'_>| None::<Event>;
+/// let _drop = chain.add(Box::new(drop));
+/// let _record = chain.add(Box::new(record));
+/// assert!(chain.process(press, Instant::now()).is_empty());
+/// assert_eq!(recorded.borrow().len(), 2);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::add_middleware`]: crate::Girl::add_middleware
+#[derive(Default)]
+pub struct MiddlewareChain {
+    /// Added middleware, in order.
+    entries: Vec<Entry>,
+    /// [`MiddlewareId`] of the next added middleware.
+    next_id: u64,
+}
+
+impl fmt::Debug for MiddlewareChain {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("ids", &self.ids().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MiddlewareChain {
+    /// Creates an empty [`MiddlewareChain`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new(), next_id: 0 }
+    }
+
+    /// Adds an [`EventMiddleware`] after the ones already added, returning
+    /// its handle.
+    #[inline]
+    pub fn add(
+        &mut self,
+        middleware: Box<dyn EventMiddleware>,
+    ) -> MiddlewareId {
+        let id = MiddlewareId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.entries.push(Entry { id, middleware });
+        id
+    }
+
+    /// Removes an [`EventMiddleware`], returning it.
+    ///
+    /// Returns [`None`] if it was already removed.
+    #[inline]
+    pub fn remove(
+        &mut self,
+        id: MiddlewareId,
+    ) -> Option<Box<dyn EventMiddleware>> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index).middleware)
+    }
+
+    /// Iterates over the handles of the added [`EventMiddleware`]s, in
+    /// order.
+    #[inline]
+    pub fn ids(&self) -> impl Iterator<Item = MiddlewareId> {
+        self.entries.iter().map(|entry| entry.id)
+    }
+
+    /// Checks whether no [`EventMiddleware`] was added.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Runs an [`Event`] through the chain at `now`, returning what comes
+    /// out of it, in order.
+    #[must_use]
+    #[inline]
+    pub fn process(&mut self, event: Event, now: Instant) -> Vec<Event> {
+        let mut out = VecDeque::new();
+        let devices = BTreeMap::new();
+        self.run(Scope::new(now, &devices, None), event, &mut out, None);
+        out.into()
+    }
+
+    /// Runs an [`Event`] through the chain at `now`, appending what comes
+    /// out of it to `out`, and timing every middleware into `perf` if
+    /// given.
+    pub(crate) fn run(
+        &mut self,
+        scope: Scope<'_>,
+        event: Event,
+        out: &mut VecDeque<Event>,
+        mut perf: Option<&mut PerfCounters>,
+    ) {
+        if let Some(counters) = perf.as_deref_mut() {
+            counters.count_allocation();
+        }
+        let entries = &mut self.entries;
+        scope.drive(event, entries.len(), out, |index, input, ctx| {
+            let entry = entries.get_mut(index)?;
+            let started = perf.is_some().then(Instant::now);
+            let output = entry.middleware.process(input, ctx);
+            if let (Some(counters), Some(start_time)) =
+                (perf.as_deref_mut(), started)
+            {
+                counters.add_middleware_time(entry.id, start_time.elapsed());
+                if !ctx.emitted.is_empty() {
+                    counters.count_allocation();
+                }
+            }
+            output
+        });
+    }
+}
+
+/// What the [`MiddlewareCtx`]s of a run of [`EventMiddleware`]s see.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Scope<'ctx> {
+    /// When the [`Event`] is processed.
+    now: Instant,
+    /// Metadata of the [`Gamepad`]s, see [`MiddlewareCtx::device`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    devices: &'ctx BTreeMap<GamepadId, DeviceInfo>,
+    /// State resolving [`GamepadId`]s, see [`MiddlewareCtx::id`].
+    shared: Option<&'ctx Shared>,
+}
+
+impl<'ctx> Scope<'ctx> {
+    /// Creates a [`Scope`] of processing at `now`.
+    pub(crate) const fn new(
+        now: Instant,
+        devices: &'ctx BTreeMap<GamepadId, DeviceInfo>,
+        shared: Option<&'ctx Shared>,
+    ) -> Self {
+        Self { now, devices, shared }
+    }
+
+    /// Runs an [`Event`] through `len` [`EventMiddleware`]s, `step` running
+    /// the one with the given index, appending what comes out to `out`, in
+    /// order.
+    ///
+    /// Emitted [`Event`]s only go through the [`EventMiddleware`]s after the
+    /// emitting one.
+    pub(crate) fn drive<F>(
+        self,
+        event: Event,
+        len: usize,
+        out: &mut VecDeque<Event>,
+        mut step: F,
+    ) where
+        F: FnMut(usize, Event, &mut MiddlewareCtx<'_>) -> Option<Event>,
+    {
+        let Self { now, devices, shared } = self;
+        let mut queue = VecDeque::from([(event, 0)]);
+        while let Some((first, start)) = queue.pop_front() {
+            let mut current = Some(first);
+            for index in start..len {
+                let Some(input) = current else { break };
+                let mut ctx =
+                    MiddlewareCtx { now, devices, shared, emitted: Vec::new() };
+                current = step(index, input, &mut ctx);
+                let next = index.saturating_add(1);
+                queue
+                    .extend(ctx.emitted.into_iter().map(|extra| (extra, next)));
+            }
+            out.extend(current);
+        }
+    }
+}
+
+/// [`EventMiddleware`] added to a [`MiddlewareChain`].
+struct Entry {
+    /// Handle returned when adding it.
+    id: MiddlewareId,
+    /// The middleware itself.
+    middleware: Box<dyn EventMiddleware>,
+}
+
+/// [`EventMiddleware`] making physical [`Button`]s report as other ones in
+/// button [`Event`]s, including the held [`Button`]s of
+/// [`Event::ControllerButtonDownWithState`].
+///
+/// Unlike [`Gamepad::remap_button`], which remaps polled [`Button`]s of a
+/// single [`Gamepad`], applies to the [`Event`]s of every [`Gamepad`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Instant;
+/// # use girl::{ButtonRemap, MiddlewareChain, prelude::*};
+/// let mut remap = ButtonRemap::new();
+/// remap.set(Button::A, Button::B)?;
+/// remap.set(Button::B, Button::A)?;
+/// assert!(remap.set(Button::A | Button::B, Button::X).is_err());
+/// assert_eq!(remap.remapped(Button::X), Button::X);
+///
+/// let mut chain = MiddlewareChain::new();
+/// let _remap = chain.add(Box::new(remap));
+/// let press = Event::ControllerButtonDownWithState {
+///     which: 1,
+///     button: Button::A,
+///     held: Button::B | Button::X,
+/// };
+/// let out = chain.process(press, Instant::now());
+/// let [Event::ControllerButtonDownWithState { button, held, .. }] = out[..]
+/// else {
+///     panic!("not remapped");
+/// };
+/// assert_eq!(button, Button::B);
+/// assert_eq!(held, Button::A | Button::X);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::remap_button`]: crate::Gamepad::remap_button
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ButtonRemap {
+    /// Physical to logical [`Button`] remaps.
+    remap: Vec<(Button, Button)>,
+}
+
+impl EventMiddleware for ButtonRemap {
+    #[inline]
+    #[expect(
+        clippy::pattern_type_mismatch,
+        reason = "remaps the buttons in place"
+    )]
+    fn process(
+        &mut self,
+        mut event: Event,
+        _ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        if let Event::ControllerButtonDown { button, .. }
+        | Event::ControllerButtonUp { button, .. } = &mut event
+        {
+            *button = self.remapped(*button);
+        }
+        if let Event::ControllerButtonDownWithState { button, held, .. } =
+            &mut event
+        {
+            *button = self.remapped(*button);
+            *held = held.iter().map(|pressed| self.remapped(pressed)).collect();
+        }
+        Some(event)
+    }
+}
+
+impl ButtonRemap {
+    /// Creates a [`ButtonRemap`] without any remap.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { remap: Vec::new() }
+    }
+
+    /// Makes the physical `from` [`Button`] report as `to`.
+    ///
+    /// Remapping `from` again replaces the previous remap, remapping it onto
+    /// itself removes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if either [`Button`] has more or
+    /// less than one bit set.
+    #[inline]
+    pub fn set(&mut self, from: Button, to: Button) -> Result<(), Error> {
+        check_single_button(from)?;
+        check_single_button(to)?;
+        self.remap.retain(|&(physical, _)| physical != from);
+        if from != to {
+            self.remap.push((from, to));
+        }
+        Ok(())
+    }
+
+    /// Gets the logical [`Button`] the physical `button` reports as.
+    #[must_use]
+    #[inline]
+    pub fn remapped(&self, button: Button) -> Button {
+        self.remap
+            .iter()
+            .find(|&&(physical, _)| physical == button)
+            .map_or(button, |&(_, logical)| logical)
+    }
+}
+
+/// [`EventMiddleware`] dropping stick and trigger motion [`Event`]s that
+/// moved less than a threshold since the last reported one, e.g. to not
+/// flood a network connection with jitter.
+///
+/// Returning to rest is always reported, so the last reported value of a
+/// released stick or trigger is never stuck off-center.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Instant;
+/// # use girl::{AxisThreshold, MiddlewareChain, prelude::*};
+/// let mut chain = MiddlewareChain::new();
+/// let _threshold = chain.add(Box::new(AxisThreshold::new(0.1)?));
+/// let mut moved = |offset| {
+///     let event = Event::ControllerStickMotion {
+///         which: 1,
+///         stick: Stick::Left,
+///         offset,
+///     };
+///     chain.process(event, Instant::now()).len()
+/// };
+///
+/// assert_eq!(moved([0.5, 0.0]), 1);
+/// assert_eq!(moved([0.55, 0.0]), 0);
+/// assert_eq!(moved([0.6, 0.05]), 1);
+/// // back to rest
+/// assert_eq!(moved([0.0, 0.0]), 1);
+/// assert!(AxisThreshold::new(0.0).is_err());
+/// # Ok::<(), girl::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisThreshold {
+    /// Smallest reported change.
+    threshold: f64,
+    /// Last reported offsets of the [`Stick`]s, by controller instance ID.
+    sticks: BTreeMap<(u32, Stick), [f64; 2]>,
+    /// Last reported values of the [`Trigger`]s, by controller instance ID.
+    triggers: BTreeMap<(u32, Trigger), f64>,
+}
+
+impl EventMiddleware for AxisThreshold {
+    #[inline]
+    fn process(
+        &mut self,
+        event: Event,
+        _ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        if let Event::ControllerDeviceRemoved { which: removed } = event {
+            self.sticks.retain(|&(which, _), _| which != removed);
+            self.triggers.retain(|&(which, _), _| which != removed);
+        }
+        if let Event::ControllerStickMotion { which, stick, offset } = event {
+            let [x, y] = offset;
+            let last = self.sticks.get(&(which, stick)).copied();
+            let [lx, ly] = last.unwrap_or_default();
+            let rest = x == 0.0f64 && y == 0.0f64;
+            if !rest && (x - lx).hypot(y - ly) < self.threshold {
+                return None;
+            }
+            _ = self.sticks.insert((which, stick), offset);
+        }
+        if let Event::ControllerTriggerMotion { which, trigger, offset } = event
+        {
+            let last = self.triggers.get(&(which, trigger)).copied();
+            let last = last.unwrap_or_default();
+            if offset != 0.0f64 && (offset - last).abs() < self.threshold {
+                return None;
+            }
+            _ = self.triggers.insert((which, trigger), offset);
+        }
+        Some(event)
+    }
+}
+
+impl AxisThreshold {
+    /// Creates an [`AxisThreshold`] dropping changes smaller than the
+    /// `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the `threshold` is not in the
+    /// `(0.0, 1.0]` range.
+    #[inline]
+    pub fn new(threshold: f64) -> Result<Self, Error> {
+        check_threshold(threshold)?;
+        Ok(Self {
+            threshold,
+            sticks: BTreeMap::new(),
+            triggers: BTreeMap::new(),
+        })
+    }
+
+    /// Gets the smallest reported change.
+    #[must_use]
+    #[inline]
+    pub const fn threshold(&self) -> f64 {
+        self.threshold
+    }
+}
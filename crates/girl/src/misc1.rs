@@ -0,0 +1,206 @@
+//! Platform semantics of [`Button::Misc1`], see [`Gamepad::misc1_role`].
+//!
+//! [`Gamepad::misc1_role`]: crate::Gamepad::misc1_role
+
+use crate::{Button, Event};
+
+/// What [`Button::Misc1`] does on a controller, from its
+/// [`ControllerType`], see [`Gamepad::misc1_role`].
+///
+/// SDL2 only delivers the button through its HIDAPI drivers, and through
+/// the Game Controller framework on macOS. Controllers read through `XInput`
+/// or a generic driver, e.g. Xbox controllers on Windows with the HIDAPI
+/// Xbox driver disabled, never report it. Older controllers of a type may
+/// lack the button altogether, such as Xbox One controllers released
+/// before the Series ones.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{Misc1Role, prelude::*};
+/// use ControllerType as CT;
+/// let table = [
+///     (CT::Xbox360, Misc1Role::Unknown),
+///     (CT::XboxOne, Misc1Role::Share),
+///     (CT::Ps3, Misc1Role::Unknown),
+///     (CT::Ps4, Misc1Role::Unknown),
+///     (CT::Ps5, Misc1Role::MicMute),
+///     (CT::SwitchPro, Misc1Role::Capture),
+///     (CT::JoyConLeft, Misc1Role::Capture),
+///     (CT::JoyConRight, Misc1Role::Unknown),
+///     (CT::JoyConPair, Misc1Role::Capture),
+///     (CT::Virtual, Misc1Role::Unknown),
+///     (CT::Other, Misc1Role::Unknown),
+///     (CT::Unknown, Misc1Role::Unknown),
+/// ];
+/// for (controller_type, role) in table {
+///     assert_eq!(controller_type.misc1_role(), role, "{controller_type:?}");
+/// }
+/// assert_eq!(Misc1Role::Capture.label(), "Capture");
+/// ```
+///
+/// [`ControllerType`]: crate::ControllerType
+/// [`Gamepad::misc1_role`]: crate::Gamepad::misc1_role
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Misc1Role {
+    /// Microphone mute, on the `DualSense`.
+    MicMute,
+    /// Share, on Xbox Series controllers.
+    Share,
+    /// Screenshot capture, on Switch controllers.
+    Capture,
+    /// Not known, or the controller has no such button.
+    Unknown,
+}
+
+impl Misc1Role {
+    /// Gets the name printed on, or next to, the button, e.g. for button
+    /// prompts.
+    #[must_use]
+    #[inline]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::MicMute => "Mic Mute",
+            Self::Share => "Share",
+            Self::Capture => "Capture",
+            Self::Unknown => "Misc 1",
+        }
+    }
+}
+
+/// [`Misc1Role`]s whose [`Button::Misc1`] [`Event`]s [`Girl`] drops, e.g.
+/// since the operating system reserves the button for screenshots and
+/// recordings, see [`Girl::set_misc1_suppression`].
+///
+/// Only [`Event`]s are suppressed, polled [`Button`]s still include
+/// [`Button::Misc1`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{Misc1Role, Misc1Suppression, prelude::*};
+/// let mut suppression = Misc1Suppression::none();
+/// suppression.capture = true;
+///
+/// let press = Event::ControllerButtonDown { which: 0, button: Button::Misc1 };
+/// let other = Event::ControllerButtonDown { which: 0, button: Button::A };
+/// let release = Event::ControllerButtonUp { which: 0, button: Button::Misc1 };
+/// let table = [
+///     (Misc1Role::Capture, press, false),
+///     (Misc1Role::Capture, release, false),
+///     (Misc1Role::Capture, other, true),
+///     (Misc1Role::Share, press, true),
+///     (Misc1Role::MicMute, press, true),
+///     (Misc1Role::Unknown, press, true),
+/// ];
+/// for (role, event, kept) in table {
+///     assert_eq!(suppression.filter(role, event).is_some(), kept);
+/// }
+///
+/// // the button is taken out of the held ones as well
+/// let chord = Event::ControllerButtonDownWithState {
+///     which: 0,
+///     button: Button::A,
+///     held: Button::Misc1 | Button::B,
+/// };
+/// let Some(Event::ControllerButtonDownWithState { held, .. }) =
+///     suppression.filter(Misc1Role::Capture, chord)
+/// else {
+///     panic!("dropped");
+/// };
+/// assert_eq!(held, Button::B);
+///
+/// // nothing is suppressed on Linux by default
+/// if cfg!(target_os = "linux") {
+///     assert_eq!(Misc1Suppression::platform(), Misc1Suppression::none());
+/// }
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::set_misc1_suppression`]: crate::Girl::set_misc1_suppression
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Misc1Suppression {
+    /// Whether to drop [`Misc1Role::MicMute`] [`Event`]s.
+    pub mic_mute: bool,
+    /// Whether to drop [`Misc1Role::Share`] [`Event`]s.
+    pub share: bool,
+    /// Whether to drop [`Misc1Role::Capture`] [`Event`]s.
+    pub capture: bool,
+}
+
+impl Default for Misc1Suppression {
+    #[inline]
+    fn default() -> Self {
+        Self::platform()
+    }
+}
+
+impl Misc1Suppression {
+    /// Creates a [`Misc1Suppression`] dropping nothing.
+    #[must_use]
+    #[inline]
+    pub const fn none() -> Self {
+        Self { mic_mute: false, share: false, capture: false }
+    }
+
+    /// Creates the [`Misc1Suppression`] of the target operating system,
+    /// the default of [`Girl`]: the share button opens the Game Bar on
+    /// Windows, and the share and capture buttons take screenshots on
+    /// macOS. Nothing is reserved elsewhere.
+    ///
+    /// [`Girl`]: crate::Girl
+    #[must_use]
+    #[inline]
+    pub const fn platform() -> Self {
+        Self {
+            mic_mute: false,
+            share: cfg!(any(target_os = "windows", target_os = "macos")),
+            capture: cfg!(target_os = "macos"),
+        }
+    }
+
+    /// Checks if [`Event`]s of the [`Misc1Role`] are dropped.
+    #[must_use]
+    #[inline]
+    pub const fn suppresses(self, role: Misc1Role) -> bool {
+        match role {
+            Misc1Role::MicMute => self.mic_mute,
+            Misc1Role::Share => self.share,
+            Misc1Role::Capture => self.capture,
+            Misc1Role::Unknown => false,
+        }
+    }
+
+    /// Drops the [`Button::Misc1`] [`Event`] of a controller with the
+    /// [`Misc1Role`] if it is suppressed, also taking the button out of
+    /// the held ones of [`Event::ControllerButtonDownWithState`].
+    #[must_use]
+    #[inline]
+    pub fn filter(self, role: Misc1Role, event: Event) -> Option<Event> {
+        if !self.suppresses(role) {
+            return Some(event);
+        }
+        if let Event::ControllerButtonDown { button, .. }
+        | Event::ControllerButtonDownWithState { button, .. }
+        | Event::ControllerButtonUp { button, .. } = event
+            && button == Button::Misc1
+        {
+            return None;
+        }
+        if let Event::ControllerButtonDownWithState { which, button, held } =
+            event
+        {
+            let others = held.difference(Button::Misc1);
+            return Some(Event::ControllerButtonDownWithState {
+                which,
+                button,
+                held: others,
+            });
+        }
+        Some(event)
+    }
+}
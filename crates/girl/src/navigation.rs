@@ -0,0 +1,223 @@
+//! Discrete stick-direction "buttons" for menu navigation.
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Direction of a [`Stick`] or D-pad.
+///
+/// [`Stick`]: crate::Stick
+#[expect(clippy::exhaustive_enums, reason = "only four directions exist")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Up (negative `y`).
+    Up,
+    /// Down (positive `y`).
+    Down,
+    /// Left (negative `x`).
+    Left,
+    /// Right (positive `x`).
+    Right,
+}
+
+impl Direction {
+    /// Gets how far `position` is deflected towards the [`Direction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::Direction;
+    /// assert_eq!(Direction::Up.component([0.2, -0.7]), 0.7);
+    /// assert_eq!(Direction::Down.component([0.2, -0.7]), -0.7);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn component(self, position: [f64; 2]) -> f64 {
+        let [x, y] = position;
+        match self {
+            Self::Up => -y,
+            Self::Down => y,
+            Self::Left => -x,
+            Self::Right => x,
+        }
+    }
+}
+
+/// Kind of a [`StickNavigator`] transition.
+#[expect(clippy::exhaustive_enums, reason = "mirrors button semantics")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NavigationAction {
+    /// The direction was just pressed.
+    Pressed,
+    /// The direction is still held and auto-repeated.
+    Repeat,
+    /// The direction was released.
+    Released,
+}
+
+/// Configuration of a [`StickNavigator`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavigationConfig {
+    /// Deflection at which a direction is pressed.
+    pub press_threshold: f64,
+    /// Deflection below which a pressed direction is released. Should be
+    /// lower than [`press_threshold`] to avoid chatter (hysteresis).
+    ///
+    /// [`press_threshold`]: Self::press_threshold
+    pub release_threshold: f64,
+    /// Delay before the first [`NavigationAction::Repeat`].
+    pub repeat_delay: Duration,
+    /// Interval between subsequent [`NavigationAction::Repeat`]s.
+    pub repeat_interval: Duration,
+    /// How much the vertical axis is preferred on diagonals: the vertical
+    /// direction wins when `|y| * (1.0 + vertical_bias) >= |x|`.
+    pub vertical_bias: f64,
+}
+
+impl Default for NavigationConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NavigationConfig {
+    /// Creates the default [`NavigationConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            press_threshold: 0.6,
+            release_threshold: 0.4,
+            repeat_delay: Duration::from_millis(400),
+            repeat_interval: Duration::from_millis(100),
+            vertical_bias: 0.0,
+        }
+    }
+}
+
+/// State machine turning a [`Stick`] position into discrete [`Direction`]
+/// presses with hysteresis and auto-repeat.
+///
+/// Used by [`Girl::set_stick_navigation`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{Direction, NavigationConfig, StickNavigator};
+/// # use girl::NavigationAction as Nav;
+/// let mut nav = StickNavigator::new(NavigationConfig::default());
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+/// let mut feed = |position, ms| {
+///     let mut out = vec![];
+///     nav.update(position, at(ms), |action, dir| out.push((action, dir)));
+///     out
+/// };
+///
+/// // below the press threshold
+/// assert_eq!(feed([0.0, -0.5], 0), []);
+/// assert_eq!(feed([0.0, -0.7], 10), [(Nav::Pressed, Direction::Up)]);
+/// // hysteresis: still held between the thresholds
+/// assert_eq!(feed([0.0, -0.5], 20), []);
+/// // auto-repeat after the delay, then every interval
+/// assert_eq!(feed([0.0, -0.9], 410), [(Nav::Repeat, Direction::Up)]);
+/// assert_eq!(feed([0.0, -0.9], 450), []);
+/// assert_eq!(feed([0.0, -0.9], 510), [(Nav::Repeat, Direction::Up)]);
+/// // direction change without passing through the center
+/// assert_eq!(feed([0.8, -0.1], 520), [
+///     (Nav::Released, Direction::Up),
+///     (Nav::Pressed, Direction::Right),
+/// ]);
+/// assert_eq!(feed([0.1, 0.0], 530), [(Nav::Released, Direction::Right)]);
+/// ```
+///
+/// [`Stick`]: crate::Stick
+/// [`Girl::set_stick_navigation`]: crate::Girl::set_stick_navigation
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickNavigator {
+    /// Configuration.
+    config: NavigationConfig,
+    /// Currently held direction and the time of its next repeat.
+    held: Option<(Direction, Instant)>,
+}
+
+#[cfg(feature = "std")]
+impl StickNavigator {
+    /// Creates a new [`StickNavigator`] with nothing held.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: NavigationConfig) -> Self {
+        Self { config, held: None }
+    }
+
+    /// Gets the currently held [`Direction`], if any.
+    #[must_use]
+    #[inline]
+    pub const fn held(&self) -> Option<Direction> {
+        match self.held {
+            Some((direction, _)) => Some(direction),
+            None => None,
+        }
+    }
+
+    /// Feeds a new stick `position` observed at `now`, calling `emit` for
+    /// every resulting transition (at most two: a release and a press).
+    #[inline]
+    pub fn update<F>(&mut self, position: [f64; 2], now: Instant, mut emit: F)
+    where
+        F: FnMut(NavigationAction, Direction),
+    {
+        let candidate = self.dominant(position);
+
+        if let Some((direction, next_repeat)) = self.held {
+            let released =
+                direction.component(position) < self.config.release_threshold;
+            let changed = candidate != direction
+                && candidate.component(position) >= self.config.press_threshold;
+
+            if !released && !changed {
+                if now >= next_repeat {
+                    emit(NavigationAction::Repeat, direction);
+                    let next = next_repeat
+                        .checked_add(self.config.repeat_interval)
+                        .unwrap_or(now);
+                    self.held = Some((direction, next));
+                }
+                return;
+            }
+
+            emit(NavigationAction::Released, direction);
+            self.held = None;
+        }
+
+        if candidate.component(position) >= self.config.press_threshold {
+            emit(NavigationAction::Pressed, candidate);
+            let next = now.checked_add(self.config.repeat_delay).unwrap_or(now);
+            self.held = Some((candidate, next));
+        }
+    }
+
+    /// Releases the held [`Direction`], if any, without waiting for the
+    /// stick to return.
+    #[inline]
+    pub fn reset(&mut self) -> Option<Direction> {
+        self.held.take().map(|(direction, _)| direction)
+    }
+
+    /// Resolves the dominant [`Direction`] of `position`.
+    fn dominant(&self, position: [f64; 2]) -> Direction {
+        let [x, y] = position;
+        if y.abs() * (1.0 + self.config.vertical_bias) >= x.abs() {
+            if y < 0.0 { Direction::Up } else { Direction::Down }
+        } else if x < 0.0 {
+            Direction::Left
+        } else {
+            Direction::Right
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Normalization of raw axis values and deadzones, for custom input
+//! processing on top of raw SDL2 values.
+//!
+//! These are the building blocks [`Gamepad`] and [`Event`] use themselves:
+//! sticks are [`axis`] followed by [`apply_deadzone`], triggers are
+//! [`trigger`] followed by [`TriggerProfile::apply`]. Outputs are kept in
+//! their documented ranges, stick components in `[-1.0, 1.0]` (see
+//! [`clamp_stick`]) and triggers in `[0.0, 1.0]` (see [`clamp_trigger`]),
+//! so that math assuming them, e.g. [`f64::acos`] of a dot product, never
+//! produces NaN. [`axis_unclamped`] keeps raw values proportional instead.
+//!
+//! [`Gamepad`]: crate::Gamepad
+//! [`Event`]: crate::Event
+//! [`TriggerProfile::apply`]: crate::TriggerProfile::apply
+
+/// Maximum value of raw SDL2 axes, `SDL_JOYSTICK_AXIS_MAX`.
+const AXIS_MAX: f64 = 32_767.0;
+
+/// Normalizes a raw SDL2 axis value to `[-1.0, 1.0]`.
+///
+/// Divides by [`i16::MAX`], so `0` maps to exactly `0.0` and [`i16::MAX`] to
+/// exactly `1.0`. As the range of [`i16`] is asymmetric, [`i16::MIN`] would
+/// map slightly below `-1.0` and is clamped to exactly `-1.0` instead.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize;
+/// assert_eq!(normalize::axis(0), 0.0);
+/// assert_eq!(normalize::axis(i16::MAX), 1.0);
+/// assert_eq!(normalize::axis(-i16::MAX), -1.0);
+/// assert_eq!(normalize::axis(i16::MIN), -1.0);
+/// assert_eq!(normalize::axis(16_384), 16_384.0 / 32_767.0);
+///
+/// for raw in i16::MIN..=i16::MAX {
+///     let value = normalize::axis(raw);
+///     assert!((-1.0..=1.0).contains(&value), "{raw} -> {value}");
+///     assert_eq!(value.signum(), f64::from(raw).signum());
+/// }
+/// ```
+#[must_use]
+#[inline]
+pub fn axis(value: i16) -> f64 {
+    axis_unclamped(value).max(-1.0)
+}
+
+/// Normalizes a raw SDL2 axis value by dividing it by [`i16::MAX`], without
+/// clamping, for raw proportionality.
+///
+/// Unlike [`axis`], [`i16::MIN`] maps slightly below `-1.0`.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize;
+/// assert_eq!(normalize::axis_unclamped(i16::MAX), 1.0);
+/// assert_eq!(normalize::axis_unclamped(i16::MIN), -32_768.0 / 32_767.0);
+/// assert!(normalize::axis_unclamped(i16::MIN) < -1.0);
+///
+/// for raw in -i16::MAX..=i16::MAX {
+///     assert_eq!(normalize::axis_unclamped(raw), normalize::axis(raw));
+/// }
+/// ```
+#[must_use]
+#[inline]
+pub fn axis_unclamped(value: i16) -> f64 {
+    f64::from(value) / AXIS_MAX
+}
+
+/// Normalizes a raw SDL2 trigger axis value to `[0.0, 1.0]`.
+///
+/// Triggers rest at `0`, but some drivers report the full [`i16`] range,
+/// so negative values are clamped to `0.0`.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize;
+/// assert_eq!(normalize::trigger(0), 0.0);
+/// assert_eq!(normalize::trigger(i16::MAX), 1.0);
+/// assert_eq!(normalize::trigger(i16::MIN), 0.0);
+/// assert_eq!(normalize::trigger(-1), 0.0);
+///
+/// for raw in i16::MIN..=i16::MAX {
+///     let value = normalize::trigger(raw);
+///     assert!((0.0..=1.0).contains(&value), "{raw} -> {value}");
+/// }
+/// ```
+#[must_use]
+#[inline]
+pub fn trigger(value: i16) -> f64 {
+    clamp_trigger(axis_unclamped(value))
+}
+
+/// Clamps both components of a [`Stick`] position to `[-1.0, 1.0]`.
+///
+/// The magnitude may still reach `√2` on the diagonals, see
+/// [`StickClamp`] to bound it.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize::{self, clamp_stick};
+/// let min = normalize::axis_unclamped(i16::MIN);
+/// assert_eq!(clamp_stick([min, min]), [-1.0, -1.0]);
+/// assert_eq!(clamp_stick([1.5, -0.25]), [1.0, -0.25]);
+/// assert_eq!(clamp_stick([0.0, 0.0]), [0.0, 0.0]);
+/// ```
+///
+/// [`Stick`]: crate::Stick
+/// [`StickClamp`]: crate::StickClamp
+#[must_use]
+#[inline]
+pub const fn clamp_stick(position: [f64; 2]) -> [f64; 2] {
+    let [x, y] = position;
+    [x.clamp(-1.0, 1.0), y.clamp(-1.0, 1.0)]
+}
+
+/// Clamps a [`Trigger`] value to `[0.0, 1.0]`.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize::clamp_trigger;
+/// assert_eq!(clamp_trigger(-1.0), 0.0);
+/// assert_eq!(clamp_trigger(0.5), 0.5);
+/// assert_eq!(clamp_trigger(1.5), 1.0);
+/// ```
+///
+/// [`Trigger`]: crate::Trigger
+#[must_use]
+#[inline]
+pub const fn clamp_trigger(value: f64) -> f64 {
+    value.clamp(0.0, 1.0)
+}
+
+/// Zeroes a normalized `value` whose magnitude is below the `threshold`,
+/// keeping it unchanged otherwise.
+///
+/// The output jumps from `0.0` straight to `threshold` at the edge of the
+/// deadzone, see [`apply_deadzone_rescaled`] for a continuous output. No
+/// clamping is done, a `threshold` of `0.0` or less passes every `value`
+/// through.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize::apply_deadzone;
+/// assert_eq!(apply_deadzone(0.05, 0.1), 0.0);
+/// assert_eq!(apply_deadzone(-0.05, 0.1), 0.0);
+/// assert_eq!(apply_deadzone(0.1, 0.1), 0.1);
+/// assert_eq!(apply_deadzone(-0.5, 0.1), -0.5);
+/// assert_eq!(apply_deadzone(1.0, 0.1), 1.0);
+/// assert_eq!(apply_deadzone(0.0, 0.0), 0.0);
+/// assert_eq!(apply_deadzone(-1.0, 0.0), -1.0);
+/// assert_eq!(apply_deadzone(1.0, 1.5), 0.0);
+/// ```
+#[must_use]
+#[inline]
+pub const fn apply_deadzone(value: f64, threshold: f64) -> f64 {
+    if value.abs() < threshold { 0.0 } else { value }
+}
+
+/// Zeroes a normalized `value` whose magnitude is below the `threshold`,
+/// rescaling the rest of the range.
+///
+/// The output grows continuously from `0.0` at the edge of the deadzone to
+/// `1.0` at full deflection.
+///
+/// The output is clamped to `[-1.0, 1.0]`. A `threshold` of `0.0` or less
+/// only clamps, and one of `1.0` or more zeroes every `value`.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::normalize::apply_deadzone_rescaled;
+/// assert_eq!(apply_deadzone_rescaled(0.1, 0.25), 0.0);
+/// assert_eq!(apply_deadzone_rescaled(0.25, 0.25), 0.0);
+/// assert_eq!(apply_deadzone_rescaled(0.625, 0.25), 0.5);
+/// assert_eq!(apply_deadzone_rescaled(-0.625, 0.25), -0.5);
+/// assert_eq!(apply_deadzone_rescaled(1.0, 0.25), 1.0);
+/// assert_eq!(apply_deadzone_rescaled(-1.0, 0.25), -1.0);
+///
+/// // clamping at the extremes
+/// assert_eq!(apply_deadzone_rescaled(1.5, 0.25), 1.0);
+/// assert_eq!(apply_deadzone_rescaled(-1.5, 0.0), -1.0);
+/// assert_eq!(apply_deadzone_rescaled(0.3, -0.5), 0.3);
+/// assert_eq!(apply_deadzone_rescaled(1.0, 1.0), 0.0);
+/// ```
+#[must_use]
+#[inline]
+pub const fn apply_deadzone_rescaled(value: f64, threshold: f64) -> f64 {
+    if threshold >= 1.0f64 {
+        return 0.0;
+    }
+    let deadzone = threshold.max(0.0);
+    let magnitude = value.abs();
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+    scaled.copysign(value)
+}
@@ -0,0 +1,50 @@
+//! Recording why [`Gamepad`] opens fail, see [`Girl::last_open_error`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+//! [`Girl::last_open_error`]: crate::Girl::last_open_error
+
+use core::cell::RefCell;
+
+use crate::{Error, logging::diag};
+
+/// Why the latest [`Gamepad`] open failed, if it did.
+///
+/// Used by [`Girl`] and [`SimGirl`], which open through `&self`.
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Girl`]: crate::Girl
+/// [`SimGirl`]: crate::SimGirl
+#[derive(Debug, Default)]
+pub(crate) struct LastOpenError(RefCell<Option<Error>>);
+
+impl LastOpenError {
+    /// Gets the [`Error`] of the latest open, if it failed.
+    #[must_use]
+    #[inline]
+    pub(crate) fn get(&self) -> Option<Error> {
+        self.0.borrow().clone()
+    }
+
+    /// Records the `result` of opening the device at `index`, keeping its
+    /// [`Error`] or clearing the previous one, without allocating on
+    /// success.
+    #[inline]
+    pub(crate) fn record<T>(
+        &self,
+        index: u32,
+        result: Result<T, Error>,
+    ) -> Option<T> {
+        let (opened, error) = match result {
+            Ok(opened) => (Some(opened), None),
+            Err(err) => {
+                // retried on every device change, log only the first failure
+                if self.0.borrow().is_none() {
+                    diag!(warn, "failed to open gamepad {index}: {err}");
+                }
+                (None, Some(err))
+            }
+        };
+        drop(self.0.replace(error));
+        opened
+    }
+}
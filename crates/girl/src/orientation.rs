@@ -0,0 +1,282 @@
+//! Orientation estimation from gyroscope and accelerometer readings.
+
+use std::time::Instant;
+
+/// Standard gravity in m/s², the magnitude of an accelerometer reading at
+/// rest.
+const STANDARD_GRAVITY: f64 = 9.806_65;
+
+/// Time constant, in seconds, of pulling the tilt towards the gravity
+/// measured by the accelerometer.
+const TILT_TIME_CONSTANT: f64 = 0.5;
+
+/// Largest deviation of an accelerometer reading from [`STANDARD_GRAVITY`],
+/// as a fraction of it, still trusted as measuring gravity rather than
+/// shaking.
+const GRAVITY_TOLERANCE: f64 = 0.25;
+
+/// World up.
+const UP: [f64; 3] = [0.0, 1.0, 0.0];
+
+/// Forward of the controller in its body frame.
+const FORWARD: [f64; 3] = [0.0, 0.0, -1.0];
+
+/// Rotation that doesn't rotate, as a quaternion.
+const IDENTITY: [f64; 4] = [1.0, 0.0, 0.0, 0.0];
+
+/// Estimates the orientation of a controller from its gyroscope and
+/// accelerometer.
+///
+/// Turns body-frame readings into world-frame ones, e.g. to rotate the
+/// camera around world up however the controller is held. Integrates the
+/// gyroscope and slowly pulls the tilt towards the gravity measured by the
+/// accelerometer, ignoring the accelerometer while it is shaken. The tilt is
+/// thereby gravity-aligned, while the heading (yaw) is relative to a reference
+/// set on the first reading and by [`reset_yaw`], and drifts slowly with the
+/// gyroscope.
+///
+/// Used by `Gamepad::sensor_world`, but can also be driven directly.
+///
+/// # Conventions
+///
+/// Both frames are right-handed and use the units SDL2 reports in:
+///
+/// - The body frame is that of `Gamepad::sensor`: `+x` points to the right of
+///   the controller, `+y` out of its face (up when it lies flat) and `+z` out
+///   of its grips towards the player holding it.
+/// - The world frame has `+y` pointing up, against gravity, and `-z` pointing
+///   forward: along the heading of the controller's `-z` axis at the yaw
+///   reference, or of its `-y` axis if it pointed straight up. `+x` points to
+///   the right of that.
+/// - Gyroscope readings are angular velocities in radians per second, positive
+///   counterclockwise when looking from the positive end of the axis towards
+///   the origin (right-hand rule). Turning left around world up is thereby a
+///   positive world `y`, whatever the grip.
+/// - Accelerometer readings are in m/s² and include the reaction to gravity, so
+///   a controller at rest reads `[0.0, 9.81, 0.0]` in the world frame.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{f64::consts::FRAC_PI_2, time::{Duration, Instant}};
+/// # use girl::Orientation;
+/// let close = |actual: [f64; 3], expected: [f64; 3]| {
+///     actual.iter().zip(expected).all(|(a, e)| (a - e).abs() < 1e-6)
+/// };
+/// let g = 9.806_65;
+/// let start = Instant::now();
+/// let at = |ms| start + Duration::from_millis(ms);
+///
+/// // held pointing at the sky: gravity pulls along the grips
+/// let mut skyward = Orientation::new();
+/// skyward.push([0.0; 3], [0.0, 0.0, -g], at(0));
+/// assert!(close(skyward.to_world([0.0, 0.0, -g]), [0.0, g, 0.0]));
+/// // turning around world up is a roll in the body frame
+/// assert!(close(skyward.to_world([0.0, 0.0, -1.0]), [0.0, 1.0, 0.0]));
+/// // and the face points towards the player
+/// assert!(close(skyward.to_world([0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]));
+///
+/// // lying flat, turning left at 90 °/s for a second
+/// let mut flat = Orientation::new();
+/// flat.push([0.0; 3], [0.0, g, 0.0], at(0));
+/// for step in 1..=100 {
+///     flat.push([0.0, FRAC_PI_2, 0.0], [0.0, g, 0.0], at(step * 10));
+/// }
+/// // forward now points to the left of the reference
+/// assert!(close(flat.to_world([0.0, 0.0, -1.0]), [-1.0, 0.0, 0.0]));
+/// assert!(close(flat.to_world([0.0, 1.0, 0.0]), [0.0, 1.0, 0.0]));
+/// flat.reset_yaw();
+/// assert!(close(flat.to_world([0.0, 0.0, -1.0]), [0.0, 0.0, -1.0]));
+///
+/// // a gyroscope drifting 0.1 rad/s in pitch is held level by gravity
+/// for step in 101..=1100 {
+///     flat.push([0.1, 0.0, 0.0], [0.0, g, 0.0], at(step * 10));
+/// }
+/// let [_, up, _] = flat.to_world([0.0, g, 0.0]);
+/// assert!(up > 0.99 * g);
+/// ```
+///
+/// [`reset_yaw`]: Self::reset_yaw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Orientation {
+    /// Rotation from the body to the world frame, as a unit quaternion
+    /// `[w, x, y, z]`.
+    rotation: [f64; 4],
+    /// When the previous readings were taken.
+    last: Option<Instant>,
+}
+
+impl Default for Orientation {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Orientation {
+    /// Creates a new [`Orientation`], estimated from the first readings
+    /// pushed.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { rotation: IDENTITY, last: None }
+    }
+
+    /// Feeds a gyroscope and an accelerometer reading in the body frame,
+    /// as reported by `Gamepad::sensor`, taken at `timestamp`.
+    ///
+    /// The first readings level the estimate with the accelerometer and set
+    /// the yaw reference. Every later gyroscope reading is integrated over
+    /// the time since the previous one, so readings must be fed in order.
+    #[inline]
+    pub fn push(
+        &mut self,
+        gyro: [f64; 3],
+        accel: [f64; 3],
+        timestamp: Instant,
+    ) {
+        let Some(last) = self.last.replace(timestamp) else {
+            self.rotation = arc(accel, UP, 1.0);
+            self.reset_yaw();
+            return;
+        };
+        let dt = timestamp.saturating_duration_since(last).as_secs_f64();
+
+        // the angular velocity is in the body frame, so turn on the right
+        let speed = length(gyro);
+        if speed > 0.0f64 {
+            let axis = gyro.map(|rate| rate / speed);
+            let turn = axis_angle(axis, speed * dt);
+            self.rotation = normalize(multiply(self.rotation, turn));
+        }
+
+        // gravity is in the world frame, so correct on the left
+        let measured = rotate(self.rotation, accel);
+        let deviation = (length(measured) - STANDARD_GRAVITY).abs();
+        if deviation < GRAVITY_TOLERANCE * STANDARD_GRAVITY {
+            let fraction = (dt / TILT_TIME_CONSTANT).min(1.0);
+            let correction = arc(measured, UP, fraction);
+            self.rotation = normalize(multiply(correction, self.rotation));
+        }
+    }
+
+    /// Rotates a `reading` in the body frame into the world frame, see the
+    /// [conventions](Self#conventions).
+    #[must_use]
+    #[inline]
+    pub fn to_world(&self, reading: [f64; 3]) -> [f64; 3] {
+        rotate(self.rotation, reading)
+    }
+
+    /// Gets the rotation from the body to the world frame, as a unit
+    /// quaternion `[w, x, y, z]`.
+    #[must_use]
+    #[inline]
+    pub const fn quaternion(&self) -> [f64; 4] {
+        self.rotation
+    }
+
+    /// Makes the current heading of the controller the yaw reference, e.g.
+    /// to re-center the world frame on where the player points it now.
+    #[inline]
+    pub fn reset_yaw(&mut self) {
+        // falls back to the bottom when pointing straight up, and to the
+        // face when pointing straight down
+        let [forward_x, forward_y, forward_z] = rotate(self.rotation, FORWARD);
+        let [up_x, _, up_z] = rotate(self.rotation, UP);
+        let x = (-forward_y).mul_add(up_x, forward_x);
+        let z = (-forward_y).mul_add(up_z, forward_z);
+        let heading = (-x).atan2(-z);
+        let turn = axis_angle(UP, -heading);
+        self.rotation = normalize(multiply(turn, self.rotation));
+    }
+
+    /// Forgets the estimate, starting over with the next readings.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.rotation = IDENTITY;
+        self.last = None;
+    }
+}
+
+/// Gets the length of a vector.
+fn length([x, y, z]: [f64; 3]) -> f64 {
+    x.hypot(y).hypot(z)
+}
+
+/// Creates a rotation by `angle` radians around the unit `axis`.
+fn axis_angle([x, y, z]: [f64; 3], angle: f64) -> [f64; 4] {
+    let (sin, cos) = (angle / 2.0).sin_cos();
+    [cos, x * sin, y * sin, z * sin]
+}
+
+/// Creates the rotation by `fraction` of the shortest arc turning the
+/// direction of `from` into that of `to`, identity if either is zero.
+fn arc(from: [f64; 3], to: [f64; 3], fraction: f64) -> [f64; 4] {
+    let (Some(from), Some(to)) = (unit(from), unit(to)) else {
+        return IDENTITY;
+    };
+    let cosine = dot(from, to).clamp(-1.0, 1.0);
+    let [x, _, _] = from;
+    let helper: [f64; 3] =
+        if x.abs() < 0.9f64 { [1.0, 0.0, 0.0] } else { [0.0, 0.0, 1.0] };
+    let normal = cross(from, to);
+    // opposite directions turn around any perpendicular axis
+    let Some(axis) = unit(normal)
+        .or_else(|| unit(cross(from, helper)).filter(|_| cosine < 0.0f64))
+    else {
+        return IDENTITY;
+    };
+    let sine = length(normal);
+    axis_angle(axis, sine.atan2(cosine) * fraction)
+}
+
+/// Scales a vector to unit length, [`None`] if it is about zero.
+fn unit(vector: [f64; 3]) -> Option<[f64; 3]> {
+    let norm = length(vector);
+    (norm > 1e-9).then(|| vector.map(|value| value / norm))
+}
+
+/// Gets the dot product of vectors.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn dot([ax, ay, az]: [f64; 3], [bx, by, bz]: [f64; 3]) -> f64 {
+    ax.mul_add(bx, ay.mul_add(by, az * bz))
+}
+
+/// Gets the cross product of vectors.
+fn cross([ax, ay, az]: [f64; 3], [bx, by, bz]: [f64; 3]) -> [f64; 3] {
+    [
+        ay.mul_add(bz, -(az * by)),
+        az.mul_add(bx, -(ax * bz)),
+        ax.mul_add(by, -(ay * bx)),
+    ]
+}
+
+/// Multiplies quaternions, the result rotating by `right` first.
+fn multiply(left: [f64; 4], right: [f64; 4]) -> [f64; 4] {
+    let [lw, lx, ly, lz] = left;
+    let [rw, rx, ry, rz] = right;
+    [
+        lw.mul_add(rw, -lx.mul_add(rx, ly.mul_add(ry, lz * rz))),
+        lw.mul_add(rx, lx.mul_add(rw, ly.mul_add(rz, -(lz * ry)))),
+        lw.mul_add(ry, (-lx).mul_add(rz, ly.mul_add(rw, lz * rx))),
+        lw.mul_add(rz, lx.mul_add(ry, (-ly).mul_add(rx, lz * rw))),
+    ]
+}
+
+/// Normalizes a quaternion, countering the accumulation of rounding
+/// errors.
+fn normalize(quaternion: [f64; 4]) -> [f64; 4] {
+    let [w, x, y, z] = quaternion;
+    let norm = w.hypot(x).hypot(y).hypot(z);
+    if norm > 0.0 { quaternion.map(|value| value / norm) } else { IDENTITY }
+}
+
+/// Rotates a vector by a unit quaternion.
+fn rotate(quaternion: [f64; 4], [x, y, z]: [f64; 3]) -> [f64; 3] {
+    let [w, qx, qy, qz] = quaternion;
+    let conjugate = [w, -qx, -qy, -qz];
+    let [_, rx, ry, rz] =
+        multiply(multiply(quaternion, [0.0f64, x, y, z]), conjugate);
+    [rx, ry, rz]
+}
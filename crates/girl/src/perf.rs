@@ -0,0 +1,129 @@
+//! Counters of the work [`Girl`] does per [`Girl::update`].
+//!
+//! [`Girl`]: crate::Girl
+//! [`Girl::update`]: crate::Girl::update
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+use sdl2::sys as sdl2_sys;
+
+use crate::MiddlewareId;
+
+/// Work done by [`Girl`] between two [`Girl::update`]s, or in a whole
+/// session, see [`Girl::perf_counters`].
+///
+/// Counters saturate instead of overflowing.
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::perf_counters`]: crate::Girl::perf_counters
+/// [`Girl::update`]: crate::Girl::update
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerfCounters {
+    /// SDL2 events taken from the event queue.
+    pub events_pumped: u64,
+    /// SDL2 events translated into [`Event`]s.
+    ///
+    /// [`Event`]: crate::Event
+    pub events_translated: u64,
+    /// Time spent translating SDL2 events.
+    pub translation_time: Duration,
+    /// Total time the SDL2 events waited in the event queue, from when
+    /// SDL2 queued them until they were translated, in milliseconds.
+    ///
+    /// Divide by [`events_pumped`] for the average.
+    ///
+    /// [`events_pumped`]: Self::events_pumped
+    pub queue_latency: Duration,
+    /// Longest time an SDL2 event waited in the event queue, e.g. to
+    /// compare it with and without `Hints::use_joystick_thread`.
+    pub max_queue_latency: Duration,
+    /// Sensor and touchpad reads and controller updates from SDL2, see
+    /// [`Gamepad::sensor`], [`Gamepad::touchpad`] and
+    /// [`Gamepad::read_fresh`].
+    ///
+    /// [`Gamepad::sensor`]: crate::Gamepad::sensor
+    /// [`Gamepad::touchpad`]: crate::Gamepad::touchpad
+    /// [`Gamepad::read_fresh`]: crate::Gamepad::read_fresh
+    pub ffi_calls: u64,
+    /// Heap allocations at the known sites, i.e. the queues of
+    /// [`Event`]s running through the middleware.
+    ///
+    /// [`Event`]: crate::Event
+    pub allocations: u64,
+    /// Time spent in every [`EventMiddleware`], by the [`MiddlewareId`]
+    /// returned when adding it.
+    ///
+    /// [`EventMiddleware`]: crate::EventMiddleware
+    pub middleware_time: BTreeMap<MiddlewareId, Duration>,
+}
+
+impl PerfCounters {
+    /// Creates [`PerfCounters`] of no work.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            events_pumped: 0,
+            events_translated: 0,
+            translation_time: Duration::ZERO,
+            queue_latency: Duration::ZERO,
+            max_queue_latency: Duration::ZERO,
+            ffi_calls: 0,
+            allocations: 0,
+            middleware_time: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the work counted in `other` to these counters.
+    #[inline]
+    pub fn merge(&mut self, other: &Self) {
+        self.events_pumped =
+            self.events_pumped.saturating_add(other.events_pumped);
+        self.events_translated =
+            self.events_translated.saturating_add(other.events_translated);
+        self.translation_time =
+            self.translation_time.saturating_add(other.translation_time);
+        self.queue_latency =
+            self.queue_latency.saturating_add(other.queue_latency);
+        self.max_queue_latency =
+            self.max_queue_latency.max(other.max_queue_latency);
+        self.ffi_calls = self.ffi_calls.saturating_add(other.ffi_calls);
+        self.allocations = self.allocations.saturating_add(other.allocations);
+        for (&id, &time) in &other.middleware_time {
+            self.add_middleware_time(id, time);
+        }
+    }
+
+    /// Counts the time an SDL2 event queued at `timestamp`, in milliseconds
+    /// since SDL2 initialized, waited until now.
+    #[inline]
+    pub(crate) fn count_queue_latency(&mut self, timestamp: u32) {
+        // SAFETY: SDL is alive, and the ticks are valid from any thread.
+        #[expect(unsafe_code, reason = "ffi with sdl2")]
+        let now = unsafe { sdl2_sys::SDL_GetTicks() };
+        let waited =
+            Duration::from_millis(now.saturating_sub(timestamp).into());
+        self.queue_latency = self.queue_latency.saturating_add(waited);
+        self.max_queue_latency = self.max_queue_latency.max(waited);
+    }
+
+    /// Counts an allocation at a known site.
+    #[inline]
+    pub(crate) const fn count_allocation(&mut self) {
+        self.allocations = self.allocations.saturating_add(1);
+    }
+
+    /// Adds `time` spent in the middleware with the [`MiddlewareId`].
+    #[inline]
+    pub(crate) fn add_middleware_time(
+        &mut self,
+        id: MiddlewareId,
+        time: Duration,
+    ) {
+        let total = self.middleware_time.entry(id).or_default();
+        *total = total.saturating_add(time);
+    }
+}
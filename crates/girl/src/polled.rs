@@ -0,0 +1,300 @@
+//! Rate-limited [`Gamepad`] polling.
+
+#[cfg(feature = "sensors")]
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use sdl2::sys as sdl2_sys;
+
+use crate::{
+    Axis, Button, Gamepad, GamepadState, Stick, Trigger, normalize,
+    ratelimit::RateLimiter,
+};
+#[cfg(feature = "sensors")]
+use crate::{Error, Sensor};
+
+/// Rate-limited polling of a [`Gamepad`].
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+impl Gamepad {
+    /// Wraps the [`Gamepad`] into a [`PolledGamepad`] querying it at most
+    /// `rate_hz` times per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap().poll_at(60);
+    ///
+    /// loop {
+    ///     girl.update();
+    ///     // rendering at 240 Hz still queries the gamepad at 60 Hz
+    ///     let [x, y] = gamepad.stick(Stick::Left);
+    ///     # break;
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn poll_at(self, rate_hz: u32) -> PolledGamepad {
+        PolledGamepad::new(self, rate_hz)
+    }
+
+    /// Gets the freshest value of a single [`Axis`], updating the
+    /// controllers first instead of waiting for the next [`Girl::update`],
+    /// e.g. right before judging a hit in a rhythm game.
+    ///
+    /// The update only reads the pending controller reports, without
+    /// pumping or translating events, and saves up to a whole frame of
+    /// latency over [`stick`] and [`trigger`]. It updates **all** the
+    /// controllers, so updates are limited to one per
+    /// [`Girl::set_fresh_read_interval`] across all [`Gamepad`]s, and calls
+    /// in between read the state of the last update. Other queries are
+    /// unaffected, except for reading the updated state too.
+    ///
+    /// Like other queries, it must be called from the thread pumping the
+    /// events. The value is normalized to `[-1.0, 1.0]`, or `[0.0, 1.0]` for
+    /// the triggers, without the
+    /// configured settings or the rotation of [`set_sideways_mode`], and is
+    /// `0.0` while input is suspended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{Axis, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// girl.set_fresh_read_interval(Duration::from_secs(3600));
+    /// girl.set_perf_counters(true);
+    /// # if girl.gamepad(0).is_some() {
+    /// let gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // only the first read updates the controllers
+    /// let pressed = gamepad.read_fresh(Axis::RightTrigger) > 0.5;
+    /// let before = girl.perf_counters().ffi_calls;
+    /// _ = gamepad.read_fresh(Axis::RightTrigger);
+    /// assert_eq!(girl.perf_counters().ffi_calls, before);
+    ///
+    /// // and the other queries still apply the configured settings
+    /// assert_eq!(gamepad.stick(Stick::Left), gamepad.state().left_stick);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::set_fresh_read_interval`]:
+    ///     crate::Girl::set_fresh_read_interval
+    /// [`stick`]: Self::stick
+    /// [`trigger`]: Self::trigger
+    /// [`set_sideways_mode`]: Self::set_sideways_mode
+    #[must_use]
+    #[inline]
+    pub fn read_fresh(&self, axis: Axis) -> f64 {
+        if self.suspended_for_query() {
+            return 0.0;
+        }
+        if self.shared().fresh_read_due(Instant::now()) {
+            // SAFETY: SDL is alive, and `Gamepad` is bound to the thread
+            //         pumping the events.
+            #[expect(unsafe_code, reason = "ffi with sdl2")]
+            unsafe {
+                sdl2_sys::SDL_GameControllerUpdate();
+            }
+            self.shared().count_ffi_call();
+        }
+        let raw = self.controller().axis(axis.into_sdl());
+        match axis {
+            Axis::LeftX | Axis::LeftY | Axis::RightX | Axis::RightY => {
+                normalize::axis(raw)
+            }
+            Axis::LeftTrigger | Axis::RightTrigger => normalize::trigger(raw),
+        }
+    }
+}
+
+/// [`Gamepad`] queried at a limited rate, to save power on handhelds.
+///
+/// Reads return a cached snapshot, refreshed from the [`Gamepad`] when at
+/// least `1 / rate_hz` passed since the previous refresh. [`Button`]s are
+/// cheap to query, so they can be exempted from rate limiting with
+/// [`set_exempt_buttons`].
+///
+/// Obtained with [`Gamepad::poll_at`].
+///
+/// [`set_exempt_buttons`]: Self::set_exempt_buttons
+#[derive(Debug)]
+pub struct PolledGamepad {
+    /// Polled [`Gamepad`].
+    gamepad: Gamepad,
+    /// Limits the refresh rate.
+    limiter: RateLimiter,
+    /// Whether [`Button`]s are always queried directly.
+    exempt_buttons: bool,
+    /// Cached input state.
+    state: GamepadState,
+    /// When the cache was last refreshed.
+    last_refresh: Instant,
+    /// Cached data of the [`Sensor`]s read so far.
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    sensors: BTreeMap<Sensor, [f64; 3]>,
+}
+
+impl PolledGamepad {
+    /// Wraps the [`Gamepad`], querying it at most `rate_hz` times per
+    /// second, see [`Gamepad::poll_at`]. A zero rate never queries it again.
+    #[must_use]
+    #[inline]
+    pub fn new(gamepad: Gamepad, rate_hz: u32) -> Self {
+        let now = Instant::now();
+        let interval = Duration::from_secs(1)
+            .checked_div(rate_hz)
+            .unwrap_or(Duration::MAX);
+        let mut limiter = RateLimiter::new(interval);
+        limiter.mark(now);
+        Self {
+            state: gamepad.state(),
+            gamepad,
+            limiter,
+            exempt_buttons: false,
+            last_refresh: now,
+            #[cfg(feature = "sensors")]
+            sensors: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the wrapped [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub const fn gamepad(&self) -> &Gamepad {
+        &self.gamepad
+    }
+
+    /// Unwraps the [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> Gamepad {
+        self.gamepad
+    }
+
+    /// Gets when the cached snapshot was last refreshed.
+    #[must_use]
+    #[inline]
+    pub const fn last_refresh(&self) -> Instant {
+        self.last_refresh
+    }
+
+    /// Sets whether [`Button`]s are queried directly on every read instead
+    /// of being cached.
+    #[inline]
+    pub const fn set_exempt_buttons(&mut self, exempt: bool) {
+        self.exempt_buttons = exempt;
+    }
+
+    /// Refreshes the cached snapshot if it is due at `now`, returning
+    /// whether it was refreshed.
+    ///
+    /// Reads call this with the current time, so there is no need to call
+    /// it directly, unless the caller manages the clock.
+    #[inline]
+    pub fn refresh_at(&mut self, now: Instant) -> bool {
+        let due = self.limiter.ready(now);
+        if due {
+            self.refresh(now);
+        }
+        due
+    }
+
+    /// Refreshes the cached snapshot right away, restarting the interval.
+    #[inline]
+    pub fn force_refresh(&mut self) {
+        let now = Instant::now();
+        self.limiter.mark(now);
+        self.refresh(now);
+    }
+
+    /// Gets the cached [`GamepadState`], see [`Gamepad::state`].
+    #[must_use]
+    #[inline]
+    pub fn state(&mut self) -> GamepadState {
+        _ = self.refresh_at(Instant::now());
+        let mut state = self.state;
+        if self.exempt_buttons {
+            state.buttons = self.gamepad.buttons(Button::all());
+        }
+        state
+    }
+
+    /// Gets the specified [`Button`]\(s) pressed, see [`Gamepad::buttons`].
+    #[must_use]
+    #[inline]
+    pub fn buttons(&mut self, buttons: Button) -> Button {
+        if self.exempt_buttons {
+            return self.gamepad.buttons(buttons);
+        }
+        self.state().buttons & buttons
+    }
+
+    /// Checks if all specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::buttons_pressed`].
+    #[must_use]
+    #[inline]
+    pub fn buttons_pressed(&mut self, buttons: Button) -> bool {
+        self.buttons(buttons) == buttons
+    }
+
+    /// Gets the position of the [`Stick`], see [`Gamepad::stick`].
+    #[must_use]
+    #[inline]
+    pub fn stick(&mut self, stick: Stick) -> [f64; 2] {
+        self.state().stick(stick)
+    }
+
+    /// Gets the value of the [`Trigger`], see [`Gamepad::trigger`].
+    #[must_use]
+    #[inline]
+    pub fn trigger(&mut self, trigger: Trigger) -> f64 {
+        self.state().trigger(trigger)
+    }
+
+    /// Gets the cached [`Sensor`] data, see [`Gamepad::sensor`].
+    ///
+    /// The first read of a [`Sensor`] queries it right away, then it is
+    /// refreshed along with the rest of the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the [`Sensor`] is not available or fails to
+    /// read.
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[inline]
+    pub fn sensor(&mut self, sensor: Sensor) -> Result<[f64; 3], Error> {
+        _ = self.refresh_at(Instant::now());
+        if let Some(&data) = self.sensors.get(&sensor) {
+            return Ok(data);
+        }
+        let data = self.gamepad.sensor(sensor)?;
+        _ = self.sensors.insert(sensor, data);
+        Ok(data)
+    }
+
+    /// Refreshes the cached snapshot from the [`Gamepad`].
+    fn refresh(&mut self, now: Instant) {
+        self.state = self.gamepad.state();
+        #[cfg(feature = "sensors")]
+        {
+            let gamepad = &self.gamepad;
+            // failing sensors are queried again on their next read
+            self.sensors.retain(|&sensor, data| {
+                gamepad.sensor(sensor).map(|fresh| *data = fresh).is_ok()
+            });
+        }
+        self.last_refresh = now;
+    }
+}
@@ -0,0 +1,260 @@
+//! Detection of persistently failing battery queries.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Error, Event, GamepadId, PowerLevel, logging::diag};
+
+/// Consecutive failed battery queries after which
+/// [`Event::PowerQueryFailed`] is reported.
+const THRESHOLD: u32 = 3;
+
+/// Time between the battery queries of a [`Gamepad`].
+///
+/// [`Gamepad`]: crate::Gamepad
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Outcome of the latest battery query of a [`Gamepad`], see
+/// [`Gamepad::power`], logging a streak of failures once.
+///
+/// Keeps the last known [`PowerLevel`] across failures, and can be driven
+/// with plain query results, without a controller.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{ErrorCode, PowerCache, prelude::*};
+/// let failed = || -> Result<PowerLevel, Error> {
+///     Err(Error::SdlError {
+///         op: "power",
+///         code: ErrorCode::Sdl,
+///         message: "broken".into(),
+///     })
+/// };
+/// let mut cache = PowerCache::new();
+/// assert_eq!(cache.level(), None);
+///
+/// assert_eq!(
+///     cache.observe(&Ok(PowerLevel::Medium)),
+///     Some(PowerLevel::Medium)
+/// );
+/// assert!(!cache.is_failing());
+///
+/// // failures report nothing, but the last known level is kept
+/// assert_eq!(cache.observe(&Err(failure())), None);
+/// assert_eq!(cache.observe(&Err(failure())), None);
+/// assert!(cache.is_failing());
+/// assert_eq!(cache.level(), Some(PowerLevel::Medium));
+///
+/// // a successful query ends the streak
+/// assert_eq!(cache.observe(&Ok(PowerLevel::Low)), Some(PowerLevel::Low));
+/// assert!(!cache.is_failing());
+/// assert_eq!(cache.level(), Some(PowerLevel::Low));
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::power`]: crate::Gamepad::power
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PowerCache {
+    /// [`PowerLevel`] of the latest successful query, if any.
+    level: Option<PowerLevel>,
+    /// Whether the latest query failed.
+    failing: bool,
+}
+
+impl PowerCache {
+    /// Creates a [`PowerCache`] without any query.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { level: None, failing: false }
+    }
+
+    /// Gets the [`PowerLevel`] of the latest successful query, if any.
+    #[must_use]
+    #[inline]
+    pub const fn level(&self) -> Option<PowerLevel> {
+        self.level
+    }
+
+    /// Checks if the latest query failed.
+    #[must_use]
+    #[inline]
+    pub const fn is_failing(&self) -> bool {
+        self.failing
+    }
+
+    /// Observes the outcome of a battery query, returning its
+    /// [`PowerLevel`] if it succeeded. The first failure of a streak is
+    /// logged.
+    #[inline]
+    pub fn observe(
+        &mut self,
+        result: &Result<PowerLevel, Error>,
+    ) -> Option<PowerLevel> {
+        match result.as_ref() {
+            Ok(&level) => {
+                self.level = Some(level);
+                self.failing = false;
+                Some(level)
+            }
+            Err(err) => {
+                if !self.failing {
+                    diag!(warn, "failed to query gamepad power: {err}");
+                }
+                self.failing = true;
+                None
+            }
+        }
+    }
+}
+
+/// Tracks the outcome of the battery queries of every [`Gamepad`], see
+/// [`Gamepad::try_power`].
+///
+/// Reports [`Event::PowerQueryFailed`] once they keep failing, rather than
+/// leaving a battery meter forever unknown. A failure is reported and
+/// logged once per streak of [`THRESHOLD`] consecutive failures or more:
+/// only a successful query re-arms it.
+///
+/// Used by [`Girl::update`], which only queries a [`Gamepad`] when it is
+/// [`due`], once a second, as the battery changes slowly and every query is
+/// an FFI call.
+///
+/// [`due`]: Self::due
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::try_power`]: crate::Gamepad::try_power
+/// [`Girl::update`]: crate::Girl::update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PowerWatch {
+    /// Consecutive failures per [`GamepadId`].
+    failures: BTreeMap<GamepadId, u32>,
+    /// When each [`GamepadId`] was last due.
+    queried: BTreeMap<GamepadId, Instant>,
+}
+
+impl PowerWatch {
+    /// Checks if the battery of the [`Gamepad`] with the [`GamepadId`] is
+    /// due for a query at `now`, i.e. it was never queried or [`INTERVAL`]
+    /// passed since it last was. If so, the query is recorded as made.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub(crate) fn due(&mut self, id: GamepadId, now: Instant) -> bool {
+        let due = self.queried.get(&id).is_none_or(|&last| {
+            now.saturating_duration_since(last) >= INTERVAL
+        });
+        if due {
+            _ = self.queried.insert(id, now);
+        }
+        due
+    }
+
+    /// Observes the outcome of a battery query of the [`Gamepad`] with the
+    /// [`GamepadId`], returning [`Event::PowerQueryFailed`] once its
+    /// failures reach the threshold.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub(crate) fn observe(
+        &mut self,
+        id: GamepadId,
+        result: &Result<PowerLevel, Error>,
+    ) -> Option<Event> {
+        let Err(err) = result.as_ref() else {
+            _ = self.failures.remove(&id);
+            return None;
+        };
+        let failures = self.failures.entry(id).or_default();
+        *failures = failures.saturating_add(1);
+        if *failures != THRESHOLD {
+            return None;
+        }
+        diag!(warn, "battery queries of gamepad {id} keep failing: {err}");
+        Some(Event::PowerQueryFailed { which: id.raw() })
+    }
+
+    /// Forgets the failures of the [`Gamepad`] with the [`GamepadId`], e.g.
+    /// once it disconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub(crate) fn forget(&mut self, id: GamepadId) {
+        _ = self.failures.remove(&id);
+        _ = self.queried.remove(&id);
+    }
+}
+
+/// Unit tests of [`PowerWatch`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{PowerWatch, THRESHOLD};
+    use crate::{Error, ErrorCode, Event, GamepadId, PowerLevel};
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Gets the error of a failed battery query.
+    const fn failure() -> Error {
+        Error::sdl_static("power", ErrorCode::Sdl, "broken")
+    }
+
+    /// Failures are reported once per streak reaching the threshold.
+    #[test]
+    fn reports_once_per_streak() {
+        let mut watch = PowerWatch::default();
+        let pad = GamepadId::from_raw(3);
+
+        for _ in 1..THRESHOLD {
+            assert!(
+                watch.observe(pad, &Err(failure())).is_none(),
+                "reported early"
+            );
+        }
+        assert!(
+            matches!(
+                watch.observe(pad, &Err(failure())),
+                Some(Event::PowerQueryFailed { which: 3 }),
+            ),
+            "not reported",
+        );
+        assert!(
+            watch.observe(pad, &Err(failure())).is_none(),
+            "reported twice"
+        );
+
+        assert!(watch.observe(pad, &Ok(PowerLevel::Full)).is_none(), "success");
+        for _ in 1..THRESHOLD {
+            assert!(
+                watch.observe(pad, &Err(failure())).is_none(),
+                "reported early"
+            );
+        }
+        assert!(watch.observe(pad, &Err(failure())).is_some(), "not re-armed");
+    }
+
+    /// Queries are due once per interval, and right away once forgotten.
+    #[test]
+    fn rate_limits_queries() {
+        let mut watch = PowerWatch::default();
+        let pad = GamepadId::from_raw(3);
+        let start = Instant::now();
+
+        assert!(watch.due(pad, at(start, 0)), "first query");
+        assert!(!watch.due(pad, at(start, 16)), "within the interval");
+        assert!(!watch.due(pad, at(start, 999)), "within the interval");
+        assert!(watch.due(pad, at(start, 1000)), "after the interval");
+        assert!(!watch.due(pad, at(start, 1100)), "within the interval");
+
+        // a reconnected pad is queried right away
+        watch.forget(pad);
+        assert!(watch.due(pad, at(start, 1200)), "forgotten");
+    }
+}
@@ -0,0 +1,213 @@
+//! Powering off controllers left idle, see [`Girl::set_power_off_idle`].
+//!
+//! [`Girl::set_power_off_idle`]: crate::Girl::set_power_off_idle
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Error, Event, GamepadId, IdleTracker, logging::diag};
+
+/// Powers off [`Gamepad`]s without input for a while, to save their
+/// batteries when they are put down without being turned off.
+///
+/// Reports [`Event::PoweringOffIdle`] [`WARNING`] before a [`Gamepad`] is
+/// powered off, so the application can tell its player. Input in between
+/// cancels the power off. The idle times are the ones of the
+/// [`IdleTracker`], and a power off is requested once per idle period.
+///
+/// Used by [`Girl::tick`] once enabled with [`Girl::set_power_off_idle`].
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`WARNING`]: Self::WARNING
+/// [`Girl::tick`]: crate::Girl::tick
+/// [`Girl::set_power_off_idle`]: crate::Girl::set_power_off_idle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IdlePowerOff {
+    /// Idle time after which [`Gamepad`]s are powered off, [`None`] if they
+    /// aren't.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    after: Option<Duration>,
+    /// Progress of the [`Gamepad`]s warned in their current idle period.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    stages: BTreeMap<GamepadId, Stage>,
+}
+
+/// Progress of powering off an idle [`Gamepad`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// [`Event::PoweringOffIdle`] was reported.
+    Warned,
+    /// The power off was requested.
+    Requested,
+}
+
+impl Default for IdlePowerOff {
+    #[inline]
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl IdlePowerOff {
+    /// How long before the power off [`Event::PoweringOffIdle`] is
+    /// reported.
+    pub(crate) const WARNING: Duration = Duration::from_secs(60);
+
+    /// Creates an [`IdlePowerOff`] powering off [`Gamepad`]s idle for
+    /// `after`, or nothing if [`None`], the default of [`Girl`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl`]: crate::Girl
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(after: Option<Duration>) -> Self {
+        Self { after, stages: BTreeMap::new() }
+    }
+
+    /// Gets the idle time after which [`Gamepad`]s are powered off,
+    /// [`None`] if disabled.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub(crate) const fn after(&self) -> Option<Duration> {
+        self.after
+    }
+
+    /// Checks the idle times of the `pads` able to power off at `now`,
+    /// returning [`Event::PoweringOffIdle`] for the ones due for a warning,
+    /// and requesting a power off with `power_off` for the ones warned
+    /// before whose idle time reached [`after`].
+    ///
+    /// [`after`]: Self::after
+    #[inline]
+    pub(crate) fn poll<I, F>(
+        &mut self,
+        now: Instant,
+        idle: &IdleTracker,
+        pads: I,
+        mut power_off: F,
+    ) -> Vec<Event>
+    where
+        I: IntoIterator<Item = GamepadId>,
+        F: FnMut(GamepadId) -> Result<(), Error>,
+    {
+        let mut events = Vec::new();
+        let Some(after) = self.after else {
+            return events;
+        };
+        let warn_at = after.saturating_sub(Self::WARNING);
+        for id in pads {
+            let Some(idle_time) = idle.idle_time_of(id, now) else {
+                continue;
+            };
+            if idle_time < warn_at {
+                _ = self.stages.remove(&id);
+                continue;
+            }
+            match self.stages.get(&id).copied() {
+                None => {
+                    _ = self.stages.insert(id, Stage::Warned);
+                    events.push(Event::PoweringOffIdle { which: id.raw() });
+                }
+                Some(Stage::Warned) if idle_time >= after => {
+                    _ = self.stages.insert(id, Stage::Requested);
+                    match power_off(id) {
+                        Ok(()) => diag!(info, "powering off idle gamepad {id}"),
+                        Err(err) => diag!(
+                            warn,
+                            "failed to power off idle gamepad {id}: {err}"
+                        ),
+                    }
+                }
+                Some(Stage::Warned | Stage::Requested) => {}
+            }
+        }
+        events
+    }
+
+    /// Forgets the progress of the [`Gamepad`] with the [`GamepadId`], e.g.
+    /// once it disconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub(crate) fn forget(&mut self, id: GamepadId) {
+        _ = self.stages.remove(&id);
+    }
+}
+
+/// Unit tests of [`IdlePowerOff`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::IdlePowerOff;
+    use crate::{Button, Error, Event, GamepadId, IdleConfig, IdleTracker};
+
+    /// Gets the time `secs` seconds after `start`.
+    fn at(start: Instant, secs: u64) -> Instant {
+        start.checked_add(Duration::from_secs(secs)).unwrap_or(start)
+    }
+
+    /// Only capable [`Gamepad`]s are warned a minute before, then powered
+    /// off once per idle period, unless input cancels it.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[test]
+    fn powers_off_idle() {
+        let start = Instant::now();
+        let mut idle = IdleTracker::new(IdleConfig::new(), start);
+        let mut power_off = IdlePowerOff::new(Some(Duration::from_secs(600)));
+        let (pro, pad) = (GamepadId::from_raw(1), GamepadId::from_raw(2));
+        _ = idle.observe(&Event::ControllerDeviceAdded { which: 1 }, start);
+        _ = idle.observe(&Event::ControllerDeviceAdded { which: 2 }, start);
+        assert!(idle.idle_time_of(pad, start).is_some(), "not tracked");
+        let capable = [pro];
+
+        let mut requested = Vec::new();
+        let mut request = |id: GamepadId| -> Result<(), Error> {
+            requested.push(id);
+            Ok(())
+        };
+        let mut poll = |tracker: &IdleTracker, secs| {
+            power_off.poll(at(start, secs), tracker, capable, &mut request)
+        };
+        assert!(poll(&idle, 539).is_empty(), "warned early");
+        let warned = poll(&idle, 540);
+        assert!(
+            matches!(warned.as_slice(), &[Event::PoweringOffIdle { which: 1 }]),
+            "not warned",
+        );
+        assert!(poll(&idle, 599).is_empty(), "warned twice");
+
+        let press = Event::ControllerButtonDown { which: 1, button: Button::A };
+        _ = idle.observe(&press, at(start, 599));
+        assert!(poll(&idle, 600).is_empty(), "warned after input");
+        assert_eq!(poll(&idle, 1139).len(), 1, "not warned again");
+        assert!(poll(&idle, 1199).is_empty(), "warned twice");
+        assert!(poll(&idle, 1200).is_empty(), "warned when powering off");
+        assert!(poll(&idle, 1300).is_empty(), "warned after powering off");
+        assert_eq!(requested, [pro], "not powered off once");
+    }
+
+    /// Nothing is powered off while disabled.
+    #[test]
+    fn disabled_by_default() {
+        let start = Instant::now();
+        let mut idle = IdleTracker::new(IdleConfig::new(), start);
+        _ = idle.observe(&Event::ControllerDeviceAdded { which: 1 }, start);
+        let mut power_off = IdlePowerOff::default();
+
+        assert_eq!(power_off.after(), None, "enabled");
+        let pads = [GamepadId::from_raw(1)];
+        let events = power_off.poll(at(start, 3600), &idle, pads, |_| Ok(()));
+        assert!(events.is_empty(), "warned while disabled");
+    }
+}
@@ -0,0 +1,49 @@
+//! Commonly used types, to get started with a single import.
+//!
+//! # Examples
+//!
+//! ```
+//! use girl::prelude::*;
+//!
+//! # #[cfg(feature = "sdl2")] {
+//! let mut girl = Girl::new()?;
+//! girl.update();
+//! if let Some(gamepad) = girl.gamepads().next()
+//!     && gamepad.buttons_pressed(Button::A)
+//! {
+//!     let [x, y] = gamepad.stick(Stick::Left);
+//!     let accelerate = gamepad.trigger(Trigger::Right);
+//! }
+//! # }
+//! # Ok::<(), Error>(())
+//! ```
+//!
+//! # Stability
+//!
+//! The prelude is additive only: types are added to it in minor releases,
+//! and removed or renamed only in major releases along with the types
+//! themselves. It only contains types, never traits or functions, so a
+//! glob import of it doesn't change method resolution. Feature-gated types
+//! are only part of it with their feature enabled, or the `stubs` feature
+//! for `Sensor`. [`Girl`] and the [`Gamepad`] types need the `sdl2`
+//! feature, `InputProfile` the `std` one.
+//!
+//! [`Girl`]: crate::Girl
+//! [`Gamepad`]: crate::Gamepad
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::InputProfile;
+#[cfg(any(feature = "sensors", feature = "stubs"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+pub use crate::Sensor;
+pub use crate::{
+    Button, ControllerType, Error, Event, FormFactor, GamepadId, GamepadState,
+    PowerLevel, Rgb, Stick, Trigger,
+};
+#[cfg(feature = "sdl2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+pub use crate::{Gamepad, GamepadMut, GamepadRef, Girl};
+#[cfg(any(feature = "touchpad", feature = "stubs"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "touchpad", feature = "stubs"))))]
+pub use crate::{TouchpadAction, TouchpadState};
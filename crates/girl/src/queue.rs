@@ -0,0 +1,205 @@
+//! Bounded [`Event`] queue with drop accounting.
+
+use crate::{Event, SequencedEvent, ring::EventRing};
+
+/// Number of sequenced [`Event`]s [`Girl`] and [`SimGirl`] hold before
+/// dropping the oldest ones.
+///
+/// [`Girl`]: crate::Girl
+/// [`SimGirl`]: crate::SimGirl
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+pub(crate) const EVENT_CAPACITY: usize = 256;
+
+/// Bounded FIFO queue of at most `N` [`SequencedEvent`]s, stored inline in
+/// an [`EventRing`].
+///
+/// When the queue is full, pushing drops the **oldest** [`SequencedEvent`].
+/// Dropped events are counted, and the next [`pop`] after a drop returns an
+/// [`Event::Overflow`] marker carrying the number of events dropped since the
+/// previous marker, with the sequence number of the last dropped event.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{EventQueue, SequencedEvent, prelude::*};
+/// let mut queue = EventQueue::<2>::new();
+/// for seq in 0..5 {
+///     queue.push(SequencedEvent::new(seq, Event::Quit));
+/// }
+/// assert_eq!(queue.dropped(), 3);
+///
+/// let marker = queue.pop().unwrap();
+/// assert!(matches!(marker.event, Event::Overflow { dropped: 3 }));
+/// assert_eq!(marker.seq, 2);
+///
+/// assert_eq!(queue.pop().unwrap().seq, 3);
+/// assert_eq!(queue.pop().unwrap().seq, 4);
+/// assert!(queue.pop().is_none());
+/// ```
+///
+/// [`pop`]: Self::pop
+#[derive(Debug, Clone)]
+pub struct EventQueue<const N: usize> {
+    /// Queued events, oldest first.
+    events: EventRing<N, SequencedEvent>,
+    /// Total number of dropped events.
+    dropped: u64,
+    /// Number of dropped events not yet reported with a marker.
+    unreported: u64,
+    /// Sequence number of the most recently dropped event.
+    last_dropped: u64,
+}
+
+impl<const N: usize> Default for EventQueue<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventQueue<N> {
+    /// Creates an empty [`EventQueue`].
+    ///
+    /// With `N` of zero, every pushed event is dropped.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            events: EventRing::new(),
+            dropped: 0,
+            unreported: 0,
+            last_dropped: 0,
+        }
+    }
+
+    /// Pushes a [`SequencedEvent`], dropping the oldest one if the queue is
+    /// full.
+    #[inline]
+    pub fn push(&mut self, event: SequencedEvent) {
+        if let Some(oldest) = self.events.push(event) {
+            self.record_drop(oldest.seq);
+        }
+    }
+
+    /// Pops the oldest [`SequencedEvent`], or an [`Event::Overflow`] marker if
+    /// events were dropped since the last marker.
+    #[inline]
+    pub fn pop(&mut self) -> Option<SequencedEvent> {
+        if self.unreported > 0 {
+            let dropped = self.unreported;
+            self.unreported = 0;
+            let marker = Event::Overflow { dropped };
+            return Some(SequencedEvent::new(self.last_dropped, marker));
+        }
+        self.events.pop()
+    }
+
+    /// Total number of [`SequencedEvent`]s dropped because the queue was
+    /// full.
+    #[must_use]
+    #[inline]
+    pub const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Number of queued [`SequencedEvent`]s.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Checks whether no [`SequencedEvent`]s are queued.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Maximum number of queued [`SequencedEvent`]s, `N`.
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.events.capacity()
+    }
+
+    /// Accounts for a dropped [`SequencedEvent`].
+    const fn record_drop(&mut self, seq: u64) {
+        self.dropped = self.dropped.saturating_add(1);
+        self.unreported = self.unreported.saturating_add(1);
+        self.last_dropped = seq;
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use super::EventQueue;
+    use crate::{Event, SequencedEvent};
+
+    /// Pops the next [`SequencedEvent`] as its sequence number and the
+    /// number of [`Event`]s reported dropped, if it's a marker.
+    fn pop<const N: usize>(queue: &mut EventQueue<N>) -> Option<(u64, u64)> {
+        queue.pop().map(|popped| {
+            let dropped = if let Event::Overflow { dropped } = popped.event {
+                dropped
+            } else {
+                0
+            };
+            (popped.seq, dropped)
+        })
+    }
+
+    /// Pushes the [`SequencedEvent`]s with the sequence numbers `seqs`.
+    fn push<const N: usize>(
+        queue: &mut EventQueue<N>,
+        seqs: impl IntoIterator<Item = u64>,
+    ) {
+        for seq in seqs {
+            queue.push(SequencedEvent::new(seq, Event::Quit));
+        }
+    }
+
+    /// Events fitting the queue come out in order without markers.
+    #[test]
+    fn keeps_order() {
+        let mut queue = EventQueue::<3>::new();
+        push(&mut queue, 0..3);
+        assert_eq!(queue.len(), 3, "all queued");
+        assert_eq!(pop(&mut queue), Some((0, 0)), "first");
+        push(&mut queue, [3]);
+        for seq in 1..4 {
+            assert_eq!(pop(&mut queue), Some((seq, 0)), "in order");
+        }
+        assert_eq!(pop(&mut queue), None, "drained");
+        assert_eq!(queue.dropped(), 0, "nothing dropped");
+    }
+
+    /// A marker precedes the events left after each overflow, reporting
+    /// only the drops since the previous one.
+    #[test]
+    fn reports_overflow() {
+        let mut queue = EventQueue::<2>::new();
+        push(&mut queue, 0..5);
+        assert_eq!(pop(&mut queue), Some((2, 3)), "marker of the last drop");
+        assert_eq!(pop(&mut queue), Some((3, 0)), "oldest kept");
+
+        push(&mut queue, 5..7);
+        assert_eq!(pop(&mut queue), Some((4, 1)), "only the new drop");
+        assert_eq!(pop(&mut queue), Some((5, 0)), "oldest kept");
+        assert_eq!(pop(&mut queue), Some((6, 0)), "newest kept");
+        assert_eq!(pop(&mut queue), None, "drained");
+        assert_eq!(queue.dropped(), 4, "total kept counting");
+    }
+
+    /// A queue of zero events drops everything, still reporting it.
+    #[test]
+    fn drops_everything_without_capacity() {
+        let mut queue = EventQueue::<0>::new();
+        push(&mut queue, 0..3);
+        assert!(queue.is_empty(), "nothing queued");
+        assert_eq!(queue.capacity(), 0, "capacity is N");
+        assert_eq!(pop(&mut queue), Some((2, 3)), "marker");
+        assert_eq!(pop(&mut queue), None, "drained");
+    }
+}
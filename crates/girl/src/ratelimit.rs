@@ -0,0 +1,117 @@
+//! Limiting how often something happens.
+
+use core::time::Duration;
+use std::time::Instant;
+
+/// Limits how often something happens: at most once per [`interval`].
+///
+/// Used by [`PolledGamepad`] and [`LedBindings`].
+///
+/// [`interval`]: Self::interval
+/// [`PolledGamepad`]: crate::PolledGamepad
+/// [`LedBindings`]: crate::LedBindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct RateLimiter {
+    /// Minimal time between two ready calls.
+    interval: Duration,
+    /// When it was last ready.
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a new [`RateLimiter`] ready at most once per `interval`.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(interval: Duration) -> Self {
+        Self { interval, last: None }
+    }
+
+    /// Gets the minimal time between two ready calls.
+    #[cfg(any(feature = "sdl2", test))]
+    #[must_use]
+    #[inline]
+    pub(crate) const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Gets when it was last ready, if ever.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn last(&self) -> Option<Instant> {
+        self.last
+    }
+
+    /// Checks if the [`interval`] passed since it was last ready, and if so,
+    /// marks it ready at `now`.
+    ///
+    /// [`interval`]: Self::interval
+    #[inline]
+    pub(crate) fn ready(&mut self, now: Instant) -> bool {
+        let ready = self.last.is_none_or(|last| {
+            now.saturating_duration_since(last) >= self.interval
+        });
+        if ready {
+            self.last = Some(now);
+        }
+        ready
+    }
+
+    /// Marks it ready at `now` regardless of the [`interval`].
+    ///
+    /// [`interval`]: Self::interval
+    #[inline]
+    pub(crate) const fn mark(&mut self, now: Instant) {
+        self.last = Some(now);
+    }
+}
+
+/// Unit tests of [`RateLimiter`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::RateLimiter;
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Ready at most once per interval, late calls restarting it.
+    #[test]
+    fn limits_rate() {
+        let mut limiter = RateLimiter::new(Duration::from_millis(10));
+        let start = Instant::now();
+
+        assert_eq!(limiter.interval(), Duration::from_millis(10), "interval");
+        assert!(limiter.ready(at(start, 0)), "first call");
+        assert!(!limiter.ready(at(start, 5)), "within the interval");
+        assert!(limiter.ready(at(start, 10)), "after the interval");
+        assert!(limiter.ready(at(start, 25)), "late call");
+        assert!(!limiter.ready(at(start, 30)), "within the restarted interval");
+        assert_eq!(limiter.last(), Some(at(start, 25)), "last ready");
+    }
+
+    /// Marking restarts the interval without checking it.
+    #[test]
+    fn marks() {
+        let mut limiter = RateLimiter::new(Duration::from_millis(10));
+        let start = Instant::now();
+
+        limiter.mark(at(start, 0));
+        assert!(!limiter.ready(at(start, 5)), "ready after marking");
+        assert!(limiter.ready(at(start, 10)), "after the interval");
+    }
+
+    /// An unbounded interval is never ready again.
+    #[test]
+    fn never_ready_again() {
+        let mut never = RateLimiter::new(Duration::MAX);
+        let start = Instant::now();
+
+        assert!(never.ready(at(start, 0)), "first call");
+        assert!(!never.ready(at(start, 1_000_000)), "ready again");
+    }
+}
@@ -0,0 +1,75 @@
+//! Outcome of recovering from a controller subsystem reset.
+
+use crate::{GamepadId, Guid};
+
+/// Outcome of [`Girl::reinitialize`], [`Girl::reopen_gamepads`] and
+/// [`SimGirl::reinitialize`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{RecoveryReport, prelude::*};
+/// let mut report = RecoveryReport::default();
+/// assert!(report.is_complete());
+///
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// report.lost.push((GamepadId::from_raw(3), guid));
+/// assert!(!report.is_complete());
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl::reinitialize`]: crate::Girl::reinitialize
+/// [`Girl::reopen_gamepads`]: crate::Girl::reopen_gamepads
+/// [`SimGirl::reinitialize`]: crate::SimGirl::reinitialize
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Re-opened [`Gamepad`]s, with their previous and new [`GamepadId`]s.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pub recovered: Vec<(GamepadId, GamepadId)>,
+    /// [`Gamepad`]s that could not be re-opened, with their previous
+    /// [`GamepadId`]s and their [`Guid`]s.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pub lost: Vec<(GamepadId, Guid)>,
+}
+
+impl RecoveryReport {
+    /// Checks if every [`Gamepad`] was recovered.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub const fn is_complete(&self) -> bool {
+        self.lost.is_empty()
+    }
+
+    /// Pairs each `previous` device with the first `reopened` one of the
+    /// same [`Guid`], calling `restore` with the new [`GamepadId`] and what
+    /// was saved of it, and reports those left without one as lost.
+    ///
+    /// Identical controllers are paired in order, so they keep their order.
+    #[cfg(any(feature = "sdl2", feature = "sim"))]
+    #[cfg_attr(
+        not(all(feature = "sdl2", feature = "sim")),
+        expect(clippy::single_call_fn, reason = "shared by the backends")
+    )]
+    pub(crate) fn pair<T>(
+        previous: impl IntoIterator<Item = (GamepadId, Guid, T)>,
+        mut reopened: Vec<(GamepadId, Guid)>,
+        mut restore: impl FnMut(GamepadId, T),
+    ) -> Self {
+        let mut report = Self::default();
+        for (id, guid, saved) in previous {
+            let same = reopened.iter().position(|&(_, new)| new == guid);
+            let Some((new, _)) = same.map(|at| reopened.remove(at)) else {
+                report.lost.push((id, guid));
+                continue;
+            };
+            restore(new, saved);
+            report.recovered.push((id, new));
+        }
+        report
+    }
+}
@@ -0,0 +1,363 @@
+//! Rejection of accidental touches, e.g. of a thumb resting on a corner of
+//! the touchpad while gripping the controller.
+
+use alloc::collections::BTreeMap;
+
+use crate::{Error, Gamepad, TouchpadAction, TouchpadEvent, TouchpadState};
+
+/// Touch rejection of [`Gamepad`] touchpads.
+#[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(
+    clippy::multiple_inherent_impl,
+    reason = "feature gated and documented"
+)]
+impl Gamepad {
+    /// Sets the [`TouchRejection`] of the given `touchpad`, or disables it
+    /// if it has no regions (the default).
+    ///
+    /// Touches beginning inside a rejection region are withheld until they
+    /// travel [`TouchRejection::min_travel`], then reported as
+    /// [`TouchpadAction::Touched`] at their current position, see
+    /// [`TouchRejector`]. Applies to [`touchpad`] as well as to the
+    /// touchpad [`Event`]s of [`Girl`], and is dropped on disconnect.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `touchpad` is not less than
+    /// [`num_touchpads`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::TouchRejection;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// if gamepad.has_touchpads() {
+    ///     // ignore thumbs resting on the bottom corners
+    ///     gamepad.set_touchpad_rejection(0, TouchRejection::new())?;
+    ///     assert!(gamepad.touchpad_rejection(0).is_some());
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`touchpad`]: Self::touchpad
+    /// [`num_touchpads`]: Self::num_touchpads
+    /// [`Event`]: crate::Event
+    /// [`Girl`]: crate::Girl
+    #[inline]
+    pub fn set_touchpad_rejection(
+        &mut self,
+        touchpad: usize,
+        rejection: TouchRejection,
+    ) -> Result<(), Error> {
+        if touchpad >= self.num_touchpads() {
+            return Err(Error::InvalidArgument { what: "touchpad" });
+        }
+        let mut rejections = self.shared().touch_rejection();
+        if rejection.regions.is_empty() {
+            drop(rejections.remove(&(self.id(), touchpad)));
+        } else {
+            drop(rejections.insert((self.id(), touchpad), rejection));
+        }
+        drop(rejections);
+        Ok(())
+    }
+
+    /// Gets the [`TouchRejection`] of the given `touchpad`, see
+    /// [`set_touchpad_rejection`].
+    ///
+    /// [`set_touchpad_rejection`]: Self::set_touchpad_rejection
+    #[must_use]
+    #[inline]
+    pub fn touchpad_rejection(
+        &self,
+        touchpad: usize,
+    ) -> Option<TouchRejection> {
+        self.shared().touch_rejection().get(&(self.id(), touchpad)).cloned()
+    }
+}
+
+/// Rectangle on a touchpad in normalized coordinates, from `0.0` to `1.0`
+/// from the top left corner.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::TouchRect;
+/// let corner = TouchRect::new([0.0, 0.7], [0.15, 1.0]);
+/// assert!(corner.contains([0.1, 0.9]));
+/// assert!(!corner.contains([0.5, 0.9]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TouchRect {
+    /// Top left corner `[x, y]`.
+    pub min: [f32; 2],
+    /// Bottom right corner `[x, y]`.
+    pub max: [f32; 2],
+}
+
+impl TouchRect {
+    /// Creates a new [`TouchRect`] from its top left and bottom right
+    /// corners.
+    #[must_use]
+    #[inline]
+    pub const fn new(min: [f32; 2], max: [f32; 2]) -> Self {
+        Self { min, max }
+    }
+
+    /// Checks if the normalized `position` is inside, edges included.
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, position: [f32; 2]) -> bool {
+        let [x, y] = position;
+        let ([min_x, min_y], [max_x, max_y]) = (self.min, self.max);
+        min_x <= x && x <= max_x && min_y <= y && y <= max_y
+    }
+}
+
+/// Regions of a touchpad where touches are likely accidental, see
+/// [`Gamepad::set_touchpad_rejection`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchRect, TouchRejection};
+/// let mut rejection = TouchRejection::new();
+/// assert!(rejection.rejects([0.05, 0.95]));
+/// assert!(!rejection.rejects([0.5, 0.5]));
+///
+/// // reject the whole top edge as well
+/// rejection.regions.push(TouchRect::new([0.0, 0.0], [1.0, 0.1]));
+/// assert!(rejection.rejects([0.5, 0.05]));
+/// assert!(!TouchRejection::disabled().rejects([0.05, 0.95]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TouchRejection {
+    /// Regions where touches beginning inside are withheld.
+    pub regions: Vec<TouchRect>,
+    /// Normalized distance a withheld touch must travel from where it began
+    /// to be reported.
+    pub min_travel: f32,
+}
+
+impl Default for TouchRejection {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TouchRejection {
+    /// Bottom left corner of the touchpad, where the left thumb rests.
+    pub const BOTTOM_LEFT: TouchRect = TouchRect::new([0.0, 0.7], [0.15, 1.0]);
+    /// Bottom right corner of the touchpad, where the right thumb rests.
+    pub const BOTTOM_RIGHT: TouchRect = TouchRect::new([0.85, 0.7], [1.0, 1.0]);
+
+    /// Creates a [`TouchRejection`] of the [`BOTTOM_LEFT`] and
+    /// [`BOTTOM_RIGHT`] corners, with a minimum travel of `0.1`.
+    ///
+    /// [`BOTTOM_LEFT`]: Self::BOTTOM_LEFT
+    /// [`BOTTOM_RIGHT`]: Self::BOTTOM_RIGHT
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            regions: vec![Self::BOTTOM_LEFT, Self::BOTTOM_RIGHT],
+            min_travel: 0.1,
+        }
+    }
+
+    /// Creates a [`TouchRejection`] rejecting nothing.
+    #[must_use]
+    #[inline]
+    pub const fn disabled() -> Self {
+        Self { regions: Vec::new(), min_travel: 0.0 }
+    }
+
+    /// Checks if a touch beginning at the normalized `position` is withheld.
+    #[must_use]
+    #[inline]
+    pub fn rejects(&self, position: [f32; 2]) -> bool {
+        self.regions.iter().any(|region| region.contains(position))
+    }
+}
+
+/// What a [`TouchRejector`] does with a finger sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// Reported as is.
+    Pass,
+    /// Not reported.
+    Withhold,
+    /// Reported as a new touch, as it travelled far enough.
+    Accept,
+}
+
+/// Withholds the touches beginning inside the regions of a
+/// [`TouchRejection`] until they travel far enough, per touchpad and
+/// finger.
+///
+/// A withheld touch travelling [`TouchRejection::min_travel`] is reported
+/// as [`TouchpadAction::Touched`] at its current position, followed by the
+/// usual [`TouchpadAction::Moved`] ones. A withheld touch ending without
+/// travelling that far is never reported, not even its release.
+///
+/// [`Girl`] keeps one per [`Gamepad`] for its touchpad [`Event`]s, and
+/// [`Gamepad::touchpad`] applies one before its cached finger states, so
+/// they stay consistent with what was reported.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{TouchRejection, TouchRejector, TouchpadAction as TA};
+/// # use girl::TouchpadEvent;
+/// let event = |x, y, act| TouchpadEvent::new(0, 0, 0, [x, y], 1.0, act);
+/// let rejection = TouchRejection::new();
+/// let mut rejector = TouchRejector::new();
+/// let mut replay = |events: &[TouchpadEvent]| -> Vec<_> {
+///     events
+///         .iter()
+///         .filter_map(|event| rejector.filter(&rejection, event))
+///         .map(|event| (event.position, event.action))
+///         .collect()
+/// };
+///
+/// // a thumb resting on the bottom left corner
+/// let rest = [
+///     event(0.05, 0.9, TA::Touched),
+///     event(0.06, 0.91, TA::Moved),
+///     event(0.05, 0.92, TA::Moved),
+///     event(0.05, 0.92, TA::Released),
+/// ];
+/// assert!(replay(&rest).is_empty());
+///
+/// // an intentional swipe starting there
+/// let swipe = [
+///     event(0.05, 0.9, TA::Touched),
+///     event(0.1, 0.85, TA::Moved),
+///     event(0.2, 0.8, TA::Moved),
+///     event(0.4, 0.7, TA::Moved),
+///     event(0.4, 0.7, TA::Released),
+/// ];
+/// assert_eq!(replay(&swipe), [
+///     ([0.2, 0.8], TA::Touched),
+///     ([0.4, 0.7], TA::Moved),
+///     ([0.4, 0.7], TA::Released),
+/// ]);
+///
+/// // touches beginning elsewhere are untouched
+/// let tap = [event(0.5, 0.5, TA::Touched), event(0.5, 0.5, TA::Released)];
+/// assert_eq!(replay(&tap), [
+///     ([0.5, 0.5], TA::Touched),
+///     ([0.5, 0.5], TA::Released),
+/// ]);
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Event`]: crate::Event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchRejector {
+    /// Positions where the withheld touches began, by touchpad and finger
+    /// index.
+    withheld: BTreeMap<(usize, usize), [f32; 2]>,
+}
+
+impl TouchRejector {
+    /// Creates a [`TouchRejector`] withholding nothing yet.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { withheld: BTreeMap::new() }
+    }
+
+    /// Checks if any touch is withheld.
+    #[must_use]
+    #[inline]
+    pub fn is_withholding(&self) -> bool {
+        !self.withheld.is_empty()
+    }
+
+    /// Filters a [`TouchpadEvent`] by the `rejection`, returning the event
+    /// to report, if any.
+    #[must_use]
+    #[inline]
+    pub fn filter(
+        &mut self,
+        rejection: &TouchRejection,
+        event: &TouchpadEvent,
+    ) -> Option<TouchpadEvent> {
+        let key = (
+            usize::try_from(event.idx).unwrap_or(usize::MAX),
+            usize::try_from(event.finger).unwrap_or(usize::MAX),
+        );
+        let touching = event.action != TouchpadAction::Released;
+        let began = event.action == TouchpadAction::Touched;
+        match self.admit(rejection, key, event.position, touching, began) {
+            Admission::Pass => Some(*event),
+            Admission::Withhold => None,
+            Admission::Accept => Some(TouchpadEvent {
+                action: TouchpadAction::Touched,
+                ..*event
+            }),
+        }
+    }
+
+    /// Checks if the `sample` of a finger is taken into its `cached` state,
+    /// see [`TouchpadState::update`].
+    ///
+    /// A touch begins when the `cached` finger isn't touching, so an
+    /// accepted touch is reported as [`TouchpadAction::Touched`] by the
+    /// update.
+    pub(crate) fn admit_state(
+        &mut self,
+        rejection: &TouchRejection,
+        cached: &TouchpadState,
+        sample: &TouchpadState,
+    ) -> bool {
+        let key = (sample.touchpad, sample.finger);
+        let touching = sample.action != TouchpadAction::Released;
+        let began = touching && cached.action == TouchpadAction::Released;
+        let admission =
+            self.admit(rejection, key, sample.position, touching, began);
+        admission != Admission::Withhold
+    }
+
+    /// Decides about a finger sample at `position`, `touching` the
+    /// touchpad and having just `began` to.
+    fn admit(
+        &mut self,
+        rejection: &TouchRejection,
+        key: (usize, usize),
+        position: [f32; 2],
+        touching: bool,
+        began: bool,
+    ) -> Admission {
+        if let Some(&[start_x, start_y]) = self.withheld.get(&key) {
+            let [x, y] = position;
+            let travel = (x - start_x).hypot(y - start_y);
+            if touching && travel < rejection.min_travel {
+                return Admission::Withhold;
+            }
+            _ = self.withheld.remove(&key);
+            return if touching {
+                Admission::Accept
+            } else {
+                Admission::Withhold
+            };
+        }
+        if began && rejection.rejects(position) {
+            _ = self.withheld.insert(key, position);
+            return Admission::Withhold;
+        }
+        Admission::Pass
+    }
+}
@@ -0,0 +1,222 @@
+//! What changed in the SDL2 mapping of a remapped [`Gamepad`].
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+#[cfg(feature = "std")]
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "std")]
+use crate::InputBind;
+use crate::{Button, ControllerType, Stick, Trigger};
+#[cfg(feature = "sdl2")]
+use crate::{Error, Gamepad};
+
+/// How SDL2 maps the inputs of a [`Gamepad`], compared before and after it
+/// is remapped to report [`Event::ControllerRemapDiff`], see
+/// [`Girl::mapping_snapshot`].
+///
+/// [`Event::ControllerRemapDiff`]: crate::Event::ControllerRemapDiff
+/// [`Girl::mapping_snapshot`]: crate::Girl::mapping_snapshot
+#[cfg(feature = "std")]
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MappingSnapshot {
+    /// Type of the controller.
+    pub controller_type: ControllerType,
+    /// [`InputBind`] of every bound [`Button`], see
+    /// [`Gamepad::bind_for_button`].
+    pub buttons: BTreeMap<Button, InputBind>,
+    /// [`InputBind`]s of the `x` and `y` axes of the left and right
+    /// [`Stick`]s, see [`Gamepad::bind_for_stick`].
+    pub sticks: [[InputBind; 2]; 2],
+    /// [`InputBind`]s of the left and right [`Trigger`]s, see
+    /// [`Gamepad::bind_for_trigger`].
+    pub triggers: [InputBind; 2],
+    /// Number of touchpads.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    pub touchpads: usize,
+}
+
+#[cfg(feature = "std")]
+impl MappingSnapshot {
+    /// Creates a [`MappingSnapshot`] of a controller of the
+    /// [`ControllerType`] without any input bound.
+    #[must_use]
+    #[inline]
+    pub const fn new(controller_type: ControllerType) -> Self {
+        Self {
+            controller_type,
+            buttons: BTreeMap::new(),
+            sticks: [[InputBind::None; 2]; 2],
+            triggers: [InputBind::None; 2],
+            #[cfg(feature = "touchpad")]
+            touchpads: 0,
+        }
+    }
+
+    /// Takes a [`MappingSnapshot`] of the current SDL2 mapping of the
+    /// [`Gamepad`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the controller is no longer valid.
+    #[cfg(feature = "sdl2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sdl2")))]
+    #[inline]
+    pub fn of(gamepad: &Gamepad) -> Result<Self, Error> {
+        let mut snapshot = Self::new(gamepad.controller_type());
+        for button in Button::all().iter() {
+            let bind = gamepad.bind_for_button(button)?;
+            if bind != InputBind::None {
+                _ = snapshot.buttons.insert(button, bind);
+            }
+        }
+        snapshot.sticks = [
+            gamepad.bind_for_stick(Stick::Left)?,
+            gamepad.bind_for_stick(Stick::Right)?,
+        ];
+        snapshot.triggers = [
+            gamepad.bind_for_trigger(Trigger::Left)?,
+            gamepad.bind_for_trigger(Trigger::Right)?,
+        ];
+        #[cfg(feature = "touchpad")]
+        {
+            snapshot.touchpads = gamepad.num_touchpads();
+        }
+        Ok(snapshot)
+    }
+
+    /// Gets every bound [`Button`].
+    #[must_use]
+    #[inline]
+    pub fn bound_buttons(&self) -> Button {
+        self.buttons.keys().fold(Button::empty(), |all, &button| all | button)
+    }
+
+    /// Gets what changed from this [`MappingSnapshot`] to the `new` one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{InputBind, MappingSnapshot, prelude::*};
+    /// let mut before = MappingSnapshot::new(ControllerType::Xbox360);
+    /// _ = before.buttons.insert(Button::A, InputBind::Button(0));
+    /// _ = before.buttons.insert(Button::B, InputBind::Button(1));
+    /// _ = before.buttons.insert(Button::X, InputBind::Button(2));
+    ///
+    /// // B and X are swapped, Y is added and A is removed
+    /// let mut after = before.clone();
+    /// _ = after.buttons.remove(&Button::A);
+    /// _ = after.buttons.insert(Button::B, InputBind::Button(2));
+    /// _ = after.buttons.insert(Button::X, InputBind::Button(1));
+    /// _ = after.buttons.insert(Button::Y, InputBind::Button(3));
+    /// let lefty = InputBind::Axis { index: 1, inverted: true };
+    /// after.sticks[0][1] = lefty;
+    ///
+    /// let changes = before.diff(&after);
+    /// assert_eq!(changes.added, Button::Y);
+    /// assert_eq!(changes.removed, Button::A);
+    /// assert_eq!(changes.rebound, Button::B | Button::X);
+    /// assert!(changes.stick_rebound(Stick::Left));
+    /// assert!(!changes.stick_rebound(Stick::Right));
+    /// assert!(!changes.trigger_rebound(Trigger::Left));
+    /// assert_eq!(changes.controller_type, None);
+    ///
+    /// assert!(before.diff(&before).is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn diff(&self, new: &Self) -> RemapChanges {
+        let (before, after) = (self.bound_buttons(), new.bound_buttons());
+        let rebound = self
+            .buttons
+            .iter()
+            .filter(|&(button, bind)| {
+                new.buttons.get(button).is_some_and(|new_bind| new_bind != bind)
+            })
+            .fold(Button::empty(), |all, (&button, _)| all | button);
+        RemapChanges {
+            added: after.difference(before),
+            removed: before.difference(after),
+            rebound,
+            rebound_sticks: [
+                self.sticks[0] != new.sticks[0],
+                self.sticks[1] != new.sticks[1],
+            ],
+            rebound_triggers: [
+                self.triggers[0] != new.triggers[0],
+                self.triggers[1] != new.triggers[1],
+            ],
+            controller_type: (self.controller_type != new.controller_type)
+                .then_some((self.controller_type, new.controller_type)),
+            #[cfg(feature = "touchpad")]
+            touchpads: (self.touchpads != new.touchpads)
+                .then_some((self.touchpads, new.touchpads)),
+        }
+    }
+}
+
+/// What changed in the SDL2 mapping of a [`Gamepad`], reported by
+/// [`Event::ControllerRemapDiff`], see [`MappingSnapshot::diff`].
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Event::ControllerRemapDiff`]: crate::Event::ControllerRemapDiff
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemapChanges {
+    /// [`Button`]s bound now, but not before.
+    pub added: Button,
+    /// [`Button`]s bound before, but not now.
+    pub removed: Button,
+    /// [`Button`]s bound to another physical input now.
+    pub rebound: Button,
+    /// Whether an axis of the left and right [`Stick`]s is bound to another
+    /// physical input now, see [`stick_rebound`].
+    ///
+    /// [`stick_rebound`]: Self::stick_rebound
+    pub rebound_sticks: [bool; 2],
+    /// Whether the left and right [`Trigger`]s are bound to another
+    /// physical input now, see [`trigger_rebound`].
+    ///
+    /// [`trigger_rebound`]: Self::trigger_rebound
+    pub rebound_triggers: [bool; 2],
+    /// Previous and new [`ControllerType`], if it changed.
+    pub controller_type: Option<(ControllerType, ControllerType)>,
+    /// Previous and new number of touchpads, if it changed.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    pub touchpads: Option<(usize, usize)>,
+}
+
+impl RemapChanges {
+    /// Checks if nothing changed.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks if an axis of the [`Stick`] is bound to another physical
+    /// input now.
+    #[must_use]
+    #[inline]
+    pub const fn stick_rebound(&self, stick: Stick) -> bool {
+        match stick {
+            Stick::Left => self.rebound_sticks[0],
+            Stick::Right => self.rebound_sticks[1],
+        }
+    }
+
+    /// Checks if the [`Trigger`] is bound to another physical input now.
+    #[must_use]
+    #[inline]
+    pub const fn trigger_rebound(&self, trigger: Trigger) -> bool {
+        match trigger {
+            Trigger::Left => self.rebound_triggers[0],
+            Trigger::Right => self.rebound_triggers[1],
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Retrying sensor enables failing while a controller initializes.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Error, Event, GamepadId, Sensor, logging::diag};
+
+/// Retries of failed [`Gamepad::enable_sensor`] calls, e.g. on Bluetooth
+/// `DualSense` controllers still finishing their handshake right after
+/// [`Event::ControllerDeviceAdded`].
+///
+/// Every queued enable is retried every interval until it succeeds,
+/// reporting [`Event::SensorEnabled`], or until the window passes,
+/// reporting [`Event::SensorEnableFailed`].
+///
+/// Used by [`Girl::update`] once enabled with
+/// [`Girl::set_sensor_retry_window`].
+///
+/// [`Gamepad::enable_sensor`]: crate::Gamepad::enable_sensor
+/// [`Girl::update`]: crate::Girl::update
+/// [`Girl::set_sensor_retry_window`]: crate::Girl::set_sensor_retry_window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SensorRetry {
+    /// How long enables are retried, [`None`] if they aren't.
+    window: Option<Duration>,
+    /// Time between the attempts.
+    interval: Duration,
+    /// Expiry and next attempt of the queued enables.
+    pending: BTreeMap<(GamepadId, Sensor), (Instant, Instant)>,
+}
+
+impl Default for SensorRetry {
+    #[inline]
+    fn default() -> Self {
+        Self::new(None, Self::DEFAULT_INTERVAL)
+    }
+}
+
+impl SensorRetry {
+    /// Time between the attempts used by [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    pub(crate) const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Creates a [`SensorRetry`] retrying every `interval` for `window`, or
+    /// ignoring everything queued if [`None`], the default of [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(
+        window: Option<Duration>,
+        interval: Duration,
+    ) -> Self {
+        Self { window, interval, pending: BTreeMap::new() }
+    }
+
+    /// Gets how long enables are retried, [`None`] if disabled.
+    #[must_use]
+    #[inline]
+    pub(crate) const fn window(&self) -> Option<Duration> {
+        self.window
+    }
+
+    /// Checks if no enables are queued.
+    #[must_use]
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Queues an enable of the [`Sensor`] which failed at `now`. Queueing it
+    /// again while queued keeps the original window.
+    #[inline]
+    pub(crate) fn queue(
+        &mut self,
+        id: GamepadId,
+        sensor: Sensor,
+        now: Instant,
+    ) {
+        let Some(window) = self.window else {
+            return;
+        };
+        let expires = now.checked_add(window).unwrap_or(now);
+        let next = now.checked_add(self.interval).unwrap_or(now);
+        _ = self.pending.entry((id, sensor)).or_insert((expires, next));
+    }
+
+    /// Retries the queued enables due at `now` with `enable`, returning
+    /// [`Event::SensorEnabled`] for the successful ones and
+    /// [`Event::SensorEnableFailed`] for the ones whose window passed.
+    #[inline]
+    pub(crate) fn retry<F>(&mut self, now: Instant, mut enable: F) -> Vec<Event>
+    where
+        F: FnMut(GamepadId, Sensor) -> Result<(), Error>,
+    {
+        let interval = self.interval;
+        let mut events = Vec::new();
+        self.pending.retain(|&(id, sensor), times| {
+            let (expires, next) = (times.0, &mut times.1);
+            if now < *next {
+                return true;
+            }
+            let which = id.raw();
+            match enable(id, sensor) {
+                Ok(()) => {
+                    diag!(debug, "enabled {sensor:?} of gamepad {id} on retry");
+                    events.push(Event::SensorEnabled { which, sensor });
+                    false
+                }
+                Err(err) if now >= expires => {
+                    diag!(
+                        warn,
+                        "failed to enable {sensor:?} of gamepad {id}: {err}"
+                    );
+                    events.push(Event::SensorEnableFailed { which, sensor });
+                    false
+                }
+                Err(_) => {
+                    *next = now.checked_add(interval).unwrap_or(now);
+                    true
+                }
+            }
+        });
+        events
+    }
+
+    /// Forgets the queued enables of the [`Gamepad`] with the [`GamepadId`],
+    /// e.g. once it disconnects.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub(crate) fn forget(&mut self, id: GamepadId) {
+        self.pending.retain(|&(of, _), _| of != id);
+    }
+}
+
+/// Unit tests of [`SensorRetry`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::SensorRetry;
+    use crate::{Error, ErrorCode, Event, GamepadId, Sensor};
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Gets the error of a failed sensor enable.
+    const fn failure() -> Error {
+        Error::sdl_static("enable_sensor", ErrorCode::Sdl, "busy")
+    }
+
+    /// Creates a [`SensorRetry`] retrying every 100 ms for a second.
+    const fn retrying() -> SensorRetry {
+        SensorRetry::new(
+            Some(Duration::from_secs(1)),
+            Duration::from_millis(100),
+        )
+    }
+
+    /// Enables are retried every interval until they succeed.
+    #[test]
+    fn retries_until_enabled() {
+        let mut retry = retrying();
+        let start = Instant::now();
+        let pad = GamepadId::from_raw(3);
+        retry.queue(pad, Sensor::Gyroscope, at(start, 0));
+
+        let mut attempts = 0u32;
+        let mut enable = |_: GamepadId, _: Sensor| {
+            attempts = attempts.saturating_add(1);
+            if attempts <= 2 { Err(failure()) } else { Ok(()) }
+        };
+        assert!(retry.retry(at(start, 100), &mut enable).is_empty(), "first");
+        assert!(retry.retry(at(start, 150), &mut enable).is_empty(), "early");
+        assert!(retry.retry(at(start, 200), &mut enable).is_empty(), "second");
+        let enabled = retry.retry(at(start, 300), &mut enable);
+        assert!(
+            matches!(enabled.as_slice(), &[Event::SensorEnabled {
+                which: 3,
+                sensor: Sensor::Gyroscope,
+            }]),
+            "not enabled",
+        );
+        assert_eq!(attempts, 3, "attempts");
+        assert!(retry.is_empty(), "still queued");
+    }
+
+    /// Enables are given up once the window passes.
+    #[test]
+    fn gives_up_after_window() {
+        let mut retry = retrying();
+        let start = Instant::now();
+        let pad = GamepadId::from_raw(3);
+        retry.queue(pad, Sensor::Accelerometer, at(start, 0));
+
+        let broken = |_: GamepadId, _: Sensor| Err(failure());
+        assert!(retry.retry(at(start, 900), broken).is_empty(), "given up");
+        let given_up = retry.retry(at(start, 1000), broken);
+        assert!(
+            matches!(given_up.as_slice(), &[Event::SensorEnableFailed {
+                which: 3,
+                ..
+            }]),
+            "not given up",
+        );
+        assert!(retry.is_empty(), "still queued");
+    }
+
+    /// Nothing is queued while disabled or once forgotten.
+    #[test]
+    fn forgets() {
+        let start = Instant::now();
+        let pad = GamepadId::from_raw(3);
+        let mut disabled = SensorRetry::default();
+        disabled.queue(pad, Sensor::Gyroscope, at(start, 0));
+        assert!(disabled.is_empty(), "queued while disabled");
+
+        let mut retry = retrying();
+        retry.queue(pad, Sensor::Gyroscope, at(start, 0));
+        retry.forget(pad);
+        assert!(retry.is_empty(), "still queued");
+    }
+}
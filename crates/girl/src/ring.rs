@@ -0,0 +1,287 @@
+//! Fixed-size [`Event`] buffer without allocations, e.g. for consumers of
+//! `girl::compat` bounding their own queues.
+//!
+//! [`Event`]: crate::Event
+
+use core::fmt;
+
+use crate::Event;
+
+/// Ring buffer of at most `N` [`Event`]s, stored inline.
+///
+/// When the buffer is full, [`push`] evicts and returns the **oldest**
+/// [`Event`], the drop-oldest policy of [`EventQueue`], which is backed by
+/// an [`EventRing`] of [`SequencedEvent`]s. With `N` of zero, every pushed
+/// [`Event`] is returned right away.
+///
+/// Any [`Copy`] item can be stored instead of [`Event`]s, so nothing is
+/// ever dropped in place.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{prelude::*, ring::EventRing};
+/// let press =
+///     |which| Event::ControllerButtonDown { which, button: Button::A };
+/// let which = |event: Option<Event>| event.and_then(|event| event.which());
+/// let mut ring = EventRing::<3>::new();
+/// assert!(ring.is_empty());
+///
+/// for id in 0..3 {
+///     assert!(ring.push(press(id)).is_none());
+/// }
+/// assert!(ring.is_full());
+///
+/// // overflow evicts the oldest
+/// assert_eq!(which(ring.push(press(3))), Some(0));
+/// assert_eq!(which(ring.push(press(4))), Some(1));
+/// assert_eq!(ring.len(), 3);
+///
+/// // iterated and popped oldest first, across the wraparound
+/// let ids: Vec<_> = ring.iter().filter_map(Event::which).collect();
+/// assert_eq!(ids, [2, 3, 4]);
+/// assert_eq!(which(ring.pop()), Some(2));
+/// assert!(ring.push(press(5)).is_none());
+/// let ids: Vec<_> = ring.iter().filter_map(Event::which).collect();
+/// assert_eq!(ids, [3, 4, 5]);
+///
+/// // drained in order, wrapping around the storage several times
+/// for id in 6..20 {
+///     assert_eq!(which(ring.push(press(id))), Some(id - 3));
+/// }
+/// let drained: Vec<_> = core::iter::from_fn(|| ring.pop())
+///     .filter_map(|event| event.which())
+///     .collect();
+/// assert_eq!(drained, [17, 18, 19]);
+/// assert!(ring.pop().is_none());
+/// assert_eq!(ring.iter().count(), 0);
+///
+/// ring.push(Event::Quit);
+/// ring.clear();
+/// assert!(ring.is_empty());
+///
+/// // nothing fits
+/// let mut none = EventRing::<0>::new();
+/// assert!(matches!(none.push(Event::Quit), Some(Event::Quit)));
+/// assert!(none.is_empty() && none.is_full());
+/// ```
+///
+/// [`Event`]: crate::Event
+/// [`EventQueue`]: crate::EventQueue
+/// [`SequencedEvent`]: crate::SequencedEvent
+/// [`push`]: Self::push
+#[derive(Clone, Copy)]
+#[expect(
+    clippy::module_name_repetitions,
+    reason = "named after what it holds, like `EventQueue`"
+)]
+pub struct EventRing<const N: usize, T = Event> {
+    /// Storage, [`None`] outside the queued items.
+    slots: [Option<T>; N],
+    /// Index of the oldest item.
+    head: usize,
+    /// Number of queued items.
+    len: usize,
+}
+
+impl<const N: usize, T: Copy> Default for EventRing<N, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, T: fmt::Debug> fmt::Debug for EventRing<N, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const N: usize, T> EventRing<N, T> {
+    /// Gets the maximum number of [`Event`]s, `N`.
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Gets the number of queued [`Event`]s.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no [`Event`]s are queued.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Checks if the next [`push`] evicts an [`Event`].
+    ///
+    /// [`push`]: Self::push
+    #[must_use]
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Iterates over the queued [`Event`]s, oldest first.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (wrapped, oldest) = self.slots.split_at(self.head);
+        oldest.iter().chain(wrapped).flatten()
+    }
+
+    /// Gets the index of the slot `offset` slots after the oldest one,
+    /// wrapping around. `offset` is at most `N`.
+    const fn slot(&self, offset: usize) -> usize {
+        let at = self.head.saturating_add(offset);
+        if at >= N { at.saturating_sub(N) } else { at }
+    }
+}
+
+impl<const N: usize, T: Copy> EventRing<N, T> {
+    /// Creates an empty [`EventRing`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { slots: [None; N], head: 0, len: 0 }
+    }
+
+    /// Queues the [`Event`], returning the oldest one if it was evicted to
+    /// make room.
+    #[inline]
+    pub fn push(&mut self, event: T) -> Option<T> {
+        if self.is_full() {
+            let Some(slot) = self.slots.get_mut(self.head) else {
+                return Some(event);
+            };
+            let oldest = slot.replace(event);
+            self.head = self.slot(1);
+            return oldest;
+        }
+        let newest = self.slot(self.len);
+        if let Some(slot) = self.slots.get_mut(newest) {
+            *slot = Some(event);
+            self.len = self.len.saturating_add(1);
+        }
+        None
+    }
+
+    /// Takes the oldest [`Event`], if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let oldest = self.slots.get_mut(self.head)?.take()?;
+        self.head = self.slot(1);
+        self.len = self.len.saturating_sub(1);
+        Some(oldest)
+    }
+
+    /// Removes all the queued [`Event`]s.
+    #[inline]
+    pub const fn clear(&mut self) {
+        self.slots = [None; N];
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use alloc::collections::VecDeque;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    use super::EventRing;
+    use crate::{Button, Event};
+
+    /// Number of operations in the longest sequence checked.
+    const OPS: u32 = 8;
+
+    /// Runs every sequence of up to [`OPS`] pushes and pops on an
+    /// [`EventRing`] of `N` items, checking it against a [`VecDeque`]
+    /// dropping the oldest item when full.
+    fn check_all<const N: usize>() {
+        for len in 0..=OPS {
+            for ops in 0..2u32.pow(len) {
+                let mut ring = EventRing::<N, u32>::new();
+                let mut model = VecDeque::new();
+                for step in 0..len {
+                    let is_push = ops.checked_shr(step).unwrap_or(0) & 1 == 1;
+                    let case = format!("step {step} of {ops:#b} into {N}");
+                    if is_push {
+                        model.push_back(step);
+                        let evicted =
+                            (model.len() > N).then(|| model.pop_front());
+                        let pushed = ring.push(step);
+                        assert_eq!(pushed, evicted.flatten(), "{case}");
+                    } else {
+                        assert_eq!(ring.pop(), model.pop_front(), "{case}");
+                    }
+                    assert_eq!(ring.len(), model.len(), "{case}");
+                    assert_eq!(ring.is_empty(), model.is_empty(), "{case}");
+                    assert_eq!(ring.is_full(), model.len() == N, "{case}");
+                    assert!(ring.iter().eq(model.iter()), "{case}");
+                }
+            }
+        }
+    }
+
+    /// Every sequence of pushes and pops matches the drop-oldest model,
+    /// wrapping around the storage and overflowing it, for a range of
+    /// capacities.
+    #[test]
+    fn matches_model() {
+        check_all::<0>();
+        check_all::<1>();
+        check_all::<2>();
+        check_all::<3>();
+        check_all::<4>();
+        check_all::<7>();
+    }
+
+    /// Cleared buffers start over, whatever the position of the oldest
+    /// item.
+    #[test]
+    fn clears() {
+        let mut ring = EventRing::<3, u32>::new();
+        for item in 0..5 {
+            _ = ring.push(item);
+        }
+        assert_eq!(ring.pop(), Some(2), "oldest after overflow");
+        ring.clear();
+        assert!(ring.is_empty(), "cleared");
+        assert_eq!(ring.iter().count(), 0, "nothing left to iterate");
+        assert_eq!(ring.pop(), None, "nothing left to pop");
+
+        for item in 10..13 {
+            assert_eq!(ring.push(item), None, "room for {item}");
+        }
+        let items: Vec<_> = ring.iter().copied().collect();
+        assert_eq!(items, [10, 11, 12], "in push order");
+        assert_eq!(ring.push(13), Some(10), "full again");
+    }
+
+    /// [`Event`]s are stored by default, evicted oldest first.
+    #[test]
+    fn stores_events() {
+        let press =
+            |which| Event::ControllerButtonDown { which, button: Button::A };
+        let mut ring = EventRing::<2>::new();
+        assert_eq!(ring.capacity(), 2, "capacity is N");
+        for which in 0..2 {
+            assert!(ring.push(press(which)).is_none(), "room for {which}");
+        }
+        let evicted = ring.push(press(2)).and_then(|event| event.which());
+        assert_eq!(evicted, Some(0), "oldest evicted");
+        let ids: Vec<_> = ring.iter().filter_map(Event::which).collect();
+        assert_eq!(ids, [1, 2], "oldest first");
+        let listed = format!("{:?}", [press(1), press(2)]);
+        assert_eq!(format!("{ring:?}"), listed, "debug list");
+    }
+}
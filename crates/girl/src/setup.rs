@@ -0,0 +1,348 @@
+//! Configuration of several [`Gamepad`] properties at once, e.g. on
+//! connection.
+
+#[cfg(feature = "rumble")]
+use core::time::Duration;
+
+#[cfg(feature = "sdl2")]
+use crate::Gamepad;
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{Error, InputProfile, Rgb};
+
+/// Properties to set on a [`Gamepad`] at once, see [`Gamepad::apply`].
+///
+/// Every property is optional and left as is unless set. They are applied
+/// in a fixed order, each regardless of whether the previous ones failed:
+///
+/// 1. the [`InputProfile`], see [`Gamepad::apply_profile`];
+/// 2. the LED color, see [`Gamepad::set_led`];
+/// 3. the player index, see [`Gamepad::set_player_index`];
+/// 4. the enabled sensors, in the order added, see `Gamepad::enable_sensor`;
+/// 5. a welcome rumble pulse, see `Gamepad::set_rumble`.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{PadSetup, SetupOp, prelude::*};
+/// let setup = PadSetup::new().led(Rgb::new(0, 0, 255)).player_index(0);
+///
+/// # #[cfg(feature = "sdl2")] {
+/// let mut girl = Girl::new()?;
+/// girl.update();
+/// # if girl.gamepad(0).is_some() {
+/// let mut gamepad = girl.gamepad(0).unwrap();
+///
+/// let report = gamepad.apply(&setup);
+/// // failing to set the LED doesn't stop setting the player index
+/// if let Some(Err(err)) = report.result(SetupOp::Led) {
+///     println!("{gamepad} keeps its LED color: {err}");
+/// }
+/// # }
+///
+/// // the same setup for every connected gamepad
+/// for (id, report) in girl.apply_to_all(&setup) {
+///     for (op, err) in report.failures() {
+///         println!("{id}: {op:?} failed: {err}");
+///     }
+/// }
+/// # }
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PadSetup {
+    /// [`InputProfile`] to apply.
+    profile: Option<InputProfile>,
+    /// LED color to set.
+    led: Option<Rgb>,
+    /// Player index to set.
+    player_index: Option<u32>,
+    /// Sensors to enable.
+    #[cfg(feature = "sensors")]
+    sensors: Vec<Sensor>,
+    /// Low and high frequency intensities and duration of the rumble pulse.
+    #[cfg(feature = "rumble")]
+    rumble: Option<(u16, u16, Duration)>,
+}
+
+impl PadSetup {
+    /// Creates a [`PadSetup`] leaving every property as is.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            profile: None,
+            led: None,
+            player_index: None,
+            #[cfg(feature = "sensors")]
+            sensors: Vec::new(),
+            #[cfg(feature = "rumble")]
+            rumble: None,
+        }
+    }
+
+    /// Sets the [`InputProfile`] to apply.
+    #[must_use]
+    #[inline]
+    pub fn profile(mut self, profile: InputProfile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Sets the LED color to set, as is, see [`Gamepad::set_led`].
+    #[must_use]
+    #[inline]
+    pub const fn led(mut self, color: Rgb) -> Self {
+        self.led = Some(color);
+        self
+    }
+
+    /// Sets the player index to set.
+    #[must_use]
+    #[inline]
+    pub const fn player_index(mut self, index: u32) -> Self {
+        self.player_index = Some(index);
+        self
+    }
+
+    /// Adds a [`Sensor`] to enable.
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub fn sensor(mut self, sensor: Sensor) -> Self {
+        self.sensors.push(sensor);
+        self
+    }
+
+    /// Sets a rumble pulse to play last, see [`Gamepad::set_rumble`].
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    #[must_use]
+    #[inline]
+    pub const fn rumble(
+        mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Self {
+        self.rumble =
+            Some((low_frequency_rumble, high_frequency_rumble, duration));
+        self
+    }
+}
+
+/// Operation of a [`PadSetup`], see [`SetupReport`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetupOp {
+    /// Applying the [`InputProfile`].
+    Profile,
+    /// Setting the LED color.
+    Led,
+    /// Setting the player index.
+    PlayerIndex,
+    /// Enabling a [`Sensor`].
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    Sensor(Sensor),
+    /// Playing the rumble pulse.
+    #[cfg(feature = "rumble")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rumble")))]
+    Rumble,
+}
+
+/// Outcome of every operation of a [`PadSetup`] applied with
+/// [`Gamepad::apply`], in the order they ran.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{SetupOp, SetupReport};
+/// let mut report = SetupReport::default();
+/// report.results.push((SetupOp::PlayerIndex, Ok(())));
+/// report.results.push((
+///     SetupOp::Led,
+///     Err(girl::Error::InvalidArgument { what: "led" }),
+/// ));
+///
+/// assert!(!report.is_ok());
+/// assert!(report.result(SetupOp::PlayerIndex).is_some_and(Result::is_ok));
+/// let failed: Vec<_> = report.failures().map(|(op, _)| op).collect();
+/// assert_eq!(failed, [SetupOp::Led]);
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct SetupReport {
+    /// Every operation that ran, with its outcome.
+    pub results: Vec<(SetupOp, Result<(), Error>)>,
+}
+
+impl SetupReport {
+    /// Checks if every operation succeeded.
+    #[must_use]
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|entry| entry.1.is_ok())
+    }
+
+    /// Gets the outcome of the operation, if it ran.
+    #[must_use]
+    #[inline]
+    pub fn result(&self, op: SetupOp) -> Option<&Result<(), Error>> {
+        self.results.iter().find(|&&(ran, _)| ran == op).map(|entry| &entry.1)
+    }
+
+    /// Iterates the failed operations with their [`Error`]s.
+    #[inline]
+    pub fn failures(&self) -> impl Iterator<Item = (SetupOp, &Error)> {
+        self.results.iter().filter_map(|entry| {
+            entry.1.as_ref().err().map(|err| (entry.0, err))
+        })
+    }
+}
+
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "feature gated")]
+impl PadSetup {
+    /// Applies every property set to the [`SetupTarget`], in order,
+    /// reporting the outcome of each instead of stopping at the first
+    /// failure.
+    pub(crate) fn apply_to(
+        &self,
+        target: &mut impl SetupTarget,
+    ) -> SetupReport {
+        let mut report = SetupReport::default();
+        if let Some(profile) = self.profile.as_ref() {
+            let result = target.setup_profile(profile);
+            report.results.push((SetupOp::Profile, result));
+        }
+        if let Some(color) = self.led {
+            let result = target.setup_led(color);
+            report.results.push((SetupOp::Led, result));
+        }
+        if let Some(index) = self.player_index {
+            let result = target.setup_player_index(index);
+            report.results.push((SetupOp::PlayerIndex, result));
+        }
+        #[cfg(feature = "sensors")]
+        for &sensor in &self.sensors {
+            let result = target.setup_sensor(sensor);
+            report.results.push((SetupOp::Sensor(sensor), result));
+        }
+        #[cfg(feature = "rumble")]
+        if let Some((low, high, duration)) = self.rumble {
+            let result = target.setup_rumble(low, high, duration);
+            report.results.push((SetupOp::Rumble, result));
+        }
+        report
+    }
+}
+
+/// Gamepad a [`PadSetup`] can be applied to, i.e. a [`Gamepad`] or a
+/// [`SimGamepad`], see [`PadSetup::apply_to`].
+///
+/// [`SimGamepad`]: crate::SimGamepad
+#[cfg(any(feature = "sdl2", feature = "sim"))]
+pub(crate) trait SetupTarget {
+    /// Sets the LED color.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if there is no LED or setting it fails.
+    fn setup_led(&mut self, color: Rgb) -> Result<(), Error>;
+
+    /// Sets the player index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if setting the player index fails.
+    fn setup_player_index(&mut self, index: u32) -> Result<(), Error>;
+
+    /// Applies the [`InputProfile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the [`InputProfile`] is invalid.
+    fn setup_profile(&mut self, profile: &InputProfile) -> Result<(), Error>;
+
+    /// Plays the rumble pulse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if there are no rumble motors or playing fails.
+    #[cfg(feature = "rumble")]
+    fn setup_rumble(
+        &mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Result<(), Error>;
+
+    /// Enables the [`Sensor`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if there is no such [`Sensor`] or enabling it
+    /// fails.
+    #[cfg(feature = "sensors")]
+    fn setup_sensor(&mut self, sensor: Sensor) -> Result<(), Error>;
+}
+
+/// Configuration of several [`Gamepad`] properties at once.
+#[cfg(feature = "sdl2")]
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+impl Gamepad {
+    /// Applies every property set in the [`PadSetup`], in its order,
+    /// reporting the outcome of each instead of stopping at the first
+    /// failure.
+    ///
+    /// See [`PadSetup`] for an example, and [`Girl::apply_to_all`] to
+    /// configure every connected [`Gamepad`].
+    ///
+    /// [`Girl::apply_to_all`]: crate::Girl::apply_to_all
+    #[inline]
+    pub fn apply(&mut self, setup: &PadSetup) -> SetupReport {
+        setup.apply_to(self)
+    }
+}
+
+#[cfg(feature = "sdl2")]
+impl SetupTarget for Gamepad {
+    #[inline]
+    fn setup_led(&mut self, color: Rgb) -> Result<(), Error> {
+        self.set_led(color.red, color.green, color.blue)
+    }
+
+    #[inline]
+    fn setup_player_index(&mut self, index: u32) -> Result<(), Error> {
+        self.set_player_index(Some(index))
+    }
+
+    #[inline]
+    fn setup_profile(&mut self, profile: &InputProfile) -> Result<(), Error> {
+        self.apply_profile(profile)
+    }
+
+    #[cfg(feature = "rumble")]
+    #[inline]
+    fn setup_rumble(
+        &mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        self.set_rumble(low_frequency_rumble, high_frequency_rumble, duration)
+    }
+
+    #[cfg(feature = "sensors")]
+    #[inline]
+    fn setup_sensor(&mut self, sensor: Sensor) -> Result<(), Error> {
+        self.enable_sensor(sensor)
+    }
+}
@@ -0,0 +1,793 @@
+//! State shared between a [`Girl`] and the [`Gamepad`]s it opens.
+//!
+//! [`Girl`]: crate::Girl
+//! [`Gamepad`]: crate::Gamepad
+
+use alloc::{collections::BTreeMap, sync::Weak};
+use core::{
+    mem,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+use sdl2::event::{Event as SdlEvent, EventWatchCallback};
+
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{
+    Button, ConnectionHeuristics, ControllerType, DefaultProfiles, Event,
+    Gamepad, GamepadId, Guid, InputConfig, InputOverrides, InputProfile,
+    SteamInputHeuristics, Stick, generation::IdGenerations, logging::diag,
+    ratelimit::RateLimiter, watchdog::StallWatchdog,
+};
+#[cfg(feature = "touchpad")]
+use crate::{TouchRejection, TouchpadSnapshot};
+
+/// Consecutive failures to resolve controller handles after which a
+/// subsystem reset is suspected, see [`Event::SubsystemReset`].
+const RESET_FAILURES: u32 = 16;
+
+/// State shared between a [`Girl`] and the [`Gamepad`]s it opens.
+///
+/// [`Girl`]: crate::Girl
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug, Default)]
+pub(crate) struct Shared {
+    /// Whether input is suspended, see [`Girl::suspend_input`].
+    ///
+    /// [`Girl::suspend_input`]: crate::Girl::suspend_input
+    suspended: AtomicBool,
+    /// Deadlines of running rumble effects.
+    rumble: Mutex<RumbleDeadlines>,
+    /// [`Button`]s pressed between [`Girl::update`]s.
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    taps: Mutex<TapAccumulator>,
+    /// Whether [`InputRecorder`] is watching the SDL2 events.
+    event_driven: AtomicBool,
+    /// Fingers on the touchpads per [`GamepadId`], see
+    /// [`Gamepad::touchpad`].
+    ///
+    /// [`Gamepad::touchpad`]: crate::Gamepad::touchpad
+    #[cfg(feature = "touchpad")]
+    touchpads: Mutex<BTreeMap<GamepadId, TouchpadSnapshot>>,
+    /// Touch rejection per [`GamepadId`] and touchpad index, see
+    /// [`set_touchpad_rejection`].
+    ///
+    /// [`set_touchpad_rejection`]: crate::Gamepad::set_touchpad_rejection
+    #[cfg(feature = "touchpad")]
+    touch_rejection: Mutex<BTreeMap<(GamepadId, usize), TouchRejection>>,
+    /// Profiles of new connections, see [`Girl::set_default_profile`].
+    ///
+    /// [`Girl::set_default_profile`]: crate::Girl::set_default_profile
+    defaults: Mutex<DefaultProfiles>,
+    /// Settings of new connections over their default profile, see
+    /// [`Girl::set_default_input_config`].
+    ///
+    /// [`Girl::set_default_input_config`]:
+    ///     crate::Girl::set_default_input_config
+    input_config: Mutex<InputConfig>,
+    /// Heuristics of [`Gamepad::is_steam_virtual`], see
+    /// [`Girl::set_steam_heuristics`].
+    ///
+    /// [`Gamepad::is_steam_virtual`]: crate::Gamepad::is_steam_virtual
+    /// [`Girl::set_steam_heuristics`]: crate::Girl::set_steam_heuristics
+    steam: Mutex<SteamInputHeuristics>,
+    /// Heuristics of [`Gamepad::connection_kind`], see
+    /// [`Girl::set_connection_heuristics`].
+    ///
+    /// [`Gamepad::connection_kind`]: crate::Gamepad::connection_kind
+    /// [`Girl::set_connection_heuristics`]:
+    ///     crate::Girl::set_connection_heuristics
+    connection: Mutex<ConnectionHeuristics>,
+    /// Profiles applied with [`Gamepad::apply_profile`] per [`Guid`], kept
+    /// across reconnects.
+    ///
+    /// [`Gamepad::apply_profile`]: crate::Gamepad::apply_profile
+    applied: Mutex<BTreeMap<Guid, InputProfile>>,
+    /// Watchdog of [`Girl::update`], see [`Girl::enable_stall_detection`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Girl::enable_stall_detection`]: crate::Girl::enable_stall_detection
+    watchdog: StallWatchdog,
+    /// Consecutive failures to resolve controller handles.
+    device_failures: AtomicU32,
+    /// Generations of the [`GamepadId`]s assigned to opened devices.
+    ids: Mutex<IdGenerations>,
+    /// Audio output devices matched per [`GamepadId`], see
+    /// [`Gamepad::audio_device_hint`].
+    ///
+    /// [`Gamepad::audio_device_hint`]: crate::Gamepad::audio_device_hint
+    audio_devices: Mutex<BTreeMap<GamepadId, String>>,
+    /// Tier thresholds per [`GamepadId`] and [`Stick`], see
+    /// [`Gamepad::set_stick_tiers`].
+    ///
+    /// [`Gamepad::set_stick_tiers`]: crate::Gamepad::set_stick_tiers
+    stick_tiers: Mutex<BTreeMap<(GamepadId, Stick), Vec<f64>>>,
+    /// Whether failed [`Sensor`] enables are queued for retries, see
+    /// [`Girl::set_sensor_retry_window`].
+    ///
+    /// [`Girl::set_sensor_retry_window`]:
+    ///     crate::Girl::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    retry_sensors: AtomicBool,
+    /// [`Sensor`] enables failed since the last [`Girl::update`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    #[cfg(feature = "sensors")]
+    failed_sensors: Mutex<Vec<(GamepadId, Sensor)>>,
+    /// Sensor and touchpad reads from SDL2 not yet taken into the
+    /// [`PerfCounters`].
+    ///
+    /// [`PerfCounters`]: crate::PerfCounters
+    ffi_calls: AtomicU64,
+    /// Limits the controller updates of [`Gamepad::read_fresh`].
+    ///
+    /// [`Gamepad::read_fresh`]: crate::Gamepad::read_fresh
+    fresh_reads: Mutex<FreshReads>,
+    /// Whether [`Gamepad`]s record a [`CommandJournal`] once opened, see
+    /// [`Girl::set_command_journal`].
+    ///
+    /// [`CommandJournal`]: crate::CommandJournal
+    /// [`Girl::set_command_journal`]: crate::Girl::set_command_journal
+    journal_commands: AtomicBool,
+}
+
+/// [`RateLimiter`] of the controller updates of [`Gamepad::read_fresh`],
+/// once per [`Gamepad::FRESH_READ_INTERVAL`] by default.
+#[derive(Debug)]
+struct FreshReads(RateLimiter);
+
+impl Default for FreshReads {
+    #[inline]
+    fn default() -> Self {
+        Self(RateLimiter::new(Gamepad::FRESH_READ_INTERVAL))
+    }
+}
+
+impl Shared {
+    /// Checks if input is suspended.
+    #[inline]
+    pub(crate) fn suspended(&self) -> bool {
+        self.suspended.load(Ordering::Relaxed)
+    }
+
+    /// Suspends or resumes input.
+    #[inline]
+    pub(crate) fn set_suspended(&self, suspended: bool) {
+        self.suspended.store(suspended, Ordering::Relaxed);
+    }
+
+    /// Locks the [`RumbleDeadlines`].
+    #[cfg_attr(
+        not(feature = "rumble"),
+        expect(dead_code, reason = "feature gated")
+    )]
+    #[inline]
+    pub(crate) fn rumble(&self) -> MutexGuard<'_, RumbleDeadlines> {
+        self.rumble.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`TapAccumulator`].
+    #[inline]
+    pub(crate) fn taps(&self) -> MutexGuard<'_, TapAccumulator> {
+        self.taps.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`IdGenerations`].
+    #[inline]
+    pub(crate) fn ids(&self) -> MutexGuard<'_, IdGenerations> {
+        self.ids.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Gets the [`GamepadId`] of the device with the raw instance ID, e.g.
+    /// the `which` of an [`Event`].
+    #[inline]
+    pub(crate) fn id(&self, which: u32) -> GamepadId {
+        self.ids().current(which)
+    }
+
+    /// Checks if [`InputRecorder`] is watching the SDL2 events.
+    #[cfg_attr(
+        not(feature = "touchpad"),
+        expect(dead_code, reason = "feature gated")
+    )]
+    #[inline]
+    pub(crate) fn event_driven(&self) -> bool {
+        self.event_driven.load(Ordering::Relaxed)
+    }
+
+    /// Marks [`InputRecorder`] as watching the SDL2 events.
+    #[inline]
+    pub(crate) fn set_event_driven(&self) {
+        self.event_driven.store(true, Ordering::Relaxed);
+    }
+
+    /// Gets the [`StallWatchdog`].
+    #[inline]
+    pub(crate) const fn watchdog(&self) -> &StallWatchdog {
+        &self.watchdog
+    }
+
+    /// Warns once per stall if [`Girl::update`] wasn't called for longer
+    /// than the threshold of the [`StallWatchdog`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    #[inline]
+    pub(crate) fn check_stall(&self) {
+        // don't even read the clock while disabled
+        if self.watchdog.threshold().is_none() {
+            return;
+        }
+        let now = Instant::now();
+        if self.watchdog.check(now) {
+            diag!(
+                warn,
+                "input queried {:?} after the last `Girl::update`, input is \
+                 frozen until it is called again",
+                self.watchdog.time_since_update(now),
+            );
+        }
+    }
+
+    /// Counts a sensor or touchpad read or a controller update from SDL2.
+    #[inline]
+    pub(crate) fn count_ffi_call(&self) {
+        _ = self.ffi_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Checks if a controller update of [`Gamepad::read_fresh`] is due at
+    /// `now`, and if so, counts it as done.
+    #[inline]
+    pub(crate) fn fresh_read_due(&self, now: Instant) -> bool {
+        self.fresh_reads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .ready(now)
+    }
+
+    /// Gets the minimal time between the controller updates of
+    /// [`Gamepad::read_fresh`].
+    #[inline]
+    pub(crate) fn fresh_read_interval(&self) -> Duration {
+        self.fresh_reads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .interval()
+    }
+
+    /// Sets the minimal time between the controller updates of
+    /// [`Gamepad::read_fresh`].
+    #[inline]
+    pub(crate) fn set_fresh_read_interval(&self, interval: Duration) {
+        let mut fresh_reads =
+            self.fresh_reads.lock().unwrap_or_else(PoisonError::into_inner);
+        let last = fresh_reads.0.last();
+        fresh_reads.0 = RateLimiter::new(interval);
+        if let Some(last) = last {
+            fresh_reads.0.mark(last);
+        }
+    }
+
+    /// Gets the number of reads counted with [`count_ffi_call`] since the
+    /// last [`take_ffi_calls`].
+    ///
+    /// [`count_ffi_call`]: Self::count_ffi_call
+    /// [`take_ffi_calls`]: Self::take_ffi_calls
+    #[inline]
+    pub(crate) fn ffi_calls(&self) -> u64 {
+        self.ffi_calls.load(Ordering::Relaxed)
+    }
+
+    /// Takes the number of reads counted with [`count_ffi_call`], starting
+    /// the count over.
+    ///
+    /// [`count_ffi_call`]: Self::count_ffi_call
+    #[inline]
+    pub(crate) fn take_ffi_calls(&self) -> u64 {
+        self.ffi_calls.swap(0, Ordering::Relaxed)
+    }
+
+    /// Records whether resolving a controller handle succeeded.
+    #[inline]
+    pub(crate) fn record_device_access(&self, ok: bool) {
+        if ok {
+            self.device_failures.store(0, Ordering::Relaxed);
+        } else {
+            _ = self.device_failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Checks if enough consecutive failures were recorded to suspect a
+    /// subsystem reset, starting the count over if so.
+    #[inline]
+    pub(crate) fn take_reset_suspected(&self) -> bool {
+        self.device_failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |failures| {
+                (failures >= RESET_FAILURES).then_some(0)
+            })
+            .is_ok()
+    }
+
+    /// Starts the count of consecutive failures over.
+    #[inline]
+    pub(crate) fn clear_device_failures(&self) {
+        self.device_failures.store(0, Ordering::Relaxed);
+    }
+
+    /// Checks if [`Gamepad`]s record a [`CommandJournal`] once opened.
+    ///
+    /// [`CommandJournal`]: crate::CommandJournal
+    #[inline]
+    pub(crate) fn journal_commands(&self) -> bool {
+        self.journal_commands.load(Ordering::Relaxed)
+    }
+
+    /// Sets whether [`Gamepad`]s record a [`CommandJournal`] once opened.
+    ///
+    /// [`CommandJournal`]: crate::CommandJournal
+    #[inline]
+    pub(crate) fn set_journal_commands(&self, journal: bool) {
+        self.journal_commands.store(journal, Ordering::Relaxed);
+    }
+
+    /// Sets whether failed [`Sensor`] enables are queued for retries.
+    #[cfg(feature = "sensors")]
+    #[inline]
+    pub(crate) fn set_retry_sensors(&self, retry: bool) {
+        self.retry_sensors.store(retry, Ordering::Relaxed);
+    }
+
+    /// Queues a failed [`Sensor`] enable for retries, if they are enabled.
+    #[cfg(feature = "sensors")]
+    #[inline]
+    pub(crate) fn sensor_failed(&self, id: GamepadId, sensor: Sensor) {
+        if self.retry_sensors.load(Ordering::Relaxed) {
+            self.failed_sensors
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((id, sensor));
+        }
+    }
+
+    /// Takes the [`Sensor`] enables failed since the last call.
+    #[cfg(feature = "sensors")]
+    #[inline]
+    pub(crate) fn take_failed_sensors(&self) -> Vec<(GamepadId, Sensor)> {
+        mem::take(
+            &mut *self
+                .failed_sensors
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Locks the audio output devices matched per [`GamepadId`].
+    #[inline]
+    pub(crate) fn audio_devices(
+        &self,
+    ) -> MutexGuard<'_, BTreeMap<GamepadId, String>> {
+        self.audio_devices.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the tier thresholds per [`GamepadId`] and [`Stick`].
+    #[inline]
+    pub(crate) fn stick_tiers(
+        &self,
+    ) -> MutexGuard<'_, BTreeMap<(GamepadId, Stick), Vec<f64>>> {
+        self.stick_tiers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`DefaultProfiles`].
+    #[inline]
+    pub(crate) fn defaults(&self) -> MutexGuard<'_, DefaultProfiles> {
+        self.defaults.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`SteamInputHeuristics`].
+    #[inline]
+    pub(crate) fn steam(&self) -> MutexGuard<'_, SteamInputHeuristics> {
+        self.steam.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`ConnectionHeuristics`].
+    #[inline]
+    pub(crate) fn connection(&self) -> MutexGuard<'_, ConnectionHeuristics> {
+        self.connection.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the profiles applied with [`Gamepad::apply_profile`].
+    ///
+    /// [`Gamepad::apply_profile`]: crate::Gamepad::apply_profile
+    #[inline]
+    pub(crate) fn applied(
+        &self,
+    ) -> MutexGuard<'_, BTreeMap<Guid, InputProfile>> {
+        self.applied.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`InputConfig`] of new connections.
+    #[inline]
+    pub(crate) fn input_config(&self) -> MutexGuard<'_, InputConfig> {
+        self.input_config.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Gets the [`InputProfile`] a new connection starts with and its
+    /// overridden settings: the one last applied to its [`Guid`] overriding
+    /// all of them, or else its default one or [`InputProfile::new`] with the
+    /// [`InputConfig`] applied.
+    #[inline]
+    pub(crate) fn initial_profile(
+        &self,
+        guid: Guid,
+        controller_type: ControllerType,
+    ) -> (InputProfile, InputOverrides) {
+        if let Some(applied) = self.applied().get(&guid) {
+            return (applied.clone(), InputOverrides::all());
+        }
+        let mut profile = self
+            .defaults()
+            .profile_for(guid, controller_type)
+            .cloned()
+            .unwrap_or_default();
+        self.input_config().apply(&mut profile, InputOverrides::empty());
+        (profile, InputOverrides::empty())
+    }
+
+    /// Locks the [`TouchpadSnapshot`]s.
+    #[cfg(feature = "touchpad")]
+    #[inline]
+    pub(crate) fn touchpads(
+        &self,
+    ) -> MutexGuard<'_, BTreeMap<GamepadId, TouchpadSnapshot>> {
+        self.touchpads.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the [`TouchRejection`]s per [`GamepadId`] and touchpad index.
+    #[cfg(feature = "touchpad")]
+    #[inline]
+    pub(crate) fn touch_rejection(
+        &self,
+    ) -> MutexGuard<'_, BTreeMap<(GamepadId, usize), TouchRejection>> {
+        self.touch_rejection.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records the input of the [`Event`] as soon as it arrives, queued by
+    /// SDL2 (see [`InputRecorder`]) or injected with [`Girl::inject`]:
+    /// presses into the [`TapAccumulator`] and touchpad [`Event`]s into the
+    /// [`TouchpadSnapshot`]s.
+    ///
+    /// [`Girl::inject`]: crate::Girl::inject
+    /// [`TouchpadSnapshot`]: crate::TouchpadSnapshot
+    #[inline]
+    pub(crate) fn record_input(&self, event: &Event) {
+        if let Event::ControllerButtonDown { which, button }
+        | Event::ControllerButtonDownWithState { which, button, .. } = *event
+        {
+            let id = self.id(which);
+            self.taps().record(id, button);
+        }
+        #[cfg(feature = "touchpad")]
+        if let Event::ControllerTouchpad(touch) = *event {
+            let id = self.id(touch.which);
+            self.touchpads().entry(id).or_default().observe(&touch);
+        }
+        #[cfg(feature = "touchpad")]
+        if let Event::ControllerDeviceRemoved { which } = *event {
+            let id = self.id(which);
+            drop(self.touchpads().remove(&id));
+        }
+    }
+}
+
+/// SDL2 event watch recording input as soon as SDL2 queues it.
+///
+/// Feeds [`ControllerButtonDown`] events into the [`TapAccumulator`] and
+/// touchpad events into the [`TouchpadSnapshot`]s, so they are recorded even
+/// if the application doesn't drain the [`Event`]s.
+///
+/// Holds the [`Shared`] state weakly, as [`Girl`] counts the strong
+/// references to know whether any [`Gamepad`] is open.
+///
+/// [`ControllerButtonDown`]: Event::ControllerButtonDown
+/// [`TouchpadSnapshot`]: crate::TouchpadSnapshot
+/// [`Girl`]: crate::Girl
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug)]
+pub(crate) struct InputRecorder(Weak<Shared>);
+
+impl InputRecorder {
+    /// Creates a new [`InputRecorder`] feeding the [`Shared`] state.
+    #[must_use]
+    #[inline]
+    #[expect(clippy::single_call_fn, reason = "keeps the field private")]
+    pub(crate) const fn new(shared: Weak<Shared>) -> Self {
+        Self(shared)
+    }
+}
+
+impl EventWatchCallback for InputRecorder {
+    #[inline]
+    fn callback(&mut self, event: SdlEvent) {
+        let Some(shared) = self.0.upgrade() else {
+            return;
+        };
+        if let Some(event) = Event::from_sdl(&event) {
+            shared.record_input(&event);
+        }
+    }
+}
+
+/// Deadlines of running rumble effects per [`GamepadId`], used to report
+/// when they finish and how long they played.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RumbleDeadlines {
+    /// When the running effects started and when they end.
+    deadlines: BTreeMap<GamepadId, (Instant, Instant)>,
+    /// Effects cancelled since the last [`drain_finished`], with when they
+    /// started and would have ended.
+    ///
+    /// [`drain_finished`]: Self::drain_finished
+    cancelled: Vec<(GamepadId, Instant, Instant)>,
+    /// Time played by ended effects since the last [`take_played`].
+    ///
+    /// [`take_played`]: Self::take_played
+    played: BTreeMap<GamepadId, Duration>,
+}
+
+// TODO: Try remove on next Rust version update.
+#[cfg_attr(
+    not(feature = "rumble"),
+    expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here"),
+    allow(dead_code, reason = "feature gated")
+)]
+impl RumbleDeadlines {
+    /// Tracks an effect started at `now` for `duration`, replacing the
+    /// running one, if any, just like SDL2 does.
+    #[inline]
+    pub(crate) fn start(
+        &mut self,
+        id: GamepadId,
+        now: Instant,
+        duration: Duration,
+    ) {
+        if let Some((start, deadline)) = self.deadlines.remove(&id) {
+            let end = now.min(deadline);
+            Self::credit(
+                &mut self.played,
+                id,
+                end.saturating_duration_since(start),
+            );
+        }
+        // far-future deadlines never finish on their own
+        if let Some(deadline) = now.checked_add(duration) {
+            _ = self.deadlines.insert(id, (now, deadline));
+        }
+    }
+
+    /// Cancels the running effect, returning whether there was one.
+    ///
+    /// It counts as played until the next [`drain_finished`], see
+    /// [`take_played`].
+    ///
+    /// [`drain_finished`]: Self::drain_finished
+    /// [`take_played`]: Self::take_played
+    #[inline]
+    pub(crate) fn cancel(&mut self, id: GamepadId) -> bool {
+        let Some((start, deadline)) = self.deadlines.remove(&id) else {
+            return false;
+        };
+        self.cancelled.push((id, start, deadline));
+        true
+    }
+
+    /// Gets the time left until the running effect ends at `now`.
+    ///
+    /// Returns [`None`] if no effect is running.
+    #[must_use]
+    #[inline]
+    pub(crate) fn remaining(
+        &self,
+        id: GamepadId,
+        now: Instant,
+    ) -> Option<Duration> {
+        let (_, deadline) = *self.deadlines.get(&id)?;
+        (deadline > now).then(|| deadline.duration_since(now))
+    }
+
+    /// Calls `f` for every effect cancelled since the last call (with
+    /// `cancelled` set) and for every effect whose deadline passed by `now`.
+    #[inline]
+    pub(crate) fn drain_finished<F>(&mut self, now: Instant, mut f: F)
+    where
+        F: FnMut(GamepadId, bool),
+    {
+        let played = &mut self.played;
+        for (id, start, deadline) in self.cancelled.drain(..) {
+            let end = now.min(deadline);
+            Self::credit(played, id, end.saturating_duration_since(start));
+            f(id, true);
+        }
+        self.deadlines.retain(|&id, &mut (start, deadline)| {
+            let running = deadline > now;
+            if !running {
+                Self::credit(
+                    played,
+                    id,
+                    deadline.saturating_duration_since(start),
+                );
+                f(id, false);
+            }
+            running
+        });
+    }
+
+    /// Takes the time played by effects that ended since the last call,
+    /// finished, replaced or cancelled, by [`GamepadId`].
+    #[inline]
+    pub(crate) fn take_played(&mut self) -> BTreeMap<GamepadId, Duration> {
+        mem::take(&mut self.played)
+    }
+
+    /// Adds the `time` an effect of `id` played to the `played` totals.
+    fn credit(
+        played: &mut BTreeMap<GamepadId, Duration>,
+        id: GamepadId,
+        time: Duration,
+    ) {
+        let total = played.entry(id).or_default();
+        *total = total.saturating_add(time);
+    }
+}
+
+/// [`Button`]s pressed per [`GamepadId`] between two [`publish`]es, so taps
+/// shorter than the polling interval are never lost.
+///
+/// [`Girl`] records every [`ControllerButtonDown`] as SDL2 queues it and
+/// publishes on every [`Girl::update`], see
+/// [`Gamepad::tapped_since_update`].
+///
+/// [`publish`]: Self::publish
+/// [`ControllerButtonDown`]: Event::ControllerButtonDown
+/// [`Girl`]: crate::Girl
+/// [`Girl::update`]: crate::Girl::update
+/// [`Gamepad::tapped_since_update`]: crate::Gamepad::tapped_since_update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TapAccumulator {
+    /// [`Button`]s pressed since the last [`publish`].
+    ///
+    /// [`publish`]: Self::publish
+    recording: BTreeMap<GamepadId, Button>,
+    /// [`Button`]s pressed between the two last [`publish`]es.
+    ///
+    /// [`publish`]: Self::publish
+    published: BTreeMap<GamepadId, Button>,
+}
+
+impl TapAccumulator {
+    /// Records the [`Button`] pressed on the [`Gamepad`] with the
+    /// [`GamepadId`], e.g. one resolved with [`Girl::resolve_id`].
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Girl::resolve_id`]: crate::Girl::resolve_id
+    #[inline]
+    pub(crate) fn record(&mut self, id: GamepadId, button: Button) {
+        self.recording.entry(id).or_default().insert(button);
+    }
+
+    /// Publishes the [`Button`]s recorded since the previous call, replacing
+    /// the previously published ones.
+    #[inline]
+    pub(crate) fn publish(&mut self) {
+        self.published = mem::take(&mut self.recording);
+    }
+
+    /// Gets the [`Button`]s pressed between the two last [`publish`]es.
+    ///
+    /// [`publish`]: Self::publish
+    #[must_use]
+    #[inline]
+    pub(crate) fn taps(&self, id: GamepadId) -> Button {
+        self.published.get(&id).copied().unwrap_or_default()
+    }
+}
+
+/// Unit tests of [`RumbleDeadlines`] and [`TapAccumulator`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{RumbleDeadlines, TapAccumulator};
+    use crate::{Button, GamepadId};
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Effects finish at their deadlines, and cancelled ones right away.
+    #[test]
+    fn drains_finished_rumble() {
+        let mut deadlines = RumbleDeadlines::default();
+        let (first, second) = (GamepadId::from_raw(0), GamepadId::from_raw(1));
+        let start = Instant::now();
+        let mut finished = Vec::new();
+
+        deadlines.start(first, at(start, 0), Duration::from_millis(100));
+        deadlines.start(second, at(start, 0), Duration::from_millis(500));
+        assert_eq!(
+            deadlines.remaining(first, at(start, 40)),
+            Some(Duration::from_millis(60)),
+            "remaining time",
+        );
+
+        deadlines.drain_finished(at(start, 99), |id, cancelled| {
+            finished.push((id, cancelled));
+        });
+        assert!(finished.is_empty(), "finished before the deadline");
+        deadlines.drain_finished(at(start, 100), |id, cancelled| {
+            finished.push((id, cancelled));
+        });
+        assert_eq!(finished, [(first, false)], "not finished at the deadline");
+        assert_eq!(deadlines.remaining(first, at(start, 100)), None, "running");
+
+        assert!(deadlines.cancel(second), "not cancelled");
+        assert!(!deadlines.cancel(second), "cancelled twice");
+        deadlines.drain_finished(at(start, 101), |id, cancelled| {
+            finished.push((id, cancelled));
+        });
+        assert_eq!(
+            finished,
+            [(first, false), (second, true)],
+            "cancellation not reported",
+        );
+    }
+
+    /// Played times count until the deadline or the drained cancellation.
+    #[test]
+    fn takes_played_rumble() {
+        let mut deadlines = RumbleDeadlines::default();
+        let (first, second) = (GamepadId::from_raw(0), GamepadId::from_raw(1));
+        let start = Instant::now();
+
+        deadlines.start(first, at(start, 0), Duration::from_millis(100));
+        deadlines.start(second, at(start, 0), Duration::from_millis(500));
+        _ = deadlines.cancel(second);
+        deadlines.drain_finished(at(start, 101), |_, _| {});
+
+        let played = deadlines.take_played();
+        assert_eq!(
+            played.get(&first),
+            Some(&Duration::from_millis(100)),
+            "played to the end",
+        );
+        assert_eq!(
+            played.get(&second),
+            Some(&Duration::from_millis(101)),
+            "played until drained",
+        );
+        assert!(deadlines.take_played().is_empty(), "taken twice");
+    }
+
+    /// Taps are published once, on the next publish.
+    #[test]
+    fn publishes_taps() {
+        let mut taps = TapAccumulator::default();
+        let pad = GamepadId::from_raw(0);
+
+        taps.record(pad, Button::A);
+        assert_eq!(taps.taps(pad), Button::empty(), "published early");
+        taps.publish();
+        assert_eq!(taps.taps(pad), Button::A, "not published");
+        taps.publish();
+        assert_eq!(taps.taps(pad), Button::empty(), "published twice");
+    }
+}
@@ -0,0 +1,2910 @@
+//! Scripted [`Gamepad`] input for testing without hardware.
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+#[cfg(any(feature = "sensors", feature = "stubs"))]
+use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, VecDeque};
+use core::{mem, time::Duration};
+use std::time::Instant;
+
+use crate::{
+    Button, ButtonAxis, ButtonChanges, ButtonTracker, ConnectionHeuristics,
+    ConnectionKind, ControllerType, DefaultProfiles, Error, ErrorCode, Event,
+    EventQueue, GamepadDump, GamepadId, GamepadRead, GamepadSource,
+    GamepadState, Guid, InputBind, InputProfile, LedBinding, LedBindingId,
+    LedBindings, MappingSnapshot, PadSetup, RecoveryReport, Rgb,
+    SequencedEvent, SetupReport, Stick, Trigger,
+    gamepad::{profile::check_single_button, state::fill_slots},
+    normalize::{clamp_stick, clamp_trigger},
+    openerror::LastOpenError,
+    queue::EVENT_CAPACITY,
+    setup::SetupTarget,
+};
+#[cfg(any(feature = "sensors", feature = "stubs"))]
+use crate::{JoyConSide, Sensor};
+#[cfg(feature = "sensors")]
+use crate::{SensorDump, retry::SensorRetry};
+
+/// SDL2 mapping of a [`SimGamepad`] without the GUID.
+///
+/// Used unless overridden with [`SimGirl::set_mapping_override`]. As in the
+/// common mappings of Xbox controllers, the D-pad is a hat, and
+/// [`Button::Misc1`], the paddles and [`Button::Touchpad`] aren't bound.
+const DEFAULT_MAPPING: &str = "Simulated Gamepad,a:b0,b:b1,x:b2,y:b3,\
+                               back:b4,guide:b5,start:b6,leftstick:b7,\
+                               rightstick:b8,leftshoulder:b9,\
+                               rightshoulder:b10,dpup:h0.1,dpright:h0.2,\
+                               dpdown:h0.4,dpleft:h0.8,leftx:a0,lefty:a1,\
+                               rightx:a2,righty:a3,lefttrigger:a4,\
+                               righttrigger:a5,";
+
+/// Simulated device of a [`SimGamepad`], see [`SimGirl::connect_device`].
+///
+/// The default device is a [`ControllerType::Virtual`] one without LED,
+/// rumble motors or sensors, so outputs fail on it as on a basic
+/// controller.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{Guid, SimDevice, SimGirl};
+/// let mut sim = SimGirl::new();
+/// let device = SimDevice::new().guid(Guid::from_bytes([1; 16])).led(true);
+/// let id = sim.connect_device(device);
+///
+/// let pad = sim.gamepad_mut(id).unwrap();
+/// pad.set_led(0, 0, 255)?;
+/// let pulse = Duration::from_millis(100);
+/// assert!(pad.set_rumble(0xffff, 0, pulse).is_err());
+/// # Ok::<(), girl::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(feature = "sensors", feature = "stubs")),
+    expect(missing_copy_implementations, reason = "not `Copy` with `sensors`")
+)]
+pub struct SimDevice {
+    /// [`Guid`] of the device.
+    guid: Guid,
+    /// Type of the device.
+    controller_type: ControllerType,
+    /// Whether the device has an RGB LED.
+    led: bool,
+    /// Whether the device has rumble motors.
+    rumble: bool,
+    /// Available sensors.
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    sensors: Vec<Sensor>,
+}
+
+impl Default for SimDevice {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimDevice {
+    /// Creates the default [`SimDevice`], see [`SimDevice`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            guid: Guid::from_bytes([0; 16]),
+            controller_type: ControllerType::Virtual,
+            led: false,
+            rumble: false,
+            #[cfg(any(feature = "sensors", feature = "stubs"))]
+            sensors: Vec::new(),
+        }
+    }
+
+    /// Sets the [`Guid`] of the device.
+    #[must_use]
+    #[inline]
+    pub const fn guid(mut self, guid: Guid) -> Self {
+        self.guid = guid;
+        self
+    }
+
+    /// Sets the [`ControllerType`] of the device, picking its
+    /// [`DefaultProfiles`] entry.
+    #[must_use]
+    #[inline]
+    pub const fn controller_type(
+        mut self,
+        controller_type: ControllerType,
+    ) -> Self {
+        self.controller_type = controller_type;
+        self
+    }
+
+    /// Sets whether the device has an RGB LED.
+    #[must_use]
+    #[inline]
+    pub const fn led(mut self, has_led: bool) -> Self {
+        self.led = has_led;
+        self
+    }
+
+    /// Sets whether the device has rumble motors.
+    #[must_use]
+    #[inline]
+    pub const fn rumble(mut self, has_rumble: bool) -> Self {
+        self.rumble = has_rumble;
+        self
+    }
+
+    /// Adds an available [`Sensor`].
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn sensor(mut self, sensor: Sensor) -> Self {
+        self.sensors.push(sensor);
+        self
+    }
+}
+
+/// Simulated [`Gamepad`] driven by a [`SimGirl`].
+///
+/// Reads input with the same [`InputProfile`] processing as a [`Gamepad`]
+/// does (deadzones, response curves, `y` inversion and remaps), starting
+/// from the raw values set through the [`SimGirl`].
+///
+/// [`Gamepad`]: crate::Gamepad
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimGamepad {
+    /// Instance ID.
+    id: GamepadId,
+    /// The simulated device, see [`SimGirl::connect_device`].
+    device: SimDevice,
+    /// SDL2 mapping, see [`SimGirl::set_mapping_override`].
+    mapping: String,
+    /// Raw input: physical [`Button`]s and values without deadzones.
+    raw: GamepadState,
+    /// Raw values of the extra joystick axes, see [`SimGirl::move_axis`].
+    axes: BTreeMap<u32, f64>,
+    /// Input settings.
+    profile: InputProfile,
+    /// Whether the [`profile`] was applied with [`apply_profile`] rather
+    /// than taken from the [`DefaultProfiles`].
+    ///
+    /// [`profile`]: Self::profile
+    /// [`apply_profile`]: Self::apply_profile
+    applied: bool,
+    /// [`Button`]s reported by the previous [`buttons_changed`] call.
+    ///
+    /// [`buttons_changed`]: Self::buttons_changed
+    button_tracker: ButtonTracker,
+    /// LED color, see [`SimGamepad::set_led`].
+    led: Option<Rgb>,
+    /// Number of LED colors set, see [`SimGamepad::led_sets`].
+    led_sets: u32,
+    /// Player index, see [`SimGamepad::set_player_index`].
+    player_index: Option<u32>,
+    /// Rumble last played, see [`SimGamepad::set_rumble`].
+    rumble: Option<(u16, u16, Duration)>,
+    /// How it is connected, see [`SimGamepad::connection_kind`].
+    connection: ConnectionKind,
+    /// Enabled sensors, see [`SimGamepad::enable_sensor`].
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    enabled_sensors: BTreeSet<Sensor>,
+    /// Preferred Joy-Con of the primary sensors.
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    preferred_side: JoyConSide,
+    /// Number of the next sensor enables failing, see
+    /// [`SimGirl::fail_sensor_enables`].
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    enable_failures: u32,
+    /// [`Sensor`]s failed to enable since the last
+    /// [`SimGirl::advance`].
+    #[cfg(feature = "sensors")]
+    failed_sensors: Vec<Sensor>,
+}
+
+#[expect(clippy::same_name_method, reason = "implements `GamepadRead`")]
+impl SimGamepad {
+    /// Creates a new connected [`SimGamepad`] at rest.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn new(id: GamepadId) -> Self {
+        Self {
+            id,
+            device: SimDevice::new(),
+            mapping: format!("{},{DEFAULT_MAPPING}", Guid::default()),
+            raw: GamepadState { connected: true, ..GamepadState::default() },
+            axes: BTreeMap::new(),
+            profile: InputProfile::new(),
+            applied: false,
+            button_tracker: ButtonTracker::new(),
+            led: None,
+            led_sets: 0,
+            player_index: None,
+            rumble: None,
+            connection: ConnectionKind::Unknown,
+            #[cfg(any(feature = "sensors", feature = "stubs"))]
+            enabled_sensors: BTreeSet::new(),
+            #[cfg(any(feature = "sensors", feature = "stubs"))]
+            preferred_side: JoyConSide::Right,
+            #[cfg(any(feature = "sensors", feature = "stubs"))]
+            enable_failures: 0,
+            #[cfg(feature = "sensors")]
+            failed_sensors: Vec::new(),
+        }
+    }
+
+    /// Gets the instance ID, see [`Gamepad::id`].
+    ///
+    /// [`Gamepad::id`]: crate::Gamepad::id
+    #[must_use]
+    #[inline]
+    pub const fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    /// Gets the [`Guid`] of the [`SimDevice`], see [`Gamepad::guid`].
+    ///
+    /// [`Gamepad::guid`]: crate::Gamepad::guid
+    #[must_use]
+    #[inline]
+    pub const fn guid(&self) -> Guid {
+        self.device.guid
+    }
+
+    /// Gets the [`ControllerType`] of the [`SimDevice`], see
+    /// [`Gamepad::controller_type`].
+    ///
+    /// [`Gamepad::controller_type`]: crate::Gamepad::controller_type
+    #[must_use]
+    #[inline]
+    pub const fn controller_type(&self) -> ControllerType {
+        self.device.controller_type
+    }
+
+    /// Gets how the [`SimGamepad`] is connected, inferred from its
+    /// [`Guid`] by the [`ConnectionHeuristics`] of the [`SimGirl`], see
+    /// [`Gamepad::connection_kind`].
+    ///
+    /// [`Gamepad::connection_kind`]: crate::Gamepad::connection_kind
+    #[must_use]
+    #[inline]
+    pub const fn connection_kind(&self) -> ConnectionKind {
+        self.connection
+    }
+
+    /// Gets the SDL2 mapping, see [`Gamepad::mapping`].
+    ///
+    /// [`Gamepad::mapping`]: crate::Gamepad::mapping
+    #[must_use]
+    #[inline]
+    pub fn mapping(&self) -> &str {
+        &self.mapping
+    }
+
+    /// Takes a [`MappingSnapshot`] of the mapping, see
+    /// [`Girl::mapping_snapshot`].
+    ///
+    /// A [`SimGamepad`] has no touchpads.
+    ///
+    /// [`Girl::mapping_snapshot`]: crate::Girl::mapping_snapshot
+    #[must_use]
+    #[inline]
+    pub fn mapping_snapshot(&self) -> MappingSnapshot {
+        let mut snapshot = MappingSnapshot::new(self.device.controller_type);
+        for button in Button::all().iter() {
+            if let Ok(bind) = self.bind_for_button(button)
+                && bind != InputBind::None
+            {
+                _ = snapshot.buttons.insert(button, bind);
+            }
+        }
+        snapshot.sticks =
+            [Stick::Left, Stick::Right].map(|stick| self.bind_for_stick(stick));
+        snapshot.triggers = [Trigger::Left, Trigger::Right]
+            .map(|trigger| self.bind_for_trigger(trigger));
+        snapshot
+    }
+
+    /// Gets the input of the underlying joystick the [`Button`] is read
+    /// from according to the mapping, see [`Gamepad::bind_for_button`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if not exactly one [`Button`] is
+    /// given.
+    ///
+    /// [`Gamepad::bind_for_button`]: crate::Gamepad::bind_for_button
+    #[inline]
+    pub fn bind_for_button(&self, button: Button) -> Result<InputBind, Error> {
+        check_single_button(button)?;
+        let name = button.mapping_name();
+        Ok(name.map_or(InputBind::None, |name| {
+            InputBind::in_mapping(&self.mapping, name)
+        }))
+    }
+
+    /// Gets the inputs of the underlying joystick the `x` and `y` axes of
+    /// the [`Stick`] are read from, see [`Gamepad::bind_for_stick`].
+    ///
+    /// [`Gamepad::bind_for_stick`]: crate::Gamepad::bind_for_stick
+    #[must_use]
+    #[inline]
+    pub fn bind_for_stick(&self, stick: Stick) -> [InputBind; 2] {
+        stick
+            .mapping_names()
+            .map(|name| InputBind::in_mapping(&self.mapping, name))
+    }
+
+    /// Gets the input of the underlying joystick the [`Trigger`] is read
+    /// from, see [`Gamepad::bind_for_trigger`].
+    ///
+    /// [`Gamepad::bind_for_trigger`]: crate::Gamepad::bind_for_trigger
+    #[must_use]
+    #[inline]
+    pub fn bind_for_trigger(&self, trigger: Trigger) -> InputBind {
+        InputBind::in_mapping(&self.mapping, trigger.mapping_name())
+    }
+
+    /// Checks if the [`SimGamepad`] is connected, see
+    /// [`Gamepad::connected`].
+    ///
+    /// [`Gamepad::connected`]: crate::Gamepad::connected
+    #[must_use]
+    #[inline]
+    pub const fn connected(&self) -> bool {
+        self.raw.connected
+    }
+
+    /// Gets the position of the [`Stick`], see [`Gamepad::stick`].
+    ///
+    /// Like on real hardware, values are kept in their ranges however far
+    /// the raw input goes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    /// sim.move_stick(id, Stick::Left, [-1.5, 2.0]);
+    /// sim.move_trigger(id, Trigger::Left, -0.5);
+    /// sim.move_trigger(id, Trigger::Right, 1.5);
+    ///
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert_eq!(pad.stick(Stick::Left), [-1.0, 1.0]);
+    /// assert_eq!(pad.trigger(Trigger::Left), 0.0);
+    /// assert_eq!(pad.trigger(Trigger::Right), 1.0);
+    /// ```
+    ///
+    /// [`Gamepad::stick`]: crate::Gamepad::stick
+    #[must_use]
+    #[inline]
+    pub fn stick(&self, stick: Stick) -> [f64; 2] {
+        clamp_stick(self.profile.stick(stick).apply(self.raw.stick(stick)))
+    }
+
+    /// Gets the value of the [`Trigger`], see [`Gamepad::trigger`].
+    ///
+    /// [`Gamepad::trigger`]: crate::Gamepad::trigger
+    #[must_use]
+    #[inline]
+    pub const fn trigger(&self, trigger: Trigger) -> f64 {
+        clamp_trigger(
+            self.profile.trigger(trigger).apply(self.raw.trigger(trigger)),
+        )
+    }
+
+    /// Gets the specified [`Button`]\(s) pressed, see [`Gamepad::buttons`].
+    ///
+    /// [`Gamepad::buttons`]: crate::Gamepad::buttons
+    #[must_use]
+    #[inline]
+    pub fn buttons(&self, buttons: Button) -> Button {
+        let pressed: Button = Button::all()
+            .iter()
+            .filter(|&button| self.pressed(button))
+            .map(|button| self.profile.remapped(button))
+            .collect();
+        pressed & buttons
+    }
+
+    /// Gets how far the specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::button_value`].
+    ///
+    /// # Examples
+    ///
+    /// An analog A button, e.g. of a `GameCube` controller adapter:
+    ///
+    /// ```
+    /// # use girl::{ButtonAxis, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    /// let mut profile = InputProfile::new();
+    /// profile.button_axes.push((Button::A, ButtonAxis::Joystick(6)));
+    /// sim.gamepad_mut(id).unwrap().apply_profile(&profile)?;
+    ///
+    /// sim.move_axis(id, 6, 0.3);
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert_eq!(pad.button_value(Button::A), 0.3);
+    /// assert!(!pad.buttons_pressed(Button::A));
+    ///
+    /// // pressed above the threshold, still with its analog value
+    /// sim.move_axis(id, 6, 0.7);
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert_eq!(pad.button_value(Button::A), 0.7);
+    /// assert!(pad.buttons_pressed(Button::A));
+    ///
+    /// // digital buttons report all or nothing
+    /// sim.press(id, Button::B);
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert_eq!(pad.button_value(Button::B), 1.0);
+    /// assert_eq!(pad.button_value(Button::X), 0.0);
+    /// assert_eq!(pad.button_value(Button::A | Button::B), 0.7);
+    ///
+    /// // sources of multiple buttons at once are rejected
+    /// let combo = Button::X | Button::Y;
+    /// profile.button_axes.push((combo, ButtonAxis::Joystick(7)));
+    /// let pad = sim.gamepad_mut(id).unwrap();
+    /// assert!(pad.apply_profile(&profile).is_err());
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Gamepad::button_value`]: crate::Gamepad::button_value
+    #[must_use]
+    #[inline]
+    pub fn button_value(&self, buttons: Button) -> f64 {
+        if buttons.is_empty() {
+            return 0.0;
+        }
+        buttons
+            .iter()
+            .map(|logical| {
+                Button::all()
+                    .iter()
+                    .filter(|&physical| {
+                        self.profile.remapped(physical) == logical
+                    })
+                    .map(|physical| self.physical_value(physical))
+                    .fold(0.0f64, f64::max)
+            })
+            .fold(1.0, f64::min)
+    }
+
+    /// Checks if all specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::buttons_pressed`].
+    ///
+    /// [`Gamepad::buttons_pressed`]: crate::Gamepad::buttons_pressed
+    #[must_use]
+    #[inline]
+    pub fn buttons_pressed(&self, buttons: Button) -> bool {
+        self.buttons(buttons) == buttons
+    }
+
+    /// Checks if all specified [`Button`]\(s) are released, see
+    /// [`Gamepad::buttons_released`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    /// sim.press(id, Button::A | Button::B);
+    /// let pad = sim.gamepad(id).unwrap();
+    ///
+    /// // (mask, pressed, released, none pressed, exactly)
+    /// let table = [
+    ///     (Button::empty(), true, true, true, false),
+    ///     (Button::A, true, false, false, false),
+    ///     (Button::X, false, true, true, false),
+    ///     (Button::A | Button::X, false, false, false, false),
+    ///     (Button::X | Button::Y, false, true, true, false),
+    ///     (Button::A | Button::B, true, false, false, true),
+    ///     (Button::all(), false, false, false, false),
+    /// ];
+    /// for (mask, pressed, released, none, exactly) in table {
+    ///     assert_eq!(pad.buttons_pressed(mask), pressed, "{mask:?}");
+    ///     assert_eq!(pad.buttons_released(mask), released, "{mask:?}");
+    ///     assert_eq!(pad.buttons_none_pressed(mask), none, "{mask:?}");
+    ///     assert_eq!(pad.buttons_exactly(mask), exactly, "{mask:?}");
+    /// }
+    ///
+    /// // with nothing held, everything is released
+    /// sim.release(id, Button::all());
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert!(pad.buttons_released(Button::all()));
+    /// assert!(pad.buttons_exactly(Button::empty()));
+    /// assert!(!pad.buttons_exactly(Button::A));
+    ///
+    /// // and with everything held, nothing is
+    /// sim.press(id, Button::all());
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert!(pad.buttons_exactly(Button::all()));
+    /// assert!(!pad.buttons_released(Button::A));
+    /// assert!(pad.buttons_released(Button::empty()));
+    /// ```
+    ///
+    /// [`Gamepad::buttons_released`]: crate::Gamepad::buttons_released
+    #[must_use]
+    #[inline]
+    pub fn buttons_released(&self, buttons: Button) -> bool {
+        !self.buttons(Button::all()).intersects(buttons)
+    }
+
+    /// Checks if none of the specified [`Button`]\(s) is pressed, see
+    /// [`Gamepad::buttons_none_pressed`].
+    ///
+    /// [`Gamepad::buttons_none_pressed`]:
+    ///     crate::Gamepad::buttons_none_pressed
+    #[must_use]
+    #[inline]
+    pub fn buttons_none_pressed(&self, buttons: Button) -> bool {
+        self.buttons_released(buttons)
+    }
+
+    /// Checks if exactly the specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::buttons_exactly`].
+    ///
+    /// [`Gamepad::buttons_exactly`]: crate::Gamepad::buttons_exactly
+    #[must_use]
+    #[inline]
+    pub fn buttons_exactly(&self, buttons: Button) -> bool {
+        self.buttons(Button::all()) == buttons
+    }
+
+    /// Gets the [`ButtonChanges`] since the previous call, see
+    /// [`Gamepad::buttons_changed`].
+    ///
+    /// [`Gamepad::buttons_changed`]: crate::Gamepad::buttons_changed
+    #[inline]
+    pub fn buttons_changed(&mut self) -> ButtonChanges {
+        let current = self.buttons(Button::all());
+        self.button_tracker.changes(current)
+    }
+
+    /// Takes a [`GamepadState`] snapshot, see [`Gamepad::state`].
+    ///
+    /// [`Gamepad::state`]: crate::Gamepad::state
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> GamepadState {
+        GamepadState {
+            connected: self.connected(),
+            buttons: self.buttons(Button::all()),
+            left_stick: self.stick(Stick::Left),
+            right_stick: self.stick(Stick::Right),
+            left_trigger: self.trigger(Trigger::Left),
+            right_trigger: self.trigger(Trigger::Right),
+        }
+    }
+
+    /// Captures a [`GamepadDump`], see [`Gamepad::dump`].
+    ///
+    /// A [`SimGamepad`] has no name, and the [`ControllerType`] and the
+    /// capabilities of its [`SimDevice`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    /// let mut profile = InputProfile::new();
+    /// profile.remap.push((Button::A, Button::B));
+    /// sim.gamepad_mut(id).unwrap().apply_profile(&profile)?;
+    /// sim.press(id, Button::A | Button::Start);
+    /// sim.move_stick(id, Stick::Left, [0.5, 0.05]);
+    /// sim.move_trigger(id, Trigger::Right, 0.25);
+    ///
+    /// let dump = sim.gamepad(id).unwrap().dump();
+    /// assert_eq!(dump.raw.buttons, Button::A | Button::Start);
+    /// assert_eq!(dump.state.buttons, Button::B | Button::Start);
+    ///
+    /// # #[cfg(all(
+    /// #     feature = "rumble",
+    /// #     feature = "touchpad",
+    /// #     feature = "sensors",
+    /// # ))]
+    /// assert_eq!(
+    ///     dump.to_string(),
+    ///     include_str!(concat!(
+    ///         env!("CARGO_MANIFEST_DIR"),
+    ///         "/tests/golden/gamepad_dump.txt",
+    ///     )),
+    /// );
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Gamepad::dump`]: crate::Gamepad::dump
+    #[must_use]
+    #[inline]
+    pub fn dump(&self) -> GamepadDump {
+        let physical = Button::all()
+            .iter()
+            .filter(|&button| self.pressed(button))
+            .collect();
+        GamepadDump {
+            id: self.id,
+            name: String::new(),
+            guid: self.device.guid,
+            serial: None,
+            controller_type: self.device.controller_type,
+            power: None,
+            has_led: self.device.led,
+            #[cfg(feature = "rumble")]
+            has_rumble: self.device.rumble,
+            #[cfg(feature = "rumble")]
+            has_rumble_triggers: false,
+            state: self.state(),
+            raw: GamepadState { buttons: physical, ..self.raw },
+            #[cfg(feature = "touchpad")]
+            touchpad: Ok(Vec::new()),
+            #[cfg(feature = "sensors")]
+            sensors: self
+                .device
+                .sensors
+                .iter()
+                .map(|&sensor| SensorDump {
+                    sensor,
+                    enabled: self.enabled_sensors.contains(&sensor),
+                    reading: Ok([0.0f64; 3]),
+                })
+                .collect(),
+            profile: self.profile.clone(),
+        }
+    }
+
+    /// Gets the current [`InputProfile`], see [`Gamepad::current_profile`].
+    ///
+    /// [`Gamepad::current_profile`]: crate::Gamepad::current_profile
+    #[must_use]
+    #[inline]
+    pub fn current_profile(&self) -> InputProfile {
+        self.profile.clone()
+    }
+
+    /// Applies all the settings from an [`InputProfile`], see
+    /// [`Gamepad::apply_profile`].
+    ///
+    /// Like on a [`Gamepad`], the profile takes precedence over the
+    /// [`DefaultProfiles`] of the [`SimGamepad`]s connected later with the
+    /// same [`Guid`], see [`SimGirl::set_default_profile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the [`InputProfile`] is invalid.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    /// [`Gamepad::apply_profile`]: crate::Gamepad::apply_profile
+    #[inline]
+    pub fn apply_profile(
+        &mut self,
+        profile: &InputProfile,
+    ) -> Result<(), Error> {
+        profile.validate()?;
+        self.profile.clone_from(profile);
+        self.applied = true;
+        Ok(())
+    }
+
+    /// Checks if the [`SimDevice`] has an RGB LED, see [`Gamepad::has_led`].
+    ///
+    /// [`Gamepad::has_led`]: crate::Gamepad::has_led
+    #[must_use]
+    #[inline]
+    pub const fn has_led(&self) -> bool {
+        self.device.led
+    }
+
+    /// Gets the LED color last set, if any.
+    #[must_use]
+    #[inline]
+    pub const fn led(&self) -> Option<Rgb> {
+        self.led
+    }
+
+    /// Gets how many times the LED color was set, e.g. to check that
+    /// [`LedBindings`] don't flood the controller, see
+    /// [`SimGirl::add_led_binding`].
+    #[must_use]
+    #[inline]
+    pub const fn led_sets(&self) -> u32 {
+        self.led_sets
+    }
+
+    /// Sets the LED color, see [`Gamepad::set_led`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `"set_led"` if the
+    /// [`SimGamepad`] is disconnected or the [`SimDevice`] has no LED.
+    ///
+    /// [`Gamepad::set_led`]: crate::Gamepad::set_led
+    #[inline]
+    pub fn set_led(
+        &mut self,
+        red: u8,
+        green: u8,
+        blue: u8,
+    ) -> Result<(), Error> {
+        self.output("set_led", self.device.led)?;
+        self.led = Some(Rgb::new(red, green, blue));
+        self.led_sets = self.led_sets.saturating_add(1);
+        Ok(())
+    }
+
+    /// Gets the player index, if set, see [`Gamepad::player_index`].
+    ///
+    /// [`Gamepad::player_index`]: crate::Gamepad::player_index
+    #[must_use]
+    #[inline]
+    pub const fn player_index(&self) -> Option<u32> {
+        self.player_index
+    }
+
+    /// Sets or clears the player index, see [`Gamepad::set_player_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `"set_player_index"` if the
+    /// [`SimGamepad`] is disconnected.
+    ///
+    /// [`Gamepad::set_player_index`]: crate::Gamepad::set_player_index
+    #[inline]
+    pub fn set_player_index(
+        &mut self,
+        index: Option<u32>,
+    ) -> Result<(), Error> {
+        self.output("set_player_index", true)?;
+        self.player_index = index;
+        Ok(())
+    }
+
+    /// Checks if the [`SimDevice`] has rumble motors, see
+    /// [`Gamepad::has_rumble`].
+    ///
+    /// [`Gamepad::has_rumble`]: crate::Gamepad::has_rumble
+    #[must_use]
+    #[inline]
+    pub const fn has_rumble(&self) -> bool {
+        self.device.rumble
+    }
+
+    /// Gets the low and high frequency intensities and the duration of the
+    /// rumble last played, if any.
+    #[must_use]
+    #[inline]
+    pub const fn rumble(&self) -> Option<(u16, u16, Duration)> {
+        self.rumble
+    }
+
+    /// Plays a rumble, see [`Gamepad::set_rumble`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `"set_rumble"` if the
+    /// [`SimGamepad`] is disconnected or the [`SimDevice`] has no rumble
+    /// motors, hinting at Bluetooth if the [`SimGamepad`] is on it, see
+    /// [`ConnectionKind::hint`].
+    ///
+    /// [`Gamepad::set_rumble`]: crate::Gamepad::set_rumble
+    #[inline]
+    pub fn set_rumble(
+        &mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        self.output("set_rumble", self.device.rumble)
+            .map_err(|err| self.connection.hint(err, "rumble"))?;
+        self.rumble =
+            Some((low_frequency_rumble, high_frequency_rumble, duration));
+        Ok(())
+    }
+
+    /// Checks if the [`SimDevice`] has a [`Sensor`], see
+    /// [`Gamepad::has_sensor`].
+    ///
+    /// [`Gamepad::has_sensor`]: crate::Gamepad::has_sensor
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn has_sensor(&self, sensor: Sensor) -> bool {
+        self.device.sensors.contains(&sensor)
+    }
+
+    /// Lists the [`Sensor`]s the [`SimDevice`] has, see
+    /// [`Gamepad::available_sensors`].
+    ///
+    /// [`Gamepad::available_sensors`]: crate::Gamepad::available_sensors
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn available_sensors(&self) -> Vec<Sensor> {
+        Sensor::ALL
+            .into_iter()
+            .filter(|&sensor| self.has_sensor(sensor))
+            .collect()
+    }
+
+    /// Picks the gyroscope to use, see [`Gamepad::primary_gyroscope`].
+    ///
+    /// Returns [`None`] if the [`SimDevice`] has no gyroscope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{JoyConSide, Sensor, SimDevice, SimGirl};
+    /// let mut sim = SimGirl::new();
+    /// let joy_cons = SimDevice::new()
+    ///     .sensor(Sensor::LeftGyroscope)
+    ///     .sensor(Sensor::RightGyroscope);
+    /// let id = sim.connect_device(joy_cons);
+    /// let pad = sim.gamepad_mut(id).unwrap();
+    ///
+    /// pad.set_preferred_side(JoyConSide::Left);
+    /// if let Some(gyroscope) = pad.primary_gyroscope() {
+    ///     pad.enable_sensor(gyroscope)?;
+    /// }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Gamepad::primary_gyroscope`]: crate::Gamepad::primary_gyroscope
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn primary_gyroscope(&self) -> Option<Sensor> {
+        let available = self.available_sensors();
+        Sensor::primary_gyroscope(&available, self.preferred_side)
+    }
+
+    /// Picks the accelerometer to use, the same way as
+    /// [`primary_gyroscope`] does.
+    ///
+    /// Returns [`None`] if the [`SimDevice`] has no accelerometer.
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn primary_accelerometer(&self) -> Option<Sensor> {
+        let available = self.available_sensors();
+        Sensor::primary_accelerometer(&available, self.preferred_side)
+    }
+
+    /// Gets the Joy-Con preferred by [`primary_gyroscope`] and
+    /// [`primary_accelerometer`], see [`Gamepad::preferred_side`].
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    /// [`primary_accelerometer`]: Self::primary_accelerometer
+    /// [`Gamepad::preferred_side`]: crate::Gamepad::preferred_side
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub const fn preferred_side(&self) -> JoyConSide {
+        self.preferred_side
+    }
+
+    /// Sets the Joy-Con preferred by [`primary_gyroscope`] and
+    /// [`primary_accelerometer`], see [`Gamepad::set_preferred_side`].
+    ///
+    /// [`primary_gyroscope`]: Self::primary_gyroscope
+    /// [`primary_accelerometer`]: Self::primary_accelerometer
+    /// [`Gamepad::set_preferred_side`]: crate::Gamepad::set_preferred_side
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[inline]
+    pub const fn set_preferred_side(&mut self, side: JoyConSide) {
+        self.preferred_side = side;
+    }
+
+    /// Enables a [`Sensor`], see [`Gamepad::enable_sensor`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `"enable_sensor"` if the
+    /// [`SimGamepad`] is disconnected, the [`SimDevice`] doesn't have the
+    /// [`Sensor`], or it isn't ready yet, see
+    /// [`SimGirl::fail_sensor_enables`], hinting at Bluetooth if the
+    /// [`SimGamepad`] is on it. The failed enable is retried by
+    /// the [`SimGirl`] if [`SimGirl::set_sensor_retry_window`] says so.
+    ///
+    /// [`Gamepad::enable_sensor`]: crate::Gamepad::enable_sensor
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[inline]
+    pub fn enable_sensor(&mut self, sensor: Sensor) -> Result<(), Error> {
+        let enabled = self.try_enable_sensor(sensor);
+        #[cfg(feature = "sensors")]
+        if enabled.is_err() {
+            self.failed_sensors.push(sensor);
+        }
+        enabled
+    }
+
+    /// Checks if a [`Sensor`] is enabled, see [`Gamepad::sensor_enabled`].
+    ///
+    /// [`Gamepad::sensor_enabled`]: crate::Gamepad::sensor_enabled
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[must_use]
+    #[inline]
+    pub fn sensor_enabled(&self, sensor: Sensor) -> bool {
+        self.enabled_sensors.contains(&sensor)
+    }
+
+    /// Applies every property set in the [`PadSetup`], see
+    /// [`Gamepad::apply`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{PadSetup, SetupOp, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    /// let pad = sim.gamepad_mut(id).unwrap();
+    ///
+    /// let setup = PadSetup::new().led(Rgb::new(0, 0, 255)).player_index(0);
+    /// let report = pad.apply(&setup);
+    /// // the default device has no LED
+    /// assert!(report.failures().any(|(op, _)| op == SetupOp::Led));
+    /// assert_eq!(pad.player_index(), Some(0));
+    /// ```
+    ///
+    /// [`Gamepad::apply`]: crate::Gamepad::apply
+    #[inline]
+    pub fn apply(&mut self, setup: &PadSetup) -> SetupReport {
+        setup.apply_to(self)
+    }
+
+    /// Checks that an output `op` can be sent to the [`SimDevice`], failing
+    /// with the [`Error::SdlError`] a [`Gamepad`] would.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `op` if the [`SimGamepad`] is
+    /// disconnected or the output isn't `supported`.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    const fn output(
+        &self,
+        op: &'static str,
+        supported: bool,
+    ) -> Result<(), Error> {
+        if !self.raw.connected {
+            return Err(Error::sdl_static(
+                op,
+                ErrorCode::Disconnected,
+                Error::DISCONNECTED,
+            ));
+        }
+        if !supported {
+            return Err(Error::sdl_static(
+                op,
+                ErrorCode::NotSupported,
+                Error::UNSUPPORTED,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Enables a [`Sensor`] without queueing it for retries on failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::SdlError`] tagged `"enable_sensor"` if the
+    /// [`SimGamepad`] is disconnected, the [`SimDevice`] doesn't have the
+    /// [`Sensor`], or it isn't ready yet, hinting at Bluetooth if the
+    /// [`SimGamepad`] is on it.
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    fn try_enable_sensor(&mut self, sensor: Sensor) -> Result<(), Error> {
+        let connection = self.connection;
+        let hint = |err| connection.hint(err, "sensor rates");
+        self.output("enable_sensor", self.has_sensor(sensor)).map_err(hint)?;
+        if self.enable_failures > 0 {
+            self.enable_failures = self.enable_failures.saturating_sub(1);
+            return Err(hint(Error::sdl_static(
+                "enable_sensor",
+                ErrorCode::Sdl,
+                "sensor not ready",
+            )));
+        }
+        _ = self.enabled_sensors.insert(sensor);
+        Ok(())
+    }
+
+    /// Gets the [`Button`]s the mapping binds to the joystick input.
+    fn bound(&self, bind: InputBind) -> Button {
+        Button::all()
+            .iter()
+            .filter(|button| {
+                button.mapping_name().is_some_and(|name| {
+                    InputBind::in_mapping(&self.mapping, name) == bind
+                })
+            })
+            .collect()
+    }
+
+    /// Checks if the physical single-bit [`Button`] is pressed.
+    fn pressed(&self, button: Button) -> bool {
+        if let Some(source) = self.profile.button_axis(button) {
+            return self.axis_value(source) >= self.profile.button_threshold;
+        }
+        self.raw.buttons.contains(button)
+    }
+
+    /// Gets how far the physical single-bit [`Button`] is pressed.
+    fn physical_value(&self, button: Button) -> f64 {
+        match self.profile.button_axis(button) {
+            Some(source) => self.axis_value(source),
+            None if self.pressed(button) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Reads an analog [`Button`] source, in the range `[0.0, 1.0]`.
+    fn axis_value(&self, source: ButtonAxis) -> f64 {
+        let raw = match source {
+            ButtonAxis::Trigger(trigger) => self.raw.trigger(trigger),
+            ButtonAxis::Joystick(index) => {
+                self.axes.get(&index).copied().unwrap_or(0.0f64)
+            }
+        };
+        raw.clamp(0.0, 1.0)
+    }
+
+    /// Updates the raw input with an input [`Event`] of this
+    /// [`SimGamepad`], returning whether anything changed.
+    fn apply_event(&mut self, event: &Event) -> bool {
+        if !self.raw.connected {
+            return false;
+        }
+        let before = self.raw;
+        if let Event::ControllerStickMotion { stick, offset, .. } = *event {
+            match stick {
+                Stick::Left => self.raw.left_stick = offset,
+                Stick::Right => self.raw.right_stick = offset,
+            }
+        } else if let Event::ControllerTriggerMotion {
+            trigger, offset, ..
+        } = *event
+        {
+            match trigger {
+                Trigger::Left => self.raw.left_trigger = offset,
+                Trigger::Right => self.raw.right_trigger = offset,
+            }
+        } else if let Event::ControllerButtonDown { button, .. }
+        | Event::ControllerButtonDownWithState { button, .. } = *event
+        {
+            self.raw.buttons.insert(button);
+        } else if let Event::ControllerButtonUp { button, .. } = *event {
+            self.raw.buttons.remove(button);
+        } else {
+            return false;
+        }
+        self.raw != before
+    }
+
+    /// Disconnects the [`SimGamepad`], resetting its input, returning
+    /// whether it was connected.
+    #[expect(clippy::single_call_fn, reason = "extracted for readability")]
+    fn disconnect(&mut self) -> bool {
+        self.axes.clear();
+        mem::take(&mut self.raw).connected
+    }
+}
+
+/// Simulated [`Girl`] with a virtual clock, for running gameplay logic
+/// against scripted input, e.g. in CI.
+///
+/// Input is given either by imperative calls, applied right away, or by a
+/// script of [`Event`]s [`schedule`]d at virtual timestamps and applied by
+/// [`advance`]. Either way the [`SimGamepad`]s are updated and the
+/// [`Event`]s are queued for [`event`], so the same event handling code,
+/// generic over [`GamepadSource`], works with both [`Girl`] and [`SimGirl`].
+/// Like SDL2, [`SimGirl`] only queues [`Event`]s that actually change the
+/// input.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{SimGirl, prelude::*};
+/// let mut sim = SimGirl::new();
+/// let id = sim.connect();
+/// assert!(matches!(sim.event(), Some(Event::ControllerDeviceAdded { .. })));
+///
+/// sim.press(id, Button::A);
+/// sim.move_stick(id, Stick::Left, [0.05, -1.0]);
+/// let pad = sim.gamepad_mut(id).unwrap();
+/// assert!(pad.buttons_changed().pressed.contains(Button::A));
+/// assert!(pad.buttons_changed().is_empty());
+/// // the deadzone applies just like on real hardware
+/// assert_eq!(pad.stick(Stick::Left), [0.0, -1.0]);
+///
+/// // scripted input
+/// let which = id.raw();
+/// let release = Event::ControllerButtonUp { which, button: Button::A };
+/// sim.schedule(Duration::from_millis(500), release);
+/// sim.advance(Duration::from_millis(499));
+/// assert!(sim.gamepad(id).unwrap().buttons_pressed(Button::A));
+/// sim.advance(Duration::from_millis(1));
+/// assert!(!sim.gamepad(id).unwrap().buttons_pressed(Button::A));
+///
+/// sim.disconnect(id);
+/// assert!(!sim.gamepad(id).unwrap().connected());
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`schedule`]: Self::schedule
+/// [`advance`]: Self::advance
+/// [`event`]: Self::event
+#[derive(Debug)]
+pub struct SimGirl {
+    /// Real time of the creation, the origin of the virtual clock for
+    /// the [`LedBindings`] and the [`SensorRetry`].
+    epoch: Instant,
+    /// Virtual time elapsed since creation.
+    now: Duration,
+    /// Instance ID of the next connected [`SimGamepad`].
+    next_id: u32,
+    /// Connected and disconnected [`SimGamepad`]s.
+    pads: BTreeMap<GamepadId, SimGamepad>,
+    /// Scheduled [`Event`]s, ordered by their timestamps.
+    script: VecDeque<(Duration, Event)>,
+    /// Sequence number of the next queued [`Event`].
+    next_seq: u64,
+    /// Queued [`Event`]s, see [`SimGirl::dropped_events`].
+    events: EventQueue<EVENT_CAPACITY>,
+    /// SDL2 mappings without the GUID, see
+    /// [`SimGirl::set_mapping_override`].
+    mapping_overrides: BTreeMap<Guid, String>,
+    /// Profiles of new connections, see [`SimGirl::set_default_profile`].
+    defaults: DefaultProfiles,
+    /// Colors of the LEDs, see [`SimGirl::add_led_binding`].
+    led_bindings: LedBindings,
+    /// Why the latest open failed, see [`SimGirl::last_open_error`].
+    last_open_error: LastOpenError,
+    /// Tells how [`SimGamepad`]s are connected, see
+    /// [`SimGirl::set_connection_heuristics`].
+    connection: ConnectionHeuristics,
+    /// [`SimGamepad`]s connected at the latest [`SimGirl::reset_subsystem`],
+    /// reopened by [`SimGirl::reinitialize`].
+    reset: Vec<GamepadId>,
+    /// Retries failed sensor enables, see
+    /// [`SimGirl::set_sensor_retry_window`].
+    #[cfg(feature = "sensors")]
+    sensor_retry: SensorRetry,
+}
+
+impl Default for SimGirl {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[expect(clippy::same_name_method, reason = "implements `GamepadSource`")]
+impl SimGirl {
+    /// Creates a new [`SimGirl`] without [`SimGamepad`]s, with the built-in
+    /// [`DefaultProfiles`].
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            now: Duration::ZERO,
+            next_id: 0,
+            pads: BTreeMap::new(),
+            script: VecDeque::new(),
+            next_seq: 0,
+            events: EventQueue::new(),
+            mapping_overrides: BTreeMap::new(),
+            defaults: DefaultProfiles::new(),
+            led_bindings: LedBindings::default(),
+            last_open_error: LastOpenError::default(),
+            connection: ConnectionHeuristics::new(),
+            reset: Vec::new(),
+            #[cfg(feature = "sensors")]
+            sensor_retry: SensorRetry::default(),
+        }
+    }
+
+    /// Gets the virtual time elapsed since creation.
+    #[must_use]
+    #[inline]
+    pub const fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Connects a new [`SimGamepad`], returning its instance ID.
+    #[inline]
+    pub fn connect(&mut self) -> GamepadId {
+        self.connect_with_guid(Guid::default())
+    }
+
+    /// Connects a new [`SimGamepad`] of the device with the [`Guid`],
+    /// returning its instance ID.
+    #[inline]
+    pub fn connect_with_guid(&mut self, guid: Guid) -> GamepadId {
+        self.connect_device(SimDevice::new().guid(guid))
+    }
+
+    /// Connects a new [`SimGamepad`] of the [`SimDevice`], returning its
+    /// instance ID.
+    ///
+    /// The [`SimGamepad`] starts with the [`InputProfile`] last applied to
+    /// a [`SimGamepad`] with the same [`Guid`], or else its
+    /// [`DefaultProfiles`] entry, see [`set_default_profile`].
+    ///
+    /// [`set_default_profile`]: Self::set_default_profile
+    #[inline]
+    pub fn connect_device(&mut self, device: SimDevice) -> GamepadId {
+        let id = GamepadId::from_raw(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.apply(Event::ControllerDeviceAdded { which: id.raw() });
+        let mapping = self.mapping(device.guid);
+        let profile = self.initial_profile(&device);
+        let connection = self.connection.infer(device.guid, "", None);
+        if let Some(pad) = self.pads.get_mut(&id) {
+            (pad.device, pad.mapping, pad.profile) = (device, mapping, profile);
+            pad.connection = connection;
+        }
+        id
+    }
+
+    /// Disconnects the [`SimGamepad`], which keeps reporting no input.
+    #[inline]
+    pub fn disconnect(&mut self, id: GamepadId) {
+        self.apply(Event::ControllerDeviceRemoved { which: id.raw() });
+    }
+
+    /// Gets a [`SimGamepad`], connected or not.
+    #[must_use]
+    #[inline]
+    pub fn gamepad(&self, id: GamepadId) -> Option<&SimGamepad> {
+        self.pads.get(&id)
+    }
+
+    /// Gets a [`SimGamepad`] mutably, connected or not.
+    #[must_use]
+    #[inline]
+    pub fn gamepad_mut(&mut self, id: GamepadId) -> Option<&mut SimGamepad> {
+        self.pads.get_mut(&id)
+    }
+
+    /// Opens the connected [`SimGamepad`] at the device `index`, counting
+    /// in connection order, as an independent copy, see [`Girl::gamepad`].
+    ///
+    /// Returns [`None`] if no [`SimGamepad`] is connected at the `index`,
+    /// see [`last_open_error`] for why.
+    ///
+    /// [`Girl::gamepad`]: crate::Girl::gamepad
+    /// [`last_open_error`]: Self::last_open_error
+    #[must_use]
+    #[inline]
+    pub fn open(&self, index: u32) -> Option<SimGamepad> {
+        let result = usize::try_from(index)
+            .ok()
+            .and_then(|nth| {
+                self.pads.values().filter(|pad| pad.connected()).nth(nth)
+            })
+            .cloned()
+            .ok_or(Error::sdl_static(
+                "gamepad",
+                ErrorCode::Sdl,
+                "no gamepad at that device index",
+            ));
+        self.last_open_error.record(index, result)
+    }
+
+    /// Gets why the latest [`open`] failed, see [`Girl::last_open_error`].
+    ///
+    /// Cleared by the next successful [`open`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::SimGirl;
+    /// let sim = SimGirl::new();
+    /// if sim.open(0).is_none()
+    ///     && let Some(err) = sim.last_open_error()
+    /// {
+    ///     println!("no gamepad: {err}");
+    /// }
+    /// ```
+    ///
+    /// [`open`]: Self::open
+    /// [`Girl::last_open_error`]: crate::Girl::last_open_error
+    #[must_use]
+    #[inline]
+    pub fn last_open_error(&self) -> Option<Error> {
+        self.last_open_error.get()
+    }
+
+    /// Takes a [`GamepadState`] snapshot of every connected [`SimGamepad`]
+    /// into `out`, see [`Girl::snapshot_all`]. A [`SimGamepad`] goes to
+    /// the slot of its raw instance ID, its connection order, which stays
+    /// [`None`] once it is disconnected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let ids = [sim.connect(), sim.connect(), sim.connect()];
+    /// sim.press(ids[1], Button::A);
+    ///
+    /// let mut players = [None; 4];
+    /// assert_eq!(sim.snapshot_all(&mut players), 3);
+    /// let pressed = |state: Option<GamepadState>| {
+    ///     state.is_some_and(|state| state.buttons_pressed(Button::A))
+    /// };
+    /// assert!(pressed(players[1]));
+    /// assert!(players[3].is_none());
+    ///
+    /// // a disconnected slot stays empty, the others keep theirs
+    /// sim.disconnect(ids[0]);
+    /// assert_eq!(sim.snapshot_all(&mut players), 2);
+    /// assert!(players[0].is_none());
+    /// assert!(pressed(players[1]));
+    ///
+    /// // a short slice only gets the slots fitting in it
+    /// let mut short = [None; 2];
+    /// assert_eq!(sim.snapshot_all(&mut short), 1);
+    /// assert_eq!(short, [None, players[1]]);
+    /// assert_eq!(sim.snapshot_all_vec(), players[..3]);
+    /// ```
+    ///
+    /// [`Girl::snapshot_all`]: crate::Girl::snapshot_all
+    #[inline]
+    pub fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+        let slot = |pad: &SimGamepad| usize::try_from(pad.id.raw()).ok();
+        fill_slots(out, self.pads.values(), slot, |pad| {
+            Some(pad.state()).filter(|state| state.connected)
+        })
+    }
+
+    /// Takes a [`GamepadState`] snapshot of every connected [`SimGamepad`]
+    /// like [`snapshot_all`], into a [`Vec`] with a slot per [`SimGamepad`]
+    /// ever connected.
+    ///
+    /// [`snapshot_all`]: Self::snapshot_all
+    #[must_use]
+    #[inline]
+    pub fn snapshot_all_vec(&self) -> Vec<Option<GamepadState>> {
+        let mut out = vec![None; self.pads.len()];
+        _ = self.snapshot_all(&mut out);
+        out
+    }
+
+    /// Presses the [`Button`]\(s) on the [`SimGamepad`].
+    #[inline]
+    pub fn press(&mut self, id: GamepadId, buttons: Button) {
+        for button in buttons.iter() {
+            self.apply(Event::ControllerButtonDown { which: id.raw(), button });
+        }
+    }
+
+    /// Releases the [`Button`]\(s) on the [`SimGamepad`].
+    #[inline]
+    pub fn release(&mut self, id: GamepadId, buttons: Button) {
+        for button in buttons.iter() {
+            self.apply(Event::ControllerButtonUp { which: id.raw(), button });
+        }
+    }
+
+    /// Presses the button of the underlying joystick with the `index` on the
+    /// [`SimGamepad`], pressing the [`Button`]\(s) its mapping binds to it.
+    ///
+    /// Nothing happens if no [`Button`] is bound to it, see
+    /// [`set_mapping_override`].
+    ///
+    /// [`set_mapping_override`]: Self::set_mapping_override
+    #[inline]
+    pub fn press_joystick_button(&mut self, id: GamepadId, index: u8) {
+        let bind = InputBind::Button(index);
+        if let Some(buttons) = self.pads.get(&id).map(|pad| pad.bound(bind)) {
+            self.press(id, buttons);
+        }
+    }
+
+    /// Releases the button of the underlying joystick with the `index` on the
+    /// [`SimGamepad`], see [`press_joystick_button`].
+    ///
+    /// [`press_joystick_button`]: Self::press_joystick_button
+    #[inline]
+    pub fn release_joystick_button(&mut self, id: GamepadId, index: u8) {
+        let bind = InputBind::Button(index);
+        if let Some(buttons) = self.pads.get(&id).map(|pad| pad.bound(bind)) {
+            self.release(id, buttons);
+        }
+    }
+
+    /// Moves the [`Stick`] of the [`SimGamepad`] to the raw `position`,
+    /// before the deadzone.
+    #[inline]
+    pub fn move_stick(
+        &mut self,
+        id: GamepadId,
+        stick: Stick,
+        position: [f64; 2],
+    ) {
+        let which = id.raw();
+        let offset = position;
+        self.apply(Event::ControllerStickMotion { which, stick, offset });
+    }
+
+    /// Moves the [`Trigger`] of the [`SimGamepad`] to the raw `value`,
+    /// before the deadzone.
+    #[inline]
+    pub fn move_trigger(
+        &mut self,
+        id: GamepadId,
+        trigger: Trigger,
+        value: f64,
+    ) {
+        let (which, offset) = (id.raw(), value);
+        self.apply(Event::ControllerTriggerMotion { which, trigger, offset });
+    }
+
+    /// Moves an extra joystick axis of the [`SimGamepad`] to the raw `value`,
+    /// see [`ButtonAxis::Joystick`].
+    ///
+    /// No [`Event`] is queued, as SDL2 reports no game controller events
+    /// for such axes either.
+    #[inline]
+    pub fn move_axis(&mut self, id: GamepadId, index: u32, value: f64) {
+        if let Some(pad) = self.pads.get_mut(&id)
+            && pad.connected()
+        {
+            _ = pad.axes.insert(index, value);
+        }
+    }
+
+    /// Schedules an [`Event`] to be applied once the virtual clock reaches
+    /// `at`, after the [`Event`]s already scheduled for the same time.
+    ///
+    /// Stick and trigger motions carry raw values, before the deadzone.
+    /// Device events connect and disconnect [`SimGamepad`]s.
+    #[inline]
+    pub fn schedule(&mut self, at: Duration, event: Event) {
+        let index = self.script.partition_point(|&(time, _)| time <= at);
+        self.script.insert(index, (at, event));
+    }
+
+    /// Advances the virtual clock by `dt`, applying the scheduled [`Event`]s
+    /// that became due, in order, then retrying the failed sensor enables
+    /// due, see [`set_sensor_retry_window`], and setting the LED colors of
+    /// the bindings, see [`add_led_binding`].
+    ///
+    /// [`set_sensor_retry_window`]: Self::set_sensor_retry_window
+    /// [`add_led_binding`]: Self::add_led_binding
+    #[inline]
+    pub fn advance(&mut self, dt: Duration) {
+        self.now = self.now.saturating_add(dt);
+        while let Some(&(at, event)) = self.script.front()
+            && at <= self.now
+        {
+            _ = self.script.pop_front();
+            self.apply(event);
+        }
+        #[cfg(feature = "sensors")]
+        self.retry_sensors();
+        self.bind_leds();
+    }
+
+    /// Makes the next `count` [`SimGamepad::enable_sensor`] calls of the
+    /// [`SimGamepad`] fail as if the controller were still initializing,
+    /// e.g. a Bluetooth `DualSense` right after connecting.
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "sensors", feature = "stubs"))))]
+    #[inline]
+    pub fn fail_sensor_enables(&mut self, id: GamepadId, count: u32) {
+        if let Some(pad) = self.pads.get_mut(&id) {
+            pad.enable_failures = count;
+        }
+    }
+
+    /// Retries failed [`SimGamepad::enable_sensor`] calls on every
+    /// [`advance`] for `window` of virtual time, or stops retrying if
+    /// [`None`], the default, see [`Girl::set_sensor_retry_window`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{Sensor, SimDevice, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// sim.set_sensor_retry_window(Some(Duration::from_secs(1)));
+    /// let id = sim.connect_device(SimDevice::new().sensor(Sensor::Gyroscope));
+    /// sim.fail_sensor_enables(id, 1);
+    ///
+    /// let pad = sim.gamepad_mut(id).unwrap();
+    /// assert!(pad.enable_sensor(Sensor::Gyroscope).is_err());
+    /// // queued on the next advance, and retried 100 ms later
+    /// sim.advance(Duration::from_millis(100));
+    /// sim.advance(Duration::from_millis(100));
+    /// let pad = sim.gamepad(id).unwrap();
+    /// assert!(pad.sensor_enabled(Sensor::Gyroscope));
+    /// ```
+    ///
+    /// [`advance`]: Self::advance
+    /// [`Girl::set_sensor_retry_window`]: crate::Girl::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[inline]
+    pub fn set_sensor_retry_window(&mut self, window: Option<Duration>) {
+        self.sensor_retry =
+            SensorRetry::new(window, SensorRetry::DEFAULT_INTERVAL);
+    }
+
+    /// Gets the window set with [`set_sensor_retry_window`].
+    ///
+    /// [`set_sensor_retry_window`]: Self::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub const fn sensor_retry_window(&self) -> Option<Duration> {
+        self.sensor_retry.window()
+    }
+
+    /// Simulates a controller subsystem reset, e.g. a driver reset, queueing
+    /// [`Event::SubsystemReset`] as [`Girl`] reports it.
+    ///
+    /// The connected [`SimGamepad`]s keep their instance IDs until
+    /// [`reinitialize`] reopens them. Disconnect some in between to
+    /// simulate devices that don't come back.
+    ///
+    /// [`Girl`]: crate::Girl
+    /// [`reinitialize`]: Self::reinitialize
+    #[inline]
+    pub fn reset_subsystem(&mut self) {
+        self.reset = self
+            .pads
+            .values()
+            .filter(|pad| pad.connected())
+            .map(SimGamepad::id)
+            .collect();
+        self.queue(Event::SubsystemReset);
+    }
+
+    /// Recovers from the latest [`reset_subsystem`], see
+    /// [`Girl::reinitialize`].
+    ///
+    /// Every [`SimGamepad`] connected at the reset and still connected is
+    /// reopened with a new instance ID, carrying its [`InputProfile`] over
+    /// by [`Guid`]. The others are reported lost. The previous instances
+    /// are left disconnected, without an [`Event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    ///
+    /// sim.reset_subsystem();
+    /// let report = sim.reinitialize();
+    /// for &(previous, new) in &report.recovered {
+    ///     println!("controller {previous} is now {new}");
+    /// }
+    /// assert!(report.is_complete());
+    /// ```
+    ///
+    /// [`reset_subsystem`]: Self::reset_subsystem
+    /// [`Girl::reinitialize`]: crate::Girl::reinitialize
+    #[inline]
+    pub fn reinitialize(&mut self) -> RecoveryReport {
+        let mut previous = Vec::new();
+        let mut reopened = Vec::new();
+        for id in mem::take(&mut self.reset) {
+            let Some(pad) = self.pads.get_mut(&id) else {
+                continue;
+            };
+            let (device, guid) = (pad.device.clone(), pad.device.guid);
+            previous.push((id, guid, pad.profile.clone()));
+            if !pad.connected() {
+                continue;
+            }
+            let (raw, axes) =
+                (mem::take(&mut pad.raw), mem::take(&mut pad.axes));
+            let new = self.connect_device(device);
+            if let Some(new_pad) = self.pads.get_mut(&new) {
+                (new_pad.raw, new_pad.axes) = (raw, axes);
+            }
+            reopened.push((new, guid));
+        }
+        let pads = &mut self.pads;
+        let report = RecoveryReport::pair(previous, reopened, |id, profile| {
+            if let Some(pad) = pads.get_mut(&id) {
+                pad.profile = profile;
+            }
+        });
+        self.led_bindings.reassign(&report.recovered);
+        report
+    }
+
+    /// Sets the default [`InputProfile`] of the [`ControllerType`], applied to
+    /// [`SimGamepad`]s connected from now on, see
+    /// [`Girl::set_default_profile`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidProfile`] if the `profile` is invalid, see
+    /// [`InputProfile::validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{SimDevice, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let mut profile = InputProfile::new();
+    /// profile.left_stick.deadzone = 0.2;
+    /// sim.set_default_profile(ControllerType::Ps5, profile.clone())?;
+    ///
+    /// let ps5 = SimDevice::new().controller_type(ControllerType::Ps5);
+    /// let id = sim.connect_device(ps5);
+    /// assert_eq!(sim.gamepad(id).unwrap().current_profile(), profile);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::set_default_profile`]: crate::Girl::set_default_profile
+    #[inline]
+    pub fn set_default_profile(
+        &mut self,
+        controller_type: ControllerType,
+        profile: InputProfile,
+    ) -> Result<(), Error> {
+        self.defaults.set(controller_type, profile)
+    }
+
+    /// Gets the [`DefaultProfiles`] applied to [`SimGamepad`]s connected
+    /// from now on.
+    #[must_use]
+    #[inline]
+    pub const fn default_profiles(&self) -> &DefaultProfiles {
+        &self.defaults
+    }
+
+    /// Replaces the [`DefaultProfiles`] applied to [`SimGamepad`]s connected
+    /// from now on, see [`set_default_profile`].
+    ///
+    /// [`set_default_profile`]: Self::set_default_profile
+    #[inline]
+    pub fn set_default_profiles(&mut self, defaults: DefaultProfiles) {
+        self.defaults = defaults;
+    }
+
+    /// Forgets the [`InputProfile`]s applied with
+    /// [`SimGamepad::apply_profile`] to [`SimGamepad`]s with the [`Guid`], so
+    /// the ones connected from now on start with their default profile, see
+    /// [`Girl::forget_profile`].
+    ///
+    /// [`Girl::forget_profile`]: crate::Girl::forget_profile
+    #[inline]
+    pub fn forget_profile(&mut self, guid: &Guid) {
+        for pad in self.pads.values_mut() {
+            if pad.device.guid == *guid {
+                pad.applied = false;
+            }
+        }
+    }
+
+    /// Applies the [`PadSetup`] to every connected [`SimGamepad`], see
+    /// [`Girl::apply_to_all`].
+    ///
+    /// [`Girl::apply_to_all`]: crate::Girl::apply_to_all
+    #[inline]
+    pub fn apply_to_all(
+        &mut self,
+        setup: &PadSetup,
+    ) -> BTreeMap<GamepadId, SetupReport> {
+        self.pads
+            .iter_mut()
+            .filter(|entry| entry.1.connected())
+            .map(|(&id, pad)| (id, pad.apply(setup)))
+            .collect()
+    }
+
+    /// Colors the LED of the [`SimGamepad`] with what `binding` returns for
+    /// its [`GamepadState`] on every [`advance`], returning a handle to
+    /// remove it with, see [`Girl::add_led_binding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{SimDevice, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect_device(SimDevice::new().led(true));
+    /// let pressed = |state: &GamepadState| {
+    ///     state.buttons.contains(Button::A).then_some(Rgb::new(0, 0, 255))
+    /// };
+    /// let binding = sim.add_led_binding(id, Box::new(pressed));
+    ///
+    /// sim.press(id, Button::A);
+    /// sim.advance(Duration::from_millis(16));
+    /// assert_eq!(sim.gamepad(id).unwrap().led(), Some(Rgb::new(0, 0, 255)));
+    /// drop(sim.remove_led_binding(binding));
+    /// ```
+    ///
+    /// [`advance`]: Self::advance
+    /// [`Girl::add_led_binding`]: crate::Girl::add_led_binding
+    #[inline]
+    pub fn add_led_binding(
+        &mut self,
+        id: GamepadId,
+        binding: LedBinding,
+    ) -> LedBindingId {
+        self.led_bindings.add(id, binding)
+    }
+
+    /// Removes a binding added with [`add_led_binding`], returning it, see
+    /// [`Girl::remove_led_binding`].
+    ///
+    /// Returns [`None`] if it was already removed, e.g. once its
+    /// [`SimGamepad`] disconnected.
+    ///
+    /// [`add_led_binding`]: Self::add_led_binding
+    /// [`Girl::remove_led_binding`]: crate::Girl::remove_led_binding
+    #[inline]
+    pub fn remove_led_binding(
+        &mut self,
+        id: LedBindingId,
+    ) -> Option<LedBinding> {
+        self.led_bindings.remove(id)
+    }
+
+    /// Sets the minimal time between two colors set on a [`SimGamepad`] by
+    /// its bindings, [`LedBindings::DEFAULT_INTERVAL`] by default, see
+    /// [`add_led_binding`].
+    ///
+    /// [`add_led_binding`]: Self::add_led_binding
+    #[inline]
+    pub fn set_led_binding_interval(&mut self, interval: Duration) {
+        self.led_bindings.set_interval(interval);
+    }
+
+    /// Gets the minimal time between two colors set on a [`SimGamepad`] by
+    /// its bindings, see [`set_led_binding_interval`].
+    ///
+    /// [`set_led_binding_interval`]: Self::set_led_binding_interval
+    #[must_use]
+    #[inline]
+    pub const fn led_binding_interval(&self) -> Duration {
+        self.led_bindings.interval()
+    }
+
+    /// Gets the [`ConnectionHeuristics`] of
+    /// [`SimGamepad::connection_kind`].
+    #[must_use]
+    #[inline]
+    pub fn connection_heuristics(&self) -> ConnectionHeuristics {
+        self.connection.clone()
+    }
+
+    /// Replaces the [`ConnectionHeuristics`] of
+    /// [`SimGamepad::connection_kind`], inferring the [`ConnectionKind`] of
+    /// every [`SimGamepad`] again, see [`Girl::set_connection_heuristics`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{ConnectionKind, SimGirl};
+    /// let mut sim = SimGirl::new();
+    /// let id = sim.connect();
+    ///
+    /// let mut heuristics = sim.connection_heuristics();
+    /// let guid = sim.gamepad(id).unwrap().guid();
+    /// heuristics.force(guid, ConnectionKind::Bluetooth);
+    /// sim.set_connection_heuristics(heuristics);
+    /// let kind = sim.gamepad(id).unwrap().connection_kind();
+    /// assert_eq!(kind, ConnectionKind::Bluetooth);
+    /// ```
+    ///
+    /// [`Girl::set_connection_heuristics`]:
+    ///     crate::Girl::set_connection_heuristics
+    #[inline]
+    pub fn set_connection_heuristics(
+        &mut self,
+        heuristics: ConnectionHeuristics,
+    ) {
+        self.connection = heuristics;
+        for pad in self.pads.values_mut() {
+            pad.connection = self.connection.infer(pad.device.guid, "", None);
+        }
+    }
+
+    /// Overrides the SDL2 mapping of [`SimGamepad`]s with the [`Guid`], see
+    /// [`Girl::set_mapping_override`].
+    ///
+    /// `mapping` is an SDL2 mapping string without the leading GUID. The
+    /// connected [`SimGamepad`]s keep their input and get an
+    /// [`Event::ControllerDeviceRemapped`], followed by an
+    /// [`Event::ControllerRemapDiff`] if their [`MappingSnapshot`] changed.
+    /// The ones connected later start with the override.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSyntax`] if the `mapping` has no name or a
+    /// field isn't an `input:bind` pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{Guid, SimGirl, prelude::*};
+    /// let mut sim = SimGirl::new();
+    /// let guid = Guid::from_bytes([1; 16]);
+    /// let id = sim.connect_with_guid(guid);
+    ///
+    /// // the capture button of the device is its 15th button
+    /// sim.set_mapping_override(&guid, "Fixed Gamepad,a:b0,b:b1,misc1:b15")?;
+    /// sim.press_joystick_button(id, 15);
+    /// println!("{}", sim.export_mapping(sim.gamepad(id).unwrap()));
+    ///
+    /// sim.clear_mapping_override(&guid);
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::set_mapping_override`]: crate::Girl::set_mapping_override
+    #[inline]
+    pub fn set_mapping_override(
+        &mut self,
+        guid: &Guid,
+        mapping: &str,
+    ) -> Result<(), Error> {
+        check_mapping(mapping)?;
+        drop(self.mapping_overrides.insert(*guid, mapping.to_owned()));
+        self.remap(*guid);
+        Ok(())
+    }
+
+    /// Restores the default mapping of [`SimGamepad`]s with the [`Guid`],
+    /// see [`Girl::clear_mapping_override`].
+    ///
+    /// Does nothing if the mapping wasn't overridden.
+    ///
+    /// [`Girl::clear_mapping_override`]: crate::Girl::clear_mapping_override
+    #[inline]
+    pub fn clear_mapping_override(&mut self, guid: &Guid) {
+        if self.mapping_overrides.remove(guid).is_some() {
+            self.remap(*guid);
+        }
+    }
+
+    /// Exports the SDL2 mapping of the [`SimGamepad`], including the leading
+    /// GUID, see [`Girl::export_mapping`].
+    ///
+    /// [`Girl::export_mapping`]: crate::Girl::export_mapping
+    #[must_use]
+    #[inline]
+    pub fn export_mapping(&self, gamepad: &SimGamepad) -> String {
+        gamepad.mapping().to_owned()
+    }
+
+    /// Polls for the next queued [`Event`], see [`Girl::event`].
+    ///
+    /// [`Girl::event`]: crate::Girl::event
+    #[must_use]
+    #[inline]
+    pub fn event(&mut self) -> Option<Event> {
+        self.sequenced_event().map(|sequenced| sequenced.event)
+    }
+
+    /// Polls for the next queued [`Event`] along with its sequence number,
+    /// see [`Girl::sequenced_event`].
+    ///
+    /// [`Girl::sequenced_event`]: crate::Girl::sequenced_event
+    #[must_use]
+    #[inline]
+    pub fn sequenced_event(&mut self) -> Option<SequencedEvent> {
+        self.events.pop()
+    }
+
+    /// Gets the total number of queued [`Event`]s dropped because they were
+    /// not polled in time, see [`Girl::dropped_events`].
+    ///
+    /// [`Girl::dropped_events`]: crate::Girl::dropped_events
+    #[must_use]
+    #[inline]
+    pub const fn dropped_events(&self) -> u64 {
+        self.events.dropped()
+    }
+
+    /// Applies an [`Event`] to the [`SimGamepad`]s and queues it, if it
+    /// changes anything.
+    fn apply(&mut self, event: Event) {
+        let changed = if let Event::ControllerDeviceAdded { which } = event {
+            let id = GamepadId::from_raw(which);
+            let connected =
+                self.pads.get(&id).is_some_and(SimGamepad::connected);
+            if !connected {
+                drop(self.pads.insert(id, SimGamepad::new(id)));
+            }
+            !connected
+        } else if let Event::ControllerDeviceRemoved { which } = event {
+            let id = GamepadId::from_raw(which);
+            #[cfg(feature = "sensors")]
+            self.sensor_retry.forget(id);
+            self.led_bindings.forget(id);
+            self.pads.get_mut(&id).is_some_and(SimGamepad::disconnect)
+        } else if let Some(which) = event.which()
+            && let Some(pad) = self.pads.get_mut(&GamepadId::from_raw(which))
+        {
+            pad.apply_event(&event)
+        } else {
+            false
+        };
+        if changed {
+            self.queue(event);
+        }
+    }
+
+    /// Gets the [`InputProfile`] a new [`SimGamepad`] of the [`SimDevice`]
+    /// starts with: the one applied to the latest connected [`SimGamepad`]
+    /// with the same [`Guid`], or else its [`DefaultProfiles`] entry or
+    /// [`InputProfile::new`].
+    fn initial_profile(&self, device: &SimDevice) -> InputProfile {
+        let applied = self
+            .pads
+            .values()
+            .rev()
+            .find(|pad| pad.applied && pad.device.guid == device.guid);
+        if let Some(pad) = applied {
+            return pad.profile.clone();
+        }
+        self.defaults
+            .profile_for(device.guid, device.controller_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Queues the sensor enables failed since the last call for retries,
+    /// and retries the ones due, as [`Girl::update`] does.
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    #[cfg(feature = "sensors")]
+    fn retry_sensors(&mut self) {
+        let now = self.epoch.checked_add(self.now).unwrap_or(self.epoch);
+        for (&id, pad) in &mut self.pads {
+            let failed = mem::take(&mut pad.failed_sensors);
+            if pad.raw.connected {
+                for sensor in failed {
+                    self.sensor_retry.queue(id, sensor, now);
+                }
+            }
+        }
+        if self.sensor_retry.is_empty() {
+            return;
+        }
+        let pads = &mut self.pads;
+        let events = self.sensor_retry.retry(now, |id, sensor| {
+            pads.get_mut(&id)
+                .ok_or(Error::InvalidArgument { what: "gamepad" })?
+                .try_enable_sensor(sensor)
+        });
+        for event in events {
+            self.queue(event);
+        }
+    }
+
+    /// Sets the colors of the [`LedBindings`] on the [`SimGamepad`]s with
+    /// an LED, as [`Girl::tick`] does.
+    ///
+    /// [`Girl::tick`]: crate::Girl::tick
+    fn bind_leds(&mut self) {
+        if self.led_bindings.is_empty() {
+            return;
+        }
+        let now = self.epoch.checked_add(self.now).unwrap_or(self.epoch);
+        for (&id, pad) in &mut self.pads {
+            if !pad.has_led() {
+                continue;
+            }
+            let state = pad.state();
+            _ = self.led_bindings.apply(now, id, &state, |color| {
+                pad.set_led(color.red, color.green, color.blue)
+            });
+        }
+    }
+
+    /// Gets the SDL2 mapping of [`SimGamepad`]s with the [`Guid`], including
+    /// it.
+    fn mapping(&self, guid: Guid) -> String {
+        let mapping = self.mapping_overrides.get(&guid);
+        format!("{guid},{}", mapping.map_or(DEFAULT_MAPPING, String::as_str))
+    }
+
+    /// Applies the current mapping of the [`Guid`] to the connected
+    /// [`SimGamepad`]s with it, reporting them remapped along with what
+    /// changed.
+    fn remap(&mut self, guid: Guid) {
+        let mapping = self.mapping(guid);
+        let mut remapped = Vec::new();
+        for pad in self.pads.values_mut() {
+            if pad.device.guid == guid && pad.connected() {
+                let before = pad.mapping_snapshot();
+                pad.mapping.clone_from(&mapping);
+                remapped.push((pad.id, before.diff(&pad.mapping_snapshot())));
+            }
+        }
+        for (id, changes) in remapped {
+            let which = id.raw();
+            self.queue(Event::ControllerDeviceRemapped { which });
+            if !changes.is_empty() {
+                self.queue(Event::ControllerRemapDiff { which, changes });
+            }
+        }
+    }
+
+    /// Queues an [`Event`] with the next sequence number.
+    fn queue(&mut self, event: Event) {
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.events.push(SequencedEvent::new(seq, event));
+    }
+}
+
+impl GamepadRead for SimGamepad {
+    #[inline]
+    fn button_value(&self, buttons: Button) -> f64 {
+        Self::button_value(self, buttons)
+    }
+
+    #[inline]
+    fn buttons(&self, buttons: Button) -> Button {
+        Self::buttons(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_changed(&mut self) -> ButtonChanges {
+        Self::buttons_changed(self)
+    }
+
+    #[inline]
+    fn buttons_exactly(&self, buttons: Button) -> bool {
+        Self::buttons_exactly(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_none_pressed(&self, buttons: Button) -> bool {
+        Self::buttons_none_pressed(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_pressed(&self, buttons: Button) -> bool {
+        Self::buttons_pressed(self, buttons)
+    }
+
+    #[inline]
+    fn buttons_released(&self, buttons: Button) -> bool {
+        Self::buttons_released(self, buttons)
+    }
+
+    #[inline]
+    fn connected(&self) -> bool {
+        Self::connected(self)
+    }
+
+    #[inline]
+    fn id(&self) -> GamepadId {
+        Self::id(self)
+    }
+
+    #[inline]
+    fn state(&self) -> GamepadState {
+        Self::state(self)
+    }
+
+    #[inline]
+    fn stick(&self, stick: Stick) -> [f64; 2] {
+        Self::stick(self, stick)
+    }
+
+    #[inline]
+    fn trigger(&self, trigger: Trigger) -> f64 {
+        Self::trigger(self, trigger)
+    }
+}
+
+impl SetupTarget for SimGamepad {
+    #[inline]
+    fn setup_led(&mut self, color: Rgb) -> Result<(), Error> {
+        self.set_led(color.red, color.green, color.blue)
+    }
+
+    #[inline]
+    fn setup_player_index(&mut self, index: u32) -> Result<(), Error> {
+        self.set_player_index(Some(index))
+    }
+
+    #[inline]
+    fn setup_profile(&mut self, profile: &InputProfile) -> Result<(), Error> {
+        self.apply_profile(profile)
+    }
+
+    #[cfg(feature = "rumble")]
+    #[inline]
+    fn setup_rumble(
+        &mut self,
+        low_frequency_rumble: u16,
+        high_frequency_rumble: u16,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        self.set_rumble(low_frequency_rumble, high_frequency_rumble, duration)
+    }
+
+    #[cfg(feature = "sensors")]
+    #[inline]
+    fn setup_sensor(&mut self, sensor: Sensor) -> Result<(), Error> {
+        self.enable_sensor(sensor)
+    }
+}
+
+impl GamepadSource for SimGirl {
+    type Gamepad = SimGamepad;
+
+    #[inline]
+    fn event(&mut self) -> Option<Event> {
+        Self::event(self)
+    }
+
+    #[inline]
+    fn gamepad_by_id(&self, id: GamepadId) -> Option<&SimGamepad> {
+        self.gamepad(id)
+    }
+
+    #[inline]
+    fn gamepad_by_id_mut(&mut self, id: GamepadId) -> Option<&mut SimGamepad> {
+        self.gamepad_mut(id)
+    }
+
+    #[inline]
+    fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+        Self::snapshot_all(self, out)
+    }
+}
+
+/// Checks the SDL2 `mapping` without the GUID: a name followed by
+/// `input:bind` pairs.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidSyntax`] at the first invalid field.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn check_mapping(mapping: &str) -> Result<(), Error> {
+    let mut position = 0usize;
+    for (index, field) in mapping.split(',').enumerate() {
+        let reason = if index == 0 {
+            field.is_empty().then_some("missing name")
+        } else {
+            let pair = field.split_once(':');
+            let valid = field.is_empty()
+                || pair.is_some_and(|(input, _)| !input.is_empty());
+            (!valid).then_some("expected `input:bind`")
+        };
+        if let Some(reason) = reason {
+            return Err(Error::InvalidSyntax {
+                what: "mapping",
+                position,
+                reason,
+            });
+        }
+        position = position.saturating_add(field.len()).saturating_add(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::{iter, time::Duration};
+
+    use super::{SimDevice, SimGamepad, SimGirl};
+    use crate::{
+        Button, ConnectionKind, ControllerType, Error, ErrorCode, Event,
+        GamepadId, GamepadState, Guid, InputBind, InputProfile, PadSetup, Rgb,
+        SetupOp, Stick, Trigger, queue::EVENT_CAPACITY,
+    };
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    use crate::{JoyConSide, Sensor};
+
+    /// Checks if [`Button::Misc1`] of the [`SimGamepad`] is pressed.
+    fn misc1(sim: &SimGirl, id: GamepadId) -> bool {
+        sim.gamepad(id).is_some_and(|pad| pad.buttons_pressed(Button::Misc1))
+    }
+
+    /// Sets the LED, plays a rumble and enables the gyroscope of the
+    /// [`SimGamepad`], returning the failing operations with the
+    /// [`ErrorCode`]s.
+    fn outputs(
+        sim: &mut SimGirl,
+        id: GamepadId,
+    ) -> Vec<(Option<&'static str>, ErrorCode)> {
+        let Some(pad) = sim.gamepad_mut(id) else {
+            return Vec::new();
+        };
+        let pulse = Duration::from_millis(100);
+        let results = iter::once(pad.set_led(0, 0, 255))
+            .chain(iter::once(pad.set_rumble(0xffff, 0, pulse)));
+        #[cfg(any(feature = "sensors", feature = "stubs"))]
+        let results =
+            results.chain(iter::once(pad.enable_sensor(Sensor::Gyroscope)));
+        results
+            .filter_map(Result::err)
+            .map(|err| (err.sdl_op(), err.code()))
+            .collect()
+    }
+
+    /// Advances the virtual clock of `sim` by a millisecond `count` times.
+    fn tick_ms(sim: &mut SimGirl, count: u32) {
+        for _ in 0..count {
+            sim.advance(Duration::from_millis(1));
+        }
+    }
+
+    /// Queues `count` [`Event`]s by pressing and releasing [`Button::A`].
+    fn toggle(sim: &mut SimGirl, count: usize) {
+        let id = GamepadId::from_raw(0);
+        let mut pressed = false;
+        for _ in 0..count {
+            if pressed {
+                sim.release(id, Button::A);
+            } else {
+                sim.press(id, Button::A);
+            }
+            pressed = !pressed;
+        }
+    }
+
+    /// Events polled in time come out with consecutive sequence numbers.
+    #[test]
+    fn sequences_without_gaps() {
+        let mut sim = SimGirl::new();
+        _ = sim.connect();
+        toggle(&mut sim, 4);
+        let mut last = None;
+        while let Some(sequenced) = sim.sequenced_event() {
+            if let Some(previous) = last {
+                assert_eq!(sequenced.missed_since(previous), 0, "no gap");
+            }
+            last = Some(sequenced.seq);
+        }
+        assert_eq!(last, Some(4), "every event sequenced");
+        assert_eq!(sim.dropped_events(), 0, "nothing dropped");
+    }
+
+    /// Events not polled in time are dropped oldest first, and the marker
+    /// reports exactly the gap the consumer sees in the sequence numbers.
+    #[test]
+    fn reports_exact_gap() {
+        let mut sim = SimGirl::new();
+        _ = sim.connect();
+        let first = sim.sequenced_event().map(|sequenced| sequenced.seq);
+        assert_eq!(first, Some(0), "connection polled");
+
+        toggle(&mut sim, EVENT_CAPACITY.saturating_add(3));
+        assert_eq!(sim.dropped_events(), 3, "overflow counted");
+        let marker = sim.sequenced_event().map(|sequenced| sequenced.event);
+        assert!(
+            matches!(marker, Some(Event::Overflow { dropped: 3 })),
+            "marker first"
+        );
+        let next =
+            sim.sequenced_event().map(|sequenced| sequenced.missed_since(0));
+        assert_eq!(next, Some(3), "gap matches the marker");
+
+        let mut polled = 1;
+        while sim.sequenced_event().is_some() {
+            polled += 1;
+        }
+        assert_eq!(polled, EVENT_CAPACITY, "the newest events kept");
+        assert_eq!(sim.dropped_events(), 3, "total unchanged");
+    }
+
+    /// After a subsystem reset, the devices still connected are reopened
+    /// with new IDs and get their profile back by [`Guid`], identical ones
+    /// in order, and the others are reported lost.
+    #[test]
+    fn reinitializes_by_guid() {
+        let mut sim = SimGirl::new();
+        let (pad, other) =
+            (Guid::from_bytes([1; 16]), Guid::from_bytes([2; 16]));
+        let ids = [pad, other, pad].map(|guid| sim.connect_with_guid(guid));
+        let profiles = [0.25f64, 0.5f64, 0.75f64].map(|deadzone| {
+            let mut profile = InputProfile::new();
+            profile.left_stick.deadzone = deadzone;
+            profile.left_stick.invert_y = true;
+            profile
+        });
+        for (&id, profile) in ids.iter().zip(&profiles) {
+            let applied = sim
+                .gamepad_mut(id)
+                .map(|sim_pad| sim_pad.apply_profile(profile));
+            assert!(matches!(applied, Some(Ok(()))), "valid profile");
+        }
+        sim.move_stick(ids[2], Stick::Left, [0.0f64, 0.9f64]);
+        while sim.event().is_some() {}
+
+        sim.reset_subsystem();
+        sim.disconnect(ids[1]);
+        let report = sim.reinitialize();
+        assert_eq!(report.lost, [(ids[1], other)], "unplugged device lost");
+        let recovered: Vec<_> =
+            report.recovered.iter().map(|&(id, _)| id).collect();
+        assert_eq!(recovered, [ids[0], ids[2]], "identical pads in order");
+
+        for (&(previous, new), profile) in
+            report.recovered.iter().zip([&profiles[0], &profiles[2]])
+        {
+            assert_ne!(previous, new, "reopened with a new ID");
+            let old = sim.gamepad(previous).map(SimGamepad::connected);
+            assert_eq!(old, Some(false), "previous handle closed");
+            let reopened = sim.gamepad(new);
+            assert_eq!(reopened.map(SimGamepad::guid), Some(pad));
+            let dump = reopened.map(|sim_pad| sim_pad.dump().profile);
+            assert_eq!(dump.as_ref(), Some(profile), "profile restored");
+        }
+        let held = report.recovered.get(1).and_then(|&(_, new)| {
+            sim.gamepad(new).map(|sim_pad| sim_pad.stick(Stick::Left))
+        });
+        assert_eq!(held.map(|[_, y]| y < 0.0f64), Some(true), "calibrated");
+
+        let events: Vec<_> = iter::from_fn(|| sim.event()).collect();
+        assert!(
+            matches!(events.as_slice(), [
+                Event::SubsystemReset,
+                Event::ControllerDeviceRemoved { .. },
+                Event::ControllerDeviceAdded { .. },
+                Event::ControllerDeviceAdded { .. },
+            ]),
+            "reset reported, then the reopened devices"
+        );
+    }
+
+    /// A mapping override makes a joystick button that wasn't mapped report
+    /// as a [`Button`] on the devices with the [`Guid`] only, keeping their
+    /// input, until it is cleared.
+    #[test]
+    fn overrides_mapping_by_guid() {
+        let mut sim = SimGirl::new();
+        let (odd, other) =
+            (Guid::from_bytes([1; 16]), Guid::from_bytes([2; 16]));
+        let id = sim.connect_with_guid(odd);
+        let unaffected = sim.connect_with_guid(other);
+        sim.move_stick(id, Stick::Left, [0.0f64, 0.9f64]);
+        sim.press_joystick_button(id, 15);
+        assert!(!misc1(&sim, id), "not mapped by default");
+        while sim.event().is_some() {}
+
+        let fixed = "Fixed Gamepad,a:b0,b:b1,misc1:b15,leftx:a0,lefty:a1";
+        assert!(sim.set_mapping_override(&odd, fixed).is_ok(), "valid");
+        let events: Vec<_> = iter::from_fn(|| sim.event()).collect();
+        assert!(
+            matches!(events.as_slice(), [
+                Event::ControllerDeviceRemapped { which },
+                Event::ControllerRemapDiff { .. },
+            ] if *which == id.raw()),
+            "only the device with the guid remapped"
+        );
+        let held = sim.gamepad(id).map(|pad| pad.stick(Stick::Left));
+        assert_eq!(held.map(|[_, y]| y > 0.0f64), Some(true), "input kept");
+
+        let later = sim.connect_with_guid(odd);
+        for pad in [id, unaffected, later] {
+            sim.press_joystick_button(pad, 15);
+        }
+        assert!(misc1(&sim, id), "mapped by the override");
+        assert!(misc1(&sim, later), "applies to new connections");
+        assert!(!misc1(&sim, unaffected), "other devices unaffected");
+        let exported = sim.gamepad(id).map(|pad| sim.export_mapping(pad));
+        assert_eq!(exported, Some(format!("{odd},{fixed}")), "round-trips");
+
+        sim.release_joystick_button(id, 15);
+        sim.clear_mapping_override(&odd);
+        sim.press_joystick_button(id, 15);
+        assert!(!misc1(&sim, id), "default mapping restored");
+
+        let invalid = sim.set_mapping_override(&odd, "Broken,a:b0,b1");
+        assert!(
+            matches!(invalid, Err(Error::InvalidSyntax { position: 12, .. })),
+            "rejected at the field"
+        );
+    }
+
+    /// The binds of a known mapping are converted per input kind, with
+    /// inverted axes, shared buttons and unbound inputs, and multi-bit
+    /// [`Button`] masks are rejected.
+    #[test]
+    fn converts_binds() {
+        let mut sim = SimGirl::new();
+        let guid = Guid::from_bytes([1; 16]);
+        let id = sim.connect_with_guid(guid);
+        let mapping = "Test,a:b1,b:b1,dpup:h0.1,leftx:a0,lefty:a1~,\
+                       lefttrigger:a2,platform:Linux";
+        assert!(sim.set_mapping_override(&guid, mapping).is_ok(), "valid");
+        let pad = sim.gamepad(id);
+
+        let bind = |button| pad?.bind_for_button(button).ok();
+        assert_eq!(bind(Button::A), Some(InputBind::Button(1)), "button");
+        assert_eq!(bind(Button::B), bind(Button::A), "shared input");
+        assert_eq!(bind(Button::X), Some(InputBind::None), "unbound");
+        let up = Some(InputBind::Hat { index: 0, mask: 1 });
+        assert_eq!(bind(Button::DPadUp), up, "hat");
+        let stick = |stick| pad.map(|sim_pad| sim_pad.bind_for_stick(stick));
+        assert_eq!(
+            stick(Stick::Left),
+            Some([
+                InputBind::Axis { index: 0, inverted: false },
+                InputBind::Axis { index: 1, inverted: true },
+            ]),
+            "axes"
+        );
+        assert_eq!(stick(Stick::Right), Some([InputBind::None; 2]), "none");
+        assert_eq!(
+            pad.map(|sim_pad| sim_pad.bind_for_trigger(Trigger::Left)),
+            Some(InputBind::Axis { index: 2, inverted: false }),
+            "trigger"
+        );
+
+        for buttons in [Button::A | Button::B, Button::empty()] {
+            let multiple = pad.map(|sim_pad| sim_pad.bind_for_button(buttons));
+            assert!(
+                matches!(
+                    multiple,
+                    Some(Err(Error::InvalidArgument { what: "button" }))
+                ),
+                "not a single button"
+            );
+        }
+    }
+
+    /// Outputs fail tagged with their operation: as unsupported when the
+    /// [`SimDevice`] lacks the hardware, and as disconnected once the
+    /// [`SimGamepad`] is gone, keeping what was set before.
+    #[test]
+    fn tags_output_failures() {
+        let mut sim = SimGirl::new();
+        let device = SimDevice::new().led(true).rumble(true);
+        #[cfg(any(feature = "sensors", feature = "stubs"))]
+        let device = device.sensor(Sensor::Gyroscope);
+        let (full, basic) = (sim.connect_device(device), sim.connect());
+        let ops = ["set_led", "set_rumble"].into_iter();
+        #[cfg(any(feature = "sensors", feature = "stubs"))]
+        let ops = ops.chain(["enable_sensor"]);
+        let failed = |code| -> Vec<_> {
+            ops.clone().map(|op| (Some(op), code)).collect()
+        };
+
+        assert_eq!(outputs(&mut sim, full), [], "everything supported");
+        let unsupported = failed(ErrorCode::NotSupported);
+        assert_eq!(outputs(&mut sim, basic), unsupported, "no hardware");
+
+        sim.disconnect(full);
+        let disconnected = failed(ErrorCode::Disconnected);
+        assert_eq!(outputs(&mut sim, full), disconnected, "gone");
+        let message = sim.gamepad_mut(full).and_then(|pad| {
+            pad.set_led(255, 0, 0).err().map(|err| err.to_string())
+        });
+        let expected = "SDL error in set_led: gamepad disconnected";
+        assert_eq!(message.as_deref(), Some(expected), "rendered with op");
+        let pad = sim.gamepad(full);
+        let led = pad.and_then(SimGamepad::led);
+        assert_eq!(led, Some(Rgb::new(0, 0, 255)), "last color kept");
+        let rumble = pad.and_then(SimGamepad::rumble);
+        assert_eq!(rumble.map(|(low, ..)| low), Some(0xffff), "rumble kept");
+        #[cfg(any(feature = "sensors", feature = "stubs"))]
+        assert!(
+            pad.is_some_and(|pad| pad.sensor_enabled(Sensor::Gyroscope)),
+            "sensor kept"
+        );
+    }
+
+    /// Primary sensors are the combined ones if present, else the ones of
+    /// the preferred Joy-Con of a pair, else the ones of the single
+    /// Joy-Con.
+    #[cfg(any(feature = "sensors", feature = "stubs"))]
+    #[test]
+    fn picks_primary_sensors() {
+        let mut sim = SimGirl::new();
+        let with = |sensors: &[Sensor]| {
+            sensors.iter().copied().fold(SimDevice::new(), SimDevice::sensor)
+        };
+        let (gyroscopes, accelerometers) =
+            ([Sensor::LeftGyroscope, Sensor::RightGyroscope], [
+                Sensor::LeftAccelerometer,
+                Sensor::RightAccelerometer,
+            ]);
+        let devices = [
+            with(&[Sensor::LeftAccelerometer, Sensor::LeftGyroscope]),
+            with(&[Sensor::RightGyroscope, Sensor::RightAccelerometer]),
+            with(&[Sensor::Gyroscope, Sensor::Accelerometer])
+                .sensor(Sensor::LeftGyroscope)
+                .sensor(Sensor::RightGyroscope),
+            with(&gyroscopes)
+                .sensor(accelerometers[0])
+                .sensor(accelerometers[1]),
+        ];
+        let ids = devices.map(|device| sim.connect_device(device));
+        let primary = |current: &SimGirl| {
+            ids.map(|id| {
+                let pad = current.gamepad(id)?;
+                Some((pad.primary_gyroscope(), pad.primary_accelerometer()))
+            })
+        };
+
+        let available = sim.gamepad(ids[0]).map(SimGamepad::available_sensors);
+        let reported = [Sensor::LeftGyroscope, Sensor::LeftAccelerometer];
+        assert_eq!(available.as_deref(), Some(reported.as_slice()), "sorted");
+        let side = |index: usize| {
+            Some((
+                gyroscopes.get(index).copied(),
+                accelerometers.get(index).copied(),
+            ))
+        };
+        let combined =
+            Some((Some(Sensor::Gyroscope), Some(Sensor::Accelerometer)));
+        let by_default = [side(0), side(1), combined, side(1)];
+        assert_eq!(primary(&sim), by_default, "right preferred by default");
+
+        for id in ids {
+            if let Some(pad) = sim.gamepad_mut(id) {
+                pad.set_preferred_side(JoyConSide::Left);
+            }
+        }
+        let preferred = [side(0), side(1), combined, side(0)];
+        assert_eq!(primary(&sim), preferred, "only the pair follows");
+        let kept = sim.gamepad(ids[3]).map(SimGamepad::preferred_side);
+        assert_eq!(kept, Some(JoyConSide::Left), "preference kept");
+    }
+
+    /// New connections start with the default profile of their type, the
+    /// conservative fallback one without it, or the profile last applied
+    /// to their [`Guid`], which survives reconnecting until forgotten.
+    #[test]
+    fn applies_default_profiles() {
+        let mut sim = SimGirl::new();
+        let device = |byte: u8, controller_type| {
+            let guid = Guid::from_bytes([byte; 16]);
+            SimDevice::new().guid(guid).controller_type(controller_type)
+        };
+        let profile = |current: &SimGirl, id| {
+            current.gamepad(id).map(SimGamepad::current_profile)
+        };
+        let pro = sim.connect_device(device(1, ControllerType::SwitchPro));
+        let ps5 = sim.connect_device(device(2, ControllerType::Ps5));
+        let other = sim.connect_device(device(3, ControllerType::Unknown));
+
+        let switch = profile(&sim, pro);
+        let swapped =
+            switch.as_ref().map(|defaults| defaults.remapped(Button::A));
+        assert_eq!(swapped, Some(Button::B), "labels swapped");
+        let deadzone = switch.map(|defaults| defaults.left_stick.deadzone);
+        assert_eq!(deadzone, Some(0.15f64), "larger deadzone");
+        assert_eq!(profile(&sim, ps5), Some(InputProfile::new()), "standard");
+        let fallback = profile(&sim, other).map(|pad| pad.right_stick.deadzone);
+        assert_eq!(fallback, Some(0.15f64), "conservative");
+
+        let mut custom = InputProfile::new();
+        custom.left_stick.invert_y = true;
+        let applied =
+            sim.gamepad_mut(ps5).map(|pad| pad.apply_profile(&custom));
+        assert!(matches!(applied, Some(Ok(()))), "valid profile");
+        sim.disconnect(ps5);
+        let mut tuned = InputProfile::new();
+        tuned.left_stick.deadzone = 0.3f64;
+        let set = sim.set_default_profile(ControllerType::Ps5, tuned.clone());
+        assert!(set.is_ok(), "valid default");
+        let again = sim.connect_device(device(2, ControllerType::Ps5));
+        assert_eq!(profile(&sim, again), Some(custom), "applied one kept");
+        let fresh = sim.connect_device(device(4, ControllerType::Ps5));
+        assert_eq!(profile(&sim, fresh), Some(tuned.clone()), "overridden");
+
+        sim.forget_profile(&Guid::from_bytes([2; 16]));
+        let forgotten = sim.connect_device(device(2, ControllerType::Ps5));
+        assert_eq!(profile(&sim, forgotten), Some(tuned), "default again");
+
+        let mut defaults = sim.default_profiles().clone();
+        defaults.set_enabled(false);
+        sim.set_default_profiles(defaults);
+        let plain = sim.connect_device(device(1, ControllerType::SwitchPro));
+        assert_eq!(profile(&sim, plain), Some(InputProfile::new()), "opt-out");
+    }
+
+    /// A [`PadSetup`] runs every operation in order past a failing one: the
+    /// LED the device lacks fails, and what comes after it is still set,
+    /// on the connected [`SimGamepad`]s only.
+    #[test]
+    fn applies_setup_past_failures() {
+        let mut sim = SimGirl::new();
+        let pad = sim.connect_device(SimDevice::new().rumble(true));
+        let gone = sim.connect();
+        sim.disconnect(gone);
+        let mut profile = InputProfile::new();
+        profile.right_stick.invert_y = true;
+        let setup = PadSetup::new()
+            .profile(profile.clone())
+            .led(Rgb::new(255, 0, 0))
+            .player_index(2);
+        #[cfg(feature = "rumble")]
+        let setup = setup.rumble(0x8000, 0x8000, Duration::from_millis(200));
+
+        let reports = sim.apply_to_all(&setup);
+        let ids: Vec<_> = reports.keys().copied().collect();
+        assert_eq!(ids, [pad], "connected only");
+        let ran = reports.get(&pad).map(|report| {
+            report
+                .results
+                .iter()
+                .map(|entry| (entry.0, entry.1.as_ref().err().map(Error::code)))
+                .collect::<Vec<_>>()
+        });
+        let expected = [
+            (SetupOp::Profile, None),
+            (SetupOp::Led, Some(ErrorCode::NotSupported)),
+            (SetupOp::PlayerIndex, None),
+        ]
+        .into_iter();
+        #[cfg(feature = "rumble")]
+        let expected = expected.chain([(SetupOp::Rumble, None)]);
+        assert_eq!(ran, Some(expected.collect()), "in order, past the LED");
+        let configured = sim.gamepad(pad);
+        let current = configured.map(SimGamepad::current_profile);
+        assert_eq!(current, Some(profile), "profile applied");
+        let index = configured.and_then(SimGamepad::player_index);
+        assert_eq!(index, Some(2), "player index set");
+        #[cfg(feature = "rumble")]
+        assert_eq!(
+            configured.and_then(SimGamepad::rumble),
+            Some((0x8000, 0x8000, Duration::from_millis(200))),
+            "rumble played last"
+        );
+    }
+
+    /// Sensor enables failing while the controller initializes are retried
+    /// every 100 ms: two failures, then success, reported once.
+    ///
+    /// Those still failing when the window passes are given up, and those
+    /// of disconnected [`SimGamepad`]s dropped.
+    #[cfg(feature = "sensors")]
+    #[test]
+    fn retries_sensor_enables() {
+        let mut sim = SimGirl::new();
+        sim.set_sensor_retry_window(Some(Duration::from_secs(1)));
+        let device = SimDevice::new().sensor(Sensor::Gyroscope);
+        let ids = [(); 3].map(|()| sim.connect_device(device.clone()));
+        let [ready, stuck, gone] = ids;
+        for (id, failures) in ids.into_iter().zip([2, u32::MAX, 1]) {
+            sim.fail_sensor_enables(id, failures);
+            let enabled = sim
+                .gamepad_mut(id)
+                .map(|pad| pad.enable_sensor(Sensor::Gyroscope).is_ok());
+            assert_eq!(enabled, Some(false), "first attempt fails");
+        }
+        sim.disconnect(gone);
+        while sim.event().is_some() {}
+
+        let mut retried = Vec::new();
+        for tick in 1..=12u32 {
+            sim.advance(Duration::from_millis(100));
+            retried.extend(
+                iter::from_fn(|| sim.event()).map(|event| (tick, event)),
+            );
+        }
+        // queued at 100 ms, failing again at 200 ms, enabled at 300 ms
+        assert!(
+            matches!(retried.as_slice(), [
+                (3, Event::SensorEnabled { which, sensor: Sensor::Gyroscope }),
+                (11, Event::SensorEnableFailed { which: given_up, .. }),
+            ] if *which == ready.raw() && *given_up == stuck.raw()),
+            "enabled on the second retry, the other given up"
+        );
+        let enabled = sim
+            .gamepad(ready)
+            .is_some_and(|pad| pad.sensor_enabled(Sensor::Gyroscope));
+        assert!(enabled, "the sensor is on");
+    }
+
+    /// The [`ConnectionKind`] comes from the bus type of the [`Guid`] or is
+    /// forced, and output and sensor errors on Bluetooth hint that the
+    /// feature may be limited.
+    #[test]
+    fn hints_bluetooth_errors() {
+        let mut sim = SimGirl::new();
+        let [usb, bluetooth, unknown] = [3, 5, 0].map(|bus| {
+            let mut bytes = [0; 16];
+            bytes[0] = bus;
+            let device = SimDevice::new().guid(Guid::from_bytes(bytes));
+            sim.connect_device(device)
+        });
+        let kinds = |current: &SimGirl| {
+            [usb, bluetooth, unknown]
+                .map(|id| current.gamepad(id).map(SimGamepad::connection_kind))
+        };
+        let (usb_kind, bluetooth_kind, unknown_kind) = (
+            Some(ConnectionKind::Usb),
+            Some(ConnectionKind::Bluetooth),
+            Some(ConnectionKind::Unknown),
+        );
+        assert_eq!(
+            kinds(&sim),
+            [usb_kind, bluetooth_kind, unknown_kind],
+            "from the bus type"
+        );
+
+        let hinted = |err: Error, feature: &str| {
+            let hint = format!("on Bluetooth; {feature} may be limited)");
+            err.to_string().ends_with(&hint)
+        };
+        let rumble = |current: &mut SimGirl, id| {
+            let pulse = Duration::from_millis(100);
+            let failed = current.gamepad_mut(id)?.set_rumble(1, 1, pulse);
+            failed.err().map(|err| hinted(err, "rumble"))
+        };
+        assert_eq!(rumble(&mut sim, usb), Some(false), "on USB");
+        assert_eq!(rumble(&mut sim, bluetooth), Some(true), "on Bluetooth");
+        #[cfg(any(feature = "sensors", feature = "stubs"))]
+        {
+            let failed = sim
+                .gamepad_mut(bluetooth)
+                .and_then(|pad| pad.enable_sensor(Sensor::Gyroscope).err());
+            let sensor = failed.map(|err| hinted(err, "sensor rates"));
+            assert_eq!(sensor, Some(true), "sensor enable on Bluetooth");
+        }
+
+        let mut heuristics = sim.connection_heuristics();
+        if let Some(guid) = sim.gamepad(unknown).map(SimGamepad::guid) {
+            heuristics.force(guid, ConnectionKind::Bluetooth);
+        }
+        sim.set_connection_heuristics(heuristics);
+        assert_eq!(
+            kinds(&sim),
+            [usb_kind, bluetooth_kind, bluetooth_kind],
+            "forced"
+        );
+        assert_eq!(rumble(&mut sim, unknown), Some(true), "forced");
+    }
+
+    /// Failing to open an out of range or disconnected [`SimGamepad`]
+    /// records why, until the next successful open.
+    #[test]
+    fn records_open_errors() {
+        let mut sim = SimGirl::new();
+        let first = sim.connect();
+        assert!(sim.open(u32::MAX).is_none(), "out of range");
+        let tagged = |current: &SimGirl| {
+            current.last_open_error().map(|err| (err.sdl_op(), err.code()))
+        };
+        let out_of_range = Some((Some("gamepad"), ErrorCode::Sdl));
+        assert_eq!(tagged(&sim), out_of_range, "recorded");
+
+        sim.disconnect(first);
+        assert!(sim.open(0).is_none(), "disconnected");
+        assert_eq!(tagged(&sim), out_of_range, "still recorded");
+
+        let second = sim.connect();
+        let opened = sim.open(0).map(|pad| pad.id());
+        assert_eq!(opened, Some(second), "the connected one");
+        assert!(sim.last_open_error().is_none(), "cleared");
+    }
+
+    /// LED bindings of input changing every millisecond set a color at most
+    /// once per interval, and nothing on a [`SimGamepad`] without an LED.
+    ///
+    /// Unchanged colors and ones within the tolerance aren't set again. The
+    /// last added binding returning a color wins.
+    #[test]
+    fn rate_limits_led_bindings() {
+        let mut sim = SimGirl::new();
+        let lit = sim.connect_device(SimDevice::new().led(true));
+        let unlit = sim.connect();
+        let (red, dim, blue) =
+            (Rgb::new(255, 0, 0), Rgb::new(254, 0, 0), Rgb::new(0, 0, 255));
+        let blink = move |state: &GamepadState| {
+            Some(if state.buttons.contains(Button::A) { red } else { blue })
+        };
+        let held = move |state: &GamepadState| {
+            state.buttons.contains(Button::B).then_some(dim)
+        };
+        let binding = sim.add_led_binding(lit, Box::new(blink));
+        _ = sim.add_led_binding(unlit, Box::new(blink));
+        let sets = |current: &SimGirl| {
+            [lit, unlit].map(|id| current.gamepad(id).map(SimGamepad::led_sets))
+        };
+
+        for _ in 0..500u32 {
+            sim.press(lit, Button::A);
+            tick_ms(&mut sim, 1);
+            sim.release(lit, Button::A);
+            tick_ms(&mut sim, 1);
+        }
+        assert_eq!(sets(&sim), [Some(20), Some(0)], "20 times per second");
+        let led = sim.gamepad(lit).and_then(SimGamepad::led);
+        assert_eq!(led, Some(blue), "released");
+        tick_ms(&mut sim, 1000);
+        assert_eq!(sets(&sim), [Some(20), Some(0)], "unchanged color");
+        sim.press(lit, Button::A);
+        tick_ms(&mut sim, 1000);
+        assert_eq!(sets(&sim), [Some(21), Some(0)], "new color once");
+
+        _ = sim.add_led_binding(lit, Box::new(held));
+        sim.press(lit, Button::B);
+        tick_ms(&mut sim, 100);
+        sim.release(lit, Button::A);
+        tick_ms(&mut sim, 100);
+        let kept = sim.gamepad(lit).and_then(SimGamepad::led);
+        assert_eq!(kept, Some(red), "within the tolerance, taking precedence");
+        sim.release(lit, Button::B);
+        tick_ms(&mut sim, 100);
+        assert_eq!(sets(&sim), [Some(22), Some(0)], "back to the first");
+
+        sim.disconnect(lit);
+        assert!(sim.remove_led_binding(binding).is_none(), "dropped");
+    }
+
+    /// Remapping reports what changed right after the raw event: rebound,
+    /// added and removed [`Button`]s and rebound axes, and nothing more
+    /// when the mapping stays the same.
+    #[test]
+    fn reports_remap_diff() {
+        let mut sim = SimGirl::new();
+        let guid = Guid::from_bytes([1; 16]);
+        let id = sim.connect_with_guid(guid);
+        while sim.event().is_some() {}
+        let before = sim.gamepad(id).map(SimGamepad::mapping_snapshot);
+
+        let fixed = "Fixed Gamepad,a:b1,b:b0,y:b3,misc1:b15,leftx:a0,\
+                     lefty:a1~,rightx:a2,righty:a3,lefttrigger:a4,\
+                     righttrigger:a5";
+        assert!(sim.set_mapping_override(&guid, fixed).is_ok(), "valid");
+        let events: Vec<_> = iter::from_fn(|| sim.event()).collect();
+        assert!(
+            matches!(events.as_slice(), [
+                Event::ControllerDeviceRemapped { which },
+                Event::ControllerRemapDiff { which: diffed, .. },
+            ] if *which == id.raw() && *diffed == id.raw()),
+            "remapped, then the diff"
+        );
+        let changes = events
+            .iter()
+            .find_map(|event| {
+                if let Event::ControllerRemapDiff { changes, .. } = *event {
+                    Some(changes)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let kept = Button::A | Button::B | Button::Y;
+        let removed =
+            before.as_ref().map(|snapshot| snapshot.bound_buttons() - kept);
+        assert_eq!(Some(changes.removed), removed, "unbound buttons");
+        assert_eq!(changes.added, Button::Misc1, "newly bound");
+        assert_eq!(changes.rebound, Button::A | Button::B, "swapped");
+        assert!(changes.stick_rebound(Stick::Left), "inverted axis");
+        assert!(!changes.stick_rebound(Stick::Right), "same axes");
+        assert!(!changes.trigger_rebound(Trigger::Left), "same trigger");
+        assert_eq!(changes.controller_type, None, "same type");
+        let after = sim.gamepad(id).map(SimGamepad::mapping_snapshot);
+        let diff = before.zip(after).map(|(old, new)| old.diff(&new));
+        assert_eq!(diff, Some(changes), "matches the snapshots");
+
+        assert!(sim.set_mapping_override(&guid, fixed).is_ok(), "valid");
+        let again: Vec<_> = iter::from_fn(|| sim.event()).collect();
+        assert!(
+            matches!(again.as_slice(), [
+                Event::ControllerDeviceRemapped { .. }
+            ]),
+            "nothing changed"
+        );
+    }
+}
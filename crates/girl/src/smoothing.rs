@@ -0,0 +1,272 @@
+//! Time-based smoothing of analog [`Stick`] positions, e.g. for camera
+//! control.
+//!
+//! [`Stick`]: crate::Stick
+
+use core::time::Duration;
+use std::time::Instant;
+
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, Stick};
+
+/// Output smoothing of [`Gamepad`] sticks.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Sets the output smoothing of a [`Stick`], or disables it if `config`
+    /// is [`None`] (the default).
+    ///
+    /// Smoothing is the last stage of [`stick`], applied after the
+    /// [`StickProfile`], and is clocked by the calls to [`stick`] (and
+    /// [`state`]): read the [`Stick`] once per frame for a steady output.
+    /// Setting it resets the smoothing state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use girl::{SmoothingConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let mut config = SmoothingConfig::new();
+    /// config.time_constant = Some(Duration::from_millis(80));
+    /// gamepad.set_stick_smoothing(Stick::Right, Some(config));
+    /// assert_eq!(gamepad.stick_smoothing(Stick::Right), Some(config));
+    ///
+    /// let [yaw, pitch] = gamepad.stick(Stick::Right);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    /// [`state`]: Self::state
+    /// [`StickProfile`]: crate::StickProfile
+    #[inline]
+    pub fn set_stick_smoothing(
+        &mut self,
+        stick: Stick,
+        config: Option<SmoothingConfig>,
+    ) {
+        self.smoother(stick).set(config.map(StickSmoother::new));
+    }
+
+    /// Gets the output smoothing of a [`Stick`], see
+    /// [`set_stick_smoothing`].
+    ///
+    /// [`set_stick_smoothing`]: Self::set_stick_smoothing
+    #[must_use]
+    #[inline]
+    pub fn stick_smoothing(&self, stick: Stick) -> Option<SmoothingConfig> {
+        self.smoother(stick).get().map(|smoother| smoother.config)
+    }
+
+    /// Smooths the `position` of the [`Stick`] if configured, see
+    /// [`set_stick_smoothing`].
+    ///
+    /// [`set_stick_smoothing`]: Self::set_stick_smoothing
+    #[inline]
+    pub(crate) fn smooth(&self, stick: Stick, position: [f64; 2]) -> [f64; 2] {
+        let cell = self.smoother(stick);
+        let Some(mut smoother) = cell.get() else {
+            return position;
+        };
+        let output = smoother.update_at(position, Instant::now());
+        cell.set(Some(smoother));
+        output
+    }
+}
+
+/// Configuration of [`Stick`] output smoothing, see
+/// [`Gamepad::set_stick_smoothing`] and [`StickSmoother`].
+///
+/// The stages apply in order: exponential smoothing towards the raw
+/// position, slew-rate limiting of the result, then snapping to rest.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmoothingConfig {
+    /// Time constant of the exponential smoothing: the time the output takes
+    /// to cover about 63% of a step. Independent of the frame rate. [`None`]
+    /// or zero disables it.
+    pub time_constant: Option<Duration>,
+    /// Maximal distance the output moves per second, in stick units.
+    /// [`None`] disables the limit.
+    pub max_slew_rate: Option<f64>,
+    /// Maximal time the output lags behind a [`Stick`] returned to rest,
+    /// after which it snaps to `[0.0, 0.0]`. [`Duration::ZERO`] snaps
+    /// immediately, [`None`] lets the output settle on its own.
+    pub snap_to_zero: Option<Duration>,
+}
+
+impl Default for SmoothingConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SmoothingConfig {
+    /// Creates the default [`SmoothingConfig`]: a 50 ms time constant, no
+    /// slew-rate limit, and snapping to rest immediately.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            time_constant: Some(Duration::from_millis(50)),
+            max_slew_rate: None,
+            snap_to_zero: Some(Duration::ZERO),
+        }
+    }
+}
+
+/// Smoothing state of a [`Stick`], see [`SmoothingConfig`].
+///
+/// Used by [`Gamepad::set_stick_smoothing`], and usable on its own for
+/// positions from other sources, e.g. [`Event`]s.
+///
+/// # Examples
+///
+/// Step responses with a fixed time step:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use girl::{SmoothingConfig, StickSmoother};
+/// let dt = Duration::from_millis(100);
+/// let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+///
+/// // exponential: 1 - e^-1 of the step after one time constant
+/// let mut config = SmoothingConfig::new();
+/// config.time_constant = Some(dt);
+/// let mut smoother = StickSmoother::new(config);
+/// assert_eq!(smoother.update([0.0, 0.0], dt), [0.0, 0.0]);
+/// let [x, y] = smoother.update([1.0, 0.0], dt);
+/// assert!(close(x, 1.0 - (-1.0_f64).exp()));
+/// assert_eq!(y, 0.0);
+/// let [x, _] = smoother.update([1.0, 0.0], dt);
+/// assert!(close(x, 1.0 - (-2.0_f64).exp()));
+///
+/// // the same time at twice the frame rate ends up at the same place
+/// let mut fast = StickSmoother::new(config);
+/// _ = fast.update([0.0, 0.0], dt);
+/// _ = fast.update([1.0, 0.0], dt / 2);
+/// let [x, _] = fast.update([1.0, 0.0], dt / 2);
+/// assert!(close(x, 1.0 - (-1.0_f64).exp()));
+///
+/// // back at rest, snapped immediately
+/// assert_eq!(smoother.update([0.0, 0.0], dt), [0.0, 0.0]);
+///
+/// // slew rate: at most 2 units per second, along the direction of travel
+/// config.time_constant = None;
+/// config.max_slew_rate = Some(2.0);
+/// let mut smoother = StickSmoother::new(config);
+/// _ = smoother.update([0.0, 0.0], dt);
+/// let [x, y] = smoother.update([0.6, 0.8], dt);
+/// assert!(close(x, 0.12) && close(y, 0.16));
+/// let [x, y] = smoother.update([0.6, 0.8], dt);
+/// assert!(close(x, 0.24) && close(y, 0.32));
+///
+/// // bounded latency back to rest
+/// config.snap_to_zero = Some(Duration::from_millis(200));
+/// let mut smoother = StickSmoother::new(config);
+/// _ = smoother.update([1.0, 0.0], dt);
+/// let [x, _] = smoother.update([0.0, 0.0], dt);
+/// assert!(close(x, 0.8));
+/// assert_eq!(smoother.update([0.0, 0.0], dt), [0.0, 0.0]);
+/// ```
+///
+/// [`Event`]: crate::Event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickSmoother {
+    /// Smoothing configuration.
+    config: SmoothingConfig,
+    /// Last output, [`None`] before the first update.
+    output: Option<[f64; 2]>,
+    /// How long the input has been at rest.
+    resting: Duration,
+    /// Time of the last [`StickSmoother::update_at`].
+    last: Option<Instant>,
+}
+
+impl StickSmoother {
+    /// Creates a [`StickSmoother`] without any history: the first update
+    /// passes its position through.
+    #[must_use]
+    #[inline]
+    pub const fn new(config: SmoothingConfig) -> Self {
+        Self { config, output: None, resting: Duration::ZERO, last: None }
+    }
+
+    /// Gets the [`SmoothingConfig`].
+    #[must_use]
+    #[inline]
+    pub const fn config(&self) -> &SmoothingConfig {
+        &self.config
+    }
+
+    /// Forgets the history, so the next update passes its position through.
+    #[inline]
+    pub const fn reset(&mut self) {
+        *self = Self::new(self.config);
+    }
+
+    /// Smooths the `position` read `dt` after the previous one.
+    #[inline]
+    pub fn update(&mut self, position: [f64; 2], dt: Duration) -> [f64; 2] {
+        let Some([px, py]) = self.output else {
+            self.output = Some(position);
+            return position;
+        };
+        let secs = dt.as_secs_f64();
+        let [rx, ry] = position;
+
+        let [mut x, mut y] = match self.config.time_constant {
+            Some(tau) if !tau.is_zero() => {
+                let alpha = -(-secs / tau.as_secs_f64()).exp_m1();
+                [alpha.mul_add(rx - px, px), alpha.mul_add(ry - py, py)]
+            }
+            Some(_) | None => position,
+        };
+
+        if let Some(rate) = self.config.max_slew_rate {
+            let max_step = rate.max(0.0) * secs;
+            let (dx, dy) = (x - px, y - py);
+            let step = dx.hypot(dy);
+            if step > max_step {
+                let scale = max_step / step;
+                x = dx.mul_add(scale, px);
+                y = dy.mul_add(scale, py);
+            }
+        }
+
+        if rx == 0.0f64 && ry == 0.0f64 {
+            self.resting = self.resting.saturating_add(dt);
+            if self
+                .config
+                .snap_to_zero
+                .is_some_and(|bound| self.resting >= bound)
+            {
+                [x, y] = [0.0f64, 0.0f64];
+            }
+        } else {
+            self.resting = Duration::ZERO;
+        }
+
+        self.output = Some([x, y]);
+        [x, y]
+    }
+
+    /// Smooths the `position` read at `now`, timed against the previous
+    /// call.
+    #[inline]
+    pub fn update_at(&mut self, position: [f64; 2], now: Instant) -> [f64; 2] {
+        let dt = self
+            .last
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        self.last = Some(now);
+        self.update(position, dt)
+    }
+}
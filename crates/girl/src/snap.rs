@@ -0,0 +1,157 @@
+//! Notch snapping of analog [`Stick`] positions, e.g. for emulating retro
+//! controllers with octagonal gates.
+//!
+//! [`Stick`]: crate::Stick
+
+use core::f64::consts::{PI, TAU};
+
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, Stick};
+
+/// Components smaller than this are snapped to exactly `0.0`, to undo the
+/// rounding of `sin` and `cos` on cardinal angles.
+const ZERO_EPSILON: f64 = 1e-12;
+
+/// Notch snapping of [`Gamepad`] sticks.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Gets the current position of an analog [`Stick`] snapped onto the
+    /// nearest notch, see [`StickSnapConfig::snap`].
+    ///
+    /// Snapping is applied on top of [`stick`], so the configured deadzone,
+    /// response curve and `y` axis inversion still apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{StickSnapConfig, prelude::*};
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// let config = StickSnapConfig::new();
+    /// let [x, y] = gamepad.stick_snapped(Stick::Left, &config);
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`stick`]: Self::stick
+    #[must_use]
+    #[inline]
+    pub fn stick_snapped(
+        &self,
+        stick: Stick,
+        config: &StickSnapConfig,
+    ) -> [f64; 2] {
+        config.snap(self.stick(stick))
+    }
+}
+
+/// Configuration of [`Stick`] notch snapping, see
+/// [`Gamepad::stick_snapped`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickSnapConfig {
+    /// Number of evenly spaced notches, starting at the right. `0` disables
+    /// snapping.
+    pub notches: u32,
+    /// Maximal angle from a notch, in radians, at which a position is
+    /// snapped onto it.
+    pub tolerance: f64,
+    /// Number of equal magnitude steps snapped positions are rounded to,
+    /// if any.
+    pub magnitude_steps: Option<u32>,
+}
+
+impl Default for StickSnapConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StickSnapConfig {
+    /// Creates the default [`StickSnapConfig`]: 8 notches on the cardinal
+    /// and diagonal directions, snapping within 11.25° and keeping the
+    /// magnitude.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { notches: 8, tolerance: PI / 16.0, magnitude_steps: None }
+    }
+
+    /// Snaps the `position` onto the nearest notch if it is within the
+    /// [`tolerance`], rounding its magnitude to the [`magnitude_steps`] if
+    /// configured. Positions outside the [`tolerance`] pass through
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::StickSnapConfig;
+    /// let mut config = StickSnapConfig::new();
+    /// let at = |degrees: f64, magnitude: f64| {
+    ///     let (y, x) = degrees.to_radians().sin_cos();
+    ///     [x * magnitude, y * magnitude]
+    /// };
+    /// let close = |[ax, ay]: [f64; 2], [bx, by]: [f64; 2]| {
+    ///     (ax - bx).abs() < 1e-9 && (ay - by).abs() < 1e-9
+    /// };
+    ///
+    /// // just inside the tolerance
+    /// assert!(close(config.snap(at(11.0, 0.8)), [0.8, 0.0]));
+    /// assert!(close(config.snap(at(-79.0, 0.8)), [0.0, -0.8]));
+    /// assert!(close(config.snap(at(56.0, 0.8)), at(45.0, 0.8)));
+    /// // wraps around ±π
+    /// assert!(close(config.snap(at(175.0, 0.8)), [-0.8, 0.0]));
+    /// assert!(close(config.snap(at(-175.0, 0.8)), [-0.8, 0.0]));
+    /// assert_eq!(config.snap(at(90.0, 0.8))[0], 0.0);
+    ///
+    /// // just outside the tolerance
+    /// for degrees in [11.5, -78.5, 56.5, 168.5, -168.5] {
+    ///     let position = at(degrees, 0.8);
+    ///     assert_eq!(config.snap(position), position);
+    /// }
+    ///
+    /// // quantized magnitude
+    /// config.magnitude_steps = Some(4);
+    /// assert!(close(config.snap(at(-5.0, 0.7)), [0.75, 0.0]));
+    /// assert!(close(config.snap(at(30.0, 0.7)), at(30.0, 0.7)));
+    /// ```
+    ///
+    /// [`tolerance`]: Self::tolerance
+    /// [`magnitude_steps`]: Self::magnitude_steps
+    #[must_use]
+    #[inline]
+    pub fn snap(&self, position: [f64; 2]) -> [f64; 2] {
+        let [x, y] = position;
+        let mut magnitude = x.hypot(y);
+        if self.notches == 0 || magnitude < ZERO_EPSILON {
+            return position;
+        }
+
+        let step = TAU / f64::from(self.notches);
+        let angle = y.atan2(x);
+        let notch = (angle / step).round() * step;
+        if (angle - notch).abs() > self.tolerance {
+            return position;
+        }
+
+        if let Some(steps) = self.magnitude_steps
+            && steps > 0
+        {
+            let levels = f64::from(steps);
+            magnitude = (magnitude * levels).round() / levels;
+        }
+        let (sin, cos) = notch.sin_cos();
+        [exact_zero(cos * magnitude), exact_zero(sin * magnitude)]
+    }
+}
+
+/// Rounds values within [`ZERO_EPSILON`] of zero to exactly `0.0`.
+fn exact_zero(value: f64) -> f64 {
+    if value.abs() < ZERO_EPSILON { 0.0 } else { value }
+}
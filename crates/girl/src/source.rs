@@ -0,0 +1,384 @@
+//! Backend-independent access to gamepad input, implemented by both the
+//! SDL2 backend and the simulated one.
+
+use crate::{
+    Button, ButtonChanges, Event, GamepadId, GamepadState, Stick, Trigger,
+};
+
+/// Input of a single gamepad, implemented by [`Gamepad`] and
+/// [`SimGamepad`], so gameplay code can be tested against scripted input.
+///
+/// Values follow the conventions of the [`Gamepad`] methods of the same
+/// names, e.g. sticks in `[-1.0, 1.0]` after the deadzone.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "sim")] {
+/// # use girl::{GamepadRead, SimGirl, prelude::*};
+/// fn jumping(pad: &impl GamepadRead) -> bool {
+///     pad.buttons_pressed(Button::A)
+/// }
+///
+/// let mut sim = SimGirl::new();
+/// let id = sim.connect();
+/// sim.press(id, Button::A);
+/// assert!(jumping(sim.gamepad(id).unwrap()));
+/// # }
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`SimGamepad`]: crate::SimGamepad
+pub trait GamepadRead {
+    /// Gets how far the specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::button_value`].
+    ///
+    /// [`Gamepad::button_value`]: crate::Gamepad::button_value
+    fn button_value(&self, buttons: Button) -> f64;
+
+    /// Gets the specified [`Button`]\(s) pressed, see [`Gamepad::buttons`].
+    ///
+    /// [`Gamepad::buttons`]: crate::Gamepad::buttons
+    fn buttons(&self, buttons: Button) -> Button;
+
+    /// Gets the [`ButtonChanges`] since the previous call, see
+    /// [`Gamepad::buttons_changed`].
+    ///
+    /// [`Gamepad::buttons_changed`]: crate::Gamepad::buttons_changed
+    fn buttons_changed(&mut self) -> ButtonChanges;
+
+    /// Checks if exactly the specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::buttons_exactly`].
+    ///
+    /// [`Gamepad::buttons_exactly`]: crate::Gamepad::buttons_exactly
+    #[inline]
+    fn buttons_exactly(&self, buttons: Button) -> bool {
+        self.buttons(Button::all()) == buttons
+    }
+
+    /// Checks if none of the specified [`Button`]\(s) is pressed, see
+    /// [`Gamepad::buttons_none_pressed`].
+    ///
+    /// [`Gamepad::buttons_none_pressed`]:
+    ///     crate::Gamepad::buttons_none_pressed
+    #[inline]
+    fn buttons_none_pressed(&self, buttons: Button) -> bool {
+        self.buttons_released(buttons)
+    }
+
+    /// Checks if all specified [`Button`]\(s) are pressed, see
+    /// [`Gamepad::buttons_pressed`].
+    ///
+    /// [`Gamepad::buttons_pressed`]: crate::Gamepad::buttons_pressed
+    #[inline]
+    fn buttons_pressed(&self, buttons: Button) -> bool {
+        self.buttons(buttons) == buttons
+    }
+
+    /// Checks if all specified [`Button`]\(s) are released, see
+    /// [`Gamepad::buttons_released`].
+    ///
+    /// [`Gamepad::buttons_released`]: crate::Gamepad::buttons_released
+    #[inline]
+    fn buttons_released(&self, buttons: Button) -> bool {
+        !self.buttons(Button::all()).intersects(buttons)
+    }
+
+    /// Checks if the gamepad is connected, see [`Gamepad::connected`].
+    ///
+    /// [`Gamepad::connected`]: crate::Gamepad::connected
+    fn connected(&self) -> bool;
+
+    /// Gets the instance ID, see [`Gamepad::id`].
+    ///
+    /// [`Gamepad::id`]: crate::Gamepad::id
+    fn id(&self) -> GamepadId;
+
+    /// Takes a [`GamepadState`] snapshot, see [`Gamepad::state`].
+    ///
+    /// [`Gamepad::state`]: crate::Gamepad::state
+    #[inline]
+    fn state(&self) -> GamepadState {
+        GamepadState {
+            connected: self.connected(),
+            buttons: self.buttons(Button::all()),
+            left_stick: self.stick(Stick::Left),
+            right_stick: self.stick(Stick::Right),
+            left_trigger: self.trigger(Trigger::Left),
+            right_trigger: self.trigger(Trigger::Right),
+        }
+    }
+
+    /// Gets the position of the [`Stick`], see [`Gamepad::stick`].
+    ///
+    /// [`Gamepad::stick`]: crate::Gamepad::stick
+    fn stick(&self, stick: Stick) -> [f64; 2];
+
+    /// Gets the value of the [`Trigger`], see [`Gamepad::trigger`].
+    ///
+    /// [`Gamepad::trigger`]: crate::Gamepad::trigger
+    fn trigger(&self, trigger: Trigger) -> f64;
+}
+
+/// Source of [`Event`]s and the gamepads they come from, implemented by
+/// [`Girl`] and [`SimGirl`].
+///
+/// # Examples
+///
+/// The same script gives the same [`Event`]s on both backends, here
+/// without any controller connected to [`Girl`]:
+///
+/// ```
+/// # #[cfg(feature = "sim")] {
+/// # use std::{iter, time::Duration};
+/// # use girl::{GamepadSource, SimGirl, prelude::*};
+/// fn drain(source: &mut impl GamepadSource) -> Vec<String> {
+///     iter::from_fn(|| source.event())
+///         .map(|event| format!("{event:?}"))
+///         .collect()
+/// }
+///
+/// let script = [
+///     Event::ControllerButtonDown { which: 0, button: Button::A },
+///     Event::stick_motion(0, Stick::Left, [0.5, -0.25]),
+///     Event::ControllerButtonDown { which: 0, button: Button::B },
+///     Event::ControllerButtonUp { which: 0, button: Button::A },
+///     Event::trigger_motion(0, Trigger::Right, 0.75),
+///     Event::ControllerButtonUp { which: 0, button: Button::B },
+/// ];
+///
+/// let mut sim = SimGirl::new();
+/// let id = sim.connect();
+/// assert_eq!(id.raw(), 0);
+/// _ = drain(&mut sim);
+/// for (at, event) in (1..).map(Duration::from_millis).zip(script) {
+///     sim.schedule(at, event);
+/// }
+/// sim.advance(Duration::from_secs(1));
+///
+/// let simulated = drain(&mut sim);
+/// assert_eq!(simulated.len(), script.len());
+///
+/// # #[cfg(feature = "sdl2")] {
+/// let mut girl = Girl::new()?;
+/// girl.update();
+/// _ = drain(&mut girl);
+/// for event in script {
+///     girl.inject(event);
+/// }
+/// assert_eq!(simulated, drain(&mut girl));
+/// # }
+/// # }
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`SimGirl`]: crate::SimGirl
+pub trait GamepadSource {
+    /// Gamepads of the source.
+    type Gamepad: GamepadRead;
+
+    /// Polls for the next [`Event`], see [`Girl::event`].
+    ///
+    /// [`Girl::event`]: crate::Girl::event
+    fn event(&mut self) -> Option<Event>;
+
+    /// Gets the gamepad with the given [`GamepadId`], if there is one.
+    fn gamepad_by_id(&self, id: GamepadId) -> Option<&Self::Gamepad>;
+
+    /// Gets the gamepad with the given [`GamepadId`] mutably, if there is
+    /// one, e.g. to call [`GamepadRead::buttons_changed`].
+    fn gamepad_by_id_mut(
+        &mut self,
+        id: GamepadId,
+    ) -> Option<&mut Self::Gamepad>;
+
+    /// Takes a [`GamepadState`] snapshot of every connected gamepad into
+    /// `out` by slot, returning how many were written, see
+    /// [`Girl::snapshot_all`].
+    ///
+    /// [`Girl::snapshot_all`]: crate::Girl::snapshot_all
+    fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize;
+}
+
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::iter;
+
+    use super::{GamepadRead, GamepadSource};
+    use crate::{
+        Button, ButtonChanges, ButtonTracker, Event, GamepadId, GamepadState,
+        Stick, Trigger, ring::EventRing,
+    };
+
+    /// Position of the left [`Stick`] in the script.
+    const OFFSET: [f64; 2] = [0.5, -0.25];
+    /// Value of the right [`Trigger`] in the script.
+    const PULL: f64 = 0.75;
+
+    /// Gamepad reading a [`GamepadState`] as is, without any allocation.
+    #[derive(Debug, Default)]
+    struct MockPad {
+        /// Instance ID.
+        id: u32,
+        /// Current input.
+        state: GamepadState,
+        /// Held [`Button`]s of the previous
+        /// [`GamepadRead::buttons_changed`] call.
+        tracker: ButtonTracker,
+    }
+
+    impl MockPad {
+        /// Applies the input [`Event`]s of the script to the state.
+        fn apply(&mut self, event: Event) {
+            let state = &mut self.state;
+            if let Event::ControllerButtonDown { button, .. } = event {
+                state.buttons.insert(button);
+            }
+            if let Event::ControllerButtonUp { button, .. } = event {
+                state.buttons.remove(button);
+            }
+            if let Event::ControllerStickMotion {
+                stick: Stick::Left,
+                offset,
+                ..
+            } = event
+            {
+                state.left_stick = offset;
+            }
+            if let Event::ControllerTriggerMotion {
+                trigger: Trigger::Right,
+                offset,
+                ..
+            } = event
+            {
+                state.right_trigger = offset;
+            }
+        }
+    }
+
+    impl GamepadRead for MockPad {
+        fn button_value(&self, buttons: Button) -> f64 {
+            if self.buttons_pressed(buttons) { 1.0 } else { 0.0 }
+        }
+
+        fn buttons(&self, buttons: Button) -> Button {
+            self.state.buttons & buttons
+        }
+
+        fn buttons_changed(&mut self) -> ButtonChanges {
+            self.tracker.changes(self.state.buttons)
+        }
+
+        fn connected(&self) -> bool {
+            self.state.connected
+        }
+
+        fn id(&self) -> GamepadId {
+            GamepadId::from_raw(self.id)
+        }
+
+        fn stick(&self, stick: Stick) -> [f64; 2] {
+            self.state.stick(stick)
+        }
+
+        fn trigger(&self, trigger: Trigger) -> f64 {
+            self.state.trigger(trigger)
+        }
+    }
+
+    /// Fixed-capacity source of scripted [`Event`]s applied to its
+    /// gamepads as they are polled.
+    #[derive(Debug, Default)]
+    struct MockSource {
+        /// [`Event`]s not polled yet.
+        events: EventRing<8>,
+        /// Gamepads by instance ID.
+        pads: [MockPad; 2],
+    }
+
+    impl GamepadSource for MockSource {
+        type Gamepad = MockPad;
+
+        fn event(&mut self) -> Option<Event> {
+            let event = self.events.pop()?;
+            let id = GamepadId::from_raw(event.which().unwrap_or(u32::MAX));
+            if let Some(pad) = self.gamepad_by_id_mut(id) {
+                pad.apply(event);
+            }
+            Some(event)
+        }
+
+        fn gamepad_by_id(&self, id: GamepadId) -> Option<&MockPad> {
+            self.pads.iter().find(|pad| pad.connected() && pad.id() == id)
+        }
+
+        fn gamepad_by_id_mut(&mut self, id: GamepadId) -> Option<&mut MockPad> {
+            self.pads.iter_mut().find(|pad| pad.connected() && pad.id() == id)
+        }
+
+        fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+            out.fill(None);
+            for (slot, pad) in out.iter_mut().zip(&self.pads) {
+                *slot = pad.connected().then(|| pad.state());
+            }
+            out.iter().flatten().count()
+        }
+    }
+
+    /// Drains the pending [`Event`]s of `source` as a game loop would,
+    /// returning how many there were.
+    fn poll(source: &mut impl GamepadSource) -> usize {
+        iter::from_fn(|| source.event()).count()
+    }
+
+    /// The polling API works on a source needing no allocator: events
+    /// update the gamepads, which are then read through [`GamepadRead`].
+    #[test]
+    fn polls_without_alloc() {
+        let mut source = MockSource::default();
+        for (id, pad) in (0..).zip(&mut source.pads) {
+            pad.id = id;
+        }
+        source.pads[1].state.connected = true;
+        for event in [
+            Event::button_down(1, Button::A),
+            Event::button_down(0, Button::B),
+            Event::stick_motion(1, Stick::Left, OFFSET),
+            Event::button_down(1, Button::X),
+            Event::trigger_motion(1, Trigger::Right, PULL),
+            Event::button_up(1, Button::X),
+        ] {
+            assert!(source.events.push(event).is_none());
+        }
+        assert_eq!(poll(&mut source), 6);
+        assert_eq!(poll(&mut source), 0);
+
+        let (absent, present) =
+            (GamepadId::from_raw(0), GamepadId::from_raw(1));
+        assert!(source.gamepad_by_id(absent).is_none());
+        let pad = source.gamepad_by_id(present);
+        assert!(pad.is_some_and(|pad| pad.buttons_exactly(Button::A)
+            && pad.buttons_released(Button::B | Button::X)));
+
+        let expected = GamepadState {
+            connected: true,
+            buttons: Button::A,
+            left_stick: OFFSET,
+            right_trigger: PULL,
+            ..GamepadState::default()
+        };
+        assert_eq!(pad.map(GamepadRead::state), Some(expected));
+        let mut states = [None; 4];
+        assert_eq!(source.snapshot_all(&mut states), 1);
+        assert_eq!(states, [None, Some(expected), None, None]);
+
+        let changes = source
+            .gamepad_by_id_mut(present)
+            .map(|mock| [mock.buttons_changed(), mock.buttons_changed()]);
+        let pressed =
+            ButtonChanges { pressed: Button::A, ..Default::default() };
+        assert_eq!(changes, Some([pressed, ButtonChanges::default()]));
+    }
+}
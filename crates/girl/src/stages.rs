@@ -0,0 +1,626 @@
+//! Built-in processing stages of [`Girl`], run around the
+//! [`EventMiddleware`]s added with [`Girl::add_middleware`].
+//!
+//! [`Girl`]: crate::Girl
+//! [`Girl::add_middleware`]: crate::Girl::add_middleware
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{
+    ActivityTracker, BackgroundPolicy, Error, Event, EventMiddleware,
+    GamepadId, IdleConfig, IdleTracker, MiddlewareChain, MiddlewareCtx,
+    Misc1Role, Misc1Suppression, PerfCounters, gamepad::Gamepad,
+    middleware::Scope, power::PowerWatch, poweroff::IdlePowerOff,
+    shared::Shared,
+};
+#[cfg(feature = "sensors")]
+use crate::{SensorDivider, background::SensorRestore, retry::SensorRetry};
+
+/// What a [`Stage`] can see and do when acting on the [`Gamepad`]s.
+#[derive(Debug)]
+pub(crate) struct StageCtx<'ctx> {
+    /// When the [`Stage`] acts, on the clock of the hook.
+    now: Instant,
+    /// The [`Gamepad`]s opened by [`Girl`].
+    ///
+    /// [`Girl`]: crate::Girl
+    pads: &'ctx mut BTreeMap<GamepadId, Gamepad>,
+    /// State shared with the [`Gamepad`]s.
+    #[cfg_attr(
+        not(feature = "sensors"),
+        expect(dead_code, reason = "feature gated")
+    )]
+    shared: &'ctx Shared,
+    /// [`Event`]s to report, processed like the ones of SDL2.
+    out: &'ctx mut VecDeque<Event>,
+}
+
+impl<'ctx> StageCtx<'ctx> {
+    /// Creates a [`StageCtx`] acting at `now`, queueing [`Event`]s to `out`.
+    pub(crate) const fn new(
+        now: Instant,
+        pads: &'ctx mut BTreeMap<GamepadId, Gamepad>,
+        shared: &'ctx Shared,
+        out: &'ctx mut VecDeque<Event>,
+    ) -> Self {
+        Self { now, pads, shared, out }
+    }
+}
+
+/// Tracker [`Girl`] keeps up to date, told about the [`Gamepad`]s it opens
+/// and closes, and acting on them as the [`Event`]s and time pass.
+///
+/// The ones looking at [`Event`]s are [`EventMiddleware`]s too, see
+/// [`Stages::run`].
+///
+/// [`Girl`]: crate::Girl
+pub(crate) trait Stage {
+    /// Stops tracking the [`Gamepad`] with the [`GamepadId`] once closed.
+    fn closed(&mut self, _id: GamepadId) {}
+
+    /// Starts tracking a [`Gamepad`] once opened.
+    fn opened(&mut self, _pad: &Gamepad) {}
+
+    /// Acts on what the [`Event`] just processed asked for.
+    fn react(&mut self, _ctx: &mut StageCtx<'_>) {}
+
+    /// Acts on [`Girl::tick`], at its clock.
+    ///
+    /// [`Girl::tick`]: crate::Girl::tick
+    fn tick(&mut self, _ctx: &mut StageCtx<'_>) {}
+
+    /// Acts on [`Girl::update`], at the wall clock.
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    fn update(&mut self, _ctx: &mut StageCtx<'_>) {}
+}
+
+/// The [`Stage`]s of [`Girl`], in the order they run.
+///
+/// [`Girl`]: crate::Girl
+#[derive(Debug)]
+pub(crate) struct Stages {
+    /// Skips sensor updates, see [`Girl::set_sensor_event_divisor`].
+    ///
+    /// [`Girl::set_sensor_event_divisor`]:
+    ///     crate::Girl::set_sensor_event_divisor
+    #[cfg(feature = "sensors")]
+    divider: SensorDivider,
+    /// Drops [`Button::Misc1`] [`Event`]s, see [`Misc1Filter`].
+    ///
+    /// [`Button::Misc1`]: crate::Button::Misc1
+    misc1: Misc1Filter,
+    /// Pauses output in the background, see [`BackgroundPause`].
+    background: BackgroundPause,
+    /// Queries the batteries, see [`PowerWatch`].
+    power: PowerWatch,
+    /// Retries failed sensor enables, see [`Girl::set_sensor_retry_window`].
+    ///
+    /// [`Girl::set_sensor_retry_window`]:
+    ///     crate::Girl::set_sensor_retry_window
+    #[cfg(feature = "sensors")]
+    sensor_retry: SensorRetry,
+    /// Tracks the input, see [`IdleTracking`].
+    idle: IdleTracking,
+    /// [`Event`]s out of the stages before the [`MiddlewareChain`].
+    refined: VecDeque<Event>,
+    /// [`Event`]s out of the [`MiddlewareChain`].
+    processed: VecDeque<Event>,
+}
+
+impl Stages {
+    /// Creates the [`Stages`] of a [`Girl`] whose clock starts at `clock`.
+    ///
+    /// [`Girl`]: crate::Girl
+    #[expect(clippy::single_call_fn, reason = "keeps the fields private")]
+    pub(crate) fn new(clock: Instant) -> Self {
+        let misc1 = Misc1Filter {
+            suppression: Misc1Suppression::platform(),
+            roles: BTreeMap::new(),
+        };
+        let background = BackgroundPause {
+            policy: BackgroundPolicy::disabled(),
+            backgrounded: false,
+            transition: None,
+            #[cfg(feature = "sensors")]
+            restore: SensorRestore::default(),
+        };
+        let timeout = ActivityTracker::DEFAULT_IDLE_TIMEOUT;
+        let idle = IdleTracking {
+            activity: ActivityTracker::new(timeout),
+            tracker: IdleTracker::new(IdleConfig::new(), clock),
+            power_off: IdlePowerOff::new(None),
+            capable: BTreeSet::new(),
+            clock,
+        };
+        Self {
+            #[cfg(feature = "sensors")]
+            divider: SensorDivider::new(),
+            misc1,
+            background,
+            power: PowerWatch::default(),
+            #[cfg(feature = "sensors")]
+            sensor_retry: SensorRetry::default(),
+            idle,
+            refined: VecDeque::new(),
+            processed: VecDeque::new(),
+        }
+    }
+
+    /// Gets the [`SensorDivider`] skipping sensor updates.
+    #[cfg(feature = "sensors")]
+    pub(crate) const fn divider(&self) -> &SensorDivider {
+        &self.divider
+    }
+
+    /// Gets the [`SensorDivider`] skipping sensor updates, mutably.
+    #[cfg(feature = "sensors")]
+    pub(crate) const fn divider_mut(&mut self) -> &mut SensorDivider {
+        &mut self.divider
+    }
+
+    /// Gets the [`Misc1Filter`].
+    pub(crate) const fn misc1(&self) -> &Misc1Filter {
+        &self.misc1
+    }
+
+    /// Gets the [`Misc1Filter`], mutably.
+    pub(crate) const fn misc1_mut(&mut self) -> &mut Misc1Filter {
+        &mut self.misc1
+    }
+
+    /// Gets the [`BackgroundPause`].
+    pub(crate) const fn background(&self) -> &BackgroundPause {
+        &self.background
+    }
+
+    /// Gets the [`BackgroundPause`], mutably.
+    pub(crate) const fn background_mut(&mut self) -> &mut BackgroundPause {
+        &mut self.background
+    }
+
+    /// Gets the [`SensorRetry`] of failed sensor enables.
+    #[cfg(feature = "sensors")]
+    pub(crate) const fn sensor_retry(&self) -> &SensorRetry {
+        &self.sensor_retry
+    }
+
+    /// Replaces the [`SensorRetry`] of failed sensor enables.
+    #[cfg(feature = "sensors")]
+    pub(crate) fn set_sensor_retry(&mut self, retry: SensorRetry) {
+        self.sensor_retry = retry;
+    }
+
+    /// Gets the [`IdleTracking`].
+    pub(crate) const fn idle(&self) -> &IdleTracking {
+        &self.idle
+    }
+
+    /// Gets the [`IdleTracking`], mutably.
+    pub(crate) const fn idle_mut(&mut self) -> &mut IdleTracking {
+        &mut self.idle
+    }
+
+    /// Runs an [`Event`] through the [`Stage`]s filtering it, then the
+    /// `middleware`, then the [`Stage`]s observing what comes out of it,
+    /// appending the result to `out`.
+    pub(crate) fn run(
+        &mut self,
+        scope: Scope<'_>,
+        event: Event,
+        middleware: &mut MiddlewareChain,
+        out: &mut VecDeque<Event>,
+        mut perf: Option<&mut PerfCounters>,
+    ) {
+        #[cfg(feature = "sensors")]
+        let divider = &mut self.divider;
+        #[cfg(feature = "sensors")]
+        let mut divide =
+            |update, _: &mut MiddlewareCtx<'_>| divider.feed(update);
+        let before: &mut [&mut dyn EventMiddleware] = &mut [
+            #[cfg(feature = "sensors")]
+            &mut divide,
+            &mut self.misc1,
+            &mut self.background,
+        ];
+        let refined = &mut self.refined;
+        scope.drive(event, before.len(), refined, |index, input, ctx| {
+            before.get_mut(index)?.process(input, ctx)
+        });
+        while let Some(refined_event) = self.refined.pop_front() {
+            let processed = &mut self.processed;
+            middleware.run(
+                scope,
+                refined_event,
+                processed,
+                perf.as_deref_mut(),
+            );
+        }
+        let after: &mut [&mut dyn EventMiddleware] = &mut [&mut self.idle];
+        while let Some(processed) = self.processed.pop_front() {
+            scope.drive(processed, after.len(), out, |index, input, ctx| {
+                after.get_mut(index)?.process(input, ctx)
+            });
+        }
+    }
+
+    /// Tells the [`Stage`]s about a [`Gamepad`] once opened.
+    pub(crate) fn opened(&mut self, pad: &Gamepad) {
+        self.each(|stage| stage.opened(pad));
+    }
+
+    /// Tells the [`Stage`]s about the [`Gamepad`] with the [`GamepadId`]
+    /// once closed.
+    pub(crate) fn closed(&mut self, id: GamepadId) {
+        self.each(|stage| stage.closed(id));
+    }
+
+    /// Lets the [`Stage`]s act on the [`Event`] just processed.
+    pub(crate) fn react(&mut self, ctx: &mut StageCtx<'_>) {
+        self.each(|stage| stage.react(ctx));
+    }
+
+    /// Lets the [`Stage`]s act on [`Girl::update`].
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    pub(crate) fn update(&mut self, ctx: &mut StageCtx<'_>) {
+        self.each(|stage| stage.update(ctx));
+    }
+
+    /// Lets the [`Stage`]s act on [`Girl::tick`].
+    ///
+    /// [`Girl::tick`]: crate::Girl::tick
+    pub(crate) fn tick(&mut self, ctx: &mut StageCtx<'_>) {
+        self.each(|stage| stage.tick(ctx));
+    }
+
+    /// Calls `visit` with every [`Stage`], in order.
+    fn each<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&mut dyn Stage),
+    {
+        let stages: &mut [&mut dyn Stage] = &mut [
+            &mut self.misc1,
+            &mut self.background,
+            &mut self.power,
+            #[cfg(feature = "sensors")]
+            &mut self.sensor_retry,
+            &mut self.idle,
+        ];
+        for stage in stages {
+            visit(&mut **stage);
+        }
+    }
+}
+
+/// [`Stage`] dropping the [`Button::Misc1`] [`Event`]s of the roles
+/// suppressed with [`Girl::set_misc1_suppression`].
+///
+/// [`Button::Misc1`]: crate::Button::Misc1
+/// [`Girl::set_misc1_suppression`]: crate::Girl::set_misc1_suppression
+#[derive(Debug, Clone)]
+pub(crate) struct Misc1Filter {
+    /// The suppressed roles.
+    suppression: Misc1Suppression,
+    /// [`Misc1Role`] of every opened [`Gamepad`].
+    roles: BTreeMap<GamepadId, Misc1Role>,
+}
+
+impl EventMiddleware for Misc1Filter {
+    fn process(
+        &mut self,
+        event: Event,
+        ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        let role = event
+            .which()
+            .and_then(|which| self.roles.get(&ctx.id(which)))
+            .copied()
+            .unwrap_or(Misc1Role::Unknown);
+        self.suppression.filter(role, event)
+    }
+}
+
+impl Stage for Misc1Filter {
+    fn closed(&mut self, id: GamepadId) {
+        _ = self.roles.remove(&id);
+    }
+
+    fn opened(&mut self, pad: &Gamepad) {
+        _ = self.roles.insert(pad.id(), pad.misc1_role());
+    }
+}
+
+impl Misc1Filter {
+    /// Gets the suppressed roles.
+    pub(crate) const fn suppression(&self) -> Misc1Suppression {
+        self.suppression
+    }
+
+    /// Sets the suppressed roles.
+    pub(crate) const fn set_suppression(
+        &mut self,
+        suppression: Misc1Suppression,
+    ) {
+        self.suppression = suppression;
+    }
+}
+
+/// [`Stage`] pausing what the [`BackgroundPolicy`] asks for on
+/// [`Event::AppBackgrounded`], and restoring it on
+/// [`Event::AppForegrounded`].
+#[derive(Debug, Clone)]
+pub(crate) struct BackgroundPause {
+    /// What is paused.
+    policy: BackgroundPolicy,
+    /// Whether the application is in the background.
+    backgrounded: bool,
+    /// Whether to pause or restore on the next [`Stage::react`], if either.
+    transition: Option<bool>,
+    /// The [`Sensor`]s disabled in the background.
+    ///
+    /// [`Sensor`]: crate::Sensor
+    #[cfg(feature = "sensors")]
+    restore: SensorRestore,
+}
+
+impl EventMiddleware for BackgroundPause {
+    fn process(
+        &mut self,
+        event: Event,
+        _ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        if matches!(event, Event::AppBackgrounded) && !self.backgrounded {
+            self.backgrounded = true;
+            self.transition = Some(true);
+        }
+        if matches!(event, Event::AppForegrounded) {
+            self.backgrounded = false;
+            self.transition = Some(false);
+        }
+        Some(event)
+    }
+}
+
+impl Stage for BackgroundPause {
+    fn react(&mut self, ctx: &mut StageCtx<'_>) {
+        let Some(background) = self.transition.take() else {
+            return;
+        };
+        if background {
+            self.pause(ctx.pads);
+        }
+        #[cfg(feature = "sensors")]
+        if !background {
+            self.resume(ctx.pads);
+        }
+    }
+}
+
+impl BackgroundPause {
+    /// Gets what is paused.
+    pub(crate) const fn policy(&self) -> BackgroundPolicy {
+        self.policy
+    }
+
+    /// Sets what is paused.
+    pub(crate) const fn set_policy(&mut self, policy: BackgroundPolicy) {
+        self.policy = policy;
+    }
+
+    /// Checks if the application is in the background.
+    pub(crate) const fn backgrounded(&self) -> bool {
+        self.backgrounded
+    }
+
+    /// Checks if LED animations are paused.
+    pub(crate) const fn leds_paused(&self) -> bool {
+        self.backgrounded && self.policy.pause_leds
+    }
+
+    /// Pauses the output of the `pads`.
+    #[cfg_attr(
+        not(feature = "sensors"),
+        expect(
+            clippy::needless_pass_by_ref_mut,
+            reason = "remembers the sensors"
+        )
+    )]
+    fn pause(&mut self, pads: &mut BTreeMap<GamepadId, Gamepad>) {
+        let policy = self.policy;
+        for pad in pads.values_mut() {
+            // pausing is best-effort, the pad may be gone already
+            #[cfg(feature = "rumble")]
+            if policy.stop_rumble {
+                if pad.has_rumble() {
+                    drop(pad.end_rumble());
+                }
+                if pad.has_rumble_triggers() {
+                    drop(pad.end_rumble_triggers());
+                }
+            }
+            if policy.pause_leds {
+                drop(pad.cancel_identify());
+            }
+            #[cfg(feature = "sensors")]
+            if policy.disable_sensors {
+                let enabled: Vec<_> = pad
+                    .available_sensors()
+                    .into_iter()
+                    .filter(|&sensor| pad.sensor_enabled(sensor))
+                    .filter(|&sensor| pad.disable_sensor(sensor).is_ok())
+                    .collect();
+                self.restore.remember(pad.id(), pad.guid(), enabled);
+            }
+        }
+    }
+
+    /// Restores what [`pause`] paused on the `pads`.
+    ///
+    /// [`pause`]: Self::pause
+    #[cfg(feature = "sensors")]
+    fn resume(&mut self, pads: &BTreeMap<GamepadId, Gamepad>) {
+        let connected: Vec<_> =
+            pads.iter().map(|(&id, pad)| (id, pad.guid())).collect();
+        for (id, sensors) in self.restore.assign(&connected) {
+            let Some(pad) = pads.get(&id) else {
+                continue;
+            };
+            for sensor in sensors {
+                // restoring is best-effort, like pausing
+                drop(pad.enable_sensor(sensor));
+            }
+        }
+    }
+}
+
+/// Queries the battery of every [`Gamepad`] due for it, see
+/// [`PowerWatch::due`], queueing [`Event::PowerQueryFailed`] for those whose
+/// queries keep failing.
+impl Stage for PowerWatch {
+    fn closed(&mut self, id: GamepadId) {
+        self.forget(id);
+    }
+
+    fn update(&mut self, ctx: &mut StageCtx<'_>) {
+        for (&id, pad) in &*ctx.pads {
+            if !self.due(id, ctx.now) {
+                continue;
+            }
+            let power = pad.try_power();
+            if let Some(failed) = self.observe(id, &power) {
+                ctx.out.push_back(failed);
+            }
+        }
+    }
+}
+
+/// Retries the failed [`Sensor`] enables.
+///
+/// [`Sensor`]: crate::Sensor
+#[cfg(feature = "sensors")]
+impl Stage for SensorRetry {
+    fn closed(&mut self, id: GamepadId) {
+        self.forget(id);
+    }
+
+    fn update(&mut self, ctx: &mut StageCtx<'_>) {
+        for (id, sensor) in ctx.shared.take_failed_sensors() {
+            if ctx.pads.contains_key(&id) {
+                self.queue(id, sensor, ctx.now);
+            }
+        }
+        if self.is_empty() {
+            return;
+        }
+        let pads = &*ctx.pads;
+        let events = self.retry(ctx.now, |id, sensor| {
+            pads.get(&id)
+                .ok_or(Error::InvalidArgument { what: "gamepad" })?
+                .try_enable_sensor(sensor)
+        });
+        ctx.out.extend(events);
+    }
+}
+
+/// [`Stage`] tracking the input of every [`Gamepad`], for the most recently
+/// active one, for idle times, and to power off the idle ones.
+#[derive(Debug, Clone)]
+pub(crate) struct IdleTracking {
+    /// The most recently active [`Gamepad`], at the wall clock.
+    activity: ActivityTracker,
+    /// Idle times, at the clock of [`Girl::tick`].
+    ///
+    /// [`Girl::tick`]: crate::Girl::tick
+    tracker: IdleTracker,
+    /// Powers off the idle [`Gamepad`]s.
+    power_off: IdlePowerOff,
+    /// The [`Gamepad`]s able to power off.
+    capable: BTreeSet<GamepadId>,
+    /// The clock of [`Girl::tick`] as of its last call.
+    ///
+    /// [`Girl::tick`]: crate::Girl::tick
+    clock: Instant,
+}
+
+impl EventMiddleware for IdleTracking {
+    fn process(
+        &mut self,
+        event: Event,
+        ctx: &mut MiddlewareCtx<'_>,
+    ) -> Option<Event> {
+        _ = self.activity.observe(&event, ctx.now());
+        let id = event.which().map(|which| ctx.id(which));
+        if let Some(resumed) = self.tracker.observe_as(&event, id, self.clock) {
+            ctx.emit(resumed);
+        }
+        Some(event)
+    }
+}
+
+impl Stage for IdleTracking {
+    fn closed(&mut self, id: GamepadId) {
+        self.power_off.forget(id);
+        _ = self.capable.remove(&id);
+    }
+
+    fn opened(&mut self, pad: &Gamepad) {
+        if pad.can_power_off() {
+            _ = self.capable.insert(pad.id());
+        }
+    }
+
+    fn tick(&mut self, ctx: &mut StageCtx<'_>) {
+        self.clock = ctx.now;
+        if let Some(idle) = self.tracker.poll(ctx.now) {
+            ctx.out.push_back(idle);
+        }
+        if self.power_off.after().is_none() {
+            return;
+        }
+        let pads = &mut *ctx.pads;
+        let capable = self.capable.iter().copied();
+        let events =
+            self.power_off.poll(ctx.now, &self.tracker, capable, |id| {
+                pads.get_mut(&id)
+                    .ok_or(Error::GamepadNotFound(id))?
+                    .request_power_off()
+            });
+        ctx.out.extend(events);
+    }
+}
+
+impl IdleTracking {
+    /// Gets the [`ActivityTracker`] of the most recently active
+    /// [`Gamepad`].
+    pub(crate) const fn activity(&self) -> &ActivityTracker {
+        &self.activity
+    }
+
+    /// Sets how long a [`Gamepad`] is remembered as active without input.
+    pub(crate) const fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.activity.set_idle_timeout(idle_timeout);
+    }
+
+    /// Gets the [`IdleTracker`] of the idle times.
+    pub(crate) const fn tracker(&self) -> &IdleTracker {
+        &self.tracker
+    }
+
+    /// Sets what counts as input, see [`IdleTracker::set_config`].
+    pub(crate) const fn set_config(&mut self, config: IdleConfig) {
+        self.tracker.set_config(config);
+    }
+
+    /// Gets the idle time after which [`Gamepad`]s are powered off.
+    pub(crate) const fn power_off_idle(&self) -> Option<Duration> {
+        self.power_off.after()
+    }
+
+    /// Powers off [`Gamepad`]s idle for `after`, or stops if [`None`].
+    pub(crate) fn set_power_off_idle(&mut self, after: Option<Duration>) {
+        self.power_off = IdlePowerOff::new(after);
+    }
+}
@@ -0,0 +1,159 @@
+//! Detection of [`Gamepad`]s virtualized by Steam Input.
+//!
+//! [`Gamepad`]: crate::Gamepad
+
+use alloc::collections::BTreeMap;
+
+use crate::{DeviceInfo, GamepadId, Guid};
+
+/// USB vendor ID of Valve.
+const VALVE_VENDOR: u16 = 0x28de;
+
+/// USB product IDs of the virtual controllers Steam Input creates, under
+/// [`VALVE_VENDOR`].
+const VIRTUAL_PRODUCTS: [u16; 1] = [0x11ff];
+
+/// Lowercase name fragments of the virtual controllers Steam Input creates.
+const VIRTUAL_NAMES: [&str; 1] = ["steam virtual gamepad"];
+
+/// Heuristics telling whether a [`Gamepad`] is a virtual controller created
+/// by Steam Input rather than the physical one, see
+/// [`Gamepad::is_steam_virtual`].
+///
+/// Under Steam Input, SDL2 sees a virtual Xbox-like controller, so features
+/// of the physical one, e.g. its touchpad and gyroscope, are missing. A
+/// [`Gamepad`] is virtual if, in order:
+///
+/// 1. a verdict was forced for its [`Guid`] with [`force`], that verdict;
+/// 2. SDL2 reports a Steam Input handle for it;
+/// 3. its [`Guid`] has the vendor and product IDs of a Steam virtual
+///    controller, see [`Guid::vendor_product`];
+/// 4. its name contains "Steam Virtual Gamepad", ignoring case.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{DeviceInfo, SteamInputHeuristics, prelude::*};
+/// let mut heuristics = SteamInputHeuristics::new();
+/// let device = |name: &str, guid: &str| -> Result<DeviceInfo, Error> {
+///     let guid = guid.parse()?;
+///     Ok(DeviceInfo::new(name.to_owned(), guid, FormFactor::Standard))
+/// };
+///
+/// // posing as an Xbox 360 controller, but with a Steam virtual product ID
+/// let xbox = "Xbox 360 Controller";
+/// let virtual_id = device(xbox, "03000000de280000ff11000001000000")?;
+/// assert!(heuristics.is_virtual(&virtual_id, false));
+///
+/// let steam = "Steam Virtual Gamepad";
+/// let virtual_name = device(steam, "030000005e0400008e02000010010000")?;
+/// assert!(heuristics.is_virtual(&virtual_name, false));
+///
+/// let ps5 = device("PS5 Controller", "030000004c050000e60c000000010000")?;
+/// assert!(!heuristics.is_virtual(&ps5, false));
+/// assert!(heuristics.is_virtual(&ps5, true));
+///
+/// // forced verdicts take precedence
+/// heuristics.force(virtual_id.guid, false);
+/// assert!(!heuristics.is_virtual(&virtual_id, false));
+/// assert_eq!(heuristics.unforce(virtual_id.guid), Some(false));
+/// assert!(heuristics.is_virtual(&virtual_id, false));
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`force`]: Self::force
+/// [`Gamepad`]: crate::Gamepad
+/// [`Gamepad::is_steam_virtual`]: crate::Gamepad::is_steam_virtual
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SteamInputHeuristics {
+    /// Verdicts forced per [`Guid`].
+    forced: BTreeMap<Guid, bool>,
+}
+
+impl SteamInputHeuristics {
+    /// Creates [`SteamInputHeuristics`] without forced verdicts.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { forced: BTreeMap::new() }
+    }
+
+    /// Forces the verdict of [`Gamepad`]s with the [`Guid`], e.g. for a
+    /// controller the heuristics misjudge.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn force(&mut self, guid: Guid, is_virtual: bool) {
+        _ = self.forced.insert(guid, is_virtual);
+    }
+
+    /// Removes the verdict forced for the [`Guid`], returning it.
+    #[inline]
+    pub fn unforce(&mut self, guid: Guid) -> Option<bool> {
+        self.forced.remove(&guid)
+    }
+
+    /// Gets the verdict forced for the [`Guid`], if any.
+    #[must_use]
+    #[inline]
+    pub fn forced(&self, guid: Guid) -> Option<bool> {
+        self.forced.get(&guid).copied()
+    }
+
+    /// Checks if the device is virtualized by Steam Input, given whether
+    /// SDL2 reports a Steam Input handle for it.
+    #[must_use]
+    #[inline]
+    pub fn is_virtual(&self, device: &DeviceInfo, steam_handle: bool) -> bool {
+        if let Some(forced) = self.forced(device.guid) {
+            return forced;
+        }
+        let virtual_id =
+            device.guid.vendor_product().is_some_and(|(vendor, product)| {
+                vendor == VALVE_VENDOR && VIRTUAL_PRODUCTS.contains(&product)
+            });
+        let name = device.name.to_lowercase();
+        let virtual_name =
+            VIRTUAL_NAMES.iter().any(|fragment| name.contains(fragment));
+        steam_handle || virtual_id || virtual_name
+    }
+}
+
+/// Which [`Gamepad`]s are virtualized by Steam Input, see
+/// [`Girl::steam_input`].
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`Girl::steam_input`]: crate::Girl::steam_input
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SteamInputStatus {
+    /// Whether every connected [`Gamepad`] is virtualized.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    pub devices: BTreeMap<GamepadId, bool>,
+}
+
+impl SteamInputStatus {
+    /// Checks if any [`Gamepad`] is virtualized, e.g. to suggest disabling
+    /// Steam Input for gyroscope support.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[must_use]
+    #[inline]
+    pub fn any_virtual(&self) -> bool {
+        self.devices.values().any(|&is_virtual| is_virtual)
+    }
+
+    /// Iterates the [`GamepadId`]s of the virtualized [`Gamepad`]s.
+    ///
+    /// [`Gamepad`]: crate::Gamepad
+    #[inline]
+    pub fn virtualized(&self) -> impl Iterator<Item = GamepadId> {
+        self.devices
+            .iter()
+            .filter(|&(_, &is_virtual)| is_virtual)
+            .map(|(&id, _)| id)
+    }
+}
@@ -0,0 +1,76 @@
+//! Summary of the [`Event`]s of an update cycle, see
+//! [`Girl::update_and_collect`].
+//!
+//! [`Girl::update_and_collect`]: crate::Girl::update_and_collect
+
+use crate::{Event, EventKind};
+
+/// What happened in an update cycle, e.g. to skip processing input
+/// altogether when nothing did, see [`Girl::update_and_collect`].
+///
+/// Tells input from housekeeping by the [`EventKind`] of every [`Event`].
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{UpdateSummary, prelude::*};
+/// // empty cycle
+/// let summary = UpdateSummary::new();
+/// assert!(summary.is_empty());
+/// assert!(!summary.any_input && !summary.device_changes);
+///
+/// // axis only
+/// let mut summary = UpdateSummary::new();
+/// summary.observe(&Event::stick_motion(0, Stick::Left, [0.0, 0.5]));
+/// summary.observe(&Event::trigger_motion(0, Trigger::Right, 0.25));
+/// assert!(summary.any_input && !summary.device_changes);
+/// assert_eq!(summary.events, 2);
+///
+/// // device change only, housekeeping doesn't count as input
+/// let mut summary = UpdateSummary::new();
+/// summary.observe(&Event::device_added(1));
+/// summary.observe(&Event::ControllerDeviceRemapped { which: 1 });
+/// assert!(!summary.any_input && summary.device_changes);
+/// assert_eq!(summary.events, 2);
+/// ```
+///
+/// [`Girl::update_and_collect`]: crate::Girl::update_and_collect
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateSummary {
+    /// Whether any [`EventKind::Input`] [`Event`] arrived.
+    pub any_input: bool,
+    /// Whether any controller connected or disconnected, i.e. any
+    /// [`EventKind::Device`] [`Event`] arrived.
+    pub device_changes: bool,
+    /// Number of [`Event`]s of any [`EventKind`].
+    pub events: usize,
+}
+
+impl UpdateSummary {
+    /// Creates an [`UpdateSummary`] of an empty cycle.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { any_input: false, device_changes: false, events: 0 }
+    }
+
+    /// Checks if no [`Event`]s arrived.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.events == 0
+    }
+
+    /// Adds the [`Event`] to the summary.
+    #[inline]
+    pub const fn observe(&mut self, event: &Event) {
+        match event.kind() {
+            EventKind::Input => self.any_input = true,
+            EventKind::Device => self.device_changes = true,
+            EventKind::Housekeeping => {}
+        }
+        self.events = self.events.saturating_add(1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Fabrication of [`Event`]s for tests of code consuming them.
+
+#[cfg(feature = "sensors")]
+use crate::Sensor;
+use crate::{Button, Event, Stick, Trigger};
+#[cfg(feature = "touchpad")]
+use crate::{TouchpadAction, TouchpadEvent};
+
+/// Standard gravity in m/s², reported by an accelerometer at rest.
+#[cfg(feature = "sensors")]
+const STANDARD_GRAVITY: f64 = 9.806_65;
+
+/// Builds [`Event`]s of a single controller with sensible defaults, e.g. to
+/// test code consuming [`Event`]s without a controller.
+///
+/// Set the details that matter to the test, then finish with the kind of
+/// [`Event`] to build. The simpler [`Event`]s are also available as
+/// constructors like [`Event::button_down`]. The defaults are:
+///
+/// - touchpad `0` and finger `0`, touched at the center `[0.5, 0.5]` with full
+///   pressure;
+/// - sensor data of a controller lying flat at rest: no rotation, and gravity
+///   along the `y` axis for accelerometers.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{EventBuilder, prelude::*};
+/// let pad = EventBuilder::new(0);
+///
+/// let press = pad.button_down(Button::A);
+/// assert!(matches!(press, Event::ControllerButtonDown { which: 0, .. }));
+/// let tilt = pad.stick_motion(Stick::Left, [0.0, 1.0]);
+/// assert_eq!(tilt.which(), Some(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(any(feature = "touchpad", feature = "sensors")), derive(Eq))]
+pub struct EventBuilder {
+    /// Controller instance ID.
+    which: u32,
+    /// Touchpad index.
+    #[cfg(feature = "touchpad")]
+    touchpad: u32,
+    /// Finger index.
+    #[cfg(feature = "touchpad")]
+    finger: u32,
+    /// Normalized finger position `[x, y]`.
+    #[cfg(feature = "touchpad")]
+    position: [f32; 2],
+    /// Normalized finger pressure.
+    #[cfg(feature = "touchpad")]
+    pressure: f32,
+    /// Sensor data `[x, y, z]`, [`None`] for a controller at rest.
+    #[cfg(feature = "sensors")]
+    data: Option<[f64; 3]>,
+}
+
+impl EventBuilder {
+    /// Creates an [`EventBuilder`] of the controller with the `which`
+    /// instance ID, the raw value of its [`GamepadId`].
+    ///
+    /// [`GamepadId`]: crate::GamepadId
+    #[must_use]
+    #[inline]
+    pub const fn new(which: u32) -> Self {
+        Self {
+            which,
+            #[cfg(feature = "touchpad")]
+            touchpad: 0,
+            #[cfg(feature = "touchpad")]
+            finger: 0,
+            #[cfg(feature = "touchpad")]
+            position: [0.5, 0.5],
+            #[cfg(feature = "touchpad")]
+            pressure: 1.0,
+            #[cfg(feature = "sensors")]
+            data: None,
+        }
+    }
+
+    /// Builds an [`Event::ControllerButtonDown`], see
+    /// [`Event::button_down`].
+    #[must_use]
+    #[inline]
+    pub const fn button_down(self, button: Button) -> Event {
+        Event::button_down(self.which, button)
+    }
+
+    /// Builds an [`Event::ControllerButtonUp`], see [`Event::button_up`].
+    #[must_use]
+    #[inline]
+    pub const fn button_up(self, button: Button) -> Event {
+        Event::button_up(self.which, button)
+    }
+
+    /// Builds an [`Event::ControllerStickMotion`], see
+    /// [`Event::stick_motion`].
+    #[must_use]
+    #[inline]
+    pub const fn stick_motion(self, stick: Stick, offset: [f64; 2]) -> Event {
+        Event::stick_motion(self.which, stick, offset)
+    }
+
+    /// Builds an [`Event::ControllerTriggerMotion`], see
+    /// [`Event::trigger_motion`].
+    #[must_use]
+    #[inline]
+    pub const fn trigger_motion(self, trigger: Trigger, offset: f64) -> Event {
+        Event::trigger_motion(self.which, trigger, offset)
+    }
+
+    /// Sets the touchpad index, `0` by default.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn touchpad(mut self, touchpad: u32) -> Self {
+        self.touchpad = touchpad;
+        self
+    }
+
+    /// Sets the finger index, `0` by default.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn finger(mut self, finger: u32) -> Self {
+        self.finger = finger;
+        self
+    }
+
+    /// Sets the normalized finger position `[x, y]`, the center
+    /// `[0.5, 0.5]` by default.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn position(mut self, position: [f32; 2]) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets the normalized finger pressure, `1.0` by default.
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn pressure(mut self, pressure: f32) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Builds an [`Event::ControllerTouchpad`] with the `action` of the
+    /// finger.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{EventBuilder, prelude::*};
+    /// let pad = EventBuilder::new(0);
+    /// let moved = TouchpadAction::Moved;
+    /// let swipe = pad.finger(1).position([0.9, 0.5]).touch(moved);
+    /// let Event::ControllerTouchpad(touch) = swipe else {
+    ///     panic!("not a touchpad event");
+    /// };
+    /// assert_eq!((touch.finger, touch.position), (1, [0.9, 0.5]));
+    /// assert_eq!((touch.idx, touch.pressure), (0, 1.0));
+    /// ```
+    #[cfg(feature = "touchpad")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "touchpad")))]
+    #[must_use]
+    #[inline]
+    pub const fn touch(self, action: TouchpadAction) -> Event {
+        Event::touchpad(TouchpadEvent::new(
+            self.which,
+            self.touchpad,
+            self.finger,
+            self.position,
+            self.pressure,
+            action,
+        ))
+    }
+
+    /// Sets the sensor data `[x, y, z]`, that of a controller at rest by
+    /// default.
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub const fn data(mut self, data: [f64; 3]) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Builds an [`Event::ControllerSensorUpdated`] of the [`Sensor`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::{EventBuilder, prelude::*};
+    /// let pad = EventBuilder::new(0);
+    /// let Event::ControllerSensorUpdated { data, .. } =
+    ///     pad.sensor(Sensor::Accelerometer)
+    /// else {
+    ///     panic!("not a sensor update");
+    /// };
+    /// assert_eq!(data, [0.0, 9.806_65, 0.0]);
+    ///
+    /// let turn = pad.data([0.0, 1.5, 0.0]).sensor(Sensor::Gyroscope);
+    /// let Event::ControllerSensorUpdated { data, .. } = turn else {
+    ///     panic!("not a sensor update");
+    /// };
+    /// assert_eq!(data, [0.0, 1.5, 0.0]);
+    /// ```
+    #[cfg(feature = "sensors")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sensors")))]
+    #[must_use]
+    #[inline]
+    pub fn sensor(self, sensor: Sensor) -> Event {
+        let rest = match sensor {
+            Sensor::Accelerometer
+            | Sensor::LeftAccelerometer
+            | Sensor::RightAccelerometer => [0.0f64, STANDARD_GRAVITY, 0.0f64],
+            Sensor::Unknown
+            | Sensor::Gyroscope
+            | Sensor::LeftGyroscope
+            | Sensor::RightGyroscope => [0.0f64; 3],
+        };
+        let data = self.data.unwrap_or(rest);
+        Event::sensor_updated(self.which, sensor, data)
+    }
+}
@@ -0,0 +1,398 @@
+//! Gamepad-driven text entry: the model of an on-screen keyboard, without
+//! any rendering.
+
+use crate::{Button, Direction, Error, Event};
+
+/// Rows of the default letters page of [`KeyboardLayout::qwerty`].
+const QWERTY_LETTERS: [&str; 4] =
+    ["1234567890", "qwertyuiop", "asdfghjkl'", "zxcvbnm,.-"];
+
+/// Rows of the default symbols page of [`KeyboardLayout::qwerty`].
+const QWERTY_SYMBOLS: [&str; 3] = ["!@#$%^&*()", "~`_=+[]{}\\", "|;:\"<>/?"];
+
+/// Pages of [`char`] grids an [`OnScreenKeyboardModel`] moves over.
+///
+/// Rows may have different lengths, but no page or row can be empty.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::KeyboardLayout;
+/// let digits = KeyboardLayout::from_rows(&[&["123", "456", "789", "0"]])?;
+/// assert_eq!(digits.pages(), 1);
+/// assert_eq!(digits.key(0, 3, 0), Some('0'));
+/// assert_eq!(digits.key(0, 3, 1), None);
+///
+/// assert!(KeyboardLayout::from_rows(&[&["abc", ""]]).is_err());
+/// # Ok::<(), girl::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyboardLayout {
+    /// Pages of rows of keys.
+    pages: Vec<Vec<Vec<char>>>,
+}
+
+impl Default for KeyboardLayout {
+    #[inline]
+    fn default() -> Self {
+        Self::qwerty()
+    }
+}
+
+impl KeyboardLayout {
+    /// Creates a [`KeyboardLayout`] from pages of rows of keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if there are no pages, or any page
+    /// or row is empty.
+    #[inline]
+    pub fn new(pages: Vec<Vec<Vec<char>>>) -> Result<Self, Error> {
+        let valid = !pages.is_empty()
+            && pages.iter().all(|page| {
+                !page.is_empty() && page.iter().all(|row| !row.is_empty())
+            });
+        if valid {
+            Ok(Self { pages })
+        } else {
+            Err(Error::InvalidArgument { what: "keyboard layout" })
+        }
+    }
+
+    /// Creates a [`KeyboardLayout`] from pages of rows given as strings,
+    /// one key per [`char`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if there are no pages, or any page
+    /// or row is empty.
+    #[inline]
+    pub fn from_rows(pages: &[&[&str]]) -> Result<Self, Error> {
+        Self::new(pages.iter().map(|page| chars(page)).collect())
+    }
+
+    /// Creates the default layout: a QWERTY page with digits, followed by a
+    /// symbols page.
+    #[must_use]
+    #[inline]
+    pub fn qwerty() -> Self {
+        Self { pages: vec![chars(&QWERTY_LETTERS), chars(&QWERTY_SYMBOLS)] }
+    }
+
+    /// Gets the number of pages.
+    #[must_use]
+    #[inline]
+    pub const fn pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Gets the key at the given position, if any.
+    #[must_use]
+    #[inline]
+    pub fn key(&self, page: usize, row: usize, col: usize) -> Option<char> {
+        self.pages.get(page)?.get(row)?.get(col).copied()
+    }
+
+    /// Gets the rows of the `page`, empty if there is no such page.
+    fn rows(&self, page: usize) -> &[Vec<char>] {
+        self.pages.get(page).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Edges of the [`KeyboardLayout`] the cursor of an
+/// [`OnScreenKeyboardModel`] wraps around.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdgeWrap {
+    /// Stops at every edge.
+    None,
+    /// Wraps from the end of a row to its start and back.
+    Horizontal,
+    /// Wraps from the last row to the first one and back.
+    Vertical,
+    /// Wraps around all the edges.
+    #[default]
+    Both,
+}
+
+impl EdgeWrap {
+    /// Checks if the cursor wraps when moving horizontally.
+    #[must_use]
+    #[inline]
+    pub const fn horizontal(self) -> bool {
+        matches!(self, Self::Horizontal | Self::Both)
+    }
+
+    /// Checks if the cursor wraps when moving vertically.
+    #[must_use]
+    #[inline]
+    pub const fn vertical(self) -> bool {
+        matches!(self, Self::Vertical | Self::Both)
+    }
+}
+
+/// Output of an [`OnScreenKeyboardModel`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextEvent {
+    /// A character was typed.
+    Char(char),
+    /// The last character should be deleted.
+    Backspace,
+    /// The text was submitted.
+    Submit,
+    /// The cursor moved to another key.
+    CursorMoved {
+        /// Row of the key.
+        row: usize,
+        /// Column of the key.
+        col: usize,
+    },
+    /// Another page of the [`KeyboardLayout`] is shown, with the cursor
+    /// moved onto it.
+    PageChanged {
+        /// Index of the page.
+        page: usize,
+        /// Row of the key.
+        row: usize,
+        /// Column of the key.
+        col: usize,
+    },
+}
+
+/// Model of an on-screen keyboard driven by [`Event`]s, leaving the
+/// rendering to the application.
+///
+/// Feed it the [`Event`]s of the [`Gamepad`] typing, see [`handle`]:
+///
+/// - D-pad [`Button`]s and stick navigation [`Event`]s (see
+///   [`Girl::set_stick_navigation`]) move the cursor;
+/// - [`Button::A`] types the key under the cursor;
+/// - [`Button::B`] deletes, [`Button::X`] types a space;
+/// - [`Button::LeftShoulder`] and [`Button::RightShoulder`] switch pages;
+/// - [`Button::Start`] submits.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::{OnScreenKeyboardModel, TextEvent, prelude::*};
+/// let mut keyboard = OnScreenKeyboardModel::default();
+/// let press = |button| Event::ControllerButtonDown { which: 0, button };
+///
+/// let script = [
+///     // from "1" down to "a" and right to "h"
+///     [Button::DPadDown; 2].as_slice(),
+///     &[Button::DPadRight; 5],
+///     &[Button::A],
+///     // up to "y" and right to "i"
+///     &[Button::DPadUp, Button::DPadRight, Button::DPadRight, Button::A],
+///     // space, delete it, and "*" above "i" on the symbols page
+///     &[Button::X, Button::B, Button::RightShoulder, Button::DPadUp],
+///     &[Button::A, Button::Start],
+/// ];
+/// let events: Vec<_> = script
+///     .concat()
+///     .into_iter()
+///     .filter_map(|button| keyboard.handle(&press(button)))
+///     .collect();
+///
+/// let mut text = String::new();
+/// for event in &events {
+///     match *event {
+///         TextEvent::Char(ch) => text.push(ch),
+///         TextEvent::Backspace => _ = text.pop(),
+///         _ => {}
+///     }
+/// }
+/// assert_eq!(text, "hi*");
+/// assert_eq!(events.last(), Some(&TextEvent::Submit));
+/// assert!(events.contains(&TextEvent::CursorMoved { row: 2, col: 5 }));
+///
+/// // wraps around the edges by default
+/// let mut fresh = OnScreenKeyboardModel::default();
+/// assert_eq!(
+///     fresh.handle(&press(Button::DPadLeft)),
+///     Some(TextEvent::CursorMoved { row: 0, col: 9 }),
+/// );
+/// fresh.set_wrap(girl::EdgeWrap::None);
+/// assert_eq!(fresh.handle(&press(Button::DPadRight)), None);
+/// ```
+///
+/// [`Gamepad`]: crate::Gamepad
+/// [`handle`]: Self::handle
+/// [`Girl::set_stick_navigation`]: crate::Girl::set_stick_navigation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnScreenKeyboardModel {
+    /// Keys to move over.
+    layout: KeyboardLayout,
+    /// Edges the cursor wraps around.
+    wrap: EdgeWrap,
+    /// Current page.
+    page: usize,
+    /// Current row.
+    row: usize,
+    /// Current column.
+    col: usize,
+}
+
+impl Default for OnScreenKeyboardModel {
+    #[inline]
+    fn default() -> Self {
+        Self::new(KeyboardLayout::qwerty())
+    }
+}
+
+impl OnScreenKeyboardModel {
+    /// Creates an [`OnScreenKeyboardModel`] with the cursor on the first key
+    /// of the `layout`, wrapping around all the edges.
+    #[must_use]
+    #[inline]
+    pub const fn new(layout: KeyboardLayout) -> Self {
+        Self { layout, wrap: EdgeWrap::Both, page: 0, row: 0, col: 0 }
+    }
+
+    /// Gets the [`KeyboardLayout`].
+    #[must_use]
+    #[inline]
+    pub const fn layout(&self) -> &KeyboardLayout {
+        &self.layout
+    }
+
+    /// Sets the edges the cursor wraps around.
+    #[inline]
+    pub const fn set_wrap(&mut self, wrap: EdgeWrap) {
+        self.wrap = wrap;
+    }
+
+    /// Gets the cursor position `(page, row, col)`.
+    #[must_use]
+    #[inline]
+    pub const fn cursor(&self) -> (usize, usize, usize) {
+        (self.page, self.row, self.col)
+    }
+
+    /// Gets the key under the cursor.
+    #[must_use]
+    #[inline]
+    pub fn current_key(&self) -> Option<char> {
+        self.layout.key(self.page, self.row, self.col)
+    }
+
+    /// Updates the model with an [`Event`], returning the resulting
+    /// [`TextEvent`], if any.
+    ///
+    /// Only button presses, [`Event::StickDirectionPressed`] and
+    /// [`Event::StickDirectionRepeat`] are handled.
+    #[inline]
+    pub fn handle(&mut self, event: &Event) -> Option<TextEvent> {
+        if let Event::ControllerButtonDown { button, .. }
+        | Event::ControllerButtonDownWithState { button, .. } = *event
+        {
+            return self.press(button);
+        }
+        if let Event::StickDirectionPressed { direction, .. }
+        | Event::StickDirectionRepeat { direction, .. } = *event
+        {
+            return self.navigate(direction);
+        }
+        None
+    }
+
+    /// Handles a pressed [`Button`], see [`OnScreenKeyboardModel`].
+    #[inline]
+    pub fn press(&mut self, button: Button) -> Option<TextEvent> {
+        let direction = [
+            (Button::DPadUp, Direction::Up),
+            (Button::DPadDown, Direction::Down),
+            (Button::DPadLeft, Direction::Left),
+            (Button::DPadRight, Direction::Right),
+        ]
+        .into_iter()
+        .find_map(|(dpad, direction)| (dpad == button).then_some(direction));
+
+        if let Some(towards) = direction {
+            self.navigate(towards)
+        } else if button == Button::A {
+            self.current_key().map(TextEvent::Char)
+        } else if button == Button::B {
+            Some(TextEvent::Backspace)
+        } else if button == Button::X {
+            Some(TextEvent::Char(' '))
+        } else if button == Button::Start {
+            Some(TextEvent::Submit)
+        } else if button == Button::LeftShoulder {
+            self.turn_page(false)
+        } else if button == Button::RightShoulder {
+            self.turn_page(true)
+        } else {
+            None
+        }
+    }
+
+    /// Moves the cursor towards the [`Direction`], returning
+    /// [`TextEvent::CursorMoved`] unless it stopped at an edge.
+    #[inline]
+    pub fn navigate(&mut self, direction: Direction) -> Option<TextEvent> {
+        let rows = self.layout.rows(self.page);
+        let (row, col) = match direction {
+            Direction::Up | Direction::Down => {
+                let forward = direction == Direction::Down;
+                let wrap = self.wrap.vertical();
+                let row = step(self.row, rows.len(), forward, wrap)?;
+                let len = rows.get(row).map_or(1, Vec::len);
+                (row, self.col.min(len.saturating_sub(1)))
+            }
+            Direction::Left | Direction::Right => {
+                let forward = direction == Direction::Right;
+                let len = rows.get(self.row).map_or(1, Vec::len);
+                let wrap = self.wrap.horizontal();
+                let col = step(self.col, len, forward, wrap)?;
+                (self.row, col)
+            }
+        };
+        self.row = row;
+        self.col = col;
+        Some(TextEvent::CursorMoved { row, col })
+    }
+
+    /// Switches to the next or previous page, keeping the cursor as close
+    /// as possible to its position.
+    fn turn_page(&mut self, forward: bool) -> Option<TextEvent> {
+        let pages = self.layout.pages();
+        if pages < 2 {
+            return None;
+        }
+        self.page = step(self.page, pages, forward, true)?;
+
+        let rows = self.layout.rows(self.page);
+        self.row = self.row.min(rows.len().saturating_sub(1));
+        let len = rows.get(self.row).map_or(1, Vec::len);
+        self.col = self.col.min(len.saturating_sub(1));
+
+        Some(TextEvent::PageChanged {
+            page: self.page,
+            row: self.row,
+            col: self.col,
+        })
+    }
+}
+
+/// Splits every row into its [`char`]s.
+fn chars(rows: &[&str]) -> Vec<Vec<char>> {
+    rows.iter().map(|row| row.chars().collect()).collect()
+}
+
+/// Steps `index` forward or backward within `0..len`, wrapping around if
+/// `wrap`, or [`None`] if stopped at an edge.
+fn step(index: usize, len: usize, forward: bool, wrap: bool) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    match (forward, index >= last, index == 0) {
+        (true, false, _) => index.checked_add(1),
+        (true, true, _) => (wrap && last > 0).then_some(0),
+        (false, _, false) => index.checked_sub(1),
+        (false, _, true) => (wrap && last > 0).then_some(last),
+    }
+}
@@ -0,0 +1,192 @@
+//! Discrete magnitude tiers of analog [`Stick`]s, e.g. walk and run.
+//!
+//! [`Stick`]: crate::Stick
+
+#[cfg(feature = "sdl2")]
+use crate::{Gamepad, Stick};
+
+/// Magnitude tiers of [`Gamepad`] sticks.
+// TODO: Try remove on next Rust version update.
+#[expect(clippy::allow_attributes, reason = "`#[expect]` doesn't work here")]
+#[allow(clippy::multiple_inherent_impl, reason = "documented implementation")]
+#[cfg(feature = "sdl2")]
+impl Gamepad {
+    /// Sets the magnitude `thresholds` of the tiers of a [`Stick`], or
+    /// disables them if empty (the default).
+    ///
+    /// While enabled, [`Girl::update`] reports [`Event::StickTierChanged`]
+    /// whenever the [`stick_magnitude`] crosses into another tier, see
+    /// [`StickTiers`] for the exact semantics. Tier `0` is below the lowest
+    /// threshold, and the settings are dropped on disconnect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use girl::prelude::*;
+    /// let mut girl = girl::Girl::new()?;
+    /// # if girl.gamepad(0).is_some() {
+    /// let mut gamepad = girl.gamepad(0).unwrap();
+    ///
+    /// // tier 1 walks, tier 2 runs
+    /// gamepad.set_stick_tiers(Stick::Left, &[0.3, 0.8]);
+    ///
+    /// girl.update();
+    /// while let Some(event) = girl.event() {
+    ///     if let Event::StickTierChanged { to, .. } = event {
+    ///         println!("now in tier {to}");
+    ///     }
+    /// }
+    /// # }
+    /// # Ok::<(), girl::Error>(())
+    /// ```
+    ///
+    /// [`Girl::update`]: crate::Girl::update
+    /// [`Event::StickTierChanged`]: crate::Event::StickTierChanged
+    /// [`stick_magnitude`]: Self::stick_magnitude
+    #[inline]
+    pub fn set_stick_tiers(&mut self, stick: Stick, thresholds: &[f64]) {
+        let mut tiers = self.shared().stick_tiers();
+        if thresholds.is_empty() {
+            drop(tiers.remove(&(self.id(), stick)));
+        } else {
+            drop(tiers.insert((self.id(), stick), thresholds.to_vec()));
+        }
+    }
+
+    /// Gets the magnitude thresholds of the tiers of a [`Stick`], see
+    /// [`set_stick_tiers`].
+    ///
+    /// [`set_stick_tiers`]: Self::set_stick_tiers
+    #[must_use]
+    #[inline]
+    pub fn stick_tiers(&self, stick: Stick) -> Vec<f64> {
+        self.shared()
+            .stick_tiers()
+            .get(&(self.id(), stick))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// State machine turning a [`Stick`] magnitude into discrete tiers with
+/// hysteresis.
+///
+/// Tier `n` is reached once the magnitude is at or above the `n`-th lowest
+/// threshold, and left once it drops below that threshold by more than the
+/// [`hysteresis`], so a magnitude hovering around a threshold doesn't
+/// chatter. A magnitude of `0.0`, i.e. within the deadzone, always resets
+/// the tier to `0`.
+///
+/// Used by [`Gamepad::set_stick_tiers`], but can also be driven directly.
+///
+/// # Examples
+///
+/// ```
+/// # use girl::StickTiers;
+/// let mut tiers = StickTiers::new(&[0.8, 0.3]);
+/// let mut sweep = |magnitudes: &[f64]| -> Vec<(usize, usize)> {
+///     magnitudes.iter().filter_map(|&value| tiers.update(value)).collect()
+/// };
+///
+/// // one change per crossing, up and down
+/// let up: Vec<_> = (0..=100).map(|step| f64::from(step) / 100.0).collect();
+/// let down: Vec<_> = up.iter().rev().copied().collect();
+/// assert_eq!(sweep(&up), [(0, 1), (1, 2)]);
+/// assert_eq!(sweep(&down), [(2, 1), (1, 0)]);
+///
+/// // hovering around a threshold doesn't chatter
+/// let hover = [0.5, 0.81, 0.79, 0.8, 0.77, 0.82];
+/// assert_eq!(sweep(&hover), [(0, 1), (1, 2)]);
+/// // until it drops below by more than the hysteresis
+/// assert_eq!(sweep(&[0.74, 0.79]), [(2, 1)]);
+///
+/// // jumps skip tiers in one change
+/// assert_eq!(sweep(&[1.0, 0.2]), [(1, 2), (2, 0)]);
+///
+/// // the deadzone resets whatever the hysteresis
+/// assert_eq!(sweep(&[0.9]), [(0, 2)]);
+/// tiers.set_hysteresis(1.0);
+/// assert_eq!(tiers.update(0.01), None);
+/// assert_eq!(tiers.update(0.0), Some((2, 0)));
+/// assert_eq!(tiers.tier(), 0);
+/// ```
+///
+/// [`Stick`]: crate::Stick
+/// [`hysteresis`]: Self::set_hysteresis
+#[derive(Debug, Clone, PartialEq)]
+pub struct StickTiers {
+    /// Magnitude thresholds of the tiers, in ascending order.
+    thresholds: Vec<f64>,
+    /// Margin below a threshold before its tier is left.
+    hysteresis: f64,
+    /// Current tier.
+    tier: usize,
+}
+
+impl StickTiers {
+    /// Default margin below a threshold before its tier is left.
+    pub const HYSTERESIS: f64 = 0.05;
+
+    /// Creates a new [`StickTiers`] in tier `0`, with the default
+    /// [`HYSTERESIS`]. The `thresholds` may be in any order.
+    ///
+    /// [`HYSTERESIS`]: Self::HYSTERESIS
+    #[must_use]
+    #[inline]
+    pub fn new(thresholds: &[f64]) -> Self {
+        let mut sorted = thresholds.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Self { thresholds: sorted, hysteresis: Self::HYSTERESIS, tier: 0 }
+    }
+
+    /// Gets the magnitude thresholds, in ascending order.
+    #[must_use]
+    #[inline]
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    /// Sets the margin below a threshold before its tier is left.
+    #[inline]
+    pub const fn set_hysteresis(&mut self, hysteresis: f64) {
+        self.hysteresis = hysteresis;
+    }
+
+    /// Gets the current tier.
+    #[must_use]
+    #[inline]
+    pub const fn tier(&self) -> usize {
+        self.tier
+    }
+
+    /// Resets the tier to `0`.
+    #[inline]
+    pub const fn reset(&mut self) {
+        self.tier = 0;
+    }
+
+    /// Updates the tier with a new `magnitude`, returning the previous and
+    /// the new tier if it changed.
+    #[inline]
+    pub fn update(&mut self, magnitude: f64) -> Option<(usize, usize)> {
+        let from = self.tier;
+        let reached = |margin: f64| {
+            self.thresholds
+                .iter()
+                .take_while(|&&threshold| magnitude >= threshold - margin)
+                .count()
+        };
+        let to = if magnitude <= 0.0f64 {
+            0
+        } else {
+            let raised = reached(0.0f64);
+            if raised >= from {
+                raised
+            } else {
+                reached(self.hysteresis).min(from)
+            }
+        };
+        self.tier = to;
+        (to != from).then_some((from, to))
+    }
+}
@@ -0,0 +1,100 @@
+//! Borrowed views of the [`Gamepad`]s tracked by [`Girl`].
+//!
+//! [`Girl`]: crate::Girl
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::Gamepad;
+
+/// Shared view of a [`Gamepad`] tracked by [`Girl`], see
+/// [`Girl::gamepad_ref`].
+///
+/// Dereferences to the single [`Gamepad`] cached by [`Girl`], carrying its
+/// whole query API, so it reflects the configuration set through any
+/// [`GamepadMut`] of the same controller. Unlike [`Girl::gamepad`] it opens
+/// no SDL2 handle of its own.
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::gamepad`]: crate::Girl::gamepad
+/// [`Girl::gamepad_ref`]: crate::Girl::gamepad_ref
+#[derive(Debug, Clone, Copy)]
+pub struct GamepadRef<'girl> {
+    /// Cached [`Gamepad`].
+    pad: &'girl Gamepad,
+}
+
+impl<'girl> GamepadRef<'girl> {
+    /// Creates a [`GamepadRef`] of a cached [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(pad: &'girl Gamepad) -> Self {
+        Self { pad }
+    }
+}
+
+impl Deref for GamepadRef<'_> {
+    type Target = Gamepad;
+
+    #[inline]
+    fn deref(&self) -> &Gamepad {
+        self.pad
+    }
+}
+
+impl fmt::Display for GamepadRef<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.pad, f)
+    }
+}
+
+/// Exclusive view of a [`Gamepad`] tracked by [`Girl`], see
+/// [`Girl::gamepad_mut`].
+///
+/// Dereferences to the single [`Gamepad`] cached by [`Girl`], carrying its
+/// whole query and mutation API, so configuration set through it, e.g.
+/// deadzones, applies to the [`Gamepad`] [`Girl`] reads input from and is
+/// visible on every later fetch.
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::gamepad_mut`]: crate::Girl::gamepad_mut
+#[derive(Debug)]
+pub struct GamepadMut<'girl> {
+    /// Cached [`Gamepad`].
+    pad: &'girl mut Gamepad,
+}
+
+impl<'girl> GamepadMut<'girl> {
+    /// Creates a [`GamepadMut`] of a cached [`Gamepad`].
+    #[must_use]
+    #[inline]
+    pub(crate) const fn new(pad: &'girl mut Gamepad) -> Self {
+        Self { pad }
+    }
+}
+
+impl Deref for GamepadMut<'_> {
+    type Target = Gamepad;
+
+    #[inline]
+    fn deref(&self) -> &Gamepad {
+        self.pad
+    }
+}
+
+impl DerefMut for GamepadMut<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Gamepad {
+        self.pad
+    }
+}
+
+impl fmt::Display for GamepadMut<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.pad, f)
+    }
+}
@@ -0,0 +1,147 @@
+//! Detection of a stalled [`Girl::update`] loop.
+//!
+//! [`Girl::update`]: crate::Girl::update
+
+use core::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+use std::time::Instant;
+
+/// Watchdog of the [`Girl::update`] loop, see
+/// [`Girl::enable_stall_detection`].
+///
+/// Only keeps atomic timestamps, so it is cheap to check on every input
+/// query, and never panics. Disabled until a threshold is set.
+///
+/// [`Girl::update`]: crate::Girl::update
+/// [`Girl::enable_stall_detection`]: crate::Girl::enable_stall_detection
+#[derive(Debug)]
+pub(crate) struct StallWatchdog {
+    /// Origin of the timestamps.
+    origin: Instant,
+    /// Stall threshold in nanoseconds, `0` when disabled.
+    threshold: AtomicU64,
+    /// Time of the last update in nanoseconds since the `origin`.
+    last_update: AtomicU64,
+    /// Whether the current stall was already reported.
+    reported: AtomicBool,
+}
+
+impl Default for StallWatchdog {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            threshold: AtomicU64::new(0),
+            last_update: AtomicU64::new(0),
+            reported: AtomicBool::new(false),
+        }
+    }
+}
+
+impl StallWatchdog {
+    /// Gets the stall threshold, [`None`] if disabled.
+    #[must_use]
+    #[inline]
+    pub(crate) fn threshold(&self) -> Option<Duration> {
+        match self.threshold.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Sets the stall threshold, or disables the [`StallWatchdog`] if
+    /// [`None`].
+    #[inline]
+    pub(crate) fn set_threshold(&self, threshold: Option<Duration>) {
+        // `0` is reserved for disabled
+        let nanos = threshold.map_or(0, |duration| to_nanos(duration).max(1));
+        self.threshold.store(nanos, Ordering::Relaxed);
+        self.reported.store(false, Ordering::Relaxed);
+    }
+
+    /// Records an update at `now`, re-arming the stall report.
+    #[inline]
+    pub(crate) fn updated(&self, now: Instant) {
+        let nanos = to_nanos(now.saturating_duration_since(self.origin));
+        self.last_update.store(nanos, Ordering::Relaxed);
+        self.reported.store(false, Ordering::Relaxed);
+    }
+
+    /// Gets the time elapsed since the last update at `now`.
+    #[must_use]
+    #[inline]
+    pub(crate) fn time_since_update(&self, now: Instant) -> Duration {
+        let nanos = self.last_update.load(Ordering::Relaxed);
+        let last = Duration::from_nanos(nanos);
+        now.saturating_duration_since(self.origin).saturating_sub(last)
+    }
+
+    /// Checks if the updates stalled for longer than the threshold at `now`.
+    ///
+    /// Returns `true` only once per stall, so the caller can report it
+    /// without flooding, and always `false` while disabled.
+    #[must_use]
+    #[inline]
+    pub(crate) fn check(&self, now: Instant) -> bool {
+        let Some(threshold) = self.threshold() else {
+            return false;
+        };
+        self.time_since_update(now) > threshold
+            && !self.reported.swap(true, Ordering::Relaxed)
+    }
+}
+
+/// Converts the `duration` to nanoseconds, saturating at [`u64::MAX`].
+fn to_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Unit tests of [`StallWatchdog`].
+#[cfg(test)]
+#[expect(clippy::missing_panics_doc, reason = "tests")]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::StallWatchdog;
+
+    /// Gets the time `ms` milliseconds after `start`.
+    fn at(start: Instant, ms: u64) -> Instant {
+        start.checked_add(Duration::from_millis(ms)).unwrap_or(start)
+    }
+
+    /// Nothing is reported until a threshold is set.
+    #[test]
+    fn disabled_by_default() {
+        let watchdog = StallWatchdog::default();
+        let start = Instant::now();
+
+        assert_eq!(watchdog.threshold(), None, "disabled");
+        assert!(!watchdog.check(at(start, 10_000)), "reported while disabled");
+    }
+
+    /// A stall is reported once, until the next update re-arms it.
+    #[test]
+    fn reports_once_per_stall() {
+        let watchdog = StallWatchdog::default();
+        let start = Instant::now();
+        watchdog.set_threshold(Some(Duration::from_millis(100)));
+        watchdog.updated(at(start, 0));
+
+        assert!(!watchdog.check(at(start, 100)), "reported at the threshold");
+        assert_eq!(
+            watchdog.time_since_update(at(start, 150)),
+            Duration::from_millis(150),
+            "time since the update",
+        );
+        assert!(watchdog.check(at(start, 150)), "stall not reported");
+        assert!(!watchdog.check(at(start, 200)), "stall reported twice");
+        assert!(!watchdog.check(at(start, 5_000)), "stall reported twice");
+
+        watchdog.updated(at(start, 5_000));
+        assert!(!watchdog.check(at(start, 5_050)), "reported after update");
+        assert!(watchdog.check(at(start, 5_101)), "next stall not reported");
+    }
+}
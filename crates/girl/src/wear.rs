@@ -0,0 +1,249 @@
+//! Cumulative usage statistics of controllers, e.g. to rotate worn ones.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use crate::{Event, GamepadId, Guid, Trigger};
+
+/// Cumulative usage of a single controller, see [`WearMetrics`].
+///
+/// Counters saturate instead of overflowing.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceWear {
+    /// Total time connected.
+    pub connected: Duration,
+    /// Total number of button presses.
+    pub button_presses: u64,
+    /// Total travel of both triggers, in full trigger ranges, so a full
+    /// pull and release adds `2.0`.
+    pub trigger_travel: f64,
+    /// Total time rumbling.
+    pub rumble: Duration,
+}
+
+impl DeviceWear {
+    /// Creates a [`DeviceWear`] of an unused controller.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            connected: Duration::ZERO,
+            button_presses: 0,
+            trigger_travel: 0.0,
+            rumble: Duration::ZERO,
+        }
+    }
+
+    /// Adds the usage of `other` to this one.
+    #[inline]
+    pub fn merge(&mut self, other: &Self) {
+        self.connected = self.connected.saturating_add(other.connected);
+        self.button_presses =
+            self.button_presses.saturating_add(other.button_presses);
+        self.trigger_travel += other.trigger_travel;
+        self.rumble = self.rumble.saturating_add(other.rumble);
+    }
+}
+
+/// A connection of a controller, see [`WearMetrics::connect`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Session {
+    /// [`Guid`] of the controller.
+    guid: Guid,
+    /// Since when the connected time is not yet counted.
+    since: Instant,
+    /// Last positions of the left and right [`Trigger`]s.
+    triggers: [f64; 2],
+}
+
+/// Per-controller usage statistics keyed by [`Guid`], accumulated across
+/// sessions.
+///
+/// Tracks connected time, button presses, trigger travel and rumble time,
+/// e.g. for esports organizations rotating worn controllers.
+///
+/// Kept up to date by [`Girl`] once attached with [`Girl::attach_wear`],
+/// from the [`Event`]s it reports and the rumble effects it tracks, so
+/// there is no per-frame cost. Persist it with `serde`, and combine the
+/// metrics of several sessions or machines with [`merge`].
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use girl::{WearMetrics, prelude::*};
+/// let start = Instant::now();
+/// let at = |secs| start + Duration::from_secs(secs);
+/// let guid = "030000004c050000e60c000000010000".parse()?;
+/// let pad = GamepadId::from_raw(0);
+///
+/// let mut wear = WearMetrics::new();
+/// wear.connect(pad, guid, at(0));
+/// wear.observe(&Event::button_down(0, Button::A));
+/// wear.observe(&Event::button_down(0, Button::B));
+/// wear.observe(&Event::trigger_motion(0, Trigger::Right, 1.0));
+/// wear.observe(&Event::trigger_motion(0, Trigger::Right, 0.0));
+/// wear.add_rumble(pad, Duration::from_millis(500));
+/// wear.disconnect(pad, at(60));
+///
+/// // events of disconnected controllers are not counted
+/// wear.observe(&Event::button_down(0, Button::A));
+///
+/// let device = wear.device(guid).unwrap();
+/// assert_eq!(device.connected, Duration::from_secs(60));
+/// assert_eq!(device.button_presses, 2);
+/// assert_eq!(device.trigger_travel, 2.0);
+/// assert_eq!(device.rumble, Duration::from_millis(500));
+///
+/// // a second session, e.g. loaded from another machine
+/// let mut other = WearMetrics::new();
+/// other.connect(pad, guid, at(0));
+/// other.observe(&Event::button_down(0, Button::X));
+/// other.flush(at(30));
+///
+/// wear.merge(&other);
+/// let device = wear.device(guid).unwrap();
+/// assert_eq!(device.connected, Duration::from_secs(90));
+/// assert_eq!(device.button_presses, 3);
+/// # Ok::<(), girl::Error>(())
+/// ```
+///
+/// [`Girl`]: crate::Girl
+/// [`Girl::attach_wear`]: crate::Girl::attach_wear
+/// [`merge`]: Self::merge
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WearMetrics {
+    /// Accumulated usage by [`Guid`].
+    devices: BTreeMap<Guid, DeviceWear>,
+    /// Connected controllers.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    sessions: BTreeMap<GamepadId, Session>,
+}
+
+impl WearMetrics {
+    /// Creates empty [`WearMetrics`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { devices: BTreeMap::new(), sessions: BTreeMap::new() }
+    }
+
+    /// Gets the usage of the controller with the [`Guid`].
+    ///
+    /// Time connected since the last [`flush`] is not included yet.
+    ///
+    /// [`flush`]: Self::flush
+    #[must_use]
+    #[inline]
+    pub fn device(&self, guid: Guid) -> Option<&DeviceWear> {
+        self.devices.get(&guid)
+    }
+
+    /// Iterates the usage of every controller seen, by [`Guid`].
+    #[inline]
+    pub fn devices(&self) -> impl Iterator<Item = (Guid, &DeviceWear)> {
+        self.devices.iter().map(|(&guid, wear)| (guid, wear))
+    }
+
+    /// Starts counting the usage of the controller with the [`GamepadId`]
+    /// and [`Guid`], connected at `now`.
+    #[inline]
+    pub fn connect(&mut self, id: GamepadId, guid: Guid, now: Instant) {
+        _ = self.devices.entry(guid).or_default();
+        let session = Session { guid, since: now, triggers: [0.0f64; 2] };
+        if let Some(previous) = self.sessions.insert(id, session) {
+            let connected = now.saturating_duration_since(previous.since);
+            add_connected(&mut self.devices, previous.guid, connected);
+        }
+    }
+
+    /// Stops counting the usage of the controller with the [`GamepadId`],
+    /// disconnected at `now`.
+    #[inline]
+    pub fn disconnect(&mut self, id: GamepadId, now: Instant) {
+        if let Some(session) = self.sessions.remove(&id) {
+            let connected = now.saturating_duration_since(session.since);
+            add_connected(&mut self.devices, session.guid, connected);
+        }
+    }
+
+    /// Counts the time connected of every connected controller up to `now`,
+    /// e.g. before saving.
+    #[inline]
+    pub fn flush(&mut self, now: Instant) {
+        for session in self.sessions.values_mut() {
+            let connected = now.saturating_duration_since(session.since);
+            session.since = session.since.max(now);
+            add_connected(&mut self.devices, session.guid, connected);
+        }
+    }
+
+    /// Counts the button presses and trigger travel in the [`Event`] of a
+    /// connected controller.
+    #[inline]
+    pub fn observe(&mut self, event: &Event) {
+        let Some(which) = event.which() else {
+            return;
+        };
+        // a single session per instance ID is connected at a time, whatever
+        // the generation of its `GamepadId`
+        let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find_map(|(id, session)| (id.raw() == which).then_some(session))
+        else {
+            return;
+        };
+        let wear = self.devices.entry(session.guid).or_default();
+        if let Event::ControllerButtonDown { .. }
+        | Event::ControllerButtonDownWithState { .. } = *event
+        {
+            wear.button_presses = wear.button_presses.saturating_add(1);
+        }
+        if let Event::ControllerTriggerMotion { trigger, offset, .. } = *event {
+            let last = match trigger {
+                Trigger::Left => &mut session.triggers[0],
+                Trigger::Right => &mut session.triggers[1],
+            };
+            wear.trigger_travel += (offset - *last).abs();
+            *last = offset;
+        }
+    }
+
+    /// Counts `time` of rumble of the connected controller with the
+    /// [`GamepadId`].
+    #[inline]
+    pub fn add_rumble(&mut self, id: GamepadId, time: Duration) {
+        let Some(session) = self.sessions.get(&id) else {
+            return;
+        };
+        let wear = self.devices.entry(session.guid).or_default();
+        wear.rumble = wear.rumble.saturating_add(time);
+    }
+
+    /// Adds the usage accumulated in `other`, e.g. of another session, to
+    /// these metrics. Time connected since the last [`flush`] of `other` is
+    /// not included.
+    ///
+    /// [`flush`]: Self::flush
+    #[inline]
+    pub fn merge(&mut self, other: &Self) {
+        for (&guid, wear) in &other.devices {
+            self.devices.entry(guid).or_default().merge(wear);
+        }
+    }
+}
+
+/// Adds `time` connected to the usage of the controller with the [`Guid`].
+fn add_connected(
+    devices: &mut BTreeMap<Guid, DeviceWear>,
+    guid: Guid,
+    time: Duration,
+) {
+    let wear = devices.entry(guid).or_default();
+    wear.connected = wear.connected.saturating_add(time);
+}
@@ -0,0 +1,171 @@
+//! Checks that polling gamepads through [`GamepadSource`] and
+//! [`GamepadRead`] doesn't allocate without any feature, so the `no_std`
+//! core works without a global allocator, against a fixed-capacity source.
+//!
+//! Run with `cargo test --test core --no-default-features`. The test harness
+//! is disabled, as its threads would allocate while counting.
+#![expect(unused_crate_dependencies, reason = "test")]
+
+#[path = "../benches/counting/mod.rs"]
+mod counting;
+
+use core::{hint::black_box, iter};
+
+use girl::{
+    Button, ButtonChanges, ButtonTracker, Event, GamepadId, GamepadRead,
+    GamepadSource, GamepadState, Stick, Trigger, ring::EventRing,
+};
+
+/// Raw position of the left [`Stick`] in the [`SCRIPT`].
+const OFFSET: [f64; 2] = [0.5, -0.25];
+/// Raw value of the right [`Trigger`] in the [`SCRIPT`].
+const PULL: f64 = 0.75;
+
+/// Input played on the gamepad.
+const SCRIPT: [Event; 6] = [
+    Event::button_down(0, Button::A),
+    Event::stick_motion(0, Stick::Left, OFFSET),
+    Event::button_down(0, Button::B),
+    Event::button_up(0, Button::A),
+    Event::trigger_motion(0, Trigger::Right, PULL),
+    Event::button_up(0, Button::B),
+];
+
+/// Gamepad reading a [`GamepadState`] as is.
+#[derive(Debug, Default)]
+struct Pad {
+    /// Current input.
+    state: GamepadState,
+    /// Held [`Button`]s of the previous [`GamepadRead::buttons_changed`]
+    /// call.
+    tracker: ButtonTracker,
+}
+
+impl GamepadRead for Pad {
+    fn button_value(&self, buttons: Button) -> f64 {
+        if self.buttons_pressed(buttons) { 1.0 } else { 0.0 }
+    }
+
+    fn buttons(&self, buttons: Button) -> Button {
+        self.state.buttons & buttons
+    }
+
+    fn buttons_changed(&mut self) -> ButtonChanges {
+        self.tracker.changes(self.state.buttons)
+    }
+
+    fn connected(&self) -> bool {
+        self.state.connected
+    }
+
+    fn id(&self) -> GamepadId {
+        GamepadId::from_raw(0)
+    }
+
+    fn stick(&self, stick: Stick) -> [f64; 2] {
+        self.state.stick(stick)
+    }
+
+    fn trigger(&self, trigger: Trigger) -> f64 {
+        self.state.trigger(trigger)
+    }
+}
+
+/// Source of the [`SCRIPT`] with a single gamepad, applying each [`Event`]
+/// as it is polled.
+#[derive(Debug, Default)]
+struct Source {
+    /// [`Event`]s not polled yet.
+    events: EventRing<8>,
+    /// The gamepad with instance ID 0.
+    pad: Pad,
+}
+
+impl GamepadSource for Source {
+    type Gamepad = Pad;
+
+    fn event(&mut self) -> Option<Event> {
+        let event = self.events.pop()?;
+        let state = &mut self.pad.state;
+        if let Event::ControllerButtonDown { button, .. } = event {
+            state.buttons.insert(button);
+        }
+        if let Event::ControllerButtonUp { button, .. } = event {
+            state.buttons.remove(button);
+        }
+        if let Event::ControllerStickMotion {
+            stick: Stick::Left, offset, ..
+        } = event
+        {
+            state.left_stick = offset;
+        }
+        if let Event::ControllerTriggerMotion {
+            trigger: Trigger::Right,
+            offset,
+            ..
+        } = event
+        {
+            state.right_trigger = offset;
+        }
+        Some(event)
+    }
+
+    fn gamepad_by_id(&self, id: GamepadId) -> Option<&Pad> {
+        (id == self.pad.id()).then_some(&self.pad)
+    }
+
+    fn gamepad_by_id_mut(&mut self, id: GamepadId) -> Option<&mut Pad> {
+        (id == self.pad.id()).then_some(&mut self.pad)
+    }
+
+    fn snapshot_all(&self, out: &mut [Option<GamepadState>]) -> usize {
+        out.fill(None);
+        if let Some(slot) = out.first_mut() {
+            *slot = Some(self.pad.state());
+        }
+        usize::from(!out.is_empty())
+    }
+}
+
+fn main() {
+    let mut source = Source::default();
+    source.pad.state.connected = true;
+    for event in SCRIPT {
+        assert!(source.events.push(event).is_none(), "the ring has room");
+    }
+
+    let mut states = [None; 4];
+    let mut polled = 0;
+    let allocations = counting::allocations(|| {
+        polled = poll(&mut source, GamepadId::from_raw(0), &mut states);
+    });
+    assert_eq!(polled, SCRIPT.len(), "every event is polled");
+    assert_eq!(allocations, 0, "polling allocated");
+    let held = states[0].map(|state| (state.buttons, state.right_trigger));
+    assert_eq!(held, Some((Button::empty(), PULL)), "the script is applied");
+}
+
+/// Drains the [`Event`]s of `source`, reading the gamepad `id` and taking
+/// snapshots into `states` after each, as a game loop would. Returns how
+/// many [`Event`]s there were.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn poll(
+    source: &mut impl GamepadSource,
+    id: GamepadId,
+    states: &mut [Option<GamepadState>],
+) -> usize {
+    iter::from_fn(|| {
+        let event = source.event()?;
+        if let Some(pad) = source.gamepad_by_id_mut(id) {
+            _ = black_box(pad.buttons_changed());
+            _ = black_box(pad.buttons(Button::all()));
+            _ = black_box(pad.button_value(Button::A));
+            _ = black_box(pad.stick(Stick::Left));
+            _ = black_box(pad.trigger(Trigger::Right));
+            _ = black_box((pad.connected(), pad.id(), pad.state()));
+        }
+        _ = black_box(source.snapshot_all(states));
+        Some(event)
+    })
+    .count()
+}
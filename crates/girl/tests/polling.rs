@@ -0,0 +1,74 @@
+//! Checks that polling gamepads through [`GamepadSource`] and
+//! [`GamepadRead`] doesn't allocate, so the polling API works without a
+//! global allocator, against the simulated backend.
+//!
+//! Run with `cargo test --test polling --features sim`. The test harness is
+//! disabled, as its threads would allocate while counting.
+#![expect(unused_crate_dependencies, reason = "test")]
+
+#[path = "../benches/counting/mod.rs"]
+mod counting;
+
+use core::{hint::black_box, iter, time::Duration};
+
+use girl::{GamepadRead as _, GamepadSource, SimGirl, prelude::*};
+
+/// Raw position of the left [`Stick`] in the [`SCRIPT`].
+const OFFSET: [f64; 2] = [0.5, -0.25];
+/// Raw value of the right [`Trigger`] in the [`SCRIPT`].
+const PULL: f64 = 0.75;
+
+/// Input played on the simulated gamepad.
+const SCRIPT: [Event; 6] = [
+    Event::button_down(0, Button::A),
+    Event::stick_motion(0, Stick::Left, OFFSET),
+    Event::button_down(0, Button::B),
+    Event::button_up(0, Button::A),
+    Event::trigger_motion(0, Trigger::Right, PULL),
+    Event::button_up(0, Button::B),
+];
+
+fn main() {
+    let mut sim = SimGirl::new();
+    let id = sim.connect();
+    assert!(sim.event().is_some(), "the connection is reported");
+    for (at, event) in (1..).map(Duration::from_millis).zip(SCRIPT) {
+        sim.schedule(at, event);
+    }
+    sim.advance(Duration::from_secs(1));
+
+    let mut states = [None; 4];
+    let mut polled = 0;
+    let allocations = counting::allocations(|| {
+        polled = poll(&mut sim, id, &mut states);
+    });
+    assert_eq!(polled, SCRIPT.len(), "every event is polled");
+    assert_eq!(allocations, 0, "polling allocated");
+    let released = states[0].is_some_and(|state| state.buttons.is_empty());
+    assert!(released, "the script ends with every button released");
+}
+
+/// Drains the [`Event`]s of `source`, reading the gamepad `id` and taking
+/// snapshots into `states` after each, as a game loop would. Returns how
+/// many [`Event`]s there were.
+#[expect(clippy::single_call_fn, reason = "extracted for readability")]
+fn poll(
+    source: &mut impl GamepadSource,
+    id: GamepadId,
+    states: &mut [Option<GamepadState>],
+) -> usize {
+    iter::from_fn(|| {
+        let event = source.event()?;
+        if let Some(pad) = source.gamepad_by_id_mut(id) {
+            _ = black_box(pad.buttons_changed());
+            _ = black_box(pad.buttons(Button::all()));
+            _ = black_box(pad.button_value(Button::A));
+            _ = black_box(pad.stick(Stick::Left));
+            _ = black_box(pad.trigger(Trigger::Right));
+            _ = black_box((pad.connected(), pad.id(), pad.state()));
+        }
+        _ = black_box(source.snapshot_all(states));
+        Some(event)
+    })
+    .count()
+}